@@ -0,0 +1,741 @@
+//! 数据流分析
+//!
+//! 计算每条指令定义（def）和使用（use）的通用寄存器，并在指令级控制流图上迭代求解
+//! 寄存器活跃性，得到每个寄存器的活跃区间。报告中用它标注被调用者保存寄存器（X19-X28）
+//! 在函数中何时保存着调用者的值、何时保存着函数自己的变量。此外还枚举函数的所有出口，
+//! 追踪每个出口处 X0 的来源（常量、参数、调用结果或计算结果）。
+
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::register::Register;
+use serde::Serialize;
+use std::fmt;
+
+/// 第一个被调用者保存寄存器（X19）
+pub const FIRST_CALLEE_SAVED: usize = 19;
+
+/// 最后一个被调用者保存寄存器（X28）
+pub const LAST_CALLEE_SAVED: usize = 28;
+
+/// 通用寄存器集合（X0-X30，按编号存放在位图中）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegSet(u32);
+
+impl RegSet {
+    /// 空集合
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// 编号在 [first, last] 范围内的寄存器
+    pub fn range(first: usize, last: usize) -> Self {
+        Self((first..=last).fold(0, |bits, i| bits | (1 << i)))
+    }
+
+    /// 加入寄存器
+    pub fn insert(&mut self, index: usize) {
+        self.0 |= 1 << index;
+    }
+
+    /// 加入寄存器（零寄存器、SP、浮点寄存器等没有通用寄存器编号的忽略）
+    pub fn insert_reg(&mut self, reg: Register) {
+        if let Some(index) = reg.index() {
+            self.insert(index);
+        }
+    }
+
+    /// 是否包含寄存器
+    pub fn contains(&self, index: usize) -> bool {
+        self.0 & (1 << index) != 0
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// 并集
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// 差集
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// 按编号从小到大遍历
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..31).filter(|i| self.contains(*i))
+    }
+}
+
+impl fmt::Display for RegSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = self.iter().map(|i| format!("x{}", i)).collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+/// 寄存器活跃性分析结果（与输入指令一一对应）
+#[derive(Debug, Clone)]
+pub struct LivenessAnalysis {
+    /// 每条指令定义的寄存器
+    pub defs: Vec<RegSet>,
+    /// 每条指令使用的寄存器
+    pub uses: Vec<RegSet>,
+    /// 指令执行前活跃的寄存器
+    pub live_in: Vec<RegSet>,
+    /// 指令执行后活跃的寄存器
+    pub live_out: Vec<RegSet>,
+}
+
+impl LivenessAnalysis {
+    /// 对一个函数的指令序列（按地址排列）计算活跃性
+    pub fn compute(instructions: &[Instruction]) -> Self {
+        let (defs, mut uses): (Vec<RegSet>, Vec<RegSet>) = instructions.iter().map(def_use).unzip();
+        let successors: Vec<Vec<usize>> = (0..instructions.len())
+            .map(|i| successors(instructions, i))
+            .collect();
+
+        // 跳出函数的 B 是尾调用：参数寄存器、被调用者保存寄存器和 LR 仍然有意义
+        for (i, inst) in instructions.iter().enumerate() {
+            if inst.instruction_type == InstructionType::B && inst.condition.is_none() && successors[i].is_empty() {
+                uses[i] = uses[i]
+                    .union(RegSet::range(0, 7))
+                    .union(RegSet::range(FIRST_CALLEE_SAVED, 30));
+            }
+        }
+
+        let mut live_in = vec![RegSet::new(); instructions.len()];
+        let mut live_out = vec![RegSet::new(); instructions.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in (0..instructions.len()).rev() {
+                let out = successors[i]
+                    .iter()
+                    .fold(RegSet::new(), |set, s| set.union(live_in[*s]));
+                let input = uses[i].union(out.difference(defs[i]));
+                if out != live_out[i] || input != live_in[i] {
+                    live_out[i] = out;
+                    live_in[i] = input;
+                    changed = true;
+                }
+            }
+        }
+
+        Self { defs, uses, live_in, live_out }
+    }
+
+    /// 寄存器的活跃区间（指令下标的闭区间，按地址顺序合并相邻指令）
+    ///
+    /// 寄存器在某条指令执行前或执行后活跃，即认为该指令处于活跃区间内
+    pub fn live_ranges(&self, index: usize) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for i in 0..self.defs.len() {
+            let live = self.live_in[i].contains(index) || self.live_out[i].contains(index);
+            if !live {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == i && self.live_in[i].contains(index) => *end = i,
+                _ => ranges.push((i, i)),
+            }
+        }
+        ranges
+    }
+
+    /// 函数中被定义过的寄存器
+    pub fn defined(&self) -> RegSet {
+        self.defs.iter().fold(RegSet::new(), |set, d| set.union(*d))
+    }
+}
+
+/// 函数的参数寄存器（X0-X7 的编号）
+///
+/// 入口处活跃、且按地址顺序第一次出现（不计调用和返回）时被读取的 X0-X7 视为参数，
+/// 这样调用前才设置的参数寄存器不会被误认为本函数的参数
+pub fn parameter_registers(instructions: &[Instruction], liveness: &LivenessAnalysis) -> Vec<usize> {
+    use InstructionType::*;
+
+    let Some(live_in) = liveness.live_in.first() else {
+        return Vec::new();
+    };
+    live_in
+        .iter()
+        .filter(|r| *r <= 7)
+        .filter(|r| {
+            instructions
+                .iter()
+                .enumerate()
+                .filter(|(_, inst)| !matches!(inst.instruction_type, BL | BLR | RET | B | SVC))
+                .find(|(i, _)| liveness.defs[*i].contains(*r) || liveness.uses[*i].contains(*r))
+                .is_some_and(|(i, _)| liveness.uses[i].contains(*r))
+        })
+        .collect()
+}
+
+/// 返回值（X0）的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueSource {
+    /// 常量
+    Constant(i64),
+    /// 入口时的寄存器值（X0-X7 为参数）
+    Parameter(usize),
+    /// 函数调用的返回值（附带被调用函数名）
+    CallResult(String),
+    /// 从内存加载（附带语义解释）
+    Load(String),
+    /// 计算得到（附带语义解释）
+    Expression(String),
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::Constant(value) => write!(f, "常量 {}", value),
+            ValueSource::Parameter(reg) if *reg <= 7 => write!(f, "参数 x{}", reg),
+            ValueSource::Parameter(reg) => write!(f, "入口时的 x{}", reg),
+            ValueSource::CallResult(callee) => write!(f, "{} 的返回值", callee),
+            ValueSource::Load(text) => write!(f, "从内存加载（{}）", text),
+            ValueSource::Expression(text) => write!(f, "计算结果（{}）", text),
+        }
+    }
+}
+
+/// 函数的一个出口
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitPath {
+    /// 出口指令（RET 或尾调用 B）的下标
+    pub index: usize,
+    /// 尾调用的目标函数（为 None 时是 RET）
+    pub tail_call: Option<String>,
+    /// 到达该出口时 X0 可能的来源（每项附带定义指令的下标，来自入口时为 None）
+    pub sources: Vec<(Option<usize>, ValueSource)>,
+}
+
+/// 枚举函数的所有出口，并追踪每个出口处 X0 的来源
+///
+/// 沿控制流图向前查找到达出口的 X0 定义；遇到 `mov x0, xN` 这样的寄存器复制时继续追踪 xN。
+/// 尾调用出口的返回值由被调用函数提供，不再追踪。
+pub fn exit_paths(instructions: &[Instruction]) -> Vec<ExitPath> {
+    let successors: Vec<Vec<usize>> = (0..instructions.len())
+        .map(|i| successors(instructions, i))
+        .collect();
+    let mut predecessors = vec![Vec::new(); instructions.len()];
+    for (i, succ) in successors.iter().enumerate() {
+        for s in succ {
+            predecessors[*s].push(i);
+        }
+    }
+    let defs: Vec<RegSet> = instructions.iter().map(|inst| def_use(inst).0).collect();
+
+    instructions
+        .iter()
+        .enumerate()
+        .filter(|(i, inst)| match inst.instruction_type {
+            InstructionType::RET => true,
+            InstructionType::B => successors[*i].is_empty(),
+            _ => false,
+        })
+        .map(|(index, inst)| {
+            if inst.instruction_type == InstructionType::B {
+                let target = inst.operands.iter().find_map(|op| match op {
+                    Operand::Label(label) => crate::objdump::target_symbol(label).map(String::from),
+                    _ => None,
+                });
+                return ExitPath { index, tail_call: Some(target.unwrap_or_default()), sources: Vec::new() };
+            }
+            let mut sources = Vec::new();
+            trace_sources(instructions, &predecessors, &defs, index, 0, 0, &mut sources);
+            ExitPath { index, tail_call: None, sources }
+        })
+        .collect()
+}
+
+/// 查找到达 at 的寄存器 reg 的所有定义，并解释其来源
+fn trace_sources(
+    instructions: &[Instruction],
+    predecessors: &[Vec<usize>],
+    defs: &[RegSet],
+    at: usize,
+    reg: usize,
+    depth: usize,
+    sources: &mut Vec<(Option<usize>, ValueSource)>,
+) {
+    let mut visited = vec![false; instructions.len()];
+    let mut stack: Vec<usize> = predecessors[at].clone();
+    let mut reaches_entry = at == 0;
+
+    while let Some(i) = stack.pop() {
+        if std::mem::replace(&mut visited[i], true) {
+            continue;
+        }
+        if !defs[i].contains(reg) {
+            reaches_entry |= i == 0;
+            stack.extend(&predecessors[i]);
+            continue;
+        }
+
+        let inst = &instructions[i];
+        // 寄存器复制：继续追踪源寄存器
+        if let (InstructionType::MOV, [_, Operand::Register(src)]) = (inst.instruction_type, inst.operands.as_slice()) {
+            if let (Some(src), true) = (src.index(), depth < 4) {
+                trace_sources(instructions, predecessors, defs, i, src, depth + 1, sources);
+                continue;
+            }
+        }
+        let source = value_source(inst);
+        if !sources.contains(&(Some(i), source.clone())) {
+            sources.push((Some(i), source));
+        }
+    }
+
+    if reaches_entry && !sources.contains(&(None, ValueSource::Parameter(reg))) {
+        sources.push((None, ValueSource::Parameter(reg)));
+    }
+    sources.sort_by_key(|(i, _)| *i);
+}
+
+/// 根据定义指令判断值的来源
+fn value_source(inst: &Instruction) -> ValueSource {
+    use crate::semantic::SemanticInterpreter;
+    use InstructionType::*;
+
+    match (inst.instruction_type, inst.operands.as_slice()) {
+        (MOV | MOVZ, [_, Operand::Immediate(value)]) => ValueSource::Constant(*value),
+        (BL, ops) => ValueSource::CallResult(
+            ops.iter()
+                .find_map(|op| match op {
+                    Operand::Label(label) => crate::objdump::target_symbol(label),
+                    _ => None,
+                })
+                .unwrap_or("被调用函数")
+                .trim_end_matches("@plt")
+                .to_string(),
+        ),
+        (BLR, _) => ValueSource::CallResult(String::from("函数指针")),
+        (LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | LDUR | LDP, _) => {
+            ValueSource::Load(SemanticInterpreter::interpret(inst))
+        }
+        _ => ValueSource::Expression(SemanticInterpreter::interpret(inst)),
+    }
+}
+
+/// 由向后跳转形成的循环
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    /// 循环头（跳转目标）的指令下标
+    pub head: usize,
+    /// 跳回循环头的分支指令下标
+    pub latch: usize,
+}
+
+impl Loop {
+    /// 指令下标是否在循环体内
+    pub fn contains(&self, index: usize) -> bool {
+        (self.head..=self.latch).contains(&index)
+    }
+}
+
+/// 查找函数中的循环（按循环头排序）
+///
+/// 跳转目标不在分支指令之后的分支即为循环的回边
+pub fn find_loops(instructions: &[Instruction]) -> Vec<Loop> {
+    let mut loops: Vec<Loop> = (0..instructions.len())
+        .filter_map(|latch| {
+            let target = branch_target(&instructions[latch])?;
+            let head = instructions.iter().position(|x| x.address == target)?;
+            (head <= latch && is_branch(&instructions[latch])).then_some(Loop { head, latch })
+        })
+        .collect();
+    loops.sort_by_key(|l| (l.head, l.latch));
+    loops
+}
+
+/// 是否为带标签目标的直接分支（不含 BL 调用）
+pub(crate) fn is_branch(inst: &Instruction) -> bool {
+    use InstructionType::*;
+    matches!(
+        inst.instruction_type,
+        B | BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE
+            | CBZ | CBNZ | TBZ | TBNZ
+    )
+}
+
+/// 分支指令的目标地址（objdump 格式: `40 <func+0x40>`）
+pub fn branch_target(inst: &Instruction) -> Option<u64> {
+    inst.operands.iter().rev().find_map(|op| match op {
+        Operand::Label(label) => label
+            .split_whitespace()
+            .next()
+            .and_then(|addr| u64::from_str_radix(addr.trim_start_matches("0x"), 16).ok()),
+        _ => None,
+    })
+}
+
+/// 基本块的首条指令下标（升序）：函数入口、跳转目标和分支之后的指令
+pub(crate) fn block_leaders(instructions: &[Instruction]) -> Vec<usize> {
+    if instructions.is_empty() {
+        return Vec::new();
+    }
+    let mut leaders = vec![0];
+    for i in 0..instructions.len() {
+        let successors = successors(instructions, i);
+        if successors != [i + 1] {
+            leaders.extend(successors.iter().copied().chain([i + 1]).filter(|s| *s < instructions.len()));
+        }
+    }
+    leaders.sort_unstable();
+    leaders.dedup();
+    leaders
+}
+
+/// 指令级控制流图中的后继
+pub(crate) fn successors(instructions: &[Instruction], i: usize) -> Vec<usize> {
+    use InstructionType::*;
+
+    let inst = &instructions[i];
+    let index_of = |address: u64| instructions.iter().position(|x| x.address == address);
+    let next = (i + 1 < instructions.len()).then_some(i + 1);
+    let target = branch_target(inst).and_then(index_of);
+
+    match inst.instruction_type {
+        // 返回、间接跳转和跳出函数的尾调用都是出口
+        RET | BR | ERET => Vec::new(),
+        B if inst.condition.is_none() => target.into_iter().collect(),
+        B | BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE
+        | CBZ | CBNZ | TBZ | TBNZ => next.into_iter().chain(target).collect(),
+        _ => next.into_iter().collect(),
+    }
+}
+
+/// 栈帧状态：SP 和 X29 相对入口 SP 的偏移
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Frame {
+    pub sp: i64,
+    pub fp: Option<i64>,
+}
+
+/// 沿控制流图推算每条指令执行前的栈帧状态，不可达的指令为 None
+///
+/// SP 被无法推算的方式修改或不同路径上的 SP 不一致时返回 None
+pub(crate) fn stack_frames(instructions: &[Instruction]) -> Option<Vec<Option<Frame>>> {
+    let mut frames: Vec<Option<Frame>> = vec![None; instructions.len()];
+    if instructions.is_empty() {
+        return Some(frames);
+    }
+    frames[0] = Some(Frame { sp: 0, fp: None });
+    let mut pending = vec![0];
+    while let Some(i) = pending.pop() {
+        let after = stack_step(&instructions[i], frames[i]?)?;
+        for s in successors(instructions, i) {
+            match frames[s] {
+                None => {
+                    frames[s] = Some(after);
+                    pending.push(s);
+                }
+                Some(existing) if existing.sp != after.sp => return None,
+                // 只有一条路径建立了帧指针时，不能通过 X29 访问栈
+                Some(existing) if existing.fp != after.fp && existing.fp.is_some() => {
+                    frames[s] = Some(Frame { fp: None, ..existing });
+                    pending.push(s);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    Some(frames)
+}
+
+/// 是否为按常量调整 SP 或 X29 的指令（`sub sp, sp, #n`、`mov x29, sp` 等）
+pub(crate) fn adjusts_frame(inst: &Instruction) -> bool {
+    use InstructionType::*;
+
+    let is_fp = |r: &Register| r.index() == Some(29);
+    match (inst.instruction_type, inst.operands.as_slice()) {
+        (SUB | ADD, [Operand::Register(Register::SP), Operand::Register(Register::SP), Operand::Immediate(_)]) => true,
+        (MOV, [Operand::Register(fp), Operand::Register(Register::SP)]) => is_fp(fp),
+        (ADD, [Operand::Register(fp), Operand::Register(Register::SP), Operand::Immediate(_)]) => is_fp(fp),
+        (MOV, [Operand::Register(Register::SP), Operand::Register(fp)]) => is_fp(fp),
+        (SUB, [Operand::Register(Register::SP), Operand::Register(fp), Operand::Immediate(_)]) => is_fp(fp),
+        _ => false,
+    }
+}
+
+/// 一条指令对栈帧状态的影响，SP 被无法推算的方式修改时返回 None
+fn stack_step(inst: &Instruction, mut frame: Frame) -> Option<Frame> {
+    use InstructionType::*;
+
+    let ops = inst.operands.as_slice();
+    match (inst.instruction_type, ops) {
+        (SUB, [Operand::Register(Register::SP), Operand::Register(Register::SP), Operand::Immediate(n)]) => frame.sp -= n,
+        (ADD, [Operand::Register(Register::SP), Operand::Register(Register::SP), Operand::Immediate(n)]) => frame.sp += n,
+        (MOV, [_, Operand::Register(Register::SP)]) if adjusts_frame(inst) => frame.fp = Some(frame.sp),
+        (ADD, [_, Operand::Register(Register::SP), Operand::Immediate(n)]) if adjusts_frame(inst) => {
+            frame.fp = Some(frame.sp + n)
+        }
+        (MOV, [Operand::Register(Register::SP), _]) if adjusts_frame(inst) => frame.sp = frame.fp?,
+        (SUB, [Operand::Register(Register::SP), _, Operand::Immediate(n)]) if adjusts_frame(inst) => {
+            frame.sp = frame.fp? - n
+        }
+        _ => {
+            for (op, access) in ops.iter().zip(register_access(inst)) {
+                match (op, access) {
+                    (Operand::Register(Register::SP), Some(access)) if access.writes() => return None,
+                    (Operand::Register(r), Some(access)) if access.writes() && r.index() == Some(29) => {
+                        frame.fp = None
+                    }
+                    _ => {}
+                }
+            }
+            // SP 的前变址/后变址回写
+            if let Some(Operand::Memory { base: Register::SP, offset, pre_indexed, .. }) =
+                ops.iter().find(|op| matches!(op, Operand::Memory { .. }))
+            {
+                match ops.last() {
+                    Some(Operand::Immediate(n)) => frame.sp += n,
+                    _ if *pre_indexed => frame.sp += offset.unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+    }
+    Some(frame)
+}
+
+/// 寄存器操作数的读写方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Access {
+    /// 只读
+    Read,
+    /// 只写
+    Write,
+    /// 读后写（如 MOVK 只替换部分位）
+    ReadWrite,
+}
+
+impl Access {
+    /// 是否读取
+    pub fn reads(self) -> bool {
+        self != Access::Write
+    }
+
+    /// 是否写入
+    pub fn writes(self) -> bool {
+        self != Access::Read
+    }
+}
+
+/// 每个显式寄存器操作数的读写方式（非寄存器操作数为 None）
+///
+/// 不含调用、返回和系统调用隐式读写的寄存器，以及内存操作数中的基址/索引寄存器
+pub fn register_access(inst: &Instruction) -> Vec<Option<Access>> {
+    use InstructionType::*;
+
+    let ops = &inst.operands;
+    let registers: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| matches!(op, Operand::Register(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let mut access = vec![None; ops.len()];
+    let mut set = |i: usize, a: Access| {
+        if matches!(ops.get(i), Some(Operand::Register(_))) {
+            access[i] = Some(a);
+        }
+    };
+
+    match inst.instruction_type {
+        LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | LDUR | LDXR | LDAR | LDXRB | LDXRH
+        | LDAXRB | LDAXRH | LDP | LDXP | LD1 | LD2 | LDG => registers.iter().for_each(|&i| set(i, Access::Write)),
+        STR | STRB | STRH | STP | STUR | STLR | ST1 | ST2 | STG | STADD | STADDL | STADDB
+        | STADDH => registers.iter().for_each(|&i| set(i, Access::Read)),
+        // 独占存储的第一个操作数是状态寄存器
+        STXR | STXRB | STXRH | STLXRB | STLXRH | STXP => {
+            for (n, &i) in registers.iter().enumerate() {
+                set(i, if n == 0 { Access::Write } else { Access::Read });
+            }
+        }
+        // 原子操作：Rs 为操作数，Rt 接收旧值
+        LDADD | LDADDAL | LDCLR | LDEOR | LDSET | SWP | LDADDH | LDADDB | LDADDLH | LDADDLB => {
+            set(0, Access::Read);
+            set(1, Access::Write);
+        }
+        // CAS 的比较值寄存器同时接收旧值；部分写入的目标寄存器其余位保持不变
+        CAS | CASAL | CASA | CASB | CASH | CASP | MOVK | BFI | BFXIL | BFM => {
+            registers.iter().for_each(|&i| set(i, Access::Read));
+            set(0, Access::ReadWrite);
+        }
+        CMP | CMN | TST | CCMP | CCMN | FCMP | FCMPE | CBZ | CBNZ | TBZ | TBNZ | BR | MSR | RET | BL
+        | BLR => registers.iter().for_each(|&i| set(i, Access::Read)),
+        B | BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE
+        | NOP | HLT | BRK | DMB | DSB | ISB | WFE | WFI | YIELD | ERET | DRPS | SVC => {}
+        _ => {
+            for (n, &i) in registers.iter().enumerate() {
+                set(i, if n == 0 { Access::Write } else { Access::Read });
+            }
+        }
+    }
+    access
+}
+
+/// 计算一条指令定义和使用的通用寄存器
+pub fn def_use(inst: &Instruction) -> (RegSet, RegSet) {
+    use InstructionType::*;
+
+    let mut defs = RegSet::new();
+    let mut uses = RegSet::new();
+    let ops = &inst.operands;
+
+    // 内存操作数的基址和索引寄存器总是被使用，写回时基址同时被定义
+    for op in ops {
+        if let Operand::Memory { base, index, pre_indexed, post_indexed, .. } = op {
+            uses.insert_reg(*base);
+            if let Some(index) = index {
+                uses.insert_reg(*index);
+            }
+            if *pre_indexed || *post_indexed {
+                defs.insert_reg(*base);
+            }
+        }
+    }
+    // objdump 把后变址写成 `[x0], #8`，立即数在内存操作数之后
+    if let [.., Operand::Memory { base, .. }, Operand::Immediate(_)] = ops.as_slice() {
+        defs.insert_reg(*base);
+    }
+
+    for (op, access) in ops.iter().zip(register_access(inst)) {
+        if let (Operand::Register(reg), Some(access)) = (op, access) {
+            if access.reads() {
+                uses.insert_reg(*reg);
+            }
+            if access.writes() {
+                defs.insert_reg(*reg);
+            }
+        }
+    }
+
+    // 隐式读写的寄存器
+    match inst.instruction_type {
+        RET => {
+            // 返回值和被调用者保存寄存器在返回后仍然有意义
+            if ops.is_empty() {
+                uses.insert(30);
+            }
+            uses.insert(0);
+            uses = uses.union(RegSet::range(FIRST_CALLEE_SAVED, 29));
+        }
+        BL | BLR => {
+            // 调用使用参数寄存器，破坏调用者保存寄存器和 LR
+            uses = uses.union(RegSet::range(0, 7));
+            defs = defs.union(RegSet::range(0, 18));
+            defs.insert(30);
+        }
+        SVC => {
+            uses = uses.union(RegSet::range(0, 5));
+            uses.insert(8);
+            defs.insert(0);
+        }
+        _ => {}
+    }
+
+    (defs, uses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <count>:
+   0:   a9bf7bf3    stp x19, x30, [sp, #-16]!
+   4:   aa0003f3    mov x19, x0
+   8:   94000000    bl 0 <helper>
+   c:   8b130000    add x0, x0, x19
+  10:   a8c17bf3    ldp x19, x30, [sp], #16
+  14:   d65f03c0    ret
+"#;
+
+    fn instructions() -> Vec<Instruction> {
+        ObjdumpParser::new(DUMP.to_string())
+            .extract_function_data("count")
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| e.parsed_instruction)
+            .collect()
+    }
+
+    #[test]
+    fn test_def_use() {
+        let insts = instructions();
+        let (defs, uses) = def_use(&insts[0]);
+        assert!(defs.is_empty());
+        assert_eq!(uses.to_string(), "x19, x30");
+
+        let (defs, uses) = def_use(&insts[3]);
+        assert_eq!(defs.to_string(), "x0");
+        assert_eq!(uses.to_string(), "x0, x19");
+    }
+
+    #[test]
+    fn test_exit_paths() {
+        let dump = r#"
+0000000000000000 <pick>:
+   0:   34000060    cbz w0, c <pick+0xc>
+   4:   52800540    mov w0, #0x2a
+   8:   d65f03c0    ret
+   c:   2a0103e0    mov w0, w1
+  10:   d65f03c0    ret
+  14:   14000000    b 40 <other>
+"#;
+        let insts: Vec<Instruction> = ObjdumpParser::new(dump.to_string())
+            .extract_function_data("pick")
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| e.parsed_instruction)
+            .collect();
+        let exits = exit_paths(&insts);
+
+        assert_eq!(exits.len(), 3);
+        assert_eq!(exits[0].sources, vec![(Some(1), ValueSource::Constant(42))]);
+        assert_eq!(exits[1].sources, vec![(None, ValueSource::Parameter(1))]);
+        assert_eq!(exits[2].tail_call.as_deref(), Some("other"));
+    }
+
+    #[test]
+    fn test_find_loops() {
+        let dump = r#"
+0000000000000000 <sum>:
+   0:   52800002    mov w2, #0x0
+   4:   b8404403    ldr w3, [x0], #4
+   8:   0b030042    add w2, w2, w3
+   c:   51000421    sub w1, w1, #0x1
+  10:   35ffffa1    cbnz w1, 4 <sum+0x4>
+  14:   2a0203e0    mov w0, w2
+  18:   d65f03c0    ret
+"#;
+        let insts: Vec<Instruction> = ObjdumpParser::new(dump.to_string())
+            .extract_function_data("sum")
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| e.parsed_instruction)
+            .collect();
+        assert_eq!(find_loops(&insts), vec![Loop { head: 1, latch: 4 }]);
+    }
+
+    #[test]
+    fn test_callee_saved_live_ranges() {
+        let liveness = LivenessAnalysis::compute(&instructions());
+
+        // x19 在 stp 之前保存调用者的值，在 mov 之后保存参数，ldp 恢复后一直活跃到 ret
+        assert_eq!(liveness.live_ranges(19), vec![(0, 0), (1, 3), (4, 5)]);
+        // bl 把 x0 作为参数使用并写入返回值，x1 被调用破坏后不再活跃
+        assert!(liveness.live_in[2].contains(0));
+        assert!(liveness.live_out[2].contains(0));
+        assert!(!liveness.live_out[2].contains(1));
+    }
+}
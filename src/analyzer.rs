@@ -0,0 +1,508 @@
+//! 批量分析
+//!
+//! [`Analyzer`] 一次性加载 `<前缀>_<级别>.dump`，在多个线程上并行分析各级别共有的函数，
+//! 写出 [`TableGenerator`] 配置的所有输出格式，并返回每个函数的结果清单
+//! （函数、写入的文件、警告、耗时）。CI 等流水线可以直接调用，不必经过交互式命令行。
+//!
+//! 其他 Rust 工具也可以用 [`Analyzer::analyze`] 只取结构化的分析结果（[`AnalysisReport`]），
+//! 不写文件、不输出：
+//!
+//! ```no_run
+//! let analyzer = alaz::analyzer::Analyzer::open_prefix("matrix")?;
+//! for function in analyzer.functions()? {
+//!     let report = analyzer.analyze(&function)?;
+//!     for level in &report.per_level {
+//!         println!("{} {}: {} 条指令", function, level.level, level.instruction_count());
+//!     }
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::diagnostics::ParseDiagnostics;
+use crate::metrics::{BranchStats, SizeMetrics};
+use crate::objdump::{DumpEntry, ObjdumpParser};
+use crate::table::{ReportOutput, TableGenerator};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// 单个函数的分析结果
+#[derive(Debug, Clone)]
+pub struct FunctionAnalysisResult {
+    pub function: String,
+    /// 写入的文件（报告在前，其后为 JSON 等附带输出）
+    pub outputs: Vec<PathBuf>,
+    /// 不影响生成报告的问题（如未能识别的指令）
+    pub warnings: Vec<String>,
+    pub duration: Duration,
+    /// 分析失败时的错误信息
+    pub error: Option<String>,
+}
+
+impl FunctionAnalysisResult {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// 函数在单个优化级别下的分析结果
+#[derive(Debug, Clone, Serialize)]
+pub struct LevelAnalysis {
+    pub level: String,
+    /// 函数的条目（已按生成器的设置插入跳转表、辅助函数和备注等提示行）
+    pub entries: Vec<DumpEntry>,
+    /// 每个条目的语义解释，与 `entries` 一一对应（提示行为空）
+    pub semantics: Vec<String>,
+    /// 条件跳转方向和循环头对齐的统计
+    pub branches: BranchStats,
+    /// 字节数、指令数、基本块数和跳转数
+    pub size: SizeMetrics,
+    /// 推断的函数签名（Markdown 表格，生成器启用 `with_signature` 时才有）
+    pub signature: Option<String>,
+    /// 未能识别的指令，以及文本与机器码不一致的指令
+    pub warnings: Vec<String>,
+    /// 未被文本解析器完整解析的指令（行、列和原因）与解析覆盖率
+    pub diagnostics: ParseDiagnostics,
+}
+
+impl LevelAnalysis {
+    /// 指令条数（不含提示行）
+    pub fn instruction_count(&self) -> usize {
+        self.entries.iter().filter(|e| !e.asm_instruction.is_empty()).count()
+    }
+}
+
+/// 一个函数在各优化级别下的分析结果
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisReport {
+    pub function: String,
+    /// 按优化级别排列
+    pub per_level: Vec<LevelAnalysis>,
+}
+
+impl AnalysisReport {
+    /// 按名称查找某个优化级别的结果
+    pub fn level(&self, level: &str) -> Option<&LevelAnalysis> {
+        self.per_level.iter().find(|l| l.level == level)
+    }
+
+    /// (级别, 条目)，供 [`TableGenerator`] 的各个表格使用
+    pub fn level_refs(&self) -> Vec<(&str, &[DumpEntry])> {
+        self.per_level.iter().map(|l| (l.level.as_str(), l.entries.as_slice())).collect()
+    }
+}
+
+/// 结果清单中的一项（JSON）
+#[derive(Serialize)]
+struct ManifestEntry<'a> {
+    function: &'a str,
+    outputs: Vec<String>,
+    warnings: &'a [String],
+    /// 可复现模式下省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// 结果清单（JSON）
+#[derive(Serialize)]
+struct Manifest<'a> {
+    prefix: &'a str,
+    levels: &'a [String],
+    functions: Vec<ManifestEntry<'a>>,
+}
+
+/// 批量分析器
+pub struct Analyzer {
+    prefix: String,
+    levels: Vec<String>,
+    /// 各级别的解析器，与 `levels` 一一对应
+    parsers: Vec<ObjdumpParser>,
+    generator: TableGenerator,
+    output_dir: Option<PathBuf>,
+    /// 并行分析的线程数（0 表示按 CPU 核数）
+    jobs: usize,
+}
+
+impl Analyzer {
+    /// 加载前缀对应的各优化级别 dump 文件
+    ///
+    /// `levels` 为空时自动发现 `<前缀>_<级别>.dump`
+    pub fn new(prefix: &str, levels: &[String]) -> anyhow::Result<Self> {
+        let prefix = crate::levels::clean_prefix(prefix);
+        let levels = if levels.is_empty() {
+            crate::levels::discover(&prefix)
+        } else {
+            levels.to_vec()
+        };
+        if levels.is_empty() {
+            anyhow::bail!("未找到 {}_<级别>.dump 文件", prefix);
+        }
+        let parsers = crate::levels::load_all(&prefix, &levels)?;
+        Ok(Self {
+            prefix,
+            generator: TableGenerator::new().with_levels(levels.clone()),
+            levels,
+            parsers,
+            output_dir: None,
+            jobs: 0,
+        })
+    }
+
+    /// 打开前缀对应的所有 `<前缀>_<级别>.dump`（自动发现级别）
+    pub fn open_prefix(prefix: &str) -> anyhow::Result<Self> {
+        Self::new(prefix, &[])
+    }
+
+    /// 设置生成报告使用的表格生成器（其中的级别设置由分析器覆盖）
+    pub fn with_generator(mut self, generator: TableGenerator) -> Self {
+        self.generator = generator.with_levels(self.levels.clone());
+        self
+    }
+
+    /// 设置报告的输出目录（默认为当前目录）
+    pub fn with_output_dir(mut self, dir: PathBuf) -> Self {
+        self.output_dir = Some(dir);
+        self
+    }
+
+    /// 设置并行分析的线程数（0 表示按 CPU 核数）
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// 去掉 `.dump` 和级别后缀后的前缀
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn levels(&self) -> &[String] {
+        &self.levels
+    }
+
+    pub fn generator(&self) -> &TableGenerator {
+        &self.generator
+    }
+
+    /// 所有优化级别共有的函数（按名称排序）
+    pub fn functions(&self) -> anyhow::Result<Vec<String>> {
+        let mut common: Option<BTreeSet<String>> = None;
+        for parser in &self.parsers {
+            let functions: BTreeSet<String> = parser.list_functions()?.into_iter().collect();
+            common = Some(match common {
+                None => functions,
+                Some(existing) => existing.intersection(&functions).cloned().collect(),
+            });
+        }
+        Ok(common.unwrap_or_default().into_iter().collect())
+    }
+
+    /// 分析单个函数，返回各级别的结构化结果（不写文件，也不输出）
+    ///
+    /// 条目的加工方式（辅助函数展开、备注等）和语义解释的语言由表格生成器的设置决定
+    pub fn analyze(&self, function: &str) -> anyhow::Result<AnalysisReport> {
+        self.generator.analyze_parsers(function, &self.level_parsers())
+    }
+
+    /// 并行分析 `filter` 接受的所有共同函数，写出报告并返回每个函数的结果
+    ///
+    /// 结果按函数名排序，与完成顺序无关；单个函数失败不影响其他函数
+    pub fn analyze_all<F>(&self, filter: F) -> anyhow::Result<Vec<FunctionAnalysisResult>>
+    where
+        F: Fn(&str) -> bool + Sync,
+    {
+        if let Some(dir) = &self.output_dir {
+            std::fs::create_dir_all(dir)?;
+        }
+        let functions: Vec<String> = self.functions()?.into_iter().filter(|f| filter(f)).collect();
+        Ok(self.run_parallel(&functions, |function| self.analyze_one(function)))
+    }
+
+    /// 并行分析 `filter` 接受的所有共同函数，只返回结构化结果（不写文件），供合并报告使用
+    ///
+    /// 结果按函数名排序；分析失败的函数带错误信息
+    pub fn analyze_reports<F>(&self, filter: F) -> anyhow::Result<Vec<(String, Result<AnalysisReport, String>)>>
+    where
+        F: Fn(&str) -> bool + Sync,
+    {
+        let functions: Vec<String> = self.functions()?.into_iter().filter(|f| filter(f)).collect();
+        Ok(self.run_parallel(&functions, |function| {
+            (function.to_string(), self.analyze(function).map_err(|e| e.to_string()))
+        }))
+    }
+
+    /// 在 `jobs` 个线程上对每个函数执行 `work`，结果与 `functions` 的顺序一致
+    fn run_parallel<T, W>(&self, functions: &[String], work: W) -> Vec<T>
+    where
+        T: Send,
+        W: Fn(&str) -> T + Sync,
+    {
+        let jobs = match self.jobs {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        }
+        .min(functions.len())
+        .max(1);
+
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, T)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..jobs)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(function) = functions.get(i) else {
+                                break;
+                            };
+                            done.push((i, work(function)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("分析线程异常退出"))
+                .collect()
+        });
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// (级别, 解析器)
+    fn level_parsers(&self) -> Vec<(&str, &ObjdumpParser)> {
+        self.levels.iter().map(|level| level.as_str()).zip(self.parsers.iter()).collect()
+    }
+
+    /// 分析单个函数
+    fn analyze_one(&self, function: &str) -> FunctionAnalysisResult {
+        let start = Instant::now();
+        let result = self.generator.generate_from_parsers(function, &self.level_parsers(), self.output_dir.as_ref());
+        let (outputs, error) = match result {
+            Ok(ReportOutput::Saved(path)) => (self.generator.output_files(&path), None),
+            Ok(ReportOutput::Terminal(_)) => (Vec::new(), None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        FunctionAnalysisResult {
+            function: function.to_string(),
+            outputs,
+            warnings: self.warnings(function),
+            duration: start.elapsed(),
+            error,
+        }
+    }
+
+    /// 各级别中未能识别的指令，以及文本与机器码解码或重新编码结果不一致的指令
+    /// （ARM32 dump 不逐条解析，不计入）
+    fn warnings(&self, function: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for (level, parser) in self.levels.iter().zip(&self.parsers) {
+            if parser.is_arm32() {
+                continue;
+            }
+            let Ok(entries) = parser.extract_function_data(function) else {
+                continue;
+            };
+            warnings.extend(entry_warnings(&entries).into_iter().map(|w| format!("{}: {}", level, w)));
+        }
+        warnings
+    }
+
+    /// 批量分析的索引和结果清单的保存路径：`<前缀名>_index.md`、`<前缀名>_manifest.json`
+    pub fn index_paths(&self) -> (PathBuf, PathBuf) {
+        let stem = self.stem();
+        let in_output = |name: String| match &self.output_dir {
+            Some(dir) => dir.join(name),
+            None => PathBuf::from(name),
+        };
+        (in_output(format!("{}_index.md", stem)), in_output(format!("{}_manifest.json", stem)))
+    }
+
+    /// 写入批量分析的索引和结果清单，与报告一样遵循表格生成器的覆盖设置
+    pub fn write_index(&self, results: &[FunctionAnalysisResult]) -> anyhow::Result<(PathBuf, PathBuf)> {
+        let (index_path, manifest_path) = self.index_paths();
+        self.generator.prepare_files(&[index_path.clone(), manifest_path.clone()])?;
+        let reports: Vec<(String, Result<PathBuf, String>)> = results
+            .iter()
+            .map(|r| {
+                let report = match r.error {
+                    Some(ref e) => Err(e.clone()),
+                    None => r.outputs.first().cloned().ok_or_else(|| String::from("未写入报告")),
+                };
+                (r.function.clone(), report)
+            })
+            .collect();
+        self.generator
+            .save_to_file(&self.generator.generate_index(&self.stem(), &self.levels, &reports), &index_path)?;
+        self.generator.save_to_file(&self.manifest_json(results), &manifest_path)?;
+        Ok((index_path, manifest_path))
+    }
+
+    /// 前缀去掉目录后的文件名部分
+    fn stem(&self) -> String {
+        Path::new(&self.prefix)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.prefix.clone())
+    }
+
+    /// 把分析结果写成 JSON 清单
+    ///
+    /// 可复现模式下前缀只保留文件名、输出文件写成相对于输出目录（即清单所在目录）的路径，
+    /// 并省略耗时，同一份输入在不同机器、不同目录下得到相同的清单
+    pub fn manifest_json(&self, results: &[FunctionAnalysisResult]) -> String {
+        let reproducible = self.generator.is_reproducible();
+        let output = |path: &PathBuf| {
+            let relative = match &self.output_dir {
+                Some(dir) if reproducible => path.strip_prefix(dir).unwrap_or(path),
+                _ => path,
+            };
+            relative.display().to_string()
+        };
+        let manifest = Manifest {
+            prefix: self.generator.source_name(&self.prefix),
+            levels: &self.levels,
+            functions: results
+                .iter()
+                .map(|r| ManifestEntry {
+                    function: &r.function,
+                    outputs: r.outputs.iter().map(output).collect(),
+                    warnings: &r.warnings,
+                    duration_ms: (!reproducible).then_some(r.duration.as_millis()),
+                    error: r.error.as_deref(),
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&manifest).unwrap_or_default()
+    }
+}
+
+/// 未能识别的指令，以及文本与机器码解码或重新编码结果不一致的指令
+pub(crate) fn entry_warnings(entries: &[DumpEntry]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let unparsed = entries
+        .iter()
+        .filter(|e| !e.asm_instruction.is_empty() && e.parsed_instruction.is_none())
+        .count();
+    if unparsed > 0 {
+        warnings.push(format!("{} 条指令未能识别", unparsed));
+    }
+    for entry in entries {
+        if let Some(mismatch) = crate::decoder::check(entry) {
+            warnings.push(format!(
+                "0x{:x} `{}` 与机器码解码结果 `{}` 不一致",
+                mismatch.address,
+                mismatch.text,
+                crate::decoder::assembly(&mismatch.decoded)
+            ));
+        } else if let Some(mismatch) = crate::encoder::check(entry) {
+            warnings.push(format!(
+                "0x{:x} `{}` 重新编码为 {:08x}，与机器码 {:08x} 不一致",
+                mismatch.address, mismatch.text, mismatch.encoded, mismatch.expected
+            ));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Overwrite;
+
+    const DUMP: &str = r#"
+t:     file format elf64-littleaarch64
+
+Disassembly of section .text:
+
+0000000000000000 <add>:
+   0:   0b010000    add w0, w0, w1
+   4:   d65f03c0    ret
+
+0000000000000008 <neg>:
+   8:   4b0003e0    neg w0, w0
+   c:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_analyze_all() {
+        let dir = std::env::temp_dir().join("alaz-analyzer-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let prefix = dir.join("t");
+        for level in ["O0", "O2"] {
+            std::fs::write(format!("{}_{}.dump", prefix.display(), level), DUMP).unwrap();
+        }
+
+        let analyzer = Analyzer::new(&prefix.to_string_lossy(), &[])
+            .unwrap()
+            .with_generator(TableGenerator::new().with_json_output(true))
+            .with_output_dir(dir.join("out"))
+            .with_jobs(2);
+        assert_eq!(analyzer.levels(), ["O0", "O2"]);
+        assert_eq!(analyzer.functions().unwrap(), ["add", "neg"]);
+
+        let results = analyzer.analyze_all(|f| f != "neg").unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_ok(), "{:?}", results[0].error);
+        assert_eq!(results[0].outputs, [dir.join("out/add_comparison.md"), dir.join("out/add_comparison.json")]);
+        assert!(results[0].outputs.iter().all(|p| p.exists()));
+
+        let manifest: serde_json::Value = serde_json::from_str(&analyzer.manifest_json(&results)).unwrap();
+        assert_eq!(manifest["levels"], serde_json::json!(["O0", "O2"]));
+        assert_eq!(manifest["functions"][0]["function"], "add");
+        assert!(manifest["functions"][0].get("error").is_none());
+        assert_eq!(manifest["prefix"], prefix.display().to_string());
+        assert!(manifest["functions"][0]["duration_ms"].is_u64());
+
+        // 可复现模式下清单不含目录和耗时
+        let reproducible = Analyzer::new(&prefix.to_string_lossy(), &[])
+            .unwrap()
+            .with_generator(TableGenerator::new().with_reproducible(true))
+            .with_output_dir(dir.join("out"));
+        let manifest: serde_json::Value = serde_json::from_str(&reproducible.manifest_json(&results)).unwrap();
+        assert_eq!(manifest["prefix"], "t");
+        assert_eq!(manifest["functions"][0]["outputs"], serde_json::json!(["add_comparison.md", "add_comparison.json"]));
+        assert!(manifest["functions"][0].get("duration_ms").is_none());
+
+        // 索引和结果清单与报告一样默认不覆盖，--force 时覆盖
+        let (index_path, manifest_path) = analyzer.write_index(&results).unwrap();
+        assert_eq!(index_path, dir.join("out/t_index.md"));
+        assert_eq!(manifest_path, dir.join("out/t_manifest.json"));
+        let err = analyzer.write_index(&results).unwrap_err();
+        assert!(err.to_string().contains("t_index.md 已存在"));
+        assert!(analyzer.analyze_all(|f| f == "add").unwrap()[0].error.as_ref().unwrap().contains("已存在"));
+        let analyzer = analyzer.with_generator(TableGenerator::new().with_overwrite(Overwrite::Always));
+        assert!(analyzer.write_index(&results).is_ok());
+        assert!(analyzer.analyze_all(|f| f == "add").unwrap()[0].is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_analyze() {
+        let dir = std::env::temp_dir().join("alaz-analyzer-report-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let prefix = dir.join("t");
+        for level in ["O0", "O2"] {
+            std::fs::write(format!("{}_{}.dump", prefix.display(), level), DUMP).unwrap();
+        }
+
+        let analyzer = Analyzer::open_prefix(&prefix.to_string_lossy()).unwrap();
+        let report = analyzer.analyze("neg").unwrap();
+        assert_eq!(report.function, "neg");
+        assert_eq!(report.per_level.len(), 2);
+        let o2 = report.level("O2").unwrap();
+        assert_eq!(o2.instruction_count(), 2);
+        assert_eq!(o2.semantics.len(), o2.entries.len());
+        assert!(o2.warnings.is_empty());
+        assert!(analyzer.analyze("missing").is_err());
+        // 不写任何文件
+        assert!(!std::path::Path::new("neg_comparison.md").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
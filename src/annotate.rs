@@ -0,0 +1,222 @@
+//! 逆向工具注释脚本导出
+//!
+//! 把语义解释、基本块摘要和函数导读生成为 Ghidra（Jython）或 Binary Ninja（Python API）脚本，
+//! 在这些工具中运行后以注释的形式写到对应地址上。注释按“函数名 + 偏移”定位，
+//! 与二进制在工具中的加载基址无关。
+
+use crate::analysis;
+use crate::error::{InterpreterError, Result};
+use crate::instruction::Instruction;
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::table::TableGenerator;
+
+/// 脚本格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptFormat {
+    /// Ghidra Script Manager 中运行的 Jython 脚本
+    Ghidra,
+    /// Binary Ninja 脚本控制台中运行的 Python 脚本
+    BinaryNinja,
+}
+
+impl ScriptFormat {
+    /// 解析格式名称（ghidra, binja）
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.to_lowercase().as_str() {
+            "ghidra" => Ok(ScriptFormat::Ghidra),
+            "binja" | "binaryninja" => Ok(ScriptFormat::BinaryNinja),
+            _ => Err(InterpreterError::ParseError(format!(
+                "不支持的脚本格式: {} (可选: ghidra, binja)",
+                text
+            ))),
+        }
+    }
+}
+
+/// 注释的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// 函数注释（函数导读）
+    Function,
+    /// 基本块开头的注释（块摘要）
+    Block,
+    /// 指令行尾的注释（语义解释）
+    Instruction,
+}
+
+/// 一条注释
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// 函数名
+    pub function: String,
+    /// 相对函数起始地址的偏移
+    pub offset: u64,
+    /// 位置
+    pub kind: CommentKind,
+    /// 注释内容
+    pub text: String,
+}
+
+/// 为函数收集注释：函数导读、每个基本块的摘要和每条指令的语义解释
+pub fn collect(function: &str, entries: &[DumpEntry], lang: Lang) -> Vec<Annotation> {
+    let semantics = TableGenerator::new().with_language(lang).semantic_column(entries);
+    let rows: Vec<(u64, &DumpEntry, &String)> = entries
+        .iter()
+        .zip(&semantics)
+        .filter(|(e, _)| !e.asm_instruction.is_empty())
+        .filter_map(|(e, text)| Some((offset_of(e)?, e, text)))
+        .collect();
+    let annotation = |offset, kind, text| Annotation { function: function.to_string(), offset, kind, text };
+
+    let mut annotations = Vec::new();
+    if let Some(text) = crate::walkthrough::generate(entries, lang) {
+        annotations.push(annotation(0, CommentKind::Function, text));
+    }
+
+    // 基本块只在指令都能解析时划分，否则控制流不完整
+    let instructions: Vec<Instruction> = rows.iter().filter_map(|(_, e, _)| e.parsed_instruction.clone()).collect();
+    if instructions.len() == rows.len() {
+        let loops = analysis::find_loops(&instructions);
+        let leaders = analysis::block_leaders(&instructions);
+        let ends = leaders.iter().skip(1).copied().chain([rows.len()]);
+        for (n, range) in leaders.iter().zip(ends).map(|(start, end)| *start..end).enumerate() {
+            let mut code: Vec<&str> = Vec::new();
+            for (_, e, _) in &rows[range.clone()] {
+                if !e.c_code.is_empty() && !code.contains(&e.c_code.as_str()) {
+                    code.push(&e.c_code);
+                }
+            }
+            let mut text = match lang {
+                Lang::Zh => format!("基本块 {}（{} 条指令）", n + 1, range.len()),
+                Lang::En => format!("block {} ({} instructions)", n + 1, range.len()),
+            };
+            if loops.iter().any(|l| l.head == range.start) {
+                text.push_str(lang.pick("，循环头", ", loop header"));
+            }
+            if !code.is_empty() {
+                text.push_str(lang.pick("：", ": "));
+                text.push_str(&code.join(" / ").replace("<br>", " "));
+            }
+            annotations.push(annotation(rows[range.start].0, CommentKind::Block, text));
+        }
+    }
+
+    for (offset, _, text) in &rows {
+        annotations.push(annotation(*offset, CommentKind::Instruction, text.to_string()));
+    }
+    annotations
+}
+
+/// 条目相对函数起始地址的偏移（取自指令标识符 `函数名+0x偏移`）
+fn offset_of(entry: &DumpEntry) -> Option<u64> {
+    let (_, offset) = entry.id.rsplit_once("+0x")?;
+    u64::from_str_radix(offset, 16).ok()
+}
+
+
+/// 生成应用注释的脚本
+pub fn to_script(annotations: &[Annotation], format: ScriptFormat) -> String {
+    let mut rows = String::new();
+    for a in annotations {
+        let kind = match a.kind {
+            CommentKind::Function => "function",
+            CommentKind::Block => "block",
+            CommentKind::Instruction => "instruction",
+        };
+        // JSON 字符串同时也是合法的 Python 字符串字面量
+        let text = serde_json::to_string(&a.text).unwrap_or_default();
+        let function = serde_json::to_string(&a.function).unwrap_or_default();
+        match format {
+            ScriptFormat::Ghidra => rows.push_str(&format!("    (u{}, 0x{:x}, \"{}\", u{}),\n", function, a.offset, kind, text)),
+            ScriptFormat::BinaryNinja => rows.push_str(&format!("    ({}, 0x{:x}, \"{}\", {}),\n", function, a.offset, kind, text)),
+        }
+    }
+
+    match format {
+        ScriptFormat::Ghidra => format!(
+            r#"# -*- coding: utf-8 -*-
+# 由 alaz 生成：在 Ghidra 的 Script Manager 中运行，把分析结果写为注释
+# @category alaz
+from ghidra.program.model.listing import CodeUnit
+
+ANNOTATIONS = [
+{}]
+
+KINDS = {{"function": CodeUnit.PLATE_COMMENT, "block": CodeUnit.PRE_COMMENT, "instruction": CodeUnit.EOL_COMMENT}}
+
+listing = currentProgram.getListing()
+for name, offset, kind, text in ANNOTATIONS:
+    functions = getGlobalFunctions(name)
+    if not functions:
+        print(u"未找到函数 %s" % name)
+        continue
+    address = functions[0].getEntryPoint().add(offset)
+    listing.setComment(address, KINDS[kind], text)
+"#,
+            rows
+        ),
+        ScriptFormat::BinaryNinja => format!(
+            r#"# 由 alaz 生成：在 Binary Ninja 的脚本控制台中运行（需要当前视图 bv），把分析结果写为注释
+ANNOTATIONS = [
+{}]
+
+comments = {{}}
+for name, offset, kind, text in ANNOTATIONS:
+    symbol = bv.get_symbol_by_raw_name(name)
+    function = bv.get_function_at(symbol.address) if symbol else None
+    if function is None:
+        print("未找到函数 %s" % name)
+        continue
+    if kind == "function":
+        function.comment = text
+    else:
+        # 每个地址只有一条注释，块摘要放在语义解释之前
+        key = (function.start, function.start + offset)
+        comments[key] = comments.get(key, []) + [text]
+
+for (start, address), texts in comments.items():
+    bv.get_function_at(start).set_comment_at(address, "\n".join(texts))
+"#,
+            rows
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <count>:
+   0:   52800001    mov w1, #0x0
+   4:   11000421    add w1, w1, #0x1
+   8:   6b00003f    cmp w1, w0
+   c:   54ffffcb    b.lt 4 <count+0x4>
+  10:   2a0103e0    mov w0, w1
+  14:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_collect_annotations() {
+        let entries = ObjdumpParser::new(DUMP.to_string()).extract_function_data("count").unwrap();
+        let annotations = collect("count", &entries, Lang::Zh);
+
+        let blocks: Vec<(u64, &str)> = annotations
+            .iter()
+            .filter(|a| a.kind == CommentKind::Block)
+            .map(|a| (a.offset, a.text.as_str()))
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![(0x0, "基本块 1（1 条指令）"), (0x4, "基本块 2（3 条指令），循环头"), (0x10, "基本块 3（2 条指令）")]
+        );
+        assert_eq!(annotations.iter().filter(|a| a.kind == CommentKind::Instruction).count(), 6);
+        assert!(annotations.iter().any(|a| a.kind == CommentKind::Function));
+
+        let script = to_script(&annotations, ScriptFormat::Ghidra);
+        assert!(script.contains("(u\"count\", 0x4, \"block\", u\"基本块 2（3 条指令），循环头\"),"));
+        assert!(to_script(&annotations, ScriptFormat::BinaryNinja).contains("set_comment_at"));
+    }
+}
@@ -0,0 +1,758 @@
+//! ARM32（A32/T32）指令支持
+//!
+//! armv7 目标（`file format elf32-littlearm`）的 dump 使用 32 位 ARM 与 Thumb 指令：
+//! 寄存器为 r0–r15，几乎每条指令都可以带条件码后缀（`addne`）和 `s` 后缀（`movs`），
+//! Thumb-2 还会加宽度限定符（`ldr.w`、`b.n`）。AArch64 的解析器不认识这些写法，
+//! 这里按文本独立解析：先把助记符拆成基本操作、`s` 后缀、条件码和宽度限定符，
+//! 再按基本操作生成与 AArch64 相同风格的语义解释。
+
+use crate::locale::Lang;
+use crate::register::Condition;
+
+/// 是否为 32 位 ARM 目标的 file format（`elf32-littlearm`、`elf32-bigarm`）
+pub fn is_arm_format(format: &str) -> bool {
+    format.contains("arm") && !format.contains("aarch64")
+}
+
+/// 解析 ARM32 寄存器名，返回编号 0–15（含 sp/lr/pc/fp/ip/sl/sb 别名）
+pub fn register_index(name: &str) -> Option<u8> {
+    let name = name.trim().to_lowercase();
+    let index = match name.as_str() {
+        "sb" => 9,
+        "sl" => 10,
+        "fp" => 11,
+        "ip" => 12,
+        "sp" => 13,
+        "lr" => 14,
+        "pc" => 15,
+        _ => name.strip_prefix('r')?.parse().ok().filter(|n: &u8| *n <= 15)?,
+    };
+    Some(index)
+}
+
+/// 可以带 `s` 后缀（设置标志位）的数据处理指令
+const FLAG_SETTING: &[&str] = &[
+    "mov", "mvn", "add", "adc", "sub", "sbc", "rsb", "rsc", "and", "orr", "eor", "bic", "orn",
+    "lsl", "lsr", "asr", "ror", "rrx", "mul", "mla", "umull", "smull", "umlal", "smlal",
+];
+
+/// 不带 `s` 后缀的指令
+const PLAIN: &[&str] = &[
+    "movw", "movt", "mls", "udiv", "sdiv", "cmp", "cmn", "tst", "teq",
+    "ldr", "ldrb", "ldrh", "ldrsb", "ldrsh", "ldrd", "ldrex",
+    "str", "strb", "strh", "strd", "strex",
+    "ldm", "ldmia", "ldmfd", "ldmdb", "stm", "stmia", "stmea", "stmdb", "stmfd",
+    "push", "pop", "b", "bl", "blx", "bx", "cbz", "cbnz", "tbb", "tbh", "adr",
+    "uxtb", "uxth", "sxtb", "sxth", "ubfx", "sbfx", "bfi", "bfc", "clz", "rev",
+    "nop", "svc", "bkpt", "dmb", "dsb", "isb",
+];
+
+/// 拆分后的助记符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mnemonic {
+    /// 基本操作（如 `add`、`ldrb`）
+    pub base: &'static str,
+    /// 是否带 `s` 后缀
+    pub sets_flags: bool,
+    /// 条件码后缀（`al` 视为无条件）
+    pub condition: Option<Condition>,
+}
+
+impl Mnemonic {
+    /// 拆分助记符：基本操作 + 可选 `s` + 可选条件码 + 可选 `.w`/`.n`
+    ///
+    /// 同一前缀可能有多种拆法（`bls` 是 `b` + `ls`，不是 `bl` + `s`），
+    /// 按基本操作从长到短逐个尝试，取第一个能完整拆开的
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        let text = text.strip_suffix(".w").or_else(|| text.strip_suffix(".n")).unwrap_or(&text);
+
+        let mut bases: Vec<(&'static str, bool)> = FLAG_SETTING
+            .iter()
+            .map(|base| (*base, true))
+            .chain(PLAIN.iter().map(|base| (*base, false)))
+            .collect();
+        bases.sort_by_key(|(base, _)| std::cmp::Reverse(base.len()));
+
+        bases.into_iter().find_map(|(base, flag_setting)| {
+            let rest = text.strip_prefix(base)?;
+            let (sets_flags, condition) = Self::suffix(rest, flag_setting)?;
+            Some(Mnemonic { base, sets_flags, condition })
+        })
+    }
+
+    /// 解析基本操作之后的后缀：``、`s`、`cc`、`scc`、`ccs`（UAL 之前的写法）
+    fn suffix(rest: &str, flag_setting: bool) -> Option<(bool, Option<Condition>)> {
+        let condition = |text: &str| {
+            Condition::parse(text).ok().map(|c| (c != Condition::AL).then_some(c))
+        };
+        match rest.len() {
+            0 => Some((false, None)),
+            1 if rest == "s" && flag_setting => Some((true, None)),
+            2 => Some((false, condition(rest)?)),
+            3 if flag_setting && rest.starts_with('s') => Some((true, condition(&rest[1..])?)),
+            3 if flag_setting && rest.ends_with('s') => Some((true, condition(&rest[..2])?)),
+            _ => None,
+        }
+    }
+}
+
+/// 操作数
+#[derive(Debug, Clone, PartialEq)]
+enum Arg {
+    /// 寄存器（`!` 表示基址回写，用于 `ldm sp!, {...}`）
+    Reg { name: String, writeback: bool },
+    /// 立即数
+    Imm(i64),
+    /// 移位（`lsl #2`、`asr r3`）
+    Shift(String),
+    /// 内存地址 `[base, #off]`、`[base, index, lsl #2]`，`!` 为前变址回写
+    Mem { base: String, offset: Option<String>, writeback: bool },
+    /// 寄存器列表 `{r4, r7, lr}`
+    List(Vec<String>),
+    /// 跳转目标（`1c <main+0x1c>`）
+    Label(String),
+}
+
+/// 寄存器的显示名称（大写，与 AArch64 的语义解释一致）
+fn reg(text: &str) -> Option<String> {
+    register_index(text).map(|_| text.trim().to_uppercase())
+}
+
+/// 立即数的显示方式与 AArch64 的语义解释一致：负数为十进制，其他为十六进制
+fn imm_text(value: i64) -> String {
+    if value < 0 {
+        value.to_string()
+    } else {
+        format!("0x{:x}", value)
+    }
+}
+
+fn parse_imm(text: &str) -> Option<i64> {
+    let text = text.trim().strip_prefix('#')?;
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None => digits.parse().ok()?,
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// 移位操作数（`lsl #2`、`lsr r3`、`rrx`）
+fn parse_shift(text: &str) -> Option<String> {
+    let mut parts = text.split_whitespace();
+    let kind = parts.next()?.to_lowercase();
+    if !["lsl", "lsr", "asr", "ror", "rrx"].contains(&kind.as_str()) {
+        return None;
+    }
+    let amount = match parts.next() {
+        Some(amount) => parse_imm(amount).map(|n| n.to_string()).or_else(|| reg(amount))?,
+        None if kind == "rrx" => return Some("rrx".to_string()),
+        None => return None,
+    };
+    let op = match kind.as_str() {
+        "lsl" => "<<",
+        "lsr" | "asr" => ">>",
+        _ => "ror",
+    };
+    Some(format!("{} {}", op, amount))
+}
+
+/// 按顶层逗号切分操作数（`[]`、`{}` 内的逗号不切）
+fn split_operands(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for c in text.chars() {
+        match c {
+            '[' | '{' | '<' => depth += 1,
+            ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+fn parse_arg(text: &str) -> Option<Arg> {
+    if let Some(inner) = text.strip_prefix('[') {
+        let (inner, writeback) = match inner.strip_suffix("]!") {
+            Some(inner) => (inner, true),
+            None => (inner.strip_suffix(']')?, false),
+        };
+        let parts = split_operands(inner);
+        let base = reg(parts.first()?)?;
+        let offset = match parts.get(1) {
+            None => None,
+            Some(part) => Some(match parse_imm(part) {
+                Some(value) if value < 0 => format!("- 0x{:x}", -value),
+                Some(value) => format!("+ 0x{:x}", value),
+                None => {
+                    let (sign, index) = match part.strip_prefix('-') {
+                        Some(index) => ("-", index),
+                        None => ("+", part.as_str()),
+                    };
+                    let shift = match parts.get(2) {
+                        Some(shift) => format!(" {}", parse_shift(shift)?),
+                        None => String::new(),
+                    };
+                    format!("{} {}{}", sign, reg(index)?, shift)
+                }
+            }),
+        };
+        return Some(Arg::Mem { base, offset, writeback });
+    }
+    if let Some(inner) = text.strip_prefix('{') {
+        let registers = split_operands(inner.strip_suffix('}')?)
+            .iter()
+            .map(|item| match item.split_once('-') {
+                Some((from, to)) => Some(format!("{}–{}", reg(from)?, reg(to)?)),
+                None => reg(item),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        return Some(Arg::List(registers));
+    }
+    if let Some(value) = parse_imm(text) {
+        return Some(Arg::Imm(value));
+    }
+    if let Some(shift) = parse_shift(text) {
+        return Some(Arg::Shift(shift));
+    }
+    let (name, writeback) = match text.strip_suffix('!') {
+        Some(name) => (name, true),
+        None => (text, false),
+    };
+    if let Some(name) = reg(name) {
+        return Some(Arg::Reg { name, writeback });
+    }
+    // 跳转目标：十六进制地址，可带 <符号>
+    let address = text.split_whitespace().next()?;
+    if address.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Some(Arg::Label(text.to_string()));
+    }
+    None
+}
+
+/// 分离注释：objdump 的 ARM 注释以 `@` 开头（如 `@ (2c <main+0x2c>)`），llvm-objdump 用 `;`
+fn split_comment(asm: &str) -> (&str, Option<&str>) {
+    let at = asm
+        .char_indices()
+        .find(|&(i, c)| c == ';' || (c == '@' && asm[..i].ends_with(char::is_whitespace)))
+        .map(|(i, _)| i);
+    match at {
+        Some(i) => (asm[..i].trim(), Some(asm[i + 1..].trim())),
+        None => (asm.trim(), None),
+    }
+}
+
+/// 条件的说明：(中文, 英文, 标志位条件)
+fn condition_text(condition: Condition) -> (&'static str, &'static str, &'static str) {
+    match condition {
+        Condition::EQ => ("相等", "equal", "Z=1"),
+        Condition::NE => ("不相等", "not equal", "Z=0"),
+        Condition::CS => ("无符号大于等于", "unsigned higher or same", "C=1"),
+        Condition::CC => ("无符号小于", "unsigned lower", "C=0"),
+        Condition::MI => ("为负", "negative", "N=1"),
+        Condition::PL => ("非负", "non-negative", "N=0"),
+        Condition::VS => ("溢出", "overflow", "V=1"),
+        Condition::VC => ("未溢出", "no overflow", "V=0"),
+        Condition::HI => ("无符号大于", "unsigned higher", "C=1且Z=0"),
+        Condition::LS => ("无符号小于等于", "unsigned lower or same", "C=0或Z=1"),
+        Condition::GE => ("有符号大于等于", "signed greater or equal", "N=V"),
+        Condition::LT => ("有符号小于", "signed less than", "N≠V"),
+        Condition::GT => ("有符号大于", "signed greater than", "Z=0且N=V"),
+        Condition::LE => ("有符号小于等于", "signed less or equal", "Z=1或N≠V"),
+        Condition::AL => ("总是", "always", ""),
+    }
+}
+
+/// 标志位条件（英文把“且/或”换成 and/or）
+fn condition_flags(condition: Condition, lang: Lang) -> String {
+    let (_, _, flags) = condition_text(condition);
+    match lang {
+        Lang::Zh => flags.to_string(),
+        Lang::En => flags.replace('且', " and ").replace('或', " or "),
+    }
+}
+
+fn condition_phrase(condition: Condition, lang: Lang) -> String {
+    let (zh, en, _) = condition_text(condition);
+    format!("{} ({})", lang.pick(zh, en), condition_flags(condition, lang))
+}
+
+/// 解释 IT 块（`itte eq`）：随后最多 4 条指令按条件执行
+fn describe_it(pattern: &str, operand: &str, lang: Lang) -> Option<String> {
+    if pattern.len() > 3 || !pattern.chars().all(|c| c == 't' || c == 'e') {
+        return None;
+    }
+    let condition = Condition::parse(operand).ok()?;
+    let then = 1 + pattern.chars().filter(|&c| c == 't').count();
+    let otherwise = pattern.len() + 1 - then;
+    let phrase = condition_phrase(condition, lang);
+    let mut text = match lang {
+        Lang::Zh => format!("IT 块：随后 {} 条指令在{}时执行", then, phrase),
+        Lang::En => format!("IT block: the next {} instruction(s) execute if {}", then, phrase),
+    };
+    if otherwise > 0 {
+        text.push_str(&match lang {
+            Lang::Zh => format!("，{} 条在条件不成立时执行", otherwise),
+            Lang::En => format!(", {} if it does not hold", otherwise),
+        });
+    }
+    Some(text)
+}
+
+/// 解释一条 ARM32/Thumb 指令，不认识的指令或操作数（包括 AArch64 寄存器）返回 None
+pub fn describe(asm: &str, lang: Lang) -> Option<String> {
+    let (text, comment) = split_comment(asm);
+    let (mnemonic, operands) = match text.split_once(char::is_whitespace) {
+        Some((mnemonic, operands)) => (mnemonic, operands.trim()),
+        None => (text, ""),
+    };
+    let lower = mnemonic.to_lowercase();
+
+    if let Some(pattern) = lower.strip_prefix("it") {
+        if let Some(text) = describe_it(pattern, operands, lang) {
+            return Some(text);
+        }
+    }
+    if matches!(lower.as_str(), ".word" | ".short" | ".byte") {
+        return Some(match lang {
+            Lang::Zh => format!("数据 {}（字面量池或跳转表）", operands),
+            Lang::En => format!("data {} (literal pool or jump table)", operands),
+        });
+    }
+
+    let mnemonic = Mnemonic::parse(mnemonic)?;
+    let args = split_operands(operands)
+        .iter()
+        .map(|operand| parse_arg(operand))
+        .collect::<Option<Vec<_>>>()?;
+    let core = core(&mnemonic, &args, comment, lang)?;
+
+    let is_branch = matches!(mnemonic.base, "b" | "bl" | "blx" | "bx");
+    let mut text = match mnemonic.condition {
+        Some(condition) if !is_branch => match lang {
+            Lang::Zh => format!("若{}：{}", condition_phrase(condition, lang), core),
+            Lang::En => format!("if {}: {}", condition_phrase(condition, lang), core),
+        },
+        _ => core,
+    };
+    if mnemonic.sets_flags {
+        text.push_str(lang.pick(" (设置标志位)", " (sets flags)"));
+    }
+    Some(text)
+}
+
+/// 操作数的显示文本；后面跟着移位操作数时一并带上（`R2 << 2`）
+fn value(args: &[Arg], i: usize) -> Option<String> {
+    let text = match args.get(i)? {
+        Arg::Reg { name, .. } => name.clone(),
+        Arg::Imm(value) => imm_text(*value),
+        Arg::Label(label) => label.clone(),
+        Arg::Mem { base, offset, .. } => match offset {
+            Some(offset) => format!("({} {})", base, offset),
+            None => format!("({})", base),
+        },
+        Arg::List(registers) => registers.join(", "),
+        Arg::Shift(_) => return None,
+    };
+    match args.get(i + 1) {
+        Some(Arg::Shift(shift)) => Some(format!("{} {}", text, shift)),
+        _ => Some(text),
+    }
+}
+
+/// 去掉移位操作数后的操作数个数
+fn count(args: &[Arg]) -> usize {
+    args.iter().filter(|arg| !matches!(arg, Arg::Shift(_))).count()
+}
+
+/// 跳转目标中的符号名（`1c <main+0x1c>` → `main+0x1c`）
+fn target_symbol(label: &str) -> Option<&str> {
+    let start = label.find('<')?;
+    label[start + 1..].strip_suffix('>')
+}
+
+/// 不含条件前缀与 `s` 后缀的解释
+fn core(mnemonic: &Mnemonic, args: &[Arg], comment: Option<&str>, lang: Lang) -> Option<String> {
+    let zh_en = |zh: String, en: String| Some(match lang {
+        Lang::Zh => zh,
+        Lang::En => en,
+    });
+    let v = |i: usize| value(args, i);
+    // Thumb 的两操作数形式（`adds r0, #1`）等价于 `adds r0, r0, #1`
+    let binary = |op: &str| -> Option<String> {
+        let (d, a, b) = if count(args) >= 3 {
+            (v(0)?, v(1)?, value(args, 2)?)
+        } else {
+            (v(0)?, v(0)?, v(1)?)
+        };
+        Some(format!("{} = {} {} {}", d, a, op, b))
+    };
+
+    match mnemonic.base {
+        "mov" => Some(format!("{} = {}", v(0)?, v(1)?)),
+        "mvn" => Some(format!("{} = ~{}", v(0)?, v(1)?)),
+        "movw" => zh_en(
+            format!("{} = {} (高 16 位清零)", v(0)?, v(1)?),
+            format!("{} = {} (upper 16 bits cleared)", v(0)?, v(1)?),
+        ),
+        "movt" => zh_en(
+            format!("{} 的高 16 位 = {} (低 16 位不变)", v(0)?, v(1)?),
+            format!("{}[31:16] = {} (low half kept)", v(0)?, v(1)?),
+        ),
+        "add" => binary("+"),
+        "sub" => binary("-"),
+        "and" => binary("&"),
+        "orr" => binary("|"),
+        "eor" => binary("^"),
+        "lsl" => binary("<<"),
+        "lsr" | "asr" => binary(">>"),
+        "ror" => binary("ror"),
+        "mul" => binary("×"),
+        "adc" => binary("+").map(|text| format!("{} + C", text)),
+        "sbc" => binary("-").map(|text| format!("{} - !C", text)),
+        "bic" => binary("& ~"),
+        "orn" => binary("| ~"),
+        "rsb" | "rsc" => {
+            let (d, a, b) = if count(args) >= 3 { (v(0)?, v(1)?, v(2)?) } else { (v(0)?, v(0)?, v(1)?) };
+            Some(format!("{} = {} - {}", d, b, a))
+        }
+        "rrx" => Some(format!("{} = {} rrx", v(0)?, v(1)?)),
+        "mla" => Some(format!("{} = {} × {} + {}", v(0)?, v(1)?, v(2)?, v(3)?)),
+        "mls" => Some(format!("{} = {} - {} × {}", v(0)?, v(3)?, v(1)?, v(2)?)),
+        "udiv" | "sdiv" => {
+            let signed = mnemonic.base == "sdiv";
+            zh_en(
+                format!("{} = {} ÷ {} ({})", v(0)?, v(1)?, v(2)?, if signed { "有符号" } else { "无符号" }),
+                format!("{} = {} ÷ {} ({})", v(0)?, v(1)?, v(2)?, if signed { "signed" } else { "unsigned" }),
+            )
+        }
+        "umull" | "smull" | "umlal" | "smlal" => {
+            let op = if mnemonic.base.ends_with("lal") { "+=" } else { "=" };
+            let signed = mnemonic.base.starts_with('s');
+            zh_en(
+                format!("{}:{} {} {} × {} (64 位{}乘法)", v(1)?, v(0)?, op, v(2)?, v(3)?, if signed { "有符号" } else { "无符号" }),
+                format!("{}:{} {} {} × {} (64-bit {} multiply)", v(1)?, v(0)?, op, v(2)?, v(3)?, if signed { "signed" } else { "unsigned" }),
+            )
+        }
+        "cmp" => zh_en(
+            format!("比较 {} 和 {} (设置标志位)", v(0)?, v(1)?),
+            format!("compare {} with {} (sets flags)", v(0)?, v(1)?),
+        ),
+        "cmn" => zh_en(
+            format!("比较 {} 和 -{} (设置标志位)", v(0)?, v(1)?),
+            format!("compare {} with -{} (sets flags)", v(0)?, v(1)?),
+        ),
+        "tst" => zh_en(
+            format!("测试 {} & {} (设置标志位)", v(0)?, v(1)?),
+            format!("test {} & {} (sets flags)", v(0)?, v(1)?),
+        ),
+        "teq" => zh_en(
+            format!("测试 {} ^ {} (设置标志位)", v(0)?, v(1)?),
+            format!("test {} ^ {} (sets flags)", v(0)?, v(1)?),
+        ),
+        "ldr" | "ldrb" | "ldrh" | "ldrsb" | "ldrsh" | "ldrex" => {
+            let (zh, en) = match mnemonic.base {
+                "ldrb" => ("字节", "byte "),
+                "ldrh" => ("半字", "halfword "),
+                "ldrsb" => ("有符号字节", "signed byte "),
+                "ldrsh" => ("有符号半字", "signed halfword "),
+                "ldrex" => ("(独占)", "(exclusive) "),
+                _ => ("", ""),
+            };
+            // `ldr r3, [pc, #8] @ (2c <main+0x2c>)`：从字面量池加载常量
+            if let (Some(Arg::Mem { base, .. }), Some(pool)) = (args.get(1), comment) {
+                if base == "PC" {
+                    let pool = pool.trim_start_matches('(').trim_end_matches(')');
+                    return zh_en(
+                        format!("从字面量池 {} 加载{}到 {}", pool, zh, v(0)?),
+                        format!("load {}{} from the literal pool at {}", en, v(0)?, pool),
+                    );
+                }
+            }
+            let text = zh_en(
+                format!("从 {} 加载{}到 {}", v(1)?, zh, v(0)?),
+                format!("load {}{} from {}", en, v(0)?, v(1)?),
+            )?;
+            Some(text + &writeback(args, 1, lang)?)
+        }
+        "str" | "strb" | "strh" => {
+            let (zh, en) = match mnemonic.base {
+                "strb" => (" (字节)", " (byte)"),
+                "strh" => (" (半字)", " (halfword)"),
+                _ => ("", ""),
+            };
+            let text = zh_en(
+                format!("将 {}{} 存储到 {}", v(0)?, zh, v(1)?),
+                format!("store {}{} to {}", v(0)?, en, v(1)?),
+            )?;
+            Some(text + &writeback(args, 1, lang)?)
+        }
+        "strex" => zh_en(
+            format!("将 {} 独占存储到 {}，结果写入 {} (0 为成功)", v(1)?, v(2)?, v(0)?),
+            format!("store {} exclusively to {}, status in {} (0 = success)", v(1)?, v(2)?, v(0)?),
+        ),
+        "ldrd" => {
+            let text = zh_en(
+                format!("从 {} 加载 {} 和 {}", v(2)?, v(0)?, v(1)?),
+                format!("load {} and {} from {}", v(0)?, v(1)?, v(2)?),
+            )?;
+            Some(text + &writeback(args, 2, lang)?)
+        }
+        "strd" => {
+            let text = zh_en(
+                format!("将 {} 和 {} 存储到 {}", v(0)?, v(1)?, v(2)?),
+                format!("store {} and {} to {}", v(0)?, v(1)?, v(2)?),
+            )?;
+            Some(text + &writeback(args, 2, lang)?)
+        }
+        "push" => zh_en(
+            format!("将 {} 压栈", v(0)?),
+            format!("push {} onto the stack", v(0)?),
+        ),
+        "pop" => describe_pop(args, lang),
+        "ldm" | "ldmia" | "ldmfd" | "ldmdb" | "stm" | "stmia" | "stmea" | "stmdb" | "stmfd" => {
+            let Some(Arg::Reg { name: base, writeback }) = args.first() else {
+                return None;
+            };
+            let mut text = if mnemonic.base.starts_with("ldm") {
+                zh_en(
+                    format!("从 {} 指向的内存依次加载 {}", base, v(1)?),
+                    format!("load {} from consecutive words at {}", v(1)?, base),
+                )?
+            } else {
+                zh_en(
+                    format!("将 {} 依次存储到 {} 指向的内存", v(1)?, base),
+                    format!("store {} to consecutive words at {}", v(1)?, base),
+                )?
+            };
+            if *writeback {
+                text.push_str(&match lang {
+                    Lang::Zh => format!("，并更新 {}", base),
+                    Lang::En => format!(", updating {}", base),
+                });
+            }
+            Some(text)
+        }
+        "b" => match mnemonic.condition {
+            Some(condition) => {
+                let (zh, en, _) = condition_text(condition);
+                let flags = condition_flags(condition, lang);
+                zh_en(
+                    format!("如果{}则跳转到 {} ({})", zh, v(0)?, flags),
+                    format!("branch to {} if {} ({})", v(0)?, en, flags),
+                )
+            }
+            None => zh_en(format!("跳转到 {}", v(0)?), format!("branch to {}", v(0)?)),
+        },
+        "bl" | "blx" => {
+            let call = match args.first()? {
+                Arg::Reg { name, .. } => zh_en(
+                    format!("调用寄存器 {} 中的地址 (保存返回地址)", name),
+                    format!("call the address in {} (saves the return address)", name),
+                )?,
+                Arg::Label(label) => {
+                    if let Some(note) = target_symbol(label).and_then(|symbol| crate::libc::call_note(symbol, lang)) {
+                        note
+                    } else {
+                        zh_en(
+                            format!("调用函数 {} (保存返回地址)", label),
+                            format!("call {} (saves the return address)", label),
+                        )?
+                    }
+                }
+                _ => return None,
+            };
+            Some(conditional_branch(call, mnemonic.condition, lang))
+        }
+        "bx" => {
+            let text = match args.first()? {
+                Arg::Reg { name, .. } if name == "LR" => lang.pick("函数返回 (跳转到 LR)", "return (branch to LR)").to_string(),
+                Arg::Reg { name, .. } => zh_en(
+                    format!("跳转到寄存器 {} 中的地址", name),
+                    format!("branch to the address in {}", name),
+                )?,
+                _ => return None,
+            };
+            Some(conditional_branch(text, mnemonic.condition, lang))
+        }
+        "cbz" => zh_en(
+            format!("如果 {} == 0 则跳转到 {}", v(0)?, v(1)?),
+            format!("if {} == 0 branch to {}", v(0)?, v(1)?),
+        ),
+        "cbnz" => zh_en(
+            format!("如果 {} ≠ 0 则跳转到 {}", v(0)?, v(1)?),
+            format!("if {} ≠ 0 branch to {}", v(0)?, v(1)?),
+        ),
+        "tbb" | "tbh" => {
+            let Some(Arg::Mem { offset: Some(offset), .. }) = args.first() else {
+                return None;
+            };
+            let index = offset.trim_start_matches("+ ").split_whitespace().next()?;
+            let (zh, en) = if mnemonic.base == "tbb" { ("字节", "byte") } else { ("半字", "halfword") };
+            zh_en(
+                format!("switch 跳转：以 {} 为下标查{}偏移表后跳转", index, zh),
+                format!("switch: branch through a {} offset table indexed by {}", en, index),
+            )
+        }
+        "adr" => zh_en(
+            format!("{} = {} 的地址", v(0)?, v(1)?),
+            format!("{} = address of {}", v(0)?, v(1)?),
+        ),
+        "uxtb" | "uxth" | "sxtb" | "sxth" => {
+            let (zh_width, en_width) = if mnemonic.base.ends_with('b') { ("8", "byte") } else { ("16", "halfword") };
+            let signed = mnemonic.base.starts_with('s');
+            zh_en(
+                format!("{} = {} 的低 {} 位{}", v(0)?, v(1)?, zh_width, if signed { "符号扩展" } else { "零扩展" }),
+                format!("{} = {} low {} of {}", v(0)?, if signed { "sign-extended" } else { "zero-extended" }, en_width, v(1)?),
+            )
+        }
+        "ubfx" | "sbfx" => zh_en(
+            format!("{} = {} 从第 {} 位起的 {} 位{}", v(0)?, v(1)?, bit(args, 2)?, bit(args, 3)?, if mnemonic.base == "sbfx" { " (符号扩展)" } else { "" }),
+            format!("{} = {} bits of {} starting at bit {}{}", v(0)?, bit(args, 3)?, v(1)?, bit(args, 2)?, if mnemonic.base == "sbfx" { " (sign-extended)" } else { "" }),
+        ),
+        "bfi" => zh_en(
+            format!("将 {} 的低 {} 位插入 {} 的第 {} 位", v(1)?, bit(args, 3)?, v(0)?, bit(args, 2)?),
+            format!("insert the low {} bits of {} into {} at bit {}", bit(args, 3)?, v(1)?, v(0)?, bit(args, 2)?),
+        ),
+        "bfc" => zh_en(
+            format!("将 {} 从第 {} 位起的 {} 位清零", v(0)?, bit(args, 1)?, bit(args, 2)?),
+            format!("clear {} bits of {} starting at bit {}", bit(args, 2)?, v(0)?, bit(args, 1)?),
+        ),
+        "clz" => zh_en(
+            format!("{} = {} 的前导零个数", v(0)?, v(1)?),
+            format!("{} = count leading zeros of {}", v(0)?, v(1)?),
+        ),
+        "rev" => zh_en(
+            format!("{} = {} 字节序反转", v(0)?, v(1)?),
+            format!("{} = byte-reversed {}", v(0)?, v(1)?),
+        ),
+        "nop" => Some(lang.pick("空操作", "no operation").to_string()),
+        "svc" => zh_en(format!("系统调用 (svc {})", v(0)?), format!("system call (svc {})", v(0)?)),
+        "bkpt" => Some(lang.pick("断点", "breakpoint").to_string()),
+        "dmb" => Some(lang.pick("数据内存屏障", "data memory barrier").to_string()),
+        "dsb" => Some(lang.pick("数据同步屏障", "data synchronization barrier").to_string()),
+        "isb" => Some(lang.pick("指令同步屏障", "instruction synchronization barrier").to_string()),
+        _ => None,
+    }
+}
+
+/// 位域操作的位号/位数（十进制）
+fn bit(args: &[Arg], i: usize) -> Option<String> {
+    match args.get(i)? {
+        Arg::Imm(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// 带条件的调用与寄存器跳转：在解释前加上条件
+fn conditional_branch(text: String, condition: Option<Condition>, lang: Lang) -> String {
+    match condition {
+        Some(condition) => match lang {
+            Lang::Zh => format!("若{}：{}", condition_phrase(condition, lang), text),
+            Lang::En => format!("if {}: {}", condition_phrase(condition, lang), text),
+        },
+        None => text,
+    }
+}
+
+/// 访存指令的基址回写说明：前变址 `[r3, #4]!`，后变址 `[r3], #4`
+fn writeback(args: &[Arg], mem: usize, lang: Lang) -> Option<String> {
+    let Arg::Mem { base, offset, writeback } = args.get(mem)? else {
+        return None;
+    };
+    if *writeback {
+        let offset = offset.as_deref()?;
+        return Some(match lang {
+            Lang::Zh => format!("（先更新 {} = {} {}）", base, base, offset),
+            Lang::En => format!(" ({} = {} {} first)", base, base, offset),
+        });
+    }
+    if args.len() > mem + 1 {
+        let step = match args.get(mem + 1)? {
+            Arg::Imm(value) if *value < 0 => format!("- 0x{:x}", -value),
+            Arg::Imm(value) => format!("+ 0x{:x}", value),
+            Arg::Reg { name, .. } => format!("+ {}", name),
+            _ => return None,
+        };
+        return Some(match lang {
+            Lang::Zh => format!("（之后 {} = {} {}）", base, base, step),
+            Lang::En => format!(" (then {} = {} {})", base, base, step),
+        });
+    }
+    Some(String::new())
+}
+
+/// `pop {r4, r7, pc}`：弹出 PC 即函数返回
+fn describe_pop(args: &[Arg], lang: Lang) -> Option<String> {
+    let Arg::List(registers) = args.first()? else {
+        return None;
+    };
+    let (pc, rest): (Vec<&String>, Vec<&String>) = registers.iter().partition(|r| r.as_str() == "PC");
+    let rest: Vec<&str> = rest.iter().map(|r| r.as_str()).collect();
+    Some(match (pc.is_empty(), lang) {
+        (true, Lang::Zh) => format!("从栈弹出 {}", rest.join(", ")),
+        (true, Lang::En) => format!("pop {} from the stack", rest.join(", ")),
+        (false, Lang::Zh) if rest.is_empty() => "从栈弹出 PC，函数返回".to_string(),
+        (false, Lang::En) if rest.is_empty() => "pop PC from the stack and return".to_string(),
+        (false, Lang::Zh) => format!("从栈弹出 {}，并弹出 PC 返回", rest.join(", ")),
+        (false, Lang::En) => format!("pop {} from the stack and return (pops PC)", rest.join(", ")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mnemonic() {
+        let parse = |text| Mnemonic::parse(text).unwrap();
+        assert_eq!(parse("addne"), Mnemonic { base: "add", sets_flags: false, condition: Some(Condition::NE) });
+        assert_eq!(parse("movs"), Mnemonic { base: "mov", sets_flags: true, condition: None });
+        assert_eq!(parse("bls"), Mnemonic { base: "b", sets_flags: false, condition: Some(Condition::LS) });
+        assert_eq!(parse("ldrhi"), Mnemonic { base: "ldr", sets_flags: false, condition: Some(Condition::HI) });
+        assert_eq!(parse("ldrsh.w").base, "ldrsh");
+        assert_eq!(parse("b.n"), Mnemonic { base: "b", sets_flags: false, condition: None });
+        assert!(Mnemonic::parse("ret").is_none());
+        assert!(Mnemonic::parse("bls.x").is_none());
+        assert_eq!(register_index("r12"), Some(12));
+        assert_eq!(register_index("fp"), Some(11));
+        assert_eq!(register_index("x0"), None);
+    }
+
+    #[test]
+    fn test_describe() {
+        let zh = |asm| describe(asm, Lang::Zh).unwrap();
+        assert_eq!(zh("push\t{r7, lr}"), "将 R7, LR 压栈");
+        assert_eq!(zh("add\tr7, sp, #0"), "R7 = SP + 0x0");
+        assert_eq!(zh("adds\tr3, #1"), "R3 = R3 + 0x1 (设置标志位)");
+        assert_eq!(zh("addne\tr0, r0, r1, lsl #2"), "若不相等 (Z=0)：R0 = R0 + R1 << 2");
+        assert_eq!(zh("cmp\tr3, #9"), "比较 R3 和 0x9 (设置标志位)");
+        assert_eq!(zh("ble.n\t1c <main+0x1c>"), "如果有符号小于等于则跳转到 1c <main+0x1c> (Z=1或N≠V)");
+        assert_eq!(zh("ldr\tr3, [r7, #4]"), "从 (R7 + 0x4) 加载到 R3");
+        assert_eq!(zh("str.w\tr2, [r3], #4"), "将 R2 存储到 (R3)（之后 R3 = R3 + 0x4）");
+        assert_eq!(zh("ldr\tr3, [pc, #8]\t@ (2c <main+0x2c>)"), "从字面量池 2c <main+0x2c> 加载到 R3");
+        assert_eq!(zh("bl\t0 <printf>"), "调用标准库函数 printf：格式化输出");
+        assert_eq!(zh("bx\tlr"), "函数返回 (跳转到 LR)");
+        assert_eq!(zh("pop\t{r4, r7, pc}"), "从栈弹出 R4, R7，并弹出 PC 返回");
+        assert_eq!(zh("ite\tgt"), "IT 块：随后 1 条指令在有符号大于 (Z=0且N=V)时执行，1 条在条件不成立时执行");
+        assert_eq!(describe("movt\tr3, #0", Lang::En).unwrap(), "R3[31:16] = 0x0 (low half kept)");
+        assert_eq!(describe("bhi\t40 <f+0x40>", Lang::En).unwrap(), "branch to 40 <f+0x40> if unsigned higher (C=1 and Z=0)");
+        // AArch64 指令不被误认
+        assert!(describe("ldr\tw0, [x1, w0, uxtw #2]", Lang::Zh).is_none());
+        assert!(describe("mov\tx0, x1", Lang::Zh).is_none());
+    }
+}
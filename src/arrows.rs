@@ -0,0 +1,158 @@
+//! 跳转箭头
+//!
+//! 类似 `objdump --visualize-jumps`：在指令左侧的栏中用制表符画出函数内跳转与目标之间的箭头，
+//! 循环一眼就能看出来。跨度短的跳转在内侧（靠近指令），互相重叠的跳转分到不同的列。
+
+use crate::objdump::DumpEntry;
+
+/// 一条函数内跳转（按条目下标）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Arrow {
+    from: usize,
+    to: usize,
+    /// 所在的列（0 为最内侧）
+    lane: usize,
+}
+
+impl Arrow {
+    fn top(&self) -> usize {
+        self.from.min(self.to)
+    }
+
+    fn bottom(&self) -> usize {
+        self.from.max(self.to)
+    }
+}
+
+/// 函数内跳转的箭头布局，按条目下标取每行左侧的箭头栏
+#[derive(Debug, Clone)]
+pub struct JumpArrows {
+    arrows: Vec<Arrow>,
+    lanes: usize,
+}
+
+impl JumpArrows {
+    /// 从函数的条目中找出目标在函数内的跳转并分配列
+    pub fn new(entries: &[DumpEntry]) -> Self {
+        // 目标可能是无法解析的指令，按条目的地址查找
+        let row_of = |address: u64| {
+            entries
+                .iter()
+                .position(|e| !e.asm_instruction.is_empty() && u64::from_str_radix(&e.address, 16).ok() == Some(address))
+        };
+        let mut arrows: Vec<Arrow> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(from, e)| {
+                let inst = e.parsed_instruction.as_ref().filter(|inst| crate::analysis::is_branch(inst))?;
+                let to = row_of(crate::analysis::branch_target(inst)?)?;
+                (to != from).then_some(Arrow { from, to, lane: 0 })
+            })
+            .collect();
+
+        // 先放跨度短的跳转，每条放在与已放置的跳转不重叠的最内侧一列
+        arrows.sort_by_key(|a| (a.bottom() - a.top(), a.top()));
+        let mut lanes = 0;
+        for i in 0..arrows.len() {
+            let (placed, rest) = arrows.split_at_mut(i);
+            let arrow = &mut rest[0];
+            arrow.lane = (0..)
+                .find(|lane| {
+                    !placed
+                        .iter()
+                        .any(|p| p.lane == *lane && p.top() <= arrow.bottom() && arrow.top() <= p.bottom())
+                })
+                .unwrap_or(0);
+            lanes = lanes.max(arrow.lane + 1);
+        }
+        Self { arrows, lanes }
+    }
+
+    /// 函数中没有函数内跳转
+    pub fn is_empty(&self) -> bool {
+        self.arrows.is_empty()
+    }
+
+    /// 箭头栏的宽度（字符数）
+    pub fn width(&self) -> usize {
+        if self.lanes == 0 { 0 } else { self.lanes * 2 + 1 }
+    }
+
+    /// 第 row 个条目左侧的箭头栏：跳转和目标所在行画出拐角和横线，目标处以 `>` 结尾
+    pub fn row(&self, row: usize) -> String {
+        let mut cells = self.verticals(|a| a.top() < row && row < a.bottom());
+        let width = cells.len();
+        let ends: Vec<&Arrow> = self.arrows.iter().filter(|a| a.top() == row || a.bottom() == row).collect();
+        for arrow in &ends {
+            cells[self.column(arrow.lane)] = if arrow.top() == row { '┌' } else { '└' };
+        }
+        // 横线从拐角延伸到指令，穿过内侧的列
+        for arrow in &ends {
+            for cell in &mut cells[self.column(arrow.lane) + 1..width - 1] {
+                *cell = match *cell {
+                    ' ' | '─' => '─',
+                    '│' => '┼',
+                    '┌' => '┬',
+                    '└' => '┴',
+                    other => other,
+                };
+            }
+        }
+        if let Some(last) = cells.last_mut() {
+            *last = if ends.iter().any(|a| a.to == row) {
+                '>'
+            } else if ends.is_empty() {
+                ' '
+            } else {
+                '─'
+            };
+        }
+        cells.into_iter().collect()
+    }
+
+    /// 插在第 row 个条目之前的附加行（C 代码等）的箭头栏：只画穿过这里的竖线
+    pub fn between(&self, row: usize) -> String {
+        self.verticals(|a| a.top() < row && row <= a.bottom()).into_iter().collect()
+    }
+
+    fn verticals(&self, through: impl Fn(&Arrow) -> bool) -> Vec<char> {
+        let mut cells = vec![' '; self.width()];
+        for arrow in self.arrows.iter().filter(|a| through(a)) {
+            cells[self.column(arrow.lane)] = '│';
+        }
+        cells
+    }
+
+    /// 列在箭头栏中的位置（外侧的列在左）
+    fn column(&self, lane: usize) -> usize {
+        (self.lanes - 1 - lane) * 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jump_arrows() {
+        let dump = r#"
+0000000000000000 <f>:
+   0:   b4000080    cbz x0, 10 <f+0x10>
+   4:   f1000400    subs x0, x0, #0x1
+   8:   54ffffe1    b.ne 4 <f+0x4>
+   c:   d503201f    nop
+  10:   d65f03c0    ret
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string()).extract_function_data("f").unwrap();
+        let arrows = JumpArrows::new(&entries);
+        let rows: Vec<String> = (0..entries.len()).map(|i| arrows.row(i)).collect();
+        assert_eq!(arrows.width(), 5);
+        assert_eq!(rows, ["┌────", "│ ┌─>", "│ └──", "│    ", "└───>"]);
+        assert_eq!(arrows.between(2), "│ │  ");
+        assert_eq!(arrows.between(4), "│    ");
+
+        let none = JumpArrows::new(&entries[3..]);
+        assert!(none.is_empty());
+        assert_eq!(none.row(0), "");
+    }
+}
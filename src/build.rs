@@ -0,0 +1,157 @@
+//! 从 C 源码编译并生成 dump 文件
+//!
+//! 以各优化级别（默认 O0/O1/O2）分别编译源文件，再对每个目标文件运行 objdump，
+//! 生成 `<前缀>_O0.dump` 等文件供对比分析使用，无需手写 Makefile。
+
+use crate::error::{Result, InterpreterError};
+use crate::levels::{self, DEFAULT_LEVELS};
+use crate::objdump::{ObjdumpParser, DEFAULT_OBJDUMP};
+use crate::progress::{Progress, ProgressSink};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 默认使用的编译器
+pub const DEFAULT_CC: &str = "gcc";
+
+/// 编译配置
+#[derive(Debug, Clone)]
+pub struct BuildConfig {
+    /// 编译器（如 aarch64-linux-gnu-gcc）
+    pub cc: String,
+    /// objdump 程序
+    pub objdump: String,
+    /// 额外的编译选项
+    pub cflags: Vec<String>,
+    /// 目标文件和 dump 文件的输出目录
+    pub out_dir: PathBuf,
+    /// 编译的优化级别
+    pub levels: Vec<String>,
+}
+
+impl BuildConfig {
+    pub fn new() -> Self {
+        Self {
+            cc: DEFAULT_CC.to_string(),
+            objdump: DEFAULT_OBJDUMP.to_string(),
+            cflags: Vec::new(),
+            out_dir: PathBuf::from("."),
+            levels: DEFAULT_LEVELS.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    /// 设置编译器
+    pub fn with_cc(mut self, cc: &str) -> Self {
+        self.cc = cc.to_string();
+        self
+    }
+
+    /// 设置 objdump 程序
+    pub fn with_objdump(mut self, objdump: &str) -> Self {
+        self.objdump = objdump.to_string();
+        self
+    }
+
+    /// 设置额外的编译选项
+    pub fn with_cflags(mut self, cflags: Vec<String>) -> Self {
+        self.cflags = cflags;
+        self
+    }
+
+    /// 设置输出目录
+    pub fn with_out_dir(mut self, out_dir: PathBuf) -> Self {
+        self.out_dir = out_dir;
+        self
+    }
+
+    /// 设置编译的优化级别（如 O0, O2, Os）
+    pub fn with_levels(mut self, levels: Vec<String>) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    /// 源文件对应的 dump 文件前缀（输出目录 + 文件名去掉扩展名）
+    pub fn dump_prefix(&self, source: &Path) -> Result<String> {
+        let stem = source
+            .file_stem()
+            .ok_or_else(|| InterpreterError::ExecutionError(
+                format!("无效的源文件路径: {}", source.display())
+            ))?;
+        Ok(self.out_dir.join(stem).to_string_lossy().into_owned())
+    }
+
+    /// 以各个优化级别编译源文件并生成 dump 文件，返回 dump 文件前缀
+    ///
+    /// 每一步编译和反汇编都通知 `progress`
+    pub fn build_dumps(&self, source: &Path, progress: &dyn ProgressSink) -> Result<String> {
+        let prefix = self.dump_prefix(source)?;
+        std::fs::create_dir_all(&self.out_dir)?;
+
+        for level in &self.levels {
+            let object = format!("{}_{}.o", prefix, level);
+            let dump = levels::dump_path(&prefix, level);
+
+            progress.report(Progress::Compiling { source, level, object: &object });
+            self.compile(source, level, &object)?;
+
+            progress.report(Progress::Disassembling { object: &object, dump: &dump });
+            let content = ObjdumpParser::run_objdump(&object, &self.objdump)?;
+            std::fs::write(&dump, content)?;
+        }
+
+        Ok(prefix)
+    }
+
+    /// 以指定优化级别编译为目标文件（带调试信息，便于 objdump -S 交织源码）
+    fn compile(&self, source: &Path, level: &str, object: &str) -> Result<()> {
+        let output = Command::new(&self.cc)
+            .arg(format!("-{}", level))
+            .arg("-g")
+            .arg("-c")
+            .args(&self.cflags)
+            .arg(source)
+            .arg("-o")
+            .arg(object)
+            .output()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => InterpreterError::ExecutionError(format!(
+                    "找不到编译器 '{}'，请安装或通过 --cc 指定 (如 aarch64-linux-gnu-gcc)",
+                    self.cc
+                )),
+                _ => InterpreterError::IoError(e),
+            })?;
+
+        if !output.status.success() {
+            return Err(InterpreterError::ExecutionError(format!(
+                "{} -{} 编译失败:\n{}",
+                self.cc,
+                level,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_prefix_and_missing_compiler() {
+        let config = BuildConfig::new()
+            .with_cc("alaz-no-such-cc")
+            .with_out_dir(std::env::temp_dir().join("alaz-build-test"));
+
+        let prefix = config.dump_prefix(Path::new("src/matrix.c")).unwrap();
+        assert!(prefix.ends_with("matrix"));
+
+        let err = config.build_dumps(Path::new("matrix.c"), &crate::progress::Silent).unwrap_err();
+        assert!(err.to_string().contains("--cc"));
+    }
+}
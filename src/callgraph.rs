@@ -0,0 +1,263 @@
+//! 调用图生成
+//!
+//! 扫描 dump 中的所有函数，借助符号表记录 BL/B/BLR 的调用目标，输出 DOT 或 JSON 格式的调用图。
+//! 对比不同优化级别的调用图，可以看出编译器引入或内联掉了哪些辅助函数。
+//!
+//! JSON 格式带有 `schema` 和 `version` 字段，结构见仓库中的 `schemas/callgraph.schema.json`。
+//! 只增加可选字段时版本号不变；删除、重命名字段或改变含义时递增版本号。
+
+use crate::objdump::{ObjdumpParser, SymbolTable};
+use crate::error::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// JSON 导出的 schema 名称
+pub const JSON_SCHEMA: &str = "alaz.callgraph";
+
+/// JSON 导出的 schema 版本号
+pub const JSON_VERSION: u32 = 1;
+
+/// JSON Schema 文档，描述 [`CallGraph::to_json`] 的输出
+pub const JSON_SCHEMA_DOCUMENT: &str = include_str!("../schemas/callgraph.schema.json");
+
+/// 调用类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallKind {
+    /// BL 直接调用
+    Direct,
+    /// B 尾调用
+    Tail,
+    /// BLR 间接调用（被调用者为寄存器名）
+    Indirect,
+}
+
+/// 一条调用边
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct CallEdge {
+    /// 调用者
+    pub caller: String,
+    /// 被调用者（间接调用时为保存目标地址的寄存器）
+    pub callee: String,
+    /// 调用类型
+    pub kind: CallKind,
+    /// 调用指令的地址（同一对函数之间可能有多处调用）
+    pub sites: Vec<u64>,
+}
+
+/// 调用图
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// dump 中定义的函数
+    pub functions: Vec<String>,
+    /// 去重后的调用边
+    pub edges: Vec<CallEdge>,
+    /// 用于查找函数地址的符号表
+    symbols: SymbolTable,
+}
+
+/// JSON 导出的顶层结构
+#[derive(Serialize)]
+struct CallGraphDocument<'a> {
+    schema: &'static str,
+    version: u32,
+    nodes: Vec<NodeDocument<'a>>,
+    edges: Vec<EdgeDocument<'a>>,
+}
+
+/// JSON 导出中的函数节点
+#[derive(Serialize)]
+struct NodeDocument<'a> {
+    name: &'a str,
+    /// 入口地址（十六进制字符串，避免超出 JavaScript 的安全整数范围）
+    address: Option<String>,
+    /// 被调用但未在 dump 中定义
+    external: bool,
+}
+
+/// JSON 导出中的调用边
+#[derive(Serialize)]
+struct EdgeDocument<'a> {
+    caller: &'a str,
+    callee: &'a str,
+    kind: CallKind,
+    sites: Vec<String>,
+}
+
+impl CallGraph {
+    /// 扫描 dump 中的所有函数构建调用图
+    pub fn build(parser: &ObjdumpParser) -> Result<Self> {
+        let functions = parser.list_functions()?;
+        let mut edges: BTreeMap<(String, String, CallKind), Vec<u64>> = BTreeMap::new();
+
+        for caller in &functions {
+            // PLT 桩等无法提取的函数直接跳过
+            let Ok(entries) = parser.extract_function_data(caller) else {
+                continue;
+            };
+
+            for entry in &entries {
+                let mut parts = entry.asm_instruction.split_whitespace();
+                let edge = match parts.next() {
+                    Some(mnemonic @ ("bl" | "b")) => entry.call_target().map(|callee| {
+                        let kind = if mnemonic == "bl" { CallKind::Direct } else { CallKind::Tail };
+                        (callee, kind)
+                    }),
+                    Some("blr") => parts.next().map(|reg| (reg.to_string(), CallKind::Indirect)),
+                    _ => None,
+                };
+
+                if let Some((callee, kind)) = edge {
+                    // 跳回自身入口的 B 是循环，不是调用
+                    if kind == CallKind::Tail && &callee == caller {
+                        continue;
+                    }
+                    let site = u64::from_str_radix(&entry.address, 16).unwrap_or(0);
+                    edges.entry((caller.clone(), callee, kind)).or_default().push(site);
+                }
+            }
+        }
+
+        let edges = edges
+            .into_iter()
+            .map(|((caller, callee, kind), sites)| CallEdge { caller, callee, kind, sites })
+            .collect();
+        Ok(Self { functions, edges, symbols: parser.symbols().clone() })
+    }
+
+    /// 被调用但未在 dump 中定义的函数（如 `printf@plt`）
+    pub fn external_functions(&self) -> BTreeSet<&str> {
+        self.edges
+            .iter()
+            .filter(|e| e.kind != CallKind::Indirect)
+            .map(|e| e.callee.as_str())
+            .filter(|callee| !self.functions.iter().any(|f| f == callee))
+            .collect()
+    }
+
+    /// 输出 Graphviz DOT 格式
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph callgraph {\n");
+        dot.push_str("    node [shape=box];\n");
+
+        for function in &self.functions {
+            dot.push_str(&format!("    \"{}\";\n", function));
+        }
+        for external in self.external_functions() {
+            dot.push_str(&format!("    \"{}\" [shape=ellipse, style=dashed];\n", external));
+        }
+        if self.edges.iter().any(|e| e.kind == CallKind::Indirect) {
+            dot.push_str("    \"(间接调用)\" [shape=diamond];\n");
+        }
+
+        for edge in &self.edges {
+            let line = match edge.kind {
+                CallKind::Direct => format!("    \"{}\" -> \"{}\";\n", edge.caller, edge.callee),
+                CallKind::Tail => format!(
+                    "    \"{}\" -> \"{}\" [style=dashed, label=\"tail\"];\n",
+                    edge.caller, edge.callee
+                ),
+                CallKind::Indirect => format!(
+                    "    \"{}\" -> \"(间接调用)\" [style=dotted, label=\"blr {}\"];\n",
+                    edge.caller, edge.callee
+                ),
+            };
+            dot.push_str(&line);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// 输出 JSON 格式（间接调用的被调用者是寄存器，不作为节点）
+    pub fn to_json(&self) -> String {
+        let address = |name: &str| self.symbols.address_of(name).map(|a| format!("0x{:x}", a));
+        let defined = self.functions.iter().map(|name| (name.as_str(), false));
+        let external = self.external_functions().into_iter().map(|name| (name, true));
+        let document = CallGraphDocument {
+            schema: JSON_SCHEMA,
+            version: JSON_VERSION,
+            nodes: defined
+                .chain(external)
+                .map(|(name, external)| NodeDocument { name, address: address(name), external })
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .map(|edge| EdgeDocument {
+                    caller: &edge.caller,
+                    callee: &edge.callee,
+                    kind: edge.kind,
+                    sites: edge.sites.iter().map(|site| format!("0x{:x}", site)).collect(),
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&document).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"
+0000000000400560 <printf@plt>:
+  400560:   90000090    adrp x16, 410000
+
+0000000000400600 <helper>:
+  400600:   d65f03c0    ret
+
+0000000000400610 <main>:
+  400610:   94000000    bl 400600 <helper>
+  400614:   94000000    bl 400560
+  400618:   d63f0100    blr x8
+  40061c:   17fffff9    b 400600 <helper>
+"#;
+
+    #[test]
+    fn test_build_callgraph() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let graph = CallGraph::build(&parser).unwrap();
+
+        let edges: Vec<(&str, &str, CallKind)> = graph
+            .edges
+            .iter()
+            .map(|e| (e.caller.as_str(), e.callee.as_str(), e.kind))
+            .collect();
+        assert!(edges.contains(&("main", "helper", CallKind::Direct)));
+        assert!(edges.contains(&("main", "helper", CallKind::Tail)));
+        assert!(edges.contains(&("main", "printf@plt", CallKind::Direct)));
+        assert!(edges.contains(&("main", "x8", CallKind::Indirect)));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"main\" -> \"helper\";"));
+    }
+
+    #[test]
+    fn test_json_matches_schema() {
+        let graph = CallGraph::build(&ObjdumpParser::new(DUMP.to_string())).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+        assert_eq!(json["schema"], JSON_SCHEMA);
+        assert_eq!(json["version"], JSON_VERSION);
+        assert_eq!(json["nodes"][0], serde_json::json!({ "name": "printf@plt", "address": "0x400560", "external": false }));
+        let tail = json["edges"].as_array().unwrap().iter().find(|e| e["kind"] == "tail").unwrap();
+        assert_eq!(tail["sites"], serde_json::json!(["0x40061c"]));
+
+        // 输出中的字段与 schema 声明的必需字段一致
+        let schema: serde_json::Value = serde_json::from_str(JSON_SCHEMA_DOCUMENT).unwrap();
+        let keys = |value: &serde_json::Value| {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        let required = |path: &str| {
+            let mut keys: Vec<String> = serde_json::from_value(schema.pointer(path).unwrap().clone()).unwrap();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys(&json), required("/required"));
+        assert_eq!(keys(&json["nodes"][0]), required("/$defs/node/required"));
+        assert_eq!(keys(&json["edges"][0]), required("/$defs/edge/required"));
+        assert_eq!(schema.pointer("/properties/version/const").unwrap(), JSON_VERSION);
+    }
+}
@@ -0,0 +1,168 @@
+//! Shell 补全中的函数名补全
+//!
+//! clap_complete 生成的脚本只补全子命令和选项。这里在脚本中加入钩子，在函数名参数处
+//! 调用 `alaz list-functions --plain` 列出当前目录中 dump 文件里的函数。
+//! Bash 和 Fish 的钩子追加在脚本末尾（重新注册补全函数），Zsh 需要改写参数规格中的补全动作；
+//! 测试按当前版本的 clap_complete 输出确认钩子确实生效。
+
+use clap::Command;
+use clap_complete::Shell;
+
+/// 各子命令中函数名参数是第几个位置参数（从 1 开始），隐藏的子命令除外
+pub fn function_positions(cmd: &Command) -> Vec<(String, usize)> {
+    cmd.get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .filter_map(|sub| {
+            let index = sub.get_positionals().position(|arg| matches!(arg.get_id().as_str(), "function" | "functions"))?;
+            Some((sub.get_name().to_string(), index + 1))
+        })
+        .collect()
+}
+
+/// 在 clap_complete 生成的补全脚本中加入函数名补全，不支持的 shell 原样返回
+pub fn with_function_completion(shell: Shell, bin_name: &str, script: String, positions: &[(String, usize)]) -> String {
+    match shell {
+        Shell::Bash => {
+            let cases: String = positions
+                .iter()
+                .map(|(name, position)| format!("        {}) position={} ;;\n", name, position))
+                .collect();
+            // 在脚本末尾重新注册补全函数，覆盖 clap_complete 注册的 _{bin}
+            format!(
+                r#"{script}
+# 函数名补全：在函数名参数处列出当前目录中 dump 文件里的函数
+_{bin}_function_names() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" position i count=0
+    case "${{COMP_WORDS[1]}}" in
+{cases}        *) return 1 ;;
+    esac
+    [[ "$cur" == -* ]] && return 1
+    for ((i = 2; i < COMP_CWORD; i++)); do
+        [[ "${{COMP_WORDS[i]}}" != -* ]] && ((count++))
+    done
+    ((count + 1 == position)) || return 1
+    COMPREPLY=( $(compgen -W "$({bin} list-functions --plain 2>/dev/null)" -- "$cur") )
+}}
+
+_{bin}_with_functions() {{
+    _{bin}_function_names || _{bin} "$@"
+}}
+
+if [[ "${{BASH_VERSINFO[0]}}" -eq 4 && "${{BASH_VERSINFO[1]}}" -ge 4 || "${{BASH_VERSINFO[0]}}" -gt 4 ]]; then
+    complete -F _{bin}_with_functions -o nosort -o bashdefault -o default {bin}
+else
+    complete -F _{bin}_with_functions -o bashdefault -o default {bin}
+fi
+"#,
+                script = script.trim_end(),
+                bin = bin_name,
+                cases = cases
+            )
+        }
+        Shell::Zsh => {
+            // 函数名参数的规格形如 ':function -- 说明:_default' 或 '*::functions -- 说明:_default'
+            let spec = regex::Regex::new(r"(?m)^('(?:\*)?::?functions? -- .*):_default'").unwrap();
+            let script = spec.replace_all(&script, format!("$1:_{}_function_names'", bin_name)).into_owned();
+            let hook = format!(
+                r#"(( $+functions[_{bin}_function_names] )) ||
+_{bin}_function_names() {{
+    local -a functions
+    functions=(${{(f)"$({bin} list-functions --plain 2>/dev/null)"}})
+    compadd -a functions
+}}
+"#,
+                bin = bin_name
+            );
+            // 紧跟在 #compdef 行之后定义，保证在脚本末尾调用补全函数之前就已存在
+            match script.split_once('\n') {
+                Some((compdef, rest)) => format!("{}\n\n{}{}", compdef, hook, rest),
+                None => hook + &script,
+            }
+        }
+        Shell::Fish => {
+            let mut script = script;
+            script.push_str("\n# 函数名补全：在函数名参数处列出当前目录中 dump 文件里的函数\n");
+            for (name, position) in positions {
+                script.push_str(&format!(
+                    "complete -c {bin} -n \"__fish_{bin}_using_subcommand {}; and __fish_is_nth_token {}\" -f -a \"({bin} list-functions --plain 2>/dev/null)\"\n",
+                    name,
+                    position + 1,
+                    bin = bin_name
+                ));
+            }
+            script
+        }
+        _ => script,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ArgAction};
+
+    fn command() -> Command {
+        Command::new("alaz")
+            .subcommand(
+                Command::new("analyze")
+                    .arg(Arg::new("function").help("函数名称").required(true))
+                    .arg(Arg::new("prefix").help("dump 文件前缀")),
+            )
+            .subcommand(
+                Command::new("xref-reg")
+                    .arg(Arg::new("register").help("寄存器").required(true))
+                    .arg(Arg::new("function").help("函数名称")),
+            )
+            .subcommand(Command::new("annotate").arg(Arg::new("functions").help("要导出的函数").num_args(0..).action(ArgAction::Append)))
+            .subcommand(Command::new("list-functions").hide(true).arg(Arg::new("function")))
+    }
+
+    fn script(shell: Shell) -> String {
+        let mut cmd = command();
+        let positions = function_positions(&cmd);
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut cmd, "alaz", &mut script);
+        with_function_completion(shell, "alaz", String::from_utf8(script).unwrap(), &positions)
+    }
+
+    #[test]
+    fn test_function_positions() {
+        let positions = function_positions(&command());
+        let expected = [("analyze", 1), ("xref-reg", 2), ("annotate", 1)].map(|(name, i)| (name.to_string(), i));
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn test_bash_hook() {
+        let script = script(Shell::Bash);
+        assert!(script.contains("        analyze) position=1 ;;\n"));
+        assert!(script.contains("        xref-reg) position=2 ;;\n"));
+        // 最后一次注册的补全函数生效
+        let last = script.rfind("complete -F ").unwrap();
+        assert!(script[last..].starts_with("complete -F _alaz_with_functions "));
+        assert!(script.contains("_alaz() {"));
+    }
+
+    #[test]
+    fn test_zsh_hook() {
+        let script = script(Shell::Zsh);
+        assert!(script.starts_with("#compdef alaz\n"));
+        assert!(script.contains("_alaz_function_names() {"));
+        assert!(script.contains("':function -- 函数名称:_alaz_function_names'"));
+        assert!(script.contains("'::function -- 函数名称:_alaz_function_names'"));
+        assert!(script.contains("'*::functions -- 要导出的函数:_alaz_function_names'"));
+        // 非函数名参数不受影响
+        assert!(script.contains("'::prefix -- dump 文件前缀:_default'"));
+    }
+
+    #[test]
+    fn test_fish_hook() {
+        let script = script(Shell::Fish);
+        // clap_complete 定义了钩子依赖的子命令判断函数
+        assert!(script.contains("function __fish_alaz_using_subcommand"));
+        assert!(script.contains(
+            "complete -c alaz -n \"__fish_alaz_using_subcommand xref-reg; and __fish_is_nth_token 3\" -f -a \"(alaz list-functions --plain 2>/dev/null)\"\n"
+        ));
+        assert!(!script.contains("__fish_alaz_using_subcommand list-functions; and"));
+    }
+}
@@ -0,0 +1,121 @@
+//! 配置文件
+//!
+//! 启动时读取 `~/.config/alaz/config.toml`（设置了 `XDG_CONFIG_HOME` 时为 `$XDG_CONFIG_HOME/alaz/config.toml`，
+//! 或由 `--config` 指定），为各子命令提供输出目录、语言、objdump/编译器路径、优化级别、
+//! 表格列和颜色的默认值。命令行中显式指定的参数优先于配置文件，团队可以共享同一份配置。
+//!
+//! 文件格式示例:
+//!
+//! ```toml
+//! output_dir = "./reports"
+//! lang = "en"
+//! objdump = "aarch64-linux-gnu-objdump"
+//! cc = "aarch64-linux-gnu-gcc"
+//! levels = ["O0", "O2", "O3"]
+//! columns = ["address", "asm", "semantics"]
+//! color = "never"
+//! ```
+
+use crate::locale::Lang;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// 终端颜色设置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// 由环境决定（`NO_COLOR`、`CLICOLOR` 等）
+    #[default]
+    Auto,
+    /// 总是输出颜色
+    Always,
+    /// 不输出颜色
+    Never,
+}
+
+/// 配置文件中的默认设置，未设置的项为 None 或空
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// 报告的输出目录（analyze、interactive、build、elf 的 `-o`）
+    pub output_dir: Option<PathBuf>,
+    /// 报告和语义解释的语言（zh、en）
+    pub lang: Option<String>,
+    /// objdump 程序名或路径
+    pub objdump: Option<String>,
+    /// 编译器（build 子命令）
+    pub cc: Option<String>,
+    /// 对比的优化级别（代替自动发现）
+    pub levels: Vec<String>,
+    /// 指令表格的列
+    pub columns: Vec<String>,
+    /// 终端颜色
+    pub color: ColorMode,
+}
+
+impl Config {
+    /// 从 TOML 字符串解析并检查取值
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let config: Config = toml::from_str(content).context("配置文件格式错误")?;
+        if let Some(ref lang) = config.lang {
+            Lang::parse(lang)?;
+        }
+        Ok(config)
+    }
+
+    /// 从文件加载
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&content).context(format!("配置文件 {} 无效", path.display()))
+    }
+
+    /// 默认的配置文件路径：`$XDG_CONFIG_HOME/alaz/config.toml` 或 `~/.config/alaz/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        let dir = match std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME").filter(|home| !home.is_empty())?).join(".config"),
+        };
+        Some(dir.join("alaz").join("config.toml"))
+    }
+
+    /// 加载配置：指定了路径时该文件必须存在，否则读取默认路径（不存在时使用空配置）
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::load_from_file(path),
+            None => match Self::default_path().filter(|path| path.is_file()) {
+                Some(path) => Self::load_from_file(&path),
+                None => Ok(Self::default()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::from_toml(
+            r#"
+output_dir = "./reports"
+lang = "en"
+levels = ["O0", "O3"]
+color = "never"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.output_dir, Some(PathBuf::from("./reports")));
+        assert_eq!(config.lang.as_deref(), Some("en"));
+        assert_eq!(config.levels, ["O0", "O3"]);
+        assert_eq!(config.color, ColorMode::Never);
+        assert_eq!(config.objdump, None);
+        assert!(config.columns.is_empty());
+
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+        assert!(Config::from_toml("lang = \"fr\"").is_err());
+        assert!(Config::from_toml("colour = \"never\"").is_err());
+    }
+}
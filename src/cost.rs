@@ -0,0 +1,375 @@
+//! 指令代价模型
+//!
+//! 从 `aarch64_latency.json` 加载所选核心（Cortex-A53/A72、Neoverse-N1）各类指令的
+//! 延迟和吞吐量，为每条指令标注代价，并估计函数的周期数。数据按指令类别给出，
+//! 只是近似值，用于比较不同优化级别的相对开销，而不是预测真实的运行时间。
+//!
+//! [`CostModel::function_cost`] 把函数划分为基本块，逐块累加代价，循环中的块按假定的
+//! 迭代次数加权（嵌套循环逐层相乘），得到粗略的静态周期数。
+
+use crate::analysis;
+use crate::error::{InterpreterError, Result};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 嵌入的代价数据
+const LATENCY_JSON: &str = include_str!("../aarch64_latency.json");
+
+/// 核心模型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Core {
+    /// 顺序双发射的小核
+    CortexA53,
+    /// 乱序三发射的大核
+    #[default]
+    CortexA72,
+    /// 服务器核心
+    NeoverseN1,
+}
+
+impl Core {
+    /// 所有核心模型
+    pub const ALL: [Core; 3] = [Core::CortexA53, Core::CortexA72, Core::NeoverseN1];
+
+    /// 解析核心名称（a53, a72, n1，也接受 cortex-a53 等全称）
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "a53" | "cortex-a53" => Ok(Core::CortexA53),
+            "a72" | "cortex-a72" => Ok(Core::CortexA72),
+            "n1" | "neoverse-n1" => Ok(Core::NeoverseN1),
+            _ => Err(InterpreterError::ParseError(format!(
+                "不支持的核心模型: {} (可选: a53, a72, n1)",
+                text
+            ))),
+        }
+    }
+
+    /// JSON 中的键
+    fn key(self) -> &'static str {
+        match self {
+            Core::CortexA53 => "cortex-a53",
+            Core::CortexA72 => "cortex-a72",
+            Core::NeoverseN1 => "neoverse-n1",
+        }
+    }
+}
+
+/// 一条指令的代价
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct InstructionCost {
+    /// 结果可用前的周期数
+    pub latency: f64,
+    /// 每周期可发射的条数
+    pub throughput: f64,
+}
+
+impl InstructionCost {
+    /// 发射占用的周期数（吞吐量的倒数）
+    pub fn issue_cycles(&self) -> f64 {
+        1.0 / self.throughput
+    }
+}
+
+#[derive(Deserialize)]
+struct CoreData {
+    name: String,
+    default: InstructionCost,
+    costs: HashMap<String, InstructionCost>,
+}
+
+#[derive(Deserialize)]
+struct LatencyData {
+    classes: HashMap<String, Vec<String>>,
+    cores: HashMap<String, CoreData>,
+}
+
+/// 函数的周期估计
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CycleEstimate {
+    pub instructions: usize,
+    /// 延迟之和：每条指令都依赖上一条时的周期数（上限）
+    pub latency: f64,
+    /// 发射周期之和：指令之间没有依赖时的周期数（下限）
+    pub issue: f64,
+}
+
+/// 基本块的代价
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockCost {
+    /// 块首指令的地址
+    pub start: u64,
+    /// 块尾指令的地址
+    pub end: u64,
+    pub instructions: usize,
+    /// 所在循环的嵌套层数
+    pub loop_depth: usize,
+    /// 执行次数的权重（迭代次数的 `loop_depth` 次方）
+    pub weight: f64,
+    /// 执行一次的延迟之和
+    pub latency: f64,
+    /// 执行一次的发射周期之和
+    pub issue: f64,
+}
+
+/// 按基本块和循环加权的函数代价
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunctionCost {
+    pub blocks: Vec<BlockCost>,
+    pub loops: usize,
+    pub instructions: usize,
+    /// 加权的延迟之和
+    pub latency: f64,
+    /// 加权的发射周期之和
+    pub issue: f64,
+}
+
+/// 所选核心的代价表
+#[derive(Debug, Clone)]
+pub struct CostModel {
+    core: Core,
+    name: String,
+    /// 助记符 -> 类别
+    classes: HashMap<String, String>,
+    costs: HashMap<String, InstructionCost>,
+    default: InstructionCost,
+}
+
+impl CostModel {
+    /// 加载嵌入的代价数据中指定核心的代价表
+    pub fn new(core: Core) -> Self {
+        let mut data: LatencyData = serde_json::from_str(LATENCY_JSON).expect("aarch64_latency.json 格式错误");
+        let core_data = data.cores.remove(core.key()).expect("aarch64_latency.json 缺少核心");
+        let classes = data
+            .classes
+            .into_iter()
+            .flat_map(|(class, mnemonics)| mnemonics.into_iter().map(move |m| (m, class.clone())))
+            .collect();
+        Self {
+            core,
+            name: core_data.name,
+            classes,
+            costs: core_data.costs,
+            default: core_data.default,
+        }
+    }
+
+    pub fn core(&self) -> Core {
+        self.core
+    }
+
+    /// 核心的显示名称（如 `Cortex-A72`）
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 汇编指令所属的代价类别（如 `alu`、`alu_shift`、`load`、`simd`），未收录的助记符返回 None
+    pub fn class_of(&self, asm: &str) -> Option<&str> {
+        let asm = asm.split("//").next().unwrap_or("").trim();
+        let (mnemonic, operands) = asm.split_once(char::is_whitespace).unwrap_or((asm, ""));
+        let mnemonic = mnemonic.to_lowercase();
+        let operands: Vec<&str> = operands.split(',').map(str::trim).collect();
+        // 向量寄存器（v0.4s、v1.16b）
+        if operands.iter().any(|op| op.starts_with('v') && op.contains('.')) {
+            return Some("simd");
+        }
+        let key = if mnemonic.starts_with("b.") { "b.cond" } else { mnemonic.as_str() };
+        let class = self.classes.get(key)?.as_str();
+        // 第二操作数是移位或扩展后的寄存器
+        let shifted = operands.len() >= 2 && {
+            let last = operands[operands.len() - 1];
+            let previous = operands[operands.len() - 2];
+            ["lsl", "lsr", "asr", "ror", "sxt", "uxt"].iter().any(|s| last.starts_with(s)) && !previous.starts_with('#')
+        };
+        Some(if class == "alu" && shifted { "alu_shift" } else { class })
+    }
+
+    /// 汇编指令的代价（未收录的指令使用核心的默认代价）
+    pub fn cost_of(&self, asm: &str) -> InstructionCost {
+        self.class_of(asm).and_then(|class| self.costs.get(class)).copied().unwrap_or(self.default)
+    }
+
+    /// 条目的代价（提示行返回 None）
+    pub fn entry_cost(&self, entry: &DumpEntry) -> Option<InstructionCost> {
+        (!entry.asm_instruction.is_empty()).then(|| self.cost_of(&entry.asm_instruction))
+    }
+
+    /// 按指令顺序累加的周期估计（不考虑循环和分支）
+    pub fn estimate(&self, entries: &[DumpEntry]) -> CycleEstimate {
+        entries.iter().filter_map(|e| self.entry_cost(e)).fold(CycleEstimate::default(), |total, cost| CycleEstimate {
+            instructions: total.instructions + 1,
+            latency: total.latency + cost.latency,
+            issue: total.issue + cost.issue_cycles(),
+        })
+    }
+
+    /// 按基本块累加代价，循环内的块按 `trip_count` 次迭代加权
+    ///
+    /// 只统计能解析的指令；循环由向后跳转识别，嵌套循环的权重逐层相乘
+    pub fn function_cost(&self, entries: &[DumpEntry], trip_count: u32) -> FunctionCost {
+        let entries: Vec<&DumpEntry> = entries.iter().filter(|e| e.parsed_instruction.is_some()).collect();
+        let instructions: Vec<_> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+        let loops = analysis::find_loops(&instructions);
+
+        // 块首：函数入口、跳转目标、跳转和返回之后的指令
+        let mut leaders = vec![false; instructions.len()];
+        if let Some(first) = leaders.first_mut() {
+            *first = true;
+        }
+        for i in 0..instructions.len() {
+            let successors = analysis::successors(&instructions, i);
+            if successors != [i + 1] {
+                if let Some(next) = leaders.get_mut(i + 1) {
+                    *next = true;
+                }
+                for target in successors {
+                    leaders[target] = true;
+                }
+            }
+        }
+
+        let mut blocks: Vec<BlockCost> = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let cost = self.cost_of(&entry.asm_instruction);
+            let address = instructions[i].address;
+            if leaders[i] || blocks.is_empty() {
+                let loop_depth = loops.iter().filter(|l| l.contains(i)).count();
+                blocks.push(BlockCost {
+                    start: address,
+                    end: address,
+                    instructions: 0,
+                    loop_depth,
+                    weight: f64::from(trip_count).powi(loop_depth as i32),
+                    latency: 0.0,
+                    issue: 0.0,
+                });
+            }
+            let block = blocks.last_mut().expect("已添加基本块");
+            block.end = address;
+            block.instructions += 1;
+            block.latency += cost.latency;
+            block.issue += cost.issue_cycles();
+        }
+
+        FunctionCost {
+            loops: loops.len(),
+            instructions: instructions.len(),
+            latency: blocks.iter().map(|b| b.latency * b.weight).sum(),
+            issue: blocks.iter().map(|b| b.issue * b.weight).sum(),
+            blocks,
+        }
+    }
+
+    /// 各级别的周期估计表
+    pub fn estimate_table(&self, levels: &[(&str, &[DumpEntry])], lang: Lang) -> String {
+        let mut output = String::new();
+        output.push_str(lang.pick(
+            "| 级别 | 指令数 | 延迟合计 | 发射下限 |\n",
+            "| Level | Instructions | Total latency | Issue bound |\n",
+        ));
+        output.push_str("|------|--------|----------|----------|\n");
+        for (level, entries) in levels {
+            let estimate = self.estimate(entries);
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                level,
+                estimate.instructions,
+                format_cycles(estimate.latency),
+                format_cycles(estimate.issue)
+            ));
+        }
+        output.push_str(lang.pick(
+            "\n> 延迟合计假设每条指令都依赖上一条，发射下限假设指令之间没有依赖；均按指令顺序累加，未计循环次数\n",
+            "\n> Total latency assumes every instruction depends on the previous one; the issue bound assumes no dependencies. Both sum the instructions once, ignoring loop trip counts\n",
+        ));
+        output
+    }
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self::new(Core::default())
+    }
+}
+
+/// 周期数的显示：整数不带小数，其余保留一位小数
+pub fn format_cycles(cycles: f64) -> String {
+    if (cycles - cycles.round()).abs() < 0.05 {
+        format!("{}", cycles.round())
+    } else {
+        format!("{:.1}", cycles)
+    }
+}
+
+/// 单条指令的代价显示（`延迟 / 吞吐`）
+pub fn format_cost(cost: &InstructionCost) -> String {
+    let throughput = if cost.throughput >= 1.0 {
+        format_cycles(cost.throughput)
+    } else {
+        format!("1/{}", format_cycles(cost.issue_cycles()))
+    };
+    format!("{} / {}", format_cycles(cost.latency), throughput)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(asm: &str) -> DumpEntry {
+        DumpEntry {
+            id: String::new(),
+            c_line: None,
+            c_code: String::new(),
+            address: String::new(),
+            machine_code: String::new(),
+            asm_instruction: asm.to_string(),
+            parsed_instruction: None,
+        }
+    }
+
+    #[test]
+    fn test_function_cost() {
+        let dump = r#"
+0000000000000000 <sum>:
+   0:   52800002    mov w2, #0x0
+   4:   b8404403    ldr w3, [x0], #4
+   8:   0b030042    add w2, w2, w3
+   c:   eb01001f    cmp x0, x1
+  10:   54ffffa1    b.ne 4 <sum+0x4>
+  14:   2a0203e0    mov w0, w2
+  18:   d65f03c0    ret
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string()).extract_function_data("sum").unwrap();
+        let cost = CostModel::new(Core::CortexA72).function_cost(&entries, 10);
+        assert_eq!(cost.loops, 1);
+        let blocks: Vec<(u64, u64, usize)> = cost.blocks.iter().map(|b| (b.start, b.end, b.loop_depth)).collect();
+        assert_eq!(blocks, [(0x0, 0x0, 0), (0x4, 0x10, 1), (0x14, 0x18, 0)]);
+        // 循环体 ldr 4 + add 1 + cmp 1 + b.ne 1，执行 10 次
+        assert_eq!(cost.latency, 1.0 + 7.0 * 10.0 + 2.0);
+    }
+
+    #[test]
+    fn test_class_of() {
+        let model = CostModel::new(Core::CortexA72);
+        assert_eq!(model.class_of("add x0, x1, x2"), Some("alu"));
+        assert_eq!(model.class_of("add x0, x1, x2, lsl #2"), Some("alu_shift"));
+        assert_eq!(model.class_of("add x0, x1, #0x1, lsl #12"), Some("alu"));
+        assert_eq!(model.class_of("b.ne 8 <sum+0x8>"), Some("branch"));
+        assert_eq!(model.class_of("add v0.4s, v1.4s, v2.4s"), Some("simd"));
+        assert_eq!(model.class_of("ldr w0, [sp, #12]"), Some("load"));
+        assert_eq!(model.class_of("paciasp"), None);
+    }
+
+    #[test]
+    fn test_estimate() {
+        let entries = [entry("ldr w1, [x0]"), entry("sdiv w0, w1, w2"), entry("ret"), entry("")];
+        let a72 = CostModel::new(Core::CortexA72).estimate(&entries);
+        assert_eq!(a72.instructions, 3);
+        assert_eq!(a72.latency, 4.0 + 12.0 + 1.0);
+        let n1 = CostModel::new(Core::NeoverseN1).estimate(&[entry("mul w0, w1, w2")]);
+        assert_eq!(n1.latency, 2.0);
+        assert_eq!(format_cost(&CostModel::new(Core::CortexA72).cost_of("sdiv w0, w1, w2")), "12 / 1/12");
+        assert!(Core::parse("neoverse-n1").is_ok() && Core::parse("m1").is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! 无用存储检测
+//!
+//! 找出写入后在被覆盖或函数返回之前从未被读取的寄存器和栈槽。-O0 把每个变量都存到栈上，
+//! 很多存储随后又被覆盖，这里把这些冗余存储明确标出来。
+//!
+//! 寄存器部分直接使用活跃性分析；栈槽部分先沿控制流图推算每条指令处 SP 和 X29 相对入口 SP 的偏移，
+//! 再按字节做向后的活跃性分析。栈地址被传给其他指令（如 `add x0, sp, #16`）时无法确定谁会读取栈槽，
+//! 此时跳过栈槽分析。调用处 SP 之上 64 字节内、函数自身从未读取的栈槽视为传给被调用者的栈上参数。
+
+use crate::analysis::{self, Frame, LivenessAnalysis};
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::objdump::DumpEntry;
+use crate::register::Register;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// 调用时可能被被调用者读取的栈上参数区大小
+const OUTGOING_ARGS: i64 = 64;
+
+/// 无用存储的目标
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadTarget {
+    /// 寄存器（如 `w0`）
+    Register(String),
+    /// 栈槽：相对函数入口 SP 的偏移和字节数
+    Stack { offset: i64, size: i64 },
+}
+
+/// 一处无用存储
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadStore {
+    /// 指令地址
+    pub address: String,
+    /// 汇编指令
+    pub asm_instruction: String,
+    /// 被写入但未被读取的寄存器或栈槽
+    pub target: DeadTarget,
+}
+
+/// 检测函数中的无用存储（按地址排序）
+pub fn detect(entries: &[DumpEntry]) -> Vec<DeadStore> {
+    let entries: Vec<&DumpEntry> = entries.iter().filter(|e| e.parsed_instruction.is_some()).collect();
+    let instructions: Vec<Instruction> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+
+    let mut found: Vec<(usize, DeadTarget)> = dead_registers(&entries, &instructions);
+    found.extend(dead_stack_slots(&instructions));
+    found.sort_by_key(|(i, _)| *i);
+    found
+        .into_iter()
+        .map(|(i, target)| DeadStore {
+            address: entries[i].address.clone(),
+            asm_instruction: entries[i].asm_instruction.replace('\t', " "),
+            target,
+        })
+        .collect()
+}
+
+/// 写入的寄存器在之后的所有路径上都没有被读取
+///
+/// 只考虑除写入寄存器外没有其他作用的指令：调用、设置标志位和回写基址的访存不算在内，
+/// 帧指针和链接寄存器也不算。
+fn dead_registers(entries: &[&DumpEntry], instructions: &[Instruction]) -> Vec<(usize, DeadTarget)> {
+    use InstructionType::*;
+
+    let liveness = LivenessAnalysis::compute(instructions);
+    let mut found = Vec::new();
+    for (i, inst) in instructions.iter().enumerate() {
+        let defs = liveness.defs[i];
+        let mnemonic = entries[i].asm_instruction.split_whitespace().next().unwrap_or("");
+        let side_effects = matches!(inst.instruction_type, BL | BLR | SVC)
+            || crate::explain::sets_flags(mnemonic)
+            || inst.operands.iter().any(|op| match op {
+                Operand::Memory { base, .. } => base.index().is_some_and(|b| defs.contains(b)),
+                _ => false,
+            });
+        if defs.is_empty() || side_effects || defs.contains(29) || defs.contains(30) {
+            continue;
+        }
+        if defs.iter().all(|r| !liveness.live_out[i].contains(r)) {
+            if let Some(Operand::Register(reg)) = inst.operands.first() {
+                found.push((i, DeadTarget::Register(format!("{:?}", reg).to_lowercase())));
+            }
+        }
+    }
+    found
+}
+
+/// 一次栈访问：相对入口 SP 的起始偏移和字节数
+type Slot = (i64, i64);
+
+/// 在栈槽上做向后的活跃性分析，栈帧无法确定或栈地址被传出时返回空列表
+fn dead_stack_slots(instructions: &[Instruction]) -> Vec<(usize, DeadTarget)> {
+    if instructions.iter().any(escapes) {
+        return Vec::new();
+    }
+    let Some(frames) = analysis::stack_frames(instructions) else {
+        return Vec::new();
+    };
+    let accesses: Vec<(Option<Slot>, Option<Slot>)> = instructions
+        .iter()
+        .zip(&frames)
+        .map(|(inst, frame)| frame.map_or((None, None), |frame| access(inst, frame)))
+        .collect();
+    let bytes = |(offset, size): Slot| offset..offset + size;
+    let stored: BTreeSet<i64> = accesses.iter().filter_map(|(s, _)| *s).flat_map(bytes).collect();
+    let loaded: BTreeSet<i64> = accesses.iter().filter_map(|(_, l)| *l).flat_map(bytes).collect();
+    // 从未被本函数读取的栈槽可能是调用时的栈上参数
+    let unread: BTreeSet<i64> = stored.difference(&loaded).copied().collect();
+
+    let successors: Vec<Vec<usize>> = (0..instructions.len()).map(|i| analysis::successors(instructions, i)).collect();
+    let mut live_in = vec![BTreeSet::new(); instructions.len()];
+    let mut live_out = vec![BTreeSet::new(); instructions.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in (0..instructions.len()).rev() {
+            let inst = &instructions[i];
+            let mut out: BTreeSet<i64> = successors[i].iter().flat_map(|s| live_in[*s].iter().copied()).collect();
+            // 间接跳转和尾调用之后的去向未知
+            if successors[i].is_empty() && inst.instruction_type != InstructionType::RET {
+                out.extend(&stored);
+            }
+            let mut input = out.clone();
+            if let Some(slot) = accesses[i].0 {
+                for byte in bytes(slot) {
+                    input.remove(&byte);
+                }
+            }
+            if let Some(slot) = accesses[i].1 {
+                input.extend(bytes(slot));
+            }
+            if let (InstructionType::BL | InstructionType::BLR, Some(frame)) = (inst.instruction_type, frames[i]) {
+                input.extend(unread.range(frame.sp..frame.sp + OUTGOING_ARGS));
+            }
+            if out != live_out[i] || input != live_in[i] {
+                live_out[i] = out;
+                live_in[i] = input;
+                changed = true;
+            }
+        }
+    }
+
+    accesses
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (store, _))| {
+            let (offset, size) = (*store)?;
+            // 入口 SP 之上属于调用者的栈帧
+            let own = offset + size <= 0;
+            (own && bytes((offset, size)).all(|b| !live_out[i].contains(&b)))
+                .then_some((i, DeadTarget::Stack { offset, size }))
+        })
+        .collect()
+}
+
+/// 指令是否把栈地址（SP 或 X29 的值）传给了其他指令
+///
+/// 调整栈帧的指令、访存的基址和保存/恢复帧记录不算
+fn escapes(inst: &Instruction) -> bool {
+    use InstructionType::*;
+
+    if analysis::adjusts_frame(inst) {
+        return false;
+    }
+    // 保存帧记录时 X29 作为数据写入栈中，恢复 X29 是对它的写入
+    let saves_fp = matches!(inst.instruction_type, STP | STR);
+    let restores_fp = matches!(inst.instruction_type, LDP | LDR);
+    inst.operands.iter().enumerate().any(|(position, op)| match op {
+        Operand::Register(Register::SP) => true,
+        Operand::Register(r) if r.index() == Some(29) => !(restores_fp || saves_fp && position < 2),
+        _ => false,
+    })
+}
+
+/// 指令写入和读取的栈槽（写入, 读取）
+fn access(inst: &Instruction, frame: Frame) -> (Option<Slot>, Option<Slot>) {
+    use InstructionType::*;
+
+    let ops = inst.operands.as_slice();
+    let Some(Operand::Memory { base, offset, index: None, .. }) = ops.iter().find(|op| matches!(op, Operand::Memory { .. }))
+    else {
+        return (None, None);
+    };
+    let start = match base {
+        Register::SP => frame.sp,
+        r if r.index() == Some(29) => match frame.fp {
+            Some(fp) => fp,
+            None => return (None, None),
+        },
+        _ => return (None, None),
+    };
+    // 后变址 `[sp], #16` 访问的是回写前的地址
+    let post_indexed = matches!(ops.last(), Some(Operand::Immediate(_)));
+    let address = start + if post_indexed { 0 } else { offset.unwrap_or(0) };
+    let width = match (inst.instruction_type, ops.first()) {
+        (STRB | LDRB | LDRSB, _) => 1,
+        (STRH | LDRH | LDRSH, _) => 2,
+        (LDRSW, _) => 4,
+        (_, Some(Operand::Register(reg))) => register_bytes(*reg),
+        _ => return (None, None),
+    };
+
+    match inst.instruction_type {
+        STR | STRB | STRH | STUR => (Some((address, width)), None),
+        STP => (Some((address, width * 2)), None),
+        LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | LDUR => (None, Some((address, width))),
+        LDP => (None, Some((address, width * 2))),
+        _ => (None, None),
+    }
+}
+
+/// 寄存器的字节数
+fn register_bytes(reg: Register) -> i64 {
+    match format!("{:?}", reg).chars().next() {
+        Some('W') | Some('S') => 4,
+        Some('H') => 2,
+        Some('B') => 1,
+        Some('Q') | Some('V') => 16,
+        _ => 8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <f>:
+   0:   d10083ff    sub sp, sp, #0x20
+   4:   b9001fe0    str w0, [sp, #28]
+   8:   b9001bff    str wzr, [sp, #24]
+   c:   52800021    mov w1, #0x1
+  10:   b9001be1    str w1, [sp, #24]
+  14:   b9401be0    ldr w0, [sp, #24]
+  18:   52800042    mov w2, #0x2
+  1c:   910083ff    add sp, sp, #0x20
+  20:   d65f03c0    ret
+
+0000000000000030 <escape>:
+  30:   d10043ff    sub sp, sp, #0x10
+  34:   b9000fff    str wzr, [sp, #12]
+  38:   910033e0    add x0, sp, #0xc
+  3c:   910043ff    add sp, sp, #0x10
+  40:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_detect_dead_stores() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let found: Vec<(String, DeadTarget)> = detect(&parser.extract_function_data("f").unwrap())
+            .into_iter()
+            .map(|d| (d.address, d.target))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                // 参数从未被读取，w0 被 ldr 覆盖
+                (String::from("4"), DeadTarget::Stack { offset: -4, size: 4 }),
+                // 初始化为 0 后立即被覆盖
+                (String::from("8"), DeadTarget::Stack { offset: -8, size: 4 }),
+                (String::from("18"), DeadTarget::Register(String::from("w2"))),
+            ]
+        );
+
+        // 栈地址被传出时不分析栈槽
+        assert!(detect(&parser.extract_function_data("escape").unwrap()).is_empty());
+    }
+}
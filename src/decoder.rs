@@ -0,0 +1,1105 @@
+//! 机器码解码
+//!
+//! 把 32 位 AArch64 编码直接解码为 [`Instruction`]，操作数的表示与文本解析器一致
+//! （别名按 GNU objdump 的首选写法：`mov`、`cmp`、`lsl`、`cset` 等）。
+//!
+//! objdump 的文本能解析时以文本为准（保留 objdump 给出的符号名），解码结果用于交叉检查，
+//! 两者不一致时说明文本解析有误或 dump 被改动过；文本无法解析（格式差异、不认识的写法）时
+//! 使用解码结果。只覆盖整数、访存、跳转和标量浮点的常用指令，其余编码返回 None。
+
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::objdump::DumpEntry;
+use crate::register::{Condition, Register};
+
+/// 取 `word` 中从第 `lo` 位开始的 `len` 位
+fn bits(word: u32, lo: u32, len: u32) -> u32 {
+    (word >> lo) & ((1u32 << len) - 1)
+}
+
+fn bit(word: u32, n: u32) -> bool {
+    (word >> n) & 1 == 1
+}
+
+/// 把 `len` 位的值按有符号数扩展
+fn sign_extend(value: u32, len: u32) -> i64 {
+    let shift = 64 - len;
+    ((value as i64) << shift) >> shift
+}
+
+/// 通用寄存器：编号 31 在 `sp` 为真时是 SP，否则是零寄存器
+fn gpr(n: u32, is_64bit: bool, sp: bool) -> Option<Register> {
+    match (n, is_64bit, sp) {
+        (31, true, true) => Some(Register::SP),
+        // 32 位的 WSP 没有对应的寄存器
+        (31, false, true) => None,
+        (31, true, false) => Some(Register::XZR),
+        (31, false, false) => Some(Register::WZR),
+        _ => Register::parse(&format!("{}{}", if is_64bit { 'x' } else { 'w' }, n)).ok(),
+    }
+}
+
+fn x(n: u32) -> Option<Operand> {
+    gpr(n, true, false).map(Operand::Register)
+}
+
+fn reg(n: u32, is_64bit: bool) -> Option<Operand> {
+    gpr(n, is_64bit, false).map(Operand::Register)
+}
+
+fn reg_sp(n: u32, is_64bit: bool) -> Option<Operand> {
+    gpr(n, is_64bit, true).map(Operand::Register)
+}
+
+/// 标量浮点寄存器，`kind` 为 h/s/d
+fn fpr(n: u32, kind: char) -> Option<Operand> {
+    Register::parse(&format!("{}{}", kind, n)).ok().map(Operand::Register)
+}
+
+/// 浮点类型字段对应的寄存器前缀
+fn fp_kind(ftype: u32) -> Option<char> {
+    match ftype {
+        0 => Some('s'),
+        1 => Some('d'),
+        3 => Some('h'),
+        _ => None,
+    }
+}
+
+/// 跳转目标（与 objdump 一样写成不带 0x 的十六进制地址）
+fn label(target: u64) -> Operand {
+    Operand::Label(format!("{:x}", target))
+}
+
+fn relative(address: u64, offset: i64) -> u64 {
+    address.wrapping_add(offset as u64)
+}
+
+fn memory(base: u32, offset: i64) -> Option<Operand> {
+    Some(Operand::Memory {
+        base: gpr(base, true, true)?,
+        offset: (offset != 0).then_some(offset),
+        index: None,
+        pre_indexed: false,
+        post_indexed: false,
+    })
+}
+
+/// 条件码字段（NV 与 AL 相同）
+fn condition(code: u32) -> Condition {
+    use Condition::*;
+    [EQ, NE, CS, CC, MI, PL, VS, VC, HI, LS, GE, LT, GT, LE, AL, AL][code as usize & 15]
+}
+
+/// 移位操作数（`lsl #2` 等，与文本解析一样作为标签保存）
+fn shift(kind: u32, amount: u32) -> Option<Operand> {
+    let name = ["lsl", "lsr", "asr", "ror"][kind as usize & 3];
+    (kind != 0 || amount != 0).then(|| Operand::Label(format!("{} #{}", name, amount)))
+}
+
+fn op(ty: InstructionType, operands: Vec<Option<Operand>>, address: u64) -> Option<Instruction> {
+    let operands = operands.into_iter().collect::<Option<Vec<_>>>()?;
+    Some(Instruction::new(ty, operands, address))
+}
+
+/// 解码逻辑立即数（N:immr:imms），返回按操作数宽度展开的位模式
+pub fn decode_bit_masks(n: bool, immr: u32, imms: u32, is_64bit: bool) -> Option<u64> {
+    let combined = (u32::from(n) << 6) | (!imms & 0x3f);
+    if combined < 2 || (!is_64bit && n) {
+        return None;
+    }
+    let len = 31 - combined.leading_zeros();
+    let size = 1u32 << len;
+    let levels = size - 1;
+    let s = imms & levels;
+    let r = immr & levels;
+    if s == levels {
+        return None;
+    }
+    let mask = if size == 64 { u64::MAX } else { (1u64 << size) - 1 };
+    let ones = (1u64 << (s + 1)) - 1;
+    let element = if r == 0 { ones } else { ((ones >> r) | (ones << (size - r))) & mask };
+    let width = if is_64bit { 64 } else { 32 };
+    let mut value = 0u64;
+    let mut i = 0;
+    while i < width {
+        value |= element << i;
+        i += size;
+    }
+    Some(if is_64bit { value } else { value & 0xffff_ffff })
+}
+
+/// 按操作数宽度把立即数写成与 objdump 文本相同的数值（32 位按无符号，64 位按位模式）
+fn wide_immediate(value: u64, is_64bit: bool) -> Operand {
+    if is_64bit {
+        Operand::Immediate(value as i64)
+    } else {
+        Operand::Immediate((value & 0xffff_ffff) as i64)
+    }
+}
+
+/// 解码一条指令，不认识的编码返回 None
+pub fn decode(word: u32, address: u64) -> Option<Instruction> {
+    let mut inst = match bits(word, 25, 4) {
+        0b1000 | 0b1001 => data_immediate(word, address),
+        0b1010 | 0b1011 => branch_system(word, address),
+        0b0100 | 0b0110 | 0b1100 | 0b1110 => load_store(word, address),
+        0b0101 | 0b1101 => data_register(word, address),
+        0b0111 | 0b1111 => floating_point(word, address),
+        _ => None,
+    }?;
+    inst.encoding = Some(word);
+    Some(inst)
+}
+
+/// 立即数数据处理：PC 相对地址、加减、逻辑、宽立即数、位域、EXTR
+fn data_immediate(word: u32, address: u64) -> Option<Instruction> {
+    use InstructionType::*;
+    let sf = bit(word, 31);
+    let rd = bits(word, 0, 5);
+    let rn = bits(word, 5, 5);
+    match bits(word, 23, 3) {
+        0b000 | 0b001 => {
+            let imm = sign_extend((bits(word, 5, 19) << 2) | bits(word, 29, 2), 21);
+            if sf {
+                let target = relative(address & !0xfff, imm << 12);
+                op(ADRP, vec![x(rd), Some(label(target))], address)
+            } else {
+                op(ADR, vec![x(rd), Some(label(relative(address, imm)))], address)
+            }
+        }
+        0b010 => {
+            let (sub, set_flags) = (bit(word, 30), bit(word, 29));
+            let imm = Some(Operand::Immediate(bits(word, 10, 12) as i64));
+            let shifted = bit(word, 22).then(|| Operand::Label(String::from("lsl #12")));
+            let mut operands = match (sub, set_flags, rd) {
+                (false, false, _) if !bit(word, 22) && bits(word, 10, 12) == 0 && (rd == 31 || rn == 31) => {
+                    return op(MOV, vec![reg_sp(rd, sf), reg_sp(rn, sf)], address);
+                }
+                (_, true, 31) => vec![reg_sp(rn, sf), imm],
+                (_, false, _) => vec![reg_sp(rd, sf), reg_sp(rn, sf), imm],
+                (_, true, _) => vec![reg(rd, sf), reg_sp(rn, sf), imm],
+            };
+            operands.extend(shifted.map(Some));
+            let ty = match (sub, set_flags, rd) {
+                (false, false, _) => ADD,
+                (true, false, _) => SUB,
+                (false, true, 31) => CMN,
+                (true, true, 31) => CMP,
+                (false, true, _) => ADDS,
+                (true, true, _) => SUBS,
+            };
+            op(ty, operands, address)
+        }
+        0b100 => {
+            let value = decode_bit_masks(bit(word, 22), bits(word, 16, 6), bits(word, 10, 6), sf)?;
+            let imm = Some(wide_immediate(value, sf));
+            match bits(word, 29, 2) {
+                0b00 => op(AND, vec![reg_sp(rd, sf), reg(rn, sf), imm], address),
+                0b01 if rn == 31 && !move_wide_preferred(value, sf) => op(MOV, vec![reg_sp(rd, sf), imm], address),
+                0b01 => op(ORR, vec![reg_sp(rd, sf), reg(rn, sf), imm], address),
+                0b10 => op(EOR, vec![reg_sp(rd, sf), reg(rn, sf), imm], address),
+                _ if rd == 31 => op(TST, vec![reg(rn, sf), imm], address),
+                _ => op(ANDS, vec![reg(rd, sf), reg(rn, sf), imm], address),
+            }
+        }
+        0b101 => {
+            let hw = bits(word, 21, 2);
+            let imm16 = bits(word, 5, 16) as u64;
+            if !sf && hw > 1 {
+                return None;
+            }
+            let shifted = imm16 << (hw * 16);
+            let lsl = (hw != 0).then(|| Operand::Label(format!("lsl #{}", hw * 16)));
+            match bits(word, 29, 2) {
+                // MOVN：imm16 为 0 且有移位、或 32 位的 0xffff 时不写成 mov
+                0b00 if !(imm16 == 0 && hw != 0) && (sf || imm16 != 0xffff) => {
+                    op(MOV, vec![reg(rd, sf), Some(wide_immediate(!shifted, sf))], address)
+                }
+                0b00 => op(MOVN, [vec![reg(rd, sf), Some(Operand::Immediate(imm16 as i64))], lsl.map(Some).into_iter().collect()].concat(), address),
+                0b10 if !(imm16 == 0 && hw != 0) => op(MOV, vec![reg(rd, sf), Some(wide_immediate(shifted, sf))], address),
+                0b10 => op(MOVZ, [vec![reg(rd, sf), Some(Operand::Immediate(imm16 as i64))], lsl.map(Some).into_iter().collect()].concat(), address),
+                0b11 => op(MOVK, [vec![reg(rd, sf), Some(Operand::Immediate(imm16 as i64))], lsl.map(Some).into_iter().collect()].concat(), address),
+                _ => None,
+            }
+        }
+        0b110 => bitfield(word, address),
+        0b111 if bits(word, 29, 2) == 0 && !bit(word, 21) && bit(word, 22) == sf && (sf || !bit(word, 15)) => {
+            let rm = bits(word, 16, 5);
+            let lsb = Some(Operand::Immediate(bits(word, 10, 6) as i64));
+            if rn == rm {
+                op(ROR, vec![reg(rd, sf), reg(rn, sf), lsb], address)
+            } else {
+                op(EXTR, vec![reg(rd, sf), reg(rn, sf), reg(rm, sf), lsb], address)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// ORR 立即数写成 mov 的条件：值不能由一条 MOVZ/MOVN 表示
+fn move_wide_preferred(value: u64, is_64bit: bool) -> bool {
+    let width = if is_64bit { 64 } else { 32 };
+    let fits = |v: u64| (0..width / 16).any(|hw| v & !(0xffffu64 << (hw * 16)) == 0);
+    let mask = if is_64bit { u64::MAX } else { 0xffff_ffff };
+    fits(value) || fits(!value & mask)
+}
+
+/// 位域指令及其别名（asr/lsl/lsr、ubfx/sbfx、ubfiz/sbfiz、bfi/bfxil）
+fn bitfield(word: u32, address: u64) -> Option<Instruction> {
+    use InstructionType::*;
+    let sf = bit(word, 31);
+    if bit(word, 22) != sf {
+        return None;
+    }
+    let size = if sf { 64 } else { 32 };
+    let (rd, rn) = (bits(word, 0, 5), bits(word, 5, 5));
+    let (immr, imms) = (bits(word, 16, 6), bits(word, 10, 6));
+    if immr >= size || imms >= size {
+        return None;
+    }
+    let imm = |v: u32| Some(Operand::Immediate(v as i64));
+    let (ty, operands) = match bits(word, 29, 2) {
+        0b00 => match () {
+            _ if imms == size - 1 => (ASR, vec![imm(immr)]),
+            _ if imms < immr => (SBFIZ, vec![imm(size - immr), imm(imms + 1)]),
+            // SXTB/SXTH/SXTW 没有对应的指令类型
+            _ if immr == 0 && matches!(imms, 7 | 15 | 31) => return None,
+            _ => (SBFX, vec![imm(immr), imm(imms + 1 - immr)]),
+        },
+        0b01 => match () {
+            _ if imms < immr && rn == 31 => return None,
+            _ if imms < immr => (BFI, vec![imm(size - immr), imm(imms + 1)]),
+            _ => (BFXIL, vec![imm(immr), imm(imms + 1 - immr)]),
+        },
+        0b10 => match () {
+            _ if imms != size - 1 && imms + 1 == immr => (LSL, vec![imm(size - 1 - imms)]),
+            _ if imms == size - 1 => (LSR, vec![imm(immr)]),
+            _ if imms < immr => (UBFIZ, vec![imm(size - immr), imm(imms + 1)]),
+            // UXTB/UXTH
+            _ if immr == 0 && matches!(imms, 7 | 15) => return None,
+            _ => (UBFX, vec![imm(immr), imm(imms + 1 - immr)]),
+        },
+        _ => return None,
+    };
+    op(ty, [vec![reg(rd, sf), reg(rn, sf)], operands].concat(), address)
+}
+
+/// 跳转、异常和系统指令
+fn branch_system(word: u32, address: u64) -> Option<Instruction> {
+    use InstructionType::*;
+    let rt = bits(word, 0, 5);
+    if word & 0x7c00_0000 == 0x1400_0000 {
+        let target = relative(address, sign_extend(bits(word, 0, 26), 26) << 2);
+        return op(if bit(word, 31) { BL } else { B }, vec![Some(label(target))], address);
+    }
+    if word & 0xff00_0010 == 0x5400_0000 {
+        let target = relative(address, sign_extend(bits(word, 5, 19), 19) << 2);
+        let ty = match condition(bits(word, 0, 4)) {
+            Condition::EQ => BEQ,
+            Condition::NE => BNE,
+            Condition::CS => BCS,
+            Condition::CC => BCC,
+            Condition::MI => BMI,
+            Condition::PL => BPL,
+            Condition::VS => BVS,
+            Condition::VC => BVC,
+            Condition::HI => BHI,
+            Condition::LS => BLS,
+            Condition::GE => BGE,
+            Condition::LT => BLT,
+            Condition::GT => BGT,
+            Condition::LE => BLE,
+            Condition::AL => return None,
+        };
+        return op(ty, vec![Some(label(target))], address);
+    }
+    if word & 0x7e00_0000 == 0x3400_0000 {
+        let target = relative(address, sign_extend(bits(word, 5, 19), 19) << 2);
+        let ty = if bit(word, 24) { CBNZ } else { CBZ };
+        return op(ty, vec![reg(rt, bit(word, 31)), Some(label(target))], address);
+    }
+    if word & 0x7e00_0000 == 0x3600_0000 {
+        let target = relative(address, sign_extend(bits(word, 5, 14), 14) << 2);
+        let number = (u32::from(bit(word, 31)) << 5) | bits(word, 19, 5);
+        let ty = if bit(word, 24) { TBNZ } else { TBZ };
+        return op(ty, vec![reg(rt, bit(word, 31)), Some(Operand::Immediate(number as i64)), Some(label(target))], address);
+    }
+    if word & 0xffff_fc1f == 0xd65f_0000 {
+        let rn = bits(word, 5, 5);
+        let operands = if rn == 30 { Vec::new() } else { vec![x(rn)] };
+        return op(RET, operands, address);
+    }
+    match word & 0xffff_fc1f {
+        0xd61f_0000 => return op(BR, vec![x(bits(word, 5, 5))], address),
+        0xd63f_0000 => return op(BLR, vec![x(bits(word, 5, 5))], address),
+        _ => {}
+    }
+    match word {
+        0xd69f_03e0 => return op(ERET, Vec::new(), address),
+        0xd6bf_03e0 => return op(DRPS, Vec::new(), address),
+        0xd503_201f => return op(NOP, Vec::new(), address),
+        0xd503_203f => return op(YIELD, Vec::new(), address),
+        0xd503_205f => return op(WFE, Vec::new(), address),
+        0xd503_207f => return op(WFI, Vec::new(), address),
+        _ => {}
+    }
+    let imm16 = Some(Operand::Immediate(bits(word, 5, 16) as i64));
+    match word & 0xffe0_001f {
+        0xd400_0001 => return op(SVC, vec![imm16], address),
+        0xd440_0000 => return op(HLT, vec![imm16], address),
+        0xd420_0000 => return op(BRK, vec![imm16], address),
+        _ => {}
+    }
+    if word & 0xffff_f01f == 0xd503_301f {
+        let crm = bits(word, 8, 4);
+        let option = match crm {
+            15 => "sy",
+            14 => "st",
+            13 => "ld",
+            11 => "ish",
+            10 => "ishst",
+            9 => "ishld",
+            7 => "nsh",
+            6 => "nshst",
+            5 => "nshld",
+            3 => "osh",
+            2 => "oshst",
+            1 => "oshld",
+            _ => return None,
+        };
+        let option = Some(Operand::Label(String::from(option)));
+        return match bits(word, 5, 3) {
+            4 => op(DSB, vec![option], address),
+            5 => op(DMB, vec![option], address),
+            6 if crm == 15 => op(ISB, Vec::new(), address),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// 访存指令：独占/获取-释放、字面量、寄存器对、单寄存器
+fn load_store(word: u32, address: u64) -> Option<Instruction> {
+    use InstructionType::*;
+    let (rt, rn) = (bits(word, 0, 5), bits(word, 5, 5));
+    let size = bits(word, 30, 2);
+    let vector = bit(word, 26);
+
+    if word & 0x3f00_0000 == 0x0800_0000 && !bit(word, 21) {
+        let is_64bit = size == 3;
+        let (o2, load, o0) = (bit(word, 23), bit(word, 22), bit(word, 15));
+        let ty = match (o2, load, o0, size) {
+            (false, true, false, 0) => LDXRB,
+            (false, true, false, 1) => LDXRH,
+            (false, true, false, _) => LDXR,
+            (false, true, true, 0) => LDAXRB,
+            (false, true, true, 1) => LDAXRH,
+            (false, false, false, 0) => STXRB,
+            (false, false, false, 1) => STXRH,
+            (false, false, false, _) => STXR,
+            (false, false, true, 0) => STLXRB,
+            (false, false, true, 1) => STLXRH,
+            (true, true, true, 2 | 3) => LDAR,
+            (true, false, true, 2 | 3) => STLR,
+            _ => return None,
+        };
+        let status = (!o2 && !load).then(|| reg(bits(word, 16, 5), false));
+        let operands = status.into_iter().chain([reg(rt, is_64bit), memory(rn, 0)]).collect();
+        return op(ty, operands, address);
+    }
+
+    if word & 0x3b00_0000 == 0x1800_0000 {
+        let target = Some(label(relative(address, sign_extend(bits(word, 5, 19), 19) << 2)));
+        return match (vector, size) {
+            (false, 0) => op(LDR, vec![reg(rt, false), target], address),
+            (false, 1) => op(LDR, vec![x(rt), target], address),
+            (false, 2) => op(LDRSW, vec![x(rt), target], address),
+            (true, 0) => op(LDR, vec![fpr(rt, 's'), target], address),
+            (true, 1) => op(LDR, vec![fpr(rt, 'd'), target], address),
+            _ => None,
+        };
+    }
+
+    if word & 0x3a00_0000 == 0x2800_0000 {
+        let (kind, scale) = match (vector, size) {
+            (false, 0) => ('w', 4),
+            (false, 2) => ('x', 8),
+            (true, 0) => ('s', 4),
+            (true, 1) => ('d', 8),
+            _ => return None,
+        };
+        let register = |n| match kind {
+            'w' => reg(n, false),
+            'x' => x(n),
+            _ => fpr(n, kind),
+        };
+        let offset = sign_extend(bits(word, 15, 7), 7) * scale;
+        let ty = if bit(word, 22) { LDP } else { STP };
+        let mut operands = vec![register(rt), register(bits(word, 10, 5))];
+        match bits(word, 23, 2) {
+            0b01 => operands.extend([memory(rn, 0), Some(Operand::Immediate(offset))]),
+            0b10 => operands.push(memory(rn, offset)),
+            0b11 => operands.push(pre_indexed(rn, offset)),
+            _ => return None,
+        }
+        return op(ty, operands, address);
+    }
+
+    if word & 0x3b00_0000 != 0x3800_0000 && word & 0x3b00_0000 != 0x3900_0000 {
+        return None;
+    }
+    let opc = bits(word, 22, 2);
+    let (ty, target) = match (vector, size, opc) {
+        (false, 0, 0) => (STRB, reg(rt, false)),
+        (false, 0, 1) => (LDRB, reg(rt, false)),
+        (false, 0, 2) => (LDRSB, x(rt)),
+        (false, 0, 3) => (LDRSB, reg(rt, false)),
+        (false, 1, 0) => (STRH, reg(rt, false)),
+        (false, 1, 1) => (LDRH, reg(rt, false)),
+        (false, 1, 2) => (LDRSH, x(rt)),
+        (false, 1, 3) => (LDRSH, reg(rt, false)),
+        (false, 2, 0) => (STR, reg(rt, false)),
+        (false, 2, 1) => (LDR, reg(rt, false)),
+        (false, 2, 2) => (LDRSW, x(rt)),
+        (false, 3, 0) => (STR, x(rt)),
+        (false, 3, 1) => (LDR, x(rt)),
+        (true, 1, 0) => (STR, fpr(rt, 'h')),
+        (true, 1, 1) => (LDR, fpr(rt, 'h')),
+        (true, 2, 0) => (STR, fpr(rt, 's')),
+        (true, 2, 1) => (LDR, fpr(rt, 's')),
+        (true, 3, 0) => (STR, fpr(rt, 'd')),
+        (true, 3, 1) => (LDR, fpr(rt, 'd')),
+        _ => return None,
+    };
+    if bit(word, 24) {
+        let offset = bits(word, 10, 12) as i64 * (1 << size);
+        return op(ty, vec![target, memory(rn, offset)], address);
+    }
+    if bit(word, 21) {
+        // 寄存器偏移只解码不带扩展和移位的 [Xn, Xm]
+        if bits(word, 10, 2) != 0b10 || bits(word, 13, 3) != 0b011 || bit(word, 12) {
+            return None;
+        }
+        let index = gpr(bits(word, 16, 5), true, false)?;
+        let base = gpr(rn, true, true)?;
+        let memory = Operand::Memory { base, offset: None, index: Some(index), pre_indexed: false, post_indexed: false };
+        return op(ty, vec![target, Some(memory)], address);
+    }
+    let offset = sign_extend(bits(word, 12, 9), 9);
+    match bits(word, 10, 2) {
+        0b00 => {
+            let ty = match ty {
+                LDR => LDUR,
+                STR => STUR,
+                _ => return None,
+            };
+            op(ty, vec![target, memory(rn, offset)], address)
+        }
+        0b01 => op(ty, vec![target, memory(rn, 0), Some(Operand::Immediate(offset))], address),
+        0b11 => op(ty, vec![target, pre_indexed(rn, offset)], address),
+        _ => None,
+    }
+}
+
+fn pre_indexed(base: u32, offset: i64) -> Option<Operand> {
+    Some(Operand::Memory {
+        base: gpr(base, true, true)?,
+        offset: Some(offset),
+        index: None,
+        pre_indexed: true,
+        post_indexed: false,
+    })
+}
+
+/// 寄存器数据处理
+fn data_register(word: u32, address: u64) -> Option<Instruction> {
+    use InstructionType::*;
+    let sf = bit(word, 31);
+    let (rd, rn, rm) = (bits(word, 0, 5), bits(word, 5, 5), bits(word, 16, 5));
+    let amount = bits(word, 10, 6);
+    // 32 位的移位量不能超过 31（扩展寄存器形式的这几位是扩展方式）
+    let extended = bit(word, 24) && bit(word, 21);
+    if !sf && amount >= 32 && !bit(word, 28) && !extended {
+        return None;
+    }
+
+    if !bit(word, 28) && !bit(word, 24) {
+        let shifted = shift(bits(word, 22, 2), amount).map(Some);
+        let (ty, invert) = match bits(word, 29, 2) {
+            0b00 => (AND, BIC),
+            0b01 => (ORR, ORN),
+            0b10 => (EOR, EON),
+            _ if rd == 31 => (TST, TST),
+            _ => (ANDS, ANDS),
+        };
+        let ty = if bit(word, 21) { invert } else { ty };
+        let operands: Vec<Option<Operand>> = match ty {
+            ORR if rn == 31 && shifted.is_none() => return op(MOV, vec![reg(rd, sf), reg(rm, sf)], address),
+            ORN if rn == 31 => vec![reg(rd, sf), reg(rm, sf)],
+            // BICS 没有对应的指令类型
+            TST | ANDS if bit(word, 21) => return None,
+            TST => vec![reg(rn, sf), reg(rm, sf)],
+            _ => vec![reg(rd, sf), reg(rn, sf), reg(rm, sf)],
+        };
+        let ty = if ty == ORN && rn == 31 { MVN } else { ty };
+        return op(ty, operands.into_iter().chain(shifted).collect(), address);
+    }
+
+    if !bit(word, 28) && !bit(word, 21) {
+        if bits(word, 22, 2) == 3 {
+            return None;
+        }
+        let shifted = shift(bits(word, 22, 2), amount).map(Some);
+        let operands: Vec<Option<Operand>> = match (bit(word, 30), bit(word, 29)) {
+            (_, true) if rd == 31 => vec![reg(rn, sf), reg(rm, sf)],
+            // NEGS 没有对应的指令类型
+            (true, true) if rn == 31 => return None,
+            (true, false) if rn == 31 => vec![reg(rd, sf), reg(rm, sf)],
+            _ => vec![reg(rd, sf), reg(rn, sf), reg(rm, sf)],
+        };
+        let ty = match (bit(word, 30), bit(word, 29)) {
+            (false, false) => ADD,
+            (true, false) if rn == 31 => NEG,
+            (true, false) => SUB,
+            (false, true) if rd == 31 => CMN,
+            (true, true) if rd == 31 => CMP,
+            (false, true) => ADDS,
+            (true, true) => SUBS,
+        };
+        return op(ty, operands.into_iter().chain(shifted).collect(), address);
+    }
+
+    if !bit(word, 28) {
+        // 扩展寄存器：add x0, x1, w2, sxtw #2
+        if bits(word, 22, 2) != 0 || bits(word, 10, 3) > 4 {
+            return None;
+        }
+        let option = bits(word, 13, 3);
+        let imm3 = bits(word, 10, 3);
+        let set_flags = bit(word, 29);
+        let uses_sp = (rd == 31 && !set_flags) || rn == 31;
+        let natural = if sf { 0b011 } else { 0b010 };
+        let extend = if uses_sp && option == natural {
+            (imm3 != 0).then(|| Operand::Label(format!("lsl #{}", imm3)))
+        } else {
+            let name = ["uxtb", "uxth", "uxtw", "uxtx", "sxtb", "sxth", "sxtw", "sxtx"][option as usize];
+            Some(Operand::Label(if imm3 == 0 { name.to_string() } else { format!("{} #{}", name, imm3) }))
+        };
+        let index = reg(rm, sf && option & 3 == 3);
+        let operands: Vec<Option<Operand>> = match (set_flags, rd) {
+            (true, 31) => vec![reg_sp(rn, sf), index],
+            (true, _) => vec![reg(rd, sf), reg_sp(rn, sf), index],
+            _ => vec![reg_sp(rd, sf), reg_sp(rn, sf), index],
+        };
+        let ty = match (bit(word, 30), set_flags, rd) {
+            (false, false, _) => ADD,
+            (true, false, _) => SUB,
+            (false, true, 31) => CMN,
+            (true, true, 31) => CMP,
+            (false, true, _) => ADDS,
+            (true, true, _) => SUBS,
+        };
+        return op(ty, operands.into_iter().chain(extend.map(Some)).collect(), address);
+    }
+
+    if bit(word, 24) {
+        let ra = bits(word, 10, 5);
+        let o0 = bit(word, 15);
+        return match (bits(word, 29, 2), bits(word, 21, 3), o0) {
+            (0, 0b000, false) if ra == 31 => op(MUL, vec![reg(rd, sf), reg(rn, sf), reg(rm, sf)], address),
+            (0, 0b000, false) => op(MADD, vec![reg(rd, sf), reg(rn, sf), reg(rm, sf), reg(ra, sf)], address),
+            (0, 0b000, true) if ra != 31 => op(MSUB, vec![reg(rd, sf), reg(rn, sf), reg(rm, sf), reg(ra, sf)], address),
+            (0, 0b001, false) if sf && ra == 31 => op(SMULL, vec![x(rd), reg(rn, false), reg(rm, false)], address),
+            (0, 0b101, false) if sf && ra == 31 => op(UMULL, vec![x(rd), reg(rn, false), reg(rm, false)], address),
+            _ => None,
+        };
+    }
+
+    match bits(word, 21, 3) {
+        0b000 if bits(word, 10, 6) == 0 && !bit(word, 29) => {
+            let ty = if bit(word, 30) { SBC } else { ADC };
+            op(ty, vec![reg(rd, sf), reg(rn, sf), reg(rm, sf)], address)
+        }
+        0b010 if bit(word, 29) && !bit(word, 10) && !bit(word, 4) => {
+            let ty = if bit(word, 30) { CCMP } else { CCMN };
+            let second = if bit(word, 11) { Some(Operand::Immediate(rm as i64)) } else { reg(rm, sf) };
+            let nzcv = Some(Operand::Immediate(bits(word, 0, 4) as i64));
+            let mut inst = op(ty, vec![reg(rn, sf), second, nzcv], address)?;
+            inst.condition = Some(condition(bits(word, 12, 4)));
+            Some(inst)
+        }
+        0b100 if !bit(word, 29) && !bit(word, 11) => conditional_select(word, address),
+        0b110 if !bit(word, 29) && !bit(word, 30) => {
+            let ty = match bits(word, 10, 6) {
+                0b000010 => UDIV,
+                0b000011 => SDIV,
+                0b001000 => LSL,
+                0b001001 => LSR,
+                0b001010 => ASR,
+                0b001011 => ROR,
+                0b010000 if !sf => CRC32B,
+                0b010001 if !sf => CRC32H,
+                0b010010 if !sf => CRC32W,
+                0b010011 if sf => CRC32X,
+                0b010100 if !sf => CRC32CB,
+                _ => return None,
+            };
+            let rm = if ty == CRC32X { x(rm) } else { reg(rm, sf) };
+            let rd_rn_64 = sf && !matches!(ty, CRC32X);
+            op(ty, vec![reg(rd, rd_rn_64), reg(rn, rd_rn_64), rm], address)
+        }
+        0b110 if !bit(word, 29) && bits(word, 16, 5) == 0 => {
+            let ty = match (bits(word, 10, 6), sf) {
+                (0, _) => RBIT,
+                (1, _) => REV16,
+                (2, true) => REV32,
+                (2, false) | (3, true) => REV,
+                (4, _) => CLZ,
+                (5, _) => CLS,
+                _ => return None,
+            };
+            op(ty, vec![reg(rd, sf), reg(rn, sf)], address)
+        }
+        _ => None,
+    }
+}
+
+/// 条件选择及其别名（cset/csetm/cinc/cinv/cneg）
+fn conditional_select(word: u32, address: u64) -> Option<Instruction> {
+    use InstructionType::*;
+    let sf = bit(word, 31);
+    let (rd, rn, rm) = (bits(word, 0, 5), bits(word, 5, 5), bits(word, 16, 5));
+    let code = bits(word, 12, 4);
+    let (base, same, zero) = match (bit(word, 30), bit(word, 10)) {
+        (false, false) => (CSEL, None, None),
+        (false, true) => (CSINC, Some(CINC), Some(CSET)),
+        (true, false) => (CSINV, Some(CINV), Some(CSETM)),
+        (true, true) => (CSNEG, Some(CNEG), None),
+    };
+    // 别名使用相反的条件，条件为 AL/NV 时不使用别名
+    let invertible = code >> 1 != 0b111;
+    let (ty, operands, code) = match (same, zero) {
+        (_, Some(zero)) if invertible && rn == 31 && rm == 31 => (zero, vec![reg(rd, sf)], code ^ 1),
+        (Some(same), _) if invertible && rn == rm && rn != 31 => (same, vec![reg(rd, sf), reg(rn, sf)], code ^ 1),
+        _ => (base, vec![reg(rd, sf), reg(rn, sf), reg(rm, sf)], code),
+    };
+    let mut inst = op(ty, operands, address)?;
+    inst.condition = Some(condition(code));
+    Some(inst)
+}
+
+/// 标量浮点指令
+fn floating_point(word: u32, address: u64) -> Option<Instruction> {
+    use InstructionType::*;
+    let (rd, rn, rm) = (bits(word, 0, 5), bits(word, 5, 5), bits(word, 16, 5));
+    let kind = fp_kind(bits(word, 22, 2));
+
+    if word & 0xff00_0000 == 0x1f00_0000 {
+        let kind = kind?;
+        let ty = match (bit(word, 21), bit(word, 15)) {
+            (false, false) => FMADD,
+            (false, true) => FMSUB,
+            _ => return None,
+        };
+        return op(ty, vec![fpr(rd, kind), fpr(rn, kind), fpr(rm, kind), fpr(bits(word, 10, 5), kind)], address);
+    }
+    if word & 0x5f20_0000 != 0x1e20_0000 || bit(word, 29) {
+        return None;
+    }
+    let kind = kind?;
+
+    if bits(word, 10, 2) == 0b10 {
+        let ty = match bits(word, 12, 4) {
+            0 => FMUL,
+            1 => FDIV,
+            2 => FADD,
+            3 => FSUB,
+            4 => FMAX,
+            5 => FMIN,
+            6 => FMAXNM,
+            7 => FMINNM,
+            _ => return None,
+        };
+        return op(ty, vec![fpr(rd, kind), fpr(rn, kind), fpr(rm, kind)], address);
+    }
+    if bits(word, 10, 5) == 0b10000 {
+        let (ty, target) = match bits(word, 15, 6) {
+            0 => (FMOV, kind),
+            1 => (FABS, kind),
+            2 => (FNEG, kind),
+            3 => (FSQRT, kind),
+            4 => (FCVT, 's'),
+            5 => (FCVT, 'd'),
+            7 => (FCVT, 'h'),
+            8 => (FRINTN, kind),
+            9 => (FRINTP, kind),
+            10 => (FRINTM, kind),
+            11 => (FRINTZ, kind),
+            12 => (FRINTA, kind),
+            14 => (FRINTX, kind),
+            15 => (FRINTI, kind),
+            _ => return None,
+        };
+        if ty == FCVT && target == kind {
+            return None;
+        }
+        return op(ty, vec![fpr(rd, target), fpr(rn, kind)], address);
+    }
+    if bits(word, 10, 4) == 0b1000 && bits(word, 14, 2) == 0 && bits(word, 0, 3) == 0 {
+        let ty = if bit(word, 4) { FCMPE } else { FCMP };
+        let second = if bit(word, 3) { Some(Operand::Immediate(0)) } else { fpr(rm, kind) };
+        return op(ty, vec![fpr(rn, kind), second], address);
+    }
+    if bits(word, 10, 6) == 0 {
+        let sf = bit(word, 31);
+        let to_fp = |ty| op(ty, vec![fpr(rd, kind), reg(rn, sf)], address);
+        let to_int = |ty| op(ty, vec![reg(rd, sf), fpr(rn, kind)], address);
+        return match (bits(word, 19, 2), bits(word, 16, 3)) {
+            (0b00, 0b000) => to_int(FCVTNS),
+            (0b00, 0b001) => to_int(FCVTNU),
+            (0b00, 0b010) => to_fp(SCVTF),
+            (0b00, 0b011) => to_fp(UCVTF),
+            (0b00, 0b100) => to_int(FCVTAS),
+            (0b00, 0b101) => to_int(FCVTAU),
+            (0b00, 0b110) if sf == (kind == 'd') => to_int(FMOV),
+            (0b00, 0b111) if sf == (kind == 'd') => to_fp(FMOV),
+            (0b01, 0b000) => to_int(FCVTPS),
+            (0b01, 0b001) => to_int(FCVTPU),
+            (0b10, 0b000) => to_int(FCVTMS),
+            (0b10, 0b001) => to_int(FCVTMU),
+            (0b11, 0b000) => to_int(FCVTZS),
+            (0b11, 0b001) => to_int(FCVTZU),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// 文本解析结果与机器码解码结果不一致的指令
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub address: u64,
+    /// objdump 给出的指令文本
+    pub text: String,
+    pub decoded: Instruction,
+}
+
+/// 按 objdump 的汇编写法输出指令，如 `b.ne 8`、`ldr w0, [sp, #12]`、`csel w0, w1, w2, lt`
+pub fn assembly(inst: &Instruction) -> String {
+    use InstructionType::*;
+    let name = format!("{:?}", inst.instruction_type).to_lowercase();
+    let mnemonic = match inst.instruction_type {
+        BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE => format!("b.{}", &name[1..]),
+        _ => name,
+    };
+    let register = |r: &Register| format!("{:?}", r).to_lowercase();
+    let mut operands: Vec<String> = inst
+        .operands
+        .iter()
+        .map(|operand| match operand {
+            Operand::Register(r) => register(r),
+            Operand::Immediate(value) if *value < 0 => format!("#{}", value),
+            Operand::Immediate(value) => format!("#0x{:x}", value),
+            Operand::Label(label) => label.clone(),
+            Operand::Memory { base, offset, index, pre_indexed, post_indexed } => {
+                let mut text = format!("[{}", register(base));
+                if let Some(index) = index {
+                    text.push_str(&format!(", {}", register(index)));
+                }
+                match offset {
+                    Some(offset) if *post_indexed => text.push_str(&format!("], #{}", offset)),
+                    Some(offset) if *offset != 0 || *pre_indexed => text.push_str(&format!(", #{}]", offset)),
+                    _ => text.push(']'),
+                }
+                if *pre_indexed {
+                    text.push('!');
+                }
+                text
+            }
+        })
+        .collect();
+    if let Some(condition) = inst.condition {
+        operands.push(format!("{:?}", condition).to_lowercase());
+    }
+    if operands.is_empty() {
+        mnemonic
+    } else {
+        format!("{} {}", mnemonic, operands.join(", "))
+    }
+}
+
+/// 条目的机器码（只接受 8 位十六进制的 AArch64 编码）
+pub fn entry_word(entry: &DumpEntry) -> Option<u32> {
+    if entry.machine_code.len() != 8 {
+        return None;
+    }
+    u32::from_str_radix(&entry.machine_code, 16).ok()
+}
+
+/// 交叉检查一条指令：解码结果与文本解析结果不一致时返回 Mismatch
+///
+/// 任一方无法得到结果时不做判断
+pub fn check(entry: &DumpEntry) -> Option<Mismatch> {
+    let parsed = entry.parsed_instruction.as_ref()?;
+    let word = entry_word(entry)?;
+    let mut decoded = decode(word, parsed.address)?;
+    // 目标文件中尚未重定位的指令偏移字段为 0，objdump 显示的是重定位的符号（如 `bl 0 <puts>`），
+    // 按机器码只能算出当前地址，这时目标以文本为准，只比较其余操作数
+    let mask = crate::encoder::relocation_mask(parsed);
+    if mask != 0 && word & mask == 0 {
+        for (text, operand) in parsed.operands.iter().zip(decoded.operands.iter_mut()) {
+            if matches!((text, &*operand), (Operand::Label(_), Operand::Label(_))) {
+                *operand = text.clone();
+            }
+        }
+    }
+    if equivalent(parsed, &decoded) {
+        return None;
+    }
+    Some(Mismatch {
+        address: parsed.address,
+        text: entry.asm_instruction.clone(),
+        decoded,
+    })
+}
+
+/// 判断两条指令是否相同：寄存器别名（fp/x29）、立即数的写法（32 位的 -1 与 0xffffffff）、
+/// 跳转目标后的符号名不影响结果
+pub fn equivalent(a: &Instruction, b: &Instruction) -> bool {
+    let (a, b) = (canonical(a), canonical(b));
+    let narrow = matches!(a.operands.first(), Some(Operand::Register(r)) if r.index().is_some() && !r.is_64bit());
+    a.instruction_type == b.instruction_type
+        && a.condition == b.condition
+        && a.operands.len() == b.operands.len()
+        && a.operands.iter().zip(&b.operands).all(|(x, y)| same_operand(x, y, narrow))
+}
+
+/// 把等价的写法统一：`orr w0, wzr, #imm` → `mov w0, #imm`
+fn canonical(inst: &Instruction) -> Instruction {
+    let mut inst = inst.clone();
+    if inst.instruction_type == InstructionType::ORR
+        && matches!(inst.operands.as_slice(), [_, Operand::Register(Register::WZR | Register::XZR), Operand::Immediate(_)])
+    {
+        inst.instruction_type = InstructionType::MOV;
+        inst.operands.remove(1);
+    }
+    inst
+}
+
+fn same_register(a: Register, b: Register) -> bool {
+    a == b || (a.index().is_some() && a.index() == b.index() && a.is_64bit() == b.is_64bit())
+}
+
+fn same_operand(a: &Operand, b: &Operand, narrow: bool) -> bool {
+    match (a, b) {
+        (Operand::Register(a), Operand::Register(b)) => same_register(*a, *b),
+        (Operand::Immediate(a), Operand::Immediate(b)) => a == b || (narrow && *a as u32 == *b as u32),
+        (Operand::Label(a), Operand::Label(b)) => {
+            let address = |label: &str| {
+                let first = label.split_whitespace().next()?;
+                u64::from_str_radix(first.trim_start_matches("0x"), 16).ok()
+            };
+            match (address(a), address(b)) {
+                (Some(x), Some(y)) => x == y,
+                _ => a.split_whitespace().collect::<Vec<_>>() == b.split_whitespace().collect::<Vec<_>>(),
+            }
+        }
+        (
+            Operand::Memory { base: b1, offset: o1, index: i1, pre_indexed: p1, .. },
+            Operand::Memory { base: b2, offset: o2, index: i2, pre_indexed: p2, .. },
+        ) => {
+            same_register(*b1, *b2)
+                && o1.unwrap_or(0) == o2.unwrap_or(0)
+                && p1 == p2
+                && match (i1, i2) {
+                    (Some(x), Some(y)) => same_register(*x, *y),
+                    (None, None) => true,
+                    _ => false,
+                }
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::parser::AssemblyParser;
+
+    /// 编码取自 llvm-mc，文本为 GNU objdump 的写法
+    pub(crate) const CASES: &[(u32, &str)] = &[
+        (0x91400420, "add x0, x1, #0x1, lsl #12"),
+        (0x910003fd, "mov x29, sp"),
+        (0x12800000, "mov w0, #0xffffffff"),
+        (0x92800000, "mov x0, #0xffffffffffffffff"),
+        (0x52a00020, "mov w0, #0x10000"),
+        (0xf2a24680, "movk x0, #0x1234, lsl #16"),
+        (0x32001fe0, "mov w0, #0xff"),
+        (0x927cec20, "and x0, x1, #0xfffffffffffffff0"),
+        (0x7101901f, "cmp w0, #0x64"),
+        (0xb100041f, "cmn x0, #0x1"),
+        (0xd1004000, "sub x0, x0, #0x10"),
+        (0x71000400, "subs w0, w0, #0x1"),
+        (0xab020020, "adds x0, x1, x2"),
+        (0xab22c020, "adds x0, x1, w2, sxtw"),
+        (0x72001c20, "ands w0, w1, #0xff"),
+        (0xea020c20, "ands x0, x1, x2, lsl #3"),
+        (0x93431820, "sbfx x0, x1, #3, #4"),
+        (0xd37ef420, "lsl x0, x1, #2"),
+        (0x53017c20, "lsr w0, w1, #1"),
+        (0x937ffc20, "asr x0, x1, #63"),
+        (0xd3607c20, "lsl x0, x1, #32"),
+        (0xd37e2420, "ubfiz x0, x1, #2, #10"),
+        (0x33001c20, "bfxil w0, w1, #0, #8"),
+        (0xf8626820, "ldr x0, [x1, x2]"),
+        (0xb9800420, "ldrsw x0, [x1, #4]"),
+        (0xb9001fe0, "str w0, [sp, #28]"),
+        (0xf85f83a0, "ldur x0, [x29, #-8]"),
+        (0xb8404403, "ldr w3, [x0], #4"),
+        (0xf81f0fe0, "str x0, [sp, #-16]!"),
+        (0x39400020, "ldrb w0, [x1]"),
+        (0xa9be7bfd, "stp x29, x30, [sp, #-32]!"),
+        (0xa8c27bfd, "ldp x29, x30, [sp], #32"),
+        (0x6d0127e8, "stp d8, d9, [sp, #16]"),
+        (0x1a9f17e0, "cset w0, eq"),
+        (0x9a811000, "csel x0, x0, x1, ne"),
+        (0x1a810420, "cinc w0, w1, ne"),
+        (0x5a805400, "cneg w0, w0, mi"),
+        (0x7a400804, "ccmp w0, #0x0, #0x4, eq"),
+        (0x0b010000, "add w0, w0, w1"),
+        (0x8b020820, "add x0, x1, x2, lsl #2"),
+        (0xcb0203e0, "neg x0, x2"),
+        (0xeb01001f, "cmp x0, x1"),
+        (0x8b22c820, "add x0, x1, w2, sxtw #2"),
+        (0x8b2163ff, "add sp, sp, x1"),
+        (0xeb21401f, "cmp x0, w1, uxtw"),
+        (0x2a0203e0, "mov w0, w2"),
+        (0x2a2103e0, "mvn w0, w1"),
+        (0x6a01001f, "tst w0, w1"),
+        (0x72001c1f, "tst w0, #0xff"),
+        (0x4a010c20, "eor w0, w1, w1, lsl #3"),
+        (0x1b027c20, "mul w0, w1, w2"),
+        (0x9b020c20, "madd x0, x1, x2, x3"),
+        (0x1b028c20, "msub w0, w1, w2, w3"),
+        (0x9b227c20, "smull x0, w1, w2"),
+        (0x1ac20c20, "sdiv w0, w1, w2"),
+        (0x9ac22020, "lsl x0, x1, x2"),
+        (0x5ac01020, "clz w0, w1"),
+        (0xdac00c20, "rev x0, x1"),
+        (0x1ac24020, "crc32b w0, w1, w2"),
+        (0x1e622820, "fadd d0, d1, d2"),
+        (0x1e211820, "fdiv s0, s1, s1"),
+        (0x1e602020, "fcmp d1, d0"),
+        (0x1e624020, "fcvt s0, d1"),
+        (0x1e780020, "fcvtzs w0, d1"),
+        (0x9e220020, "scvtf s0, x1"),
+        (0x9e670020, "fmov d0, x1"),
+        (0x1f420c20, "fmadd d0, d1, d2, d3"),
+        (0xd65f03c0, "ret"),
+        (0xd63f0020, "blr x1"),
+        (0xd61f0200, "br x16"),
+        (0xd503201f, "nop"),
+        (0xd5033bbf, "dmb ish"),
+        (0xd5033fdf, "isb"),
+        (0xd4000001, "svc #0x0"),
+        (0xc85f7c20, "ldxr x0, [x1]"),
+        (0x88027c20, "stxr w2, w0, [x1]"),
+        (0xc8dffc20, "ldar x0, [x1]"),
+        (0x889ffc20, "stlr w0, [x1]"),
+    ];
+
+    fn parse(text: &str) -> Instruction {
+        AssemblyParser::new().parse(text).unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_decode_matches_text() {
+        for &(word, text) in CASES {
+            let decoded = decode(word, 0).unwrap_or_else(|| panic!("未能解码 {:08x} ({})", word, text));
+            assert!(equivalent(&parse(text), &decoded), "{:08x}: {} 解码为 {}", word, text, decoded);
+        }
+    }
+
+    #[test]
+    fn test_branch_targets() {
+        let cases: &[(u32, u64, &str)] = &[
+            (0x94000004, 0x400600, "bl 400610 <foo>"),
+            (0x17ffffff, 0x400600, "b 4005fc <main+0x3c>"),
+            (0x5400006d, 0x20, "b.le 2c <clamp+0x2c>"),
+            (0x54ffff81, 0x18, "b.ne 8 <sum+0x8>"),
+            (0x34000101, 0x4, "cbz w1, 24 <sum+0x24>"),
+            (0xb5000040, 0x400600, "cbnz x0, 400608 <f+0x8>"),
+            (0x37180040, 0x10, "tbnz w0, #3, 18 <f+0x18>"),
+            (0x90000080, 0x400640, "adrp x0, 410000 <data>"),
+            (0x10000040, 0x400640, "adr x0, 400648 <f+0x8>"),
+            (0x58000040, 0x400640, "ldr x0, 400648 <f+0x8>"),
+        ];
+        for &(word, address, text) in cases {
+            let decoded = decode(word, address).unwrap();
+            assert!(equivalent(&parse(text), &decoded), "{:08x}: {} 解码为 {}", word, text, decoded);
+        }
+    }
+
+    #[test]
+    fn test_check_flags_mismatch() {
+        let entry = |machine_code: &str, asm: &str| DumpEntry {
+            id: String::new(),
+            c_line: None,
+            c_code: String::new(),
+            address: String::from("0"),
+            machine_code: machine_code.to_string(),
+            asm_instruction: asm.to_string(),
+            parsed_instruction: Some(parse(asm)),
+        };
+        assert!(check(&entry("0b010000", "add w0, w0, w1")).is_none());
+        // 操作数顺序错误
+        let mismatch = check(&entry("cb010000", "sub w0, w1, w0")).unwrap();
+        assert_eq!(mismatch.decoded.instruction_type, InstructionType::SUB);
+        assert_eq!(mismatch.decoded.operands[2], Operand::Register(Register::X1));
+        // 不认识的编码不做判断
+        assert!(check(&entry("d503233f", "nop")).is_none());
+    }
+
+    #[test]
+    fn test_check_unrelocated_call() {
+        // 目标文件中的外部调用：偏移字段为 0，objdump 按重定位显示符号
+        let entry = |machine_code: &str, asm: &str| {
+            let mut parsed = parse(asm);
+            parsed.address = 0x64;
+            DumpEntry {
+                id: String::new(),
+                c_line: None,
+                c_code: String::new(),
+                address: String::from("64"),
+                machine_code: machine_code.to_string(),
+                asm_instruction: asm.to_string(),
+                parsed_instruction: Some(parsed),
+            }
+        };
+        let call = entry("94000000", "bl 0 <puts>");
+        assert!(check(&call).is_none());
+        assert!(crate::encoder::check(&call).is_none());
+        assert!(check(&entry("90000000", "adrp x0, 0 <main>")).is_none());
+        // 已重定位的跳转照常比较目标
+        let mismatch = check(&entry("94000002", "bl 0 <puts>")).unwrap();
+        assert_eq!(assembly(&mismatch.decoded), "bl 6c");
+    }
+
+    #[test]
+    fn test_assembly() {
+        let text = |word, address| assembly(&decode(word, address).unwrap());
+        assert_eq!(text(0x54ffff81, 0x18), "b.ne 8");
+        assert_eq!(text(0x0b010000, 0), "add w0, w0, w1");
+        assert_eq!(text(0xa9bf7bfd, 0), "stp x29, x30, [sp, #-16]!");
+        assert_eq!(text(0xb9400fe0, 0), "ldr w0, [sp, #12]");
+        assert_eq!(text(0xd65f03c0, 0), "ret");
+    }
+
+    #[test]
+    fn test_decode_bit_masks() {
+        assert_eq!(decode_bit_masks(false, 0, 7, false), Some(0xff));
+        assert_eq!(decode_bit_masks(true, 60, 59, true), Some(0xffff_ffff_ffff_fff0));
+        assert_eq!(decode_bit_masks(false, 0, 0b111100, false), Some(0x5555_5555));
+        assert_eq!(decode_bit_masks(true, 0, 63, true), None);
+    }
+}
@@ -0,0 +1,171 @@
+//! 解析诊断
+//!
+//! 记录函数中没有被文本解析器完整解析的指令（`ParseIssue`：行、列和原因），并统计解析覆盖率。
+//! 这些指令或者按机器码解码，或者只能由语义解释的后备逻辑按文本解释，报告中相应的部分不如完整解析的可靠。
+//! 宽松模式（默认）保留这些指令照常生成报告；严格模式（`--strict`）下只要有一条就报错。
+
+use crate::error::InterpreterError;
+use crate::locale::Lang;
+use serde::Serialize;
+
+/// 一条没有被完整解析的指令
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParseIssue {
+    /// 在 dump 文件中的行号（从 1 开始）
+    pub line: usize,
+    /// 出错位置的列号（从 1 开始，按字符计）
+    pub column: usize,
+    /// 指令地址
+    pub address: String,
+    /// 汇编指令
+    pub asm_instruction: String,
+    /// 文本解析器报告的原因
+    pub reason: String,
+    /// 是否按机器码解码成功（否则只能按文本解释）
+    pub decoded: bool,
+}
+
+/// 一个函数的解析诊断
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ParseDiagnostics {
+    /// 指令条数
+    pub instructions: usize,
+    /// 没有被完整解析的指令（按行号排序）
+    pub issues: Vec<ParseIssue>,
+}
+
+impl ParseDiagnostics {
+    /// 完整解析的指令条数
+    pub fn parsed(&self) -> usize {
+        self.instructions - self.issues.len()
+    }
+
+    /// 按机器码解码的指令条数
+    pub fn decoded(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.decoded).count()
+    }
+
+    /// 完整解析的百分比（没有指令时为 100）
+    pub fn coverage(&self) -> f64 {
+        if self.instructions == 0 {
+            return 100.0;
+        }
+        self.parsed() as f64 * 100.0 / self.instructions as f64
+    }
+
+    /// 严格模式：有未完整解析的指令时返回列出每条指令的行、列和原因的错误
+    pub fn check_strict(&self, level: &str) -> crate::Result<()> {
+        if self.issues.is_empty() {
+            return Ok(());
+        }
+        let mut message = format!("{} 中有 {} 条指令未能完整解析 (--strict):", level, self.issues.len());
+        for issue in &self.issues {
+            message.push_str(&format!(
+                "\n  第 {} 行第 {} 列 `{}`: {}",
+                issue.line, issue.column, issue.asm_instruction, issue.reason
+            ));
+        }
+        Err(InterpreterError::ParseError(message))
+    }
+
+    /// 一行覆盖率说明（`92%（25 条指令中 23 条完整解析，……）`）
+    fn describe(&self, lang: Lang) -> String {
+        let percent = format!("{:.0}%", self.coverage().floor());
+        if self.issues.is_empty() {
+            return match lang {
+                Lang::Zh => format!("{}（{} 条指令全部完整解析）", percent, self.instructions),
+                Lang::En => format!("{} (all {} instructions fully parsed)", percent, self.instructions),
+            };
+        }
+        let decoded = self.decoded();
+        let fallback = self.issues.len() - decoded;
+        match lang {
+            Lang::Zh => format!(
+                "{}（{} 条指令中 {} 条完整解析，{} 条按机器码解码，{} 条仅按文本解释）",
+                percent, self.instructions, self.parsed(), decoded, fallback
+            ),
+            Lang::En => format!(
+                "{} ({} of {} instructions fully parsed, {} decoded from machine code, {} interpreted from text only)",
+                percent, self.parsed(), self.instructions, decoded, fallback
+            ),
+        }
+    }
+}
+
+/// 汇总各级别的解析覆盖率（Markdown 列表），没有指令的级别（如 ARM32 dump）不列出
+pub fn summary(levels: &[(&str, &ParseDiagnostics)], lang: Lang) -> String {
+    levels
+        .iter()
+        .filter(|(_, diagnostics)| diagnostics.instructions > 0)
+        .map(|(level, diagnostics)| format!("- {}: {}\n", level, diagnostics.describe(lang)))
+        .collect()
+}
+
+/// 未完整解析的指令列表（Markdown 表格）；没有时返回 None
+pub fn issue_table(diagnostics: &ParseDiagnostics, lang: Lang) -> Option<String> {
+    if diagnostics.issues.is_empty() {
+        return None;
+    }
+    let mut output = String::from(lang.pick(
+        "| 行 | 列 | 地址 | 指令 | 原因 | 处理 |\n",
+        "| Line | Column | Address | Instruction | Reason | Handling |\n",
+    ));
+    output.push_str("|------|------|------|------|------|------|\n");
+    for issue in &diagnostics.issues {
+        let handling = if issue.decoded {
+            lang.pick("按机器码解码", "decoded from machine code")
+        } else {
+            lang.pick("仅按文本解释", "text only")
+        };
+        output.push_str(&format!(
+            "| {} | {} | {} | `{}` | {} | {} |\n",
+            issue.line,
+            issue.column,
+            issue.address,
+            issue.asm_instruction.replace('|', "\\|"),
+            issue.reason.replace('|', "\\|"),
+            handling
+        ));
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let dump = r#"
+0000000000000000 <f>:
+   0:   8b020020    add x0, x1, x2
+   4:   d503201f    frobnicate
+   8:   00000000    mystery x0, [foo]
+   c:   d65f03c0    ret
+"#;
+        let parser = ObjdumpParser::new(dump.to_string());
+        let diagnostics = parser.parse_diagnostics("f").unwrap();
+        assert_eq!(diagnostics.instructions, 4);
+        assert_eq!(diagnostics.parsed(), 2);
+        assert_eq!(diagnostics.decoded(), 1);
+        assert_eq!(diagnostics.coverage(), 50.0);
+
+        let issue = &diagnostics.issues[0];
+        assert_eq!((issue.line, issue.column), (4, 21));
+        assert_eq!(issue.reason, "无效的指令: frobnicate");
+        assert!(issue.decoded);
+        assert!(!diagnostics.issues[1].decoded);
+
+        let text = summary(&[("O0", &diagnostics)], Lang::Zh);
+        assert_eq!(text, "- O0: 50%（4 条指令中 2 条完整解析，1 条按机器码解码，1 条仅按文本解释）\n");
+        let error = diagnostics.check_strict("O0").unwrap_err().to_string();
+        assert!(error.contains("O0 中有 2 条指令未能完整解析"));
+        assert!(error.contains("\n  第 4 行第 21 列 `frobnicate`: 无效的指令: frobnicate"));
+        assert!(issue_table(&diagnostics, Lang::Zh).unwrap().contains("| 4 | 21 | 4 | `frobnicate` |"));
+
+        let clean = ParseDiagnostics { instructions: 3, issues: Vec::new() };
+        assert!(clean.check_strict("O2").is_ok());
+        assert_eq!(summary(&[("O2", &clean)], Lang::Zh), "- O2: 100%（3 条指令全部完整解析）\n");
+    }
+}
@@ -0,0 +1,1237 @@
+//! AArch64 指令模拟器
+//!
+//! 在简化的 CPU 状态（通用寄存器、SP、PC、NZCV 标志、稀疏内存）上
+//! 单步执行解析后的指令，用于观察每条指令对机器状态的影响
+
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::register::{Condition, ConditionFlags, Register};
+use crate::error::{Result, InterpreterError};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::syscall::{self, SyscallEvent};
+use serde::Serialize;
+
+/// 默认栈大小（64 KiB）
+pub const DEFAULT_STACK_SIZE: usize = 64 * 1024;
+
+/// 栈顶地址：初始 SP 指向这里，栈区域向低地址延伸
+pub const STACK_TOP: u64 = 0x7fff_0000;
+
+/// 返回哨兵地址：初始 LR 指向这里，函数 `ret` 后 PC 等于该值即表示执行结束
+pub const RETURN_ADDRESS: u64 = 0xffff_ffff_ffff_fff0;
+
+/// 函数第一条可执行指令的地址
+pub fn entry_address(entries: &[DumpEntry]) -> Option<u64> {
+    entries
+        .iter()
+        .find(|e| !e.asm_instruction.is_empty())
+        .and_then(|e| u64::from_str_radix(&e.address, 16).ok())
+}
+
+/// 单个寄存器或标志的状态变化
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StateChange {
+    /// 寄存器名称（如 X0、SP、NZCV）
+    pub name: String,
+    /// 变化前的值
+    pub old: u64,
+    /// 变化后的值
+    pub new: u64,
+}
+
+/// 一次内存访问
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MemoryAccess {
+    /// 访问地址
+    pub address: u64,
+    /// 访问字节数
+    pub size: usize,
+    /// 读出或写入的值
+    pub value: u64,
+    /// 是否为写操作
+    pub write: bool,
+}
+
+/// 已映射的内存区域
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// 区域名称（如 stack、buffer@0x10000）
+    pub name: String,
+    /// 起始地址
+    pub base: u64,
+    /// 区域内容
+    pub data: Vec<u8>,
+}
+
+impl MemoryRegion {
+    /// 结束地址（不含）
+    pub fn end(&self) -> u64 {
+        self.base + self.data.len() as u64
+    }
+}
+
+/// 稀疏内存：由若干互不重叠的区域组成，访问未映射的地址会报错
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Memory {
+    regions: Vec<MemoryRegion>,
+}
+
+impl Memory {
+    /// 创建空内存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 映射一段内存区域，区域不能与已有区域重叠
+    pub fn map(&mut self, name: &str, base: u64, data: Vec<u8>) -> Result<()> {
+        let end = base.checked_add(data.len() as u64).ok_or_else(|| {
+            InterpreterError::MemoryError(format!("区域 {} 超出地址空间", name))
+        })?;
+        if let Some(other) = self.regions.iter().find(|r| base < r.end() && r.base < end) {
+            return Err(InterpreterError::MemoryError(format!(
+                "区域 {} [0x{:x}, 0x{:x}) 与 {} [0x{:x}, 0x{:x}) 重叠",
+                name, base, end, other.name, other.base, other.end()
+            )));
+        }
+        self.regions.push(MemoryRegion { name: name.to_string(), base, data });
+        Ok(())
+    }
+
+    /// 所有已映射的区域
+    pub fn regions(&self) -> &[MemoryRegion] {
+        &self.regions
+    }
+
+    /// 读取 size 字节（小端序）
+    pub fn load(&self, address: u64, size: usize) -> Result<u64> {
+        let (idx, offset) = self.locate(address, size)?;
+        let mut value = 0u64;
+        for (i, byte) in self.regions[idx].data[offset..offset + size].iter().enumerate() {
+            value |= (*byte as u64) << (i * 8);
+        }
+        Ok(value)
+    }
+
+    /// 写入 size 字节（小端序）
+    pub fn store(&mut self, address: u64, size: usize, value: u64) -> Result<()> {
+        let (idx, offset) = self.locate(address, size)?;
+        for (i, byte) in self.regions[idx].data[offset..offset + size].iter_mut().enumerate() {
+            *byte = (value >> (i * 8)) as u8;
+        }
+        Ok(())
+    }
+
+    /// 查找完整包含访问范围的区域，返回区域下标和区域内偏移
+    fn locate(&self, address: u64, size: usize) -> Result<(usize, usize)> {
+        let end = address.checked_add(size as u64);
+        self.regions
+            .iter()
+            .position(|r| address >= r.base && end.is_some_and(|end| end <= r.end()))
+            .map(|idx| (idx, (address - self.regions[idx].base) as usize))
+            .ok_or_else(|| InterpreterError::MemoryError(format!(
+                "地址 0x{:x} 未映射 (访问 {} 字节)，可用 --mem 预置缓冲区",
+                address, size
+            )))
+    }
+}
+
+/// 解析缓冲区预置表达式 `ADDR[:TYPE]=V1,V2,...`
+///
+/// TYPE 为 u8/i8/u16/i16/u32/i32/u64/i64，默认 i32；`V*N` 表示将 V 重复 N 次，
+/// 例如 `0x10000:i32=1,2,3,4`、`0x20000=0*16`。TYPE 为 str 时按字符串写入（支持 `\n` `\t` `\0`），
+/// 例如 `0x30000:str=hello\n`
+pub fn parse_buffer(text: &str) -> Result<(u64, Vec<u8>)> {
+    let invalid = |msg: &str| InterpreterError::ParseError(format!("{}: {}", msg, text));
+
+    let (target, values) = text
+        .split_once('=')
+        .ok_or_else(|| invalid("缓冲区格式应为 ADDR[:TYPE]=V1,V2,..."))?;
+    let (address, elem_type) = target.split_once(':').unwrap_or((target, "i32"));
+    let address = parse_number(address.trim()).ok_or_else(|| invalid("无效的缓冲区地址"))?;
+    if elem_type.trim() == "str" {
+        return Ok((address, unescape(values).into_bytes()));
+    }
+    let size = match elem_type.trim() {
+        "u8" | "i8" => 1,
+        "u16" | "i16" => 2,
+        "u32" | "i32" => 4,
+        "u64" | "i64" => 8,
+        _ => return Err(invalid("元素类型应为 u8/i8/u16/i16/u32/i32/u64/i64")),
+    };
+
+    let mut bytes = Vec::new();
+    for item in values.split(',') {
+        let (value, count) = match item.split_once('*') {
+            Some((value, count)) => (value, count.trim().parse::<usize>().map_err(|_| invalid("无效的重复次数"))?),
+            None => (item, 1),
+        };
+        let value = parse_number(value.trim()).ok_or_else(|| invalid("无效的元素值"))?;
+        for _ in 0..count {
+            bytes.extend_from_slice(&value.to_le_bytes()[..size]);
+        }
+    }
+
+    Ok((address, bytes))
+}
+
+/// 处理字符串中的 `\n` `\t` `\0` `\\` 转义
+fn unescape(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('0') => result.push('\0'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// 解析十六进制（0x 前缀）或十进制（可为负）数字
+fn parse_number(text: &str) -> Option<u64> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse::<i64>().ok().map(|v| v as u64),
+    }
+}
+
+/// CPU 状态
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpuState {
+    /// X0 - X30 通用寄存器
+    pub x: [u64; 31],
+    /// 栈指针
+    pub sp: u64,
+    /// 程序计数器
+    pub pc: u64,
+    /// NZCV 条件标志
+    pub flags: ConditionFlags,
+    /// 稀疏内存
+    pub memory: Memory,
+}
+
+impl CpuState {
+    /// 创建新的 CPU 状态：映射大小为 stack_size 的栈区域，SP 指向栈顶，LR 指向返回哨兵地址
+    pub fn new(stack_size: usize) -> Self {
+        let mut x = [0; 31];
+        x[30] = RETURN_ADDRESS;
+        let mut memory = Memory::new();
+        memory
+            .map("stack", STACK_TOP - stack_size as u64, vec![0; stack_size])
+            .expect("空内存中映射栈区域不会失败");
+        Self {
+            x,
+            sp: STACK_TOP,
+            pc: 0,
+            flags: ConditionFlags::new(),
+            memory,
+        }
+    }
+
+    /// 在指定地址预置一段缓冲区
+    pub fn map_buffer(&mut self, address: u64, data: Vec<u8>) -> Result<()> {
+        self.memory.map(&format!("buffer@0x{:x}", address), address, data)
+    }
+
+    /// 读取寄存器（W 寄存器只返回低 32 位）
+    pub fn read_reg(&self, reg: Register) -> u64 {
+        match reg {
+            Register::SP => self.sp,
+            Register::PC => self.pc,
+            Register::XZR | Register::WZR => 0,
+            _ => match reg.index() {
+                Some(idx) if reg.is_64bit() => self.x[idx],
+                Some(idx) => self.x[idx] & 0xffff_ffff,
+                None => 0,
+            },
+        }
+    }
+
+    /// 写入寄存器（写 W 寄存器时高 32 位清零）
+    pub fn write_reg(&mut self, reg: Register, value: u64) {
+        match reg {
+            Register::SP => self.sp = value,
+            Register::PC => self.pc = value,
+            Register::XZR | Register::WZR => {}
+            _ => {
+                if let Some(idx) = reg.index() {
+                    self.x[idx] = if reg.is_64bit() { value } else { value & 0xffff_ffff };
+                }
+            }
+        }
+    }
+
+    /// NZCV 标志打包为 4 位整数（N 为最高位）
+    pub fn nzcv(&self) -> u64 {
+        ((self.flags.n as u64) << 3)
+            | ((self.flags.z as u64) << 2)
+            | ((self.flags.c as u64) << 1)
+            | (self.flags.v as u64)
+    }
+
+    /// 与之前的状态比较，列出发生变化的寄存器和标志（不含 PC）
+    pub fn changes_from(&self, before: &CpuState) -> Vec<StateChange> {
+        let mut changes = Vec::new();
+        for (i, (old, new)) in before.x.iter().zip(self.x.iter()).enumerate() {
+            if old != new {
+                changes.push(StateChange { name: format!("X{}", i), old: *old, new: *new });
+            }
+        }
+        if before.sp != self.sp {
+            changes.push(StateChange { name: String::from("SP"), old: before.sp, new: self.sp });
+        }
+        if before.flags != self.flags {
+            changes.push(StateChange { name: String::from("NZCV"), old: before.nzcv(), new: self.nzcv() });
+        }
+        changes
+    }
+
+    /// 从内存读取 size 字节（小端序）
+    pub fn load(&self, address: u64, size: usize) -> Result<u64> {
+        self.memory.load(address, size)
+    }
+
+    /// 向内存写入 size 字节（小端序）
+    pub fn store(&mut self, address: u64, size: usize, value: u64) -> Result<()> {
+        self.memory.store(address, size, value)
+    }
+}
+
+impl Default for CpuState {
+    fn default() -> Self {
+        Self::new(DEFAULT_STACK_SIZE)
+    }
+}
+
+/// 函数执行结束的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum TraceEnd {
+    /// 函数返回到哨兵地址（附带 X0）
+    Returned(u64),
+    /// 程序通过 exit 系统调用退出（附带退出码）
+    Exited(u64),
+    /// PC 离开了函数范围
+    LeftFunction(u64),
+    /// 遇到无法解析的指令
+    Unparsed(String),
+    /// 指令执行失败
+    Failed(String),
+    /// 达到最大步数
+    StepLimit(usize),
+}
+
+impl TraceEnd {
+    /// 函数是否正常结束（返回或通过 exit 退出）
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TraceEnd::Returned(_) | TraceEnd::Exited(_))
+    }
+
+    /// 按语言描述结束方式
+    pub fn describe(&self, lang: Lang) -> String {
+        match (self, lang) {
+            (TraceEnd::Returned(x0), Lang::Zh) => format!("函数返回，X0 = 0x{:x}", x0),
+            (TraceEnd::Returned(x0), Lang::En) => format!("returned, X0 = 0x{:x}", x0),
+            (TraceEnd::Exited(code), Lang::Zh) => format!("程序退出，退出码 {}", code),
+            (TraceEnd::Exited(code), Lang::En) => format!("exited with code {}", code),
+            (TraceEnd::LeftFunction(pc), Lang::Zh) => format!("PC = 0x{:x} 已离开函数范围", pc),
+            (TraceEnd::LeftFunction(pc), Lang::En) => format!("PC = 0x{:x} left the function", pc),
+            (TraceEnd::Unparsed(asm), Lang::Zh) => format!("无法解析指令 '{}'", asm),
+            (TraceEnd::Unparsed(asm), Lang::En) => format!("could not parse instruction '{}'", asm),
+            (TraceEnd::Failed(err), Lang::Zh) => format!("执行失败: {}", err),
+            (TraceEnd::Failed(err), Lang::En) => format!("execution failed: {}", err),
+            (TraceEnd::StepLimit(n), Lang::Zh) => format!("已执行 {} 步，可能陷入死循环", n),
+            (TraceEnd::StepLimit(n), Lang::En) => format!("stopped after {} steps, possibly an infinite loop", n),
+        }
+    }
+}
+
+impl std::fmt::Display for TraceEnd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(Lang::Zh))
+    }
+}
+
+/// 执行轨迹中的一步
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceStep {
+    /// 指令地址
+    pub address: u64,
+    /// 汇编指令
+    pub asm_instruction: String,
+    /// 发生变化的寄存器和标志
+    pub changes: Vec<StateChange>,
+    /// 内存访问
+    pub accesses: Vec<MemoryAccess>,
+    /// 系统调用
+    pub syscall: Option<SyscallEvent>,
+    /// 执行后的 NZCV
+    pub nzcv: u64,
+    /// 是否跳过了对外部函数的调用
+    pub skipped_call: bool,
+}
+
+/// 函数的完整执行轨迹
+#[derive(Debug, Clone, Serialize)]
+pub struct Trace {
+    /// 每一步的状态变化
+    pub steps: Vec<TraceStep>,
+    /// 结束原因
+    pub end: TraceEnd,
+}
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// 停止条件，如 `x0==0`、`w1>=10`、`sp!=0x10000`
+///
+/// 大小比较按寄存器宽度解释为有符号数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StopCondition {
+    /// 寄存器
+    pub register: Register,
+    /// 比较运算符
+    pub op: CompareOp,
+    /// 比较值
+    pub value: u64,
+}
+
+impl StopCondition {
+    /// 解析条件表达式
+    pub fn parse(text: &str) -> Result<Self> {
+        // 双字符运算符必须先匹配
+        const OPS: [(&str, CompareOp); 6] = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+        ];
+
+        let (pos, symbol, op) = OPS
+            .iter()
+            .find_map(|(symbol, op)| text.find(symbol).map(|pos| (pos, *symbol, *op)))
+            .ok_or_else(|| InterpreterError::ParseError(
+                format!("条件格式应为 REG==VALUE (支持 == != < <= > >=): {}", text)
+            ))?;
+
+        let register = Register::parse(text[..pos].trim())?;
+        let value_text = text[pos + symbol.len()..].trim();
+        let value = match value_text.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => value_text.parse::<i64>().ok().map(|v| v as u64),
+        }
+        .ok_or_else(|| InterpreterError::ParseError(format!("无效的比较值: {}", value_text)))?;
+
+        Ok(Self { register, op, value })
+    }
+
+    /// 判断条件在当前状态下是否成立
+    pub fn holds(&self, state: &CpuState) -> bool {
+        let actual = state.read_reg(self.register);
+        let (actual, expected) = if self.register.is_64bit() {
+            (actual as i64, self.value as i64)
+        } else {
+            (actual as u32 as i32 as i64, self.value as u32 as i32 as i64)
+        };
+        match self.op {
+            CompareOp::Eq => actual == expected,
+            CompareOp::Ne => actual != expected,
+            CompareOp::Lt => actual < expected,
+            CompareOp::Le => actual <= expected,
+            CompareOp::Gt => actual > expected,
+            CompareOp::Ge => actual >= expected,
+        }
+    }
+}
+
+/// 指令模拟器
+pub struct Emulator {
+    /// 当前 CPU 状态
+    pub state: CpuState,
+    /// 最近一次 step 的内存访问
+    pub accesses: Vec<MemoryAccess>,
+    /// 最近一次 step 的系统调用
+    pub last_syscall: Option<SyscallEvent>,
+    /// 程序通过 write 系统调用输出的内容
+    pub output: Vec<u8>,
+    /// 程序通过 exit 系统调用退出时的退出码
+    pub exit_code: Option<u64>,
+}
+
+impl Emulator {
+    /// 创建新的模拟器
+    pub fn new() -> Self {
+        Self::with_state(CpuState::default())
+    }
+
+    /// 使用给定的 CPU 状态创建模拟器
+    pub fn with_state(state: CpuState) -> Self {
+        Self {
+            state,
+            accesses: Vec::new(),
+            last_syscall: None,
+            output: Vec::new(),
+            exit_code: None,
+        }
+    }
+
+    /// 执行一条指令并更新 PC
+    pub fn step(&mut self, inst: &Instruction) -> Result<()> {
+        self.accesses.clear();
+        self.last_syscall = None;
+        let next_pc = inst.address.wrapping_add(4);
+        let ops = &inst.operands;
+
+        let target = match inst.instruction_type {
+            // 算术运算
+            InstructionType::ADD | InstructionType::SUB => {
+                let dest = Self::reg(ops, 0)?;
+                let a = self.value(ops, 1)?;
+                let b = self.shifted_value(ops, 2)?;
+                let result = if inst.instruction_type == InstructionType::ADD {
+                    a.wrapping_add(b)
+                } else {
+                    a.wrapping_sub(b)
+                };
+                self.state.write_reg(dest, result);
+                None
+            }
+            InstructionType::MUL => {
+                let dest = Self::reg(ops, 0)?;
+                let result = self.value(ops, 1)?.wrapping_mul(self.value(ops, 2)?);
+                self.state.write_reg(dest, result);
+                None
+            }
+            InstructionType::MADD | InstructionType::MSUB => {
+                let dest = Self::reg(ops, 0)?;
+                let product = self.value(ops, 1)?.wrapping_mul(self.value(ops, 2)?);
+                let acc = self.value(ops, 3)?;
+                let result = if inst.instruction_type == InstructionType::MADD {
+                    acc.wrapping_add(product)
+                } else {
+                    acc.wrapping_sub(product)
+                };
+                self.state.write_reg(dest, result);
+                None
+            }
+            InstructionType::UDIV | InstructionType::SDIV => {
+                let dest = Self::reg(ops, 0)?;
+                let a = self.value(ops, 1)?;
+                let b = self.value(ops, 2)?;
+                // AArch64 中除数为 0 时结果为 0，不会产生异常
+                let result = if b == 0 {
+                    0
+                } else if inst.instruction_type == InstructionType::UDIV {
+                    a / b
+                } else if dest.is_64bit() {
+                    (a as i64).wrapping_div(b as i64) as u64
+                } else {
+                    (a as u32 as i32).wrapping_div(b as u32 as i32) as u32 as u64
+                };
+                self.state.write_reg(dest, result);
+                None
+            }
+            InstructionType::NEG => {
+                let dest = Self::reg(ops, 0)?;
+                let value = self.shifted_value(ops, 1)?;
+                self.state.write_reg(dest, 0u64.wrapping_sub(value));
+                None
+            }
+
+            // 逻辑运算
+            InstructionType::AND | InstructionType::ORR | InstructionType::EOR
+            | InstructionType::BIC | InstructionType::ORN | InstructionType::EON => {
+                let dest = Self::reg(ops, 0)?;
+                let a = self.value(ops, 1)?;
+                let b = self.shifted_value(ops, 2)?;
+                let result = match inst.instruction_type {
+                    InstructionType::AND => a & b,
+                    InstructionType::ORR => a | b,
+                    InstructionType::EOR => a ^ b,
+                    InstructionType::BIC => a & !b,
+                    InstructionType::ORN => a | !b,
+                    _ => a ^ !b,
+                };
+                self.state.write_reg(dest, result);
+                None
+            }
+            InstructionType::MVN => {
+                let dest = Self::reg(ops, 0)?;
+                let value = self.shifted_value(ops, 1)?;
+                self.state.write_reg(dest, !value);
+                None
+            }
+
+            // 移位
+            InstructionType::LSL | InstructionType::LSR
+            | InstructionType::ASR | InstructionType::ROR => {
+                let dest = Self::reg(ops, 0)?;
+                let value = self.value(ops, 1)?;
+                let amount = self.value(ops, 2)?;
+                let kind = format!("{:?}", inst.instruction_type).to_lowercase();
+                let result = Self::apply_shift(&kind, value, amount, dest.is_64bit());
+                self.state.write_reg(dest, result);
+                None
+            }
+
+            // 数据移动
+            InstructionType::MOV | InstructionType::MOVZ => {
+                let dest = Self::reg(ops, 0)?;
+                let value = self.shifted_value(ops, 1)?;
+                self.state.write_reg(dest, value);
+                None
+            }
+            InstructionType::MOVN => {
+                let dest = Self::reg(ops, 0)?;
+                let value = self.shifted_value(ops, 1)?;
+                self.state.write_reg(dest, !value);
+                None
+            }
+            InstructionType::MOVK => {
+                let dest = Self::reg(ops, 0)?;
+                let imm = self.value(ops, 1)? & 0xffff;
+                let shift = Self::shift_amount(ops, 2).unwrap_or(0);
+                let width = if dest.is_64bit() { 64 } else { 32 };
+                if !shift.is_multiple_of(16) || shift >= width {
+                    return Err(InterpreterError::InvalidOperand(
+                        format!("MOVK 的移位量应为 0/16{}: lsl #{}", if width == 64 { "/32/48" } else { "" }, shift)
+                    ));
+                }
+                let old = self.state.read_reg(dest);
+                let result = (old & !(0xffff << shift)) | (imm << shift);
+                self.state.write_reg(dest, result);
+                None
+            }
+
+            // 比较
+            InstructionType::CMP | InstructionType::CMN | InstructionType::TST => {
+                let first = Self::reg(ops, 0)?;
+                let is_64bit = first.is_64bit();
+                let a = self.value(ops, 0)?;
+                let b = self.shifted_value(ops, 1)?;
+                self.state.flags = match inst.instruction_type {
+                    InstructionType::CMP => Self::add_with_carry(a, !b, true, is_64bit).1,
+                    InstructionType::CMN => Self::add_with_carry(a, b, false, is_64bit).1,
+                    _ => {
+                        let mut flags = ConditionFlags::new();
+                        flags.set_nz(a & b, is_64bit);
+                        flags
+                    }
+                };
+                None
+            }
+
+            // 设置标志位的运算：结果写入 Rd，标志位与对应的比较相同
+            InstructionType::ADDS | InstructionType::SUBS | InstructionType::ANDS => {
+                let dest = Self::reg(ops, 0)?;
+                let is_64bit = dest.is_64bit();
+                let a = self.value(ops, 1)?;
+                let b = self.shifted_value(ops, 2)?;
+                let (result, flags) = match inst.instruction_type {
+                    InstructionType::ADDS => Self::add_with_carry(a, b, false, is_64bit),
+                    InstructionType::SUBS => Self::add_with_carry(a, !b, true, is_64bit),
+                    _ => {
+                        let mut flags = ConditionFlags::new();
+                        flags.set_nz(a & b, is_64bit);
+                        (a & b, flags)
+                    }
+                };
+                self.state.write_reg(dest, result);
+                self.state.flags = flags;
+                None
+            }
+
+            // 加载存储
+            InstructionType::LDR | InstructionType::LDUR | InstructionType::LDRB
+            | InstructionType::LDRH | InstructionType::LDRSB | InstructionType::LDRSH
+            | InstructionType::LDRSW => {
+                let dest = Self::reg(ops, 0)?;
+                let address = self.memory_address(ops, 1)?;
+                let (size, signed) = match inst.instruction_type {
+                    InstructionType::LDRB => (1, false),
+                    InstructionType::LDRH => (2, false),
+                    InstructionType::LDRSB => (1, true),
+                    InstructionType::LDRSH => (2, true),
+                    InstructionType::LDRSW => (4, true),
+                    _ => (Self::reg_size(dest)?, false),
+                };
+                let mut value = self.load(address, size)?;
+                if signed {
+                    let shift = 64 - size * 8;
+                    value = (((value << shift) as i64) >> shift) as u64;
+                }
+                self.state.write_reg(dest, value);
+                None
+            }
+            InstructionType::STR | InstructionType::STUR
+            | InstructionType::STRB | InstructionType::STRH => {
+                let src = Self::reg(ops, 0)?;
+                let address = self.memory_address(ops, 1)?;
+                let size = match inst.instruction_type {
+                    InstructionType::STRB => 1,
+                    InstructionType::STRH => 2,
+                    _ => Self::reg_size(src)?,
+                };
+                let value = self.state.read_reg(src);
+                self.store(address, size, value)?;
+                None
+            }
+            InstructionType::LDP => {
+                let first = Self::reg(ops, 0)?;
+                let second = Self::reg(ops, 1)?;
+                let size = Self::reg_size(first)?;
+                let address = self.memory_address(ops, 2)?;
+                let v1 = self.load(address, size)?;
+                let v2 = self.load(address.wrapping_add(size as u64), size)?;
+                self.state.write_reg(first, v1);
+                self.state.write_reg(second, v2);
+                None
+            }
+            InstructionType::STP => {
+                let first = Self::reg(ops, 0)?;
+                let second = Self::reg(ops, 1)?;
+                let size = Self::reg_size(first)?;
+                let address = self.memory_address(ops, 2)?;
+                let v1 = self.state.read_reg(first);
+                let v2 = self.state.read_reg(second);
+                self.store(address, size, v1)?;
+                self.store(address.wrapping_add(size as u64), size, v2)?;
+                None
+            }
+
+            // 分支
+            InstructionType::B => Some(Self::branch_target(ops, 0)?),
+            InstructionType::BL => {
+                self.state.write_reg(Register::X30, next_pc);
+                Some(Self::branch_target(ops, 0)?)
+            }
+            InstructionType::BR => Some(self.value(ops, 0)?),
+            InstructionType::BLR => {
+                let target = self.value(ops, 0)?;
+                self.state.write_reg(Register::X30, next_pc);
+                Some(target)
+            }
+            InstructionType::RET => {
+                let reg = if ops.is_empty() { Register::X30 } else { Self::reg(ops, 0)? };
+                Some(self.state.read_reg(reg))
+            }
+            InstructionType::CBZ | InstructionType::CBNZ => {
+                let value = self.value(ops, 0)?;
+                let taken = (value == 0) == (inst.instruction_type == InstructionType::CBZ);
+                if taken { Some(Self::branch_target(ops, 1)?) } else { None }
+            }
+            InstructionType::TBZ | InstructionType::TBNZ => {
+                let value = self.value(ops, 0)?;
+                let bit = self.value(ops, 1)?;
+                let is_zero = (value >> (bit & 63)) & 1 == 0;
+                let taken = is_zero == (inst.instruction_type == InstructionType::TBZ);
+                if taken { Some(Self::branch_target(ops, 2)?) } else { None }
+            }
+            InstructionType::NOP => None,
+            InstructionType::SVC => {
+                let event = syscall::handle(&mut self.state, &mut self.output);
+                if event.exit_code.is_some() {
+                    self.exit_code = event.exit_code;
+                }
+                self.last_syscall = Some(event);
+                None
+            }
+            other => {
+                if let Some(cond) = Self::branch_condition(other) {
+                    if cond.evaluate(&self.state.flags) {
+                        Some(Self::branch_target(ops, 0)?)
+                    } else {
+                        None
+                    }
+                } else {
+                    return Err(InterpreterError::Unimplemented(
+                        format!("模拟器暂不支持 {:?} 指令", other)
+                    ));
+                }
+            }
+        };
+
+        self.state.pc = target.unwrap_or(next_pc);
+        Ok(())
+    }
+
+    /// 条件分支指令对应的条件码
+    fn branch_condition(inst_type: InstructionType) -> Option<Condition> {
+        match inst_type {
+            InstructionType::BEQ => Some(Condition::EQ),
+            InstructionType::BNE => Some(Condition::NE),
+            InstructionType::BCS => Some(Condition::CS),
+            InstructionType::BCC => Some(Condition::CC),
+            InstructionType::BMI => Some(Condition::MI),
+            InstructionType::BPL => Some(Condition::PL),
+            InstructionType::BVS => Some(Condition::VS),
+            InstructionType::BVC => Some(Condition::VC),
+            InstructionType::BHI => Some(Condition::HI),
+            InstructionType::BLS => Some(Condition::LS),
+            InstructionType::BGE => Some(Condition::GE),
+            InstructionType::BLT => Some(Condition::LT),
+            InstructionType::BGT => Some(Condition::GT),
+            InstructionType::BLE => Some(Condition::LE),
+            _ => None,
+        }
+    }
+
+    /// 获取寄存器操作数
+    fn reg(ops: &[Operand], idx: usize) -> Result<Register> {
+        match ops.get(idx) {
+            Some(Operand::Register(reg)) => Ok(*reg),
+            other => Err(InterpreterError::InvalidOperand(
+                format!("第 {} 个操作数应为寄存器: {:?}", idx + 1, other)
+            )),
+        }
+    }
+
+    /// 获取寄存器或立即数操作数的值
+    fn value(&self, ops: &[Operand], idx: usize) -> Result<u64> {
+        match ops.get(idx) {
+            Some(Operand::Register(reg)) => Ok(self.state.read_reg(*reg)),
+            Some(Operand::Immediate(imm)) => Ok(*imm as u64),
+            other => Err(InterpreterError::InvalidOperand(
+                format!("第 {} 个操作数应为寄存器或立即数: {:?}", idx + 1, other)
+            )),
+        }
+    }
+
+    /// 获取操作数的值，并应用紧随其后的移位/扩展修饰（如 `lsl #3`、`sxtw #2`）
+    fn shifted_value(&self, ops: &[Operand], idx: usize) -> Result<u64> {
+        let value = self.value(ops, idx)?;
+        if let Some(Operand::Label(modifier)) = ops.get(idx + 1) {
+            let mut parts = modifier.split_whitespace();
+            let kind = parts.next().unwrap_or("").to_lowercase();
+            let amount = match parts.next() {
+                Some(amount) => amount.trim_start_matches('#').parse::<u64>().map_err(|_| {
+                    InterpreterError::InvalidOperand(format!("无效的移位量: {}", modifier))
+                })?,
+                None => 0,
+            };
+            let is_64bit = !matches!(ops.first(), Some(Operand::Register(r)) if !r.is_64bit());
+            if let Some(extended) = Self::apply_extend(&kind, value) {
+                return Ok(Self::apply_shift("lsl", extended, amount, is_64bit));
+            }
+            return Ok(Self::apply_shift(&kind, value, amount, is_64bit));
+        }
+        Ok(value)
+    }
+
+    /// 执行寄存器扩展（uxtb/sxtw 等），不是扩展修饰时返回 None
+    fn apply_extend(kind: &str, value: u64) -> Option<u64> {
+        let (signed, bits) = match kind {
+            "uxtb" => (false, 8),
+            "uxth" => (false, 16),
+            "uxtw" => (false, 32),
+            "uxtx" => (false, 64),
+            "sxtb" => (true, 8),
+            "sxth" => (true, 16),
+            "sxtw" => (true, 32),
+            "sxtx" => (true, 64),
+            _ => return None,
+        };
+        if bits == 64 {
+            return Some(value);
+        }
+        let shift = 64 - bits;
+        Some(if signed {
+            (((value << shift) as i64) >> shift) as u64
+        } else {
+            value & ((1u64 << bits) - 1)
+        })
+    }
+
+    /// 解析 `lsl #N` 形式修饰的移位量
+    fn shift_amount(ops: &[Operand], idx: usize) -> Option<u64> {
+        match ops.get(idx) {
+            Some(Operand::Label(modifier)) => modifier
+                .split_whitespace()
+                .nth(1)
+                .and_then(|amount| amount.trim_start_matches('#').parse().ok()),
+            _ => None,
+        }
+    }
+
+    /// 执行移位运算
+    fn apply_shift(kind: &str, value: u64, amount: u64, is_64bit: bool) -> u64 {
+        let width = if is_64bit { 64 } else { 32 };
+        let amount = (amount % width) as u32;
+        let mask = if is_64bit { u64::MAX } else { 0xffff_ffff };
+        let value = value & mask;
+        let result = match kind {
+            "lsl" => value.wrapping_shl(amount),
+            "lsr" => value.wrapping_shr(amount),
+            "asr" if is_64bit => ((value as i64) >> amount) as u64,
+            "asr" => ((value as u32 as i32) >> amount) as u32 as u64,
+            "ror" if is_64bit => value.rotate_right(amount),
+            "ror" => (value as u32).rotate_right(amount) as u64,
+            _ => value,
+        };
+        result & mask
+    }
+
+    /// 读取内存并记录访问
+    fn load(&mut self, address: u64, size: usize) -> Result<u64> {
+        let value = self.state.load(address, size)?;
+        self.accesses.push(MemoryAccess { address, size, value, write: false });
+        Ok(value)
+    }
+
+    /// 写入内存并记录访问
+    fn store(&mut self, address: u64, size: usize, value: u64) -> Result<()> {
+        self.state.store(address, size, value)?;
+        let mask = if size >= 8 { u64::MAX } else { (1u64 << (size * 8)) - 1 };
+        self.accesses.push(MemoryAccess { address, size, value: value & mask, write: true });
+        Ok(())
+    }
+
+    /// 计算内存操作数的地址，并处理前/后变址的基址写回
+    fn memory_address(&mut self, ops: &[Operand], idx: usize) -> Result<u64> {
+        let (base, offset, index, pre_indexed) = match ops.get(idx) {
+            Some(Operand::Memory { base, offset, index, pre_indexed, .. }) => {
+                (*base, *offset, *index, *pre_indexed)
+            }
+            other => {
+                return Err(InterpreterError::InvalidOperand(
+                    format!("第 {} 个操作数应为内存操作数: {:?}", idx + 1, other)
+                ))
+            }
+        };
+
+        let base_value = self.state.read_reg(base);
+        let mut address = base_value.wrapping_add(offset.unwrap_or(0) as u64);
+        if let Some(index) = index {
+            address = address.wrapping_add(self.state.read_reg(index));
+        }
+
+        if pre_indexed {
+            self.state.write_reg(base, address);
+        } else if let Some(Operand::Immediate(post)) = ops.get(idx + 1) {
+            // 后变址：[base], #imm
+            self.state.write_reg(base, base_value.wrapping_add(*post as u64));
+        }
+
+        Ok(address)
+    }
+
+    /// 寄存器的访问宽度（字节）
+    fn reg_size(reg: Register) -> Result<usize> {
+        if reg.is_fp() {
+            return Err(InterpreterError::Unimplemented(
+                format!("模拟器暂不支持浮点寄存器 {:?}", reg)
+            ));
+        }
+        Ok(if reg.is_64bit() { 8 } else { 4 })
+    }
+
+    /// 解析分支目标地址（objdump 格式: `40 <func+0x40>`）
+    fn branch_target(ops: &[Operand], idx: usize) -> Result<u64> {
+        match ops.get(idx) {
+            Some(Operand::Label(label)) => {
+                let addr = label.split_whitespace().next().unwrap_or("");
+                let addr = addr.trim_start_matches("0x");
+                u64::from_str_radix(addr, 16).map_err(|_| {
+                    InterpreterError::ExecutionError(format!("无法解析跳转目标: {}", label))
+                })
+            }
+            Some(Operand::Immediate(imm)) => Ok(*imm as u64),
+            other => Err(InterpreterError::InvalidOperand(
+                format!("第 {} 个操作数应为跳转目标: {:?}", idx + 1, other)
+            )),
+        }
+    }
+
+    /// 带进位加法，返回结果和 NZCV 标志
+    fn add_with_carry(a: u64, b: u64, carry_in: bool, is_64bit: bool) -> (u64, ConditionFlags) {
+        let mut flags = ConditionFlags::new();
+        let carry = carry_in as u64;
+        if is_64bit {
+            let unsigned = a as u128 + b as u128 + carry as u128;
+            let result = unsigned as u64;
+            let signed = a as i64 as i128 + b as i64 as i128 + carry as i128;
+            flags.set_nz(result, true);
+            flags.c = unsigned > u64::MAX as u128;
+            flags.v = signed != result as i64 as i128;
+            (result, flags)
+        } else {
+            let (a, b) = (a as u32, b as u32);
+            let unsigned = a as u64 + b as u64 + carry;
+            let result = unsigned as u32;
+            let signed = a as i32 as i64 + b as i32 as i64 + carry as i64;
+            flags.set_nz(result as u64, false);
+            flags.c = unsigned > u32::MAX as u64;
+            flags.v = signed != result as i32 as i64;
+            (result as u64, flags)
+        }
+    }
+}
+
+impl Emulator {
+    /// 查找当前 PC 对应的条目；函数已返回或 PC 离开函数范围时返回结束原因
+    pub fn current_entry<'a>(&self, entries: &'a [DumpEntry]) -> std::result::Result<&'a DumpEntry, TraceEnd> {
+        let pc = self.state.pc;
+        if let Some(code) = self.exit_code {
+            return Err(TraceEnd::Exited(code));
+        }
+        if pc == RETURN_ADDRESS {
+            return Err(TraceEnd::Returned(self.state.x[0]));
+        }
+        entries
+            .iter()
+            .filter(|e| !e.asm_instruction.is_empty())
+            .find(|e| u64::from_str_radix(&e.address, 16).ok() == Some(pc))
+            .ok_or(TraceEnd::LeftFunction(pc))
+    }
+
+    /// 在函数范围内执行一条指令
+    ///
+    /// 对函数外部的 BL 调用不做模拟，直接跳到下一条指令，返回值表示是否跳过了调用
+    pub fn step_in_function(&mut self, entries: &[DumpEntry], inst: &Instruction) -> Result<bool> {
+        self.step(inst)?;
+
+        let next_pc = self.state.pc;
+        let inside = entries
+            .iter()
+            .any(|e| !e.asm_instruction.is_empty() && u64::from_str_radix(&e.address, 16).ok() == Some(next_pc));
+        if inst.instruction_type == InstructionType::BL && !inside {
+            self.state.pc = inst.address.wrapping_add(4);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// 从当前 PC 开始执行函数直到结束，记录每一步的状态变化
+    pub fn trace_function(&mut self, entries: &[DumpEntry], max_steps: usize) -> Trace {
+        let mut steps = Vec::new();
+
+        let end = loop {
+            if steps.len() >= max_steps {
+                break TraceEnd::StepLimit(max_steps);
+            }
+            let entry = match self.current_entry(entries) {
+                Ok(entry) => entry,
+                Err(end) => break end,
+            };
+            let Some(inst) = entry.parsed_instruction.as_ref() else {
+                break TraceEnd::Unparsed(entry.asm_instruction.clone());
+            };
+
+            let before = self.state.clone();
+            let skipped_call = match self.step_in_function(entries, inst) {
+                Ok(skipped) => skipped,
+                Err(e) => break TraceEnd::Failed(e.to_string()),
+            };
+
+            steps.push(TraceStep {
+                address: before.pc,
+                asm_instruction: entry.asm_instruction.clone(),
+                changes: self.state.changes_from(&before),
+                accesses: self.accesses.clone(),
+                syscall: self.last_syscall.clone(),
+                nzcv: self.state.nzcv(),
+                skipped_call,
+            });
+        };
+
+        Trace { steps, end }
+    }
+}
+
+impl Default for Emulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::AssemblyParser;
+
+    fn run(code: &str) -> Emulator {
+        let mut parser = AssemblyParser::new();
+        let instructions = parser.parse(code).unwrap();
+        let mut emu = Emulator::new();
+        for inst in &instructions {
+            emu.step(inst).unwrap();
+        }
+        emu
+    }
+
+    #[test]
+    fn test_arithmetic_and_move() {
+        let emu = run("mov x1, #5\nmov x2, #7\nadd x0, x1, x2\nsub w3, w1, #6");
+        assert_eq!(emu.state.read_reg(Register::X0), 12);
+        assert_eq!(emu.state.read_reg(Register::X3), 0xffff_ffff);
+        assert_eq!(emu.state.pc, 16);
+    }
+
+    #[test]
+    fn test_extended_register_operand() {
+        let emu = run("mov x0, #0x100\nmov w1, #-1\nadd x3, x0, w1, sxtw #2");
+        assert_eq!(emu.state.read_reg(Register::X3), 0xfc);
+    }
+
+    #[test]
+    fn test_movk_shift() {
+        let emu = run("mov x0, #0x1234\nmovk x0, #0x5678, lsl #48");
+        assert_eq!(emu.state.read_reg(Register::X0), 0x5678_0000_0000_1234);
+
+        let mut parser = AssemblyParser::new();
+        let mut emu = Emulator::new();
+        for code in ["movk x0, #1, lsl #64", "movk x0, #1, lsl #8", "movk w0, #1, lsl #32"] {
+            let inst = &parser.parse(code).unwrap()[0];
+            assert!(matches!(emu.step(inst), Err(InterpreterError::InvalidOperand(_))), "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_stack_load_store() {
+        let emu = run("mov x0, #42\nstr x0, [sp, #-16]!\nldr x1, [sp]\nadd sp, sp, #16");
+        assert_eq!(emu.state.read_reg(Register::X1), 42);
+        assert_eq!(emu.state.sp, STACK_TOP);
+    }
+
+    #[test]
+    fn test_compare_and_branch() {
+        let mut parser = AssemblyParser::new();
+        let insts = parser.parse("mov w0, #3\ncmp w0, #3\nb.eq 40 <f+0x40>").unwrap();
+        let mut emu = Emulator::new();
+        for inst in &insts {
+            emu.step(inst).unwrap();
+        }
+        assert!(emu.state.flags.z);
+        assert!(emu.state.flags.c);
+        assert_eq!(emu.state.pc, 0x40);
+    }
+
+    #[test]
+    fn test_flag_setting_arithmetic() {
+        let emu = run("mov w0, #1\nsubs w0, w0, #1");
+        assert_eq!(emu.state.read_reg(Register::X0), 0);
+        assert!(emu.state.flags.z && emu.state.flags.c);
+
+        let emu = run("mov x1, #-1\nadds x0, x1, #1\nands w2, w1, #0x80000000");
+        assert_eq!(emu.state.read_reg(Register::X0), 0);
+        assert!(emu.state.flags.n && !emu.state.flags.z && !emu.state.flags.c);
+        assert_eq!(emu.state.read_reg(Register::X2), 0x8000_0000);
+    }
+
+    #[test]
+    fn test_trace_function() {
+        let content = r#"
+0000000000000000 <double_it>:
+   0:   a9bf7bfd    stp x29, x30, [sp, #-16]!
+   4:   8b000000    add x0, x0, x0
+   8:   94000000    bl 100 <printf>
+   c:   a8c17bfd    ldp x29, x30, [sp], #16
+  10:   d65f03c0    ret
+"#;
+        let parser = crate::objdump::ObjdumpParser::new(content.to_string());
+        let entries = parser.extract_function_data("double_it").unwrap();
+
+        let mut emu = Emulator::new();
+        emu.state.write_reg(Register::X0, 21);
+        let trace = emu.trace_function(&entries, 100);
+
+        assert_eq!(trace.steps.len(), 5);
+        assert_eq!(trace.steps[1].changes[0], StateChange { name: String::from("X0"), old: 21, new: 42 });
+        assert!(trace.steps[2].skipped_call);
+        assert_eq!(trace.end, TraceEnd::Returned(42));
+    }
+
+    #[test]
+    fn test_memory_buffers() {
+        let (address, bytes) = parse_buffer("0x10000:i32=1,-2,3*2").unwrap();
+        assert_eq!(address, 0x10000);
+        assert_eq!(bytes.len(), 16);
+
+        let mut state = CpuState::default();
+        state.map_buffer(address, bytes).unwrap();
+        assert!(state.map_buffer(0x10008, vec![0; 4]).is_err());
+
+        let mut emu = Emulator::with_state(state);
+        let mut parser = AssemblyParser::new();
+        for inst in &parser.parse("mov x0, #0x10000\nldr w1, [x0, #4]\nstr w1, [x0, #12]").unwrap() {
+            emu.step(inst).unwrap();
+        }
+        assert_eq!(emu.state.read_reg(Register::W1), 0xffff_fffe);
+        assert_eq!(emu.state.load(0x1000c, 4).unwrap(), 0xffff_fffe);
+        assert!(emu.accesses[0].write);
+
+        // 未映射的地址
+        assert!(emu.state.load(0x20000, 4).is_err());
+    }
+
+    #[test]
+    fn test_svc_program() {
+        let content = r#"
+0000000000000000 <_start>:
+   0:   d2800020    mov x0, #0x1
+   4:   d2a00021    mov x1, #0x10000
+   8:   d2800062    mov x2, #0x3
+   c:   d2800808    mov x8, #0x40
+  10:   d4000001    svc #0x0
+  14:   d2800000    mov x0, #0x0
+  18:   d2800ba8    mov x8, #0x5d
+  1c:   d4000001    svc #0x0
+"#;
+        let parser = crate::objdump::ObjdumpParser::new(content.to_string());
+        let entries = parser.extract_function_data("_start").unwrap();
+
+        let mut state = CpuState::default();
+        let (address, bytes) = parse_buffer("0x10000:str=hi\\n").unwrap();
+        state.map_buffer(address, bytes).unwrap();
+        let mut emu = Emulator::with_state(state);
+        let trace = emu.trace_function(&entries, 100);
+
+        assert_eq!(trace.end, TraceEnd::Exited(0));
+        assert_eq!(emu.output, b"hi\n");
+        assert!(trace.steps[4].syscall.as_ref().unwrap().stubbed);
+    }
+
+    #[test]
+    fn test_stop_condition() {
+        let cond = StopCondition::parse("x0==0").unwrap();
+        assert_eq!(cond.op, CompareOp::Eq);
+
+        let emu = run("mov x0, #5");
+        assert!(!cond.holds(&emu.state));
+        assert!(StopCondition::parse("x0>=5").unwrap().holds(&emu.state));
+
+        let emu = run("mov w1, #-1");
+        assert!(StopCondition::parse("w1 < 0").unwrap().holds(&emu.state));
+        assert!(StopCondition::parse("w1==-1").unwrap().holds(&emu.state));
+        assert!(StopCondition::parse("x1 = 1").is_err());
+    }
+
+    #[test]
+    fn test_state_changes() {
+        let before = CpuState::default();
+        let emu = run("mov x2, #9\ncmp x2, #9\nret");
+        let changes = emu.state.changes_from(&before);
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0], StateChange { name: String::from("X2"), old: 0, new: 9 });
+        assert_eq!(changes[1].name, "NZCV");
+        assert_eq!(emu.state.pc, RETURN_ADDRESS);
+    }
+}
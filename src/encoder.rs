@@ -0,0 +1,938 @@
+//! 指令编码
+//!
+//! 把解析得到的 [`Instruction`] 重新编码为 32 位 AArch64 机器码，与 dump 中的机器码比较，
+//! 可以自动发现解析器的错误（操作数顺序、漏掉的移位、立即数换算）。覆盖范围与
+//! [`decoder`](crate::decoder) 相同，其余指令返回 None。
+//!
+//! 可重定位目标文件（`.o`）中跳转和 `adrp` 的偏移在链接前为 0，比较时偏移字段为 0 的差异不算不一致。
+
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::objdump::DumpEntry;
+use crate::register::{Condition, Register};
+
+/// 通用寄存器的编号和宽度（SP 与零寄存器都是 31）
+fn gpr(operand: &Operand) -> Option<(u32, bool)> {
+    let Operand::Register(reg) = operand else {
+        return None;
+    };
+    match reg {
+        Register::SP | Register::XZR => Some((31, true)),
+        Register::WZR => Some((31, false)),
+        _ => Some((reg.index()? as u32, reg.is_64bit())),
+    }
+}
+
+/// 不是 SP 的通用寄存器（SP 只能出现在允许它的位置）
+fn gpr_no_sp(operand: &Operand) -> Option<(u32, bool)> {
+    match operand {
+        Operand::Register(Register::SP) => None,
+        _ => gpr(operand),
+    }
+}
+
+/// 不是零寄存器的通用寄存器
+fn gpr_or_sp(operand: &Operand) -> Option<(u32, bool)> {
+    match operand {
+        Operand::Register(Register::XZR | Register::WZR) => None,
+        _ => gpr(operand),
+    }
+}
+
+fn is_sp(operand: &Operand) -> bool {
+    matches!(operand, Operand::Register(Register::SP))
+}
+
+/// 标量浮点寄存器的编号和浮点类型字段（S=0、D=1、H=3）
+fn fpr(operand: &Operand) -> Option<(u32, u32)> {
+    let Operand::Register(reg) = operand else {
+        return None;
+    };
+    let ftype = match format!("{:?}", reg).chars().next()? {
+        'S' => 0,
+        'D' => 1,
+        'H' => 3,
+        _ => return None,
+    };
+    Some((reg.fp_index()? as u32, ftype))
+}
+
+fn immediate(operand: &Operand) -> Option<i64> {
+    match operand {
+        Operand::Immediate(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// 跳转目标地址
+fn target(operand: &Operand) -> Option<u64> {
+    let Operand::Label(label) = operand else {
+        return None;
+    };
+    let first = label.split_whitespace().next()?;
+    u64::from_str_radix(first.trim_start_matches("0x"), 16).ok()
+}
+
+/// 移位或扩展修饰（`lsl #2`、`sxtw`、`uxtw #2`）：返回名称和移位量
+fn modifier(operand: Option<&Operand>) -> Option<(&str, u32)> {
+    let Some(Operand::Label(text)) = operand else {
+        return None;
+    };
+    let mut parts = text.split_whitespace();
+    let name = parts.next()?;
+    let amount = match parts.next() {
+        Some(n) => n.trim_start_matches('#').parse().ok()?,
+        None => 0,
+    };
+    Some((name, amount))
+}
+
+fn shift_kind(name: &str) -> Option<u32> {
+    ["lsl", "lsr", "asr", "ror"].iter().position(|s| *s == name).map(|i| i as u32)
+}
+
+fn extend_kind(name: &str) -> Option<u32> {
+    ["uxtb", "uxth", "uxtw", "uxtx", "sxtb", "sxth", "sxtw", "sxtx"].iter().position(|s| *s == name).map(|i| i as u32)
+}
+
+fn condition_code(condition: Condition) -> u32 {
+    use Condition::*;
+    [EQ, NE, CS, CC, MI, PL, VS, VC, HI, LS, GE, LT, GT, LE, AL].iter().position(|c| *c == condition).unwrap_or(14) as u32
+}
+
+/// 把 `value` 限制在 `len` 位有符号数范围内并截断
+fn signed_field(value: i64, len: u32) -> Option<u32> {
+    let limit = 1i64 << (len - 1);
+    (-limit..limit).contains(&value).then(|| (value as u32) & ((1u32 << len) - 1))
+}
+
+fn sf(is_64bit: bool) -> u32 {
+    u32::from(is_64bit) << 31
+}
+
+/// 编码逻辑立即数，返回 (N, immr, imms)；全 0、全 1 或不是重复的循环连续 1 时返回 None
+pub fn encode_bit_masks(value: u64, is_64bit: bool) -> Option<(u32, u32, u32)> {
+    let width = if is_64bit { 64 } else { 32 };
+    let value = if is_64bit { value } else { value & 0xffff_ffff };
+    let all = if is_64bit { u64::MAX } else { 0xffff_ffff };
+    if value == 0 || value == all {
+        return None;
+    }
+    // 找到最小的重复单元
+    let mut size = width;
+    while size > 2 {
+        let half = size / 2;
+        let mask = (1u64 << half) - 1;
+        if (value & mask) != ((value >> half) & mask) {
+            break;
+        }
+        size = half;
+    }
+    let mask = if size == 64 { u64::MAX } else { (1u64 << size) - 1 };
+    let element = value & mask;
+    let ones = element.count_ones();
+    let run = (1u64 << ones) - 1;
+    let rotate = (0..size).find(|&r| {
+        let rotated = if r == 0 { run } else { ((run >> r) | (run << (size - r))) & mask };
+        rotated == element
+    })?;
+    let n = u32::from(size == 64);
+    let imms = ((!(2 * size - 1)) & 0x3f) | (ones - 1);
+    Some((n, rotate, imms))
+}
+
+/// 能用一条 MOVZ 表示时返回 (hw, imm16)
+fn move_wide(value: u64, is_64bit: bool) -> Option<(u32, u32)> {
+    let halves = if is_64bit { 4 } else { 2 };
+    (0..halves).find_map(|hw| {
+        let imm16 = (value >> (hw * 16)) & 0xffff;
+        (value == imm16 << (hw * 16)).then_some((hw, imm16 as u32))
+    })
+}
+
+/// 编码一条指令，不支持的指令或操作数无法编码时返回 None
+pub fn encode(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    match inst.instruction_type {
+        ADD | SUB | ADDS | SUBS | CMP | CMN | NEG | MOV | MVN => arithmetic(inst),
+        AND | ORR | EOR | BIC | ORN | EON | ANDS | TST => logical(inst),
+        MOVZ | MOVN | MOVK => {
+            let (rd, wide) = gpr_no_sp(ops.first()?)?;
+            let imm16 = u32::try_from(immediate(ops.get(1)?)?).ok().filter(|v| *v <= 0xffff)?;
+            let hw = match modifier(ops.get(2)) {
+                Some(("lsl", amount)) if amount % 16 == 0 && amount < if wide { 64 } else { 32 } => amount / 16,
+                Some(_) => return None,
+                None => 0,
+            };
+            let opc = match inst.instruction_type {
+                MOVN => 0b00,
+                MOVZ => 0b10,
+                _ => 0b11,
+            };
+            Some(sf(wide) | opc << 29 | 0x1280_0000 | hw << 21 | imm16 << 5 | rd)
+        }
+        LSL | LSR | ASR | ROR | UBFX | SBFX | UBFIZ | SBFIZ | BFI | BFXIL | EXTR => shift_bitfield(inst),
+        MUL | MADD | MSUB | SMULL | UMULL => {
+            let (rd, wide) = gpr_no_sp(ops.first()?)?;
+            let (rn, _) = gpr_no_sp(ops.get(1)?)?;
+            let (rm, _) = gpr_no_sp(ops.get(2)?)?;
+            let (op31, o0, ra) = match inst.instruction_type {
+                MUL => (0b000, 0, 31),
+                MADD => (0b000, 0, gpr_no_sp(ops.get(3)?)?.0),
+                MSUB => (0b000, 1, gpr_no_sp(ops.get(3)?)?.0),
+                SMULL => (0b001, 0, 31),
+                _ => (0b101, 0, 31),
+            };
+            Some(sf(wide) | 0x1b00_0000 | op31 << 21 | rm << 16 | o0 << 15 | ra << 10 | rn << 5 | rd)
+        }
+        UDIV | SDIV | CRC32B | CRC32H | CRC32W | CRC32X | CRC32CB | ADC | SBC => {
+            let (rd, wide) = gpr_no_sp(ops.first()?)?;
+            let (rn, _) = gpr_no_sp(ops.get(1)?)?;
+            let (rm, _) = gpr_no_sp(ops.get(2)?)?;
+            let (base, opcode, wide) = match inst.instruction_type {
+                UDIV => (0x1ac0_0000, 0b000010, wide),
+                SDIV => (0x1ac0_0000, 0b000011, wide),
+                CRC32B => (0x1ac0_0000, 0b010000, false),
+                CRC32H => (0x1ac0_0000, 0b010001, false),
+                CRC32W => (0x1ac0_0000, 0b010010, false),
+                CRC32X => (0x1ac0_0000, 0b010011, true),
+                CRC32CB => (0x1ac0_0000, 0b010100, false),
+                ADC => (0x1a00_0000, 0, wide),
+                _ => (0x5a00_0000, 0, wide),
+            };
+            Some(sf(wide) | base | rm << 16 | opcode << 10 | rn << 5 | rd)
+        }
+        RBIT | REV16 | REV32 | REV | CLZ | CLS => {
+            let (rd, wide) = gpr_no_sp(ops.first()?)?;
+            let (rn, _) = gpr_no_sp(ops.get(1)?)?;
+            let opcode = match (inst.instruction_type, wide) {
+                (RBIT, _) => 0,
+                (REV16, _) => 1,
+                (REV32, true) | (REV, false) => 2,
+                (REV, true) => 3,
+                (CLZ, _) => 4,
+                (CLS, _) => 5,
+                _ => return None,
+            };
+            Some(sf(wide) | 0x5ac0_0000 | opcode << 10 | rn << 5 | rd)
+        }
+        CSEL | CSINC | CSINV | CSNEG | CSET | CSETM | CINC | CINV | CNEG => conditional_select(inst),
+        CCMP | CCMN => {
+            let (rn, wide) = gpr_no_sp(ops.first()?)?;
+            let (second, is_imm) = match ops.get(1)? {
+                Operand::Immediate(v) => (u32::try_from(*v).ok().filter(|v| *v < 32)?, 1),
+                other => (gpr_no_sp(other)?.0, 0),
+            };
+            let nzcv = u32::try_from(immediate(ops.get(2)?)?).ok().filter(|v| *v < 16)?;
+            let op = u32::from(inst.instruction_type == CCMP);
+            let cond = condition_code(inst.condition?);
+            Some(sf(wide) | op << 30 | 0x3a40_0000 | second << 16 | cond << 12 | is_imm << 11 | rn << 5 | nzcv)
+        }
+        B | BL | BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE | CBZ | CBNZ
+        | TBZ | TBNZ | ADR | ADRP => pc_relative(inst),
+        RET | BR | BLR => {
+            let rn = match ops.first() {
+                Some(op) => gpr_no_sp(op)?.0,
+                None if inst.instruction_type == RET => 30,
+                None => return None,
+            };
+            let base = match inst.instruction_type {
+                BR => 0xd61f_0000,
+                BLR => 0xd63f_0000,
+                _ => 0xd65f_0000,
+            };
+            Some(base | rn << 5)
+        }
+        NOP => Some(0xd503_201f),
+        YIELD => Some(0xd503_203f),
+        WFE => Some(0xd503_205f),
+        WFI => Some(0xd503_207f),
+        ERET => Some(0xd69f_03e0),
+        DRPS => Some(0xd6bf_03e0),
+        SVC | HLT | BRK => {
+            let imm16 = u32::try_from(immediate(ops.first()?)?).ok().filter(|v| *v <= 0xffff)?;
+            let base = match inst.instruction_type {
+                SVC => 0xd400_0001,
+                HLT => 0xd440_0000,
+                _ => 0xd420_0000,
+            };
+            Some(base | imm16 << 5)
+        }
+        DMB | DSB | ISB => {
+            let crm = match ops.first() {
+                None if inst.instruction_type == ISB => 15,
+                Some(Operand::Label(option)) => {
+                    let options = ["", "oshld", "oshst", "osh", "", "nshld", "nshst", "nsh", "", "ishld", "ishst", "ish", "", "ld", "st", "sy"];
+                    options.iter().position(|o| !o.is_empty() && o.eq_ignore_ascii_case(option))? as u32
+                }
+                _ => return None,
+            };
+            let op2 = match inst.instruction_type {
+                DSB => 4,
+                DMB => 5,
+                _ => 6,
+            };
+            Some(0xd503_301f | crm << 8 | op2 << 5)
+        }
+        LDR | STR | LDRB | STRB | LDRH | STRH | LDRSB | LDRSH | LDRSW | LDUR | STUR => load_store(inst),
+        LDP | STP => load_store_pair(inst),
+        LDXR | LDXRB | LDXRH | LDAXRB | LDAXRH | STXR | STXRB | STXRH | STLXRB | STLXRH | LDAR | STLR => exclusive(inst),
+        _ => floating_point(inst),
+    }
+}
+
+/// 加减法及其别名（mov 到/从 sp、cmp、cmn、neg），`mov`/`mvn` 寄存器与立即数
+fn arithmetic(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    match inst.instruction_type {
+        MOV => {
+            let first = ops.first()?;
+            return match ops.get(1)? {
+                Operand::Immediate(value) => move_immediate(first, *value),
+                second if is_sp(first) || is_sp(second) => {
+                    let (rd, wide) = gpr_or_sp(first)?;
+                    let (rn, _) = gpr_or_sp(second)?;
+                    Some(sf(wide) | 0x1100_0000 | rn << 5 | rd)
+                }
+                second => {
+                    let (rd, wide) = gpr_no_sp(first)?;
+                    let (rm, _) = gpr_no_sp(second)?;
+                    Some(sf(wide) | 0x2a00_0000 | rm << 16 | 31 << 5 | rd)
+                }
+            };
+        }
+        MVN => {
+            let (rd, wide) = gpr_no_sp(ops.first()?)?;
+            let (rm, _) = gpr_no_sp(ops.get(1)?)?;
+            let (kind, amount) = shifted(ops.get(2), wide)?;
+            return Some(sf(wide) | 0x2a20_0000 | kind << 22 | rm << 16 | amount << 10 | 31 << 5 | rd);
+        }
+        _ => {}
+    }
+
+    // 统一为 (op, S, Rd, Rn, 第二操作数, 修饰)
+    let (op, set_flags, rd, rn, rest) = match inst.instruction_type {
+        ADD => (0, 0, ops.first()?, Some(ops.get(1)?), &ops[2..]),
+        SUB => (1, 0, ops.first()?, Some(ops.get(1)?), &ops[2..]),
+        ADDS => (0, 1, ops.first()?, Some(ops.get(1)?), &ops[2..]),
+        SUBS => (1, 1, ops.first()?, Some(ops.get(1)?), &ops[2..]),
+        CMN => (0, 1, &Operand::Register(Register::XZR), Some(ops.first()?), &ops[1..]),
+        CMP => (1, 1, &Operand::Register(Register::XZR), Some(ops.first()?), &ops[1..]),
+        _ => (1, 0, ops.first()?, None, &ops[1..]),
+    };
+    let second = rest.first()?;
+    let modifier_op = rest.get(1);
+    let first_reg = rn.unwrap_or(rd);
+    let wide = gpr(first_reg)?.1;
+    // 设置标志位时 Rd 为 31 表示零寄存器
+    let rd_field = if set_flags == 1 { gpr_no_sp(rd)?.0 } else { gpr(rd)?.0 };
+    let base = sf(wide) | op << 30 | set_flags << 29;
+
+    if let Operand::Immediate(value) = second {
+        let rn = gpr_or_sp(rn?)?.0;
+        if set_flags == 0 {
+            gpr_or_sp(rd)?;
+        }
+        let (sh, imm12) = match modifier(modifier_op) {
+            Some(("lsl", 12)) => (1, *value),
+            Some(_) => return None,
+            None => (0, *value),
+        };
+        let imm12 = u32::try_from(imm12).ok().filter(|v| *v < 4096)?;
+        return Some(base | 0x1100_0000 | sh << 22 | imm12 << 10 | rn << 5 | rd_field);
+    }
+
+    let (rm, rm_wide) = gpr_no_sp(second)?;
+    let rn_field = match rn {
+        Some(op) => gpr(op)?.0,
+        None => 31,
+    };
+    let extended_name = modifier(modifier_op).is_some_and(|(name, _)| extend_kind(name).is_some());
+    let uses_sp = rn.is_some_and(is_sp) || (set_flags == 0 && is_sp(rd));
+    if extended_name || uses_sp || rm_wide != wide {
+        // 扩展寄存器形式
+        let (option, amount) = match modifier(modifier_op) {
+            Some((name, amount)) if extend_kind(name).is_some() => (extend_kind(name)?, amount),
+            Some(("lsl", amount)) if uses_sp => (if wide { 0b011 } else { 0b010 }, amount),
+            None if uses_sp => (if wide { 0b011 } else { 0b010 }, 0),
+            _ => return None,
+        };
+        if amount > 4 {
+            return None;
+        }
+        return Some(base | 0x0b20_0000 | rm << 16 | option << 13 | amount << 10 | rn_field << 5 | rd_field);
+    }
+    let (kind, amount) = shifted(modifier_op, wide)?;
+    if kind == 3 {
+        return None;
+    }
+    Some(base | 0x0b00_0000 | kind << 22 | rm << 16 | amount << 10 | rn_field << 5 | rd_field)
+}
+
+/// 移位寄存器操作数的 (移位类型, 移位量)
+fn shifted(operand: Option<&Operand>, wide: bool) -> Option<(u32, u32)> {
+    let (kind, amount) = match modifier(operand) {
+        Some((name, amount)) => (shift_kind(name)?, amount),
+        None => (0, 0),
+    };
+    (amount < if wide { 64 } else { 32 }).then_some((kind, amount))
+}
+
+/// `mov Rd, #imm`：依次尝试 MOVZ、MOVN 和逻辑立即数
+fn move_immediate(rd: &Operand, value: i64) -> Option<u32> {
+    let (rd_field, wide) = gpr(rd)?;
+    let mask = if wide { u64::MAX } else { 0xffff_ffff };
+    let value = value as u64 & mask;
+    if !is_sp(rd) {
+        if let Some((hw, imm16)) = move_wide(value, wide) {
+            return Some(sf(wide) | 0x5280_0000 | hw << 21 | imm16 << 5 | rd_field);
+        }
+        if let Some((hw, imm16)) = move_wide(!value & mask, wide) {
+            return Some(sf(wide) | 0x1280_0000 | hw << 21 | imm16 << 5 | rd_field);
+        }
+    }
+    let (n, immr, imms) = encode_bit_masks(value, wide)?;
+    Some(sf(wide) | 0x3200_0000 | n << 22 | immr << 16 | imms << 10 | 31 << 5 | rd_field)
+}
+
+/// 逻辑运算（立即数与移位寄存器）
+fn logical(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    let (rd, rest) = match inst.instruction_type {
+        TST => (&Operand::Register(Register::XZR), ops),
+        _ => (ops.first()?, ops.get(1..)?),
+    };
+    let (rn, wide) = gpr_no_sp(rest.first()?)?;
+    let (opc, negate) = match inst.instruction_type {
+        AND => (0b00, 0),
+        BIC => (0b00, 1),
+        ORR => (0b01, 0),
+        ORN => (0b01, 1),
+        EOR => (0b10, 0),
+        EON => (0b10, 1),
+        _ => (0b11, 0),
+    };
+    if inst.instruction_type == ANDS && is_sp(rd) {
+        return None;
+    }
+    let rd_field = gpr(rd)?.0;
+    match rest.get(1)? {
+        Operand::Immediate(value) if negate == 0 => {
+            let (n, immr, imms) = encode_bit_masks(*value as u64, wide)?;
+            Some(sf(wide) | opc << 29 | 0x1200_0000 | n << 22 | immr << 16 | imms << 10 | rn << 5 | rd_field)
+        }
+        second => {
+            if is_sp(rd) {
+                return None;
+            }
+            let (rm, _) = gpr_no_sp(second)?;
+            let (kind, amount) = shifted(rest.get(2), wide)?;
+            Some(sf(wide) | opc << 29 | 0x0a00_0000 | kind << 22 | negate << 21 | rm << 16 | amount << 10 | rn << 5 | rd_field)
+        }
+    }
+}
+
+/// 移位与位域（立即数移位按 UBFM/SBFM/EXTR 编码，寄存器移位按 LSLV 等编码）
+fn shift_bitfield(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    let (rd, wide) = gpr_no_sp(ops.first()?)?;
+    let (rn, _) = gpr_no_sp(ops.get(1)?)?;
+    let size = if wide { 64 } else { 32 };
+    let field = |value: i64| u32::try_from(value).ok().filter(|v| *v < size);
+
+    if let (Some(Operand::Register(_)), LSL | LSR | ASR | ROR) = (ops.get(2), inst.instruction_type) {
+        let (rm, _) = gpr_no_sp(ops.get(2)?)?;
+        let opcode = match inst.instruction_type {
+            LSL => 0b001000,
+            LSR => 0b001001,
+            ASR => 0b001010,
+            _ => 0b001011,
+        };
+        return Some(sf(wide) | 0x1ac0_0000 | rm << 16 | opcode << 10 | rn << 5 | rd);
+    }
+    if inst.instruction_type == EXTR {
+        let (rm, _) = gpr_no_sp(ops.get(2)?)?;
+        let lsb = field(immediate(ops.get(3)?)?)?;
+        return Some(sf(wide) | 0x1380_0000 | u32::from(wide) << 22 | rm << 16 | lsb << 10 | rn << 5 | rd);
+    }
+    let first = field(immediate(ops.get(2)?)?)?;
+    let second = || ops.get(3).and_then(immediate).and_then(|v| u32::try_from(v).ok()).filter(|v| (1..=size).contains(v));
+    // (opc, immr, imms)
+    let (opc, immr, imms) = match inst.instruction_type {
+        ROR => return Some(sf(wide) | 0x1380_0000 | u32::from(wide) << 22 | rn << 16 | first << 10 | rn << 5 | rd),
+        LSL => (0b10, (size - first) % size, size - 1 - first),
+        LSR => (0b10, first, size - 1),
+        ASR => (0b00, first, size - 1),
+        UBFX | SBFX | BFXIL => {
+            let width = second()?;
+            if first + width > size {
+                return None;
+            }
+            let opc = match inst.instruction_type {
+                UBFX => 0b10,
+                SBFX => 0b00,
+                _ => 0b01,
+            };
+            (opc, first, first + width - 1)
+        }
+        _ => {
+            let width = second()?;
+            if first + width > size {
+                return None;
+            }
+            let opc = match inst.instruction_type {
+                UBFIZ => 0b10,
+                SBFIZ => 0b00,
+                _ => 0b01,
+            };
+            (opc, (size - first) % size, width - 1)
+        }
+    };
+    Some(sf(wide) | opc << 29 | 0x1300_0000 | u32::from(wide) << 22 | immr << 16 | imms << 10 | rn << 5 | rd)
+}
+
+/// 条件选择及其别名（别名的条件取反）
+fn conditional_select(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    let (rd, wide) = gpr_no_sp(ops.first()?)?;
+    let code = condition_code(inst.condition?);
+    let (op, o2, rn, rm, code) = match inst.instruction_type {
+        CSEL | CSINC | CSINV | CSNEG => {
+            let (op, o2) = match inst.instruction_type {
+                CSEL => (0, 0),
+                CSINC => (0, 1),
+                CSINV => (1, 0),
+                _ => (1, 1),
+            };
+            (op, o2, gpr_no_sp(ops.get(1)?)?.0, gpr_no_sp(ops.get(2)?)?.0, code)
+        }
+        CSET | CSETM => {
+            let op = u32::from(inst.instruction_type == CSETM);
+            (op, u32::from(op == 0), 31, 31, code ^ 1)
+        }
+        _ => {
+            let rn = gpr_no_sp(ops.get(1)?)?.0;
+            let (op, o2) = match inst.instruction_type {
+                CINC => (0, 1),
+                CINV => (1, 0),
+                _ => (1, 1),
+            };
+            (op, o2, rn, rn, code ^ 1)
+        }
+    };
+    Some(sf(wide) | op << 30 | 0x1a80_0000 | rm << 16 | code << 12 | o2 << 10 | rn << 5 | rd)
+}
+
+/// PC 相对的跳转和地址计算
+fn pc_relative(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    let target = target(ops.last()?)?;
+    let offset = target.wrapping_sub(inst.address) as i64;
+    let words = |len: u32| -> Option<u32> {
+        if offset % 4 != 0 {
+            return None;
+        }
+        signed_field(offset / 4, len)
+    };
+    let condition = match inst.instruction_type {
+        BEQ => Some(Condition::EQ),
+        BNE => Some(Condition::NE),
+        BCS => Some(Condition::CS),
+        BCC => Some(Condition::CC),
+        BMI => Some(Condition::MI),
+        BPL => Some(Condition::PL),
+        BVS => Some(Condition::VS),
+        BVC => Some(Condition::VC),
+        BHI => Some(Condition::HI),
+        BLS => Some(Condition::LS),
+        BGE => Some(Condition::GE),
+        BLT => Some(Condition::LT),
+        BGT => Some(Condition::GT),
+        BLE => Some(Condition::LE),
+        _ => None,
+    };
+    if let Some(condition) = condition {
+        return Some(0x5400_0000 | words(19)? << 5 | condition_code(condition));
+    }
+    match inst.instruction_type {
+        B => Some(0x1400_0000 | words(26)?),
+        BL => Some(0x9400_0000 | words(26)?),
+        CBZ | CBNZ => {
+            let (rt, wide) = gpr_no_sp(ops.first()?)?;
+            let op = u32::from(inst.instruction_type == CBNZ);
+            Some(sf(wide) | 0x3400_0000 | op << 24 | words(19)? << 5 | rt)
+        }
+        TBZ | TBNZ => {
+            let (rt, _) = gpr_no_sp(ops.first()?)?;
+            let number = u32::try_from(immediate(ops.get(1)?)?).ok().filter(|v| *v < 64)?;
+            let op = u32::from(inst.instruction_type == TBNZ);
+            Some((number >> 5) << 31 | 0x3600_0000 | op << 24 | (number & 31) << 19 | words(14)? << 5 | rt)
+        }
+        ADR => {
+            let (rd, _) = gpr_no_sp(ops.first()?)?;
+            let imm = signed_field(offset, 21)?;
+            Some(0x1000_0000 | (imm & 3) << 29 | (imm >> 2) << 5 | rd)
+        }
+        ADRP => {
+            let (rd, _) = gpr_no_sp(ops.first()?)?;
+            let pages = ((target & !0xfff) as i64).wrapping_sub((inst.address & !0xfff) as i64) >> 12;
+            let imm = signed_field(pages, 21)?;
+            Some(0x9000_0000 | (imm & 3) << 29 | (imm >> 2) << 5 | rd)
+        }
+        _ => None,
+    }
+}
+
+/// 单寄存器访存：(size, V, opc)
+fn access(inst: &Instruction) -> Option<(u32, u32, u32)> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    if let Some((_, ftype)) = fpr(ops.first()?) {
+        let size = match ftype {
+            0 => 2,
+            1 => 3,
+            _ => 1,
+        };
+        let opc = match inst.instruction_type {
+            LDR | LDUR => 1,
+            STR | STUR => 0,
+            _ => return None,
+        };
+        return Some((size, 1, opc));
+    }
+    let (_, wide) = gpr_no_sp(ops.first()?)?;
+    Some(match (inst.instruction_type, wide) {
+        (STRB, false) => (0, 0, 0),
+        (LDRB, false) => (0, 0, 1),
+        (LDRSB, true) => (0, 0, 2),
+        (LDRSB, false) => (0, 0, 3),
+        (STRH, false) => (1, 0, 0),
+        (LDRH, false) => (1, 0, 1),
+        (LDRSH, true) => (1, 0, 2),
+        (LDRSH, false) => (1, 0, 3),
+        (STR | STUR, false) => (2, 0, 0),
+        (LDR | LDUR, false) => (2, 0, 1),
+        (LDRSW, true) => (2, 0, 2),
+        (STR | STUR, true) => (3, 0, 0),
+        (LDR | LDUR, true) => (3, 0, 1),
+        _ => return None,
+    })
+}
+
+/// 访存指令的目标寄存器编号（通用或浮点）
+fn transfer_register(operand: &Operand) -> Option<u32> {
+    fpr(operand).map(|(n, _)| n).or_else(|| gpr_no_sp(operand).map(|(n, _)| n))
+}
+
+fn load_store(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    let rt = transfer_register(ops.first()?)?;
+    let (size, v, opc) = access(inst)?;
+    let base = size << 30 | v << 26 | opc << 22 | rt;
+    let unscaled = matches!(inst.instruction_type, LDUR | STUR);
+
+    let Operand::Memory { base: rn, offset, index, pre_indexed, .. } = ops.get(1)? else {
+        // 字面量：ldr x0, 400648 <f+0x8>
+        if unscaled || opc == 0 {
+            return None;
+        }
+        let offset = target(ops.get(1)?)?.wrapping_sub(inst.address) as i64;
+        let opc = match (v, size, opc) {
+            (0, 2, 1) => 0,
+            (0, 3, 1) => 1,
+            (0, 2, 2) => 2,
+            (1, 2, 1) => 0,
+            (1, 3, 1) => 1,
+            _ => return None,
+        };
+        let imm19 = if offset % 4 == 0 { signed_field(offset / 4, 19)? } else { return None };
+        return Some(opc << 30 | v << 26 | 0x1800_0000 | imm19 << 5 | rt);
+    };
+    let rn = gpr_or_sp(&Operand::Register(*rn))?.0;
+    let offset = offset.unwrap_or(0);
+
+    if let Some(index) = index {
+        let (rm, true) = gpr_no_sp(&Operand::Register(*index))? else {
+            return None;
+        };
+        return Some(base | 0x3820_0000 | rm << 16 | 0b011 << 13 | 0b10 << 10 | rn << 5);
+    }
+    if let Some(post) = ops.get(2).and_then(immediate) {
+        let imm9 = signed_field(post, 9)?;
+        return Some(base | 0x3800_0000 | imm9 << 12 | 0b01 << 10 | rn << 5);
+    }
+    if *pre_indexed {
+        let imm9 = signed_field(offset, 9)?;
+        return Some(base | 0x3800_0000 | imm9 << 12 | 0b11 << 10 | rn << 5);
+    }
+    if unscaled {
+        let imm9 = signed_field(offset, 9)?;
+        return Some(base | 0x3800_0000 | imm9 << 12 | rn << 5);
+    }
+    let scale = 1i64 << size;
+    if offset < 0 || offset % scale != 0 || offset / scale >= 4096 {
+        return None;
+    }
+    Some(base | 0x3900_0000 | ((offset / scale) as u32) << 10 | rn << 5)
+}
+
+fn load_store_pair(inst: &Instruction) -> Option<u32> {
+    let ops = inst.operands.as_slice();
+    let (opc, v, scale) = match fpr(ops.first()?) {
+        Some((_, 0)) => (0b00, 1, 4),
+        Some((_, 1)) => (0b01, 1, 8),
+        Some(_) => return None,
+        None if gpr_no_sp(ops.first()?)?.1 => (0b10, 0, 8),
+        None => (0b00, 0, 4),
+    };
+    let rt = transfer_register(ops.first()?)?;
+    let rt2 = transfer_register(ops.get(1)?)?;
+    let Operand::Memory { base, offset, index: None, pre_indexed, .. } = ops.get(2)? else {
+        return None;
+    };
+    let rn = gpr_or_sp(&Operand::Register(*base))?.0;
+    let (mode, offset) = match ops.get(3).and_then(immediate) {
+        Some(post) => (0b01, post),
+        None if *pre_indexed => (0b11, offset.unwrap_or(0)),
+        None => (0b10, offset.unwrap_or(0)),
+    };
+    if offset % scale != 0 {
+        return None;
+    }
+    let imm7 = signed_field(offset / scale, 7)?;
+    let load = u32::from(inst.instruction_type == InstructionType::LDP);
+    Some(opc << 30 | 0x2800_0000 | v << 26 | mode << 23 | load << 22 | imm7 << 15 | rt2 << 10 | rn << 5 | rt)
+}
+
+/// 独占与获取-释放访存
+fn exclusive(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    let (status, rest) = match inst.instruction_type {
+        STXR | STXRB | STXRH | STLXRB | STLXRH => (gpr_no_sp(ops.first()?)?.0, &ops[1..]),
+        _ => (31, ops),
+    };
+    let (rt, wide) = gpr_no_sp(rest.first()?)?;
+    let Operand::Memory { base, offset: None, index: None, .. } = rest.get(1)? else {
+        return None;
+    };
+    let rn = gpr_or_sp(&Operand::Register(*base))?.0;
+    let word_size = if wide { 3 } else { 2 };
+    // (size, o2, L, o0)
+    let (size, o2, load, o0) = match inst.instruction_type {
+        LDXRB => (0, 0, 1, 0),
+        LDXRH => (1, 0, 1, 0),
+        LDXR => (word_size, 0, 1, 0),
+        LDAXRB => (0, 0, 1, 1),
+        LDAXRH => (1, 0, 1, 1),
+        STXRB => (0, 0, 0, 0),
+        STXRH => (1, 0, 0, 0),
+        STXR => (word_size, 0, 0, 0),
+        STLXRB => (0, 0, 0, 1),
+        STLXRH => (1, 0, 0, 1),
+        LDAR => (word_size, 1, 1, 1),
+        _ => (word_size, 1, 0, 1),
+    };
+    Some(size << 30 | 0x0800_0000 | o2 << 23 | load << 22 | status << 16 | o0 << 15 | 31 << 10 | rn << 5 | rt)
+}
+
+/// 标量浮点指令
+fn floating_point(inst: &Instruction) -> Option<u32> {
+    use InstructionType::*;
+    let ops = inst.operands.as_slice();
+    let first = ops.first()?;
+    match inst.instruction_type {
+        FMUL | FDIV | FADD | FSUB | FMAX | FMIN | FMAXNM | FMINNM => {
+            let (rd, ftype) = fpr(first)?;
+            let (rn, _) = fpr(ops.get(1)?)?;
+            let (rm, _) = fpr(ops.get(2)?)?;
+            let opcode = [FMUL, FDIV, FADD, FSUB, FMAX, FMIN, FMAXNM, FMINNM].iter().position(|t| *t == inst.instruction_type)? as u32;
+            Some(0x1e20_0800 | ftype << 22 | rm << 16 | opcode << 12 | rn << 5 | rd)
+        }
+        FMADD | FMSUB => {
+            let (rd, ftype) = fpr(first)?;
+            let (rn, _) = fpr(ops.get(1)?)?;
+            let (rm, _) = fpr(ops.get(2)?)?;
+            let (ra, _) = fpr(ops.get(3)?)?;
+            let o0 = u32::from(inst.instruction_type == FMSUB);
+            Some(0x1f00_0000 | ftype << 22 | rm << 16 | o0 << 15 | ra << 10 | rn << 5 | rd)
+        }
+        FCMP | FCMPE => {
+            let (rn, ftype) = fpr(first)?;
+            let e = u32::from(inst.instruction_type == FCMPE) << 4;
+            match ops.get(1)? {
+                Operand::Immediate(0) => Some(0x1e20_2008 | ftype << 22 | rn << 5 | e),
+                second => Some(0x1e20_2000 | ftype << 22 | fpr(second)?.0 << 16 | rn << 5 | e),
+            }
+        }
+        _ => {
+            let second = ops.get(1)?;
+            match (fpr(first), fpr(second)) {
+                (Some((rd, target)), Some((rn, ftype))) => {
+                    let opcode = match inst.instruction_type {
+                        FMOV => 0,
+                        FABS => 1,
+                        FNEG => 2,
+                        FSQRT => 3,
+                        FCVT => match target {
+                            0 => 4,
+                            1 => 5,
+                            _ => 7,
+                        },
+                        FRINTN => 8,
+                        FRINTP => 9,
+                        FRINTM => 10,
+                        FRINTZ => 11,
+                        FRINTA => 12,
+                        FRINTX => 14,
+                        FRINTI => 15,
+                        _ => return None,
+                    };
+                    if (inst.instruction_type == FCVT) == (target == ftype) {
+                        return None;
+                    }
+                    Some(0x1e20_4000 | ftype << 22 | opcode << 15 | rn << 5 | rd)
+                }
+                (Some((rd, ftype)), None) => {
+                    let (rn, wide) = gpr_no_sp(second)?;
+                    let (rmode, opcode) = match inst.instruction_type {
+                        SCVTF => (0, 0b010),
+                        UCVTF => (0, 0b011),
+                        FMOV if wide == (ftype == 1) => (0, 0b111),
+                        _ => return None,
+                    };
+                    Some(sf(wide) | 0x1e20_0000 | ftype << 22 | rmode << 19 | opcode << 16 | rn << 5 | rd)
+                }
+                (None, Some((rn, ftype))) => {
+                    let (rd, wide) = gpr_no_sp(first)?;
+                    let (rmode, opcode) = match inst.instruction_type {
+                        FCVTNS => (0b00, 0b000),
+                        FCVTNU => (0b00, 0b001),
+                        FCVTAS => (0b00, 0b100),
+                        FCVTAU => (0b00, 0b101),
+                        FMOV if wide == (ftype == 1) => (0b00, 0b110),
+                        FCVTPS => (0b01, 0b000),
+                        FCVTPU => (0b01, 0b001),
+                        FCVTMS => (0b10, 0b000),
+                        FCVTMU => (0b10, 0b001),
+                        FCVTZS => (0b11, 0b000),
+                        FCVTZU => (0b11, 0b001),
+                        _ => return None,
+                    };
+                    Some(sf(wide) | 0x1e20_0000 | ftype << 22 | rmode << 19 | opcode << 16 | rn << 5 | rd)
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// 重新编码的结果与 dump 中的机器码不一致的指令
+#[derive(Debug, Clone)]
+pub struct EncodingMismatch {
+    pub address: u64,
+    /// objdump 给出的指令文本
+    pub text: String,
+    /// dump 中的机器码
+    pub expected: u32,
+    /// 按解析结果重新编码的机器码
+    pub encoded: u32,
+}
+
+/// 未重定位的 PC 相对指令中偏移字段的掩码
+pub(crate) fn relocation_mask(inst: &Instruction) -> u32 {
+    use InstructionType::*;
+    match inst.instruction_type {
+        B | BL => 0x03ff_ffff,
+        ADR | ADRP => 0x60ff_ffe0,
+        TBZ | TBNZ => 0x0007_ffe0,
+        _ if crate::analysis::branch_target(inst).is_some() => 0x00ff_ffe0,
+        _ => 0,
+    }
+}
+
+/// 重新编码一条指令并与 dump 中的机器码比较，不一致时返回 EncodingMismatch
+///
+/// 无法编码的指令不做判断；dump 中偏移为 0 的跳转视为尚未重定位，只比较其余字段；
+/// 同一写法有多种编码（如 `mov w0, #0xff` 也可以是 `orr w0, wzr, #0xff`）时，两者解码结果等价即可
+pub fn check(entry: &DumpEntry) -> Option<EncodingMismatch> {
+    let parsed = entry.parsed_instruction.as_ref()?;
+    let expected = crate::decoder::entry_word(entry)?;
+    let encoded = encode(parsed)?;
+    let mask = relocation_mask(parsed);
+    let unrelocated = mask != 0 && expected & mask == 0;
+    if encoded == expected || (unrelocated && encoded & !mask == expected & !mask) {
+        return None;
+    }
+    let decode = |word| crate::decoder::decode(word, parsed.address);
+    if let (Some(a), Some(b)) = (decode(expected), decode(encoded)) {
+        if crate::decoder::equivalent(&a, &b) {
+            return None;
+        }
+    }
+    Some(EncodingMismatch {
+        address: parsed.address,
+        text: entry.asm_instruction.clone(),
+        expected,
+        encoded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{self, tests::CASES};
+    use crate::parser::AssemblyParser;
+
+    fn parse(text: &str, address: u64) -> Instruction {
+        let mut inst = AssemblyParser::new().parse(text).unwrap().remove(0);
+        inst.address = address;
+        inst
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &(word, text) in CASES {
+            assert_eq!(encode(&decoder::decode(word, 0).unwrap()), Some(word), "{}", text);
+            // 文本可能有多种编码，重新解码后等价即可
+            let encoded = encode(&parse(text, 0)).unwrap_or_else(|| panic!("{}", text));
+            assert!(decoder::equivalent(&decoder::decode(encoded, 0).unwrap(), &parse(text, 0)), "{}", text);
+        }
+        assert_eq!(encode(&parse("bl 400610 <foo>", 0x400600)), Some(0x94000004));
+        assert_eq!(encode(&parse("adrp x0, 410000 <data>", 0x400640)), Some(0x90000080));
+        assert_eq!(encode(&parse("tbnz w0, #3, 18 <f+0x18>", 0x10)), Some(0x37180040));
+    }
+
+    #[test]
+    fn test_encode_bit_masks() {
+        assert_eq!(encode_bit_masks(0xff, false), Some((0, 0, 7)));
+        assert_eq!(encode_bit_masks(0xffff_ffff_ffff_fff0, true), Some((1, 60, 59)));
+        assert_eq!(encode_bit_masks(0x5555_5555, false), Some((0, 0, 0b111100)));
+        assert_eq!(encode_bit_masks(0x1234, false), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let entry = |machine_code: &str, asm: &str, address: u64| DumpEntry {
+            id: String::new(),
+            c_line: None,
+            c_code: String::new(),
+            address: format!("{:x}", address),
+            machine_code: machine_code.to_string(),
+            asm_instruction: asm.to_string(),
+            parsed_instruction: Some(parse(asm, address)),
+        };
+        assert!(check(&entry("8b020820", "add x0, x1, x2, lsl #2", 0)).is_none());
+        // 未重定位的调用
+        assert!(check(&entry("94000000", "bl 0 <done>", 0x20)).is_none());
+        // 漏掉的移位
+        let mismatch = check(&entry("8b020820", "add x0, x1, x2", 0)).unwrap();
+        assert_eq!((mismatch.expected, mismatch.encoded), (0x8b020820, 0x8b020020));
+    }
+}
@@ -0,0 +1,199 @@
+//! 跨优化级别的差分模拟
+//!
+//! 用相同的初始寄存器和缓冲区分别运行不同优化级别的同一函数，比较结束方式、返回值、
+//! 程序输出和缓冲区内容，报告第一个不一致之处。用来确认对比分析的几份汇编确实是等价的代码。
+
+use crate::emulator::{CpuState, Emulator, MemoryRegion, Trace, TraceEnd};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+
+/// 一次运行的结果
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    /// 执行轨迹
+    pub trace: Trace,
+    /// 程序通过 write 系统调用输出的内容
+    pub output: Vec<u8>,
+    /// 结束时的内存区域（不含栈）
+    pub buffers: Vec<MemoryRegion>,
+}
+
+impl RunOutcome {
+    /// 从函数入口开始运行，函数中没有可执行指令时返回 None
+    pub fn run(entries: &[DumpEntry], state: CpuState, max_steps: usize) -> Option<Self> {
+        let mut emulator = Emulator::with_state(state);
+        emulator.state.pc = crate::emulator::entry_address(entries)?;
+        let trace = emulator.trace_function(entries, max_steps);
+
+        let buffers = emulator
+            .state
+            .memory
+            .regions()
+            .iter()
+            .filter(|r| r.name != "stack")
+            .cloned()
+            .collect();
+        Some(Self { trace, output: emulator.output, buffers })
+    }
+}
+
+/// 两次运行的第一个不一致之处
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// 不一致的方面（如 返回值 X0、缓冲区 buffer@0x10000）
+    pub aspect: String,
+    /// 基准级别的值
+    pub expected: String,
+    /// 被比较级别的值
+    pub actual: String,
+}
+
+impl Divergence {
+    /// 按语言描述不一致之处
+    pub fn describe(&self, lang: Lang) -> String {
+        match lang {
+            Lang::Zh => format!("{}: 期望 {}，实际 {}", self.aspect, self.expected, self.actual),
+            Lang::En => format!("{}: expected {}, got {}", self.aspect, self.expected, self.actual),
+        }
+    }
+}
+
+/// 两次运行的比较结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Comparison {
+    /// 行为一致
+    Equivalent,
+    /// 第一个不一致之处
+    Diverged(Divergence),
+    /// 两次运行都没有正常结束，无法判断是否等价
+    Inconclusive,
+}
+
+/// 比较两次运行的结果，返回第一个不一致之处
+///
+/// 比较顺序为结束方式、返回值（check_return 为 false 时跳过，适用于 void 函数）、
+/// 程序输出、缓冲区内容。栈内容因栈帧布局不同而不参与比较。
+pub fn compare(expected: &RunOutcome, actual: &RunOutcome, check_return: bool, lang: Lang) -> Comparison {
+    let divergence = |aspect: &str, expected: String, actual: String| {
+        Comparison::Diverged(Divergence { aspect: aspect.to_string(), expected, actual })
+    };
+
+    match (&expected.trace.end, &actual.trace.end) {
+        (TraceEnd::Returned(a), TraceEnd::Returned(b)) => {
+            if check_return && a != b {
+                return divergence(lang.pick("返回值 X0", "return value X0"), format!("0x{:x}", a), format!("0x{:x}", b));
+            }
+        }
+        (TraceEnd::Exited(a), TraceEnd::Exited(b)) => {
+            if a != b {
+                return divergence(lang.pick("退出码", "exit code"), a.to_string(), b.to_string());
+            }
+        }
+        (a, b) if !a.is_finished() && !b.is_finished() => return Comparison::Inconclusive,
+        (a, b) => return divergence(lang.pick("结束方式", "outcome"), a.describe(lang), b.describe(lang)),
+    }
+
+    if expected.output != actual.output {
+        let offset = expected
+            .output
+            .iter()
+            .zip(&actual.output)
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| expected.output.len().min(actual.output.len()));
+        let aspect = match lang {
+            Lang::Zh => format!("程序输出 (第 {} 字节起)", offset),
+            Lang::En => format!("program output (from byte {})", offset),
+        };
+        return divergence(
+            &aspect,
+            format!("{:?}", String::from_utf8_lossy(&expected.output[offset..])),
+            format!("{:?}", String::from_utf8_lossy(&actual.output[offset..])),
+        );
+    }
+
+    for (a, b) in expected.buffers.iter().zip(&actual.buffers) {
+        if let Some(i) = a.data.iter().zip(&b.data).position(|(x, y)| x != y) {
+            let address = a.base + i as u64;
+            let aspect = match lang {
+                Lang::Zh => format!("缓冲区 {} 地址 0x{:x}", a.name, address),
+                Lang::En => format!("buffer {} at 0x{:x}", a.name, address),
+            };
+            return divergence(&aspect, format!("0x{:02x}", a.data[i]), format!("0x{:02x}", b.data[i]));
+        }
+    }
+
+    Comparison::Equivalent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP_O0: &str = r#"
+0000000000000000 <twice>:
+   0:   0b000000    add w0, w0, w0
+   4:   b9000020    str w0, [x1]
+   8:   d65f03c0    ret
+"#;
+
+    const DUMP_O2: &str = r#"
+0000000000000000 <twice>:
+   0:   531f7800    lsl w0, w0, #1
+   4:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_first_divergence() {
+        let mut state = CpuState::default();
+        state.map_buffer(0x10000, vec![0; 4]).unwrap();
+        state.x[0] = 21;
+        state.x[1] = 0x10000;
+
+        let run = |dump: &str| {
+            let entries = ObjdumpParser::new(dump.to_string()).extract_function_data("twice").unwrap();
+            RunOutcome::run(&entries, state.clone(), 100).unwrap()
+        };
+        let o0 = run(DUMP_O0);
+        let o2 = run(DUMP_O2);
+
+        assert_eq!(o0.trace.end, TraceEnd::Returned(42));
+        assert_eq!(compare(&o0, &o0, true, Lang::Zh), Comparison::Equivalent);
+
+        // 返回值相同，但 O2 没有写缓冲区
+        let Comparison::Diverged(divergence) = compare(&o0, &o2, true, Lang::Zh) else {
+            panic!("O0 与 O2 应不一致");
+        };
+        assert_eq!(divergence.aspect, "缓冲区 buffer@0x10000 地址 0x10000");
+        assert_eq!(divergence.expected, "0x2a");
+        let Comparison::Diverged(divergence) = compare(&o0, &o2, true, Lang::En) else {
+            panic!("O0 与 O2 应不一致");
+        };
+        assert_eq!(divergence.describe(Lang::En), "buffer buffer@0x10000 at 0x10000: expected 0x2a, got 0x00");
+    }
+
+    #[test]
+    fn test_both_runs_failed() {
+        // 两次运行以同样的方式离开函数：不是不一致，而是无法判断
+        let mut left = RunOutcome::run(
+            &ObjdumpParser::new(DUMP_O2.to_string()).extract_function_data("twice").unwrap(),
+            CpuState::default(),
+            100,
+        )
+        .unwrap();
+        left.trace.end = TraceEnd::LeftFunction(0x40);
+        assert_eq!(compare(&left, &left, true, Lang::Zh), Comparison::Inconclusive);
+
+        let mut limited = left.clone();
+        limited.trace.end = TraceEnd::StepLimit(100);
+        assert_eq!(compare(&left, &limited, true, Lang::En), Comparison::Inconclusive);
+
+        let mut returned = left.clone();
+        returned.trace.end = TraceEnd::Returned(0);
+        let Comparison::Diverged(divergence) = compare(&returned, &left, true, Lang::En) else {
+            panic!("正常返回与离开函数应不一致");
+        };
+        assert_eq!(divergence.aspect, "outcome");
+        assert_eq!(divergence.actual, "PC = 0x40 left the function");
+    }
+}
@@ -8,6 +8,14 @@ pub enum InterpreterError {
     #[error("解析错误: {0}")]
     ParseError(String),
 
+    #[error("未找到函数: {name}")]
+    FunctionNotFound {
+        /// 要查找的函数名
+        name: String,
+        /// dump 中名称最接近的符号（按相似程度排序）
+        candidates: Vec<String>,
+    },
+
     #[error("无效的指令: {0}")]
     InvalidInstruction(String),
 
@@ -0,0 +1,297 @@
+//! 单条指令解释
+//!
+//! 一次调用完成解析、语义解释和指令数据库查询，供其他工具、聊天机器人或 REPL
+//! 直接解释一条汇编指令，而不必构造 dump 文件。
+
+use crate::analysis::{self, Access};
+use crate::error::{Result, InterpreterError};
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::instruction_db::InstructionDef;
+use crate::locale::Lang;
+use crate::metrics::Category;
+use crate::parser::AssemblyParser;
+use crate::semantic::{self, SemanticInterpreter};
+use serde::Serialize;
+
+/// 操作数在指令中的作用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperandRole {
+    /// 被读取的寄存器
+    Source,
+    /// 被写入的寄存器
+    Destination,
+    /// 先读后写的寄存器
+    SourceDestination,
+    /// 内存地址
+    Address,
+    /// 访问后回写基址的内存地址
+    AddressWriteback,
+    /// 立即数
+    Immediate,
+    /// 跳转目标（分支、adr/adrp 计算的地址）
+    Target,
+    /// 移位或扩展修饰（`lsl #12`、`sxtw #2`）
+    Shift,
+    /// 条件码
+    Condition,
+    /// 既不读也不写的寄存器操作数
+    Other,
+}
+
+impl OperandRole {
+    /// 按语言返回作用的显示名称
+    pub fn name_in(self, lang: Lang) -> &'static str {
+        match self {
+            OperandRole::Source => lang.pick("源（读取）", "source (read)"),
+            OperandRole::Destination => lang.pick("目标（写入）", "destination (written)"),
+            OperandRole::SourceDestination => lang.pick("源和目标（读后写）", "source and destination"),
+            OperandRole::Address => lang.pick("内存地址", "memory address"),
+            OperandRole::AddressWriteback => lang.pick("内存地址（回写基址）", "memory address (base written back)"),
+            OperandRole::Immediate => lang.pick("立即数", "immediate"),
+            OperandRole::Target => lang.pick("跳转目标", "branch target"),
+            OperandRole::Shift => lang.pick("移位/扩展", "shift/extend"),
+            OperandRole::Condition => lang.pick("条件码", "condition code"),
+            OperandRole::Other => lang.pick("其他", "other"),
+        }
+    }
+}
+
+/// 一个操作数的说明
+#[derive(Debug, Clone, Serialize)]
+pub struct OperandInfo {
+    /// 操作数文本（如 `x29`、`[sp, #16]!`）
+    pub text: String,
+    /// 作用
+    pub role: OperandRole,
+}
+
+/// 一条指令的完整解释
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    /// 原始汇编文本
+    pub text: String,
+    /// 助记符（小写）
+    pub mnemonic: String,
+    /// 解析后的指令
+    pub instruction: Instruction,
+    /// 各操作数的作用
+    pub operands: Vec<OperandInfo>,
+    /// 语义解释
+    pub semantics: String,
+    /// 指令数据库中的定义（名称、格式、说明、影响的标志位）
+    pub definition: Option<InstructionDef>,
+    /// 指令类别
+    pub category: Category,
+    /// 读取的通用寄存器
+    pub reads: Vec<String>,
+    /// 写入的通用寄存器
+    pub writes: Vec<String>,
+    /// 写入的条件标志位
+    pub flags_set: Vec<String>,
+    /// 是否读取条件标志位
+    pub reads_flags: bool,
+}
+
+/// 解释一条汇编指令，如 `explain("ldp x29, x30, [sp], #16")`
+pub fn explain(text: &str) -> Result<Explanation> {
+    explain_in(text, Lang::Zh)
+}
+
+/// 按指定语言解释一条汇编指令
+pub fn explain_in(text: &str, lang: Lang) -> Result<Explanation> {
+    let text = text.trim();
+    let instruction = AssemblyParser::new()
+        .parse(text)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| InterpreterError::ParseError(String::from("空指令")))?;
+
+    let mnemonic = text.split_whitespace().next().unwrap_or("").to_lowercase();
+    let (defs, uses) = analysis::def_use(&instruction);
+    let names = |set: analysis::RegSet| set.iter().map(|r| format!("x{}", r)).collect();
+    let definition = semantic::instruction_db().find_instruction(&mnemonic).cloned();
+    let flags_set = flags_set(&mnemonic, definition.as_ref());
+
+    Ok(Explanation {
+        text: text.to_string(),
+        operands: operand_roles(&instruction),
+        semantics: SemanticInterpreter::with_language(lang).describe(&instruction),
+        definition,
+        category: Category::of(text),
+        reads: names(uses),
+        writes: names(defs),
+        flags_set,
+        reads_flags: reads_flags(&mnemonic, &instruction),
+        mnemonic,
+        instruction,
+    })
+}
+
+/// 各操作数的作用
+pub(crate) fn operand_roles(inst: &Instruction) -> Vec<OperandInfo> {
+    let access = analysis::register_access(inst);
+    let ops = &inst.operands;
+    // objdump 把后变址写成 `[x0], #8`，立即数在内存操作数之后
+    let post_indexed = matches!(ops.as_slice(), [.., Operand::Memory { .. }, Operand::Immediate(_)]);
+
+    let mut roles: Vec<OperandInfo> = ops
+        .iter()
+        .zip(access)
+        .map(|(op, access)| {
+            let role = match op {
+                Operand::Register(_) => match access {
+                    Some(Access::Read) => OperandRole::Source,
+                    Some(Access::Write) => OperandRole::Destination,
+                    Some(Access::ReadWrite) => OperandRole::SourceDestination,
+                    None => OperandRole::Other,
+                },
+                Operand::Memory { pre_indexed, post_indexed: post, .. } => {
+                    if *pre_indexed || *post || post_indexed {
+                        OperandRole::AddressWriteback
+                    } else {
+                        OperandRole::Address
+                    }
+                }
+                Operand::Immediate(_) => OperandRole::Immediate,
+                Operand::Label(label) => label_role(inst, label),
+            };
+            OperandInfo { text: operand_text(op), role }
+        })
+        .collect();
+    // 解析器把条件选择的条件码从操作数中取出放在 condition 中
+    if let Some(condition) = inst.condition {
+        roles.push(OperandInfo { text: format!("{:?}", condition).to_lowercase(), role: OperandRole::Condition });
+    }
+    roles
+}
+
+/// 标签操作数的作用：解析器把跳转目标、移位/扩展修饰和屏障选项都当作标签
+fn label_role(inst: &Instruction, label: &str) -> OperandRole {
+    use InstructionType::*;
+    const SHIFTS: [&str; 13] =
+        ["lsl", "lsr", "asr", "ror", "msl", "uxtb", "uxth", "uxtw", "uxtx", "sxtb", "sxth", "sxtw", "sxtx"];
+
+    let first = label.split_whitespace().next().unwrap_or_default();
+    match inst.instruction_type {
+        B | BL | BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE | CBZ | CBNZ
+        | TBZ | TBNZ | ADR | ADRP => OperandRole::Target,
+        _ if SHIFTS.contains(&first.to_ascii_lowercase().as_str()) => OperandRole::Shift,
+        // `ldr x0, 400648 <f+0x8>` 从字面量池读取
+        _ if analysis::branch_target(inst).is_some() => OperandRole::Address,
+        _ => OperandRole::Other,
+    }
+}
+
+/// 操作数的汇编文本
+fn operand_text(op: &Operand) -> String {
+    match op {
+        Operand::Register(reg) => format!("{:?}", reg).to_lowercase(),
+        Operand::Immediate(imm) => format!("#{}", imm),
+        Operand::Label(label) => label.clone(),
+        Operand::Memory { base, offset, index, pre_indexed, .. } => {
+            let mut text = format!("[{:?}", base).to_lowercase();
+            if let Some(index) = index {
+                text.push_str(&format!(", {:?}", index).to_lowercase());
+            }
+            if let Some(offset) = offset {
+                text.push_str(&format!(", #{}", offset));
+            }
+            text.push(']');
+            if *pre_indexed {
+                text.push('!');
+            }
+            text
+        }
+    }
+}
+
+/// 指令是否设置条件标志位：比较类指令和带 S 后缀的运算
+pub(crate) fn sets_flags(mnemonic: &str) -> bool {
+    const COMPARES: [&str; 7] = ["cmp", "cmn", "tst", "ccmp", "ccmn", "fcmp", "fcmpe"];
+    const FLAG_SETTING: [&str; 10] = ["adds", "subs", "ands", "bics", "negs", "adcs", "sbcs", "ngcs", "cmp", "cmn"];
+
+    COMPARES.contains(&mnemonic) || FLAG_SETTING.contains(&mnemonic)
+}
+
+/// 指令写入的条件标志位：只有比较类指令和带 S 后缀的运算设置标志位
+fn flags_set(mnemonic: &str, definition: Option<&InstructionDef>) -> Vec<String> {
+    if !sets_flags(mnemonic) {
+        return Vec::new();
+    }
+    match definition {
+        Some(def) if !def.flags_affected.is_empty() => def.flags_affected.clone(),
+        _ => ["N", "Z", "C", "V"].iter().map(|f| f.to_string()).collect(),
+    }
+}
+
+/// 指令是否读取条件标志位（条件跳转、条件选择、带进位运算）
+fn reads_flags(mnemonic: &str, inst: &Instruction) -> bool {
+    use InstructionType::*;
+
+    inst.condition.is_some()
+        || matches!(
+            inst.instruction_type,
+            BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE
+        )
+        || matches!(
+            mnemonic,
+            "csel" | "csinc" | "csinv" | "csneg" | "cset" | "csetm" | "cinc" | "cinv" | "cneg" | "ccmp"
+                | "ccmn" | "adc" | "adcs" | "sbc" | "sbcs" | "fcsel"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_instruction() {
+        let explanation = explain("ldp x29, x30, [sp], #16").unwrap();
+        assert_eq!(explanation.mnemonic, "ldp");
+        assert_eq!(explanation.category, Category::Load);
+        assert_eq!(explanation.writes, vec!["x29", "x30"]);
+        assert!(explanation.definition.is_some());
+        assert!(!explanation.semantics.is_empty());
+
+        let roles: Vec<OperandRole> = explanation.operands.iter().map(|o| o.role).collect();
+        assert_eq!(
+            roles,
+            vec![OperandRole::Destination, OperandRole::Destination, OperandRole::AddressWriteback, OperandRole::Immediate]
+        );
+        assert!(explanation.flags_set.is_empty());
+
+        assert!(explain("").is_err());
+        assert!(explain("frobnicate x0").is_err());
+    }
+
+    #[test]
+    fn test_explain_flags() {
+        let cmp = explain("cmp w1, w0").unwrap();
+        assert_eq!(cmp.flags_set, vec!["N", "Z", "C", "V"]);
+        assert!(!cmp.reads_flags);
+
+        let add = explain_in("add x0, x0, x1", Lang::En).unwrap();
+        assert!(add.flags_set.is_empty());
+        assert_eq!(add.operands[0].role, OperandRole::Destination);
+        assert_eq!(add.operands[1].role, OperandRole::Source);
+        assert_eq!(add.semantics, "X0 = X0 + X1");
+
+        assert!(explain("b.ne 10 <sum+0x10>").unwrap().reads_flags);
+        let csel = explain("csel w0, w1, w2, ne").unwrap();
+        assert!(csel.reads_flags);
+        assert_eq!(csel.operands.last().unwrap().role, OperandRole::Condition);
+    }
+
+    #[test]
+    fn test_label_roles() {
+        let roles = |text: &str| explain(text).unwrap().operands.into_iter().map(|o| (o.text, o.role)).collect::<Vec<_>>();
+        assert_eq!(roles("add x1, x0, w1, sxtw #2")[3], (String::from("sxtw #2"), OperandRole::Shift));
+        assert_eq!(roles("add x0, x0, #0x1, lsl #12")[3], (String::from("lsl #12"), OperandRole::Shift));
+        assert_eq!(roles("add x0, x1, x2, lsl #3")[3].1, OperandRole::Shift);
+        assert_eq!(roles("bl 400500 <foo>")[0].1, OperandRole::Target);
+        assert_eq!(roles("cbz w0, 24 <f+0x24>")[1].1, OperandRole::Target);
+        assert_eq!(roles("adrp x0, 410000 <data>")[1].1, OperandRole::Target);
+        assert_eq!(roles("dmb ish")[0].1, OperandRole::Other);
+    }
+}
@@ -0,0 +1,410 @@
+//! 函数提取为可重新汇编的 `.s` 文件
+//!
+//! 把 dump 中的一个函数输出为 GNU 汇编器可以直接汇编的源文件：函数内的跳转目标重新生成为 `.L1` 这样的标签，
+//! 调用改为引用符号名，`adrp` 补全页内偏移的立即数改写为 `:lo12:符号`，去掉 objdump 的地址、机器码和注释，
+//! 可选在每条指令后附上语义解释。无法还原为符号的绝对地址保留原值并加注释提醒。
+//! 助记符大小写、缩进和列对齐由 [`AsmStyle`] 控制，以符合目标项目的代码风格。
+
+use crate::analysis;
+use crate::error::{InterpreterError, Result};
+use crate::instruction::{InstructionType, Operand};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::semantic::{awaits_relocation, pc_relative_target};
+use crate::table::TableGenerator;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+
+/// 助记符的大小写
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MnemonicCase {
+    /// 小写（objdump 的写法，默认）
+    #[default]
+    Lower,
+    /// 大写
+    Upper,
+}
+
+impl MnemonicCase {
+    /// 解析大小写名称（lower, upper）
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.to_lowercase().as_str() {
+            "lower" => Ok(MnemonicCase::Lower),
+            "upper" => Ok(MnemonicCase::Upper),
+            _ => Err(InterpreterError::ParseError(format!(
+                "不支持的大小写: {} (可选: lower, upper)",
+                text
+            ))),
+        }
+    }
+}
+
+/// 缩进方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Indent {
+    /// 用制表符缩进，助记符与操作数、指令与注释之间也以制表符分隔（默认）
+    #[default]
+    Tab,
+    /// 用给定数量的空格缩进，各部分之间以空格分隔
+    Spaces(usize),
+}
+
+impl Indent {
+    /// 解析缩进方式（`tab` 或空格数，如 `4`）
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.to_lowercase().as_str() {
+            "tab" | "tabs" => Ok(Indent::Tab),
+            number => number.parse().map(Indent::Spaces).map_err(|_| {
+                InterpreterError::ParseError(format!("不支持的缩进: {} (可选: tab 或空格数，如 4)", text))
+            }),
+        }
+    }
+
+    /// 行首的缩进
+    fn prefix(self) -> String {
+        match self {
+            Indent::Tab => String::from("\t"),
+            Indent::Spaces(n) => " ".repeat(n),
+        }
+    }
+
+    /// 列之间的分隔符
+    fn separator(self) -> &'static str {
+        match self {
+            Indent::Tab => "\t",
+            Indent::Spaces(_) => " ",
+        }
+    }
+}
+
+/// 输出汇编的代码风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AsmStyle {
+    /// 助记符的大小写（伪指令保持小写）
+    pub case: MnemonicCase,
+    /// 缩进方式
+    pub indent: Indent,
+    /// 是否用空格把操作数和行尾注释对齐成列
+    pub align: bool,
+}
+
+/// 输出的一行
+enum Line {
+    /// 原样输出的行（文件头注释、标签）
+    Raw(String),
+    /// 伪指令或指令
+    Code {
+        mnemonic: String,
+        operands: String,
+        comment: Option<String>,
+        directive: bool,
+    },
+}
+
+impl Line {
+    /// 伪指令
+    fn directive(name: &str, operands: String) -> Self {
+        Line::Code { mnemonic: name.to_string(), operands, comment: None, directive: true }
+    }
+}
+
+impl AsmStyle {
+    /// 按风格输出各行
+    fn render(&self, lines: &[Line]) -> String {
+        let code = |mnemonic: &str, operands: &str, directive: bool, width: usize| -> String {
+            let mnemonic = match self.case {
+                MnemonicCase::Upper if !directive => mnemonic.to_uppercase(),
+                _ => mnemonic.to_string(),
+            };
+            let mut text = self.indent.prefix();
+            if operands.is_empty() {
+                text.push_str(&mnemonic);
+            } else if self.align {
+                text.push_str(&format!("{:width$} {}", mnemonic, operands, width = width));
+            } else {
+                text.push_str(&format!("{}{}{}", mnemonic, self.indent.separator(), operands));
+            }
+            text
+        };
+
+        let instructions = lines.iter().filter_map(|line| match line {
+            Line::Code { mnemonic, operands, directive, .. } => Some((mnemonic, operands, *directive)),
+            Line::Raw(_) => None,
+        });
+        let mnemonic_width = instructions.clone().map(|(m, _, _)| m.chars().count()).max().unwrap_or(0);
+        let code_width = instructions
+            .map(|(m, o, d)| code(m, o, d, mnemonic_width).chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut output = String::new();
+        for line in lines {
+            match line {
+                Line::Raw(text) => output.push_str(text),
+                Line::Code { mnemonic, operands, comment, directive } => {
+                    let text = code(mnemonic, operands, *directive, mnemonic_width);
+                    match comment {
+                        Some(comment) if self.align => {
+                            output.push_str(&format!("{:width$}  // {}", text, comment, width = code_width))
+                        }
+                        Some(comment) => {
+                            output.push_str(&format!("{}{}// {}", text, self.indent.separator(), comment))
+                        }
+                        None => output.push_str(&text),
+                    }
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// 函数提取器
+pub struct Extractor {
+    /// 是否在每条指令后附上语义解释
+    comments: bool,
+    /// 注释的语言
+    lang: Lang,
+    /// 代码风格
+    style: AsmStyle,
+}
+
+impl Extractor {
+    /// 创建提取器（不附语义解释）
+    pub fn new() -> Self {
+        Self { comments: false, lang: Lang::Zh, style: AsmStyle::default() }
+    }
+
+    /// 设置是否在每条指令后以 `//` 注释附上语义解释
+    pub fn with_comments(mut self, enabled: bool) -> Self {
+        self.comments = enabled;
+        self
+    }
+
+    /// 设置注释的语言
+    pub fn with_language(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 设置代码风格（助记符大小写、缩进和列对齐）
+    pub fn with_style(mut self, style: AsmStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// 把函数输出为汇编源文件
+    pub fn extract(&self, function: &str, entries: &[DumpEntry]) -> String {
+        let semantics = if self.comments {
+            TableGenerator::new().with_language(self.lang).semantic_column(entries)
+        } else {
+            vec![String::new(); entries.len()]
+        };
+        let rows: Vec<(&DumpEntry, &String)> =
+            entries.iter().zip(&semantics).filter(|(e, _)| !e.asm_instruction.is_empty()).collect();
+        let start = rows.first().and_then(|(e, _)| u64::from_str_radix(&e.address, 16).ok());
+        let labels = labels(rows.iter().map(|(e, _)| *e));
+        let reference = Regex::new(r"\b([0-9a-f]+) <([^>]+)>").unwrap();
+        let immediate = Regex::new(r"#(0x[0-9a-f]+|\d+)").unwrap();
+
+        let mut lines = vec![
+            Line::Raw(match self.lang {
+                Lang::Zh => format!("// {}：由 alaz 从 objdump 输出提取", function),
+                Lang::En => format!("// {}: extracted by alaz from objdump output", function),
+            }),
+            Line::directive(".text", String::new()),
+            Line::directive(".p2align", String::from("2")),
+            Line::directive(".global", function.to_string()),
+            Line::directive(".type", format!("{}, %function", function)),
+            Line::Raw(format!("{}:", function)),
+        ];
+
+        // 由 adrp 写入符号所在页的寄存器
+        let mut pages: HashMap<usize, String> = HashMap::new();
+        for (entry, semantic) in rows {
+            let inst = entry.parsed_instruction.as_ref();
+            let address = inst.map(|i| i.address).or_else(|| u64::from_str_radix(&entry.address, 16).ok());
+            if let Some(label) = address.and_then(|a| labels.get(&a)) {
+                lines.push(Line::Raw(format!("{}:", label)));
+            }
+
+            let asm = entry.asm_instruction.split("//").next().unwrap_or("").trim().replace('\t', " ");
+            let (mnemonic, operands) = match asm.split_once(' ') {
+                Some((mnemonic, operands)) => (mnemonic.to_string(), operands.trim().to_string()),
+                None => (asm.clone(), String::new()),
+            };
+
+            // 待重定位的 adr/adrp/bl 指向自身，目标要由链接器填写
+            let pending = inst.is_some_and(|inst| match inst.instruction_type {
+                InstructionType::ADR | InstructionType::ADRP => {
+                    pc_relative_target(inst).is_some_and(|target| awaits_relocation(inst, target))
+                }
+                InstructionType::BL => analysis::branch_target(inst) == Some(inst.address),
+                _ => false,
+            });
+            let mut absolute = Vec::new();
+            let mut symbols = Vec::new();
+            let mut operands = reference
+                .replace_all(&operands, |caps: &regex::Captures| {
+                    let target = u64::from_str_radix(&caps[1], 16).unwrap_or(0);
+                    let symbol = &caps[2];
+                    if !pending && Some(target) == start {
+                        function.to_string()
+                    } else if let Some(label) = labels.get(&target).filter(|_| !pending) {
+                        label.clone()
+                    } else if !pending && !symbol.contains(['+', '-']) {
+                        symbols.push(symbol.trim_end_matches("@plt").to_string());
+                        symbols[symbols.len() - 1].clone()
+                    } else {
+                        absolute.push(target);
+                        format!("0x{:x}", target)
+                    }
+                })
+                .into_owned();
+
+            // adrp 取得符号所在页之后，补全页内偏移的立即数改为 :lo12:
+            if let Some(inst) = inst {
+                if let Some(symbol) = completed_page(inst, &pages) {
+                    let low = immediate.captures_iter(&operands).last().map(|caps| {
+                        let text = caps.get(1).unwrap();
+                        let value = match text.as_str().strip_prefix("0x") {
+                            Some(hex) => u64::from_str_radix(hex, 16).unwrap_or(0),
+                            None => text.as_str().parse().unwrap_or(0),
+                        };
+                        (caps.get(0).unwrap().range(), value)
+                    });
+                    if let Some((range, value)) = low {
+                        let replacement = match value {
+                            0 => format!(":lo12:{}", symbol),
+                            _ => format!(":lo12:{}+0x{:x}", symbol, value),
+                        };
+                        operands.replace_range(range, &replacement);
+                    }
+                }
+                let (defs, _) = analysis::def_use(inst);
+                for reg in defs.iter() {
+                    pages.remove(&reg);
+                }
+                if inst.instruction_type == InstructionType::ADRP {
+                    if let (Some(Operand::Register(rd)), Some(symbol)) = (inst.operands.first(), symbols.pop()) {
+                        if let Some(index) = rd.index() {
+                            pages.insert(index, symbol);
+                        }
+                    }
+                }
+            }
+
+            let mut notes: Vec<String> = Vec::new();
+            if !semantic.is_empty() {
+                notes.push(semantic.clone());
+            }
+            if pending {
+                notes.push(self.lang.pick("⚠ 待链接器重定位，重新汇编前需改为符号", "⚠ awaits relocation; replace with a symbol before reassembling").to_string());
+            } else if let Some(target) = absolute.first() {
+                notes.push(match self.lang {
+                    Lang::Zh => format!("⚠ 绝对地址 0x{:x}，重新汇编前需改为符号", target),
+                    Lang::En => format!("⚠ absolute address 0x{:x}; replace with a symbol before reassembling", target),
+                });
+            }
+            let comment = (!notes.is_empty()).then(|| notes.join(self.lang.pick("；", "; ")));
+            lines.push(Line::Code { mnemonic, operands, comment, directive: false });
+        }
+
+        lines.push(Line::directive(".size", format!("{}, .-{}", function, function)));
+        self.style.render(&lines)
+    }
+}
+
+impl Default for Extractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 函数内被跳转、`adr` 或字面量加载引用的地址（函数入口除外），按地址顺序编号为 `.L1`、`.L2`……
+fn labels<'a>(entries: impl Iterator<Item = &'a DumpEntry>) -> BTreeMap<u64, String> {
+    let instructions: Vec<_> = entries.filter_map(|e| e.parsed_instruction.as_ref()).collect();
+    let start = instructions.first().map(|inst| inst.address);
+    let mut targets: Vec<u64> = instructions
+        .iter()
+        .filter(|inst| !matches!(inst.instruction_type, InstructionType::ADRP | InstructionType::BL))
+        .filter_map(|inst| analysis::branch_target(inst))
+        .filter(|target| Some(*target) != start && instructions.iter().any(|inst| inst.address == *target))
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+    targets.into_iter().enumerate().map(|(n, target)| (target, format!(".L{}", n + 1))).collect()
+}
+
+/// 补全页内偏移的 ADD 或只带立即数偏移的加载/存储所用的页（符号名）
+fn completed_page<'a>(inst: &crate::instruction::Instruction, pages: &'a HashMap<usize, String>) -> Option<&'a String> {
+    use InstructionType::*;
+
+    let base = match (inst.instruction_type, inst.operands.as_slice()) {
+        (ADD, [Operand::Register(_), Operand::Register(rn), Operand::Immediate(_)]) => *rn,
+        (
+            LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | STR | STRB | STRH,
+            [Operand::Register(_), Operand::Memory { base, index: None, pre_indexed: false, post_indexed: false, .. }],
+        ) => *base,
+        _ => return None,
+    };
+    pages.get(&base.index()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000400560 <printf@plt>:
+  400560:   d503201f    nop
+
+0000000000400600 <count>:
+  400600:   90000081    adrp x1, 410000 <counter>
+  400604:   b9402821    ldr w1, [x1, #40]
+  400608:   52800002    mov w2, #0x0
+  40060c:   11000442    add w2, w2, #0x1
+  400610:   6b01005f    cmp w2, w1
+  400614:   54ffffcb    b.lt 40060c <count+0xc>
+  400618:   97ffffd2    bl 400560 <printf@plt>
+  40061c:   90000000    adrp x0, 420000 <count+0x1fa00>
+  400620:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_extract() {
+        let entries = ObjdumpParser::new(DUMP.to_string()).extract_function_data("count").unwrap();
+        let source = Extractor::new().extract("count", &entries);
+        let lines: Vec<&str> = source.lines().collect();
+        assert!(lines.contains(&"count:"));
+        assert!(lines.contains(&"\tadrp\tx1, counter"));
+        assert!(lines.contains(&"\tldr\tw1, [x1, :lo12:counter+0x28]"));
+        assert!(lines.contains(&".L1:"));
+        assert!(lines.contains(&"\tb.lt\t.L1"));
+        assert!(lines.contains(&"\tbl\tprintf"));
+        assert!(lines.contains(&"\tadrp\tx0, 0x420000\t// ⚠ 绝对地址 0x420000，重新汇编前需改为符号"));
+        assert_eq!(lines.last(), Some(&"\t.size\tcount, .-count"));
+
+        let commented = Extractor::new().with_comments(true).with_language(Lang::En).extract("count", &entries);
+        assert!(commented.contains("\tmov\tw2, #0x0\t// "));
+    }
+
+    #[test]
+    fn test_extract_style() {
+        let entries = ObjdumpParser::new(DUMP.to_string()).extract_function_data("count").unwrap();
+        let style = AsmStyle { case: MnemonicCase::Upper, indent: Indent::Spaces(4), align: true };
+        let source = Extractor::new().with_style(style).extract("count", &entries);
+        let lines: Vec<&str> = source.lines().collect();
+        assert!(lines.contains(&"    .global  count"));
+        assert!(lines.contains(&"    MOV      w2, #0x0"));
+        assert!(lines.contains(&"    B.LT     .L1"));
+        let comment = lines.iter().find(|l| l.contains("ADRP     x0")).unwrap();
+        assert!(comment.starts_with("    ADRP     x0, 0x420000   "), "{}", comment);
+
+        assert_eq!(Indent::parse("tab").unwrap(), Indent::Tab);
+        assert_eq!(Indent::parse("2").unwrap(), Indent::Spaces(2));
+        assert!(MnemonicCase::parse("title").is_err());
+    }
+}
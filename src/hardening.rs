@@ -0,0 +1,162 @@
+//! 安全加固检查
+//!
+//! 检查函数用到的加固机制：返回地址签名（PAC）、分支目标标识（BTI）和栈保护，
+//! 并列出间接跳转和间接调用，便于安全审计时定位可能被劫持的控制流。
+//! 这些指令大多不在解析器支持的范围内，因此直接按汇编文本识别。
+
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+
+/// 一处间接跳转或间接调用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndirectBranch {
+    /// 指令地址
+    pub address: String,
+    /// 汇编指令
+    pub asm_instruction: String,
+    /// 是否为调用（BLR 系列）
+    pub call: bool,
+}
+
+/// 函数的加固检查结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HardeningReport {
+    /// 签名/验证返回地址的指令（paciasp、autiasp、retaa 等）
+    pub pac: Vec<String>,
+    /// BTI 着陆点指令
+    pub bti: Vec<String>,
+    /// 是否调用 __stack_chk_fail 或读取 __stack_chk_guard
+    pub stack_protector: bool,
+    /// 是否调用其他函数（叶函数通常不需要 PAC 和栈保护）
+    pub calls: bool,
+    /// 间接跳转和间接调用
+    pub indirect: Vec<IndirectBranch>,
+}
+
+/// 旧版 objdump 把 PAC/BTI 指令显示为 hint：#25 paciasp、#27 pacibsp、#29 autiasp、#31 autibsp
+const PAC_HINTS: [&str; 4] = ["#0x19", "#0x1b", "#0x1d", "#0x1f"];
+/// #32-#38（偶数）为 bti、bti c、bti j、bti jc
+const BTI_HINTS: [&str; 4] = ["#0x20", "#0x22", "#0x24", "#0x26"];
+
+impl HardeningReport {
+    /// 检查函数条目
+    pub fn from_entries(entries: &[DumpEntry]) -> Self {
+        let mut report = Self::default();
+        for entry in entries.iter().filter(|e| !e.asm_instruction.is_empty()) {
+            let asm = entry.asm_instruction.to_lowercase();
+            let mut parts = asm.split_whitespace();
+            let mnemonic = parts.next().unwrap_or("");
+            let operand = parts.next().unwrap_or("");
+
+            let pac = matches!(mnemonic, "paciasp" | "pacibsp" | "autiasp" | "autibsp" | "retaa" | "retab")
+                || (mnemonic == "hint" && PAC_HINTS.contains(&operand));
+            let name = if mnemonic == "hint" { asm.clone() } else { mnemonic.to_string() };
+            if pac && !report.pac.contains(&name) {
+                report.pac.push(name);
+            }
+            let bti = mnemonic == "bti" || (mnemonic == "hint" && BTI_HINTS.contains(&operand));
+            if bti && !report.bti.contains(&asm) {
+                report.bti.push(asm.clone());
+            }
+
+            let target = entry.call_target();
+            if asm.contains("__stack_chk_guard") || target.as_deref().is_some_and(|t| t.starts_with("__stack_chk_fail")) {
+                report.stack_protector = true;
+            }
+            report.calls |= mnemonic.starts_with("bl");
+
+            // br/blr 及其带指针认证的变体（braa、blraaz 等）
+            let call = mnemonic.starts_with("blr");
+            let variant = mnemonic.strip_prefix("blr").or_else(|| mnemonic.strip_prefix("br"));
+            if variant.is_some_and(|v| matches!(v, "" | "aa" | "aaz" | "ab" | "abz")) {
+                report.indirect.push(IndirectBranch {
+                    address: entry.address.clone(),
+                    asm_instruction: entry.asm_instruction.replace('\t', " "),
+                    call,
+                });
+            }
+        }
+        report
+    }
+
+    /// Markdown 格式的检查表和间接跳转列表
+    pub fn to_markdown(&self, lang: Lang) -> String {
+        let missing = if self.calls {
+            lang.pick("✗ 未发现", "✗ not found")
+        } else {
+            lang.pick("✗ 未发现（叶函数通常不需要）", "✗ not found (usually unneeded in leaf functions)")
+        };
+        let found = |items: &[String]| format!("✓ {}", items.join(", "));
+
+        let mut output = String::new();
+        output.push_str(lang.pick("| 检查项 | 结果 |\n", "| Check | Result |\n"));
+        output.push_str("|--------|------|\n");
+        output.push_str(&format!(
+            "| {} | {} |\n",
+            lang.pick("返回地址签名 (PAC)", "Return address signing (PAC)"),
+            if self.pac.is_empty() { missing.to_string() } else { found(&self.pac) }
+        ));
+        output.push_str(&format!(
+            "| {} | {} |\n",
+            lang.pick("分支目标标识 (BTI)", "Branch target identification (BTI)"),
+            if self.bti.is_empty() { lang.pick("✗ 未发现", "✗ not found").to_string() } else { found(&self.bti) }
+        ));
+        output.push_str(&format!(
+            "| {} | {} |\n",
+            lang.pick("栈保护", "Stack protector"),
+            if self.stack_protector { String::from("✓ __stack_chk_guard") } else { missing.to_string() }
+        ));
+        output.push_str(&format!(
+            "| {} | {} |\n",
+            lang.pick("间接跳转/调用", "Indirect branches/calls"),
+            self.indirect.len()
+        ));
+
+        if !self.indirect.is_empty() {
+            output.push_str(lang.pick("\n| 地址 | 指令 | 类型 |\n", "\n| Address | Instruction | Kind |\n"));
+            output.push_str("|------|------|------|\n");
+            for branch in &self.indirect {
+                let kind = if branch.call {
+                    lang.pick("间接调用", "indirect call")
+                } else {
+                    lang.pick("间接跳转", "indirect jump")
+                };
+                output.push_str(&format!("| 0x{} | {} | {} |\n", branch.address, branch.asm_instruction, kind));
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <dispatch>:
+   0:   d503233f    paciasp
+   4:   a9bf7bfd    stp x29, x30, [sp, #-16]!
+   8:   90000001    adrp x1, 0 <__stack_chk_guard>
+   c:   d63f0000    blr x0
+  10:   94000000    bl 0 <__stack_chk_fail>
+  14:   a8c17bfd    ldp x29, x30, [sp], #16
+  18:   d50323bf    autiasp
+  1c:   d61f0020    br x1
+"#;
+
+    #[test]
+    fn test_hardening_report() {
+        let entries = ObjdumpParser::new(DUMP.to_string()).extract_function_data("dispatch").unwrap();
+        let report = HardeningReport::from_entries(&entries);
+        assert_eq!(report.pac, vec!["paciasp", "autiasp"]);
+        assert!(report.bti.is_empty());
+        assert!(report.stack_protector);
+        assert_eq!(report.indirect.len(), 2);
+        assert!(report.indirect[0].call && !report.indirect[1].call);
+
+        let text = report.to_markdown(Lang::Zh);
+        assert!(text.contains("| 返回地址签名 (PAC) | ✓ paciasp, autiasp |"));
+        assert!(text.contains("| 0x1c | br x1 | 间接跳转 |"));
+    }
+}
@@ -0,0 +1,243 @@
+//! 内联报告
+//!
+//! 对比各优化级别 dump 中的函数列表：以第一个级别为基准，列出在较高级别中消失的函数
+//! （多半被内联进了调用者）和新出现的克隆/拆分函数（`.constprop`、`.isra`、`.part`、`.cold`）。
+//! 消失的函数附上它在基准级别中的调用者，克隆函数附上它的原函数。
+
+use crate::callgraph::{CallGraph, CallKind};
+use crate::error::Result;
+use crate::locale::Lang;
+use crate::objdump::ObjdumpParser;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// GCC 生成克隆/拆分函数时附加的后缀
+const CLONE_SUFFIXES: &[&str] = &["constprop", "isra", "part", "cold"];
+
+/// 在某个级别中消失的函数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Disappeared {
+    /// 函数名
+    pub name: String,
+    /// 基准级别中直接调用它的函数
+    pub callers: Vec<String>,
+    /// 该级别中代替它的克隆函数（如 `foo.constprop.0`）
+    pub replaced_by: Vec<String>,
+}
+
+/// 某个级别中新出现的克隆/拆分函数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClonedFunction {
+    /// 函数名（如 `foo.isra.0`）
+    pub name: String,
+    /// 原函数名（`foo`）
+    pub origin: String,
+    /// 克隆方式（`constprop`、`isra`、`part`、`cold`）
+    pub kind: String,
+}
+
+/// 一个级别相对基准级别的变化
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LevelChanges {
+    /// 优化级别
+    pub level: String,
+    /// 该级别的函数个数
+    pub functions: usize,
+    /// 消失的函数（按名称排序）
+    pub disappeared: Vec<Disappeared>,
+    /// 新出现的克隆函数（按名称排序）
+    pub clones: Vec<ClonedFunction>,
+}
+
+/// 整个 dump 的内联报告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InliningReport {
+    /// 基准级别
+    pub baseline: String,
+    /// 基准级别的函数个数
+    pub functions: usize,
+    /// 其余级别的变化
+    pub levels: Vec<LevelChanges>,
+}
+
+/// 克隆函数的原函数名和克隆方式：`foo.constprop.0` -> (`foo`, `constprop`)
+pub fn clone_origin(name: &str) -> Option<(&str, &str)> {
+    let (origin, suffix) = name.split_once('.')?;
+    let kind = suffix.split('.').find(|part| CLONE_SUFFIXES.contains(part))?;
+    (!origin.is_empty()).then_some((origin, kind))
+}
+
+impl InliningReport {
+    /// 对比各级别的 dump（第一个为基准）
+    pub fn build(levels: &[(&str, &ObjdumpParser)]) -> Result<Self> {
+        let Some(((baseline, base_parser), rest)) = levels.split_first() else {
+            return Ok(Self { baseline: String::new(), functions: 0, levels: Vec::new() });
+        };
+        let base_functions = base_parser.list_functions()?;
+        let base_set: BTreeSet<&str> = base_functions.iter().map(String::as_str).collect();
+        let graph = CallGraph::build(base_parser)?;
+
+        let mut changes = Vec::with_capacity(rest.len());
+        for (level, parser) in rest {
+            let functions = parser.list_functions()?;
+            let set: BTreeSet<&str> = functions.iter().map(String::as_str).collect();
+
+            let disappeared = base_set
+                .difference(&set)
+                .map(|name| {
+                    let callers: BTreeSet<String> = graph
+                        .edges
+                        .iter()
+                        .filter(|e| e.kind != CallKind::Indirect && e.callee == *name && e.caller != *name)
+                        .map(|e| e.caller.clone())
+                        .collect();
+                    let replaced_by = set
+                        .iter()
+                        .filter(|f| clone_origin(f).is_some_and(|(origin, _)| origin == *name))
+                        .map(|f| f.to_string())
+                        .collect();
+                    Disappeared { name: name.to_string(), callers: callers.into_iter().collect(), replaced_by }
+                })
+                .collect();
+            let clones = set
+                .difference(&base_set)
+                .filter_map(|name| {
+                    let (origin, kind) = clone_origin(name)?;
+                    Some(ClonedFunction { name: name.to_string(), origin: origin.to_string(), kind: kind.to_string() })
+                })
+                .collect();
+
+            changes.push(LevelChanges { level: level.to_string(), functions: functions.len(), disappeared, clones });
+        }
+
+        Ok(Self { baseline: baseline.to_string(), functions: base_functions.len(), levels: changes })
+    }
+
+    /// Markdown 格式的报告
+    pub fn to_markdown(&self, lang: Lang) -> String {
+        let mut output = match lang {
+            Lang::Zh => format!("以 {} 为基准（{} 个函数）。\n\n", self.baseline, self.functions),
+            Lang::En => format!("Relative to {} ({} functions).\n\n", self.baseline, self.functions),
+        };
+        for changes in &self.levels {
+            output.push_str(&format!("### {}\n\n", changes.level));
+            if changes.disappeared.is_empty() && changes.clones.is_empty() {
+                output.push_str(lang.pick("函数列表与基准相同。\n\n", "Same functions as the baseline.\n\n"));
+                continue;
+            }
+
+            if !changes.disappeared.is_empty() {
+                output.push_str(&match lang {
+                    Lang::Zh => format!("消失的函数（{} 个，可能已被内联）:\n\n", changes.disappeared.len()),
+                    Lang::En => format!("Disappeared functions ({}, likely inlined):\n\n", changes.disappeared.len()),
+                });
+                for f in &changes.disappeared {
+                    output.push_str(&format!("- `{}`", f.name));
+                    if !f.replaced_by.is_empty() {
+                        let list = code_list(&f.replaced_by);
+                        output.push_str(&match lang {
+                            Lang::Zh => format!("，由 {} 代替", list),
+                            Lang::En => format!(", replaced by {}", list),
+                        });
+                    } else if !f.callers.is_empty() {
+                        let list = code_list(&f.callers);
+                        output.push_str(&match lang {
+                            Lang::Zh => format!("，{} 中被 {} 调用", self.baseline, list),
+                            Lang::En => format!(", called by {} at {}", list, self.baseline),
+                        });
+                    }
+                    output.push('\n');
+                }
+                output.push('\n');
+            }
+
+            if !changes.clones.is_empty() {
+                output.push_str(&match lang {
+                    Lang::Zh => format!("新出现的克隆函数（{} 个）:\n\n", changes.clones.len()),
+                    Lang::En => format!("New cloned functions ({}):\n\n", changes.clones.len()),
+                });
+                for c in &changes.clones {
+                    output.push_str(&match lang {
+                        Lang::Zh => format!("- `{}`: `{}` 的{}\n", c.name, c.origin, clone_kind(&c.kind, lang)),
+                        Lang::En => format!("- `{}`: {} of `{}`\n", c.name, clone_kind(&c.kind, lang), c.origin),
+                    });
+                }
+                output.push('\n');
+            }
+        }
+        output
+    }
+
+    /// JSON 格式的报告
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+fn code_list(names: &[String]) -> String {
+    names.iter().map(|n| format!("`{}`", n)).collect::<Vec<_>>().join(", ")
+}
+
+/// 克隆方式的说明
+fn clone_kind(kind: &str, lang: Lang) -> &'static str {
+    match kind {
+        "constprop" => lang.pick("常量传播克隆", "constant-propagation clone"),
+        "isra" => lang.pick("参数标量化克隆 (IPA-SRA)", "scalar-replaced clone (IPA-SRA)"),
+        "part" => lang.pick("部分内联后剩下的部分", "outlined part after partial inlining"),
+        _ => lang.pick("拆出的冷路径", "split-out cold path"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inlining_report() {
+        let o0 = r#"
+0000000000000000 <square>:
+   0:   1b007c00    mul w0, w0, w0
+   4:   d65f03c0    ret
+
+0000000000000008 <scale>:
+   8:   1b017c00    mul w0, w0, w1
+   c:   d65f03c0    ret
+
+0000000000000010 <main>:
+  10:   97fffffc    bl 0 <square>
+  14:   97fffffd    bl 8 <scale>
+  18:   d65f03c0    ret
+"#;
+        let o2 = r#"
+0000000000000000 <scale.constprop.0>:
+   0:   531f7800    lsl w0, w0, #1
+   4:   d65f03c0    ret
+
+0000000000000008 <main>:
+   8:   1b007c00    mul w0, w0, w0
+   c:   17fffffd    b 0 <scale.constprop.0>
+"#;
+        let o0 = ObjdumpParser::new(o0.to_string());
+        let o2 = ObjdumpParser::new(o2.to_string());
+        let report = InliningReport::build(&[("O0", &o0), ("O2", &o2)]).unwrap();
+
+        assert_eq!(report.functions, 3);
+        let changes = &report.levels[0];
+        assert_eq!(changes.functions, 2);
+        assert_eq!(changes.disappeared[0].name, "scale");
+        assert_eq!(changes.disappeared[0].replaced_by, ["scale.constprop.0"]);
+        assert_eq!(changes.disappeared[1].name, "square");
+        assert_eq!(changes.disappeared[1].callers, ["main"]);
+        assert_eq!(changes.clones[0].origin, "scale");
+
+        let text = report.to_markdown(Lang::Zh);
+        assert!(text.contains("- `square`，O0 中被 `main` 调用\n"));
+        assert!(text.contains("- `scale`，由 `scale.constprop.0` 代替\n"));
+        assert!(text.contains("- `scale.constprop.0`: `scale` 的常量传播克隆\n"));
+
+        assert_eq!(clone_origin("foo.part.0"), Some(("foo", "part")));
+        assert_eq!(clone_origin("foo.isra.0.cold"), Some(("foo", "isra")));
+        assert_eq!(clone_origin("_start"), None);
+        assert_eq!(clone_origin("foo.bar"), None);
+    }
+}
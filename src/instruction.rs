@@ -38,6 +38,8 @@ pub enum InstructionType {
     NEG,
     ADC,
     SBC,
+    ADDS,
+    SUBS,
     
     // 数据处理 - 逻辑运算
     AND,
@@ -47,6 +49,7 @@ pub enum InstructionType {
     ORN,
     EON,
     MVN,
+    ANDS,
     
     // 数据处理 - 移位
     LSL,
@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::OnceLock;
 use anyhow::{Result, Context};
 
 /// 指令定义（来自 JSON）
@@ -14,11 +15,77 @@ pub struct InstructionDef {
     pub name: String,
     pub format: String,
     pub description: String,
+    /// 英文说明（缺失时使用英文名称）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_en: Option<String>,
+    /// 语义模板，如 `{rd} = {rn} + {rm}`，没有手写解释的指令按模板渲染
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// 英文语义模板（缺失时使用中文模板）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_en: Option<String>,
     #[serde(default)]
     pub flags_affected: Vec<String>,
     pub example: String,
 }
 
+impl InstructionDef {
+    /// 按语言返回指令说明
+    pub fn description_in(&self, lang: crate::locale::Lang) -> &str {
+        match lang {
+            crate::locale::Lang::Zh => &self.description,
+            crate::locale::Lang::En => self.description_en.as_deref().unwrap_or(&self.name),
+        }
+    }
+
+    /// 按语言返回语义模板
+    pub fn template_in(&self, lang: crate::locale::Lang) -> Option<&str> {
+        match lang {
+            crate::locale::Lang::Zh => self.template.as_deref(),
+            crate::locale::Lang::En => self.template_en.as_deref().or(self.template.as_deref()),
+        }
+    }
+}
+
+/// 数据库中缺少的指令的骨架条目（`alaz db stub` 的输出）
+///
+/// 字段与 [`InstructionDef`] 相同，补全名称、格式和说明后即可放进 JSON 中对应的类别；
+/// `category` 和 `occurrences` 只用于整理，加载数据库时会被忽略
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StubEntry {
+    pub mnemonic: String,
+    /// 按指令文本猜测的类别（在 JSON 中的位置，如 `categories/load_store/load`）
+    pub category: String,
+    pub name: String,
+    pub format: String,
+    pub description: String,
+    pub description_en: String,
+    pub flags_affected: Vec<String>,
+    /// dump 中第一次出现时的写法
+    pub example: String,
+    /// 在 dump 中出现的次数
+    pub occurrences: usize,
+}
+
+/// 指令类别在数据库 JSON 中对应的位置
+fn category_path(category: crate::metrics::Category) -> &'static str {
+    use crate::metrics::Category;
+    match category {
+        Category::Arithmetic => "categories/data_processing/arithmetic",
+        Category::Logic => "categories/data_processing/logical",
+        Category::Conditional => "conditional_operations/instructions",
+        Category::Compare => "categories/comparison",
+        Category::Move => "categories/move",
+        Category::Load => "categories/load_store/load",
+        Category::Store => "categories/load_store/store",
+        Category::Branch | Category::Call | Category::Return => "categories/branch/unconditional",
+        Category::FloatingPoint => "floating_point_advanced/instructions",
+        Category::Simd => "simd_data_processing/instructions",
+        Category::Atomic => "atomic_operations_extended/instructions",
+        Category::System | Category::Other => "categories/system",
+    }
+}
+
 /// 指令集数据库
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InstructionDatabase {
@@ -27,6 +94,9 @@ pub struct InstructionDatabase {
     pub categories: HashMap<String, serde_json::Value>,
     #[serde(flatten)]
     pub extra_categories: HashMap<String, serde_json::Value>,
+    /// 助记符到定义的映射表，首次查询时构建一次
+    #[serde(skip)]
+    instruction_map: OnceLock<HashMap<String, InstructionDef>>,
 }
 
 impl InstructionDatabase {
@@ -47,6 +117,11 @@ impl InstructionDatabase {
         Ok(db)
     }
 
+    /// 助记符到定义的映射表（只在第一次调用时遍历 JSON）
+    pub fn instruction_map(&self) -> &HashMap<String, InstructionDef> {
+        self.instruction_map.get_or_init(|| self.build_instruction_map())
+    }
+
     /// 构建指令助记符到定义的映射表
     pub fn build_instruction_map(&self) -> HashMap<String, InstructionDef> {
         let mut map = HashMap::new();
@@ -88,22 +163,50 @@ impl InstructionDatabase {
     }
 
     /// 根据助记符查找指令定义
-    pub fn find_instruction(&self, mnemonic: &str) -> Option<InstructionDef> {
-        let map = self.build_instruction_map();
-        map.get(&mnemonic.to_lowercase()).cloned()
+    pub fn find_instruction(&self, mnemonic: &str) -> Option<&InstructionDef> {
+        self.instruction_map().get(&mnemonic.to_lowercase())
     }
 
     /// 获取所有指令助记符列表
     pub fn get_all_mnemonics(&self) -> Vec<String> {
-        let map = self.build_instruction_map();
-        let mut mnemonics: Vec<String> = map.keys().cloned().collect();
+        let mut mnemonics: Vec<String> = self.instruction_map().keys().cloned().collect();
         mnemonics.sort();
         mnemonics
     }
 
     /// 获取指令数量统计
     pub fn get_instruction_count(&self) -> usize {
-        self.build_instruction_map().len()
+        self.instruction_map().len()
+    }
+
+    /// 为指令文本中数据库没有的助记符生成骨架条目，按出现次数从多到少（相同时按助记符）排列
+    pub fn stub_entries<'a>(&self, instructions: impl IntoIterator<Item = &'a str>) -> Vec<StubEntry> {
+        let mut stubs: Vec<StubEntry> = Vec::new();
+        for text in instructions {
+            let text = text.split("//").next().unwrap_or("").trim();
+            let Some(mnemonic) = text.split_whitespace().next().map(str::to_lowercase) else {
+                continue;
+            };
+            if self.find_instruction(&mnemonic).is_some() {
+                continue;
+            }
+            match stubs.iter_mut().find(|stub| stub.mnemonic == mnemonic) {
+                Some(stub) => stub.occurrences += 1,
+                None => stubs.push(StubEntry {
+                    category: category_path(crate::metrics::Category::of(text)).to_string(),
+                    mnemonic,
+                    name: String::new(),
+                    format: String::new(),
+                    description: String::new(),
+                    description_en: String::new(),
+                    flags_affected: Vec::new(),
+                    example: text.split_whitespace().collect::<Vec<_>>().join(" "),
+                    occurrences: 1,
+                }),
+            }
+        }
+        stubs.sort_by(|a, b| b.occurrences.cmp(&a.occurrences).then_with(|| a.mnemonic.cmp(&b.mnemonic)));
+        stubs
     }
 }
 
@@ -168,4 +271,39 @@ mod tests {
         // 应该有很多指令（至少50+）
         assert!(count > 50, "Expected at least 50 instructions, got {}", count);
     }
+
+    #[test]
+    fn test_stub_entries() {
+        let db = InstructionDatabase::load_embedded().unwrap();
+        let stubs = db.stub_entries([
+            "add x0, x1, x2",
+            "ldnp q0, q1, [x0]",
+            "frobnicate x0",
+            "LDNP\tq2, q3, [x0, #32]   // spill",
+            "b.ne 10 <f+0x10>",
+        ]);
+        assert_eq!(stubs.len(), 2);
+        assert_eq!(stubs[0].mnemonic, "ldnp");
+        assert_eq!(stubs[0].occurrences, 2);
+        assert_eq!(stubs[0].category, "categories/load_store/load");
+        assert_eq!(stubs[0].example, "ldnp q0, q1, [x0]");
+        assert_eq!(stubs[1].mnemonic, "frobnicate");
+        assert!(stubs[1].description.is_empty());
+
+        // 骨架条目可以直接作为指令定义加载
+        let json = serde_json::to_value(&stubs[0]).unwrap();
+        let def: InstructionDef = serde_json::from_value(json).unwrap();
+        assert_eq!(def.mnemonic, "ldnp");
+    }
+
+    #[test]
+    fn test_instruction_map_cached() {
+        let db = InstructionDatabase::load_embedded().unwrap();
+        let first = db.find_instruction("add").unwrap() as *const InstructionDef;
+        let second = db.find_instruction("ADD").unwrap() as *const InstructionDef;
+        // 两次查询返回同一份定义，映射表只构建一次
+        assert_eq!(first, second);
+        assert!(std::ptr::eq(db.instruction_map(), db.instruction_map()));
+        assert_eq!(db.get_instruction_count(), db.build_instruction_map().len());
+    }
 }
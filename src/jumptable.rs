@@ -0,0 +1,347 @@
+//! 跳转表（switch 语句）识别
+//!
+//! 编译器把 case 密集的 `switch` 编译成跳转表：`cmp` + `b.hi` 排除越界的下标，`adrp` + `add` 取得表的地址，
+//! `ldrb`/`ldrh`/`ldrsw` 按下标加载表项，与 `adr` 取得的基址（或表本身的地址）相加后由 `br` 跳转。
+//! 本模块识别这一模式，并从 dump 中节的内容（`objdump -d -s` 生成）读出表项，还原每个 case 的跳转目标。
+
+use crate::analysis;
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::locale::Lang;
+use crate::objdump::{DumpEntry, ObjdumpParser, SymbolTable};
+use crate::register::Register;
+use crate::semantic::{awaits_relocation, pc_relative_target};
+use regex::Regex;
+use serde::Serialize;
+
+/// 查找寄存器的定义时最多向前回溯的指令数
+const SEARCH_WINDOW: usize = 16;
+
+/// 一个跳转表
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JumpTable {
+    /// `br` 指令地址
+    pub address: String,
+    /// 下标寄存器
+    pub index: String,
+    /// 下标为 0 的表项对应的 case 值（下标由 `sub wN, wM, #k` 得到时为 k）
+    pub first_case: i64,
+    /// 表的地址（未链接的目标文件中待重定位，为 None）
+    pub table: Option<u64>,
+    /// 表项数（取自边界检查，找不到时为 None）
+    pub len: Option<usize>,
+    /// 下标越界时的跳转目标
+    pub default: Option<u64>,
+    /// 每个表项的跳转目标（表的地址、表项数或表的内容未知时为空）
+    pub targets: Vec<u64>,
+}
+
+impl JumpTable {
+    /// 说明：各 case 的跳转目标（相同目标的 case 合并），无法列出时说明原因
+    pub fn describe(&self, symbols: &SymbolTable, lang: Lang) -> String {
+        let name = |address: u64| symbols.resolve(address).unwrap_or_else(|| format!("0x{:x}", address));
+        let mut text = match (self.len, lang) {
+            (Some(len), Lang::Zh) => format!("🔀 switch 跳转表（下标 {}，{} 项）", self.index, len),
+            (None, Lang::Zh) => format!("🔀 switch 跳转表（下标 {}）", self.index),
+            (Some(len), Lang::En) => format!("🔀 switch jump table (index {}, {} entries)", self.index, len),
+            (None, Lang::En) => format!("🔀 switch jump table (index {})", self.index),
+        };
+
+        if self.targets.is_empty() {
+            let reason = match (self.table, self.len) {
+                (None, _) => lang.pick(
+                    "：表的地址待链接器重定位，无法读取表项",
+                    ": the table address awaits relocation, entries cannot be read",
+                ),
+                (_, None) => lang.pick(
+                    "：未找到边界检查，无法确定表项数",
+                    ": no bounds check found, the number of entries is unknown",
+                ),
+                _ => lang.pick(
+                    "：dump 中没有表的内容，用 objdump -d -s 生成 dump 后可列出各 case 的目标",
+                    ": the dump has no section contents; regenerate it with objdump -d -s to list the case targets",
+                ),
+            };
+            text.push_str(reason);
+        } else {
+            let mut groups: Vec<(u64, Vec<String>)> = Vec::new();
+            for (i, target) in self.targets.iter().enumerate() {
+                let case = (self.first_case + i as i64).to_string();
+                match groups.iter_mut().find(|(t, _)| t == target) {
+                    Some((_, cases)) => cases.push(case),
+                    None => groups.push((*target, vec![case])),
+                }
+            }
+            let cases: Vec<String> = groups
+                .iter()
+                .map(|(target, cases)| format!("case {} → {}", cases.join(", "), name(*target)))
+                .collect();
+            text.push_str(lang.pick("：", ": "));
+            text.push_str(&cases.join(lang.pick("；", "; ")));
+        }
+        if let Some(default) = self.default {
+            text.push_str(lang.pick("；default → ", "; default → "));
+            text.push_str(&name(default));
+        }
+        text
+    }
+}
+
+/// 识别用到的指令形式（带扩展下标的加载无法被指令解析器解析，按文本匹配）
+struct Patterns {
+    br: Regex,
+    add: Regex,
+    load: Regex,
+}
+
+impl Patterns {
+    fn new() -> Self {
+        Self {
+            br: Regex::new(r"^br\s+(x\d+)$").unwrap(),
+            add: Regex::new(r"^add\s+(x\d+),\s*(x\d+),\s*([wx]\d+)(?:,\s*([su]xt[bhw]|lsl)(?:\s*#(\d+))?)?$").unwrap(),
+            load: Regex::new(
+                r"^(ldrb|ldrh|ldrsb|ldrsh|ldrsw|ldr)\s+([wx]\d+),\s*\[(x\d+),\s*([wx]\d+)(?:,\s*(?:[su]xtw|lsl)(?:\s*#\d+)?)?\]$",
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// 找出函数中所有通过跳转表的 `br`（按地址排序），表的内容从 parser 中节的内容读取
+pub fn find(entries: &[DumpEntry], parser: &ObjdumpParser) -> Vec<JumpTable> {
+    let patterns = Patterns::new();
+    let rows: Vec<&DumpEntry> = entries.iter().filter(|e| !e.asm_instruction.is_empty()).collect();
+    (0..rows.len()).filter_map(|i| recognize(&rows, i, parser, &patterns)).collect()
+}
+
+/// 去掉 objdump 的 `//` 注释后的小写指令文本
+fn text(entry: &DumpEntry) -> String {
+    let asm = entry.asm_instruction.split("//").next().unwrap_or("");
+    asm.replace('\t', " ").trim().to_lowercase()
+}
+
+/// 通用寄存器编号
+fn reg(name: &str) -> Option<usize> {
+    Register::parse(name).ok()?.index()
+}
+
+/// 指令是否写入编号为 r 的寄存器
+fn writes(entry: &DumpEntry, r: usize) -> bool {
+    match entry.parsed_instruction {
+        Some(ref inst) => analysis::def_use(inst).0.contains(r),
+        None => {
+            // 无法解析的加载/加法按第一个操作数判断
+            let text = text(entry);
+            let mut parts = text.splitn(2, ' ');
+            let mnemonic = parts.next().unwrap_or("");
+            let dest = parts.next().and_then(|ops| ops.split(',').next()).map(str::trim);
+            (mnemonic.starts_with("ld") || mnemonic == "add") && dest.and_then(reg) == Some(r)
+        }
+    }
+}
+
+/// 第 before 条指令之前最近一次写入寄存器 r 的指令
+fn definition(rows: &[&DumpEntry], before: usize, r: usize) -> Option<usize> {
+    (before.saturating_sub(SEARCH_WINDOW)..before).rev().find(|&i| writes(rows[i], r))
+}
+
+/// 解析后的指令（类型相符时）
+fn parsed(entry: &DumpEntry, kind: InstructionType) -> Option<&Instruction> {
+    entry.parsed_instruction.as_ref().filter(|inst| inst.instruction_type == kind)
+}
+
+/// ADR 计算出的地址（未链接时为 None）
+fn adr_target(inst: &Instruction) -> Option<u64> {
+    pc_relative_target(inst).filter(|target| !awaits_relocation(inst, *target))
+}
+
+/// 识别以第 br 条指令结尾的跳转表
+fn recognize(rows: &[&DumpEntry], br: usize, parser: &ObjdumpParser, patterns: &Patterns) -> Option<JumpTable> {
+    // br xT ← add xT, xB, wE, sxtb #2
+    let branch = text(rows[br]);
+    let target_reg = reg(&patterns.br.captures(&branch)?[1])?;
+    let add = definition(rows, br, target_reg)?;
+    let add_text = text(rows[add]);
+    let caps = patterns.add.captures(&add_text)?;
+    let (base_reg, entry_reg) = (reg(&caps[2])?, reg(&caps[3])?);
+    let extend = caps.get(4).map_or("", |m| m.as_str());
+    let shift: u32 = caps.get(5).map_or(Some(0), |m| m.as_str().parse().ok())?;
+
+    // wE ← ldrb wE, [xTable, wI, uxtw]
+    let load = definition(rows, add, entry_reg)?;
+    let load_text = text(rows[load]);
+    let caps = patterns.load.captures(&load_text)?;
+    let size = match &caps[1] {
+        "ldrb" | "ldrsb" => 1,
+        "ldrh" | "ldrsh" => 2,
+        "ldr" if caps[2].starts_with('x') => 8,
+        _ => 4,
+    };
+    let signed = caps[1].starts_with("ldrs") || extend.starts_with("sxt");
+    let (table_reg, index_reg) = (reg(&caps[3])?, reg(&caps[4])?);
+    let index = caps[4].to_string();
+
+    // 基址来自 adr，或表项是相对表本身的偏移
+    let table = table_address(rows, load, table_reg);
+    let base = if base_reg == table_reg && definition(rows, add, base_reg).is_none_or(|d| d < load) {
+        table
+    } else {
+        adr_target(parsed(rows[definition(rows, add, base_reg)?], InstructionType::ADR)?)
+    };
+    // 基址待重定位时表的地址同样没有意义
+    let table = table.filter(|_| base.is_some());
+
+    let bound = bounds_check(rows, load, index_reg);
+    let first_case = bound
+        .and_then(|(cmp, _, _)| parsed(rows[definition(rows, cmp, index_reg)?], InstructionType::SUB))
+        .and_then(|sub| match sub.operands.as_slice() {
+            [Operand::Register(_), Operand::Register(_), Operand::Immediate(k)] => Some(*k),
+            _ => None,
+        })
+        .unwrap_or(0);
+    let len = bound.map(|(_, len, _)| len);
+
+    let targets = match (table, base, len) {
+        (Some(table), Some(base), Some(len)) => parser
+            .read_data(table, len * size)
+            .map(|bytes| {
+                bytes
+                    .chunks(size)
+                    .map(|chunk| {
+                        let raw = chunk.iter().rev().fold(0u64, |v, b| (v << 8) | *b as u64);
+                        let bits = (size * 8) as u32;
+                        let value = if signed && bits < 64 {
+                            ((raw << (64 - bits)) as i64) >> (64 - bits)
+                        } else {
+                            raw as i64
+                        };
+                        base.wrapping_add_signed(value << shift)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    Some(JumpTable {
+        address: rows[br].address.clone(),
+        index,
+        first_case,
+        table,
+        len,
+        default: bound.map(|(_, _, default)| default),
+        targets,
+    })
+}
+
+/// 表的地址：`adrp` + `add` 或 `adr`（未链接时为 None）
+fn table_address(rows: &[&DumpEntry], load: usize, table_reg: usize) -> Option<u64> {
+    let def = definition(rows, load, table_reg)?;
+    let inst = rows[def].parsed_instruction.as_ref()?;
+    match (inst.instruction_type, inst.operands.as_slice()) {
+        (InstructionType::ADR, _) => adr_target(inst),
+        (InstructionType::ADD, [Operand::Register(_), Operand::Register(rn), Operand::Immediate(low)]) => {
+            let page = parsed(rows[definition(rows, def, rn.index()?)?], InstructionType::ADRP)?;
+            let address = pc_relative_target(page)?;
+            // 未链接的目标文件中 ADRP 指向自身所在的页，页内偏移也为 0
+            if *low == 0 && awaits_relocation(page, address) {
+                return None;
+            }
+            Some(address.wrapping_add(*low as u64))
+        }
+        _ => None,
+    }
+}
+
+/// 下标的边界检查 `cmp wI, #k` + `b.hi`/`b.hs`（加载之前最近的分支）：返回 cmp 的位置、表项数和越界时的目标
+fn bounds_check(rows: &[&DumpEntry], load: usize, index_reg: usize) -> Option<(usize, usize, u64)> {
+    let branch = (load.saturating_sub(SEARCH_WINDOW)..load)
+        .rev()
+        .find(|&i| rows[i].parsed_instruction.as_ref().is_some_and(analysis::is_branch))?;
+    let inst = rows[branch].parsed_instruction.as_ref()?;
+    let cmp = parsed(rows[branch.checked_sub(1)?], InstructionType::CMP)?;
+    let limit = match cmp.operands.as_slice() {
+        [Operand::Register(r), Operand::Immediate(k)] if r.index() == Some(index_reg) && *k >= 0 => *k as usize,
+        _ => return None,
+    };
+    let len = match inst.instruction_type {
+        InstructionType::BHI => limit + 1,
+        InstructionType::BCS => limit,
+        _ => return None,
+    };
+    Some((branch - 1, len, analysis::branch_target(inst)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"
+Contents of section .rodata:
+ 400640 d8ffffff dcffffff                    ........
+ 4007a0 00020400 02040000 feff0000 01000000  ................
+
+Disassembly of section .text:
+
+00000000004005c0 <f>:
+  4005c0:   7100141f    cmp w0, #0x5
+  4005c4:   540001a8    b.hi 4005f8 <f+0x38>
+  4005c8:   90000001    adrp x1, 400000 <f-0x5c0>
+  4005cc:   911e8021    add x1, x1, #0x7a0
+  4005d0:   38604821    ldrb w1, [x1, w0, uxtw]
+  4005d4:   10000062    adr x2, 4005e0 <f+0x20>
+  4005d8:   8b218841    add x1, x2, w1, sxtb #2
+  4005dc:   d61f0020    br x1
+  4005e0:   52800140    mov w0, #0xa
+  4005e4:   d65f03c0    ret
+  4005e8:   52800280    mov w0, #0x14
+  4005ec:   d65f03c0    ret
+  4005f0:   528003c0    mov w0, #0x1e
+  4005f4:   d65f03c0    ret
+  4005f8:   52800000    mov w0, #0x0
+  4005fc:   d65f03c0    ret
+
+0000000000400600 <g>:
+  400600:   51000c08    sub w8, w0, #0x3
+  400604:   7100051f    cmp w8, #0x1
+  400608:   540000a8    b.hi 40061c <g+0x1c>
+  40060c:   10000069    adr x9, 400640 <g+0x40>
+  400610:   b8a87928    ldrsw x8, [x9, x8, lsl #2]
+  400614:   8b080129    add x9, x9, x8
+  400618:   d61f0120    br x9
+  40061c:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_find_jump_table() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let tables = find(&parser.extract_function_data("f").unwrap(), &parser);
+        assert_eq!(tables.len(), 1);
+        let table = &tables[0];
+        assert_eq!((table.address.as_str(), table.index.as_str()), ("4005dc", "w0"));
+        assert_eq!((table.table, table.len, table.default), (Some(0x4007a0), Some(6), Some(0x4005f8)));
+        assert_eq!(table.targets, vec![0x4005e0, 0x4005e8, 0x4005f0, 0x4005e0, 0x4005e8, 0x4005f0]);
+        assert_eq!(
+            table.describe(parser.symbols(), Lang::Zh),
+            "🔀 switch 跳转表（下标 w0，6 项）：case 0, 3 → f+0x20；case 1, 4 → f+0x28；case 2, 5 → f+0x30；default → f+0x38"
+        );
+    }
+
+    #[test]
+    fn test_relative_jump_table() {
+        // 表项是相对表本身的偏移（ldrsw + 表的地址），下标由 case 值减 3 得到
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let mut tables = find(&parser.extract_function_data("g").unwrap(), &parser);
+        assert_eq!(tables.len(), 1);
+        let table = &mut tables[0];
+        assert_eq!((table.first_case, table.len, table.default), (3, Some(2), Some(0x40061c)));
+        assert_eq!(table.targets, vec![0x400618, 0x40061c]);
+        assert_eq!(
+            table.describe(parser.symbols(), Lang::En),
+            "🔀 switch jump table (index x8, 2 entries): case 3 → g+0x18; case 4 → g+0x1c; default → g+0x1c"
+        );
+
+        // dump 中没有表的内容时说明原因
+        table.targets.clear();
+        assert!(table.describe(parser.symbols(), Lang::Zh).contains("objdump -d -s"));
+    }
+}
@@ -0,0 +1,215 @@
+//! 优化级别
+//!
+//! dump 文件按 `<前缀>_<级别>.dump` 命名（如 `matrix_O2.dump`、`matrix_Os.dump`）。
+//! 本模块负责从文件名中去掉级别后缀、在目录中自动发现已有的级别，并给出各级别的显示标题。
+
+use crate::error::{InterpreterError, Result};
+use crate::locale::Lang;
+use crate::objdump::ObjdumpParser;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// 未指定且无法自动发现时使用的优化级别
+pub const DEFAULT_LEVELS: [&str; 3] = ["O0", "O1", "O2"];
+
+/// 已知优化级别的显示顺序和说明（中文、英文）
+const KNOWN_LEVELS: [(&str, &str, &str); 8] = [
+    ("O0", "无优化", "no optimization"),
+    ("O1", "基础优化", "basic optimization"),
+    ("O2", "高级优化", "full optimization"),
+    ("O3", "激进优化", "aggressive optimization"),
+    ("Os", "优化大小", "optimize for size"),
+    ("Oz", "极致优化大小", "optimize aggressively for size"),
+    ("Og", "调试友好的优化", "debug-friendly optimization"),
+    ("Ofast", "不严格遵守标准的激进优化", "aggressive, standards-relaxed optimization"),
+];
+
+/// 判断字符串是否像一个优化级别（O 开头，后跟字母或数字）
+fn is_level(text: &str) -> bool {
+    text.strip_prefix('O')
+        .is_some_and(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// 去掉 `.dump` 后缀和 `_<级别>` 后缀，得到 dump 文件前缀
+///
+/// `matrix_O2.dump` -> `matrix`，`matrix` -> `matrix`
+pub fn clean_prefix(prefix: &str) -> String {
+    let prefix = prefix.strip_suffix(".dump").unwrap_or(prefix);
+    match prefix.rsplit_once('_') {
+        Some((base, level)) if is_level(level) => base.to_string(),
+        _ => prefix.to_string(),
+    }
+}
+
+/// 某个级别对应的 dump 文件路径
+pub fn dump_path(prefix: &str, level: &str) -> String {
+    format!("{}_{}.dump", prefix, level)
+}
+
+/// 按已知级别的顺序排序，未知级别按名称排在最后
+pub fn sort_levels(levels: &mut [String]) {
+    levels.sort_by_key(|level| {
+        let rank = KNOWN_LEVELS
+            .iter()
+            .position(|(known, _, _)| known == level)
+            .unwrap_or(KNOWN_LEVELS.len());
+        (rank, level.clone())
+    });
+}
+
+/// 在前缀所在目录中查找所有 `<前缀>_<级别>.dump` 文件，返回排好序的级别列表
+pub fn discover(prefix: &str) -> Vec<String> {
+    let path = Path::new(prefix);
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Some(stem) = path.file_name().and_then(|s| s.to_str()) else {
+        return Vec::new();
+    };
+
+    let mut levels: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let level = name.strip_prefix(stem)?.strip_prefix('_')?.strip_suffix(".dump")?;
+            is_level(level).then(|| level.to_string())
+        })
+        .collect();
+    sort_levels(&mut levels);
+    levels
+}
+
+/// 并行读取并解析各级别的 dump 文件，结果与 `levels` 一一对应
+///
+/// 每个文件在单独的线程上解析；任一文件失败时返回第一个级别的错误
+pub fn load_all(prefix: &str, levels: &[String]) -> Result<Vec<ObjdumpParser>> {
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = levels
+            .iter()
+            .map(|level| scope.spawn(move || ObjdumpParser::from_file(&dump_path(prefix, level))))
+            .collect();
+        workers.into_iter().map(|worker| worker.join().expect("解析线程异常退出")).collect()
+    })
+}
+
+/// 所有级别的 dump 文件中都存在的函数，按名称排序
+pub fn common_functions(prefix: &str, levels: &[String]) -> Result<Vec<String>> {
+    let mut common: Option<BTreeSet<String>> = None;
+    for parser in load_all(prefix, levels)? {
+        let functions: BTreeSet<String> = parser.list_functions()?.into_iter().collect();
+        common = Some(match common {
+            None => functions,
+            Some(existing) => existing.intersection(&functions).cloned().collect(),
+        });
+    }
+    Ok(common.unwrap_or_default().into_iter().collect())
+}
+
+/// 级别的显示标题，如 `O2 (高级优化)`
+pub fn title(level: &str) -> String {
+    title_in(level, Lang::Zh)
+}
+
+/// 按语言返回级别的显示标题，如 `O2 (full optimization)`
+pub fn title_in(level: &str, lang: Lang) -> String {
+    match KNOWN_LEVELS.iter().find(|(known, _, _)| *known == level) {
+        Some((_, zh, en)) => format!("{} ({})", level, lang.pick(zh, en)),
+        None => level.to_string(),
+    }
+}
+
+/// 自定义的级别标题（如 `Os=Os (体积优化)`、`O2=O2+LTO`），用于报告标题和统计表的列名
+#[derive(Debug, Clone, Default)]
+pub struct LevelLabels {
+    labels: BTreeMap<String, String>,
+}
+
+impl LevelLabels {
+    /// 创建空的标题表（全部使用内置标题）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 解析 `LEVEL=TITLE` 形式的标题
+    pub fn parse(specs: &[String]) -> Result<Self> {
+        let mut labels = Self::new();
+        for spec in specs {
+            match spec.split_once('=') {
+                Some((level, title)) if !level.trim().is_empty() && !title.trim().is_empty() => {
+                    labels = labels.with_label(level.trim(), title.trim());
+                }
+                _ => {
+                    return Err(InterpreterError::ParseError(format!(
+                        "级别标题格式应为 LEVEL=TITLE: {}",
+                        spec
+                    )))
+                }
+            }
+        }
+        Ok(labels)
+    }
+
+    /// 设置级别的标题
+    pub fn with_label(mut self, level: &str, title: &str) -> Self {
+        self.labels.insert(level.to_string(), title.to_string());
+        self
+    }
+
+    /// 级别的显示标题：有自定义标题时原样使用，否则为内置标题
+    pub fn title_in(&self, level: &str, lang: Lang) -> String {
+        match self.labels.get(level) {
+            Some(title) => title.clone(),
+            None => title_in(level, lang),
+        }
+    }
+
+    /// 表格列名等简短场合的名称：有自定义标题时使用自定义标题，否则为级别名
+    pub fn name<'a>(&'a self, level: &'a str) -> &'a str {
+        self.labels.get(level).map(String::as_str).unwrap_or(level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_prefix_and_sort() {
+        assert_eq!(clean_prefix("matrix_O2.dump"), "matrix");
+        assert_eq!(clean_prefix("dir/matrix_Ofast.dump"), "dir/matrix");
+        assert_eq!(clean_prefix("spark_matrix_naive"), "spark_matrix_naive");
+
+        let mut levels = vec![String::from("Os"), String::from("O2"), String::from("O0")];
+        sort_levels(&mut levels);
+        assert_eq!(levels, vec!["O0", "O2", "Os"]);
+        assert_eq!(title("O3"), "O3 (激进优化)");
+        assert_eq!(title_in("Os", Lang::En), "Os (optimize for size)");
+
+        let labels = LevelLabels::parse(&[String::from("O2=O2+LTO")]).unwrap();
+        assert_eq!(labels.title_in("O2", Lang::Zh), "O2+LTO");
+        assert_eq!(labels.title_in("O0", Lang::Zh), "O0 (无优化)");
+        assert_eq!(labels.name("O0"), "O0");
+        assert!(LevelLabels::parse(&[String::from("O2")]).is_err());
+    }
+
+    #[test]
+    fn test_discover_levels() {
+        let dir = std::env::temp_dir().join("alaz-levels-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["prog_O3.dump", "prog_O0.dump", "prog_Os.dump", "prog_notes.txt", "other_O1.dump"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        std::fs::write(dir.join("prog_O0.dump"), "0000000000000000 <f>:\n0000000000000010 <g>:\n").unwrap();
+        std::fs::write(dir.join("prog_O3.dump"), "0000000000000000 <g>:\n").unwrap();
+
+        let prefix = dir.join("prog");
+        let levels = discover(&prefix.to_string_lossy());
+        assert_eq!(levels, vec!["O0", "O3", "Os"]);
+        assert_eq!(common_functions(&prefix.to_string_lossy(), &levels[..2]).unwrap(), vec!["g"]);
+        assert!(load_all(&prefix.to_string_lossy(), &[String::from("O0"), String::from("O9")]).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
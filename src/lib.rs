@@ -8,21 +8,108 @@
 //! - `instruction_db`: 指令数据库（从 JSON 加载）
 //! - `register`: 寄存器定义和管理
 //! - `parser`: 汇编代码解析器
+//! - `decoder`: 机器码解码（与文本解析交叉检查）
+//! - `encoder`: 指令重新编码（与机器码交叉检查）
+//! - `arm32`: ARM32（A32/T32）指令的解析与语义解释
 //! - `error`: 错误类型定义
 //! - `objdump`: objdump 文件解析器
+//! - `diagnostics`: 解析诊断（未完整解析的指令与解析覆盖率）
 //! - `semantic`: 汇编指令语义解释器
+//! - `locale`: 输出语言（中文/英文）
+//! - `explain`: 单条指令解释（解析 + 语义 + 数据库）
+//! - `analysis`: 数据流分析（def/use、寄存器活跃性与循环检测）
+//! - `walkthrough`: 函数导读生成
+//! - `signature`: 函数签名推断
+//! - `deadstore`: 无用存储检测
+//! - `xref`: 寄存器交叉引用
+//! - `width`: 操作数位宽检查（隐式零扩展、扩展方式与比较不一致）
+//! - `stackalign`: 调用点的栈对齐检查
+//! - `jumptable`: 跳转表（switch 语句）识别
+//! - `vectorize`: 向量化检测（NEON/SVE 指令与循环）
+//! - `unroll`: 循环展开检测（估计展开倍数）
+//! - `arrows`: 跳转箭头（函数内跳转与目标之间的连线）
+//! - `pseudocode`: 类 C 伪代码（近似反编译）
+//! - `values`: 值追踪（常量传播与线性值）
+//! - `hardening`: 安全加固检查（PAC/BTI/栈保护、间接跳转）
 //! - `table`: Markdown 表格生成器
+//! - `metrics`: 指令统计（按类别）
+//! - `cost`: 指令代价模型（各核心的延迟/吞吐量与周期估计）
+//! - `levels`: 优化级别（前缀处理与自动发现）
+//! - `analyzer`: 批量分析（并行生成报告与结果清单）与结构化分析结果
+//! - `progress`: 进度通知（库代码不直接打印进度）
+//! - `profile`: 分析配置档（teaching/performance/security）
+//! - `config`: 配置文件（~/.config/alaz/config.toml 中的默认设置）
+//! - `emulator`: 指令模拟器（单步执行）
+//! - `syscall`: 系统调用模拟（svc #0）
+//! - `libc`: 外部库函数知识库（PLT 调用标注）
+//! - `trace_export`: 执行轨迹导出（JSON/二进制）
+//! - `equivalence`: 跨优化级别的差分模拟
+//! - `notes`: 用户备注文件（YAML）
+//! - `remarks`: 编译器优化备注（clang 优化记录 / GCC -fopt-info）
+//! - `outline`: 共享代码（outlining）检测
+//! - `callgraph`: 调用图生成（DOT/JSON）
+//! - `inlining`: 内联报告（各级别间消失的函数与克隆函数）
+//! - `annotate`: 逆向工具注释脚本导出（Ghidra/Binary Ninja）
+//! - `extract`: 函数提取为可重新汇编的 `.s` 文件
+//! - `tui`: 全屏终端界面
+//! - `picker`: 交互式模式的函数列表（过滤与分页）
+//! - `build`: 从 C 源码编译并生成 dump 文件
+//! - `completion`: Shell 补全脚本中的函数名补全
 
 pub mod instruction;
 pub mod instruction_db;
 pub mod register;
 pub mod parser;
+pub mod decoder;
+pub mod encoder;
+pub mod arm32;
 pub mod error;
 pub mod objdump;
+pub mod diagnostics;
 pub mod semantic;
+pub mod locale;
+pub mod explain;
+pub mod analysis;
+pub mod walkthrough;
+pub mod signature;
+pub mod deadstore;
+pub mod xref;
+pub mod width;
+pub mod stackalign;
+pub mod jumptable;
+pub mod vectorize;
+pub mod unroll;
+pub mod arrows;
+pub mod pseudocode;
+pub mod values;
+pub mod hardening;
 pub mod table;
+pub mod metrics;
+pub mod cost;
+pub mod levels;
+pub mod analyzer;
+pub mod progress;
+pub mod profile;
+pub mod config;
+pub mod emulator;
+pub mod syscall;
+pub mod libc;
+pub mod trace_export;
+pub mod equivalence;
+pub mod notes;
+pub mod remarks;
+pub mod outline;
+pub mod callgraph;
+pub mod inlining;
+pub mod annotate;
+pub mod extract;
+pub mod tui;
+pub mod picker;
+pub mod build;
+pub mod completion;
 
 // 重新导出常用类型
 pub use instruction::{Instruction, InstructionType, Operand};
 pub use register::Register;
 pub use error::{Result, InterpreterError};
+pub use explain::{explain, Explanation};
@@ -0,0 +1,110 @@
+//! 外部库函数知识库
+//!
+//! 动态链接的程序通过 PLT 桩（`<memcpy@plt>`）调用共享库中的函数，`_start` 还会直接调用
+//! `__libc_start_main` 等 C 运行库函数。这里内置一份常用 C 标准库函数的简短说明，
+//! 让报告中的调用可以标注为外部库调用并说明其作用。
+
+use crate::locale::Lang;
+
+/// 常用 C 库函数：(函数名, 中文说明, 英文说明)
+const LIBC_FUNCTIONS: &[(&str, &str, &str)] = &[
+    ("memcpy", "内存复制", "copy memory"),
+    ("memmove", "内存复制（允许重叠）", "copy possibly overlapping memory"),
+    ("memset", "内存填充", "fill memory"),
+    ("memcmp", "内存比较", "compare memory"),
+    ("strlen", "计算字符串长度", "string length"),
+    ("strcpy", "字符串复制", "copy a string"),
+    ("strncpy", "复制最多 n 个字符", "copy at most n characters"),
+    ("strcat", "字符串拼接", "concatenate strings"),
+    ("strcmp", "字符串比较", "compare strings"),
+    ("strncmp", "比较最多 n 个字符", "compare at most n characters"),
+    ("strchr", "查找字符", "find a character"),
+    ("strstr", "查找子串", "find a substring"),
+    ("strdup", "复制字符串到新分配的内存", "duplicate a string"),
+    ("printf", "格式化输出", "formatted output"),
+    ("fprintf", "格式化输出到文件", "formatted output to a stream"),
+    ("sprintf", "格式化输出到字符串", "formatted output to a string"),
+    ("snprintf", "格式化输出到定长字符串", "bounded formatted output to a string"),
+    ("puts", "输出一行字符串", "write a line"),
+    ("putchar", "输出一个字符", "write a character"),
+    ("getchar", "读取一个字符", "read a character"),
+    ("scanf", "格式化输入", "formatted input"),
+    ("fopen", "打开文件", "open a file"),
+    ("fclose", "关闭文件", "close a file"),
+    ("fread", "读取文件", "read from a stream"),
+    ("fwrite", "写入文件", "write to a stream"),
+    ("open", "打开文件描述符", "open a file descriptor"),
+    ("close", "关闭文件描述符", "close a file descriptor"),
+    ("read", "从文件描述符读取", "read from a file descriptor"),
+    ("write", "写入文件描述符", "write to a file descriptor"),
+    ("malloc", "分配内存", "allocate memory"),
+    ("calloc", "分配并清零内存", "allocate zeroed memory"),
+    ("realloc", "调整已分配内存的大小", "resize allocated memory"),
+    ("free", "释放内存", "free memory"),
+    ("atoi", "字符串转整数", "convert a string to int"),
+    ("strtol", "字符串转长整数", "convert a string to long"),
+    ("qsort", "快速排序", "sort an array"),
+    ("rand", "生成伪随机数", "pseudo-random number"),
+    ("srand", "设置随机数种子", "seed the random number generator"),
+    ("time", "获取当前时间", "current time"),
+    ("sqrt", "平方根", "square root"),
+    ("pow", "幂运算", "power"),
+    ("sin", "正弦", "sine"),
+    ("cos", "余弦", "cosine"),
+    ("exit", "退出程序", "terminate the program"),
+    ("abort", "异常终止程序", "abort the program"),
+    ("__libc_start_main", "C 运行库入口：初始化后调用 main", "C runtime entry: initializes and calls main"),
+    ("__stack_chk_fail", "栈保护检测到溢出，终止程序", "stack protector failure, aborts"),
+    ("__cxa_finalize", "运行全局析构函数", "run global destructors"),
+    ("__cxa_atexit", "注册退出时调用的函数", "register an exit handler"),
+    ("__gmon_start__", "性能分析初始化（gprof）", "profiling setup (gprof)"),
+];
+
+/// 去掉 `@plt` 和符号版本（`memcpy@GLIBC_2.17`）后的函数名
+pub fn base_name(symbol: &str) -> &str {
+    symbol.split('@').next().unwrap_or(symbol)
+}
+
+/// 是否为通过 PLT 调用的外部函数
+pub fn is_plt(symbol: &str) -> bool {
+    symbol.ends_with("@plt")
+}
+
+/// 查找 C 库函数的说明（可带 `@plt` 后缀）
+pub fn describe(symbol: &str, lang: Lang) -> Option<&'static str> {
+    let name = base_name(symbol);
+    LIBC_FUNCTIONS
+        .iter()
+        .find(|(function, _, _)| *function == name)
+        .map(|(_, zh, en)| lang.pick(zh, en))
+}
+
+/// 外部库调用的语义解释：已知的 C 库函数附带说明，其他 PLT 调用标为外部函数；
+/// 不是外部调用时返回 None
+pub fn call_note(symbol: &str, lang: Lang) -> Option<String> {
+    let name = base_name(symbol);
+    match (describe(symbol, lang), lang) {
+        (Some(text), Lang::Zh) => Some(format!("调用标准库函数 {}：{}", name, text)),
+        (Some(text), Lang::En) => Some(format!("call C library function {}: {}", name, text)),
+        (None, Lang::Zh) if is_plt(symbol) => Some(format!("调用外部函数 {}（经 PLT）", name)),
+        (None, Lang::En) if is_plt(symbol) => Some(format!("call external function {} (via PLT)", name)),
+        (None, _) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_note() {
+        assert_eq!(call_note("memcpy@plt", Lang::Zh).unwrap(), "调用标准库函数 memcpy：内存复制");
+        assert_eq!(
+            call_note("__libc_start_main", Lang::En).unwrap(),
+            "call C library function __libc_start_main: C runtime entry: initializes and calls main"
+        );
+        assert_eq!(call_note("gtk_init@plt", Lang::Zh).unwrap(), "调用外部函数 gtk_init（经 PLT）");
+        assert_eq!(call_note("sum", Lang::Zh), None);
+        assert_eq!(base_name("memcpy@GLIBC_2.17"), "memcpy");
+    }
+}
@@ -0,0 +1,49 @@
+//! 输出语言
+//!
+//! 语义解释和报告表头默认使用中文，也可以切换为英文，便于在国际课程中使用。
+
+use crate::error::{Result, InterpreterError};
+
+/// 输出语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    /// 中文（默认）
+    #[default]
+    Zh,
+    /// 英文
+    En,
+}
+
+impl Lang {
+    /// 解析语言名称（zh, en）
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.to_lowercase().as_str() {
+            "zh" | "zh-cn" => Ok(Lang::Zh),
+            "en" | "en-us" => Ok(Lang::En),
+            _ => Err(InterpreterError::ParseError(format!(
+                "不支持的语言: {} (可选: zh, en)",
+                text
+            ))),
+        }
+    }
+
+    /// 按语言选择文本
+    pub fn pick<'a>(self, zh: &'a str, en: &'a str) -> &'a str {
+        match self {
+            Lang::Zh => zh,
+            Lang::En => en,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lang() {
+        assert_eq!(Lang::parse("EN").unwrap(), Lang::En);
+        assert_eq!(Lang::parse("zh").unwrap().pick("指令", "Instruction"), "指令");
+        assert!(Lang::parse("fr").is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand, CommandFactory};
+use clap::{Args, Parser, Subcommand, CommandFactory, FromArgMatches};
 use clap_complete::{generate, Shell};
 use colored::*;
 use std::path::PathBuf;
@@ -14,12 +14,12 @@ Assembly Language Analyzer (ALAZ) - AArch64 汇编语言分析工具
 功能特性:
   • 解析 objdump 输出文件
   • 自动生成汇编指令的语义解释
-  • 支持多优化级别对比分析 (O0/O1/O2)
+  • 支持多优化级别对比分析 (O0/O1/O2/O3/Os...)
   • 交互式函数选择和分析
   • 生成美观的 Markdown 分析报告
 
 使用示例:
-  # 交互式分析三个优化级别的共同函数
+  # 交互式分析各优化级别的共同函数
   alaz interactive spark_matrix_naive
   
   # 单文件模式分析 (只分析一个 dump 文件)
@@ -38,50 +38,227 @@ struct Cli {
     /// 启用详细日志输出
     #[arg(long, global = true)]
     verbose: bool,
+
+    /// 配置文件
+    #[arg(long, global = true, value_name = "FILE", help = "配置文件 (默认: ~/.config/alaz/config.toml)，其中的设置作为各命令参数的默认值")]
+    config: Option<PathBuf>,
+}
+
+/// 报告生成选项（analyze 与 interactive 共用）
+#[derive(Args)]
+struct ReportArgs {
+    /// 备注文件 (YAML)
+    #[arg(long, value_name = "FILE", help = "合并到报告中的备注文件 (YAML: functions/addresses)")]
+    notes: Option<PathBuf>,
+
+    /// 编译器优化备注
+    #[arg(long, value_name = "[LEVEL=]FILE", help = "合并编译器优化备注 (clang -fsave-optimization-record 的 YAML 或 GCC -fopt-info 的输出)，可重复；LEVEL= 前缀表示只用于该优化级别")]
+    remarks: Vec<String>,
+
+    /// 同时输出 JSON
+    #[arg(long, help = "同时输出带稳定指令标识符的 JSON 文件")]
+    json: bool,
+
+    /// 报告文件名
+    #[arg(long, value_name = "TEMPLATE", help = "报告文件名，可用占位符 {function}、{level} (多个级别以 - 连接，单文件为 dump 文件名) 和 {date} (如: {function}_{level}_{date}.md)；相对路径放在 -o 目录下 (默认: {function}_comparison.md，单文件为 {function}_analysis.md)")]
+    output_file: Option<String>,
+
+    /// 覆盖已有文件
+    #[arg(long, help = "报告、索引等输出文件已存在时直接覆盖 (默认报错并保留原文件)")]
+    force: bool,
+
+    /// 不覆盖已有文件
+    #[arg(long, conflicts_with = "force", help = "输出文件已存在时报错，不覆盖 (默认行为，与 --force 相对)")]
+    no_clobber: bool,
+
+    /// 严格解析
+    #[arg(long, help = "有指令未能被文本解析器完整解析时报错 (列出每条指令的行、列和原因)，而不是按机器码或文本照常生成报告")]
+    strict: bool,
+
+    /// 终端输出
+    #[arg(long, conflicts_with = "json", help = "直接在终端中输出彩色对齐的指令列表 (地址、助记符、操作数、语义解释)，不写入文件")]
+    stdout: bool,
+
+    /// 检测共享代码
+    #[arg(long, help = "检测编译器提取的共享代码 (OUTLINED_FUNCTION_*) 并在调用处注释")]
+    outlining: bool,
+
+    /// 展开编译器辅助函数
+    #[arg(long, help = "在同一 dump 中找到函数引用的 .part.N、.cold、.isra.N 等辅助函数，把它们的指令附加到报告中")]
+    expand_helpers: bool,
+
+    /// 按 C 语句分组
+    #[arg(long, value_name = "MODE", help = "单文件分析时按 C 语句分组: collapsed (每条语句一行), expanded (概要行后跟详细指令)")]
+    group: Option<String>,
+
+    /// 函数导读
+    #[arg(long, help = "在指令表格前生成函数导读 (栈帧、参数、循环、调用和返回的整体叙述)")]
+    walkthrough: bool,
+
+    /// 返回值来源
+    #[arg(long, help = "列出函数的所有出口 (ret/尾调用)，追踪每个出口处 x0 的来源 (常量、参数、调用结果或计算结果)")]
+    returns: bool,
+
+    /// 推断函数签名
+    #[arg(long, help = "综合参数寄存器、栈上参数和返回值来源推断函数的 C 签名 (附带依据和置信度)")]
+    signature: bool,
+
+    /// 安全加固检查
+    #[arg(long, help = "检查返回地址签名 (PAC)、BTI 和栈保护，并列出间接跳转和间接调用")]
+    hardening: bool,
+
+    /// 分析配置档
+    #[arg(long, value_name = "PROFILE", help = "按用途打开一组分析选项: teaching (导读、返回值来源、推断签名), performance (寄存器活跃区间、共享代码检测), security (安全检查、推断签名)")]
+    profile: Option<String>,
+
+    /// 报告语言
+    #[arg(long, value_name = "LANG", default_value = "zh", help = "语义解释和报告表头的语言 (zh, en)")]
+    lang: String,
+
+    /// 指令表格的列
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',', help = "指令表格显示的列及顺序，逗号分隔: address, machine-code, c, asm, semantics, comment, cost (默认: c,asm,semantics)")]
+    columns: Vec<String>,
+
+    /// 地址和机器码列
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true", help = "默认表格是否包含地址和机器码列，便于和调试器对照 (默认: 单文件分析包含，多级别对比不包含；--address-columns=false 关闭)")]
+    address_columns: Option<bool>,
+
+    /// 自定义级别标题
+    #[arg(long = "level-label", value_name = "LEVEL=TITLE", help = "自定义级别在标题、统计信息和索引中的名称 (如: --level-label \"O2=O2+LTO\" --level-label \"Os=Os (体积优化)\")")]
+    level_labels: Vec<String>,
+
+    /// 可复现输出
+    #[arg(long, help = "生成可复现的报告：不写入路径，顺序固定，便于纳入 git 后比较不同工具链的输出")]
+    reproducible: bool,
+
+    /// 栈对齐检查
+    #[arg(long, help = "检查每个调用点的 SP 是否按 16 字节对齐 (沿控制流图推算 SP，适合检查手写汇编)")]
+    stack_align: bool,
+
+    /// 不显示跳转标签
+    #[arg(long, help = "不把函数内的跳转目标显示为 .L1: 标签 (保留 objdump 的原始跳转地址)")]
+    no_branch_labels: bool,
+
+    /// 跳转箭头
+    #[arg(long, help = "画出函数内跳转与目标之间的箭头 (类似 objdump --visualize-jumps)：终端输出在地址左侧加一栏，Markdown 报告附加带箭头的指令清单")]
+    arrows: bool,
+
+    /// 不标注值追踪结果
+    #[arg(long, help = "不在语义解释后标注值追踪推导出的常量和线性值 (如 movz/movk 拼出的常量、X1 × 8)")]
+    no_values: bool,
+
+    /// 无用存储
+    #[arg(long, help = "检测写入后从未被读取就被覆盖或返回的寄存器和栈槽，在报告中标注并统计各级别的数量")]
+    dead_stores: bool,
+
+    /// 位宽检查
+    #[arg(long, help = "检查 32 位结果未经显式扩展就作为 64 位值使用、sxtw/uxtw 与比较的有无符号不一致等整数提升问题")]
+    width_checks: bool,
+
+    /// 被调用者保存寄存器的活跃区间
+    #[arg(long, help = "附加被调用者保存寄存器 (X19-X28) 的活跃区间，标出何时保存调用者的值、何时保存函数内部的值")]
+    liveness: bool,
+
+    /// 按源码行对齐
+    #[arg(long, help = "对比报告按 C 源码行对齐：每条语句一行，列出各优化级别生成的指令条数和指令，代替各级别独立的表格")]
+    by_source: bool,
+
+    /// 跳转统计
+    #[arg(long, help = "在统计信息中附加条件跳转的方向（向后/向前）、顺序落入的跳转目标数和循环头是否对齐到 16 字节")]
+    branch_stats: bool,
+
+    /// 伪代码
+    #[arg(long, help = "附加近似的类 C 伪代码：块内表达式合并、条件跳转写成 if/goto、检测到的循环写成 while")]
+    pseudocode: bool,
+
+    /// 延迟/吞吐标注的核心模型
+    #[arg(long, value_name = "CORE", help = "按核心模型标注每条指令的延迟/吞吐量，并在统计信息中估计各级别的周期数: a53, a72, n1")]
+    core: Option<String>,
+
+    /// 生成执行轨迹
+    #[arg(long, help = "在模拟器中运行函数，附加逐条指令的寄存器/标志位变化表")]
+    trace: bool,
+
+    /// 执行轨迹的初始寄存器值
+    #[arg(long = "trace-reg", value_name = "REG=VALUE", requires = "trace", help = "执行轨迹的初始寄存器值 (如: --trace-reg x0=4)")]
+    trace_regs: Vec<String>,
+
+    /// 执行轨迹的预置缓冲区
+    #[arg(long = "trace-mem", value_name = "ADDR[:TYPE]=V1,V2,...", requires = "trace", help = "执行轨迹的预置缓冲区 (如: --trace-mem 0x10000:i32=1,2,3,4)")]
+    trace_mems: Vec<String>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// 分析指定函数并生成对比表格
     /// 
-    /// 分析三个优化级别 (O0/O1/O2) 的汇编代码差异。
-    /// 需要存在对应的 <PREFIX>_O0.dump, <PREFIX>_O1.dump, <PREFIX>_O2.dump 文件。
+    /// 分析多个优化级别的汇编代码差异。
+    /// 默认自动发现所有 <PREFIX>_<级别>.dump 文件 (如 _O0, _O2, _O3, _Os)，
+    /// 也可以用 --levels 指定。
     /// 
     /// 示例:
     ///   alaz analyze Matrix_add spark_matrix_naive
     ///   alaz analyze Matrix_mul my_code -o ./reports
+    ///   alaz analyze Matrix_mul my_code --levels O2,O3,Os
+    ///   alaz analyze 0x400640 my_code               # 按起始地址 (在第一个级别中查找)
+    ///   alaz analyze helper my_code                 # 也匹配 helper.constprop.0 等
+    ///   alaz analyze 'Matrix_*' my_code             # 分析每个匹配通配符的共同函数
+    ///   alaz analyze '/^vec[23]_' my_code           # 正则 (不区分大小写)
+    ///   alaz analyze @3 my_code                     # 交互式菜单中的第 3 个函数
+    ///   alaz analyze --all my_code -o ./reports     # 分析所有共同函数
+    ///   alaz analyze --all my_code --combined       # 合并为一份带目录的报告
     #[command(verbatim_doc_comment)]
     Analyze {
         /// 要分析的函数名称
-        #[arg(value_name = "FUNCTION", help = "函数名称 (如: Matrix_add, main)")]
-        function: String,
+        #[arg(value_name = "FUNCTION", required_unless_present = "all", conflicts_with = "all", help = "函数名称、起始地址或名称片段 (如: Matrix_add, 0x400640, Matrix)，或选择多个函数的通配符 (Matrix_*)、正则 (/REGEX) 和编号 (@3)")]
+        function: Option<String>,
 
         /// dump 文件前缀
-        #[arg(value_name = "PREFIX", help = "文件前缀 (如: spark_matrix_naive 会查找 *_O0.dump, *_O1.dump, *_O2.dump)")]
-        prefix: String,
+        #[arg(value_name = "PREFIX", required_unless_present = "all", conflicts_with = "all", help = "文件前缀 (如: spark_matrix_naive 会查找 spark_matrix_naive_<级别>.dump)")]
+        prefix: Option<String>,
+
+        /// 分析所有共同函数
+        #[arg(long, value_name = "PREFIX", help = "分析 <PREFIX>_<级别>.dump 中所有优化级别共有的每个函数，每个函数一份报告，并生成索引文件")]
+        all: Option<String>,
+
+        /// 合并为一份报告
+        #[arg(long, requires = "all", help = "--all 时把所有函数写入一份带目录和函数间调用链接的 <PREFIX>_report.md，代替每个函数一份报告和索引")]
+        combined: bool,
+
+        /// 批量分析的线程数
+        #[arg(short = 'j', long, value_name = "N", default_value_t = 0, requires = "all", help = "--all 时并行分析的线程数 (默认 0: 按 CPU 核数)")]
+        jobs: usize,
 
         /// 输出目录 (默认为当前目录)
         #[arg(short, long, value_name = "DIR", help = "保存分析报告的目录")]
         output: Option<PathBuf>,
+
+        /// 对比的优化级别
+        #[arg(short = 'l', long, value_name = "LEVELS", value_delimiter = ',', help = "对比的优化级别，逗号分隔 (如: O0,O2,O3,Os；默认自动发现 <PREFIX>_<级别>.dump)")]
+        levels: Vec<String>,
+
+        #[command(flatten)]
+        report: ReportArgs,
     },
     
     /// 交互式模式 - 浏览和选择函数进行分析
     /// 
     /// 提供交互式菜单，显示所有可用函数供选择分析。
     /// 支持两种模式:
-    ///   • 多文件模式 (默认): 分析 O0/O1/O2 三个优化级别的共同函数
+    ///   • 多文件模式 (默认): 分析各优化级别 (默认自动发现) 的共同函数
     ///   • 单文件模式 (-s): 只分析指定的一个 dump 文件
     /// 
     /// 示例:
     ///   alaz interactive spark_matrix_naive          # 多文件模式
     ///   alaz interactive -s my_code_O2.dump          # 单文件模式
+    ///   objdump -d -S a.o | alaz interactive -s -    # 从标准输入读取
     ///   alaz interactive -m spark_matrix_naive -o ./reports
     #[command(verbatim_doc_comment)]
     Interactive {
         /// dump 文件前缀或完整文件名
         #[arg(
             value_name = "PREFIX_OR_FILE",
-            help = "文件前缀 (多文件模式) 或完整文件名 (单文件模式)"
+            help = "文件前缀 (多文件模式) 或完整文件名 (单文件模式，- 表示标准输入)"
         )]
         prefix: String,
 
@@ -93,324 +270,2509 @@ enum Commands {
         )]
         single: bool,
 
-        /// 多文件模式 - 分析三个优化级别的共同函数 (默认)
+        /// 多文件模式 - 分析各优化级别的共同函数 (默认)
         #[arg(
             short = 'm',
             long,
             conflicts_with = "single",
-            help = "多文件模式: 分析 O0/O1/O2 三个文件的共同函数 (默认行为)"
+            help = "多文件模式: 分析各优化级别 dump 文件的共同函数 (默认行为)"
         )]
         multi: bool,
 
         /// 输出目录 (默认为当前目录)
         #[arg(short, long, value_name = "DIR", help = "保存分析报告的目录")]
         output: Option<PathBuf>,
+
+        /// 对比的优化级别
+        #[arg(short = 'l', long, value_name = "LEVELS", value_delimiter = ',', help = "对比的优化级别，逗号分隔 (如: O0,O2,O3,Os；默认自动发现 <PREFIX>_<级别>.dump)")]
+        levels: Vec<String>,
+
+        #[command(flatten)]
+        report: ReportArgs,
     },
     
-    /// 生成 shell 补全脚本
+    /// 从 C 源码编译并分析
     /// 
-    /// 为指定的 shell 生成自动补全脚本。
-    /// 
-    /// 支持的 shell: bash, fish, zsh, powershell, elvish
+    /// 以 O0/O1/O2 (可用 --levels 修改) 分别编译源文件，对每个目标文件运行 objdump 生成
+    /// <NAME>_O0.dump 等文件，然后进入对比分析流程。
+    /// 指定函数名时直接生成对比报告，否则进入函数选择菜单。
     /// 
-    /// 安装示例:
-    ///   # Bash
-    ///   alaz completions bash > ~/.local/share/bash-completion/completions/alaz
-    ///   
-    ///   # Fish
-    ///   alaz completions fish > ~/.config/fish/completions/alaz.fish
-    ///   
-    ///   # Zsh
-    ///   alaz completions zsh > ~/.zsh/completion/_alaz
+    /// 示例:
+    ///   alaz build matrix.c
+    ///   alaz build matrix.c Matrix_add --cc aarch64-linux-gnu-gcc --objdump-path aarch64-linux-gnu-objdump
+    ///   alaz build matrix.c --cflag=-march=armv8.2-a --build-dir ./build
     #[command(verbatim_doc_comment)]
-    Completions {
-        /// Shell 类型
-        #[arg(
-            value_name = "SHELL",
-            help = "Shell 类型 (bash, fish, zsh, powershell, elvish)"
-        )]
-        shell: String,
-    },
-}
+    Build {
+        /// C 源文件
+        #[arg(value_name = "SOURCE", help = "C 源文件 (如: matrix.c)")]
+        source: PathBuf,
 
-fn main() {
-    let cli = Cli::parse();
+        /// 要分析的函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (省略时进入函数选择菜单)")]
+        function: Option<String>,
 
-    // 配置日志
-    let log_level = if cli.verbose { "info" } else { "warn" };
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level))
-        .init();
+        /// 编译器
+        #[arg(long, value_name = "CC", default_value = alaz::build::DEFAULT_CC, help = "编译器 (如: aarch64-linux-gnu-gcc, clang)")]
+        cc: String,
 
-    // 执行命令
-    let result = match cli.command {
-        Commands::Analyze { function, prefix, output } => {
-            analyze_dumps(&function, &prefix, output.as_ref())
-        }
-        Commands::Interactive { prefix, single, multi: _, output } => {
-            interactive_mode(&prefix, single, output.as_ref())
-        }
-        Commands::Completions { shell } => {
-            generate_completions(&shell)
-        }
-    };
+        /// 额外的编译选项
+        #[arg(long = "cflag", value_name = "FLAG", allow_hyphen_values = true, help = "额外的编译选项，可多次指定 (如: --cflag=-march=armv8.2-a)")]
+        cflags: Vec<String>,
 
-    if let Err(e) = result {
-        eprintln!("{}", format!("❌ 错误: {}", e).red().bold());
-        std::process::exit(1);
-    }
-}
+        /// objdump 程序
+        #[arg(long, value_name = "PATH", default_value = alaz::objdump::DEFAULT_OBJDUMP, help = "objdump 程序名或路径 (如: aarch64-linux-gnu-objdump, llvm-objdump)")]
+        objdump_path: String,
 
-/// 分析 objdump 文件并生成对比表格
-fn analyze_dumps(
-    function: &str,
-    prefix: &str,
-    output: Option<&PathBuf>,
-) -> anyhow::Result<()> {
-    use alaz::table::TableGenerator;
+        /// 目标文件和 dump 文件的输出目录
+        #[arg(long, value_name = "DIR", default_value = ".", help = "保存目标文件和 dump 文件的目录")]
+        build_dir: PathBuf,
 
-    println!("{}", "=".repeat(60).cyan());
-    println!("{}", "  ALAZ - 汇编语言分析工具".cyan().bold());
-    println!("{}", "=".repeat(60).cyan());
-    println!();
+        /// 输出目录 (默认为当前目录)
+        #[arg(short, long, value_name = "DIR", help = "保存分析报告的目录")]
+        output: Option<PathBuf>,
 
-    println!("{} {}", "📋 分析函数:".yellow(), function.bold());
-    println!("{} {}", "📁 文件前缀:".yellow(), prefix);
-    if let Some(out) = output {
-        println!("{} {}", "💾 输出目录:".yellow(), out.display());
-    }
-    println!();
+        /// 对比的优化级别
+        #[arg(short = 'l', long, value_name = "LEVELS", value_delimiter = ',', help = "对比的优化级别，逗号分隔 (默认: O0,O1,O2)")]
+        levels: Vec<String>,
 
-    let generator = TableGenerator::new();
-    generator.generate_from_dumps(function, prefix, output)?;
+        #[command(flatten)]
+        report: ReportArgs,
+    },
+    
+    /// 直接分析 ELF/目标文件
+    /// 
+    /// 自动调用 objdump -d -S 反汇编目标文件并解析输出，无需手动生成 dump 文件。
+    /// 指定函数名时直接生成该函数的分析报告，否则进入函数选择菜单。
+    /// 
+    /// 示例:
+    ///   alaz elf my_code_O2.o
+    ///   alaz elf my_code_O2.o Matrix_add -o ./reports
+    ///   alaz elf my_code_O2.o --objdump-path aarch64-linux-gnu-objdump
+    #[command(verbatim_doc_comment)]
+    Elf {
+        /// 目标文件
+        #[arg(value_name = "FILE", help = "ELF 可执行文件或目标文件 (如: my_code_O2.o)")]
+        file: String,
 
-    println!();
-    println!("{}", "✅ 分析完成！".green().bold());
-    Ok(())
-}
+        /// 要分析的函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (省略时进入函数选择菜单)")]
+        function: Option<String>,
 
-/// 交互式菜单模式
-fn interactive_mode(prefix: &str, single_mode: bool, output: Option<&PathBuf>) -> anyhow::Result<()> {
-    use alaz::objdump::ObjdumpParser;
-    use std::io::{self, Write};
+        /// objdump 程序
+        #[arg(long, value_name = "PATH", default_value = alaz::objdump::DEFAULT_OBJDUMP, help = "objdump 程序名或路径 (如: aarch64-linux-gnu-objdump, llvm-objdump)")]
+        objdump_path: String,
 
-    println!("{}", "=".repeat(60).cyan());
-    println!("{}", "  ALAZ - 汇编语言分析工具 (交互式模式)".cyan().bold());
-    println!("{}", "=".repeat(60).cyan());
-    println!();
+        /// 输出目录 (默认为当前目录)
+        #[arg(short, long, value_name = "DIR", help = "保存分析报告的目录")]
+        output: Option<PathBuf>,
 
-    if single_mode {
-        // 单文件模式：只读取指定的文件
-        let dump_path = if prefix.ends_with(".dump") {
-            prefix.to_string()
-        } else {
-            format!("{}.dump", prefix)
-        };
-        
-        println!("{} {} (单文件模式)", "📂 正在读取:".yellow(), dump_path);
-        
-        let parser = ObjdumpParser::from_file(&dump_path)?;
-        let mut functions = parser.list_functions()?;
-        
-        if functions.is_empty() {
-            println!("{}", "❌ 未找到任何函数".red());
-            return Ok(());
-        }
-        
-        functions.sort();
-        println!();
-        println!("{} {} 个函数", "✓ 检测到".green(), functions.len());
-        println!();
-        
-        // 单文件模式下的交互循环
-        loop {
-            println!("{}", "=".repeat(60).cyan());
-            println!("{}", "可用函数列表:".yellow().bold());
-            println!("{}", "-".repeat(60));
-            
-            for (idx, func) in functions.iter().enumerate() {
-                println!("  {}. {}", format!("{:3}", idx + 1).cyan(), func);
-            }
-            
-            println!("{}", "-".repeat(60));
-            println!();
-            println!("请选择:");
-            println!("  {} 输入函数编号进行分析", "●".green());
-            println!("  {} 输入 'q' 或 'quit' 退出", "●".red());
-            println!();
+        #[command(flatten)]
+        report: ReportArgs,
+    },
+    
+    /// 单步调试模式 - 在模拟器中逐条执行函数
+    /// 
+    /// 加载 dump 文件中的函数，每按一次 Enter 执行一条指令，
+    /// 显示语义解释以及发生变化的寄存器和标志位。
+    /// 
+    /// 示例:
+    ///   alaz step sum my_code_O0.dump
+    ///   alaz step sum my_code_O2.dump -r x0=0x1000 -r w1=4
+    ///   alaz step sum my_code_O0.dump -r w1=4 --stop-when w0==6
+    ///   alaz step sum my_code_O0.dump -m 0x10000:i32=1,2,3,4 -r x0=0x10000 -r w1=4
+    ///   alaz step sum my_code_O0.dump -r w1=4 --export sum_trace.json
+    #[command(verbatim_doc_comment)]
+    Step {
+        /// 要执行的函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (如: Matrix_add, main)")]
+        function: String,
 
-            print!("{} ", "选择 >".bright_blue().bold());
-            io::stdout().flush()?;
+        /// dump 文件
+        #[arg(value_name = "DUMP", help = "objdump 输出文件 (如: my_code_O0.dump)")]
+        dump: String,
 
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            let input = input.trim();
+        /// 初始寄存器值
+        #[arg(short = 'r', long = "reg", value_name = "REG=VALUE", help = "设置初始寄存器值 (如: -r x0=0x1000 -r w1=4)")]
+        regs: Vec<String>,
 
-            // 处理退出
-            if input == "q" || input == "quit" || input.is_empty() {
-                println!();
-                println!("{}", "👋 再见！".yellow());
-                break;
-            }
+        /// 预置缓冲区
+        #[arg(short = 'm', long = "mem", value_name = "ADDR[:TYPE]=V1,V2,...", help = "在指定地址预置缓冲区 (如: -m 0x10000:i32=1,2,3,4 -m 0x20000=0*16)")]
+        mems: Vec<String>,
 
-            // 处理选择
-            match input.parse::<usize>() {
-                Ok(num) if num > 0 && num <= functions.len() => {
-                    let function = &functions[num - 1];
-                    println!();
-                    println!("{}", "=".repeat(60).cyan());
-                    
-                    use alaz::table::TableGenerator;
-                    let generator = TableGenerator::new();
-                    
-                    if let Err(e) = generator.generate_from_single_dump(function, &dump_path, output) {
-                        println!();
-                        println!("{} {}", "❌ 分析失败:".red(), e);
-                    }
-                    
-                    println!();
-                    println!("按 Enter 继续...");
-                    let mut _pause = String::new();
-                    io::stdin().read_line(&mut _pause)?;
-                    println!();
-                }
-                _ => {
-                    println!("{}", "❌ 无效的选择，请输入正确的编号".red());
-                    println!();
-                }
-            }
-        }
-        
-        return Ok(());
-    }
+        /// 停止条件
+        #[arg(long = "stop-when", value_name = "COND", help = "连续执行，直到条件首次成立时停止并输出状态 (如: --stop-when x0==0)")]
+        stop_when: Vec<String>,
 
-    // 多文件模式：读取三个优化级别的共同函数
-    // 智能处理文件路径和提取真实前缀
-    let real_prefix = if prefix.ends_with(".dump") {
-        // 如果输入的是完整文件名，需要提取前缀
-        // 例如: spark_matrix_naive_O2.dump -> spark_matrix_naive
-        prefix
-            .strip_suffix(".dump").unwrap_or(prefix)
-            .trim_end_matches("_O0")
-            .trim_end_matches("_O1")
-            .trim_end_matches("_O2")
-            .to_string()
-    } else {
-        prefix.to_string()
-    };
-    
-    // 读取所有三个优化级别的文件，找出共同的函数
-    let o0_path = format!("{}_O0.dump", &real_prefix);
-    let o1_path = format!("{}_O1.dump", &real_prefix);
-    let o2_path = format!("{}_O2.dump", &real_prefix);
-    
-    println!("{} 读取三个优化级别的文件以找出共同函数...", "⚙".yellow());
-    
-    let mut common_functions: Option<std::collections::HashSet<String>> = None;
-    let mut file_count = 0;
-    
-    for (level, path) in [("O0", &o0_path), ("O1", &o1_path), ("O2", &o2_path)] {
-        if let Ok(parser) = ObjdumpParser::from_file(path) {
-            if let Ok(funcs) = parser.list_functions() {
-                file_count += 1;
-                let func_set: std::collections::HashSet<_> = funcs.into_iter().collect();
-                common_functions = Some(match common_functions {
-                    None => func_set,
-                    Some(existing) => existing.intersection(&func_set).cloned().collect(),
-                });
-                println!("  {} {} 文件读取成功", "✓".green(), level);
-            } else {
-                println!("  {} {} 文件解析失败", "⚠".yellow(), level);
-            }
-        } else {
-            println!("  {} {} 文件未找到", "⚠".yellow(), level);
-        }
-    }
-    
-    let mut functions: Vec<String> = common_functions
-        .unwrap_or_default()
-        .into_iter()
-        .collect();
-    
-    if functions.is_empty() {
-        println!("{}", "❌ 未找到任何共同函数".red());
-        if file_count == 0 {
-            println!("{}", "提示: 请确保存在 *_O0.dump, *_O1.dump, *_O2.dump 文件".yellow());
-        }
-        return Ok(());
-    }
-    
-    functions.sort();
+        /// 导出执行轨迹
+        #[arg(long, value_name = "FILE", help = "不进入单步交互，直接执行到结束并把执行轨迹导出到文件 (如: --export trace.json)")]
+        export: Option<PathBuf>,
+
+        /// 轨迹格式
+        #[arg(long, value_name = "FORMAT", requires = "export", help = "轨迹格式: json, bin (默认按扩展名推断，.json 以外为二进制)")]
+        export_format: Option<String>,
+    },
     
-    println!();
-    println!("{} {} 个共同函数 (在所有优化级别都存在)", "✓ 检测到".green(), functions.len());
-    println!();
+    /// 差分模拟 - 检查各优化级别的函数行为是否一致
+    /// 
+    /// 用相同的初始寄存器和缓冲区在模拟器中运行每个优化级别的函数，
+    /// 与第一个级别比较结束方式、返回值、程序输出和缓冲区内容，报告第一个不一致之处。
+    /// 两个级别都没有正常结束时 (如都超出步数上限) 报告无法判断。
+    /// 
+    /// 示例:
+    ///   alaz verify sum my_code -r w0=5
+    ///   alaz verify sum my_code -l O0,O2 -m 0x10000:i32=1,2,3,4 -r x0=0x10000 -r w1=4
+    ///   alaz verify fill my_code -m 0x20000=0*16 -r x0=0x20000 --ignore-return
+    #[command(verbatim_doc_comment)]
+    Verify {
+        /// 要运行的函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (如: Matrix_add, main)")]
+        function: String,
 
-    loop {
-        // 显示函数列表
-        println!("{}", "=".repeat(60).cyan());
-        println!("{}", "可用函数列表:".yellow().bold());
-        println!("{}", "-".repeat(60));
-        
-        for (idx, func) in functions.iter().enumerate() {
-            println!("  {}. {}", format!("{:3}", idx + 1).cyan(), func);
-        }
-        
-        println!("{}", "-".repeat(60));
-        println!();
-        println!("请选择:");
-        println!("  {} 输入函数编号进行分析", "●".green());
-        println!("  {} 输入 'q' 或 'quit' 退出", "●".red());
-        println!();
+        /// dump 文件前缀
+        #[arg(value_name = "PREFIX", help = "dump 文件前缀 (如: my_code)")]
+        prefix: String,
 
-        print!("{} ", "选择 >".bright_blue().bold());
-        io::stdout().flush()?;
+        /// 优化级别
+        #[arg(short, long, value_name = "LEVELS", value_delimiter = ',', help = "要比较的优化级别，第一个作为基准 (如: -l O0,O2；默认自动发现)")]
+        levels: Vec<String>,
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        let input = input.trim();
+        /// 初始寄存器值
+        #[arg(short = 'r', long = "reg", value_name = "REG=VALUE", help = "设置初始寄存器值 (如: -r x0=0x1000 -r w1=4)")]
+        regs: Vec<String>,
 
-        // 处理退出
-        if input == "q" || input == "quit" || input.is_empty() {
-            println!();
-            println!("{}", "👋 再见！".yellow());
-            break;
-        }
+        /// 预置缓冲区
+        #[arg(short = 'm', long = "mem", value_name = "ADDR[:TYPE]=V1,V2,...", help = "在指定地址预置缓冲区 (如: -m 0x10000:i32=1,2,3,4)")]
+        mems: Vec<String>,
 
-        // 处理选择
-        match input.parse::<usize>() {
-            Ok(num) if num > 0 && num <= functions.len() => {
-                let function = &functions[num - 1];
-                println!();
-                println!("{}", "=".repeat(60).cyan());
-                
-                if let Err(e) = analyze_dumps(function, &real_prefix, output) {
-                    println!();
-                    println!("{} {}", "❌ 分析失败:".red(), e);
-                }
-                
-                println!();
-                println!("按 Enter 继续...");
-                let mut _pause = String::new();
-                io::stdin().read_line(&mut _pause)?;
-                println!();
-            }
-            _ => {
-                println!("{}", "❌ 无效的选择，请输入正确的编号".red());
-                println!();
-            }
-        }
-    }
+        /// 不比较返回值
+        #[arg(long, help = "不比较返回值 X0 (用于 void 函数)")]
+        ignore_return: bool,
 
-    Ok(())
-}
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "不一致之处的描述语言 (zh, en)")]
+        lang: String,
+    },
+    
+    /// 生成报告片段
+    /// 
+    /// 只输出函数中选定范围的指令及其语义解释 (不含报告标题)，
+    /// 便于粘贴到课堂讲义或博客中。
+    /// 
+    /// 示例:
+    ///   alaz snippet sum my_code --lines 3-9 --level O2
+    ///   alaz snippet sum my_code --lines 1-4 --format txt
+    ///   alaz snippet sum my_code_O0.dump
+    #[command(verbatim_doc_comment)]
+    Snippet {
+        /// 函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (如: Matrix_add, main)")]
+        function: String,
 
-/// 生成 shell 补全脚本
-fn generate_completions(shell_name: &str) -> anyhow::Result<()> {
-    let shell = match shell_name.to_lowercase().as_str() {
-        "bash" => Shell::Bash,
-        "fish" => Shell::Fish,
-        "zsh" => Shell::Zsh,
-        "powershell" => Shell::PowerShell,
+        /// dump 文件前缀或 dump 文件
+        #[arg(value_name = "PREFIX", help = "dump 文件前缀 (如: my_code) 或 dump 文件 (如: my_code_O2.dump)")]
+        prefix: String,
+
+        /// 指令范围
+        #[arg(long, value_name = "START-END", help = "指令范围，从 1 开始且包含两端 (如: --lines 3-9；默认整个函数)")]
+        lines: Option<String>,
+
+        /// 优化级别
+        #[arg(long, value_name = "LEVEL", default_value = "O0", help = "PREFIX 为前缀时使用的优化级别 (如: O2)")]
+        level: String,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "md", help = "输出格式 (md: Markdown 表格, txt: 带注释的汇编)")]
+        format: String,
+    },
+    
+    /// 统计指令构成
+    /// 
+    /// 按类别 (算术、加载/存储、跳转、SIMD 等) 统计函数在各优化级别的指令数，
+    /// 输出对比直方图表格。
+    /// 
+    /// 示例:
+    ///   alaz stats Matrix_add my_code
+    ///   alaz stats Matrix_add my_code -l O0,O3 -f json -o stats.json
+    #[command(verbatim_doc_comment)]
+    Stats {
+        /// 要统计的函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (如: Matrix_add, main)")]
+        function: String,
+
+        /// dump 文件前缀
+        #[arg(value_name = "PREFIX", help = "dump 文件前缀 (如: my_code)")]
+        prefix: String,
+
+        /// 优化级别
+        #[arg(short, long, value_name = "LEVELS", value_delimiter = ',', help = "要统计的优化级别 (如: -l O0,O2；默认自动发现)")]
+        levels: Vec<String>,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "md", help = "输出格式 (md, json)")]
+        format: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存统计结果的文件")]
+        output: Option<PathBuf>,
+
+        /// 自定义级别标题
+        #[arg(long = "level-label", value_name = "LEVEL=TITLE", help = "自定义级别在统计表中的列名 (如: --level-label \"O2=O2+LTO\")")]
+        level_labels: Vec<String>,
+    },
+
+    /// 静态周期估计
+    /// 
+    /// 按所选核心模型的延迟/吞吐量逐个基本块累加指令代价，循环中的块按
+    /// 假定的迭代次数加权，对比函数在各优化级别的粗略周期数。
+    /// 
+    /// 示例:
+    ///   alaz cost sum my_code --core a72
+    ///   alaz cost sum my_code --core n1 --trip-count 100 --blocks
+    ///   alaz cost sum my_code -l O0,O1,O2 -f json -o sum_cost.json
+    #[command(verbatim_doc_comment)]
+    Cost {
+        /// 要估计的函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (如: Matrix_add, main)")]
+        function: String,
+
+        /// dump 文件前缀
+        #[arg(value_name = "PREFIX", help = "dump 文件前缀 (如: my_code)")]
+        prefix: String,
+
+        /// 核心模型
+        #[arg(long, value_name = "CORE", default_value = "a72", help = "延迟/吞吐量所依据的核心模型: a53, a72, n1")]
+        core: String,
+
+        /// 循环迭代次数
+        #[arg(long, value_name = "N", default_value_t = 10, help = "每个循环假定的迭代次数，嵌套循环逐层相乘")]
+        trip_count: u32,
+
+        /// 优化级别
+        #[arg(short, long, value_name = "LEVELS", value_delimiter = ',', help = "要对比的优化级别 (如: -l O0,O1,O2；默认自动发现)")]
+        levels: Vec<String>,
+
+        /// 列出基本块
+        #[arg(long, help = "Markdown 输出中附加每个级别的基本块明细")]
+        blocks: bool,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "md", help = "输出格式 (md, json)")]
+        format: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存估计结果的文件")]
+        output: Option<PathBuf>,
+
+        /// 自定义级别标题
+        #[arg(long = "level-label", value_name = "LEVEL=TITLE", help = "自定义级别在对比表中的名称 (如: --level-label \"O2=O2+LTO\")")]
+        level_labels: Vec<String>,
+    },
+
+    /// 寄存器交叉引用
+    /// 
+    /// 列出函数中读取或写入指定寄存器的每条指令 (x19 与 w19 视为同一个)，
+    /// 给出地址、寄存器在指令中的作用和语义解释，包括调用、返回等隐式读写。
+    /// 
+    /// 示例:
+    ///   alaz xref-reg x19 Matrix_mul my_code_O2.dump
+    ///   alaz xref-reg w0 sum my_code_O0.dump -f json
+    #[command(verbatim_doc_comment)]
+    XrefReg {
+        /// 寄存器
+        #[arg(value_name = "REG", help = "通用寄存器 (如: x19, w0, sp)")]
+        register: String,
+
+        /// 函数名称
+        #[arg(value_name = "FUNCTION", help = "函数名称 (如: Matrix_add, main)")]
+        function: String,
+
+        /// dump 文件
+        #[arg(value_name = "DUMP", help = "objdump 输出文件 (如: my_code_O2.dump)")]
+        dump: String,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "text", help = "输出格式 (text, json)")]
+        format: String,
+
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "语义解释的语言 (zh, en)")]
+        lang: String,
+    },
+
+    /// 解释单条指令
+    /// 
+    /// 不需要 dump 文件，直接解析一条汇编指令，列出各操作数的作用、
+    /// 读写的寄存器和标志位、语义解释以及指令数据库中的说明。
+    /// 
+    /// 示例:
+    ///   alaz explain "ldp x29, x30, [sp], #16"
+    ///   alaz explain "cmp w1, w0" --lang en
+    ///   alaz explain "add x0, x1, x2" -f json
+    #[command(verbatim_doc_comment)]
+    Explain {
+        /// 汇编指令
+        #[arg(value_name = "INSTRUCTION", required = true, num_args = 1.., help = "汇编指令 (如: \"ldp x29, x30, [sp], #16\")")]
+        instruction: Vec<String>,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "text", help = "输出格式 (text, json)")]
+        format: String,
+
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "语义解释的语言 (zh, en)")]
+        lang: String,
+    },
+
+    /// 交互式指令练习 (REPL)
+    /// 
+    /// 逐条输入汇编指令，立即显示语义解释，并在持久的模拟器状态上执行，
+    /// 输出发生变化的寄存器和当前 CPU 状态。以 : 开头的输入为命令
+    /// (:regs 查看状态, :reset 重置状态, :exec 切换是否执行, :help, :q)。
+    /// 
+    /// 示例:
+    ///   alaz repl
+    ///   alaz repl -r x1=5 -r x2=7
+    ///   alaz repl -m 0x10000:i32=1,2,3,4 -r x0=0x10000
+    ///   alaz repl --no-exec --lang en
+    #[command(verbatim_doc_comment)]
+    Repl {
+        /// 初始寄存器值
+        #[arg(short = 'r', long = "reg", value_name = "REG=VALUE", help = "设置初始寄存器值 (如: -r x0=0x1000 -r w1=4)")]
+        regs: Vec<String>,
+
+        /// 预置缓冲区
+        #[arg(short = 'm', long = "mem", value_name = "ADDR[:TYPE]=V1,V2,...", help = "在指定地址预置缓冲区 (如: -m 0x10000:i32=1,2,3,4)")]
+        mems: Vec<String>,
+
+        /// 只解释不执行
+        #[arg(long, help = "只解释指令，不在模拟器中执行")]
+        no_exec: bool,
+
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "语义解释的语言 (zh, en)")]
+        lang: String,
+    },
+
+    /// 全屏终端界面
+    /// 
+    /// 左侧列出函数，右侧显示带语义解释的反汇编，顶部标签页切换优化级别。
+    /// 按键: ↑/↓ 选择与滚动, Tab 切换窗格, ←/→ 或 1-9 切换级别, / 搜索, n 下一个, q 退出。
+    /// 
+    /// 示例:
+    ///   alaz tui my_code
+    ///   alaz tui my_code -l O0,O3 --lang en
+    #[command(verbatim_doc_comment)]
+    Tui {
+        /// dump 文件前缀
+        #[arg(value_name = "PREFIX", help = "dump 文件前缀 (如: my_code)")]
+        prefix: String,
+
+        /// 优化级别
+        #[arg(short, long, value_name = "LEVELS", value_delimiter = ',', help = "要浏览的优化级别 (如: -l O0,O2；默认自动发现)")]
+        levels: Vec<String>,
+
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "语义解释的语言 (zh, en)")]
+        lang: String,
+    },
+
+    /// 汇总整个 dump 文件
+    /// 
+    /// 遍历 dump 中的所有函数，列出每个函数的指令数、机器码字节数、
+    /// 跳转和调用次数，以及是否使用了 SIMD 或原子操作指令。
+    /// 
+    /// 示例:
+    ///   alaz summary my_code_O2.dump
+    ///   alaz summary my_code_O2.dump -f json -o summary_O2.json
+    #[command(verbatim_doc_comment)]
+    Summary {
+        /// dump 文件
+        #[arg(value_name = "DUMP", help = "objdump 输出文件 (如: my_code_O2.dump)")]
+        dump: String,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "md", help = "输出格式 (md, json)")]
+        format: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存汇总结果的文件")]
+        output: Option<PathBuf>,
+    },
+    
+    /// 列出函数清单
+    /// 
+    /// 列出 dump 文件中所有函数的起始地址、机器码字节数和指令条数，
+    /// 不必进入交互式模式就能查看函数清单。参数为前缀时列出各优化级别的 dump。
+    /// 
+    /// 示例:
+    ///   alaz list my_code_O2.dump
+    ///   alaz list my_code --sort size --filter '^Matrix_'
+    ///   alaz list my_code_O2.dump --json
+    #[command(verbatim_doc_comment)]
+    List {
+        /// dump 文件或前缀
+        #[arg(value_name = "PREFIX_OR_FILE", help = "dump 文件 (如: my_code_O2.dump，- 表示标准输入) 或前缀 (如: my_code)")]
+        source: String,
+
+        /// 排序方式
+        #[arg(short, long, value_name = "ORDER", default_value = "address", help = "排序方式: address (dump 中的顺序), size (字节数从大到小), name")]
+        sort: String,
+
+        /// 过滤函数
+        #[arg(long, value_name = "REGEX", help = "只列出名称匹配正则表达式的函数 (如: --filter '^Matrix_')")]
+        filter: Option<String>,
+
+        /// 输出 JSON
+        #[arg(long, help = "输出 JSON 而不是表格")]
+        json: bool,
+    },
+    
+    /// 生成调用图
+    /// 
+    /// 扫描 dump 文件中的所有函数，记录 BL/B/BLR 调用目标，
+    /// 输出 Graphviz DOT 或 JSON 格式的调用图。
+    /// 
+    /// 示例:
+    ///   alaz callgraph my_code_O2.dump | dot -Tsvg > callgraph_O2.svg
+    ///   alaz callgraph my_code_O0.dump -f json -o callgraph_O0.json
+    #[command(verbatim_doc_comment)]
+    Callgraph {
+        /// dump 文件
+        #[arg(value_name = "DUMP", help = "objdump 输出文件 (如: my_code_O2.dump)")]
+        dump: String,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "dot", help = "输出格式 (dot, json)")]
+        format: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存调用图的文件")]
+        output: Option<PathBuf>,
+    },
+
+    /// 指令数据库工具
+    #[command(subcommand)]
+    Db(DbCommand),
+    
+    /// 内联报告
+    /// 
+    /// 对比各优化级别 dump 中的函数列表，以第一个级别为基准，列出在较高级别中
+    /// 消失的函数 (可能已被内联，附上原来的调用者) 和新出现的克隆/拆分函数
+    /// (.constprop、.isra、.part、.cold)。
+    /// 
+    /// 示例:
+    ///   alaz inline-report my_code
+    ///   alaz inline-report my_code -l O0,O2 -f json -o inlining.json
+    #[command(verbatim_doc_comment)]
+    InlineReport {
+        /// dump 文件前缀
+        #[arg(value_name = "PREFIX", help = "dump 文件前缀 (如: my_code)")]
+        prefix: String,
+
+        /// 优化级别
+        #[arg(short, long, value_name = "LEVELS", value_delimiter = ',', help = "对比的优化级别，第一个为基准 (如: -l O0,O2；默认自动发现)")]
+        levels: Vec<String>,
+
+        /// 输出格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "md", help = "输出格式 (md, json)")]
+        format: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存报告的文件")]
+        output: Option<PathBuf>,
+
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "报告语言 (zh, en)")]
+        lang: String,
+    },
+    
+    /// 生成逆向工具的注释脚本
+    /// 
+    /// 把语义解释、基本块摘要和函数导读生成为 Ghidra 或 Binary Ninja 脚本，
+    /// 在工具中运行后写为对应地址上的注释（按函数名 + 偏移定位）。
+    /// 不指定函数时导出 dump 文件中的所有函数。
+    /// 
+    /// 示例:
+    ///   alaz annotate my_code_O2.dump -f ghidra -o alaz_O2.py
+    ///   alaz annotate my_code_O2.dump Matrix_add -f binja -o alaz_O2_binja.py
+    #[command(verbatim_doc_comment)]
+    Annotate {
+        /// dump 文件
+        #[arg(value_name = "DUMP", help = "objdump 输出文件 (如: my_code_O2.dump)")]
+        dump: String,
+
+        /// 函数名
+        #[arg(value_name = "FUNCTION", help = "要导出的函数 (默认导出所有函数)")]
+        functions: Vec<String>,
+
+        /// 脚本格式
+        #[arg(short, long, value_name = "FORMAT", default_value = "ghidra", help = "脚本格式 (ghidra, binja)")]
+        format: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存脚本的文件")]
+        output: Option<PathBuf>,
+
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "注释语言 (zh, en)")]
+        lang: String,
+    },
+    
+    /// 把函数提取为可重新汇编的 .s 文件
+    /// 
+    /// 输出 GNU 汇编器可以直接汇编的源文件：函数内的跳转目标重新生成为标签，
+    /// 调用改为引用符号名，去掉地址、机器码和 objdump 的注释，便于修改后重新汇编。
+    /// 无法还原为符号的绝对地址保留原值并加注释提醒。
+    /// 
+    /// 示例:
+    ///   alaz extract Matrix_add my_code_O2.dump -o Matrix_add.s
+    ///   alaz extract Matrix_add my_code_O2.dump --comments
+    ///   alaz extract Matrix_add my_code_O2.dump --mnemonic-case upper --indent 4 --align
+    #[command(verbatim_doc_comment)]
+    Extract {
+        /// 函数名
+        #[arg(value_name = "FUNCTION", help = "要提取的函数名称")]
+        function: String,
+
+        /// dump 文件
+        #[arg(value_name = "DUMP", help = "objdump 输出文件 (如: my_code_O2.dump)")]
+        dump: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存汇编源文件 (如: func.s)")]
+        output: Option<PathBuf>,
+
+        /// 附上语义解释
+        #[arg(long, help = "在每条指令后以 // 注释附上语义解释")]
+        comments: bool,
+
+        /// 助记符大小写
+        #[arg(long, value_name = "CASE", default_value = "lower", help = "助记符大小写 (lower, upper)")]
+        mnemonic_case: String,
+
+        /// 缩进方式
+        #[arg(long, value_name = "INDENT", default_value = "tab", help = "缩进方式 (tab 或空格数，如 4)")]
+        indent: String,
+
+        /// 列对齐
+        #[arg(long, help = "用空格把操作数和行尾注释对齐成列")]
+        align: bool,
+
+        /// 输出语言
+        #[arg(long, value_name = "LANG", default_value = "zh", help = "注释语言 (zh, en)")]
+        lang: String,
+    },
+    
+    /// 生成 shell 补全脚本
+    /// 
+    /// 为指定的 shell 生成自动补全脚本。
+    /// 
+    /// 支持的 shell: bash, fish, zsh, powershell, elvish
+    /// 
+    /// bash、zsh 和 fish 的脚本还会在函数名参数处补全当前目录中
+    /// dump 文件里的函数名 (如 alaz analyze <TAB>)。
+    /// 
+    /// 安装示例:
+    ///   # Bash
+    ///   alaz completions bash > ~/.local/share/bash-completion/completions/alaz
+    ///   
+    ///   # Fish
+    ///   alaz completions fish > ~/.config/fish/completions/alaz.fish
+    ///   
+    ///   # Zsh
+    ///   alaz completions zsh > ~/.zsh/completion/_alaz
+    #[command(verbatim_doc_comment)]
+    Completions {
+        /// Shell 类型
+        #[arg(
+            value_name = "SHELL",
+            help = "Shell 类型 (bash, fish, zsh, powershell, elvish)"
+        )]
+        shell: String,
+    },
+
+    /// 列出函数名 (供 shell 补全使用)
+    #[command(hide = true)]
+    ListFunctions {
+        /// dump 文件或前缀
+        #[arg(value_name = "PREFIX_OR_FILE", help = "dump 文件或前缀 (省略时读取当前目录中的所有 .dump 文件)")]
+        source: Option<String>,
+
+        /// 只输出函数名
+        #[arg(long, help = "每行一个函数名，忽略无法读取的文件，不输出错误信息")]
+        plain: bool,
+    },
+}
+
+/// `alaz db` 的子命令
+#[derive(Subcommand)]
+enum DbCommand {
+    /// 为数据库中缺少的指令生成骨架条目
+    /// 
+    /// 收集 dump 中指令数据库 (aarch64_instructions.json) 没有收录的助记符，
+    /// 输出 JSON 数组：每个助记符一条，附上猜测的类别 (在 JSON 中的位置)、
+    /// 第一次出现的写法和出现次数，名称和说明留空。补全后即可合并进数据库。
+    /// 
+    /// 示例:
+    ///   alaz db stub my_code_O3.dump
+    ///   objdump -d a.o | alaz db stub - -o missing.json
+    #[command(verbatim_doc_comment)]
+    Stub {
+        /// dump 文件
+        #[arg(value_name = "DUMP", help = "objdump 输出文件 (如: my_code_O3.dump，- 表示标准输入)")]
+        dump: String,
+
+        /// 输出文件 (默认输出到标准输出)
+        #[arg(short, long, value_name = "FILE", help = "保存骨架条目的 JSON 文件")]
+        output: Option<PathBuf>,
+    },
+}
+
+fn main() {
+    // 配置文件决定命令行参数的默认值，需要在解析命令行之前读取
+    let config = match alaz::config::Config::load(config_path_arg().as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", format!("❌ 错误: {:#}", e).red().bold());
+            std::process::exit(1);
+        }
+    };
+    match config.color {
+        alaz::config::ColorMode::Always => colored::control::set_override(true),
+        alaz::config::ColorMode::Never => colored::control::set_override(false),
+        alaz::config::ColorMode::Auto => {}
+    }
+    let matches = apply_config(Cli::command(), &config).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // 配置日志
+    let log_level = if cli.verbose { "info" } else { "warn" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level))
+        .init();
+
+    // 执行命令
+    let result = match cli.command {
+        Commands::Analyze { function, prefix, all, combined, jobs, output, levels, report } => {
+            match (all, function, prefix) {
+                (Some(prefix), _, _) if combined => analyze_combined(&prefix, output.as_ref(), &levels, jobs, &report),
+                (Some(prefix), _, _) => analyze_all(&prefix, output.as_ref(), &levels, jobs, &report),
+                (None, Some(function), Some(prefix)) => match alaz::picker::FunctionSelection::parse(&function) {
+                    Ok(Some(selection)) => analyze_selection(&function, &selection, &prefix, output.as_ref(), &levels, &report),
+                    Ok(None) => resolve_function(&function, &prefix, &levels)
+                        .and_then(|function| analyze_dumps(&function, &prefix, output.as_ref(), &levels, &report)),
+                    Err(e) => Err(e.into()),
+                },
+                (None, _, _) => unreachable!("clap 保证未使用 --all 时提供了函数名称和前缀"),
+            }
+        }
+        Commands::Interactive { prefix, single, multi: _, output, levels, report } => {
+            interactive_mode(&prefix, single, output.as_ref(), &levels, &report)
+        }
+        Commands::Build { source, function, cc, cflags, objdump_path, build_dir, output, levels, report } => {
+            let mut config = alaz::build::BuildConfig::new()
+                .with_cc(&cc)
+                .with_cflags(cflags)
+                .with_objdump(&objdump_path)
+                .with_out_dir(build_dir);
+            if !levels.is_empty() {
+                config = config.with_levels(levels);
+            }
+            build_mode(&source, function.as_deref(), &config, output.as_ref(), &report)
+        }
+        Commands::Elf { file, function, objdump_path, output, report } => {
+            elf_mode(&file, function.as_deref(), &objdump_path, output.as_ref(), &report)
+        }
+        Commands::Step { function, dump, regs, mems, stop_when, export, export_format } => {
+            match export {
+                Some(path) => export_trace(&function, &dump, &regs, &mems, &path, export_format.as_deref()),
+                None => step_mode(&function, &dump, &regs, &mems, &stop_when),
+            }
+        }
+        Commands::Verify { function, prefix, levels, regs, mems, ignore_return, lang } => {
+            verify_mode(&function, &prefix, &levels, &regs, &mems, !ignore_return, &lang)
+        }
+        Commands::Snippet { function, prefix, lines, level, format } => {
+            snippet_mode(&function, &prefix, lines.as_deref(), &level, &format)
+        }
+        Commands::Stats { function, prefix, levels, format, output, level_labels } => {
+            stats_mode(&function, &prefix, &levels, &format, output.as_ref(), &level_labels)
+        }
+        Commands::Cost { function, prefix, core, trip_count, levels, blocks, format, output, level_labels } => {
+            cost_mode(&function, &prefix, &core, trip_count, &levels, blocks, &format, output.as_ref(), &level_labels)
+        }
+        Commands::XrefReg { register, function, dump, format, lang } => {
+            xref_mode(&register, &function, &dump, &format, &lang)
+        }
+        Commands::Explain { instruction, format, lang } => {
+            explain_mode(&instruction.join(" "), &format, &lang)
+        }
+        Commands::Repl { regs, mems, no_exec, lang } => {
+            repl_mode(&regs, &mems, !no_exec, &lang)
+        }
+        Commands::Tui { prefix, levels, lang } => {
+            tui_mode(&prefix, &levels, &lang)
+        }
+        Commands::Summary { dump, format, output } => {
+            summary_mode(&dump, &format, output.as_ref())
+        }
+        Commands::List { source, sort, filter, json } => {
+            list_mode(&source, &sort, filter.as_deref(), json)
+        }
+        Commands::Callgraph { dump, format, output } => {
+            callgraph_mode(&dump, &format, output.as_ref())
+        }
+        Commands::InlineReport { prefix, levels, format, output, lang } => {
+            inline_report_mode(&prefix, &levels, &format, output.as_ref(), &lang)
+        }
+        Commands::Annotate { dump, functions, format, output, lang } => {
+            annotate_mode(&dump, &functions, &format, output.as_ref(), &lang)
+        }
+        Commands::Extract { function, dump, output, comments, mnemonic_case, indent, align, lang } => {
+            asm_style(&mnemonic_case, &indent, align)
+                .and_then(|style| extract_mode(&function, &dump, output.as_ref(), comments, style, &lang))
+        }
+        Commands::Db(DbCommand::Stub { dump, output }) => {
+            db_stub_mode(&dump, output.as_ref())
+        }
+        Commands::Completions { shell } => {
+            generate_completions(&shell)
+        }
+        Commands::ListFunctions { source, plain } => {
+            list_functions_mode(source.as_deref(), plain)
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", format!("❌ 错误: {}", e).red().bold());
+        if let Some(candidates) = function_suggestions(&e) {
+            eprintln!("{} {}", "💡 你是不是要找:".yellow(), candidates.join(", ").bold());
+        }
+        std::process::exit(1);
+    }
+}
+
+/// 错误是“未找到函数”且有名称相近的函数时，返回这些候选名称
+fn function_suggestions(error: &anyhow::Error) -> Option<&[String]> {
+    match error.chain().find_map(|cause| cause.downcast_ref::<alaz::InterpreterError>())? {
+        alaz::InterpreterError::FunctionNotFound { candidates, .. } if !candidates.is_empty() => Some(candidates),
+        _ => None,
+    }
+}
+
+/// 命令行中的 `--config FILE` / `--config=FILE`
+fn config_path_arg() -> Option<PathBuf> {
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+/// 把配置文件中的设置作为各子命令对应参数的默认值，命令行中显式指定的值仍然优先
+fn apply_config(mut command: clap::Command, config: &alaz::config::Config) -> clap::Command {
+    let mut defaults: Vec<(&str, Vec<String>)> = Vec::new();
+    if let Some(ref dir) = config.output_dir {
+        defaults.push(("output", vec![dir.to_string_lossy().into_owned()]));
+    }
+    if let Some(ref lang) = config.lang {
+        defaults.push(("lang", vec![lang.clone()]));
+    }
+    if let Some(ref objdump) = config.objdump {
+        defaults.push(("objdump_path", vec![objdump.clone()]));
+    }
+    if let Some(ref cc) = config.cc {
+        defaults.push(("cc", vec![cc.clone()]));
+    }
+    if !config.levels.is_empty() {
+        defaults.push(("levels", config.levels.clone()));
+    }
+    if !config.columns.is_empty() {
+        defaults.push(("columns", config.columns.clone()));
+    }
+    if defaults.is_empty() {
+        return command;
+    }
+
+    let names: Vec<String> = command.get_subcommands().map(|sub| sub.get_name().to_string()).collect();
+    for name in names {
+        command = command.mut_subcommand(name, |mut sub| {
+            for (id, values) in &defaults {
+                // 其他命令的 -o 是输出文件，输出目录只用于 -o DIR
+                let applies = sub.get_arguments().any(|arg| {
+                    arg.get_id() == id
+                        && (*id != "output" || arg.get_value_names().is_some_and(|names| names.iter().any(|n| n == "DIR")))
+                });
+                if applies {
+                    sub = sub.mut_arg(*id, |arg| arg.default_values(values.clone()));
+                }
+            }
+            sub
+        });
+    }
+    command
+}
+
+/// 命令行的进度显示：读取和编译等步骤为灰色，完成为绿色
+struct CliProgress;
+
+impl alaz::progress::ProgressSink for CliProgress {
+    fn report(&self, progress: alaz::progress::Progress<'_>) {
+        use alaz::progress::Progress;
+
+        let message = progress.message();
+        match progress {
+            Progress::Done => println!("{}", message.green()),
+            Progress::GeneratingComparison | Progress::GeneratingAnalysis => println!("{}", message.cyan()),
+            _ => println!("{}", message.dimmed()),
+        }
+    }
+}
+
+/// 根据报告选项创建表格生成器（可选加载备注文件）
+fn build_generator(report: &ReportArgs) -> anyhow::Result<alaz::table::TableGenerator> {
+    use alaz::cost::{Core, CostModel};
+    use alaz::notes::Notes;
+    use alaz::remarks::Remarks;
+    use alaz::locale::Lang;
+    use alaz::profile::Profile;
+    use alaz::table::{Grouping, Overwrite, TableConfig, TableGenerator};
+
+    let profile = match report.profile {
+        Some(ref name) => Profile::parse(name)?,
+        None => Profile::default(),
+    };
+    let mut generator = TableGenerator::new()
+        .with_progress(CliProgress)
+        .with_language(Lang::parse(&report.lang)?)
+        .with_json_output(report.json)
+        .with_terminal_output(report.stdout)
+        .with_outlining(report.outlining || profile.outlining)
+        .with_helper_expansion(report.expand_helpers)
+        .with_liveness(report.liveness || profile.liveness)
+        .with_branch_stats(report.branch_stats || profile.branches)
+        .with_source_alignment(report.by_source)
+        .with_walkthrough(report.walkthrough || profile.walkthrough)
+        .with_returns(report.returns || profile.returns)
+        .with_signature(report.signature || profile.signature)
+        .with_hardening(report.hardening || profile.hardening)
+        .with_stack_alignment(report.stack_align)
+        .with_branch_labels(!report.no_branch_labels)
+        .with_value_tracking(!report.no_values)
+        .with_jump_arrows(report.arrows)
+        .with_dead_stores(report.dead_stores)
+        .with_width_checks(report.width_checks)
+        .with_pseudocode(report.pseudocode)
+        .with_reproducible(report.reproducible)
+        .with_strict_parsing(report.strict)
+        .with_level_labels(alaz::levels::LevelLabels::parse(&report.level_labels)?);
+    if let Some(ref mode) = report.group {
+        generator = generator.with_grouping(Grouping::parse(mode)?);
+    }
+    if let Some(ref core) = report.core {
+        generator = generator.with_cost_model(CostModel::new(Core::parse(core)?));
+    }
+    if let Some(ref template) = report.output_file {
+        generator = generator.with_output_template(template);
+    }
+    if report.force {
+        generator = generator.with_overwrite(Overwrite::Always);
+    }
+    if let Some(enabled) = report.address_columns {
+        generator = generator.with_address_columns(enabled);
+    }
+    if !report.columns.is_empty() {
+        generator = generator.with_table_config(TableConfig::parse(&report.columns)?);
+    }
+    for spec in &report.remarks {
+        let (level, path) = match spec.split_once('=') {
+            Some((level, path)) if !level.is_empty() => (Some(level), path),
+            _ => (None, spec.as_str()),
+        };
+        generator = generator.with_remarks(level, Remarks::load_from_file(path)?);
+    }
+    if report.trace {
+        generator = generator.with_trace(build_cpu_state(&report.trace_regs, &report.trace_mems)?);
+    }
+    match report.notes.as_ref() {
+        Some(path) => Ok(generator.with_notes(Notes::load_from_file(&path.to_string_lossy())?)),
+        None => Ok(generator),
+    }
+}
+
+/// 分析 objdump 文件并生成对比表格
+fn analyze_dumps(
+    function: &str,
+    prefix: &str,
+    output: Option<&PathBuf>,
+    levels: &[String],
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    println!("{} {}", "📋 分析函数:".yellow(), function.bold());
+    println!("{} {}", "📁 文件前缀:".yellow(), prefix);
+    if let Some(out) = output {
+        println!("{} {}", "💾 输出目录:".yellow(), out.display());
+    }
+    if !levels.is_empty() {
+        println!("{} {}", "📊 优化级别:".yellow(), levels.join(", "));
+    }
+    if let Some(notes) = report.notes.as_ref() {
+        println!("{} {}", "📝 备注文件:".yellow(), notes.display());
+    }
+    println!();
+
+    let mut generator = build_generator(report)?;
+    if !levels.is_empty() {
+        generator = generator.with_levels(levels.to_vec());
+    }
+    print_report(generator.generate_from_dumps(function, prefix, output)?);
+
+    println!();
+    println!("{}", "✅ 分析完成！".green().bold());
+    Ok(())
+}
+
+/// 终端模式 (--stdout) 下打印渲染好的报告；保存到文件时进度已经提示过路径
+fn print_report(report: alaz::table::ReportOutput) {
+    if let alaz::table::ReportOutput::Terminal(text) = report {
+        println!("\n{}", text);
+    }
+}
+
+/// 把命令行给出的函数名、起始地址或名称片段解析为完整的函数名
+///
+/// 在第一个优化级别的 dump 中查找；匹配到多个函数时在终端中让用户选择。
+/// 找不到 dump 文件时原样返回，由后续分析报告错误。
+fn resolve_function(query: &str, prefix: &str, levels: &[String]) -> anyhow::Result<String> {
+    use std::io::{self, IsTerminal, Write};
+
+    let prefix = alaz::levels::clean_prefix(prefix);
+    let level = match levels.first() {
+        Some(level) => level.clone(),
+        None => match alaz::levels::discover(&prefix).into_iter().next() {
+            Some(level) => level,
+            None => return Ok(query.to_string()),
+        },
+    };
+    let Ok(parser) = alaz::objdump::ObjdumpParser::from_file(&alaz::levels::dump_path(&prefix, &level)) else {
+        return Ok(query.to_string());
+    };
+
+    let mut candidates = parser.match_functions(query)?;
+    match candidates.len() {
+        0 => {
+            let error = anyhow::Error::from(parser.function_not_found(query));
+            return Err(error.context(format!("{} 中未找到函数: {}", level, query)));
+        }
+        1 => {
+            let function = candidates.remove(0);
+            if function != query {
+                println!("{} {} → {}", "🔍 匹配函数:".yellow(), query, function.bold());
+            }
+            return Ok(function);
+        }
+        _ => {}
+    }
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("{} 匹配到多个函数，请指定完整名称: {}", query, candidates.join(", "));
+    }
+
+    println!("{} {} 匹配到多个函数:", "🔍".yellow(), query);
+    for (idx, function) in candidates.iter().enumerate() {
+        println!("  {}. {}", format!("{:3}", idx + 1).cyan(), function);
+    }
+    loop {
+        print!("{} ", "选择 >".bright_blue().bold());
+        io::stdout().flush()?;
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            anyhow::bail!("未选择函数");
+        }
+        match input.trim().parse::<usize>() {
+            Ok(num) if num > 0 && num <= candidates.len() => return Ok(candidates.swap_remove(num - 1)),
+            _ => println!("{}", "❌ 无效的选择，请输入正确的编号".red()),
+        }
+    }
+}
+
+/// 输出批量分析中每个函数的结果、耗时和警告
+fn print_batch_results(results: &[alaz::analyzer::FunctionAnalysisResult]) {
+    for result in results {
+        let elapsed = format!("({} ms)", result.duration.as_millis()).dimmed();
+        match result.error {
+            Some(ref e) => println!("{} {} {} {}", "❌".red(), result.function.bold(), e, elapsed),
+            None => println!("{} {} {}", "✓".green(), result.function.bold(), elapsed),
+        }
+        for warning in &result.warnings {
+            println!("    {} {}", "⚠".yellow(), warning);
+        }
+    }
+}
+
+/// 分析共同函数中匹配通配符、正则或编号的每个函数（只匹配一个时与直接指定函数名相同）
+fn analyze_selection(
+    pattern: &str,
+    selection: &alaz::picker::FunctionSelection,
+    prefix: &str,
+    output: Option<&PathBuf>,
+    levels: &[String],
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+    let mut analyzer = alaz::analyzer::Analyzer::new(prefix, levels)?.with_generator(build_generator(report)?);
+    if let Some(out) = output {
+        analyzer = analyzer.with_output_dir(out.clone());
+    }
+    let functions = analyzer.functions()?;
+    let selected: Vec<String> = selection.select(&functions).into_iter().map(String::from).collect();
+    match selected.as_slice() {
+        [] => anyhow::bail!("{} 个共同函数中没有匹配 {} 的函数", functions.len(), pattern),
+        [function] => {
+            println!("{} {} → {}", "🔍 匹配函数:".yellow(), pattern, function.bold());
+            return analyze_dumps(function, prefix, output, levels, report);
+        }
+        _ => {}
+    }
+    if report.stdout {
+        anyhow::bail!("{} 匹配到 {} 个函数，--stdout 只能用于单个函数", pattern, selected.len());
+    }
+    if report.output_file.as_ref().is_some_and(|template| !template.contains("{function}")) {
+        anyhow::bail!("匹配多个函数时 --output-file 必须包含 {{function}}，否则各函数的报告会写到同一个文件");
+    }
+
+    println!("{} {} 匹配到 {} 个函数: {}", "🔍".yellow(), pattern, selected.len(), selected.join(", "));
+    if let Some(out) = output {
+        println!("{} {}", "💾 输出目录:".yellow(), out.display());
+    }
+    let results = analyzer.analyze_all(|function| selected.iter().any(|f| f == function))?;
+    println!();
+    print_batch_results(&results);
+
+    let failed = results.iter().filter(|r| !r.is_ok()).count();
+    println!();
+    if failed > 0 {
+        anyhow::bail!("{} 个函数分析失败", failed);
+    }
+    println!("{} {} 份报告", "✅ 已生成".green().bold(), results.len());
+    Ok(())
+}
+
+/// 批量分析所有优化级别共有的函数，并生成索引文件和结果清单
+fn analyze_all(
+    prefix: &str,
+    output: Option<&PathBuf>,
+    levels: &[String],
+    jobs: usize,
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+    if report.stdout {
+        anyhow::bail!("--stdout 不能与 --all 同时使用 (批量分析需要生成报告文件和索引)");
+    }
+    if report.output_file.as_ref().is_some_and(|template| !template.contains("{function}")) {
+        anyhow::bail!("--all 时 --output-file 必须包含 {{function}}，否则各函数的报告会写到同一个文件");
+    }
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (批量分析)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    let mut analyzer = alaz::analyzer::Analyzer::new(prefix, levels)?
+        .with_generator(build_generator(report)?)
+        .with_jobs(jobs);
+    if let Some(out) = output {
+        analyzer = analyzer.with_output_dir(out.clone());
+    }
+    let levels = analyzer.levels().to_vec();
+    let functions = analyzer.functions()?;
+    if functions.is_empty() {
+        anyhow::bail!("{} 个优化级别 ({}) 没有共同函数", levels.len(), levels.join(", "));
+    }
+    println!("{} {} 个共同函数 ({})", "✓ 检测到".green(), functions.len(), levels.join(", "));
+    if let Some(out) = output {
+        println!("{} {}", "💾 输出目录:".yellow(), out.display());
+    }
+
+    // 索引或结果清单不允许覆盖时在分析之前就报错，而不是写完所有报告后才失败
+    let (index_path, manifest_path) = analyzer.index_paths();
+    analyzer.generator().prepare_files(&[index_path, manifest_path])?;
+
+    let results = analyzer.analyze_all(|_| true)?;
+    println!();
+    print_batch_results(&results);
+
+    let (index_path, manifest_path) = analyzer.write_index(&results)?;
+
+    let failed = results.iter().filter(|r| !r.is_ok()).count();
+    println!();
+    println!("{} {}", "✅ 索引已保存到:".green().bold(), index_path.display());
+    println!("{} {}", "📋 结果清单:".green(), manifest_path.display());
+    if failed > 0 {
+        println!("{} {} 个函数分析失败，详见索引", "⚠".yellow(), failed);
+    }
+    Ok(())
+}
+
+/// 批量分析所有优化级别共有的函数，合并为一份带目录的报告
+fn analyze_combined(
+    prefix: &str,
+    output: Option<&PathBuf>,
+    levels: &[String],
+    jobs: usize,
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+    if report.stdout || report.json {
+        anyhow::bail!("--combined 只生成一份 Markdown 报告，不能与 --stdout/--json 同时使用");
+    }
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (合并报告)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    let analyzer = alaz::analyzer::Analyzer::new(prefix, levels)?
+        .with_generator(build_generator(report)?)
+        .with_jobs(jobs);
+    let levels = analyzer.levels().to_vec();
+    let reports = analyzer.analyze_reports(|_| true)?;
+    if reports.is_empty() {
+        anyhow::bail!("{} 个优化级别 ({}) 没有共同函数", levels.len(), levels.join(", "));
+    }
+    println!("{} {} 个共同函数 ({})", "✓ 检测到".green(), reports.len(), levels.join(", "));
+    for (function, result) in &reports {
+        if let Err(e) = result {
+            println!("{} {} {}", "❌".red(), function.bold(), e);
+        }
+        for warning in result.iter().flat_map(|r| r.per_level.iter().flat_map(|l| l.warnings.iter().map(move |w| (l, w)))) {
+            println!("    {} {} {}: {}", "⚠".yellow(), function, warning.0.level, warning.1);
+        }
+    }
+
+    let stem = std::path::Path::new(analyzer.prefix())
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| analyzer.prefix().to_string());
+    let generator = analyzer.generator();
+    let path = generator.combined_path(&stem, &levels, output);
+    generator.prepare_output(&path)?;
+    generator.save_to_file(&generator.generate_combined(&stem, &levels, &reports), &path)?;
+
+    println!();
+    println!("{} {}", "✅ 合并报告已保存到:".green().bold(), path.display());
+    let failed = reports.iter().filter(|(_, r)| r.is_err()).count();
+    if failed > 0 {
+        println!("{} {} 个函数分析失败，详见目录", "⚠".yellow(), failed);
+    }
+    Ok(())
+}
+
+/// 交互式菜单模式
+fn interactive_mode(
+    prefix: &str,
+    single_mode: bool,
+    output: Option<&PathBuf>,
+    levels: &[String],
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+    use alaz::objdump::{ObjdumpParser, STDIN_PATH};
+
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (交互式模式)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    if single_mode {
+        // 单文件模式：只读取指定的文件（`-` 为标准输入）
+        let dump_path = if prefix == STDIN_PATH || prefix.ends_with(".dump") {
+            prefix.to_string()
+        } else {
+            format!("{}.dump", prefix)
+        };
+        let source = if dump_path == STDIN_PATH { "stdin" } else { dump_path.as_str() };
+        
+        println!("{} {} (单文件模式)", "📂 正在读取:".yellow(), source);
+        
+        let parser = load_dump(&dump_path)?;
+        single_file_menu(&parser, source, output, report)?;
+        return Ok(());
+    }
+
+    // 多文件模式：读取各优化级别的共同函数
+    // 智能处理文件路径和提取真实前缀
+    // 例如: spark_matrix_naive_O2.dump -> spark_matrix_naive
+    let real_prefix = alaz::levels::clean_prefix(prefix);
+    
+    // 未指定级别时自动发现 <前缀>_<级别>.dump 文件
+    let levels: Vec<String> = if levels.is_empty() {
+        let found = alaz::levels::discover(&real_prefix);
+        if found.is_empty() {
+            alaz::levels::DEFAULT_LEVELS.iter().map(|l| l.to_string()).collect()
+        } else {
+            found
+        }
+    } else {
+        levels.to_vec()
+    };
+    
+    println!("{} 读取 {} 个优化级别的文件以找出共同函数...", "⚙".yellow(), levels.len());
+    
+    // 各级别的 dump 只读取一次，之后每次选择函数都直接查询已建立索引的解析器
+    let mut parsers: Vec<(String, ObjdumpParser)> = Vec::new();
+    for level in &levels {
+        let path = alaz::levels::dump_path(&real_prefix, level);
+        match ObjdumpParser::from_file(&path) {
+            Ok(parser) => {
+                println!("  {} {} 文件读取成功", "✓".green(), level);
+                parsers.push((level.clone(), parser));
+            }
+            Err(_) => println!("  {} {} 文件未找到", "⚠".yellow(), level),
+        }
+    }
+    
+    let mut common_functions: Option<std::collections::HashSet<String>> = None;
+    for (_, parser) in &parsers {
+        let func_set: std::collections::HashSet<_> = parser.list_functions()?.into_iter().collect();
+        common_functions = Some(match common_functions {
+            None => func_set,
+            Some(existing) => existing.intersection(&func_set).cloned().collect(),
+        });
+    }
+    
+    let mut functions: Vec<String> = common_functions
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    
+    if functions.is_empty() {
+        println!("{}", "❌ 未找到任何共同函数".red());
+        if parsers.is_empty() {
+            println!("{}", "提示: 请确保存在 <PREFIX>_<级别>.dump 文件 (如 *_O0.dump, *_O2.dump)".yellow());
+        }
+        return Ok(());
+    }
+    
+    functions.sort();
+    
+    println!();
+    println!("{} {} 个共同函数 (在所有优化级别都存在)", "✓ 检测到".green(), functions.len());
+    println!();
+
+    let generator = build_generator(report)?.with_levels(parsers.iter().map(|(level, _)| level.clone()).collect());
+    let parser_refs: Vec<(&str, &ObjdumpParser)> = parsers.iter().map(|(level, parser)| (level.as_str(), parser)).collect();
+    let mut picker = alaz::picker::FunctionPicker::new(functions);
+    while let Some(function) = pick_function(&mut picker)? {
+        println!();
+        println!("{}", "=".repeat(60).cyan());
+        println!("{} {}", "📋 分析函数:".yellow(), function.bold());
+
+        match generator.generate_from_parsers(&function, &parser_refs, output) {
+            Ok(report) => {
+                print_report(report);
+                println!("{}", "✅ 分析完成！".green().bold());
+            }
+            Err(e) => {
+                println!();
+                println!("{} {}", "❌ 分析失败:".red(), e);
+            }
+        }
+
+        println!();
+        println!("按 Enter 继续...");
+        let mut _pause = String::new();
+        read_input(&mut _pause)?;
+        println!();
+    }
+
+    Ok(())
+}
+
+/// 根据寄存器赋值和缓冲区预置表达式构建初始 CPU 状态
+fn build_cpu_state(regs: &[String], mems: &[String]) -> anyhow::Result<alaz::emulator::CpuState> {
+    let mut state = alaz::emulator::CpuState::default();
+    for assignment in regs {
+        let (reg, value) = parse_register_assignment(assignment)?;
+        state.write_reg(reg, value);
+    }
+    for buffer in mems {
+        let (address, bytes) = alaz::emulator::parse_buffer(buffer)?;
+        state.map_buffer(address, bytes)?;
+    }
+    Ok(state)
+}
+
+/// 解析寄存器赋值 `REG=VALUE`
+fn parse_register_assignment(text: &str) -> anyhow::Result<(alaz::Register, u64)> {
+    let (name, value) = text
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("寄存器赋值格式应为 REG=VALUE: {}", text))?;
+    let reg = alaz::Register::parse(name.trim())?;
+    let value = value.trim();
+    let value = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16)?,
+        None => value.parse::<i64>()? as u64,
+    };
+    Ok((reg, value))
+}
+
+/// 单步调试模式
+fn step_mode(
+    function: &str,
+    dump_path: &str,
+    regs: &[String],
+    mems: &[String],
+    stop_when: &[String],
+) -> anyhow::Result<()> {
+    use alaz::emulator::{Emulator, StopCondition, TraceEnd};
+    use alaz::semantic::SemanticInterpreter;
+    use std::io::{self, Write};
+
+    const MAX_STEPS: usize = 10_000;
+
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (单步调试)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    let parser = load_dump(dump_path)?;
+    let entries: Vec<_> = parser
+        .extract_function_data(function)?
+        .into_iter()
+        .filter(|e| !e.asm_instruction.is_empty())
+        .collect();
+    let address_of = |e: &alaz::objdump::DumpEntry| u64::from_str_radix(&e.address, 16).ok();
+
+    let mut emulator = Emulator::with_state(build_cpu_state(regs, mems)?);
+    emulator.state.pc = entries
+        .first()
+        .and_then(address_of)
+        .ok_or_else(|| anyhow::anyhow!("函数 {} 中没有可执行的指令", function))?;
+
+    println!("{} {} ({} 条指令)", "📋 函数:".yellow(), function.bold(), entries.len());
+    println!("操作: {} 单步  {} 运行到结束  {} 退出", "[Enter]".green(), "[c]".green(), "[q]".red());
+    println!();
+
+    let conditions = stop_when
+        .iter()
+        .map(|text| StopCondition::parse(text).map(|cond| (text.as_str(), cond)))
+        .collect::<alaz::Result<Vec<_>>>()?;
+    for (text, _) in &conditions {
+        println!("{} {}", "⏸ 停止条件:".yellow(), text);
+    }
+
+    // 设置了停止条件时直接连续执行
+    let mut run_to_end = !conditions.is_empty();
+    let mut steps = 0;
+    let mut output_len = 0;
+
+    loop {
+        let pc = emulator.state.pc;
+        let entry = match emulator.current_entry(&entries) {
+            Ok(entry) => entry,
+            Err(TraceEnd::Returned(x0)) => {
+                println!();
+                println!("{} X0 = 0x{:x}", "✅ 函数返回:".green().bold(), x0);
+                break;
+            }
+            Err(TraceEnd::Exited(code)) => {
+                println!();
+                println!("{} {}", "✅ 程序退出，退出码:".green().bold(), code);
+                break;
+            }
+            Err(end) => {
+                println!("{}", format!("⚠ {}，停止执行", end).yellow());
+                break;
+            }
+        };
+        let Some(inst) = entry.parsed_instruction.as_ref() else {
+            println!("{}", format!("⚠ 无法解析指令 '{}'，停止执行", entry.asm_instruction).yellow());
+            break;
+        };
+
+        println!(
+            "{} {}  {}",
+            format!("{:>8x}:", pc).dimmed(),
+            entry.asm_instruction.cyan(),
+            SemanticInterpreter::interpret(inst).green()
+        );
+
+        if !run_to_end {
+            print!("{} ", "step >".bright_blue().bold());
+            io::stdout().flush()?;
+            let mut input = String::new();
+            if read_input(&mut input)? == 0 {
+                break;
+            }
+            match input.trim() {
+                "q" | "quit" => break,
+                "c" | "continue" => run_to_end = true,
+                _ => {}
+            }
+        }
+
+        let before = emulator.state.clone();
+        match emulator.step_in_function(&entries, inst) {
+            Ok(true) => println!("    {}", "(跳过对外部函数的调用，返回值未模拟)".dimmed()),
+            Ok(false) => {}
+            Err(e) => {
+                println!("{} {}", "❌ 执行失败:".red(), e);
+                break;
+            }
+        }
+
+        print_register_changes(&emulator.state, &before);
+        if let Some(ref event) = emulator.last_syscall {
+            println!("    {} {}", "系统调用".magenta(), event);
+            if event.stubbed && emulator.output.len() > output_len {
+                print!("{}", String::from_utf8_lossy(&emulator.output[output_len..]).bright_white());
+                output_len = emulator.output.len();
+            }
+        }
+        print_memory_accesses(&emulator);
+
+        if let Some((text, _)) = conditions.iter().find(|(_, cond)| cond.holds(&emulator.state)) {
+            println!();
+            println!("{} {}", "⏸ 条件成立:".green().bold(), text);
+            print_cpu_state(&emulator.state);
+            break;
+        }
+
+        steps += 1;
+        if steps >= MAX_STEPS {
+            println!("{}", format!("⚠ 已执行 {} 步，可能陷入死循环，停止执行", MAX_STEPS).yellow());
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// 输出一步执行后发生变化的寄存器和标志位
+fn print_register_changes(state: &alaz::emulator::CpuState, before: &alaz::emulator::CpuState) {
+    for change in state.changes_from(before) {
+        println!(
+            "    {} 0x{:x} → {}",
+            format!("{:>4}:", change.name).yellow(),
+            change.old,
+            format!("0x{:x}", change.new).bold()
+        );
+    }
+}
+
+/// 输出一步执行中的内存访问
+fn print_memory_accesses(emulator: &alaz::emulator::Emulator) {
+    for access in &emulator.accesses {
+        let action = if access.write { "内存写" } else { "内存读" };
+        println!(
+            "    {} [0x{:x}] ({} 字节) = {}",
+            action.magenta(),
+            access.address,
+            access.size,
+            format!("0x{:x}", access.value).bold()
+        );
+    }
+}
+
+/// 全屏浏览各优化级别的反汇编
+fn tui_mode(prefix: &str, levels: &[String], lang: &str) -> anyhow::Result<()> {
+    use alaz::locale::Lang;
+
+    alaz::tui::run(prefix, levels, Lang::parse(lang)?)
+}
+
+/// 交互式指令练习：逐条解释并执行输入的指令
+fn repl_mode(regs: &[String], mems: &[String], execute: bool, lang: &str) -> anyhow::Result<()> {
+    use alaz::emulator::Emulator;
+    use alaz::locale::Lang;
+    use std::io::{self, Write};
+
+    let lang = Lang::parse(lang)?;
+    let initial = build_cpu_state(regs, mems)?;
+    let mut emulator = Emulator::with_state(initial.clone());
+    let mut execute = execute;
+
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (REPL)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+    println!("输入 AArch64 指令 (如: add x0, x1, x2)，{} 查看命令，{} 退出", ":help".green(), ":q".red());
+    println!();
+
+    loop {
+        print!("{} ", "alaz>".bright_blue().bold());
+        io::stdout().flush()?;
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            println!();
+            break;
+        }
+        let line = input.trim();
+
+        match line {
+            "" => continue,
+            ":q" | ":quit" | ":exit" => break,
+            ":help" => {
+                println!("    {}  查看当前 CPU 状态", ":regs ".green());
+                println!("    {}  重置为初始状态", ":reset".green());
+                println!("    {}  切换是否在模拟器中执行 (当前: {})", ":exec ".green(), if execute { "执行" } else { "只解释" });
+                println!("    {}  退出", ":q    ".green());
+                continue;
+            }
+            ":regs" => {
+                print_cpu_state(&emulator.state);
+                continue;
+            }
+            ":reset" => {
+                emulator = Emulator::with_state(initial.clone());
+                println!("    {}", "已重置为初始状态".dimmed());
+                continue;
+            }
+            ":exec" => {
+                execute = !execute;
+                println!("    {}", if execute { "执行已开启" } else { "执行已关闭，只解释指令" }.dimmed());
+                continue;
+            }
+            command if command.starts_with(':') => {
+                println!("{} 未知命令 {}，输入 :help 查看命令", "⚠".yellow(), command);
+                continue;
+            }
+            _ => {}
+        }
+
+        let explanation = match alaz::explain::explain_in(line, lang) {
+            Ok(explanation) => explanation,
+            Err(e) => {
+                println!("{} {}", "❌ 无法解析:".red(), e);
+                continue;
+            }
+        };
+        println!("    {}", explanation.semantics.green());
+        if !execute {
+            continue;
+        }
+
+        // 输入的指令没有地址，按当前 PC 执行，使 PC 逐条前进
+        let mut inst = explanation.instruction;
+        inst.address = emulator.state.pc;
+        let before = emulator.state.clone();
+        if let Err(e) = emulator.step(&inst) {
+            println!("{} {}", "❌ 执行失败:".red(), e);
+            continue;
+        }
+        print_register_changes(&emulator.state, &before);
+        print_memory_accesses(&emulator);
+        if let Some(ref event) = emulator.last_syscall {
+            println!("    {} {}", "系统调用".magenta(), event);
+        }
+        println!();
+        print_cpu_state(&emulator.state);
+    }
+
+    Ok(())
+}
+
+/// 执行函数并导出执行轨迹
+fn export_trace(
+    function: &str,
+    dump_path: &str,
+    regs: &[String],
+    mems: &[String],
+    path: &std::path::Path,
+    format: Option<&str>,
+) -> anyhow::Result<()> {
+    use alaz::emulator::Emulator;
+    use alaz::objdump::ObjdumpParser;
+    use alaz::trace_export::{self, TraceFormat};
+
+    const MAX_STEPS: usize = 10_000;
+
+    let format = match format {
+        Some(name) => TraceFormat::parse(name)?,
+        None => TraceFormat::from_path(path),
+    };
+
+    let parser = ObjdumpParser::from_file(dump_path)?;
+    let entries = parser.extract_function_data(function)?;
+    let mut emulator = Emulator::with_state(build_cpu_state(regs, mems)?);
+    emulator.state.pc = alaz::emulator::entry_address(&entries)
+        .ok_or_else(|| anyhow::anyhow!("函数 {} 中没有可执行的指令", function))?;
+
+    let trace = emulator.trace_function(&entries, MAX_STEPS);
+    std::fs::write(path, trace_export::export(function, &trace, format))?;
+
+    println!(
+        "{} {} ({} 步，{})",
+        "✅ 执行轨迹已导出:".green().bold(),
+        path.display(),
+        trace.steps.len(),
+        trace.end
+    );
+    Ok(())
+}
+
+/// 差分模拟：用相同输入运行各优化级别的函数并比较结果
+fn verify_mode(
+    function: &str,
+    prefix: &str,
+    levels: &[String],
+    regs: &[String],
+    mems: &[String],
+    check_return: bool,
+    lang: &str,
+) -> anyhow::Result<()> {
+    use alaz::equivalence::{self, Comparison, RunOutcome};
+    use alaz::locale::Lang;
+    use alaz::objdump::ObjdumpParser;
+
+    const MAX_STEPS: usize = 100_000;
+
+    let lang = Lang::parse(lang)?;
+
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (差分模拟)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    let clean_prefix = alaz::levels::clean_prefix(prefix);
+    let levels = if levels.is_empty() {
+        alaz::levels::discover(&clean_prefix)
+    } else {
+        levels.to_vec()
+    };
+    if levels.len() < 2 {
+        anyhow::bail!("至少需要两个优化级别的 dump 文件才能比较 (找到 {} 个)", levels.len());
+    }
+
+    let state = build_cpu_state(regs, mems)?;
+    let mut outcomes = Vec::with_capacity(levels.len());
+    for level in &levels {
+        let parser = ObjdumpParser::from_file(&alaz::levels::dump_path(&clean_prefix, level))?;
+        let entries = parser.extract_function_data(function)?;
+        let outcome = RunOutcome::run(&entries, state.clone(), MAX_STEPS)
+            .ok_or_else(|| anyhow::anyhow!("{} 中函数 {} 没有可执行的指令", level, function))?;
+        println!(
+            "{} {} 步，{}",
+            format!("{:>6}:", level).yellow(),
+            outcome.trace.steps.len(),
+            outcome.trace.end.describe(lang)
+        );
+        outcomes.push(outcome);
+    }
+    println!();
+
+    let (baseline_level, baseline) = (&levels[0], &outcomes[0]);
+    let mut diverged = 0;
+    for (level, outcome) in levels.iter().zip(&outcomes).skip(1) {
+        match equivalence::compare(baseline, outcome, check_return, lang) {
+            Comparison::Equivalent => println!("{} {} 与 {} 一致", "✅".green(), level, baseline_level),
+            Comparison::Diverged(divergence) => {
+                diverged += 1;
+                println!("{} {} 与 {} 不一致 — {}", "❌".red(), level, baseline_level, divergence.describe(lang));
+            }
+            Comparison::Inconclusive => {
+                println!("{} {} 与 {} 都没有正常结束，无法判断是否一致", "⚠️".yellow(), level, baseline_level)
+            }
+        }
+    }
+
+    if diverged > 0 {
+        anyhow::bail!("{} 个优化级别的行为与 {} 不一致", diverged, baseline_level);
+    }
+    Ok(())
+}
+
+/// 编译 C 源码生成各优化级别的 dump 文件后分析
+fn build_mode(
+    source: &std::path::Path,
+    function: Option<&str>,
+    config: &alaz::build::BuildConfig,
+    output: Option<&PathBuf>,
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (编译模式)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    println!("{} {}", "🔧 编译器:".yellow(), config.cc);
+    let prefix = config.build_dumps(source, &CliProgress)?;
+    println!("{} {}_{{{}}}.dump", "✓ 已生成".green(), prefix, config.levels.join(","));
+    println!();
+
+    match function {
+        Some(function) => analyze_dumps(function, &prefix, output, &config.levels, report),
+        None => interactive_mode(&prefix, false, output, &config.levels, report),
+    }
+}
+
+/// 调用 objdump 反汇编目标文件后分析
+fn elf_mode(
+    file: &str,
+    function: Option<&str>,
+    objdump: &str,
+    output: Option<&PathBuf>,
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+    use alaz::objdump::ObjdumpParser;
+
+    println!("{}", "=".repeat(60).cyan());
+    println!("{}", "  ALAZ - 汇编语言分析工具 (ELF 模式)".cyan().bold());
+    println!("{}", "=".repeat(60).cyan());
+    println!();
+
+    println!("{} {} -d -S {}", "⚙ 正在运行:".yellow(), objdump, file);
+    let parser = ObjdumpParser::from_object_file(file, objdump)?;
+
+    match function {
+        Some(function) => {
+            print_report(build_generator(report)?.generate_from_parser(function, &parser, file, output)?);
+            println!();
+            println!("{}", "✅ 分析完成！".green().bold());
+            Ok(())
+        }
+        None => single_file_menu(&parser, file, output, report),
+    }
+}
+
+/// dump 来自标准输入时，交互输入改从控制终端读取
+static INPUT_FROM_TTY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// 读取 dump 文件；路径为 `-` 时读取标准输入，之后的交互输入改从终端读取
+fn load_dump(path: &str) -> alaz::Result<alaz::objdump::ObjdumpParser> {
+    if path == alaz::objdump::STDIN_PATH {
+        INPUT_FROM_TTY.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+    alaz::objdump::ObjdumpParser::from_file(path)
+}
+
+/// 读取一行交互输入，返回读到的字节数（0 表示输入结束）
+fn read_input(input: &mut String) -> std::io::Result<usize> {
+    use std::io::BufRead;
+
+    #[cfg(windows)]
+    const TERMINAL: &str = "CONIN$";
+    #[cfg(not(windows))]
+    const TERMINAL: &str = "/dev/tty";
+
+    if !INPUT_FROM_TTY.load(std::sync::atomic::Ordering::Relaxed) {
+        return std::io::stdin().read_line(input);
+    }
+    let terminal = std::fs::File::open(TERMINAL).map_err(|e| {
+        std::io::Error::new(e.kind(), format!("dump 来自标准输入，无法打开终端读取交互输入: {}", e))
+    })?;
+    std::io::BufReader::new(terminal).read_line(input)
+}
+
+/// 显示函数列表并读取输入，直到选中函数（返回其名称）或退出（返回 None）
+fn pick_function(picker: &mut alaz::picker::FunctionPicker) -> anyhow::Result<Option<String>> {
+    use alaz::picker::Action;
+    use std::io::{self, Write};
+
+    loop {
+        println!("{}", "=".repeat(60).cyan());
+        match picker.filter() {
+            Some(filter) => println!(
+                "{} (过滤: {}，匹配 {} 个)",
+                "可用函数列表:".yellow().bold(),
+                filter.cyan(),
+                picker.match_count()
+            ),
+            None => println!("{}", "可用函数列表:".yellow().bold()),
+        }
+        println!("{}", "-".repeat(60));
+
+        for (num, func) in picker.page_items() {
+            println!("  {}. {}", format!("{:3}", num).cyan(), func);
+        }
+
+        let (page, pages) = picker.page();
+        println!("{}", "-".repeat(60));
+        if pages > 1 {
+            println!("第 {}/{} 页", page + 1, pages);
+        }
+        println!();
+        println!("请选择:");
+        println!("  {} 输入函数编号进行分析", "●".green());
+        println!("  {} 输入 /关键字 过滤函数 (支持正则，单独输入 / 清除过滤)", "●".green());
+        if pages > 1 {
+            println!("  {} 输入 'n' / 'p' 翻到下一页 / 上一页", "●".green());
+        }
+        println!("  {} 输入 'q' 或 'quit' 退出", "●".red());
+        println!();
+
+        print!("{} ", "选择 >".bright_blue().bold());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        read_input(&mut input)?;
+
+        match picker.handle(&input) {
+            Action::Select(index) => return Ok(Some(picker.name(index).to_string())),
+            Action::Quit => {
+                println!();
+                println!("{}", "👋 再见！".yellow());
+                return Ok(None);
+            }
+            Action::Redraw => println!(),
+            Action::Invalid => {
+                println!("{}", "❌ 无效的选择，请输入正确的编号".red());
+                println!();
+            }
+        }
+    }
+}
+
+/// 单文件的函数选择菜单（interactive -s 与 elf 共用）
+fn single_file_menu(
+    parser: &alaz::objdump::ObjdumpParser,
+    source: &str,
+    output: Option<&PathBuf>,
+    report: &ReportArgs,
+) -> anyhow::Result<()> {
+    let mut functions = parser.list_functions()?;
+    
+    if functions.is_empty() {
+        println!("{}", "❌ 未找到任何函数".red());
+        return Ok(());
+    }
+    
+    functions.sort();
+    println!();
+    println!("{} {} 个函数", "✓ 检测到".green(), functions.len());
+    println!();
+    
+    // 单文件模式下的交互循环
+    let mut picker = alaz::picker::FunctionPicker::new(functions);
+    while let Some(function) = pick_function(&mut picker)? {
+        println!();
+        println!("{}", "=".repeat(60).cyan());
+
+        let result = build_generator(report)
+            .and_then(|generator| generator.generate_from_parser(&function, parser, source, output));
+
+        match result {
+            Ok(report) => print_report(report),
+            Err(e) => {
+                println!();
+                println!("{} {}", "❌ 分析失败:".red(), e);
+            }
+        }
+
+        println!();
+        println!("按 Enter 继续...");
+        let mut _pause = String::new();
+        read_input(&mut _pause)?;
+        println!();
+    }
+
+    Ok(())
+}
+
+/// 输出完整的 CPU 状态（非零通用寄存器、SP、PC 和标志位）
+fn print_cpu_state(state: &alaz::emulator::CpuState) {
+    let registers: Vec<String> = state
+        .x
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value != 0)
+        .map(|(i, value)| format!("{:>4}: 0x{:<16x}", format!("X{}", i), value))
+        .collect();
+    for row in registers.chunks(3) {
+        println!("    {}", row.join("  ").trim_end());
+    }
+    println!("    {:>4}: 0x{:<16x}  {:>4}: 0x{:x}", "SP", state.sp, "PC", state.pc);
+    println!(
+        "    NZCV: {}{}{}{}",
+        if state.flags.n { "N" } else { "-" },
+        if state.flags.z { "Z" } else { "-" },
+        if state.flags.c { "C" } else { "-" },
+        if state.flags.v { "V" } else { "-" }
+    );
+}
+
+/// 输出函数中选定范围的指令片段
+fn snippet_mode(
+    function: &str,
+    prefix: &str,
+    lines: Option<&str>,
+    level: &str,
+    format: &str,
+) -> anyhow::Result<()> {
+    use alaz::objdump::ObjdumpParser;
+    use alaz::table::TableGenerator;
+
+    let path = if prefix == alaz::objdump::STDIN_PATH || prefix.ends_with(".dump") {
+        prefix.to_string()
+    } else {
+        alaz::levels::dump_path(&alaz::levels::clean_prefix(prefix), level)
+    };
+    let entries = ObjdumpParser::open_streaming(&path, function)?.extract_function_data(function)?;
+
+    let (start, end) = match lines {
+        Some(range) => {
+            let (start, end) = range
+                .split_once('-')
+                .ok_or_else(|| anyhow::anyhow!("无效的指令范围: {} (应为 START-END，如 3-9)", range))?;
+            (start.trim().parse::<usize>()?, end.trim().parse::<usize>()?)
+        }
+        None => (1, usize::MAX),
+    };
+    if start == 0 || start > end {
+        anyhow::bail!("无效的指令范围: {}-{} (从 1 开始，且起点不能大于终点)", start, end);
+    }
+    let selected = TableGenerator::select_instructions(&entries, start, end);
+    if selected.is_empty() {
+        anyhow::bail!("函数 {} 中没有第 {} 条及之后的指令", function, start);
+    }
+
+    let generator = TableGenerator::new();
+    match format {
+        "md" => print!("{}", generator.generate_table(&selected)),
+        "txt" => print!("{}", generator.generate_plain_snippet(&selected)),
+        _ => anyhow::bail!("不支持的输出格式: {} (可选: md, txt)", format),
+    }
+    Ok(())
+}
+
+/// 统计各优化级别的指令构成
+fn stats_mode(
+    function: &str,
+    prefix: &str,
+    levels: &[String],
+    format: &str,
+    output: Option<&PathBuf>,
+    level_labels: &[String],
+) -> anyhow::Result<()> {
+    use alaz::levels::LevelLabels;
+    use alaz::metrics::{self, Stats};
+
+    let clean_prefix = alaz::levels::clean_prefix(prefix);
+    let levels = if levels.is_empty() {
+        alaz::levels::discover(&clean_prefix)
+    } else {
+        levels.to_vec()
+    };
+    if levels.is_empty() {
+        anyhow::bail!("未找到 {}_<级别>.dump 文件", clean_prefix);
+    }
+    let labels = LevelLabels::parse(level_labels)?;
+
+    let mut stats = Vec::with_capacity(levels.len());
+    for (level, parser) in levels.iter().zip(alaz::levels::load_all(&clean_prefix, &levels)?) {
+        stats.push((level.as_str(), Stats::from_entries(&parser.extract_function_data(function)?)));
+    }
+
+    let content = match format {
+        "md" => {
+            let refs: Vec<(&str, &Stats)> = stats.iter().map(|(level, s)| (labels.name(level), s)).collect();
+            format!("## {} 指令构成\n\n{}", function, metrics::comparison_table(&refs, Default::default()))
+        }
+        "json" => {
+            let levels: Vec<_> = stats
+                .iter()
+                .map(|(level, s)| serde_json::json!({ "level": level, "total": s.total, "counts": s.counts }))
+                .collect();
+            serde_json::to_string_pretty(&serde_json::json!({ "function": function, "levels": levels }))?
+        }
+        _ => anyhow::bail!("不支持的输出格式: {} (可选: md, json)", format),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!("{} {}", "✅ 统计结果已保存到:".green().bold(), path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// 按基本块和循环加权估计函数在各优化级别的周期数
+#[allow(clippy::too_many_arguments)]
+fn cost_mode(
+    function: &str,
+    prefix: &str,
+    core: &str,
+    trip_count: u32,
+    levels: &[String],
+    blocks: bool,
+    format: &str,
+    output: Option<&PathBuf>,
+    level_labels: &[String],
+) -> anyhow::Result<()> {
+    use alaz::cost::{format_cycles, Core, CostModel, FunctionCost};
+    use alaz::levels::LevelLabels;
+
+    let model = CostModel::new(Core::parse(core)?);
+    let clean_prefix = alaz::levels::clean_prefix(prefix);
+    let levels = if levels.is_empty() {
+        alaz::levels::discover(&clean_prefix)
+    } else {
+        levels.to_vec()
+    };
+    if levels.is_empty() {
+        anyhow::bail!("未找到 {}_<级别>.dump 文件", clean_prefix);
+    }
+    let labels = LevelLabels::parse(level_labels)?;
+
+    let mut costs: Vec<(&str, FunctionCost)> = Vec::with_capacity(levels.len());
+    for (level, parser) in levels.iter().zip(alaz::levels::load_all(&clean_prefix, &levels)?) {
+        costs.push((level.as_str(), model.function_cost(&parser.extract_function_data(function)?, trip_count)));
+    }
+
+    let content = match format {
+        "md" => {
+            let mut text = format!(
+                "## {} 估计周期（{}，每个循环 {} 次迭代）\n\n",
+                function,
+                model.name(),
+                trip_count
+            );
+            text.push_str("| 级别 | 指令数 | 基本块 | 循环 | 延迟合计 | 发射下限 |\n");
+            text.push_str("|------|--------|--------|------|----------|----------|\n");
+            for (level, cost) in &costs {
+                text.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} |\n",
+                    labels.name(level),
+                    cost.instructions,
+                    cost.blocks.len(),
+                    cost.loops,
+                    format_cycles(cost.latency),
+                    format_cycles(cost.issue)
+                ));
+            }
+            text.push_str("\n> 延迟合计假设每条指令都依赖上一条，发射下限假设指令之间没有依赖；循环中的块按迭代次数加权\n");
+            if blocks {
+                for (level, cost) in &costs {
+                    text.push_str(&format!("\n### {}\n\n", labels.name(level)));
+                    text.push_str("| 基本块 | 指令数 | 循环深度 | 权重 | 延迟 | 发射 |\n");
+                    text.push_str("|--------|--------|----------|------|------|------|\n");
+                    for block in &cost.blocks {
+                        text.push_str(&format!(
+                            "| 0x{:x} - 0x{:x} | {} | {} | ×{} | {} | {} |\n",
+                            block.start,
+                            block.end,
+                            block.instructions,
+                            block.loop_depth,
+                            format_cycles(block.weight),
+                            format_cycles(block.latency),
+                            format_cycles(block.issue)
+                        ));
+                    }
+                }
+            }
+            text
+        }
+        "json" => {
+            let levels: Vec<_> = costs
+                .iter()
+                .map(|(level, cost)| serde_json::json!({ "level": level, "cost": cost }))
+                .collect();
+            serde_json::to_string_pretty(&serde_json::json!({
+                "function": function,
+                "core": model.name(),
+                "trip_count": trip_count,
+                "levels": levels,
+            }))?
+        }
+        _ => anyhow::bail!("不支持的输出格式: {} (可选: md, json)", format),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!("{} {}", "✅ 估计结果已保存到:".green().bold(), path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// 解释单条指令（文本/JSON）
+fn explain_mode(text: &str, format: &str, lang: &str) -> anyhow::Result<()> {
+    use alaz::locale::Lang;
+
+    let lang = Lang::parse(lang)?;
+    let explanation = alaz::explain::explain_in(text, lang)?;
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&explanation)?);
+        return Ok(());
+    }
+    if format != "text" {
+        anyhow::bail!("不支持的输出格式: {} (可选: text, json)", format);
+    }
+
+    let label = |zh: &'static str, en: &'static str| format!("{:<7}", lang.pick(zh, en)).yellow();
+    println!("{} {}", label("指令:", "Instr:"), explanation.text.bold());
+    if let Some(ref def) = explanation.definition {
+        println!("{} {}", label("名称:", "Name:"), def.name);
+        println!("{} {}", label("格式:", "Form:"), def.format);
+    }
+    println!("{} {}", label("类别:", "Class:"), explanation.category.name_in(lang));
+    println!("{} {}", label("语义:", "Means:"), explanation.semantics.green());
+    // 没有专门的语义模板时语义解释就是数据库说明，不重复输出
+    if let Some(ref def) = explanation.definition {
+        if def.description_in(lang) != explanation.semantics {
+            println!("{} {}", label("说明:", "About:"), def.description_in(lang));
+        }
+    }
+
+    println!();
+    println!("{}", lang.pick("操作数:", "Operands:").yellow());
+    let width = explanation.operands.iter().map(|o| o.text.len()).max().unwrap_or(0);
+    for (i, operand) in explanation.operands.iter().enumerate() {
+        println!("  {}. {:<width$}  {}", i + 1, operand.text.cyan(), operand.role.name_in(lang), width = width);
+    }
+
+    println!();
+    let none = lang.pick("(无)", "(none)");
+    let join = |regs: &[String]| if regs.is_empty() { none.to_string() } else { regs.join(", ") };
+    println!("{} {}", label("读取:", "Reads:"), join(&explanation.reads));
+    println!("{} {}", label("写入:", "Writes:"), join(&explanation.writes));
+    let flags = match (explanation.flags_set.is_empty(), explanation.reads_flags) {
+        (true, false) => lang.pick("不读写条件标志位", "not read or written").to_string(),
+        (true, true) => lang.pick("读取条件标志位", "reads the condition flags").to_string(),
+        (false, _) => {
+            let set = explanation.flags_set.join("");
+            match lang {
+                Lang::Zh => format!("设置 {}", set),
+                Lang::En => format!("sets {}", set),
+            }
+        }
+    };
+    println!("{} {}", label("标志:", "Flags:"), flags);
+    Ok(())
+}
+
+/// 列出函数中引用指定寄存器的指令
+fn xref_mode(register: &str, function: &str, dump_path: &str, format: &str, lang: &str) -> anyhow::Result<()> {
+    use alaz::locale::Lang;
+    use alaz::objdump::ObjdumpParser;
+
+    let lang = Lang::parse(lang)?;
+    let reg = alaz::Register::parse(register)?;
+    let entries = ObjdumpParser::open_streaming(dump_path, function)?.extract_function_data(function)?;
+    let xrefs = alaz::xref::find(&entries, reg, lang);
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&xrefs)?);
+        return Ok(());
+    }
+    if format != "text" {
+        anyhow::bail!("不支持的输出格式: {} (可选: text, json)", format);
+    }
+
+    let reads = xrefs.iter().filter(|x| x.reads()).count();
+    let writes = xrefs.iter().filter(|x| x.writes()).count();
+    match lang {
+        Lang::Zh => println!(
+            "{} {} 在 {} 中被 {} 条指令引用 (读取 {}，写入 {})",
+            "🔍".yellow(),
+            register.to_lowercase().bold(),
+            function.bold(),
+            xrefs.len(),
+            reads,
+            writes
+        ),
+        Lang::En => println!(
+            "{} {} is referenced by {} instructions in {} ({} reads, {} writes)",
+            "🔍".yellow(),
+            register.to_lowercase().bold(),
+            xrefs.len(),
+            function.bold(),
+            reads,
+            writes
+        ),
+    }
+    println!();
+
+    let asm_width = xrefs.iter().map(|x| x.asm_instruction.chars().count()).max().unwrap_or(0);
+    // 中文在终端中占两列
+    let display_width = |text: &str| text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum::<usize>();
+    let roles: Vec<String> = xrefs.iter().map(|x| x.roles_in(lang)).collect();
+    let role_width = roles.iter().map(|r| display_width(r)).max().unwrap_or(0);
+    for (xref, role) in xrefs.iter().zip(&roles) {
+        let role = format!("{}{}", role, " ".repeat(role_width - display_width(role)));
+        println!(
+            "{}  {:<asm_width$}  {}  {}",
+            format!("{:>8}:", xref.address).dimmed(),
+            xref.asm_instruction.cyan(),
+            if xref.writes() { role.red() } else { role.normal() },
+            xref.semantic.green(),
+            asm_width = asm_width
+        );
+    }
+    Ok(())
+}
+
+/// 汇总 dump 文件中的所有函数（Markdown/JSON）
+fn summary_mode(dump_path: &str, format: &str, output: Option<&PathBuf>) -> anyhow::Result<()> {
+    use alaz::metrics::FileSummary;
+    use alaz::objdump::ObjdumpParser;
+
+    let parser = ObjdumpParser::from_file(dump_path)?;
+    let summary = FileSummary::from_parser(&parser)?;
+
+    let content = match format {
+        "md" => format!("## {} 函数概况\n\n{}", dump_path, summary.to_markdown()),
+        "json" => summary.to_json(),
+        _ => anyhow::bail!("不支持的输出格式: {} (可选: md, json)", format),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!("{} {}", "✅ 汇总结果已保存到:".green().bold(), path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// 列出 dump 文件（或前缀下各级别的 dump 文件）中的函数清单
+fn list_mode(source: &str, sort: &str, filter: Option<&str>, json: bool) -> anyhow::Result<()> {
+    use alaz::metrics::{FunctionList, ListOrder};
+    use alaz::objdump::{ObjdumpParser, STDIN_PATH};
+
+    let order = ListOrder::parse(sort)?;
+    let filter = filter.map(regex::Regex::new).transpose()?;
+
+    let paths: Vec<String> = if source == STDIN_PATH || source.ends_with(".dump") {
+        vec![source.to_string()]
+    } else {
+        let prefix = alaz::levels::clean_prefix(source);
+        let levels = alaz::levels::discover(&prefix);
+        if levels.is_empty() {
+            anyhow::bail!("未找到 {}_<级别>.dump 文件", prefix);
+        }
+        levels.iter().map(|level| alaz::levels::dump_path(&prefix, level)).collect()
+    };
+
+    let mut lists = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let mut list = FunctionList::from_parser(&ObjdumpParser::from_file(path)?)?;
+        if let Some(ref pattern) = filter {
+            list.retain_matching(pattern);
+        }
+        list.sort(order);
+        lists.push((path, list));
+    }
+
+    if json {
+        let files: Vec<_> = lists
+            .iter()
+            .map(|(path, list)| serde_json::json!({ "file": path, "functions": list.functions }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "files": files }))?);
+        return Ok(());
+    }
+    for (i, (path, list)) in lists.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let name = if path.as_str() == STDIN_PATH { "stdin" } else { path.as_str() };
+        println!("{} ({} 个函数)", name.bold(), list.functions.len());
+        print!("{}", list.to_text());
+    }
+    Ok(())
+}
+
+/// 生成调用图（DOT/JSON）
+fn callgraph_mode(dump_path: &str, format: &str, output: Option<&PathBuf>) -> anyhow::Result<()> {
+    use alaz::callgraph::CallGraph;
+    use alaz::objdump::ObjdumpParser;
+
+    let parser = ObjdumpParser::from_file(dump_path)?;
+    let graph = CallGraph::build(&parser)?;
+
+    let content = match format.to_lowercase().as_str() {
+        "dot" => graph.to_dot(),
+        "json" => graph.to_json(),
+        _ => anyhow::bail!("不支持的输出格式: {} (支持: dot, json)", format),
+    };
+
+    // 输出到标准输出时不打印其他信息，便于直接管道给 dot
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!(
+                "{} {} ({} 个函数, {} 条调用边)",
+                "✅ 调用图已保存到".green(),
+                path.display(),
+                graph.functions.len(),
+                graph.edges.len()
+            );
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// 生成内联报告（各级别间消失的函数与克隆函数）
+fn inline_report_mode(
+    prefix: &str,
+    levels: &[String],
+    format: &str,
+    output: Option<&PathBuf>,
+    lang: &str,
+) -> anyhow::Result<()> {
+    use alaz::inlining::InliningReport;
+    use alaz::locale::Lang;
+
+    let lang = Lang::parse(lang)?;
+    let clean_prefix = alaz::levels::clean_prefix(prefix);
+    let levels = if levels.is_empty() {
+        alaz::levels::discover(&clean_prefix)
+    } else {
+        levels.to_vec()
+    };
+    if levels.len() < 2 {
+        anyhow::bail!("内联报告至少需要两个级别的 {}_<级别>.dump 文件", clean_prefix);
+    }
+
+    let parsers = alaz::levels::load_all(&clean_prefix, &levels)?;
+    let pairs: Vec<(&str, &alaz::objdump::ObjdumpParser)> =
+        levels.iter().map(String::as_str).zip(parsers.iter()).collect();
+    let report = InliningReport::build(&pairs)?;
+
+    let content = match format {
+        "md" => format!("## {} {}\n\n{}", clean_prefix, lang.pick("内联报告", "inlining report"), report.to_markdown(lang)),
+        "json" => report.to_json(),
+        _ => anyhow::bail!("不支持的输出格式: {} (可选: md, json)", format),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!("{} {}", "✅ 内联报告已保存到:".green().bold(), path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// 生成 Ghidra/Binary Ninja 注释脚本
+fn annotate_mode(
+    dump_path: &str,
+    functions: &[String],
+    format: &str,
+    output: Option<&PathBuf>,
+    lang: &str,
+) -> anyhow::Result<()> {
+    use alaz::annotate::{self, ScriptFormat};
+    use alaz::locale::Lang;
+    use alaz::objdump::ObjdumpParser;
+
+    let format = ScriptFormat::parse(format)?;
+    let lang = Lang::parse(lang)?;
+    let parser = ObjdumpParser::from_file(dump_path)?;
+    let functions = if functions.is_empty() { parser.list_functions()? } else { functions.to_vec() };
+
+    let mut annotations = Vec::new();
+    for function in &functions {
+        let entries = parser.extract_function_data(function)?;
+        annotations.extend(annotate::collect(function, &entries, lang));
+    }
+    let content = annotate::to_script(&annotations, format);
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!(
+                "{} {} ({} 个函数, {} 条注释)",
+                "✅ 注释脚本已保存到".green(),
+                path.display(),
+                functions.len(),
+                annotations.len()
+            );
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// 解析输出汇编的代码风格
+fn asm_style(case: &str, indent: &str, align: bool) -> anyhow::Result<alaz::extract::AsmStyle> {
+    use alaz::extract::{AsmStyle, Indent, MnemonicCase};
+
+    Ok(AsmStyle { case: MnemonicCase::parse(case)?, indent: Indent::parse(indent)?, align })
+}
+
+/// 把函数提取为可重新汇编的 .s 文件
+fn extract_mode(
+    function: &str,
+    dump_path: &str,
+    output: Option<&PathBuf>,
+    comments: bool,
+    style: alaz::extract::AsmStyle,
+    lang: &str,
+) -> anyhow::Result<()> {
+    use alaz::extract::Extractor;
+    use alaz::locale::Lang;
+    use alaz::objdump::ObjdumpParser;
+
+    let extractor = Extractor::new()
+        .with_comments(comments)
+        .with_language(Lang::parse(lang)?)
+        .with_style(style);
+    let entries = ObjdumpParser::open_streaming(dump_path, function)?.extract_function_data(function)?;
+    let content = extractor.extract(function, &entries);
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!("{} {}", "✅ 汇编源文件已保存到".green(), path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// 为 dump 中数据库没有收录的助记符生成骨架条目
+fn db_stub_mode(dump_path: &str, output: Option<&PathBuf>) -> anyhow::Result<()> {
+    use alaz::objdump::ObjdumpParser;
+
+    let parser = ObjdumpParser::from_file(dump_path)?;
+    if parser.is_arm32() {
+        anyhow::bail!("{} 是 ARM32 dump，指令数据库只收录 AArch64 指令", dump_path);
+    }
+    let mut functions = parser.list_functions()?;
+    functions.dedup();
+    let mut instructions = Vec::new();
+    for function in &functions {
+        instructions.extend(parser.extract_function_data(function)?.into_iter().map(|e| e.asm_instruction));
+    }
+    let database = alaz::instruction_db::InstructionDatabase::load_embedded()?;
+    let stubs = database.stub_entries(instructions.iter().map(String::as_str));
+    let content = serde_json::to_string_pretty(&stubs)?;
+
+    // 输出到标准输出时不打印其他信息，便于重定向
+    match output {
+        Some(path) => {
+            std::fs::write(path, content + "\n")?;
+            println!("{} {} ({} 个未收录的助记符)", "✅ 骨架条目已保存到".green(), path.display(), stubs.len());
+        }
+        None => println!("{}", content),
+    }
+    Ok(())
+}
+
+/// 生成 shell 补全脚本
+fn generate_completions(shell_name: &str) -> anyhow::Result<()> {
+    let shell = match shell_name.to_lowercase().as_str() {
+        "bash" => Shell::Bash,
+        "fish" => Shell::Fish,
+        "zsh" => Shell::Zsh,
+        "powershell" => Shell::PowerShell,
         "elvish" => Shell::Elvish,
         _ => {
             eprintln!("{}", format!("❌ 不支持的 shell: {}", shell_name).red());
@@ -421,9 +2783,66 @@ fn generate_completions(shell_name: &str) -> anyhow::Result<()> {
 
     let mut cmd = Cli::command();
     let bin_name = cmd.get_name().to_string();
-    
+    let positions = alaz::completion::function_positions(&cmd);
+
     // 只输出补全脚本，不输出任何其他信息
-    generate(shell, &mut cmd, bin_name, &mut std::io::stdout());
-    
+    let mut script = Vec::new();
+    generate(shell, &mut cmd, bin_name.clone(), &mut script);
+    let script = String::from_utf8(script)?;
+    print!("{}", alaz::completion::with_function_completion(shell, &bin_name, script, &positions));
+
+    Ok(())
+}
+
+/// 列出 dump 文件中的函数名：文件列出其中所有函数，前缀列出各级别共同的函数，
+/// 省略时列出当前目录中所有 .dump 文件的函数（去重排序）。只扫描函数头行，不解析指令
+fn list_functions_mode(source: Option<&str>, plain: bool) -> anyhow::Result<()> {
+    use alaz::objdump::scan_function_names;
+    use std::collections::BTreeSet;
+
+    let paths: Vec<String> = match source {
+        Some(path) if path == alaz::objdump::STDIN_PATH || path.ends_with(".dump") => vec![path.to_string()],
+        Some(prefix) => {
+            let prefix = alaz::levels::clean_prefix(prefix);
+            let mut common: Option<BTreeSet<String>> = None;
+            for level in alaz::levels::discover(&prefix) {
+                let names: BTreeSet<String> = match scan_function_names(&alaz::levels::dump_path(&prefix, &level)) {
+                    Ok(names) => names.into_iter().collect(),
+                    Err(_) if plain => BTreeSet::new(),
+                    Err(e) => return Err(e.into()),
+                };
+                common = Some(match common {
+                    None => names,
+                    Some(existing) => existing.intersection(&names).cloned().collect(),
+                });
+            }
+            for function in common.unwrap_or_default() {
+                println!("{}", function);
+            }
+            return Ok(());
+        }
+        None => {
+            let mut paths: Vec<String> = std::fs::read_dir(".")?
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "dump"))
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect();
+            paths.sort();
+            paths
+        }
+    };
+
+    let mut functions = BTreeSet::new();
+    for path in &paths {
+        match scan_function_names(path) {
+            Ok(names) => functions.extend(names),
+            Err(_) if plain => {}
+            Err(e) => anyhow::bail!("{}: {}", path, e),
+        }
+    }
+    for function in functions {
+        println!("{}", function);
+    }
     Ok(())
 }
@@ -0,0 +1,756 @@
+//! 指令统计
+//!
+//! 按类别（算术、加载、存储、跳转、SIMD 等）统计函数的指令构成，
+//! 并生成各优化级别的对比直方图，用来观察优化对指令组成的影响。
+//! 也可以汇总整个 dump 文件中每个函数的规模和特征。
+//! 面向性能分析时，还统计条件跳转的方向和循环头的对齐情况。
+
+use crate::analysis;
+use crate::instruction::{Instruction, InstructionType};
+use crate::objdump::{DumpEntry, ObjdumpParser};
+use crate::error::Result;
+use crate::locale::Lang;
+use crate::signature::Signature;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// 直方图条形的最大长度
+const BAR_WIDTH: usize = 20;
+
+/// 指令类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    /// 加减乘除
+    Arithmetic,
+    /// 逻辑、移位和位域操作
+    Logic,
+    /// 条件选择（CSEL/CSET 等）
+    Conditional,
+    /// 比较
+    Compare,
+    /// 寄存器间数据移动和地址生成
+    Move,
+    /// 内存加载
+    Load,
+    /// 内存存储
+    Store,
+    /// 跳转
+    Branch,
+    /// 函数调用
+    Call,
+    /// 函数返回
+    Return,
+    /// 标量浮点
+    FloatingPoint,
+    /// SIMD/NEON 向量
+    Simd,
+    /// 原子和独占访问
+    Atomic,
+    /// 系统指令（屏障、系统调用、指针认证等）
+    System,
+    /// 其他
+    Other,
+}
+
+impl Category {
+    /// 类别的显示名称
+    pub fn name(&self) -> &'static str {
+        self.name_in(Lang::Zh)
+    }
+
+    /// 按语言返回类别的显示名称
+    pub fn name_in(&self, lang: Lang) -> &'static str {
+        if lang == Lang::En {
+            return match self {
+                Category::Arithmetic => "Arithmetic",
+                Category::Logic => "Logic/Bitwise",
+                Category::Conditional => "Conditional select",
+                Category::Compare => "Compare",
+                Category::Move => "Move",
+                Category::Load => "Load",
+                Category::Store => "Store",
+                Category::Branch => "Branch",
+                Category::Call => "Call",
+                Category::Return => "Return",
+                Category::FloatingPoint => "Floating point",
+                Category::Simd => "SIMD",
+                Category::Atomic => "Atomic",
+                Category::System => "System",
+                Category::Other => "Other",
+            };
+        }
+        match self {
+            Category::Arithmetic => "算术",
+            Category::Logic => "逻辑/位操作",
+            Category::Conditional => "条件选择",
+            Category::Compare => "比较",
+            Category::Move => "数据移动",
+            Category::Load => "加载",
+            Category::Store => "存储",
+            Category::Branch => "跳转",
+            Category::Call => "调用",
+            Category::Return => "返回",
+            Category::FloatingPoint => "浮点",
+            Category::Simd => "SIMD",
+            Category::Atomic => "原子操作",
+            Category::System => "系统",
+            Category::Other => "其他",
+        }
+    }
+
+    /// 根据汇编指令文本判断类别
+    pub fn of(asm_instruction: &str) -> Self {
+        let mut parts = asm_instruction.split_whitespace();
+        let mnemonic = parts.next().unwrap_or("").to_lowercase();
+        let operands = parts.collect::<Vec<_>>().join(" ").to_lowercase();
+        let m = mnemonic.as_str();
+
+        // 带排列说明符的 V 寄存器（如 v0.4s）是向量运算
+        let vector = operands
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '.')
+            .any(|op| op.starts_with('v') && op.contains('.'));
+
+        match m {
+            "ret" | "eret" | "retaa" | "retab" => Category::Return,
+            "bl" | "blr" => Category::Call,
+            "b" | "br" | "cbz" | "cbnz" | "tbz" | "tbnz" => Category::Branch,
+            _ if m.starts_with("b.") => Category::Branch,
+            "cmp" | "cmn" | "tst" | "ccmp" | "ccmn" => Category::Compare,
+            "csel" | "csinc" | "csinv" | "csneg" | "cset" | "csetm" | "cinc" | "cinv" | "cneg" => {
+                Category::Conditional
+            }
+            _ if ["ldadd", "ldclr", "ldeor", "ldset", "stadd", "stclr", "steor", "stset", "swp", "cas"]
+                .iter()
+                .any(|p| m.starts_with(p)) =>
+            {
+                Category::Atomic
+            }
+            "ldxr" | "ldxrb" | "ldxrh" | "ldxp" | "ldaxr" | "ldaxrb" | "ldaxrh" | "ldaxp" | "stxr" | "stxrb"
+            | "stxrh" | "stxp" | "stlxr" | "stlxrb" | "stlxrh" | "stlxp" | "ldar" | "stlr" => Category::Atomic,
+            _ if vector => Category::Simd,
+            _ if m.starts_with("ld") => Category::Load,
+            _ if m.starts_with("st") => Category::Store,
+            "mov" | "movz" | "movk" | "movn" | "adr" | "adrp" => Category::Move,
+            _ if m.starts_with('f') || m == "scvtf" || m == "ucvtf" => Category::FloatingPoint,
+            "add" | "adds" | "sub" | "subs" | "mul" | "madd" | "msub" | "mneg" | "neg" | "negs" | "adc" | "sbc"
+            | "udiv" | "sdiv" | "smull" | "umull" | "smulh" | "umulh" | "smaddl" | "umaddl" | "smsubl"
+            | "umsubl" => Category::Arithmetic,
+            "and" | "ands" | "orr" | "eor" | "bic" | "bics" | "orn" | "eon" | "mvn" | "lsl" | "lsr" | "asr"
+            | "ror" | "ubfx" | "sbfx" | "ubfm" | "sbfm" | "bfm" | "bfi" | "bfxil" | "ubfiz" | "sbfiz" | "extr"
+            | "rev" | "rev16" | "rev32" | "clz" | "cls" | "rbit" | "sxtb" | "sxth" | "sxtw" | "uxtb" | "uxth" => {
+                Category::Logic
+            }
+            _ if m.starts_with("crc32") => Category::Logic,
+            "nop" | "svc" | "hlt" | "brk" | "dmb" | "dsb" | "isb" | "mrs" | "msr" | "hint" | "wfe" | "wfi"
+            | "yield" | "bti" => Category::System,
+            _ if m.starts_with("pac") || m.starts_with("aut") => Category::System,
+            _ => Category::Other,
+        }
+    }
+}
+
+/// 一个函数的指令构成统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Stats {
+    /// 指令总数（不含提示/备注行）
+    pub total: usize,
+    /// 各类别的指令数
+    pub counts: BTreeMap<Category, usize>,
+}
+
+impl Stats {
+    /// 统计函数条目中的指令
+    pub fn from_entries(entries: &[DumpEntry]) -> Self {
+        let mut stats = Self::default();
+        for entry in entries.iter().filter(|e| !e.asm_instruction.is_empty()) {
+            stats.total += 1;
+            *stats.counts.entry(Category::of(&entry.asm_instruction)).or_insert(0) += 1;
+        }
+        stats
+    }
+
+    /// 某个类别的指令数
+    pub fn count(&self, category: Category) -> usize {
+        self.counts.get(&category).copied().unwrap_or(0)
+    }
+}
+
+/// 生成各优化级别的指令构成对比直方图（Markdown 表格）
+pub fn comparison_table(levels: &[(&str, &Stats)], lang: Lang) -> String {
+    let mut categories: Vec<Category> = levels
+        .iter()
+        .flat_map(|(_, stats)| stats.counts.keys().copied())
+        .collect();
+    categories.sort();
+    categories.dedup();
+    let max = levels
+        .iter()
+        .flat_map(|(_, stats)| stats.counts.values().copied())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = format!("| {} |", lang.pick("类别", "Category"));
+    for (level, _) in levels {
+        output.push_str(&format!(" {} |", level));
+    }
+    output.push_str("\n|------|");
+    output.push_str(&"------|".repeat(levels.len()));
+    output.push('\n');
+
+    for category in categories {
+        output.push_str(&format!("| {} |", category.name_in(lang)));
+        for (_, stats) in levels {
+            let count = stats.count(category);
+            let bar = "█".repeat((count * BAR_WIDTH).div_ceil(max.max(1)));
+            let percent = count as f64 * 100.0 / stats.total.max(1) as f64;
+            output.push_str(format!(" {} ({:.0}%) {}", count, percent, bar).trim_end());
+            output.push_str(" |");
+        }
+        output.push('\n');
+    }
+
+    output.push_str(&format!("| **{}** |", lang.pick("总计", "Total")));
+    for (_, stats) in levels {
+        output.push_str(&format!(" **{}** |", stats.total));
+    }
+    output.push('\n');
+    output
+}
+
+/// 循环头的对齐字节数（GCC/Clang 在 AArch64 上把热循环对齐到 16 字节）
+pub const LOOP_ALIGNMENT: u64 = 16;
+
+/// 一个循环头的对齐情况
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LoopHeader {
+    /// 循环头地址
+    pub address: u64,
+    /// 是否对齐到 [`LOOP_ALIGNMENT`]
+    pub aligned: bool,
+    /// 紧邻循环头之前的 NOP 填充条数
+    pub padding: usize,
+}
+
+/// 条件跳转方向和循环头对齐的统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BranchStats {
+    /// 条件跳转数
+    pub conditional: usize,
+    /// 向后跳转的条件跳转（循环回边，静态预测为跳转）
+    pub backward: usize,
+    /// 向前跳转的条件跳转（静态预测为不跳转，顺序执行）
+    pub forward: usize,
+    /// 由前一条指令顺序执行落入的跳转目标数
+    pub fall_through: usize,
+    /// 循环头（按地址排序）
+    pub loop_headers: Vec<LoopHeader>,
+}
+
+impl BranchStats {
+    /// 统计函数条目中的跳转
+    pub fn from_entries(entries: &[DumpEntry]) -> Self {
+        let instructions: Vec<Instruction> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+        let mut stats = Self::default();
+        let mut targets = Vec::new();
+        for (i, inst) in instructions.iter().enumerate() {
+            let Some(target) = analysis::branch_target(inst) else {
+                continue;
+            };
+            if inst.instruction_type == InstructionType::BL {
+                continue;
+            }
+            if let Some(index) = instructions.iter().position(|x| x.address == target) {
+                targets.push(index);
+            }
+            let unconditional = inst.instruction_type == InstructionType::B && inst.condition.is_none();
+            if unconditional || !analysis::successors(&instructions, i).contains(&(i + 1)) {
+                continue;
+            }
+            stats.conditional += 1;
+            if target <= inst.address {
+                stats.backward += 1;
+            } else {
+                stats.forward += 1;
+            }
+        }
+        targets.sort();
+        targets.dedup();
+        stats.fall_through = targets
+            .iter()
+            .filter(|&&j| j > 0 && analysis::successors(&instructions, j - 1).contains(&j))
+            .count();
+
+        let mut heads: Vec<usize> = analysis::find_loops(&instructions).iter().map(|l| l.head).collect();
+        heads.dedup();
+        stats.loop_headers = heads
+            .into_iter()
+            .map(|head| LoopHeader {
+                address: instructions[head].address,
+                aligned: instructions[head].address.is_multiple_of(LOOP_ALIGNMENT),
+                padding: instructions[..head]
+                    .iter()
+                    .rev()
+                    .take_while(|x| x.instruction_type == InstructionType::NOP)
+                    .count(),
+            })
+            .collect();
+        stats
+    }
+
+    /// 对齐到 [`LOOP_ALIGNMENT`] 的循环头数
+    pub fn aligned_headers(&self) -> usize {
+        self.loop_headers.iter().filter(|h| h.aligned).count()
+    }
+}
+
+/// 跳转统计表格的一行：中文名称、英文名称和取值
+type BranchRow = (&'static str, &'static str, fn(&BranchStats) -> String);
+
+/// 生成各优化级别的跳转统计对比表格（Markdown 表格）
+pub fn branch_table(levels: &[(&str, &BranchStats)], lang: Lang) -> String {
+    let mut output = format!("| {} |", lang.pick("跳转", "Branches"));
+    for (level, _) in levels {
+        output.push_str(&format!(" {} |", level));
+    }
+    output.push_str("\n|------|");
+    output.push_str(&"------|".repeat(levels.len()));
+    output.push('\n');
+
+    let rows: [BranchRow; 6] = [
+        ("条件跳转", "Conditional branches", |s| s.conditional.to_string()),
+        ("向后跳转（循环）", "Taken backwards (loop)", |s| s.backward.to_string()),
+        ("向前跳转", "Forward", |s| s.forward.to_string()),
+        ("顺序落入的跳转目标", "Fall-through into branch targets", |s| s.fall_through.to_string()),
+        ("循环头已对齐", "Loop headers aligned", |s| format!("{}/{}", s.aligned_headers(), s.loop_headers.len())),
+        ("循环头", "Loop headers", |s| {
+            s.loop_headers
+                .iter()
+                .map(|h| match (h.aligned, h.padding) {
+                    (true, 0) => format!("0x{:x} ✓", h.address),
+                    (true, n) => format!("0x{:x} ✓ (NOP ×{})", h.address, n),
+                    (false, _) => format!("0x{:x} ✗", h.address),
+                })
+                .collect::<Vec<_>>()
+                .join("<br>")
+        }),
+    ];
+    for (zh, en, value) in rows {
+        output.push_str(&format!("| {} |", lang.pick(zh, en)));
+        for (_, stats) in levels {
+            output.push_str(format!(" {}", value(stats)).trim_end());
+            output.push_str(" |");
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// 函数的体积统计（比较各级别的代码膨胀）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SizeMetrics {
+    /// 字节数（最后一条指令地址 − 第一条指令地址 + 4）
+    pub bytes: u64,
+    /// 指令条数
+    pub instructions: usize,
+    /// 基本块数（按可解析的指令划分）
+    pub blocks: usize,
+    /// 跳转指令数（不含调用）
+    pub branches: usize,
+}
+
+impl SizeMetrics {
+    /// 统计函数条目
+    pub fn from_entries(entries: &[DumpEntry]) -> Self {
+        let addresses: Vec<u64> = entries
+            .iter()
+            .filter(|e| !e.asm_instruction.is_empty())
+            .filter_map(|e| u64::from_str_radix(&e.address, 16).ok())
+            .collect();
+        let bytes = match (addresses.iter().min(), addresses.iter().max()) {
+            (Some(first), Some(last)) => last - first + 4,
+            _ => 0,
+        };
+        let stats = Stats::from_entries(entries);
+        let instructions: Vec<Instruction> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+        Self {
+            bytes,
+            instructions: stats.total,
+            blocks: analysis::block_leaders(&instructions).len(),
+            branches: stats.count(Category::Branch),
+        }
+    }
+}
+
+/// 体积统计表格的一行：中文名称、英文名称和取值
+type SizeRow = (&'static str, &'static str, fn(&SizeMetrics) -> u64);
+
+/// 生成各优化级别的体积对比表格（Markdown 表格），第一个级别之后的各列附上相对第一个级别的百分比变化
+pub fn size_table(levels: &[(&str, &SizeMetrics)], lang: Lang) -> String {
+    let mut output = format!("| {} |", lang.pick("指标", "Metric"));
+    for (level, _) in levels {
+        output.push_str(&format!(" {} |", level));
+    }
+    output.push_str("\n|------|");
+    output.push_str(&"------|".repeat(levels.len()));
+    output.push('\n');
+
+    let rows: [SizeRow; 4] = [
+        ("字节数", "Bytes", |m| m.bytes),
+        ("指令数", "Instructions", |m| m.instructions as u64),
+        ("基本块", "Basic blocks", |m| m.blocks as u64),
+        ("跳转", "Branches", |m| m.branches as u64),
+    ];
+    for (zh, en, value) in rows {
+        output.push_str(&format!("| {} |", lang.pick(zh, en)));
+        let base = levels.first().map(|(_, m)| value(m)).unwrap_or(0);
+        for (i, (_, metrics)) in levels.iter().enumerate() {
+            let current = value(metrics);
+            match percent_change(base, current) {
+                Some(delta) if i > 0 => output.push_str(&format!(" {} ({}) |", current, delta)),
+                _ => output.push_str(&format!(" {} |", current)),
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// 相对基准的百分比变化（`-28.6%`、`+12.5%`）；基准为 0 时返回 None
+fn percent_change(base: u64, current: u64) -> Option<String> {
+    if base == 0 {
+        return None;
+    }
+    let delta = (current as f64 - base as f64) / base as f64 * 100.0;
+    Some(if delta == 0.0 { String::from("0%") } else { format!("{:+.1}%", delta) })
+}
+
+/// 单个函数的概况
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionSummary {
+    /// 函数名
+    pub name: String,
+    /// 指令条数
+    pub instructions: usize,
+    /// 机器码字节数
+    pub bytes: usize,
+    /// 跳转指令数
+    pub branches: usize,
+    /// 调用指令数
+    pub calls: usize,
+    /// 是否使用 SIMD 指令
+    pub simd: bool,
+    /// 是否使用原子/独占访问指令
+    pub atomics: bool,
+    /// 推断的 C 签名
+    pub signature: Option<Signature>,
+}
+
+impl FunctionSummary {
+    /// 统计函数条目
+    pub fn from_entries(name: &str, entries: &[DumpEntry]) -> Self {
+        let stats = Stats::from_entries(entries);
+        let bytes = entries
+            .iter()
+            .filter(|e| !e.asm_instruction.is_empty())
+            .map(|e| e.machine_code.chars().filter(|c| c.is_ascii_hexdigit()).count() / 2)
+            .sum();
+        Self {
+            name: name.to_string(),
+            instructions: stats.total,
+            bytes,
+            branches: stats.count(Category::Branch),
+            calls: stats.count(Category::Call),
+            simd: stats.count(Category::Simd) > 0,
+            atomics: stats.count(Category::Atomic) > 0,
+            signature: Signature::infer(name, entries, Lang::Zh),
+        }
+    }
+}
+
+/// 整个 dump 文件的概况
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FileSummary {
+    /// 每个函数的概况（按 dump 中的顺序）
+    pub functions: Vec<FunctionSummary>,
+}
+
+impl FileSummary {
+    /// 遍历 dump 中的所有函数（无法提取的 PLT 桩等跳过）
+    ///
+    /// 返回值直接来自其他函数的签名用该函数的返回类型修正
+    pub fn from_parser(parser: &ObjdumpParser) -> Result<Self> {
+        let mut functions: Vec<FunctionSummary> = parser
+            .list_functions()?
+            .iter()
+            .filter_map(|name| {
+                let entries = parser.extract_function_data(name).ok()?;
+                Some(FunctionSummary::from_entries(name, &entries))
+            })
+            .collect();
+        let signatures: Vec<Signature> = functions.iter().filter_map(|f| f.signature.clone()).collect();
+        for signature in functions.iter_mut().filter_map(|f| f.signature.as_mut()) {
+            if let Some(callee) = signatures.iter().find(|s| signature.return_callee.as_ref() == Some(&s.name)) {
+                signature.resolve_return(callee);
+            }
+        }
+        Ok(Self { functions })
+    }
+
+    /// 输出 Markdown 表格
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        output.push_str("| 函数 | 指令数 | 字节数 | 跳转 | 调用 | SIMD | 原子操作 | 推断签名 (置信度) |\n");
+        output.push_str("|------|--------|--------|------|------|------|----------|-------------------|\n");
+        let mark = |used: bool| if used { "✓" } else { "" };
+        for f in &self.functions {
+            let signature = f
+                .signature
+                .as_ref()
+                .map(|s| format!("`{}` ({})", s.to_c(), s.confidence().name(Lang::Zh)))
+                .unwrap_or_default();
+            output.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                f.name, f.instructions, f.bytes, f.branches, f.calls, mark(f.simd), mark(f.atomics), signature
+            ));
+        }
+        output.push_str(&format!(
+            "| **总计 ({} 个函数)** | **{}** | **{}** | **{}** | **{}** | | | |\n",
+            self.functions.len(),
+            self.functions.iter().map(|f| f.instructions).sum::<usize>(),
+            self.functions.iter().map(|f| f.bytes).sum::<usize>(),
+            self.functions.iter().map(|f| f.branches).sum::<usize>(),
+            self.functions.iter().map(|f| f.calls).sum::<usize>(),
+        ));
+        output
+    }
+
+    /// 输出 JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// 函数清单的排序方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListOrder {
+    /// 按 dump 中的顺序（地址）
+    #[default]
+    Address,
+    /// 按机器码字节数从大到小
+    Size,
+    /// 按名称
+    Name,
+}
+
+impl ListOrder {
+    /// 解析 `--sort` 的取值
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.to_lowercase().as_str() {
+            "address" | "addr" => Ok(Self::Address),
+            "size" => Ok(Self::Size),
+            "name" => Ok(Self::Name),
+            _ => Err(crate::error::InterpreterError::ParseError(format!(
+                "未知的排序方式: {} (可选: address, size, name)",
+                text
+            ))),
+        }
+    }
+}
+
+/// 函数清单中的一项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListedFunction {
+    /// 函数名
+    pub name: String,
+    /// 起始地址（十六进制字符串，与调用图的 JSON 一致）
+    pub address: String,
+    /// 机器码字节数
+    pub bytes: usize,
+    /// 指令条数
+    pub instructions: usize,
+}
+
+/// dump 文件中的函数清单
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FunctionList {
+    /// 函数（默认按 dump 中的顺序）
+    pub functions: Vec<ListedFunction>,
+}
+
+impl FunctionList {
+    /// 列出 dump 中的所有函数（无法提取的 PLT 桩等跳过）
+    pub fn from_parser(parser: &ObjdumpParser) -> Result<Self> {
+        let functions = parser
+            .list_functions()?
+            .into_iter()
+            .filter_map(|name| {
+                let entries = parser.extract_function_data(&name).ok()?;
+                let summary = FunctionSummary::from_entries(&name, &entries);
+                let address = parser.function_address(&name).unwrap_or(0);
+                Some(ListedFunction {
+                    address: format!("0x{:x}", address),
+                    bytes: summary.bytes,
+                    instructions: summary.instructions,
+                    name,
+                })
+            })
+            .collect();
+        Ok(Self { functions })
+    }
+
+    /// 只保留名称匹配正则表达式的函数
+    pub fn retain_matching(&mut self, pattern: &regex::Regex) {
+        self.functions.retain(|f| pattern.is_match(&f.name));
+    }
+
+    /// 按指定方式排序（相同大小的函数按名称）
+    pub fn sort(&mut self, order: ListOrder) {
+        match order {
+            ListOrder::Address => {}
+            ListOrder::Size => self.functions.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name))),
+            ListOrder::Name => self.functions.sort_by(|a, b| a.name.cmp(&b.name)),
+        }
+    }
+
+    /// 对齐的纯文本表格（终端输出）
+    pub fn to_text(&self) -> String {
+        // 中文表头每个字占两列，格式宽度按字符数计算，表头的宽度相应减少
+        let mut output = format!("{:>12}  {:>8}  {:>8}  {}\n", "地址", "字节数", "指令数", "函数");
+        for f in &self.functions {
+            output.push_str(&format!("{:>14}  {:>11}  {:>11}  {}\n", f.address, f.bytes, f.instructions, f.name));
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(Category::of("ldr\tw0, [sp, #12]"), Category::Load);
+        assert_eq!(Category::of("stp x29, x30, [sp, #-16]!"), Category::Store);
+        assert_eq!(Category::of("b.ne 10 <sum+0x10>"), Category::Branch);
+        assert_eq!(Category::of("add v0.4s, v0.4s, v1.4s"), Category::Simd);
+        assert_eq!(Category::of("ld1 {v0.4s}, [x0]"), Category::Simd);
+        assert_eq!(Category::of("ldaxr w1, [x0]"), Category::Atomic);
+        assert_eq!(Category::of("fadd d0, d0, d1"), Category::FloatingPoint);
+        assert_eq!(Category::of("madd w0, w1, w2, w0"), Category::Arithmetic);
+        assert_eq!(Category::of("cset w0, eq"), Category::Conditional);
+    }
+
+    #[test]
+    fn test_comparison_table() {
+        let o0 = Stats {
+            total: 4,
+            counts: BTreeMap::from([(Category::Load, 2), (Category::Store, 2)]),
+        };
+        let o2 = Stats { total: 1, counts: BTreeMap::from([(Category::Load, 1)]) };
+
+        let table = comparison_table(&[("O0", &o0), ("O2", &o2)], Lang::Zh);
+        assert!(table.starts_with("| 类别 | O0 | O2 |"));
+        assert!(table.contains("| 存储 | 2 (50%) ████████████████████ | 0 (0%) |"));
+        assert!(table.contains("| **总计** | **4** | **1** |"));
+    }
+
+    #[test]
+    fn test_branch_stats() {
+        let dump = r#"
+0000000000000000 <count>:
+   0:   52800002    mov w2, #0x0
+   4:   34000120    cbz w0, 28 <count+0x28>
+   8:   d503201f    nop
+   c:   d503201f    nop
+  10:   b8404403    ldr w3, [x0], #4
+  14:   7100007f    cmp w3, #0x0
+  18:   54000040    b.eq 20 <count+0x20>
+  1c:   11000442    add w2, w2, #0x1
+  20:   f100043f    cmp x1, #0x1
+  24:   54ffff61    b.ne 10 <count+0x10>
+  28:   2a0203e0    mov w0, w2
+  2c:   d65f03c0    ret
+"#;
+        let entries = ObjdumpParser::new(dump.to_string()).extract_function_data("count").unwrap();
+        let stats = BranchStats::from_entries(&entries);
+        assert_eq!((stats.conditional, stats.backward, stats.forward), (3, 1, 2));
+        // 0x10、0x20 和 0x28 都由前一条指令顺序落入
+        assert_eq!(stats.fall_through, 3);
+        assert_eq!(stats.loop_headers, vec![LoopHeader { address: 0x10, aligned: true, padding: 2 }]);
+
+        let table = branch_table(&[("O2", &stats)], Lang::Zh);
+        assert!(table.contains("| 循环头已对齐 | 1/1 |"));
+        assert!(table.contains("| 循环头 | 0x10 ✓ (NOP ×2) |"));
+    }
+
+    #[test]
+    fn test_size_metrics() {
+        let dump = r#"
+0000000000000000 <count>:
+   0:   34000080    cbz w0, 10 <count+0x10>
+   4:   b8404403    ldr w3, [x0], #4
+   8:   f100043f    cmp x1, #0x1
+   c:   54ffffc1    b.ne 4 <count+0x4>
+  10:   d65f03c0    ret
+"#;
+        let entries = ObjdumpParser::new(dump.to_string()).extract_function_data("count").unwrap();
+        let o2 = SizeMetrics::from_entries(&entries);
+        assert_eq!(o2, SizeMetrics { bytes: 20, instructions: 5, blocks: 3, branches: 2 });
+
+        let o0 = SizeMetrics { bytes: 28, instructions: 7, blocks: 3, branches: 1 };
+        let table = size_table(&[("O0", &o0), ("O2", &o2)], Lang::Zh);
+        assert!(table.starts_with("| 指标 | O0 | O2 |"));
+        assert!(table.contains("| 字节数 | 28 | 20 (-28.6%) |"));
+        assert!(table.contains("| 基本块 | 3 | 3 (0%) |"));
+        assert!(table.contains("| 跳转 | 1 | 2 (+100.0%) |"));
+    }
+
+    #[test]
+    fn test_file_summary() {
+        let dump = r#"
+0000000000000000 <add4>:
+   0:   4ea18400    add v0.4s, v0.4s, v1.4s
+   4:   d65f03c0    ret
+
+0000000000000010 <main>:
+  10:   94000000    bl 0 <add4>
+  14:   34000040    cbz w0, 1c <main+0xc>
+  18:   d65f03c0    ret
+"#;
+        let summary = FileSummary::from_parser(&ObjdumpParser::new(dump.to_string())).unwrap();
+        assert_eq!(summary.functions.len(), 2);
+        assert!(summary.functions[0].simd);
+        assert_eq!(summary.functions[1].bytes, 12);
+        assert_eq!((summary.functions[1].branches, summary.functions[1].calls), (1, 1));
+        assert!(summary.to_markdown().contains("| **总计 (2 个函数)** | **5** | **20** |"));
+    }
+
+    #[test]
+    fn test_function_list() {
+        let dump = r#"
+0000000000000000 <add4>:
+   0:   4ea18400    add v0.4s, v0.4s, v1.4s
+   4:   d65f03c0    ret
+
+0000000000000010 <main>:
+  10:   94000000    bl 0 <add4>
+  14:   34000040    cbz w0, 1c <main+0xc>
+  18:   d65f03c0    ret
+"#;
+        let mut list = FunctionList::from_parser(&ObjdumpParser::new(dump.to_string())).unwrap();
+        assert_eq!(
+            list.functions[1],
+            ListedFunction { name: "main".to_string(), address: "0x10".to_string(), bytes: 12, instructions: 3 }
+        );
+
+        list.sort(ListOrder::parse("size").unwrap());
+        assert_eq!(list.functions[0].name, "main");
+        list.sort(ListOrder::Name);
+        assert_eq!(list.functions[0].name, "add4");
+        list.retain_matching(&regex::Regex::new("^ma").unwrap());
+        assert_eq!(list.functions.len(), 1);
+        assert!(list.to_text().ends_with("          0x10           12            3  main\n"));
+        assert!(ListOrder::parse("calls").is_err());
+    }
+}
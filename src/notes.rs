@@ -0,0 +1,150 @@
+//! 用户备注文件
+//!
+//! 从 YAML 文件加载函数级和地址级的人工备注，在生成报告时以注释行的形式合并进表格，
+//! 使人工审阅结论与生成的分析结果放在一起，并在重新生成报告后依然保留。
+//!
+//! 文件格式示例:
+//!
+//! ```yaml
+//! functions:
+//!   Matrix_add: "外层循环已被 O2 展开"
+//! addresses:
+//!   "0x40": "这里是循环回边"
+//! instructions:
+//!   "Matrix_add+0x1c": "按指令标识符引用，不受函数在二进制中位置变化的影响"
+//! ```
+
+use crate::objdump::DumpEntry;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 备注集合
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Notes {
+    /// 函数名 -> 备注
+    #[serde(default)]
+    pub functions: HashMap<String, String>,
+    /// 指令地址 -> 备注
+    #[serde(default)]
+    pub addresses: HashMap<String, String>,
+    /// 指令标识符（`函数名+0x偏移`）-> 备注
+    #[serde(default)]
+    pub instructions: HashMap<String, String>,
+}
+
+impl Notes {
+    /// 从 YAML 字符串解析
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let mut notes: Notes = serde_yaml::from_str(content)
+            .context("备注文件不是有效的 YAML")?;
+        notes.addresses = notes
+            .addresses
+            .into_iter()
+            .map(|(addr, note)| (Self::normalize_address(&addr), note))
+            .collect();
+        Ok(notes)
+    }
+
+    /// 从文件加载
+    pub fn load_from_file(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read {}", path))?;
+        Self::from_yaml(&content)
+    }
+
+    /// 将备注作为注释行合并到函数的条目列表中
+    ///
+    /// 函数备注插入到最前面，地址备注插入到对应指令之后
+    pub fn apply(&self, function_name: &str, entries: &mut Vec<DumpEntry>) {
+        let mut merged = Vec::with_capacity(entries.len());
+
+        if let Some(note) = self.functions.get(function_name) {
+            merged.push(Self::note_entry(format!("📝 函数备注: {}", note)));
+        }
+
+        for entry in entries.drain(..) {
+            let note = if entry.address.is_empty() {
+                None
+            } else {
+                self.instructions
+                    .get(&entry.id)
+                    .or_else(|| self.addresses.get(&Self::normalize_address(&entry.address)))
+            };
+            let address = entry.address.clone();
+            merged.push(entry);
+            if let Some(note) = note {
+                merged.push(Self::note_entry(format!("📝 备注 ({}): {}", address, note)));
+            }
+        }
+
+        *entries = merged;
+    }
+
+    /// 构造注释行（汇编指令为空的条目会被表格按提示信息渲染）
+    fn note_entry(text: String) -> DumpEntry {
+        DumpEntry {
+            id: String::new(),
+            c_line: None,
+            c_code: text,
+            address: String::new(),
+            machine_code: String::new(),
+            asm_instruction: String::new(),
+            parsed_instruction: None,
+        }
+    }
+
+    /// 统一地址格式：去掉 0x 前缀和前导零，转为小写
+    fn normalize_address(address: &str) -> String {
+        let lower = address.trim().to_lowercase();
+        let hex = lower.strip_prefix("0x").unwrap_or(&lower);
+        let trimmed = hex.trim_start_matches('0');
+        if trimmed.is_empty() {
+            String::from("0")
+        } else {
+            trimmed.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(address: &str, asm: &str) -> DumpEntry {
+        DumpEntry {
+            id: format!("foo+0x{}", address),
+            c_line: None,
+            c_code: String::new(),
+            address: address.to_string(),
+            machine_code: String::new(),
+            asm_instruction: asm.to_string(),
+            parsed_instruction: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_notes() {
+        let yaml = "functions:\n  foo: 入口函数\naddresses:\n  \"0x04\": 循环回边\n";
+        let notes = Notes::from_yaml(yaml).unwrap();
+
+        let mut entries = vec![entry("0", "nop"), entry("4", "ret")];
+        notes.apply("foo", &mut entries);
+
+        assert_eq!(entries.len(), 4);
+        assert!(entries[0].c_code.contains("入口函数"));
+        assert_eq!(entries[2].asm_instruction, "ret");
+        assert!(entries[3].c_code.contains("循环回边"));
+    }
+
+    #[test]
+    fn test_apply_notes_by_id() {
+        let notes = Notes::from_yaml("instructions:\n  foo+0x0: 入口\n").unwrap();
+
+        let mut entries = vec![entry("0", "nop"), entry("4", "ret")];
+        notes.apply("foo", &mut entries);
+
+        assert_eq!(entries.len(), 3);
+        assert!(entries[1].c_code.contains("入口"));
+    }
+}
@@ -3,13 +3,46 @@
 //! 解析 objdump 格式的汇编文件，提取函数、C代码和汇编指令
 
 use crate::instruction::Instruction;
+use crate::diagnostics::{ParseDiagnostics, ParseIssue};
 use crate::error::{Result, InterpreterError};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
 use regex::Regex;
 
+/// 定义返回正则表达式的函数，表达式在进程内只编译一次
+macro_rules! regex {
+    ($name:ident, $pattern:expr) => {
+        fn $name() -> &'static Regex {
+            static PATTERN: OnceLock<Regex> = OnceLock::new();
+            PATTERN.get_or_init(|| Regex::new($pattern).unwrap())
+        }
+    };
+}
+
+// 函数头：`0000000000400640 <main>:`（地址、函数名）
+regex!(header_pattern, r"^([0-9a-f]+)\s+<([^>]+)>:");
+// 节的开始
+regex!(section_pattern, r"^(Disassembly|Contents) of section");
+// 调用点注释：`bl 400560 <printf@plt>`
+regex!(call_site_pattern, r"\s([0-9a-f]+)\s+<([^>+]+)>");
+// 指令行的开头（地址）
+regex!(address_pattern, r"^\s*[0-9a-f]+:\s");
+// 完整的指令行：地址、机器码、汇编文本。机器码既可以是 GNU objdump 的整字形式（d65f03c0），
+// 也可以是 llvm-objdump 的字节形式（c0 03 5f d6），Thumb-2 的 32 位指令则是两个半字（f240 0300）
+regex!(asm_pattern, r"^\s*([0-9a-f]+):\s+((?:[0-9a-f]{2} ){3}[0-9a-f]{2}|[0-9a-f]{4} [0-9a-f]{4}|[0-9a-f]+)\s+(.+)$");
+// 编译器拆出的内部函数：`<compute.part.0>`
+regex!(inline_pattern, r"<([^>]+\.part\.\d+)>");
+// `objdump -l` 的源码位置行
+regex!(source_pattern, r"^/.*:\d+");
+// `Contents of section` 的数据行
+regex!(row_pattern, r"^ ([0-9a-f]+) ");
+
 /// objdump 文件中的一条记录
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DumpEntry {
+    /// 稳定的指令标识符（`函数名+0x偏移`，提示行为空）
+    pub id: String,
     /// C 源代码行号
     pub c_line: Option<usize>,
     /// C 源代码
@@ -24,98 +57,590 @@ pub struct DumpEntry {
     pub parsed_instruction: Option<Instruction>,
 }
 
+impl DumpEntry {
+    /// 直接调用（BL 或尾调用 B 到函数入口）的目标函数名
+    ///
+    /// 跳转到函数内部偏移（如 `b 40 <sum+0x40>`）不算调用
+    pub fn call_target(&self) -> Option<String> {
+        use crate::instruction::Operand;
+
+        let mut parts = self.asm_instruction.splitn(2, char::is_whitespace);
+        let mnemonic = parts.next()?;
+        if mnemonic != "bl" && mnemonic != "b" {
+            return None;
+        }
+        // 优先使用已由符号表补全的操作数
+        let label = match self.parsed_instruction.as_ref().and_then(|i| i.operands.first()) {
+            Some(Operand::Label(label)) => label.as_str(),
+            _ => parts.next()?.trim(),
+        };
+        let symbol = target_symbol(label)?;
+        if symbol.contains('+') {
+            None
+        } else {
+            Some(symbol.to_string())
+        }
+    }
+}
+
+/// 生成稳定的指令标识符：`函数名+0x偏移`
+///
+/// 偏移相对于函数起始地址，因此函数在二进制中整体移动后标识符保持不变
+pub fn instruction_id(function: &str, offset: u64) -> String {
+    format!("{}+0x{:x}", function, offset)
+}
+
+/// 符号表（地址 -> 符号名）
+///
+/// 由函数头 `0000000000400560 <printf@plt>:` 和调用点注释 `bl 400560 <printf@plt>` 构建
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: BTreeMap<u64, String>,
+}
+
+impl SymbolTable {
+    /// 创建空符号表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加符号（同一地址保留最先出现的名称）
+    pub fn insert(&mut self, address: u64, name: &str) {
+        self.symbols.entry(address).or_insert_with(|| name.to_string());
+    }
+
+    /// 精确查找地址对应的符号
+    pub fn get(&self, address: u64) -> Option<&str> {
+        self.symbols.get(&address).map(|s| s.as_str())
+    }
+
+    /// 将地址解析为 `符号` 或 `符号+0x偏移` 形式
+    pub fn resolve(&self, address: u64) -> Option<String> {
+        let (base, name) = self.symbols.range(..=address).next_back()?;
+        if *base == address {
+            Some(name.clone())
+        } else {
+            Some(format!("{}+0x{:x}", name, address - base))
+        }
+    }
+
+    /// 查找符号的地址
+    pub fn address_of(&self, name: &str) -> Option<u64> {
+        self.symbols.iter().find(|(_, n)| n.as_str() == name).map(|(addr, _)| *addr)
+    }
+
+    /// 按地址顺序遍历所有符号
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &str)> {
+        self.symbols.iter().map(|(addr, name)| (*addr, name.as_str()))
+    }
+
+    /// 符号数量
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+/// 从分支目标标签中提取符号名（`400560 <printf@plt>` -> `printf@plt`）
+pub fn target_symbol(label: &str) -> Option<&str> {
+    let start = label.find('<')?;
+    let end = label[start..].find('>')? + start;
+    Some(&label[start + 1..end])
+}
+
+/// 是否为编译器从其他函数中拆分出来的辅助函数
+///
+/// 包括部分内联后剩下的 `.part.N`、冷路径 `.cold`、改写了参数的 `.isra.N`/`.constprop.N`
+/// 和共享代码 `OUTLINED_FUNCTION_N`
+pub fn is_compiler_helper(name: &str) -> bool {
+    name.starts_with(crate::outline::OUTLINED_PREFIX)
+        || [".part.", ".cold", ".isra.", ".constprop."].iter().any(|suffix| name.contains(suffix))
+}
+
+/// 默认调用的 objdump 程序
+pub const DEFAULT_OBJDUMP: &str = "objdump";
+
 /// objdump 文件解析器
 pub struct ObjdumpParser {
     /// 行数据
     lines: Vec<String>,
+    /// 符号表
+    symbols: SymbolTable,
+    /// 是否包含交织的 C 源码（`objdump -S`），`objdump -d` 的输出为 false
+    has_source: bool,
+    /// 节的原始内容（`objdump -s` 输出的 `Contents of section`，按行起始地址）
+    data: BTreeMap<u64, Vec<u8>>,
+    /// 是否为 32 位 ARM（A32/T32）目标的 dump
+    arm32: bool,
+    /// 函数索引（按在文件中出现的顺序），构造时建立一次，避免每次查询都扫描全文
+    functions: Vec<FunctionRange>,
+    /// 函数名 -> `functions` 中第一次出现的下标
+    function_index: HashMap<String, usize>,
+    /// `lines` 之前被跳过的行数（流式打开时不为 0），用于报告原文件中的行号
+    first_line: usize,
+    /// 整个 dump 中所有函数头的名称（流式打开时也包含未保留的函数），用于建议相近的函数名
+    function_names: Vec<String>,
+}
+
+/// 索引中的一个函数：名称、起始地址和所占的行（含函数头，含两端）
+#[derive(Debug, Clone)]
+struct FunctionRange {
+    name: String,
+    address: u64,
+    start: usize,
+    end: usize,
+}
+
+/// 逐行扫描整个 dump 时收集的全局信息：符号表、是否带源码、节内容和目标架构
+///
+/// `ObjdumpParser::new` 与 `open_streaming` 共用，后者不需要在内存中保留全部行
+struct DumpScanner {
+    symbols: SymbolTable,
+    has_source: bool,
+    data: BTreeMap<u64, Vec<u8>>,
+    /// 第一行 `file format` 决定，没有时为 None
+    arm32: Option<bool>,
+    /// 源码检测：当前是否位于函数体内
+    in_function: bool,
+    /// 节内容解析：当前是否位于 `Contents of section` 之后
+    in_contents: bool,
+    /// 函数头中的函数名（按出现顺序）
+    function_names: Vec<String>,
+}
+
+impl DumpScanner {
+    fn new() -> Self {
+        Self {
+            symbols: SymbolTable::new(),
+            has_source: false,
+            data: BTreeMap::new(),
+            arm32: None,
+            in_function: false,
+            in_contents: false,
+            function_names: Vec::new(),
+        }
+    }
+
+    /// 处理一行
+    fn feed(&mut self, line: &str) {
+        if self.arm32.is_none() {
+            if let Some((_, format)) = line.split_once("file format") {
+                self.arm32 = Some(crate::arm32::is_arm_format(format));
+            }
+        }
+        self.scan_symbol(line);
+        self.scan_source(line);
+        self.scan_data(line);
+    }
+
+    /// 从函数头和调用点注释构建符号表
+    fn scan_symbol(&mut self, line: &str) {
+        let header = header_pattern().captures(line);
+        if let Some(caps) = &header {
+            self.function_names.push(caps[2].to_string());
+        }
+        if let Some(caps) = header.or_else(|| call_site_pattern().captures(line)) {
+            if let Ok(addr) = u64::from_str_radix(&caps[1], 16) {
+                self.symbols.insert(addr, &caps[2]);
+            }
+        }
+    }
+
+    /// 检测函数体内是否存在汇编指令以外的源码行
+    fn scan_source(&mut self, line: &str) {
+        let cleaned = line.trim();
+        if header_pattern().is_match(line) {
+            self.in_function = true;
+        } else if cleaned.starts_with("Disassembly of section") || cleaned.starts_with("Contents of section") {
+            self.in_function = false;
+        } else if self.in_function
+            && !cleaned.is_empty()
+            && cleaned != "..."
+            && !address_pattern().is_match(line)
+        {
+            self.has_source = true;
+        }
+    }
+
+    /// 解析 `Contents of section` 部分的十六进制内容
+    ///
+    /// 每行为起始地址、最多 4 组（共 16 字节）按内存顺序排列的十六进制数和 ASCII 形式：
+    /// ` 4007a0 00020400 02040000 00000000 00000000  ................`
+    fn scan_data(&mut self, line: &str) {
+        if line.starts_with("Contents of section") {
+            self.in_contents = true;
+            return;
+        }
+        let Some(caps) = row_pattern().captures(line).filter(|_| self.in_contents) else {
+            self.in_contents = false;
+            return;
+        };
+        let Ok(address) = u64::from_str_radix(&caps[1], 16) else {
+            return;
+        };
+        // 十六进制部分与 ASCII 形式之间以两个空格分隔
+        let rest = &line[caps[0].len()..];
+        let hex: String = rest.split("  ").next().unwrap_or("").split(' ').collect();
+        let bytes: Option<Vec<u8>> = (0..hex.len() / 2)
+            .map(|i| u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok())
+            .collect();
+        if let Some(bytes) = bytes.filter(|b| !b.is_empty()) {
+            self.data.insert(address, bytes);
+        }
+    }
+}
+
+/// 找不到函数时最多给出的候选名称个数
+const MAX_SUGGESTIONS: usize = 3;
+
+/// 两个字符串之间的编辑距离（Levenshtein）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// 表示标准输入的 dump 路径，用于 `objdump -d a.o | alaz ... -` 这样的管道
+pub const STDIN_PATH: &str = "-";
+
+/// 打开 dump 文件，路径为 [`STDIN_PATH`] 时返回标准输入
+fn open_dump(path: &str) -> Result<Box<dyn std::io::BufRead>> {
+    if path == STDIN_PATH {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+    Ok(Box::new(std::io::BufReader::new(std::fs::File::open(path)?)))
+}
+
+/// 只扫描函数头行（`0000000000400640 <main>:`）列出 dump 中的函数名（`path` 可以为 `-`）
+///
+/// 不解析指令也不保留文件内容，供 shell 补全在每次按 TAB 时调用
+pub fn scan_function_names(path: &str) -> Result<Vec<String>> {
+    use std::io::BufRead;
+
+    let mut reader = open_dump(path)?;
+    let mut names = Vec::new();
+    let mut buffer = Vec::new();
+    while reader.read_until(b'\n', &mut buffer)? > 0 {
+        // 函数头以 `>:` 结尾，先按字节过滤再匹配
+        if buffer.trim_ascii_end().ends_with(b">:") {
+            if let Some(caps) = header_pattern().captures(&String::from_utf8_lossy(&buffer)) {
+                names.push(caps[2].to_string());
+            }
+        }
+        buffer.clear();
+    }
+    Ok(names)
 }
 
 impl ObjdumpParser {
     /// 创建新的解析器
     pub fn new(content: String) -> Self {
-        let lines = content.lines().map(|s| s.to_string()).collect();
-        Self { lines }
+        let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+        let mut scanner = DumpScanner::new();
+        for line in &lines {
+            scanner.feed(line);
+        }
+        Self::from_parts(lines, scanner, 0)
+    }
+
+    /// 以流式方式打开 dump，只在内存中保留 func_name 这一个函数的行（`path` 可以为 `-`）
+    ///
+    /// 逐行向前扫描文件：符号表、节内容等全局信息照常收集，其余函数的行读过即丢弃，
+    /// 适合几百 MB 的大型 dump。返回的解析器只认识这一个函数（`list_functions` 只列出它，
+    /// `extract_helpers` 找不到其他辅助函数）；dump 中没有该函数时 `extract_function_data` 报错
+    pub fn open_streaming(path: &str, func_name: &str) -> Result<Self> {
+        use std::io::BufRead;
+
+        let mut reader = open_dump(path)?;
+        let mut scanner = DumpScanner::new();
+        let mut lines = Vec::new();
+        let (mut capturing, mut done) = (false, false);
+        let mut skipped = 0;
+        let mut buffer = String::new();
+
+        while reader.read_line(&mut buffer)? > 0 {
+            let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            scanner.feed(line);
+            if !done {
+                let name = header_pattern().captures(line).map(|caps| caps[2].to_string());
+                if capturing && (name.is_some() || section_pattern().is_match(line)) {
+                    capturing = false;
+                    done = true;
+                } else if name.as_deref() == Some(func_name) {
+                    capturing = true;
+                }
+                if capturing {
+                    lines.push(line.to_string());
+                } else if !done {
+                    skipped += 1;
+                }
+            }
+            buffer.clear();
+        }
+        Ok(Self::from_parts(lines, scanner, skipped))
+    }
+
+    /// 由保留的行和全文扫描的结果组装解析器
+    fn from_parts(lines: Vec<String>, scanner: DumpScanner, first_line: usize) -> Self {
+        let functions = Self::build_function_index(&lines);
+        let mut function_index = HashMap::new();
+        for (i, function) in functions.iter().enumerate() {
+            function_index.entry(function.name.clone()).or_insert(i);
+        }
+        Self {
+            lines,
+            symbols: scanner.symbols,
+            has_source: scanner.has_source,
+            data: scanner.data,
+            arm32: scanner.arm32.unwrap_or(false),
+            functions,
+            function_index,
+            first_line,
+            function_names: scanner.function_names,
+        }
+    }
+
+    /// 一次扫描找出所有函数的范围：函数到下一个函数头或下一个节之前结束
+    fn build_function_index(lines: &[String]) -> Vec<FunctionRange> {
+        let mut functions: Vec<FunctionRange> = Vec::new();
+        let mut open = false;
+
+        for (i, line) in lines.iter().enumerate() {
+            let caps = header_pattern().captures(line);
+            if caps.is_some() || section_pattern().is_match(line) {
+                if let Some(last) = functions.last_mut().filter(|_| open) {
+                    last.end = i - 1;
+                }
+                open = false;
+            }
+            if let Some(caps) = caps {
+                functions.push(FunctionRange {
+                    name: caps[2].to_string(),
+                    address: u64::from_str_radix(&caps[1], 16).unwrap_or(0),
+                    start: i,
+                    end: lines.len() - 1,
+                });
+                open = true;
+            }
+        }
+        functions
     }
 
-    /// 从文件加载
+    /// 获取符号表
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
+
+    /// 是否包含交织的 C 源码
+    pub fn has_source(&self) -> bool {
+        self.has_source
+    }
+
+    /// 是否为 32 位 ARM（A32/T32）目标的 dump
+    ///
+    /// 这类 dump 的指令不经 AArch64 解析器解析（`parsed_instruction` 为 None），
+    /// 语义解释由 [`crate::arm32`] 按文本生成
+    pub fn is_arm32(&self) -> bool {
+        self.arm32
+    }
+
+    /// 读取从 address 开始的 len 字节节内容（dump 中没有这段数据时返回 None）
+    ///
+    /// 只有带 `-s` 选项生成的 dump（如 `objdump -d -s`）才包含节的内容
+    pub fn read_data(&self, address: u64, len: usize) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(len);
+        while bytes.len() < len {
+            let at = address + bytes.len() as u64;
+            let (start, chunk) = self.data.range(..=at).next_back()?;
+            let rest = chunk.get((at - start) as usize..).filter(|rest| !rest.is_empty())?;
+            bytes.extend(rest.iter().take(len - bytes.len()));
+        }
+        Some(bytes)
+    }
+
+    /// 从文件加载；路径为 [`STDIN_PATH`]（`-`）时从标准输入读取
     pub fn from_file(path: &str) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
+        use std::io::Read;
+
+        let mut content = String::new();
+        open_dump(path)?.read_to_string(&mut content)?;
         Ok(Self::new(content))
     }
 
-    /// 查找函数的起始和结束行
-    pub fn find_function(&self, func_name: &str) -> Option<(usize, usize)> {
-        let func_pattern = Regex::new(&format!(r"^[0-9a-f]+\s+<{}>:", regex::escape(func_name)))
-            .ok()?;
+    /// 调用 objdump 反汇编目标文件（`objdump -d -S`），直接解析其输出
+    ///
+    /// `objdump` 为程序名或路径，交叉编译的目标文件通常需要 `aarch64-linux-gnu-objdump`
+    pub fn from_object_file(path: &str, objdump: &str) -> Result<Self> {
+        Ok(Self::new(Self::run_objdump(path, objdump)?))
+    }
 
-        let mut start_line = None;
+    /// 运行 `objdump -d -S` 并返回经过格式检查的输出
+    pub fn run_objdump(path: &str, objdump: &str) -> Result<String> {
+        let output = std::process::Command::new(objdump)
+            .args(["-d", "-S", path])
+            .output()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => InterpreterError::ExecutionError(format!(
+                    "找不到 objdump 程序 '{}'，请安装 binutils 或通过 --objdump-path 指定 (如 aarch64-linux-gnu-objdump)",
+                    objdump
+                )),
+                _ => InterpreterError::IoError(e),
+            })?;
 
-        // 查找函数开始
-        for (i, line) in self.lines.iter().enumerate() {
-            if func_pattern.is_match(line) {
-                start_line = Some(i);
-                break;
-            }
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(InterpreterError::ExecutionError(format!(
+                "{} 无法反汇编 {}: {}",
+                objdump,
+                path,
+                stderr.trim()
+            )));
+        }
+
+        let content = String::from_utf8_lossy(&output.stdout).into_owned();
+        Self::check_format(&content, objdump)?;
+        Ok(content)
+    }
+
+    /// 检查 objdump 输出是否为 AArch64 或 32 位 ARM 反汇编
+    fn check_format(content: &str, objdump: &str) -> Result<()> {
+        let format = content
+            .lines()
+            .find_map(|line| line.split_once("file format").map(|(_, f)| f.trim()));
+
+        match format {
+            Some(f) if f.contains("aarch64") || crate::arm32::is_arm_format(f) => Ok(()),
+            Some(f) => Err(InterpreterError::ExecutionError(format!(
+                "不是 AArch64/ARM 目标文件 (file format {})，请确认编译目标或使用 --objdump-path 指定交叉工具链的 objdump",
+                f
+            ))),
+            None => Err(InterpreterError::ExecutionError(format!(
+                "无法识别 {} 的输出，请确认它是 GNU objdump 或 llvm-objdump",
+                objdump
+            ))),
         }
+    }
 
-        let start_line = start_line?;
+    /// 查找函数的起始和结束行（查索引，同名函数取第一个）
+    pub fn find_function(&self, func_name: &str) -> Option<(usize, usize)> {
+        let function = &self.functions[*self.function_index.get(func_name)?];
+        Some((function.start, function.end))
+    }
 
-        // 查找函数结束
-        let next_func_pattern = Regex::new(r"^[0-9a-f]+\s+<\w+>:").ok()?;
-        let section_pattern = Regex::new(r"^Disassembly of section").ok()?;
+    /// 函数的起始地址（函数头中的地址）
+    pub fn function_address(&self, func_name: &str) -> Option<u64> {
+        Some(self.functions[*self.function_index.get(func_name)?].address)
+    }
 
-        for i in (start_line + 1)..self.lines.len() {
-            if next_func_pattern.is_match(&self.lines[i]) 
-                || section_pattern.is_match(&self.lines[i]) {
-                return Some((start_line, i - 1));
+    /// 名称与 func_name 最接近的符号，用于“你是不是要找”提示
+    ///
+    /// 只在函数头中查找（`printf@plt` 等只出现在调用点的符号不算）：忽略大小写后互为前缀的名称最优先，
+    /// 其次按编辑距离排序，距离超过名称长度的三分之一（至少 2）的不算接近。查询为空时没有建议
+    pub fn suggest_functions(&self, func_name: &str) -> Vec<String> {
+        let query = func_name.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let limit = (query.chars().count() / 3).max(2);
+        let mut scored: Vec<(usize, &str)> = Vec::new();
+        for name in &self.function_names {
+            if scored.iter().any(|(_, n)| n == name) {
+                continue;
+            }
+            let lower = name.to_lowercase();
+            let distance = if lower.starts_with(&query) || query.starts_with(&lower) {
+                0
+            } else {
+                edit_distance(&query, &lower)
+            };
+            if distance <= limit {
+                scored.push((distance, name));
             }
         }
+        scored.sort_unstable();
+        scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, name)| name.to_string()).collect()
+    }
 
-        Some((start_line, self.lines.len() - 1))
+    /// dump 中没有 func_name 时的错误，附带名称相近的符号
+    pub fn function_not_found(&self, func_name: &str) -> InterpreterError {
+        InterpreterError::FunctionNotFound {
+            name: func_name.to_string(),
+            candidates: self.suggest_functions(func_name),
+        }
     }
 
     /// 列出所有函数名称
     pub fn list_functions(&self) -> Result<Vec<String>> {
-        let func_pattern = Regex::new(r"^[0-9a-f]+\s+<([^>]+)>:")
-            .map_err(|e| InterpreterError::ParseError(format!("正则表达式错误: {}", e)))?;
-        
-        let mut functions = Vec::new();
-        
-        for line in &self.lines {
-            if let Some(caps) = func_pattern.captures(line) {
-                let func_name = caps.get(1).unwrap().as_str().to_string();
-                functions.push(func_name);
+        Ok(self.functions.iter().map(|f| f.name.clone()).collect())
+    }
+
+    /// 按名称、起始地址或名称片段查找函数，返回候选函数名
+    ///
+    /// 依次尝试：完整名称、起始地址（`0x400640`）、名称前缀、名称子串，
+    /// 某一种方式有匹配时不再尝试后面的方式。`foo` 可以匹配 `foo.constprop.0`。
+    pub fn match_functions(&self, query: &str) -> Result<Vec<String>> {
+        let functions: Vec<(u64, &str)> = self.functions.iter().map(|f| (f.address, f.name.as_str())).collect();
+
+        let address = query
+            .strip_prefix("0x")
+            .and_then(|hex| u64::from_str_radix(hex, 16).ok());
+        for step in 0..4 {
+            let mut found: Vec<String> = Vec::new();
+            for (start, name) in &functions {
+                let matched = match step {
+                    0 => *name == query,
+                    1 => address == Some(*start),
+                    2 => name.starts_with(query),
+                    _ => name.contains(query),
+                };
+                if matched && !found.iter().any(|f| f == name) {
+                    found.push(name.to_string());
+                }
+            }
+            if !found.is_empty() {
+                return Ok(found);
             }
         }
-        
-        Ok(functions)
+        Ok(Vec::new())
     }
 
     /// 提取函数的汇编数据
     pub fn extract_function_data(&self, func_name: &str) -> Result<Vec<DumpEntry>> {
         let (start, end) = self.find_function(func_name)
-            .ok_or_else(|| InterpreterError::ParseError(
-                format!("未找到函数: {}", func_name)
-            ))?;
+            .ok_or_else(|| self.function_not_found(func_name))?;
+
+        let asm_pattern = asm_pattern();
 
-        let asm_pattern = Regex::new(r"^\s*([0-9a-f]+):\s+([0-9a-f]+)\s+(.+)$")
-            .map_err(|e| InterpreterError::ParseError(format!("正则表达式错误: {}", e)))?;
+        // 函数起始地址（用于计算指令偏移）
+        let func_start = self.lines[start]
+            .split_whitespace()
+            .next()
+            .and_then(|addr| u64::from_str_radix(addr, 16).ok())
+            .unwrap_or(0);
         
         // 检测是否有内联函数调用
-        let inline_pattern = Regex::new(r"<([^>]+\.part\.\d+)>")
-            .map_err(|e| InterpreterError::ParseError(format!("正则表达式错误: {}", e)))?;
-        
         let mut has_inline = None;
         for i in (start + 1)..=end {
-            if let Some(caps) = inline_pattern.captures(&self.lines[i]) {
+            if let Some(caps) = inline_pattern().captures(&self.lines[i]) {
                 has_inline = Some(caps.get(1).unwrap().as_str().to_string());
                 break;
             }
         }
         
-        let source_pattern = Regex::new(r"^/.*:\d+")
-            .map_err(|e| InterpreterError::ParseError(format!("正则表达式错误: {}", e)))?;
-
         // 第一步：收集所有 C 代码行
         let mut c_code_map: HashMap<usize, String> = HashMap::new();
         let mut first_asm_line = None;
@@ -131,11 +656,13 @@ impl ObjdumpParser {
             }
 
             let cleaned = line.trim();
-            if cleaned.is_empty() 
+            if !self.has_source
+                || cleaned.is_empty()
+                || cleaned == "..."
                 || cleaned.starts_with("Disassembly") 
                 || cleaned.starts_with("objdump")
                 || cleaned.starts_with("file format") 
-                || source_pattern.is_match(cleaned) {
+                || source_pattern().is_match(cleaned) {
                 continue;
             }
 
@@ -196,13 +723,28 @@ impl ObjdumpParser {
 
             if let Some(caps) = asm_pattern.captures(line) {
                 let address = caps.get(1).unwrap().as_str().to_string();
-                let machine_code = caps.get(2).unwrap().as_str().to_string();
+                let machine_code = Self::normalize_machine_code(caps.get(2).unwrap().as_str());
                 let asm_instruction = caps.get(3).unwrap().as_str().trim().to_string();
 
-                // 尝试解析汇编指令
-                let parsed_instruction = Self::parse_instruction(&asm_instruction);
+                // 尝试解析汇编指令，记录其真实地址并补全分支目标的符号；文本无法解析时按机器码解码
+                // （ARM32 的指令交给 arm32 模块按文本解释，不用 AArch64 解析器）
+                let addr_value = u64::from_str_radix(&address, 16).unwrap_or(0);
+                let encoding = u32::from_str_radix(&machine_code, 16).ok().filter(|_| machine_code.len() == 8);
+                let parsed = if self.arm32 {
+                    None
+                } else {
+                    Self::parse_instruction(&asm_instruction)
+                        .or_else(|| encoding.and_then(|word| crate::decoder::decode(word, addr_value)))
+                };
+                let parsed_instruction = parsed.map(|mut inst| {
+                    inst.address = addr_value;
+                    inst.encoding = encoding;
+                    self.annotate_targets(&mut inst);
+                    inst
+                });
 
                 entries.push(DumpEntry {
+                    id: instruction_id(func_name, addr_value.saturating_sub(func_start)),
                     c_line: current_c_line,
                     c_code: current_c_code.clone(),
                     address,
@@ -217,6 +759,7 @@ impl ObjdumpParser {
         if let Some(inline_func) = has_inline {
             if !entries.is_empty() {
                 entries.push(DumpEntry {
+                    id: String::new(),
                     c_line: None,
                     c_code: format!("⚠️ 注意：主要逻辑已被编译器优化，实际代码在编译器生成的内部函数 <{}> 中执行", inline_func),
                     address: String::new(),
@@ -230,6 +773,119 @@ impl ObjdumpParser {
         Ok(entries)
     }
 
+    /// 检查函数中每条指令能否被文本解析器完整解析，记录不能解析的指令的行、列和原因
+    ///
+    /// 判断方式与 `extract_function_data` 一致；ARM32 dump 的指令按文本解释，不做检查（返回空的诊断）
+    pub fn parse_diagnostics(&self, func_name: &str) -> Result<ParseDiagnostics> {
+        let (start, end) = self.find_function(func_name)
+            .ok_or_else(|| self.function_not_found(func_name))?;
+        let mut diagnostics = ParseDiagnostics::default();
+        if self.arm32 {
+            return Ok(diagnostics);
+        }
+
+        let parser = crate::parser::AssemblyParser::new();
+        for i in (start + 1)..=end {
+            let line = &self.lines[i];
+            let Some(caps) = asm_pattern().captures(line) else {
+                continue;
+            };
+            diagnostics.instructions += 1;
+            let asm = caps.get(3).unwrap();
+            let asm_instruction = asm.as_str().trim();
+            if Self::parse_instruction(asm_instruction).is_some() {
+                continue;
+            }
+
+            let (offset, reason) = match parser.locate_error(asm_instruction) {
+                Some((offset, error)) => (offset, error.to_string()),
+                None => (0, String::from("不是指令")),
+            };
+            let offset = asm.start() + (asm.as_str().len() - asm.as_str().trim_start().len()) + offset;
+            let address = caps.get(1).unwrap().as_str();
+            let machine_code = Self::normalize_machine_code(caps.get(2).unwrap().as_str());
+            let decoded = machine_code.len() == 8
+                && u32::from_str_radix(&machine_code, 16).ok().is_some_and(|word| {
+                    crate::decoder::decode(word, u64::from_str_radix(address, 16).unwrap_or(0)).is_some()
+                });
+            diagnostics.issues.push(ParseIssue {
+                line: self.first_line + i + 1,
+                column: line[..offset].chars().count() + 1,
+                address: address.to_string(),
+                asm_instruction: asm_instruction.to_string(),
+                reason,
+                decoded,
+            });
+        }
+        Ok(diagnostics)
+    }
+
+    /// 查找函数引用的编译器辅助函数并提取其指令（按首次引用的顺序）
+    ///
+    /// 辅助函数中再引用的辅助函数也一并提取；dump 中找不到的辅助函数跳过
+    pub fn extract_helpers(&self, entries: &[DumpEntry]) -> Vec<(String, Vec<DumpEntry>)> {
+        let references = |entries: &[DumpEntry]| -> Vec<String> {
+            entries
+                .iter()
+                .filter_map(|e| e.parsed_instruction.as_ref())
+                .flat_map(|inst| inst.operands.iter())
+                .filter_map(|op| match op {
+                    crate::instruction::Operand::Label(label) => target_symbol(label),
+                    _ => None,
+                })
+                .filter(|symbol| !symbol.contains('+') && is_compiler_helper(symbol))
+                .map(String::from)
+                .collect()
+        };
+
+        let mut helpers: Vec<(String, Vec<DumpEntry>)> = Vec::new();
+        let mut pending = references(entries);
+        pending.reverse();
+        while let Some(name) = pending.pop() {
+            if helpers.iter().any(|(helper, _)| *helper == name) {
+                continue;
+            }
+            let Ok(body) = self.extract_function_data(&name) else {
+                continue;
+            };
+            let mut nested = references(&body);
+            nested.reverse();
+            pending.extend(nested);
+            helpers.push((name, body));
+        }
+        helpers
+    }
+
+    /// 统一机器码格式：llvm-objdump 按内存顺序输出的字节转换为与 GNU objdump 相同的整字形式，
+    /// Thumb-2 的两个半字按原顺序拼接
+    fn normalize_machine_code(code: &str) -> String {
+        if code.len() == 9 && code.as_bytes()[4] == b' ' {
+            code.replace(' ', "")
+        } else if code.contains(' ') {
+            code.split(' ').rev().collect()
+        } else {
+            code.to_string()
+        }
+    }
+
+    /// 为纯地址形式的分支目标（如 `bl 400560`）补全符号注释
+    fn annotate_targets(&self, inst: &mut Instruction) {
+        use crate::instruction::Operand;
+
+        for operand in inst.operands.iter_mut() {
+            if let Operand::Label(label) = operand {
+                if label.chars().all(|c| c.is_ascii_hexdigit()) {
+                    if let Some(symbol) = u64::from_str_radix(label, 16)
+                        .ok()
+                        .and_then(|addr| self.symbols.resolve(addr))
+                    {
+                        *label = format!("{} <{}>", label, symbol);
+                    }
+                }
+            }
+        }
+    }
+
     /// 解析单条汇编指令
     fn parse_instruction(asm_str: &str) -> Option<Instruction> {
         use crate::parser::AssemblyParser;
@@ -258,4 +914,277 @@ mod tests {
         let result = parser.find_function("test_func");
         assert!(result.is_some());
     }
+
+    #[test]
+    fn test_match_functions() {
+        let content = r#"
+0000000000400600 <helper.constprop.0>:
+  400600:   d65f03c0    ret
+
+0000000000400640 <helper>:
+  400640:   d65f03c0    ret
+
+0000000000400650 <main_helper>:
+  400650:   d65f03c0    ret
+"#;
+        let parser = ObjdumpParser::new(content.to_string());
+        assert_eq!(parser.match_functions("helper").unwrap(), vec!["helper"]);
+        assert_eq!(parser.match_functions("0x400600").unwrap(), vec!["helper.constprop.0"]);
+        assert_eq!(parser.match_functions("help").unwrap(), vec!["helper.constprop.0", "helper"]);
+        assert_eq!(parser.match_functions("main").unwrap(), vec!["main_helper"]);
+        assert!(parser.match_functions("0x400604").unwrap().is_empty());
+
+        // 带后缀的函数不会把下一个函数的指令算进来
+        assert_eq!(parser.extract_function_data("helper.constprop.0").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_function_not_found() {
+        let content = r#"
+0000000000400600 <Matrix_add>:
+  400600:   94000004    bl 400610 <Matrix_mul>
+  400604:   94000040    bl 400700 <printf@plt>
+  400608:   d65f03c0    ret
+
+0000000000400610 <Matrix_mul>:
+  400610:   d65f03c0    ret
+
+0000000000400620 <main>:
+  400620:   d65f03c0    ret
+"#;
+        let parser = ObjdumpParser::new(content.to_string());
+        let Err(InterpreterError::FunctionNotFound { name, candidates }) = parser.extract_function_data("matrix_ad") else {
+            panic!("expected FunctionNotFound");
+        };
+        assert_eq!(name, "matrix_ad");
+        assert_eq!(candidates, ["Matrix_add", "Matrix_mul"]);
+        assert_eq!(parser.suggest_functions("mian"), ["main"]);
+        // printf@plt 只出现在调用点，不是 dump 中的函数
+        assert!(parser.suggest_functions("printf").is_empty());
+        assert!(parser.suggest_functions("").is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_function_index() {
+        let content = r#"
+Disassembly of section .init:
+
+0000000000400500 <_init>:
+  400500:   d503201f    nop
+
+Disassembly of section .text:
+
+0000000000400600 <f>:
+  400600:   d65f03c0    ret
+0000000000400610 <f>:
+  400610:   d503201f    nop
+"#;
+        let parser = ObjdumpParser::new(content.to_string());
+        assert_eq!(parser.list_functions().unwrap(), vec!["_init", "f", "f"]);
+        // 函数在下一个节之前结束，同名函数取第一个
+        assert_eq!(parser.find_function("_init"), Some((3, 5)));
+        assert_eq!(parser.find_function("f"), Some((8, 9)));
+        assert_eq!(parser.find_function("g"), None);
+    }
+
+    #[test]
+    fn test_open_streaming() {
+        let content = r#"a.o:     file format elf64-littleaarch64
+
+Contents of section .rodata:
+ 4007a0 01000000 02000000                    ........
+
+Disassembly of section .text:
+
+0000000000400600 <helper>:
+  400600:   d65f03c0    ret
+
+0000000000400640 <main>:
+  400640:   97fffff0    bl 400600 <helper>
+  400644:   d503201f    frobnicate
+  400648:   d65f03c0    ret
+
+0000000000400660 <other>:
+  400660:   d65f03c0    ret
+"#;
+        let path = std::env::temp_dir().join("alaz-streaming-test.dump");
+        std::fs::write(&path, content).unwrap();
+        let parser = ObjdumpParser::open_streaming(path.to_str().unwrap(), "main").unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // 只保留选中的函数，全局信息仍来自整个文件
+        assert_eq!(parser.list_functions().unwrap(), vec!["main"]);
+        assert_eq!(parser.symbols().get(0x400660), Some("other"));
+        assert_eq!(parser.read_data(0x4007a4, 1), Some(vec![2]));
+
+        let expected = ObjdumpParser::new(content.to_string()).extract_function_data("main").unwrap();
+        let entries = parser.extract_function_data("main").unwrap();
+        assert_eq!(entries.len(), 3);
+        for (a, b) in entries.iter().zip(&expected) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.parsed_instruction, b.parsed_instruction);
+        }
+        // 诊断中的行号是原文件中的行号
+        let diagnostics = parser.parse_diagnostics("main").unwrap();
+        assert_eq!(diagnostics.issues[0].line, 13);
+        assert_eq!(diagnostics, ObjdumpParser::new(content.to_string()).parse_diagnostics("main").unwrap());
+        assert!(parser.extract_function_data("other").is_err());
+        // 未保留的函数仍可作为建议
+        assert_eq!(parser.suggest_functions("othr"), ["other"]);
+    }
+
+    #[test]
+    fn test_scan_function_names() {
+        let content = "a.o:     file format elf64-littleaarch64\r\n\n\
+0000000000400600 <printf@plt>:\n  400600:   d65f03c0    ret\n\n\
+0000000000400640 <main>:\r\n  400640:   97fffff0    bl 400600 <printf@plt>\n  400644:   d65f03c0    ret\n";
+        let path = std::env::temp_dir().join("alaz-scan-names-test.dump");
+        std::fs::write(&path, content).unwrap();
+        let names = scan_function_names(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(names, vec!["printf@plt", "main"]);
+        assert_eq!(names, ObjdumpParser::new(content.to_string()).list_functions().unwrap());
+    }
+
+    #[test]
+    fn test_extract_helpers() {
+        let content = r#"
+0000000000400600 <compute.part.0>:
+  400600:   b5000040    cbnz x0, 400608 <compute.part.0+0x8>
+  400604:   14000002    b 40060c <compute.cold>
+  400608:   d65f03c0    ret
+
+000000000040060c <compute.cold>:
+  40060c:   d4200000    brk #0x0
+
+0000000000400640 <compute>:
+  400640:   b4000040    cbz x0, 400648 <compute+0x8>
+  400644:   17ffffef    b 400600 <compute.part.0>
+  400648:   94000000    bl 400700 <abort@plt>
+  40064c:   d65f03c0    ret
+"#;
+        let parser = ObjdumpParser::new(content.to_string());
+        let entries = parser.extract_function_data("compute").unwrap();
+        let helpers = parser.extract_helpers(&entries);
+        let names: Vec<&str> = helpers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["compute.part.0", "compute.cold"]);
+        assert_eq!(helpers[0].1.len(), 3);
+
+        assert!(is_compiler_helper("OUTLINED_FUNCTION_2"));
+        assert!(!is_compiler_helper("abort@plt"));
+    }
+
+    #[test]
+    fn test_object_file_errors() {
+        let err = ObjdumpParser::from_object_file("a.o", "alaz-no-such-objdump").err().unwrap();
+        assert!(err.to_string().contains("--objdump-path"));
+
+        assert!(ObjdumpParser::check_format("a.o:     file format elf64-littleaarch64\n", "objdump").is_ok());
+        assert!(ObjdumpParser::check_format("a.o:     file format elf64-x86-64\n", "objdump").is_err());
+        assert!(ObjdumpParser::check_format("a.o:     file format elf32-littlearm\n", "objdump").is_ok());
+    }
+
+    #[test]
+    fn test_thumb_format() {
+        let content = "a.o:     file format elf32-littlearm\n\nDisassembly of section .text:\n\n00000000 <main>:\n   0:\tb580      \tpush\t{r7, lr}\n   2:\tf240 0300 \tmovw\tr3, #0\n   6:\tbd80      \tpop\t{r7, pc}\n";
+        let parser = ObjdumpParser::new(content.to_string());
+        assert!(parser.is_arm32());
+        let entries = parser.extract_function_data("main").unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].machine_code, "f2400300");
+        assert_eq!(entries[1].asm_instruction, "movw\tr3, #0");
+        assert!(entries.iter().all(|e| e.parsed_instruction.is_none()));
+    }
+
+    #[test]
+    fn test_llvm_objdump_format() {
+        let content = "0000000000000000 <f>:\n       0: 00 04 00 11  \tadd\tw0, w0, #1\n       4: c0 03 5f d6  \tret\n";
+        let parser = ObjdumpParser::new(content.to_string());
+        let entries = parser.extract_function_data("f").unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].machine_code, "11000400");
+        assert_eq!(entries[1].asm_instruction, "ret");
+        assert!(entries[0].parsed_instruction.is_some());
+    }
+
+    #[test]
+    fn test_detect_source() {
+        let disassembly_only = r#"
+0000000000000000 <f>:
+   0:   d503201f    nop
+	...
+   8:   d65f03c0    ret
+"#;
+        let parser = ObjdumpParser::new(disassembly_only.to_string());
+        assert!(!parser.has_source());
+        let entries = parser.extract_function_data("f").unwrap();
+        assert!(entries.iter().all(|e| e.c_code.is_empty() && e.c_line.is_none()));
+
+        let with_source = r#"
+0000000000000000 <f>:
+int f(void) {
+   0:   d65f03c0    ret
+"#;
+        assert!(ObjdumpParser::new(with_source.to_string()).has_source());
+    }
+
+    #[test]
+    fn test_symbol_table() {
+        let content = r#"
+0000000000400560 <printf@plt>:
+  400560:   90000090    adrp x16, 410000
+
+0000000000400600 <main>:
+  400600:   94000000    bl 400560
+  400604:   97ffffd7    bl 400580 <puts@plt>
+"#;
+        let parser = ObjdumpParser::new(content.to_string());
+        let symbols = parser.symbols();
+        assert_eq!(symbols.get(0x400560), Some("printf@plt"));
+        assert_eq!(symbols.get(0x400580), Some("puts@plt"));
+        assert_eq!(symbols.resolve(0x400604).as_deref(), Some("main+0x4"));
+
+        let entries = parser.extract_function_data("main").unwrap();
+        let inst = entries[0].parsed_instruction.as_ref().unwrap();
+        assert_eq!(
+            inst.operands[0],
+            crate::instruction::Operand::Label(String::from("400560 <printf@plt>"))
+        );
+    }
+
+    #[test]
+    fn test_read_data() {
+        let content = r#"
+Contents of section .rodata:
+ 4007a0 00020400 02040000 01000000 02000000  ................
+ 4007b0 0a0b                                 ..
+
+Disassembly of section .text:
+
+00000000004005c0 <f>:
+  4005c0:   d65f03c0    ret
+"#;
+        let parser = ObjdumpParser::new(content.to_string());
+        assert_eq!(parser.read_data(0x4007a0, 6), Some(vec![0, 2, 4, 0, 2, 4]));
+        assert_eq!(parser.read_data(0x4007ac, 6), Some(vec![2, 0, 0, 0, 0x0a, 0x0b]));
+        assert_eq!(parser.read_data(0x4007b0, 3), None);
+        assert!(!parser.has_source());
+    }
+
+    #[test]
+    fn test_instruction_ids() {
+        let content = r#"
+0000000000000400 <test_func>:
+ 400:   d100c3ff    sub sp, sp, #0x30
+ 404:   f90007e0    str x0, [sp, #8]
+"#;
+        let parser = ObjdumpParser::new(content.to_string());
+        let entries = parser.extract_function_data("test_func").unwrap();
+        assert_eq!(entries[0].id, "test_func+0x0");
+        assert_eq!(entries[1].id, "test_func+0x4");
+    }
 }
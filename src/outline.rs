@@ -0,0 +1,185 @@
+//! 共享代码（outlining）检测
+//!
+//! 编译器在 `-Oz`/`-moutline` 等选项下会把多个函数中相同的指令序列提取成
+//! `OUTLINED_FUNCTION_N` 这样的辅助函数。本模块识别这些辅助函数，记录调用它们的函数，
+//! 并把辅助函数的实际内容以注释行的形式归还到每个调用者的报告中。
+
+use crate::objdump::{DumpEntry, ObjdumpParser};
+use crate::semantic::SemanticInterpreter;
+use crate::error::Result;
+use std::collections::BTreeMap;
+
+/// 编译器生成的共享代码函数名前缀
+pub const OUTLINED_PREFIX: &str = "OUTLINED_FUNCTION_";
+
+/// 一个被提取出来的共享代码辅助函数
+#[derive(Debug, Clone)]
+pub struct OutlinedHelper {
+    /// 辅助函数名
+    pub name: String,
+    /// 调用该辅助函数的函数（按名称排序）
+    pub callers: Vec<String>,
+    /// 辅助函数中每条指令的语义解释
+    pub body: Vec<String>,
+}
+
+impl OutlinedHelper {
+    /// 辅助函数内容的单行摘要
+    pub fn summary(&self) -> String {
+        self.body.join("; ")
+    }
+}
+
+/// 共享代码检测结果
+#[derive(Debug, Clone, Default)]
+pub struct OutliningReport {
+    /// 辅助函数名 -> 辅助函数信息
+    pub helpers: BTreeMap<String, OutlinedHelper>,
+}
+
+impl OutliningReport {
+    /// 扫描 dump 中的所有函数，识别共享代码辅助函数
+    ///
+    /// 满足以下任一条件即视为共享代码：
+    /// - 函数名以 `OUTLINED_FUNCTION_` 开头
+    /// - 没有对应的 C 源码、没有建立栈帧，且被两个及以上的函数调用
+    pub fn detect(parser: &ObjdumpParser) -> Result<Self> {
+        let mut functions = BTreeMap::new();
+        for name in parser.list_functions()? {
+            // PLT 桩等无法提取的函数直接跳过
+            if let Ok(entries) = parser.extract_function_data(&name) {
+                functions.insert(name, entries);
+            }
+        }
+
+        let mut callers: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (caller, entries) in &functions {
+            for target in entries.iter().filter_map(DumpEntry::call_target) {
+                if &target == caller {
+                    continue;
+                }
+                let list = callers.entry(target).or_default();
+                if !list.contains(caller) {
+                    list.push(caller.clone());
+                }
+            }
+        }
+
+        let mut helpers = BTreeMap::new();
+        for (name, entries) in &functions {
+            let callers = callers.get(name).cloned().unwrap_or_default();
+            if !Self::is_outlined(name, entries, callers.len()) {
+                continue;
+            }
+            let body = entries
+                .iter()
+                .filter(|e| !e.asm_instruction.is_empty())
+                .map(|e| match e.parsed_instruction {
+                    Some(ref inst) => SemanticInterpreter::interpret(inst),
+                    None => e.asm_instruction.clone(),
+                })
+                .collect();
+            helpers.insert(name.clone(), OutlinedHelper { name: name.clone(), callers, body });
+        }
+
+        Ok(Self { helpers })
+    }
+
+    /// 判断函数是否为共享代码辅助函数
+    fn is_outlined(name: &str, entries: &[DumpEntry], caller_count: usize) -> bool {
+        if name.starts_with(OUTLINED_PREFIX) {
+            return true;
+        }
+        let has_source = entries.iter().any(|e| e.c_line.is_some());
+        let has_frame = entries
+            .iter()
+            .any(|e| e.asm_instruction.starts_with("stp") && e.asm_instruction.contains("x29, x30"));
+        caller_count >= 2 && !has_source && !has_frame
+    }
+
+    /// 是否没有检测到共享代码
+    pub fn is_empty(&self) -> bool {
+        self.helpers.is_empty()
+    }
+
+    /// 在调用共享代码的指令之后插入注释行，说明辅助函数的内容和其他调用者
+    pub fn annotate(&self, function_name: &str, entries: &mut Vec<DumpEntry>) {
+        if self.helpers.is_empty() {
+            return;
+        }
+
+        let mut merged = Vec::with_capacity(entries.len());
+        for entry in entries.drain(..) {
+            let helper = entry.call_target().and_then(|target| self.helpers.get(&target));
+            let note = helper.map(|helper| {
+                let others: Vec<&str> = helper
+                    .callers
+                    .iter()
+                    .filter(|c| c.as_str() != function_name)
+                    .map(|c| c.as_str())
+                    .collect();
+                let mut text = format!("🔗 共享代码 <{}>: {}", helper.name, helper.summary());
+                if !others.is_empty() {
+                    text.push_str(&format!("（同时被 {} 调用）", others.join(", ")));
+                }
+                text
+            });
+            merged.push(entry);
+            if let Some(text) = note {
+                merged.push(DumpEntry {
+                    id: String::new(),
+                    c_line: None,
+                    c_code: text,
+                    address: String::new(),
+                    machine_code: String::new(),
+                    asm_instruction: String::new(),
+                    parsed_instruction: None,
+                });
+            }
+        }
+        *entries = merged;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"
+0000000000000000 <OUTLINED_FUNCTION_0>:
+   0:   8b010000    add x0, x0, x1
+   4:   d65f03c0    ret
+
+0000000000000010 <foo>:
+  10:   94000000    bl 0 <OUTLINED_FUNCTION_0>
+  14:   d65f03c0    ret
+
+0000000000000020 <bar>:
+  20:   94000000    bl 0 <OUTLINED_FUNCTION_0>
+  24:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_detect_outlined_helper() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let report = OutliningReport::detect(&parser).unwrap();
+
+        let helper = &report.helpers["OUTLINED_FUNCTION_0"];
+        assert_eq!(helper.callers, vec!["bar", "foo"]);
+        assert_eq!(helper.body.len(), 2);
+        assert!(!report.helpers.contains_key("foo"));
+    }
+
+    #[test]
+    fn test_annotate_callers() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let report = OutliningReport::detect(&parser).unwrap();
+
+        let mut entries = parser.extract_function_data("foo").unwrap();
+        report.annotate("foo", &mut entries);
+
+        assert_eq!(entries.len(), 3);
+        assert!(entries[1].c_code.contains("OUTLINED_FUNCTION_0"));
+        assert!(entries[1].c_code.contains("bar"));
+    }
+}
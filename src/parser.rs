@@ -1,7 +1,7 @@
 //! 汇编代码解析器
 
 use crate::instruction::{Instruction, InstructionType, Operand};
-use crate::register::Register;
+use crate::register::{Condition, Register};
 use crate::error::{Result, InterpreterError};
 
 /// 汇编解析器
@@ -54,6 +54,25 @@ impl AssemblyParser {
         Ok(instructions)
     }
 
+    /// 找出单行指令无法解析的位置和原因，能够解析时返回 None
+    ///
+    /// 位置为 line 中的字节偏移：助记符无法识别时指向助记符，否则指向第一个无法解析的操作数
+    pub fn locate_error(&self, line: &str) -> Option<(usize, InterpreterError)> {
+        let error = self.parse_instruction(&self.clean_line(line), 0).err()?;
+        let code = line.split("//").next().unwrap_or("").trim_end();
+        let start = code.len() - code.trim_start().len();
+        let mnemonic_end = code[start..].find(char::is_whitespace).map_or(code.len(), |i| start + i);
+        if self.parse_instruction_type(&code[start..mnemonic_end].to_lowercase()).is_err() {
+            return Some((start, error));
+        }
+        let operands = &code[mnemonic_end..];
+        let offset = Self::split_operands(operands.trim())
+            .into_iter()
+            .find(|part| self.parse_operand(part).is_err())
+            .map_or(start, |part| part.as_ptr() as usize - line.as_ptr() as usize);
+        Some((offset, error))
+    }
+
     /// 清理行（去除注释和空白）
     fn clean_line(&self, line: &str) -> String {
         // 去除注释
@@ -90,11 +109,30 @@ impl AssemblyParser {
             String::new()
         };
 
-        let operands = self.parse_operands(&operands_str)?;
+        let mut operands = self.parse_operands(&operands_str)?;
+
+        // 条件选择/条件比较的最后一个操作数是条件码
+        if Self::takes_condition(inst_type) {
+            if let Some(Operand::Label(name)) = operands.last() {
+                if let Ok(condition) = Condition::parse(name) {
+                    operands.pop();
+                    return Ok(Instruction::new_with_condition(inst_type, operands, address, condition));
+                }
+            }
+        }
 
         Ok(Instruction::new(inst_type, operands, address))
     }
 
+    /// 指令是否以条件码作为最后一个操作数
+    fn takes_condition(inst_type: InstructionType) -> bool {
+        use InstructionType::*;
+        matches!(
+            inst_type,
+            CSEL | CSINC | CSINV | CSNEG | CSET | CSETM | CINC | CINV | CNEG | CCMP | CCMN
+        )
+    }
+
     /// 解析指令类型
     fn parse_instruction_type(&self, mnemonic: &str) -> Result<InstructionType> {
         // 先尝试直接匹配常见指令
@@ -112,6 +150,8 @@ impl AssemblyParser {
             "neg" => InstructionType::NEG,
             "adc" => InstructionType::ADC,
             "sbc" => InstructionType::SBC,
+            "adds" => InstructionType::ADDS,
+            "subs" => InstructionType::SUBS,
             
             // 逻辑运算
             "and" => InstructionType::AND,
@@ -120,6 +160,7 @@ impl AssemblyParser {
             "bic" => InstructionType::BIC,
             "orn" => InstructionType::ORN,
             "eon" => InstructionType::EON,
+            "ands" => InstructionType::ANDS,
             "mvn" => InstructionType::MVN,
             
             // 移位
@@ -406,7 +447,7 @@ impl AssemblyParser {
         }
 
         let mut operands = Vec::new();
-        let parts: Vec<&str> = operands_str.split(',').map(|s| s.trim()).collect();
+        let parts = Self::split_operands(operands_str);
 
         for part in parts {
             operands.push(self.parse_operand(part)?);
@@ -415,18 +456,39 @@ impl AssemblyParser {
         Ok(operands)
     }
 
+    /// 按逗号拆分操作数（忽略 [] 和 {} 内部的逗号）
+    fn split_operands(operands_str: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+
+        for (i, c) in operands_str.char_indices() {
+            match c {
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    parts.push(operands_str[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(operands_str[start..].trim());
+
+        parts
+    }
+
     /// 解析单个操作数
     fn parse_operand(&self, operand_str: &str) -> Result<Operand> {
         let operand_str = operand_str.trim();
 
-        // 内存操作数 [...]
-        if operand_str.starts_with('[') && operand_str.ends_with(']') {
+        // 内存操作数 [...] 或前变址 [...]!
+        if operand_str.starts_with('[') && (operand_str.ends_with(']') || operand_str.ends_with("]!")) {
             return self.parse_memory_operand(operand_str);
         }
 
         // 立即数 #value
-        if operand_str.starts_with('#') {
-            let value_str = &operand_str[1..];
+        if let Some(value_str) = operand_str.strip_prefix('#') {
             let value = self.parse_immediate(value_str)?;
             return Ok(Operand::Immediate(value));
         }
@@ -447,6 +509,8 @@ impl AssemblyParser {
 
     /// 解析内存操作数
     fn parse_memory_operand(&self, operand_str: &str) -> Result<Operand> {
+        let pre_indexed = operand_str.ends_with('!');
+        let operand_str = operand_str.trim_end_matches('!');
         let inner = &operand_str[1..operand_str.len()-1]; // 去除 [ ]
         
         // 简单情况：[reg] 或 [reg, #offset]
@@ -456,13 +520,13 @@ impl AssemblyParser {
             
             let base = Register::parse(base_str)?;
             
-            if offset_str.starts_with('#') {
-                let offset = self.parse_immediate(&offset_str[1..])?;
+            if let Some(offset_str) = offset_str.strip_prefix('#') {
+                let offset = self.parse_immediate(offset_str)?;
                 Ok(Operand::Memory {
                     base,
                     offset: Some(offset),
                     index: None,
-                    pre_indexed: false,
+                    pre_indexed,
                     post_indexed: false,
                 })
             } else {
@@ -492,18 +556,39 @@ impl AssemblyParser {
     /// 解析立即数
     fn parse_immediate(&self, value_str: &str) -> Result<i64> {
         let value_str = value_str.trim();
-        
+
+        if let Some(magnitude) = value_str.strip_prefix('-') {
+            // 绝对值按无符号解析，-0x8000000000000000 恰好是 i64::MIN
+            let magnitude = Self::parse_magnitude(magnitude.trim())?;
+            if magnitude > i64::MIN.unsigned_abs() {
+                return Err(InterpreterError::ParseError(format!("立即数超出范围: {}", value_str)));
+            }
+            return Ok(0i64.wrapping_sub_unsigned(magnitude));
+        }
+
+        let value = Self::parse_magnitude(value_str)?;
+        if value_str.starts_with("0x") || value_str.starts_with("0X") {
+            // 十六进制（逻辑立即数如 0xfffffffffffffff0 按位模式解释）
+            Ok(value as i64)
+        } else {
+            i64::try_from(value)
+                .map_err(|_| InterpreterError::ParseError(format!("立即数超出范围: {}", value_str)))
+        }
+    }
+
+    /// 解析不带符号的立即数绝对值
+    fn parse_magnitude(value_str: &str) -> Result<u64> {
         if value_str.starts_with("0x") || value_str.starts_with("0X") {
             // 十六进制
-            i64::from_str_radix(&value_str[2..], 16)
+            u64::from_str_radix(&value_str[2..], 16)
                 .map_err(|e| InterpreterError::ParseError(format!("无效的十六进制数: {}", e)))
         } else if value_str.starts_with("0b") || value_str.starts_with("0B") {
             // 二进制
-            i64::from_str_radix(&value_str[2..], 2)
+            u64::from_str_radix(&value_str[2..], 2)
                 .map_err(|e| InterpreterError::ParseError(format!("无效的二进制数: {}", e)))
         } else {
             // 十进制
-            value_str.parse::<i64>()
+            value_str.parse::<u64>()
                 .map_err(|e| InterpreterError::ParseError(format!("无效的十进制数: {}", e)))
         }
     }
@@ -530,6 +615,21 @@ mod tests {
         assert_eq!(instructions[0].operands.len(), 3);
     }
 
+    #[test]
+    fn test_locate_error() {
+        let parser = AssemblyParser::new();
+        assert!(parser.locate_error("add x0, x1, #10").is_none());
+
+        let (offset, error) = parser.locate_error("frobnicate x0").unwrap();
+        assert_eq!(offset, 0);
+        assert!(matches!(error, InterpreterError::InvalidInstruction(ref m) if m == "frobnicate"));
+
+        let line = "ldr x0, [foo, #8]";
+        let (offset, error) = parser.locate_error(line).unwrap();
+        assert_eq!(&line[offset..], "[foo, #8]");
+        assert!(matches!(error, InterpreterError::InvalidRegister(_)));
+    }
+
     #[test]
     fn test_parse_with_immediate() {
         let mut parser = AssemblyParser::new();
@@ -545,7 +645,16 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // TODO: 修复立即数解析问题
+    fn test_parse_negative_immediate_bounds() {
+        let parser = AssemblyParser::new();
+        assert_eq!(parser.parse_immediate("-0x8000000000000000").unwrap(), i64::MIN);
+        assert_eq!(parser.parse_immediate("-9223372036854775808").unwrap(), i64::MIN);
+        assert_eq!(parser.parse_immediate("-0x10").unwrap(), -16);
+        assert!(parser.parse_immediate("-0x8000000000000001").is_err());
+        assert!(parser.parse_immediate("9223372036854775808").is_err());
+    }
+
+    #[test]
     fn test_parse_memory_operand() {
         let mut parser = AssemblyParser::new();
         let code = "ldr x0, [sp, #8]";
@@ -553,5 +662,22 @@ mod tests {
         
         assert_eq!(instructions.len(), 1);
         assert_eq!(instructions[0].instruction_type, InstructionType::LDR);
+        assert_eq!(instructions[0].operands.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_pre_indexed_operand() {
+        let mut parser = AssemblyParser::new();
+        let instructions = parser.parse("stp x29, x30, [sp, #-16]!").unwrap();
+
+        assert_eq!(instructions[0].operands.len(), 3);
+        match &instructions[0].operands[2] {
+            Operand::Memory { base, offset, pre_indexed, .. } => {
+                assert_eq!(*base, Register::SP);
+                assert_eq!(*offset, Some(-16));
+                assert!(*pre_indexed);
+            }
+            other => panic!("Expected memory operand, got {:?}", other),
+        }
     }
 }
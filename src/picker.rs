@@ -0,0 +1,250 @@
+//! 交互式模式的函数列表
+//!
+//! 链接了 libc 的 dump 中往往有上百个符号，逐屏滚动很难找到目标函数。
+//! 函数列表支持用 `/关键字` 过滤（按正则匹配，不区分大小写；不是合法正则时按普通子串匹配），
+//! 并按页显示。函数编号始终是在完整列表中的编号，过滤前后保持不变。
+//! 命令行中也可以用通配符、`/关键字` 或 `@编号` 一次选择多个函数（[`FunctionSelection`]）。
+
+use regex::{Regex, RegexBuilder};
+
+/// 每页默认显示的函数数
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// 一次输入的处理结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// 选中了函数（完整列表中的下标）
+    Select(usize),
+    /// 退出
+    Quit,
+    /// 过滤条件或页码改变，需要重新显示列表
+    Redraw,
+    /// 无法识别的输入
+    Invalid,
+}
+
+/// 可过滤、可分页的函数列表
+#[derive(Debug, Clone)]
+pub struct FunctionPicker {
+    functions: Vec<String>,
+    filter: Option<(String, Regex)>,
+    /// 匹配过滤条件的函数下标
+    matches: Vec<usize>,
+    page: usize,
+    page_size: usize,
+}
+
+impl FunctionPicker {
+    /// 创建函数列表
+    pub fn new(functions: Vec<String>) -> Self {
+        let matches = (0..functions.len()).collect();
+        Self { functions, filter: None, matches, page: 0, page_size: DEFAULT_PAGE_SIZE }
+    }
+
+    /// 设置每页显示的函数数
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// 函数名
+    pub fn name(&self, index: usize) -> &str {
+        &self.functions[index]
+    }
+
+    /// 当前的过滤条件
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    /// 匹配过滤条件的函数数
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// 当前页码（从 0 开始）和总页数
+    pub fn page(&self) -> (usize, usize) {
+        (self.page, self.matches.len().div_ceil(self.page_size).max(1))
+    }
+
+    /// 当前页的函数：(编号, 函数名)，编号从 1 开始
+    pub fn page_items(&self) -> Vec<(usize, &str)> {
+        self.matches
+            .iter()
+            .skip(self.page * self.page_size)
+            .take(self.page_size)
+            .map(|&i| (i + 1, self.functions[i].as_str()))
+            .collect()
+    }
+
+    /// 处理一行输入：编号选择函数，`/关键字` 过滤（单独的 `/` 清除），`n`/`p` 翻页，`q` 退出
+    pub fn handle(&mut self, input: &str) -> Action {
+        let input = input.trim();
+        if let Some(pattern) = input.strip_prefix('/') {
+            self.set_filter(pattern.trim());
+            return Action::Redraw;
+        }
+        match input {
+            "" | "q" | "quit" => Action::Quit,
+            "n" if self.page + 1 < self.page().1 => {
+                self.page += 1;
+                Action::Redraw
+            }
+            "p" if self.page > 0 => {
+                self.page -= 1;
+                Action::Redraw
+            }
+            _ => match input.parse::<usize>() {
+                Ok(num) if num > 0 && num <= self.functions.len() => Action::Select(num - 1),
+                _ => Action::Invalid,
+            },
+        }
+    }
+
+    /// 设置过滤条件并回到第一页，空字符串表示清除
+    fn set_filter(&mut self, pattern: &str) {
+        self.page = 0;
+        self.filter = (!pattern.is_empty()).then(|| {
+            let regex = RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .or_else(|_| RegexBuilder::new(&regex::escape(pattern)).case_insensitive(true).build())
+                .expect("转义后的字符串总是合法的正则");
+            (pattern.to_string(), regex)
+        });
+        self.matches = (0..self.functions.len())
+            .filter(|&i| self.filter.as_ref().is_none_or(|(_, regex)| regex.is_match(&self.functions[i])))
+            .collect();
+    }
+}
+
+/// 命令行中一次选择多个函数的写法
+///
+/// - 通配符：`Matrix_*`、`vec?_add`、`[ab]*`（区分大小写，匹配完整名称）
+/// - 正则：`/关键字`（与函数列表的过滤一样不区分大小写，匹配名称的任意部分）
+/// - 编号：`@3`（交互式模式中按名称排序的函数列表里的编号，从 1 开始）
+#[derive(Debug, Clone)]
+pub enum FunctionSelection {
+    /// 通配符（已转换为锚定的正则）
+    Glob(Regex),
+    /// 正则表达式
+    Regex(Regex),
+    /// 编号（从 1 开始）
+    Index(usize),
+}
+
+impl FunctionSelection {
+    /// 解析函数参数；普通的函数名（不含通配符，不以 `/`、`@` 开头）返回 None
+    pub fn parse(text: &str) -> crate::Result<Option<Self>> {
+        let invalid = |e: regex::Error| crate::InterpreterError::ParseError(format!("无效的函数模式 {}: {}", text, e));
+        if let Some(pattern) = text.strip_prefix('/') {
+            let regex = RegexBuilder::new(pattern).case_insensitive(true).build().map_err(invalid)?;
+            return Ok(Some(Self::Regex(regex)));
+        }
+        if let Some(number) = text.strip_prefix('@') {
+            return match number.parse::<usize>() {
+                Ok(index) if index > 0 => Ok(Some(Self::Index(index))),
+                _ => Err(crate::InterpreterError::ParseError(format!("无效的函数编号: {} (应为 @1、@2 …)", text))),
+            };
+        }
+        if !text.contains(['*', '?', '[']) {
+            return Ok(None);
+        }
+        Ok(Some(Self::Glob(Regex::new(&glob_to_regex(text)).map_err(invalid)?)))
+    }
+
+    /// 从按名称排序的函数列表中选出匹配的函数（保持列表顺序）
+    pub fn select<'a>(&self, functions: &'a [String]) -> Vec<&'a str> {
+        match self {
+            Self::Glob(regex) | Self::Regex(regex) => {
+                functions.iter().filter(|f| regex.is_match(f)).map(String::as_str).collect()
+            }
+            Self::Index(index) => functions.get(index - 1).map(String::as_str).into_iter().collect(),
+        }
+    }
+}
+
+/// 把通配符转换为匹配完整名称的正则：`*` 任意字符串，`?` 单个字符，`[...]`/`[!...]` 字符集合
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '[' => {
+                regex.push('[');
+                if chars.next_if_eq(&'!').is_some() {
+                    regex.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if c == '\\' || c == '[' {
+                        regex.push('\\');
+                    }
+                    regex.push(c);
+                }
+                regex.push(']');
+            }
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker() -> FunctionPicker {
+        let functions = ["Matrix_add", "Matrix_mul", "_init", "main", "memcpy", "printf"];
+        FunctionPicker::new(functions.iter().map(|f| f.to_string()).collect()).with_page_size(2)
+    }
+
+    #[test]
+    fn test_filter_and_paging() {
+        let mut picker = picker();
+        assert_eq!(picker.page(), (0, 3));
+        assert_eq!(picker.handle("n"), Action::Redraw);
+        assert_eq!(picker.page_items(), vec![(3, "_init"), (4, "main")]);
+
+        assert_eq!(picker.handle("/matrix"), Action::Redraw);
+        assert_eq!(picker.page(), (0, 1));
+        assert_eq!(picker.page_items(), vec![(1, "Matrix_add"), (2, "Matrix_mul")]);
+        assert_eq!(picker.handle("n"), Action::Invalid);
+
+        assert_eq!(picker.handle("/^m(ain|em)"), Action::Redraw);
+        assert_eq!(picker.page_items(), vec![(4, "main"), (5, "memcpy")]);
+        // 编号不随过滤改变
+        assert_eq!(picker.handle("5"), Action::Select(4));
+        assert_eq!(picker.name(4), "memcpy");
+
+        // 非法正则按子串匹配
+        picker.handle("/add(");
+        assert_eq!(picker.match_count(), 0);
+        picker.handle("/");
+        assert_eq!((picker.filter(), picker.match_count()), (None, 6));
+        assert_eq!(picker.handle("q"), Action::Quit);
+    }
+
+    #[test]
+    fn test_function_selection() {
+        let functions: Vec<String> =
+            ["Matrix_add", "Matrix_mul", "main", "vec2_add", "vec3_add"].iter().map(|f| f.to_string()).collect();
+        let select = |text: &str| FunctionSelection::parse(text).unwrap().unwrap().select(&functions);
+
+        assert_eq!(select("Matrix_*"), ["Matrix_add", "Matrix_mul"]);
+        assert_eq!(select("vec?_add"), ["vec2_add", "vec3_add"]);
+        assert_eq!(select("[!M]*"), ["main", "vec2_add", "vec3_add"]);
+        assert_eq!(select("/^matrix_(add|sub)"), ["Matrix_add"]);
+        assert_eq!(select("@3"), ["main"]);
+        assert!(select("@9").is_empty());
+
+        assert!(FunctionSelection::parse("Matrix_add").unwrap().is_none());
+        assert!(FunctionSelection::parse("@0").is_err());
+        assert!(FunctionSelection::parse("/(").is_err());
+    }
+}
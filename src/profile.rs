@@ -0,0 +1,60 @@
+//! 分析配置档
+//!
+//! 按用途把报告中的分析选项打包成命名的配置档：`teaching` 面向课堂讲解，
+//! `performance` 关注寄存器压力、代码体积和跳转布局，`security` 关注加固机制和间接跳转。
+//! 配置档只打开选项，命令行中单独指定的选项仍然生效。
+
+use crate::error::{Result, InterpreterError};
+use serde::Deserialize;
+
+/// 一组分析选项
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// 函数导读
+    pub walkthrough: bool,
+    /// 返回值来源
+    pub returns: bool,
+    /// 推断签名
+    pub signature: bool,
+    /// 被调用者保存寄存器的活跃区间
+    pub liveness: bool,
+    /// 共享代码检测
+    pub outlining: bool,
+    /// 跳转方向和循环头对齐统计
+    pub branches: bool,
+    /// 安全加固检查
+    pub hardening: bool,
+}
+
+impl Profile {
+    /// 内置配置档的名称
+    pub const NAMES: [&'static str; 3] = ["teaching", "performance", "security"];
+
+    /// 按名称获取内置配置档
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "teaching" => Ok(Self { walkthrough: true, returns: true, signature: true, ..Self::default() }),
+            "performance" => Ok(Self { liveness: true, outlining: true, branches: true, ..Self::default() }),
+            "security" => Ok(Self { hardening: true, signature: true, ..Self::default() }),
+            _ => Err(InterpreterError::ParseError(format!(
+                "未知的配置档: {} (可选: {})",
+                name,
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profile() {
+        let teaching = Profile::parse("Teaching").unwrap();
+        assert!(teaching.walkthrough && teaching.signature && !teaching.hardening);
+        assert!(Profile::parse("security").unwrap().hardening);
+        assert!(Profile::parse("fast").is_err());
+    }
+}
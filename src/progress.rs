@@ -0,0 +1,84 @@
+//! 进度通知
+//!
+//! 库代码不直接打印进度，而是把 [`Progress`] 事件交给 [`ProgressSink`]。
+//! 默认的 [`Silent`] 什么也不做，嵌入 GUI 或服务时不会向标准输出写任何内容；
+//! 命令行实现自己的接收者，以彩色文字显示进度。
+
+use std::path::Path;
+
+/// 进度事件
+#[derive(Debug, Clone, Copy)]
+pub enum Progress<'a> {
+    /// 开始读取 dump 文件
+    Reading(&'a str),
+    /// 以某个优化级别编译源文件
+    Compiling { source: &'a Path, level: &'a str, object: &'a str },
+    /// 反汇编目标文件
+    Disassembling { object: &'a str, dump: &'a str },
+    /// 开始生成多级别对比报告
+    GeneratingComparison,
+    /// 开始生成单文件分析报告
+    GeneratingAnalysis,
+    /// 写入输出文件
+    Saving(&'a Path),
+    /// 报告的所有文件已写出
+    Done,
+}
+
+impl Progress<'_> {
+    /// 事件的文字说明
+    pub fn message(&self) -> String {
+        match self {
+            Progress::Reading(path) => format!("读取 {} ...", path),
+            Progress::Compiling { source, level, object } => {
+                format!("编译 {} -{} -> {} ...", source.display(), level, object)
+            }
+            Progress::Disassembling { object, dump } => format!("反汇编 {} -> {} ...", object, dump),
+            Progress::GeneratingComparison => String::from("生成对比表格..."),
+            Progress::GeneratingAnalysis => String::from("生成分析表格..."),
+            Progress::Saving(path) => format!("保存到 {} ...", path.display()),
+            Progress::Done => String::from("完成！"),
+        }
+    }
+}
+
+/// 进度事件的接收者（批量分析时会在多个线程上同时调用）
+pub trait ProgressSink: Send + Sync {
+    fn report(&self, progress: Progress<'_>);
+}
+
+/// 忽略所有进度事件
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Silent;
+
+impl ProgressSink for Silent {
+    fn report(&self, _progress: Progress<'_>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Collect(Mutex<Vec<String>>);
+
+    impl ProgressSink for Collect {
+        fn report(&self, progress: Progress<'_>) {
+            self.0.lock().unwrap().push(progress.message());
+        }
+    }
+
+    #[test]
+    fn test_progress_messages() {
+        let sink = Collect::default();
+        sink.report(Progress::Reading("t_O0.dump"));
+        sink.report(Progress::Saving(Path::new("f_comparison.md")));
+        sink.report(Progress::Done);
+        Silent.report(Progress::Done);
+        assert_eq!(
+            *sink.0.lock().unwrap(),
+            ["读取 t_O0.dump ...", "保存到 f_comparison.md ...", "完成！"]
+        );
+    }
+}
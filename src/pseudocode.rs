@@ -0,0 +1,1052 @@
+//! 类 C 伪代码
+//!
+//! 在指令级控制流图上把函数切分为基本块，把每条指令翻译成赋值、存储、调用、条件跳转等语句，
+//! 再在块内把只使用一次的寄存器定义向前代入（`w8 = w0 + w1; w0 = w8 * 2` 在 w8 之后不再使用时
+//! 合并为 `w0 = (w0 + w1) * 2`），比较与条件跳转合并为 `if (w0 < 10) goto L2;`，检测到的循环输出为
+//! `while (…) { … }`、`do { … } while (…);` 或 `while (1) { … }`。
+//!
+//! 结果只是近似：寄存器即变量，不恢复变量名和类型，无法翻译的指令原样保留为注释。
+
+use crate::analysis::{self, Loop, RegSet};
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::register::{Condition, Register};
+use crate::signature::Signature;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// SP 在寄存器位图中的位置（通用寄存器只用到 0–30）
+const SP_BIT: u32 = 31;
+
+/// 表达式
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Reg(Register),
+    Const(i64),
+    /// 地址或符号
+    Text(String),
+    /// 一元运算或类型转换（`~`、`-`、`(int64_t)`）
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    /// 从地址读取指定 C 类型的值
+    Load(&'static str, Box<Expr>),
+    Call(String, Vec<Expr>),
+    /// 条件 ? a : b
+    Select(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// 比较指令设置的标志位（`cmp a, b`）
+    Compare(Box<Expr>, Box<Expr>),
+    /// 按条件码读取标志位
+    Test(Condition, Box<Expr>),
+    /// 尚未代入比较的标志位
+    Flags,
+}
+
+/// 赋值的目标
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dest {
+    Reg(Register),
+    Flags,
+}
+
+/// 语句
+#[derive(Debug, Clone, PartialEq)]
+enum Stmt {
+    Assign(Dest, Expr),
+    /// 存储：C 类型、地址、值
+    Store(&'static str, Expr, Expr),
+    /// 不使用返回值的调用
+    Call(Expr),
+    /// 条件（None 为无条件）跳转到函数内的地址
+    Branch(Option<Expr>, u64),
+    Return(Option<Expr>),
+    /// 间接跳转
+    Jump(Expr),
+    /// 无法翻译的指令
+    Raw(String),
+}
+
+/// 寄存器在位图中的位置：通用寄存器按编号，SP 为 31，零寄存器等返回 None
+fn reg_bit(reg: Register) -> Option<u32> {
+    match reg {
+        Register::SP => Some(SP_BIT),
+        _ => reg.index().map(|i| i as u32),
+    }
+}
+
+fn reg_name(reg: Register) -> String {
+    format!("{:?}", reg).to_lowercase()
+}
+
+/// 位图：寄存器（含 SP）和标志位
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Bits {
+    regs: u32,
+    flags: bool,
+}
+
+impl Bits {
+    const ALL: Bits = Bits { regs: u32::MAX, flags: true };
+
+    fn has(&self, dest: Dest) -> bool {
+        match dest {
+            Dest::Reg(reg) => reg_bit(reg).is_some_and(|bit| self.regs & (1 << bit) != 0),
+            Dest::Flags => self.flags,
+        }
+    }
+
+    fn intersects(&self, other: Bits) -> bool {
+        self.regs & other.regs != 0 || (self.flags && other.flags)
+    }
+}
+
+/// 调用破坏的寄存器：X0–X18、LR 和标志位
+const CALL_CLOBBERS: Bits = Bits { regs: 0x7_ffff | (1 << 30), flags: true };
+
+impl Expr {
+    fn binary(op: &'static str, a: Expr, b: Expr) -> Expr {
+        match (op, b) {
+            ("+" | "-" | "|" | "^" | "<<" | ">>", Expr::Const(0)) => a,
+            ("+", Expr::Const(n)) if n < 0 => Expr::Binary("-", Box::new(a), Box::new(Expr::Const(-n))),
+            ("-", Expr::Const(n)) if n < 0 => Expr::Binary("+", Box::new(a), Box::new(Expr::Const(-n))),
+            (op, b) => Expr::Binary(op, Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn children(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Unary(_, e) | Expr::Load(_, e) | Expr::Test(_, e) => vec![e],
+            Expr::Binary(_, a, b) | Expr::Compare(a, b) => vec![a, b],
+            Expr::Select(c, a, b) => vec![c, a, b],
+            Expr::Call(_, args) => args.iter().collect(),
+            Expr::Reg(_) | Expr::Const(_) | Expr::Text(_) | Expr::Flags => Vec::new(),
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Expr> {
+        match self {
+            Expr::Unary(_, e) | Expr::Load(_, e) | Expr::Test(_, e) => vec![e],
+            Expr::Binary(_, a, b) | Expr::Compare(a, b) => vec![a, b],
+            Expr::Select(c, a, b) => vec![c, a, b],
+            Expr::Call(_, args) => args.iter_mut().collect(),
+            Expr::Reg(_) | Expr::Const(_) | Expr::Text(_) | Expr::Flags => Vec::new(),
+        }
+    }
+
+    /// 读取的寄存器和标志位
+    fn reads(&self) -> Bits {
+        let mut bits = match self {
+            Expr::Reg(reg) => Bits { regs: reg_bit(*reg).map_or(0, |bit| 1 << bit), flags: false },
+            Expr::Flags => Bits { regs: 0, flags: true },
+            _ => Bits::default(),
+        };
+        for child in self.children() {
+            let child = child.reads();
+            bits.regs |= child.regs;
+            bits.flags |= child.flags;
+        }
+        bits
+    }
+
+    /// 是否读内存或调用函数（不能越过存储和调用移动）
+    fn has_effects(&self) -> bool {
+        matches!(self, Expr::Load(..) | Expr::Call(..)) || self.children().iter().any(|e| e.has_effects())
+    }
+
+    fn has_call(&self) -> bool {
+        matches!(self, Expr::Call(..)) || self.children().iter().any(|e| e.has_call())
+    }
+
+    fn matches(&self, dest: Dest) -> bool {
+        match (self, dest) {
+            (Expr::Reg(reg), Dest::Reg(d)) => reg_bit(*reg).is_some() && reg_bit(*reg) == reg_bit(d),
+            (Expr::Flags, Dest::Flags) => true,
+            _ => false,
+        }
+    }
+
+    fn count(&self, dest: Dest) -> usize {
+        usize::from(self.matches(dest)) + self.children().iter().map(|e| e.count(dest)).sum::<usize>()
+    }
+
+    fn replace(&mut self, dest: Dest, value: &Expr) {
+        if self.matches(dest) {
+            *self = value.clone();
+            return;
+        }
+        for child in self.children_mut() {
+            child.replace(dest, value);
+        }
+    }
+
+    /// 作为运算的操作数时是否需要加括号
+    fn compound(&self) -> bool {
+        matches!(self, Expr::Binary(..) | Expr::Select(..) | Expr::Test(_, _))
+    }
+}
+
+/// 作为子表达式显示（复合表达式加括号）
+struct Paren<'a>(&'a Expr);
+
+impl fmt::Display for Paren<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.compound() {
+            write!(f, "({})", self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Reg(reg) => write!(f, "{}", reg_name(*reg)),
+            Expr::Const(n) if n.unsigned_abs() < 4096 => write!(f, "{}", n),
+            Expr::Const(n) if *n < 0 => write!(f, "-0x{:x}", n.unsigned_abs()),
+            Expr::Const(n) => write!(f, "0x{:x}", n),
+            Expr::Text(text) => write!(f, "{}", text),
+            Expr::Unary(op, e) => write!(f, "{}{}", op, Paren(e)),
+            Expr::Binary(op, a, b) => write!(f, "{} {} {}", Paren(a), op, Paren(b)),
+            Expr::Load(ty, addr) => match **addr {
+                Expr::Reg(_) => write!(f, "*({} *){}", ty, addr),
+                _ => write!(f, "*({} *)({})", ty, addr),
+            },
+            Expr::Call(name, args) => {
+                let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}({})", name, args.join(", "))
+            }
+            Expr::Select(c, a, b) => write!(f, "{} ? {} : {}", Paren(c), Paren(a), Paren(b)),
+            Expr::Compare(a, b) => write!(f, "cmp({}, {})", a, b),
+            Expr::Test(condition, inner) => {
+                let comparison = match condition {
+                    Condition::EQ => Some(("==", false)),
+                    Condition::NE => Some(("!=", false)),
+                    Condition::LT => Some(("<", false)),
+                    Condition::LE => Some(("<=", false)),
+                    Condition::GT => Some((">", false)),
+                    Condition::GE => Some((">=", false)),
+                    Condition::CC => Some(("<", true)),
+                    Condition::LS => Some(("<=", true)),
+                    Condition::HI => Some((">", true)),
+                    Condition::CS => Some((">=", true)),
+                    _ => None,
+                };
+                match (&**inner, comparison) {
+                    (Expr::Compare(a, b), Some((op, false))) => write!(f, "{} {} {}", Paren(a), op, Paren(b)),
+                    (Expr::Compare(a, b), Some((op, true))) => write!(f, "(unsigned){} {} {}", Paren(a), op, Paren(b)),
+                    (Expr::Flags, _) => write!(f, "flags.{}", format!("{:?}", condition).to_lowercase()),
+                    _ => write!(f, "{}.{}", inner, format!("{:?}", condition).to_lowercase()),
+                }
+            }
+            Expr::Flags => write!(f, "flags"),
+        }
+    }
+}
+
+impl Stmt {
+    fn exprs(&self) -> Vec<&Expr> {
+        match self {
+            Stmt::Assign(_, e) | Stmt::Call(e) | Stmt::Jump(e) | Stmt::Return(Some(e)) | Stmt::Branch(Some(e), _) => vec![e],
+            Stmt::Store(_, addr, value) => vec![addr, value],
+            Stmt::Return(None) | Stmt::Branch(None, _) | Stmt::Raw(_) => Vec::new(),
+        }
+    }
+
+    fn exprs_mut(&mut self) -> Vec<&mut Expr> {
+        match self {
+            Stmt::Assign(_, e) | Stmt::Call(e) | Stmt::Jump(e) | Stmt::Return(Some(e)) | Stmt::Branch(Some(e), _) => vec![e],
+            Stmt::Store(_, addr, value) => vec![addr, value],
+            Stmt::Return(None) | Stmt::Branch(None, _) | Stmt::Raw(_) => Vec::new(),
+        }
+    }
+
+    /// 读取的寄存器和标志位（无法翻译的指令视为读取所有寄存器，但不读标志位）
+    fn reads(&self) -> Bits {
+        if let Stmt::Raw(_) = self {
+            return Bits { regs: u32::MAX, flags: false };
+        }
+        self.exprs().iter().fold(Bits::default(), |bits, e| {
+            let e = e.reads();
+            Bits { regs: bits.regs | e.regs, flags: bits.flags || e.flags }
+        })
+    }
+
+    fn clobbers(&self) -> Bits {
+        let mut bits = match self {
+            Stmt::Raw(_) => return Bits::ALL,
+            Stmt::Assign(Dest::Reg(reg), _) => Bits { regs: reg_bit(*reg).map_or(0, |bit| 1 << bit), flags: false },
+            Stmt::Assign(Dest::Flags, _) => Bits { regs: 0, flags: true },
+            _ => Bits::default(),
+        };
+        if self.exprs().iter().any(|e| e.has_call()) {
+            bits.regs |= CALL_CLOBBERS.regs;
+            bits.flags = true;
+        }
+        bits
+    }
+
+    /// 是否为不能让读内存的表达式越过的语句（存储、调用和无法翻译的指令）
+    fn is_barrier(&self) -> bool {
+        matches!(self, Stmt::Store(..) | Stmt::Raw(_)) || self.exprs().iter().any(|e| e.has_call())
+    }
+}
+
+/// 一条语句及其来源指令的下标
+struct Line {
+    inst: usize,
+    stmt: Stmt,
+    removed: bool,
+}
+
+/// 翻译时的块内状态
+struct Translator<'a> {
+    instructions: &'a [Instruction],
+    /// 每条指令执行后活跃的寄存器
+    live_out: &'a [RegSet],
+    /// 块内最近写入的参数寄存器（用于推测调用的实参）
+    args: [Option<Register>; 8],
+    /// 返回值寄存器（void 函数为 None）
+    ret: Option<Register>,
+}
+
+/// 条件分支指令的条件码
+pub(crate) fn branch_condition(ty: InstructionType) -> Option<Condition> {
+    use InstructionType::*;
+    Some(match ty {
+        BEQ => Condition::EQ,
+        BNE => Condition::NE,
+        BCS => Condition::CS,
+        BCC => Condition::CC,
+        BMI => Condition::MI,
+        BPL => Condition::PL,
+        BVS => Condition::VS,
+        BVC => Condition::VC,
+        BHI => Condition::HI,
+        BLS => Condition::LS,
+        BGE => Condition::GE,
+        BLT => Condition::LT,
+        BGT => Condition::GT,
+        BLE => Condition::LE,
+        _ => return None,
+    })
+}
+
+/// 访存的 C 类型
+fn memory_type(ty: InstructionType, reg: Register) -> Option<&'static str> {
+    use InstructionType::*;
+    let name = format!("{:?}", reg);
+    Some(match ty {
+        LDRB | STRB => "uint8_t",
+        LDRH | STRH => "uint16_t",
+        LDRSB => "int8_t",
+        LDRSH => "int16_t",
+        LDRSW => "int32_t",
+        _ if name.starts_with('W') => "int32_t",
+        _ if name.starts_with('X') || matches!(reg, Register::FP | Register::LR) => "int64_t",
+        _ if name.starts_with('S') && reg != Register::SP => "float",
+        _ if name.starts_with('D') => "double",
+        _ => return None,
+    })
+}
+
+/// 寄存器或立即数操作数
+fn value(op: &Operand) -> Option<Expr> {
+    match op {
+        Operand::Register(Register::XZR | Register::WZR) => Some(Expr::Const(0)),
+        Operand::Register(reg) => Some(Expr::Reg(*reg)),
+        Operand::Immediate(n) => Some(Expr::Const(*n)),
+        Operand::Label(label) => {
+            let address = label.split_whitespace().next()?;
+            Some(Expr::Text(match crate::objdump::target_symbol(label) {
+                Some(symbol) if !symbol.contains('+') => format!("&{}", symbol),
+                _ => format!("0x{}", address.trim_start_matches("0x")),
+            }))
+        }
+        Operand::Memory { .. } => None,
+    }
+}
+
+/// 第 i 个操作数，后面跟着移位或扩展（`lsl #2`、`sxtw`）时一并应用
+fn source(ops: &[Operand], i: usize) -> Option<Expr> {
+    let expr = value(ops.get(i)?)?;
+    let Some(Operand::Label(modifier)) = ops.get(i + 1) else {
+        return Some(expr);
+    };
+    let mut parts = modifier.split_whitespace();
+    let kind = parts.next()?;
+    let amount = parts.next().and_then(|n| n.trim_start_matches('#').parse::<i64>().ok());
+    let cast = |ty: &'static str| Expr::Unary(ty, Box::new(expr.clone()));
+    let extended = match kind {
+        "lsl" => return Some(Expr::binary("<<", expr, Expr::Const(amount?))),
+        "lsr" | "asr" => return Some(Expr::binary(">>", expr, Expr::Const(amount?))),
+        "sxtw" => cast("(int64_t)"),
+        "uxtw" => cast("(uint64_t)"),
+        "sxtb" => cast("(int8_t)"),
+        "uxtb" => cast("(uint8_t)"),
+        "sxth" => cast("(int16_t)"),
+        "uxth" => cast("(uint16_t)"),
+        _ => return None,
+    };
+    Some(match amount {
+        Some(n) => Expr::binary("<<", extended, Expr::Const(n)),
+        None => extended,
+    })
+}
+
+impl Translator<'_> {
+    /// 实参：块内写入过的最后一个参数寄存器及其之前的所有参数寄存器
+    fn call(&mut self, name: String) -> Expr {
+        let count = self.args.iter().rposition(|a| a.is_some()).map_or(0, |last| last + 1);
+        let args = (0..count)
+            .map(|n| Expr::Reg(self.args[n].unwrap_or_else(|| Register::parse(&format!("x{}", n)).unwrap_or(Register::X0))))
+            .collect();
+        self.args = [None; 8];
+        Expr::Call(name, args)
+    }
+
+    fn call_name(label: &str) -> String {
+        match crate::objdump::target_symbol(label) {
+            Some(symbol) => symbol.trim_end_matches("@plt").to_string(),
+            None => format!("sub_{}", label.split_whitespace().next().unwrap_or(label)),
+        }
+    }
+
+    fn is_internal(&self, address: u64) -> bool {
+        self.instructions.iter().any(|inst| inst.address == address)
+    }
+
+    /// 翻译一条指令，无法翻译时返回 None
+    fn translate(&mut self, i: usize) -> Option<Vec<Stmt>> {
+        use InstructionType::*;
+
+        let inst = &self.instructions[i];
+        let ops = inst.operands.as_slice();
+        let reg = |n: usize| match ops.get(n) {
+            Some(Operand::Register(reg)) => Some(*reg),
+            _ => None,
+        };
+        let assign = |expr: Expr| Some(vec![Stmt::Assign(Dest::Reg(reg(0)?), expr)]);
+        let binary = |op: &'static str| assign(Expr::binary(op, source(ops, 1)?, source(ops, 2)?));
+        let condition = || Expr::Test(inst.condition.unwrap_or(Condition::AL), Box::new(Expr::Flags));
+        let select = |a: Expr, b: Expr| assign(Expr::Select(Box::new(condition()), Box::new(a), Box::new(b)));
+        let compare = |a: Expr, b: Expr| Some(vec![Stmt::Assign(Dest::Flags, Expr::Compare(Box::new(a), Box::new(b)))]);
+
+        let stmts = match inst.instruction_type {
+            MOV | MOVZ => assign(source(ops, 1)?),
+            MOVN => assign(Expr::Unary("~", Box::new(source(ops, 1)?))),
+            MOVK => assign(Expr::binary("|", Expr::Reg(reg(0)?), source(ops, 1)?)),
+            MVN => assign(Expr::Unary("~", Box::new(source(ops, 1)?))),
+            NEG => assign(Expr::Unary("-", Box::new(source(ops, 1)?))),
+            ADD => binary("+"),
+            SUB => binary("-"),
+            MUL => binary("*"),
+            UDIV | SDIV => binary("/"),
+            AND => binary("&"),
+            ORR => binary("|"),
+            EOR => binary("^"),
+            LSL => binary("<<"),
+            LSR | ASR => binary(">>"),
+            BIC | ORN | EON => {
+                let op = match inst.instruction_type {
+                    BIC => "&",
+                    ORN => "|",
+                    _ => "^",
+                };
+                assign(Expr::binary(op, source(ops, 1)?, Expr::Unary("~", Box::new(source(ops, 2)?))))
+            }
+            MADD | MSUB => {
+                let product = Expr::binary("*", source(ops, 1)?, source(ops, 2)?);
+                let op = if inst.instruction_type == MADD { "+" } else { "-" };
+                assign(Expr::binary(op, source(ops, 3)?, product))
+            }
+            SMULL | UMULL => {
+                let cast = if inst.instruction_type == SMULL { "(int64_t)" } else { "(uint64_t)" };
+                let a = Expr::Unary(cast, Box::new(source(ops, 1)?));
+                let b = Expr::Unary(cast, Box::new(source(ops, 2)?));
+                assign(Expr::binary("*", a, b))
+            }
+            CSEL => select(source(ops, 1)?, source(ops, 2)?),
+            CSINC => select(source(ops, 1)?, Expr::binary("+", source(ops, 2)?, Expr::Const(1))),
+            CSINV => select(source(ops, 1)?, Expr::Unary("~", Box::new(source(ops, 2)?))),
+            CSNEG => select(source(ops, 1)?, Expr::Unary("-", Box::new(source(ops, 2)?))),
+            CSET => assign(condition()),
+            CSETM => select(Expr::Const(-1), Expr::Const(0)),
+            CINC => select(Expr::binary("+", source(ops, 1)?, Expr::Const(1)), source(ops, 1)?),
+            CINV => select(Expr::Unary("~", Box::new(source(ops, 1)?)), source(ops, 1)?),
+            CNEG => select(Expr::Unary("-", Box::new(source(ops, 1)?)), source(ops, 1)?),
+            CMP => compare(source(ops, 0)?, source(ops, 1)?),
+            CMN => compare(source(ops, 0)?, Expr::Unary("-", Box::new(source(ops, 1)?))),
+            TST => compare(Expr::binary("&", source(ops, 0)?, source(ops, 1)?), Expr::Const(0)),
+            LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | LDUR | STR | STRB | STRH | STUR => {
+                self.memory(inst, &[reg(0)?], 1)
+            }
+            LDP | STP => self.memory(inst, &[reg(0)?, reg(1)?], 2),
+            ADRP | ADR => assign(value(ops.get(1)?)?),
+            B if inst.condition.is_none() => {
+                let target = analysis::branch_target(inst)?;
+                if self.is_internal(target) {
+                    Some(vec![Stmt::Branch(None, target)])
+                } else {
+                    // 跳出函数的无条件跳转是尾调用
+                    let Some(Operand::Label(label)) = ops.first() else {
+                        return None;
+                    };
+                    let call = self.call(Self::call_name(label));
+                    Some(vec![Stmt::Return(Some(call))])
+                }
+            }
+            ty if branch_condition(ty).is_some() => {
+                let condition = Expr::Test(branch_condition(ty)?, Box::new(Expr::Flags));
+                Some(vec![Stmt::Branch(Some(condition), analysis::branch_target(inst)?)])
+            }
+            CBZ | CBNZ => {
+                let op = if inst.instruction_type == CBZ { "==" } else { "!=" };
+                let test = Expr::binary(op, value(ops.first()?)?, Expr::Const(0));
+                Some(vec![Stmt::Branch(Some(test), analysis::branch_target(inst)?)])
+            }
+            TBZ | TBNZ => {
+                let Some(Operand::Immediate(bit)) = ops.get(1) else {
+                    return None;
+                };
+                let op = if inst.instruction_type == TBZ { "==" } else { "!=" };
+                let masked = Expr::binary("&", value(ops.first()?)?, Expr::Const(1i64.checked_shl(*bit as u32)?));
+                Some(vec![Stmt::Branch(Some(Expr::binary(op, masked, Expr::Const(0))), analysis::branch_target(inst)?)])
+            }
+            BL | BLR => {
+                let name = match ops.first()? {
+                    Operand::Label(label) => Self::call_name(label),
+                    Operand::Register(reg) => format!("(*{})", reg_name(*reg)),
+                    _ => return None,
+                };
+                let call = self.call(name);
+                // 返回值之后不再使用时只保留调用
+                if self.live_out[i].contains(0) {
+                    Some(vec![Stmt::Assign(Dest::Reg(Register::X0), call)])
+                } else {
+                    Some(vec![Stmt::Call(call)])
+                }
+            }
+            RET => Some(vec![Stmt::Return(self.ret.map(Expr::Reg))]),
+            BR => Some(vec![Stmt::Jump(source(ops, 0)?)]),
+            NOP => Some(Vec::new()),
+            _ => None,
+        }?;
+
+        for stmt in &stmts {
+            if let Stmt::Assign(Dest::Reg(reg), _) = stmt {
+                if let Some(n) = reg.index().filter(|n| *n < 8) {
+                    self.args[n] = Some(*reg);
+                }
+            }
+        }
+        Some(stmts)
+    }
+
+    /// 加载/存储（含成对访问与前/后变址回写）
+    fn memory(&self, inst: &Instruction, regs: &[Register], mem: usize) -> Option<Vec<Stmt>> {
+        let ops = inst.operands.as_slice();
+        let Some(Operand::Memory { base, offset, index, pre_indexed, .. }) = ops.get(mem) else {
+            return None;
+        };
+        let ty = memory_type(inst.instruction_type, regs[0])?;
+        let size = match ty {
+            "int64_t" | "double" => 8,
+            _ => 4,
+        };
+        let load = matches!(
+            inst.instruction_type,
+            InstructionType::LDR | InstructionType::LDRB | InstructionType::LDRH | InstructionType::LDRSB
+                | InstructionType::LDRSH | InstructionType::LDRSW | InstructionType::LDUR | InstructionType::LDP
+        );
+        let post = match ops.get(mem + 1) {
+            Some(Operand::Immediate(n)) => Some(*n),
+            _ => None,
+        };
+
+        let mut stmts = Vec::new();
+        let base_expr = Expr::Reg(*base);
+        let mut address = base_expr.clone();
+        if *pre_indexed {
+            stmts.push(Stmt::Assign(Dest::Reg(*base), Expr::binary("+", base_expr.clone(), Expr::Const(offset.unwrap_or(0)))));
+        } else if post.is_none() {
+            address = Expr::binary("+", address, Expr::Const(offset.unwrap_or(0)));
+            if let Some(index) = index {
+                address = Expr::binary("+", address, Expr::Reg(*index));
+            }
+        }
+        for (n, reg) in regs.iter().enumerate() {
+            let at = Expr::binary("+", address.clone(), Expr::Const(n as i64 * size));
+            let reg_value = value(&Operand::Register(*reg))?;
+            stmts.push(if load {
+                Stmt::Assign(Dest::Reg(*reg), Expr::Load(ty, Box::new(at)))
+            } else {
+                Stmt::Store(ty, at, reg_value)
+            });
+        }
+        if let Some(n) = post {
+            stmts.push(Stmt::Assign(Dest::Reg(*base), Expr::binary("+", base_expr, Expr::Const(n))));
+        }
+        Some(stmts)
+    }
+}
+
+/// 块内的向前代入：定义只被下一处使用读取一次、之后不再活跃时合并到使用处
+fn fold(lines: &mut [Line], live_out: &[RegSet], flags_live_out: bool) {
+    for i in 0..lines.len() {
+        let Stmt::Assign(dest, ref expr) = lines[i].stmt else {
+            continue;
+        };
+        let expr = expr.clone();
+        // SP、帧指针等不代入
+        if let Dest::Reg(reg) = dest {
+            if reg_bit(reg).is_none_or(|bit| bit >= 29) {
+                continue;
+            }
+        }
+        let reads = expr.reads();
+
+        let Some(j) = (i + 1..lines.len()).find(|&k| {
+            !lines[k].removed && (lines[k].stmt.reads().has(dest) || lines[k].stmt.clobbers().has(dest))
+        }) else {
+            continue;
+        };
+        let target = &lines[j].stmt;
+        if !target.reads().has(dest) || matches!(target, Stmt::Raw(_)) {
+            continue;
+        }
+        if target.exprs().iter().map(|e| e.count(dest)).sum::<usize>() != 1 {
+            continue;
+        }
+        let between = &lines[i + 1..j];
+        if between.iter().any(|l| !l.removed && l.stmt.clobbers().intersects(reads)) {
+            continue;
+        }
+        if expr.has_effects() && between.iter().any(|l| !l.removed && l.stmt.is_barrier()) {
+            continue;
+        }
+        // 使用之后值不能再被读取：块内后续语句或后继块
+        let still_used = match dest {
+            Dest::Reg(reg) => {
+                !target.clobbers().has(dest) && live_out[lines[j].inst].contains(reg.index().unwrap_or(0))
+            }
+            Dest::Flags => {
+                let later = lines[j + 1..]
+                    .iter()
+                    .filter(|l| !l.removed)
+                    .find(|l| l.stmt.reads().has(dest) || l.stmt.clobbers().has(dest));
+                match later {
+                    Some(l) => l.stmt.reads().has(dest),
+                    None => flags_live_out && !target.clobbers().has(dest),
+                }
+            }
+        };
+        // 同一条指令产生的后续语句（如后变址回写）也可能读取该值
+        if still_used {
+            continue;
+        }
+
+        for e in lines[j].stmt.exprs_mut() {
+            e.replace(dest, &expr);
+        }
+        lines[i].removed = true;
+    }
+}
+
+/// 输出片段
+enum Piece {
+    Label(usize),
+    Text(String),
+}
+
+/// 生成伪代码时的上下文
+struct Emitter<'a> {
+    instructions: &'a [Instruction],
+    /// 每条指令的语句
+    lines: Vec<Vec<Stmt>>,
+    loops: &'a [Loop],
+    /// 可以写成 `while (条件) { … }` 的循环：循环头 → 条件块的起点
+    top_tested: &'a HashMap<usize, usize>,
+    /// 标签编号（按目标指令下标）
+    labels: &'a HashMap<usize, usize>,
+    output: Vec<(usize, Piece)>,
+    /// 仍以 goto 引用的跳转目标
+    used_labels: BTreeSet<usize>,
+}
+
+impl Emitter<'_> {
+    fn index_of(&self, address: u64) -> Option<usize> {
+        self.instructions.iter().position(|inst| inst.address == address)
+    }
+
+    fn push(&mut self, depth: usize, text: String) {
+        self.output.push((depth, Piece::Text(text)));
+    }
+
+    /// 跳转语句：在循环内跳到循环出口为 break、跳到循环头为 continue
+    fn jump(&mut self, target: u64, enclosing: Option<Loop>) -> String {
+        let Some(t) = self.index_of(target) else {
+            return format!("goto 0x{:x}", target);
+        };
+        if let Some(l) = enclosing {
+            if t == l.latch + 1 {
+                return String::from("break");
+            }
+            if t == l.head {
+                return String::from("continue");
+            }
+        }
+        self.used_labels.insert(t);
+        format!("goto L{}", self.labels.get(&t).copied().unwrap_or(0))
+    }
+
+    fn stmt(&mut self, stmt: &Stmt, depth: usize, enclosing: Option<Loop>) {
+        let text = match stmt {
+            Stmt::Assign(Dest::Reg(reg), e) => format!("{} = {};", reg_name(*reg), e),
+            Stmt::Assign(Dest::Flags, e) => format!("flags = {};", e),
+            Stmt::Store(ty, addr, value) => match addr {
+                Expr::Reg(_) => format!("*({} *){} = {};", ty, addr, value),
+                _ => format!("*({} *)({}) = {};", ty, addr, value),
+            },
+            Stmt::Call(e) => format!("{};", e),
+            Stmt::Branch(None, target) => format!("{};", self.jump(*target, enclosing)),
+            Stmt::Branch(Some(condition), target) => format!("if ({}) {};", condition, self.jump(*target, enclosing)),
+            Stmt::Return(Some(e)) => format!("return {};", e),
+            Stmt::Return(None) => String::from("return;"),
+            Stmt::Jump(e) => format!("goto *{};", e),
+            Stmt::Raw(asm) => format!("/* {} */", asm),
+        };
+        self.push(depth, text);
+    }
+
+    /// 循环的回边是否为跳回循环头的分支（翻译失败时不按循环输出）
+    fn has_back_edge(&self, l: Loop) -> bool {
+        match self.lines[l.latch].last() {
+            Some(Stmt::Branch(_, target)) => self.index_of(*target) == Some(l.head),
+            _ => false,
+        }
+    }
+
+    /// 输出指令下标 [start, end) 的代码
+    fn range(&mut self, start: usize, end: usize, depth: usize, enclosing: Option<Loop>) {
+        let mut i = start;
+        while i < end {
+            self.output.push((depth, Piece::Label(i)));
+            let l = self
+                .loops
+                .iter()
+                .filter(|l| l.head == i && l.latch < end && Some(**l) != enclosing)
+                .max_by_key(|l| l.latch)
+                .copied()
+                .filter(|l| self.has_back_edge(*l));
+            if let Some(l) = l {
+                self.structured(l, depth);
+                i = l.latch + 1;
+                continue;
+            }
+            // `while` 循环入口处跳到条件判断的 b 已由循环结构表达
+            let enters = self.top_tested.get(&(i + 1)).is_some_and(|&check| {
+                analysis::branch_target(&self.instructions[i]) == Some(self.instructions[check].address)
+            });
+            if !enters {
+                for stmt in self.lines[i].clone() {
+                    self.stmt(&stmt, depth, enclosing);
+                }
+            }
+            i += 1;
+        }
+    }
+
+    /// 输出一个循环，回边由循环结构表达
+    fn structured(&mut self, l: Loop, depth: usize) {
+        let condition = match self.lines[l.latch].pop() {
+            Some(Stmt::Branch(condition, _)) => condition,
+            _ => None,
+        };
+
+        if let (Some(&check), Some(condition)) = (self.top_tested.get(&l.head), condition.as_ref()) {
+            self.push(depth, format!("while ({}) {{", condition));
+            self.range(l.head, check, depth + 1, Some(l));
+            self.push(depth, String::from("}"));
+            self.output.push((depth, Piece::Label(check)));
+            return;
+        }
+        self.push(depth, String::from(if condition.is_some() { "do {" } else { "while (1) {" }));
+        self.range(l.head, l.latch + 1, depth + 1, Some(l));
+        match condition {
+            Some(condition) => self.push(depth, format!("}} while ({});", condition)),
+            None => self.push(depth, String::from("}")),
+        }
+    }
+}
+
+/// 每条指令执行后活跃的寄存器
+///
+/// 与 [`analysis::LivenessAnalysis`] 不同，调用只使用块内在调用前写入的参数寄存器，
+/// 返回只在函数有返回值时使用 X0，否则调用前的大部分赋值都会被当作参数而无法代入
+fn live_out(instructions: &[Instruction], args: &[usize], returns: bool) -> Vec<RegSet> {
+    use InstructionType::*;
+
+    let (defs, uses): (Vec<RegSet>, Vec<RegSet>) = instructions
+        .iter()
+        .enumerate()
+        .map(|(i, inst)| {
+            let (defs, uses) = analysis::def_use(inst);
+            let args = match args[i] {
+                0 => RegSet::new(),
+                n => RegSet::range(0, n - 1),
+            };
+            let uses = match inst.instruction_type {
+                BL | BLR | B => {
+                    let mut uses = uses.difference(RegSet::range(0, 7)).union(args);
+                    if let Some(Operand::Register(reg)) = inst.operands.first() {
+                        uses.insert_reg(*reg);
+                    }
+                    uses
+                }
+                RET if returns => RegSet::range(0, 0),
+                RET => RegSet::new(),
+                _ => uses,
+            };
+            (defs, uses)
+        })
+        .unzip();
+
+    let successors: Vec<Vec<usize>> = (0..instructions.len()).map(|i| analysis::successors(instructions, i)).collect();
+    let mut live_in = vec![RegSet::new(); instructions.len()];
+    let mut live_out = vec![RegSet::new(); instructions.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in (0..instructions.len()).rev() {
+            let out = successors[i].iter().fold(RegSet::new(), |set, s| set.union(live_in[*s]));
+            let input = uses[i].union(out.difference(defs[i]));
+            if out != live_out[i] || input != live_in[i] {
+                live_out[i] = out;
+                live_in[i] = input;
+                changed = true;
+            }
+        }
+    }
+    live_out
+}
+
+/// 生成函数的类 C 伪代码，函数中没有可解析的指令时返回 None
+pub fn generate(name: &str, entries: &[DumpEntry], lang: Lang) -> Option<String> {
+    let instructions: Vec<Instruction> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+    if instructions.is_empty() {
+        return None;
+    }
+    let signature = Signature::infer(name, entries, lang)?;
+    let ret = match signature.return_type.as_str() {
+        "void" => None,
+        "int" => Some(Register::W0),
+        _ => Some(Register::X0),
+    };
+    let index_of = |address: u64| instructions.iter().position(|x| x.address == address);
+
+    // 基本块的起点：函数入口、跳转目标和分支之后的指令
+    let mut leaders = BTreeSet::from([0]);
+    for (i, inst) in instructions.iter().enumerate() {
+        if analysis::is_branch(inst) || matches!(inst.instruction_type, InstructionType::RET | InstructionType::BR) {
+            leaders.insert(i + 1);
+        }
+        if let Some(t) = analysis::branch_target(inst).and_then(index_of).filter(|_| analysis::is_branch(inst)) {
+            leaders.insert(t);
+        }
+    }
+
+    // 调用的实参个数：块内在调用前写入的最后一个参数寄存器
+    let mut args = vec![0; instructions.len()];
+    let mut written = 0;
+    for (i, inst) in instructions.iter().enumerate() {
+        if leaders.contains(&i) {
+            written = 0;
+        }
+        if matches!(inst.instruction_type, InstructionType::BL | InstructionType::BLR | InstructionType::B) {
+            args[i] = written;
+        }
+        if matches!(inst.instruction_type, InstructionType::BL | InstructionType::BLR) {
+            written = 0;
+        } else if let Some(last) = analysis::def_use(inst).0.iter().filter(|r| *r < 8).last() {
+            written = written.max(last + 1);
+        }
+    }
+    let live_out = live_out(&instructions, &args, ret.is_some());
+
+    // 翻译：无法解析的指令作为注释放在下一条指令之前
+    let mut translator = Translator { instructions: &instructions, live_out: &live_out, args: [None; 8], ret };
+    let mut lines: Vec<Line> = Vec::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut i = 0;
+    for entry in entries.iter().filter(|e| !e.asm_instruction.is_empty()) {
+        let asm = entry.asm_instruction.split("//").next().unwrap_or("").trim().replace('\t', " ");
+        if entry.parsed_instruction.is_none() {
+            pending.push(asm);
+            continue;
+        }
+        if leaders.contains(&i) {
+            translator.args = [None; 8];
+        }
+        lines.extend(pending.drain(..).map(|asm| Line { inst: i, stmt: Stmt::Raw(asm), removed: false }));
+        let stmts = translator.translate(i).unwrap_or_else(|| vec![Stmt::Raw(asm)]);
+        lines.extend(stmts.into_iter().map(|stmt| Line { inst: i, stmt, removed: false }));
+        i += 1;
+    }
+    let last = instructions.len() - 1;
+    lines.extend(pending.into_iter().map(|asm| Line { inst: last, stmt: Stmt::Raw(asm), removed: false }));
+
+    // 逐块代入；标志位在后继块中先被读取时不能合并到本块的条件跳转中
+    let blocks: Vec<usize> = leaders.iter().copied().filter(|&l| l <= last).collect();
+    let block_range = |lines: &[Line], block: usize| {
+        let end = blocks.iter().copied().find(|&b| b > block).unwrap_or(usize::MAX);
+        let first = lines.iter().position(|l| l.inst >= block).unwrap_or(lines.len());
+        let last = lines.iter().position(|l| l.inst >= end).unwrap_or(lines.len());
+        first..last
+    };
+    let reads_flags_first = |lines: &[Line]| {
+        lines
+            .iter()
+            .find(|l| l.stmt.reads().flags || l.stmt.clobbers().flags)
+            .is_some_and(|l| l.stmt.reads().flags)
+    };
+    for &block in &blocks {
+        let range = block_range(&lines, block);
+        let Some(end) = lines[range.clone()].last().map(|l| l.inst) else {
+            continue;
+        };
+        let flags_live_out = analysis::successors(&instructions, end)
+            .into_iter()
+            .any(|s| reads_flags_first(&lines[block_range(&lines, s).start..]));
+        fold(&mut lines[range], &live_out, flags_live_out);
+    }
+
+    let mut per_instruction: Vec<Vec<Stmt>> = vec![Vec::new(); instructions.len()];
+    for line in lines.into_iter().filter(|l| !l.removed) {
+        per_instruction[line.inst].push(line.stmt);
+    }
+
+    // 先跳到条件判断、条件块只剩回边跳转的循环写成 while (条件)
+    let loops = analysis::find_loops(&instructions);
+    let mut top_tested = HashMap::new();
+    for l in &loops {
+        let Some(entry) = l.head.checked_sub(1).map(|e| &instructions[e]) else {
+            continue;
+        };
+        if entry.instruction_type != InstructionType::B || entry.condition.is_some() {
+            continue;
+        }
+        let Some(check) = analysis::branch_target(entry).and_then(index_of) else {
+            continue;
+        };
+        let only_branch = (check..l.latch).all(|k| per_instruction[k].is_empty())
+            && matches!(per_instruction[l.latch].as_slice(), [Stmt::Branch(Some(_), _)]);
+        if check > l.head && check <= l.latch && only_branch {
+            top_tested.insert(l.head, check);
+        }
+    }
+
+    // 先输出一遍确定仍以 goto 引用的标签，再按出现顺序编号
+    let emit = |labels: &HashMap<usize, usize>| {
+        let mut emitter = Emitter {
+            instructions: &instructions,
+            lines: per_instruction.clone(),
+            loops: &loops,
+            top_tested: &top_tested,
+            labels,
+            output: Vec::new(),
+            used_labels: BTreeSet::new(),
+        };
+        emitter.range(0, instructions.len(), 1, None);
+        (emitter.output, emitter.used_labels)
+    };
+    let (_, used) = emit(&HashMap::new());
+    let labels: HashMap<usize, usize> = used.iter().enumerate().map(|(n, &t)| (t, n + 1)).collect();
+    let (output, _) = emit(&labels);
+
+    let parameters: Vec<String> = signature
+        .parameters
+        .iter()
+        .filter(|p| !p.location.starts_with('['))
+        .map(|p| format!("{} {}", p.ty, p.location))
+        .collect();
+    let mut text = format!(
+        "// {}\n{} {}({}) {{\n",
+        lang.pick(
+            "近似的伪代码：寄存器即变量，控制流由跳转还原，仅供阅读参考",
+            "approximate pseudo-code: registers act as variables, control flow rebuilt from branches"
+        ),
+        signature.return_type,
+        name,
+        parameters.join(", ")
+    );
+    for (depth, piece) in output {
+        match piece {
+            Piece::Text(line) => text.push_str(&format!("{}{}\n", "    ".repeat(depth), line)),
+            Piece::Label(i) => {
+                if let Some(n) = labels.get(&i) {
+                    text.push_str(&format!("{}L{}:\n", "    ".repeat(depth - 1), n));
+                }
+            }
+        }
+    }
+    text.push_str("}\n");
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    fn pseudocode(dump: &str, name: &str) -> String {
+        let parser = ObjdumpParser::new(dump.to_string());
+        let entries = parser.extract_function_data(name).unwrap();
+        generate(name, &entries, Lang::Zh).unwrap()
+    }
+
+    #[test]
+    fn test_do_while() {
+        let dump = r#"
+0000000000000000 <sum>:
+   0:   2a1f03e2    mov w2, wzr
+   4:   34000101    cbz w1, 24 <sum+0x24>
+   8:   b8404403    ldr w3, [x0], #4
+   c:   0b030042    add w2, w2, w3
+  10:   71000421    subs w1, w1, #1
+  14:   7100003f    cmp w1, #0x0
+  18:   54ffff81    b.ne 8 <sum+0x8>
+  1c:   2a0203e0    mov w0, w2
+  20:   d65f03c0    ret
+  24:   2a1f03e0    mov w0, wzr
+  28:   d65f03c0    ret
+"#;
+        let text = pseudocode(dump, "sum");
+        assert!(text.contains("int sum("), "{}", text);
+        assert!(text.contains("    w2 = 0;\n"), "{}", text);
+        assert!(text.contains("    if (w1 == 0) goto L1;\n"), "{}", text);
+        assert!(text.contains("    do {\n        w3 = *(int32_t *)x0;\n        x0 = x0 + 4;\n        w2 = w2 + w3;\n        /* subs w1, w1, #1 */\n    } while (w1 != 0);\n"), "{}", text);
+        assert!(text.contains("    return w2;\nL1:\n    return 0;\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_while_loop() {
+        let dump = r#"
+0000000000000000 <count>:
+   0:   b9000fff    str wzr, [sp, #12]
+   4:   14000004    b 14 <count+0x14>
+   8:   b9400fe0    ldr w0, [sp, #12]
+   c:   11000400    add w0, w0, #0x1
+  10:   b9000fe0    str w0, [sp, #12]
+  14:   b9400fe0    ldr w0, [sp, #12]
+  18:   7100241f    cmp w0, #0x9
+  1c:   54ffff6d    b.le 8 <count+0x8>
+  20:   94000000    bl 0 <done>
+  24:   d65f03c0    ret
+"#;
+        let text = pseudocode(dump, "count");
+        assert!(text.contains(
+            "    *(int32_t *)(sp + 12) = 0;\n    while (*(int32_t *)(sp + 12) <= 9) {\n        *(int32_t *)(sp + 12) = *(int32_t *)(sp + 12) + 1;\n    }\n    return done();\n"
+        ), "{}", text);
+    }
+}
@@ -25,10 +25,28 @@ pub enum Register {
     // 帧指针和链接寄存器（别名）
     FP,   // 帧指针，相当于 X29
     LR,   // 链接寄存器，相当于 X30
+    
+    // 64位浮点寄存器（V寄存器的低64位）
+    D0, D1, D2, D3, D4, D5, D6, D7, D8, D9,
+    D10, D11, D12, D13, D14, D15, D16, D17, D18, D19,
+    D20, D21, D22, D23, D24, D25, D26, D27, D28, D29,
+    D30, D31,
+    
+    // 32位浮点寄存器（V寄存器的低32位）
+    S0, S1, S2, S3, S4, S5, S6, S7, S8, S9,
+    S10, S11, S12, S13, S14, S15, S16, S17, S18, S19,
+    S20, S21, S22, S23, S24, S25, S26, S27, S28, S29,
+    S30, S31,
+    
+    // 16位浮点寄存器（V寄存器的低16位）
+    H0, H1, H2, H3, H4, H5, H6, H7, H8, H9,
+    H10, H11, H12, H13, H14, H15, H16, H17, H18, H19,
+    H20, H21, H22, H23, H24, H25, H26, H27, H28, H29,
+    H30, H31,
 }
 
 /// 条件标志位
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct ConditionFlags {
     pub n: bool,  // Negative
     pub z: bool,  // Zero
@@ -36,17 +54,6 @@ pub struct ConditionFlags {
     pub v: bool,  // Overflow
 }
 
-impl Default for ConditionFlags {
-    fn default() -> Self {
-        Self {
-            n: false,
-            z: false,
-            c: false,
-            v: false,
-        }
-    }
-}
-
 impl ConditionFlags {
     /// 创建新的条件标志位
     pub fn new() -> Self {
@@ -86,6 +93,28 @@ pub enum Condition {
 }
 
 impl Condition {
+    /// 解析条件码名称（eq、ne、lt 等，hs/lo 为 cs/cc 的别名）
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "eq" => Ok(Condition::EQ),
+            "ne" => Ok(Condition::NE),
+            "cs" | "hs" => Ok(Condition::CS),
+            "cc" | "lo" => Ok(Condition::CC),
+            "mi" => Ok(Condition::MI),
+            "pl" => Ok(Condition::PL),
+            "vs" => Ok(Condition::VS),
+            "vc" => Ok(Condition::VC),
+            "hi" => Ok(Condition::HI),
+            "ls" => Ok(Condition::LS),
+            "ge" => Ok(Condition::GE),
+            "lt" => Ok(Condition::LT),
+            "gt" => Ok(Condition::GT),
+            "le" => Ok(Condition::LE),
+            "al" => Ok(Condition::AL),
+            _ => Err(InterpreterError::ParseError(format!("无效的条件码: {}", name))),
+        }
+    }
+
     /// 评估条件是否满足
     pub fn evaluate(&self, flags: &ConditionFlags) -> bool {
         match self {
@@ -182,6 +211,105 @@ impl Register {
             "xzr" => Ok(Register::XZR),
             "wzr" => Ok(Register::WZR),
             
+            "d0" => Ok(Register::D0),
+            "d1" => Ok(Register::D1),
+            "d2" => Ok(Register::D2),
+            "d3" => Ok(Register::D3),
+            "d4" => Ok(Register::D4),
+            "d5" => Ok(Register::D5),
+            "d6" => Ok(Register::D6),
+            "d7" => Ok(Register::D7),
+            "d8" => Ok(Register::D8),
+            "d9" => Ok(Register::D9),
+            "d10" => Ok(Register::D10),
+            "d11" => Ok(Register::D11),
+            "d12" => Ok(Register::D12),
+            "d13" => Ok(Register::D13),
+            "d14" => Ok(Register::D14),
+            "d15" => Ok(Register::D15),
+            "d16" => Ok(Register::D16),
+            "d17" => Ok(Register::D17),
+            "d18" => Ok(Register::D18),
+            "d19" => Ok(Register::D19),
+            "d20" => Ok(Register::D20),
+            "d21" => Ok(Register::D21),
+            "d22" => Ok(Register::D22),
+            "d23" => Ok(Register::D23),
+            "d24" => Ok(Register::D24),
+            "d25" => Ok(Register::D25),
+            "d26" => Ok(Register::D26),
+            "d27" => Ok(Register::D27),
+            "d28" => Ok(Register::D28),
+            "d29" => Ok(Register::D29),
+            "d30" => Ok(Register::D30),
+            "d31" => Ok(Register::D31),
+            
+            "s0" => Ok(Register::S0),
+            "s1" => Ok(Register::S1),
+            "s2" => Ok(Register::S2),
+            "s3" => Ok(Register::S3),
+            "s4" => Ok(Register::S4),
+            "s5" => Ok(Register::S5),
+            "s6" => Ok(Register::S6),
+            "s7" => Ok(Register::S7),
+            "s8" => Ok(Register::S8),
+            "s9" => Ok(Register::S9),
+            "s10" => Ok(Register::S10),
+            "s11" => Ok(Register::S11),
+            "s12" => Ok(Register::S12),
+            "s13" => Ok(Register::S13),
+            "s14" => Ok(Register::S14),
+            "s15" => Ok(Register::S15),
+            "s16" => Ok(Register::S16),
+            "s17" => Ok(Register::S17),
+            "s18" => Ok(Register::S18),
+            "s19" => Ok(Register::S19),
+            "s20" => Ok(Register::S20),
+            "s21" => Ok(Register::S21),
+            "s22" => Ok(Register::S22),
+            "s23" => Ok(Register::S23),
+            "s24" => Ok(Register::S24),
+            "s25" => Ok(Register::S25),
+            "s26" => Ok(Register::S26),
+            "s27" => Ok(Register::S27),
+            "s28" => Ok(Register::S28),
+            "s29" => Ok(Register::S29),
+            "s30" => Ok(Register::S30),
+            "s31" => Ok(Register::S31),
+            
+            "h0" => Ok(Register::H0),
+            "h1" => Ok(Register::H1),
+            "h2" => Ok(Register::H2),
+            "h3" => Ok(Register::H3),
+            "h4" => Ok(Register::H4),
+            "h5" => Ok(Register::H5),
+            "h6" => Ok(Register::H6),
+            "h7" => Ok(Register::H7),
+            "h8" => Ok(Register::H8),
+            "h9" => Ok(Register::H9),
+            "h10" => Ok(Register::H10),
+            "h11" => Ok(Register::H11),
+            "h12" => Ok(Register::H12),
+            "h13" => Ok(Register::H13),
+            "h14" => Ok(Register::H14),
+            "h15" => Ok(Register::H15),
+            "h16" => Ok(Register::H16),
+            "h17" => Ok(Register::H17),
+            "h18" => Ok(Register::H18),
+            "h19" => Ok(Register::H19),
+            "h20" => Ok(Register::H20),
+            "h21" => Ok(Register::H21),
+            "h22" => Ok(Register::H22),
+            "h23" => Ok(Register::H23),
+            "h24" => Ok(Register::H24),
+            "h25" => Ok(Register::H25),
+            "h26" => Ok(Register::H26),
+            "h27" => Ok(Register::H27),
+            "h28" => Ok(Register::H28),
+            "h29" => Ok(Register::H29),
+            "h30" => Ok(Register::H30),
+            "h31" => Ok(Register::H31),
+            
             _ => Err(InterpreterError::InvalidRegister(name.to_string())),
         }
     }
@@ -236,6 +364,50 @@ impl Register {
             _ => None,
         }
     }
+
+    /// 判断是否为标量浮点寄存器（D/S/H）
+    pub fn is_fp(&self) -> bool {
+        self.fp_index().is_some()
+    }
+
+    /// 获取浮点寄存器索引（对应的 V 寄存器编号）
+    pub fn fp_index(&self) -> Option<usize> {
+        match self {
+            Register::D0 | Register::S0 | Register::H0 => Some(0),
+            Register::D1 | Register::S1 | Register::H1 => Some(1),
+            Register::D2 | Register::S2 | Register::H2 => Some(2),
+            Register::D3 | Register::S3 | Register::H3 => Some(3),
+            Register::D4 | Register::S4 | Register::H4 => Some(4),
+            Register::D5 | Register::S5 | Register::H5 => Some(5),
+            Register::D6 | Register::S6 | Register::H6 => Some(6),
+            Register::D7 | Register::S7 | Register::H7 => Some(7),
+            Register::D8 | Register::S8 | Register::H8 => Some(8),
+            Register::D9 | Register::S9 | Register::H9 => Some(9),
+            Register::D10 | Register::S10 | Register::H10 => Some(10),
+            Register::D11 | Register::S11 | Register::H11 => Some(11),
+            Register::D12 | Register::S12 | Register::H12 => Some(12),
+            Register::D13 | Register::S13 | Register::H13 => Some(13),
+            Register::D14 | Register::S14 | Register::H14 => Some(14),
+            Register::D15 | Register::S15 | Register::H15 => Some(15),
+            Register::D16 | Register::S16 | Register::H16 => Some(16),
+            Register::D17 | Register::S17 | Register::H17 => Some(17),
+            Register::D18 | Register::S18 | Register::H18 => Some(18),
+            Register::D19 | Register::S19 | Register::H19 => Some(19),
+            Register::D20 | Register::S20 | Register::H20 => Some(20),
+            Register::D21 | Register::S21 | Register::H21 => Some(21),
+            Register::D22 | Register::S22 | Register::H22 => Some(22),
+            Register::D23 | Register::S23 | Register::H23 => Some(23),
+            Register::D24 | Register::S24 | Register::H24 => Some(24),
+            Register::D25 | Register::S25 | Register::H25 => Some(25),
+            Register::D26 | Register::S26 | Register::H26 => Some(26),
+            Register::D27 | Register::S27 | Register::H27 => Some(27),
+            Register::D28 | Register::S28 | Register::H28 => Some(28),
+            Register::D29 | Register::S29 | Register::H29 => Some(29),
+            Register::D30 | Register::S30 | Register::H30 => Some(30),
+            Register::D31 | Register::S31 | Register::H31 => Some(31),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -258,6 +430,16 @@ mod tests {
         assert!(Register::SP.is_64bit());
     }
 
+    #[test]
+    fn test_fp_register_parsing() {
+        assert_eq!(Register::parse("d0").unwrap(), Register::D0);
+        assert_eq!(Register::parse("S31").unwrap(), Register::S31);
+        assert_eq!(Register::parse("h7").unwrap(), Register::H7);
+        assert!(Register::D5.is_fp());
+        assert_eq!(Register::S5.fp_index(), Some(5));
+        assert!(!Register::X5.is_fp());
+    }
+
     #[test]
     fn test_condition_evaluation() {
         let mut flags = ConditionFlags::new();
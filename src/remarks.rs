@@ -0,0 +1,336 @@
+//! 编译器优化备注
+//!
+//! 导入 clang `-fsave-optimization-record` 生成的 YAML 记录或 GCC `-fopt-info` 的输出，
+//! 把编译器自己的说明（已向量化、已展开、已内联、未能优化的原因）放到对应的 C 代码行旁边，
+//! 与实际生成的汇编相互印证。
+//!
+//! `objdump -S` 的输出没有源码行号，备注按源文件中该行的文本与表格中的 C 代码匹配，
+//! 因此需要能读到备注中记录的源文件（相对路径先相对备注文件所在目录查找，再相对当前目录）。
+
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 备注的类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemarkKind {
+    /// 优化已完成（clang `!Passed`，GCC `optimized`）
+    Passed,
+    /// 优化未能完成（clang `!Missed`，GCC `missed`）
+    Missed,
+    /// 分析信息（clang `!Analysis`，GCC `note`）
+    Analysis,
+}
+
+impl RemarkKind {
+    /// 显示用的标记
+    fn marker(self) -> &'static str {
+        match self {
+            RemarkKind::Passed => "✅",
+            RemarkKind::Missed => "❌",
+            RemarkKind::Analysis => "ℹ️",
+        }
+    }
+}
+
+/// 一条编译器备注
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Remark {
+    /// 类别
+    pub kind: RemarkKind,
+    /// 产生备注的优化（如 `loop-vectorize`，GCC 输出中没有时为空）
+    pub pass: String,
+    /// 所在函数（GCC 输出中没有）
+    pub function: Option<String>,
+    /// 源文件
+    pub file: String,
+    /// 源码行号
+    pub line: usize,
+    /// 备注内容
+    pub message: String,
+    /// 源文件中该行的代码（读不到源文件时为 None）
+    pub code: Option<String>,
+}
+
+/// 备注集合
+#[derive(Debug, Clone, Default)]
+pub struct Remarks {
+    remarks: Vec<Remark>,
+}
+
+/// clang 优化记录中的一个文档（标签已去掉）
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ClangRemark {
+    #[serde(default)]
+    pass: String,
+    debug_loc: Option<ClangLocation>,
+    function: Option<String>,
+    #[serde(default)]
+    args: Vec<HashMap<String, serde_yaml::Value>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ClangLocation {
+    file: String,
+    line: usize,
+}
+
+impl Remarks {
+    /// 解析备注：以 `---` 开头的按 clang YAML 记录解析，否则按 GCC `-fopt-info` 输出解析
+    pub fn parse(content: &str) -> Result<Self> {
+        let remarks = if content.trim_start().starts_with("---") {
+            Self::parse_clang(content)?
+        } else {
+            Self::parse_gcc(content)
+        };
+        Ok(Self { remarks })
+    }
+
+    /// 从文件加载，并读取备注引用的源文件
+    pub fn load_from_file(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read {}", path))?;
+        let mut remarks = Self::parse(&content)?;
+        let base = Path::new(path).parent().unwrap_or(Path::new(""));
+        let files: Vec<String> = remarks.remarks.iter().map(|r| r.file.clone()).collect();
+        let mut loaded: Vec<String> = Vec::new();
+        for file in files {
+            if loaded.contains(&file) {
+                continue;
+            }
+            let source = std::fs::read_to_string(base.join(&file)).or_else(|_| std::fs::read_to_string(&file));
+            if let Ok(source) = source {
+                remarks.attach_source(&file, &source);
+            }
+            loaded.push(file);
+        }
+        Ok(remarks)
+    }
+
+    /// 用源文件的内容补全该文件中备注对应的代码行
+    pub fn attach_source(&mut self, file: &str, source: &str) {
+        let lines: Vec<&str> = source.lines().collect();
+        for remark in self.remarks.iter_mut().filter(|r| r.file == file) {
+            remark.code = remark.line.checked_sub(1).and_then(|i| lines.get(i)).map(|code| normalize(code));
+        }
+    }
+
+    /// 所有备注
+    pub fn remarks(&self) -> &[Remark] {
+        &self.remarks
+    }
+
+    /// clang `-fsave-optimization-record` 的 YAML 记录（每个文档以 `--- !Passed` 这样的标签开头）
+    fn parse_clang(content: &str) -> Result<Vec<Remark>> {
+        let mut remarks = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            let value = serde_yaml::Value::deserialize(document).context("无法解析优化记录")?;
+            let serde_yaml::Value::Tagged(tagged) = value else {
+                continue;
+            };
+            let kind = match tagged.tag.to_string().trim_start_matches('!') {
+                "Passed" => RemarkKind::Passed,
+                "Missed" => RemarkKind::Missed,
+                "Analysis" | "AnalysisFPCommute" | "AnalysisAliasing" => RemarkKind::Analysis,
+                _ => continue,
+            };
+            let record: ClangRemark =
+                serde_yaml::from_value(tagged.value).context("无法解析优化记录")?;
+            let Some(location) = record.debug_loc else {
+                continue;
+            };
+            // 参数按顺序拼接成完整的说明（每个参数的 DebugLoc 不算）
+            let message: String = record
+                .args
+                .iter()
+                .flat_map(|arg| arg.iter().filter(|(key, _)| *key != "DebugLoc").map(|(_, value)| value))
+                .map(|value| match value {
+                    serde_yaml::Value::String(text) => text.clone(),
+                    serde_yaml::Value::Number(n) => n.to_string(),
+                    serde_yaml::Value::Bool(b) => b.to_string(),
+                    _ => String::new(),
+                })
+                .collect();
+            remarks.push(Remark {
+                kind,
+                pass: record.pass,
+                function: record.function,
+                file: location.file,
+                line: location.line,
+                message: message.trim().to_string(),
+                code: None,
+            });
+        }
+        Ok(remarks)
+    }
+
+    /// GCC `-fopt-info` 的输出，如 `sum.c:4:23: optimized: loop vectorized using 16 byte vectors`
+    fn parse_gcc(content: &str) -> Vec<Remark> {
+        let pattern = Regex::new(r"^(.+?):(\d+):(?:\d+:)?\s*(optimized|missed|note):\s*(.*)$").unwrap();
+        content
+            .lines()
+            .filter_map(|line| {
+                let caps = pattern.captures(line.trim())?;
+                let kind = match &caps[3] {
+                    "optimized" => RemarkKind::Passed,
+                    "missed" => RemarkKind::Missed,
+                    _ => RemarkKind::Analysis,
+                };
+                Some(Remark {
+                    kind,
+                    pass: String::new(),
+                    function: None,
+                    file: caps[1].to_string(),
+                    line: caps[2].parse().ok()?,
+                    message: caps[4].trim().to_string(),
+                    code: None,
+                })
+            })
+            .collect()
+    }
+
+    /// 将备注作为注释行插入到函数中对应的 C 代码行之前
+    ///
+    /// 每条备注只插入一次；找不到对应代码行但属于该函数（clang 记录了函数名）的备注放在最前面
+    pub fn apply(&self, function_name: &str, entries: &mut Vec<DumpEntry>, lang: Lang) {
+        let candidates: Vec<&Remark> = self
+            .remarks
+            .iter()
+            .filter(|r| r.function.as_deref().is_none_or(|f| f == function_name))
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+
+        let mut used = vec![false; candidates.len()];
+        let mut merged = Vec::with_capacity(entries.len());
+        let mut current_code = String::new();
+        for entry in entries.drain(..) {
+            if !entry.asm_instruction.is_empty() && !entry.c_code.is_empty() && entry.c_code != current_code {
+                current_code = entry.c_code.clone();
+                // 函数签名等合并的多行 C 代码以 <br> 分隔
+                let lines: Vec<String> = entry.c_code.split("<br>").map(normalize).collect();
+                for (remark, used) in candidates.iter().zip(used.iter_mut()) {
+                    if !*used && remark.code.as_ref().is_some_and(|code| !code.is_empty() && lines.contains(code)) {
+                        *used = true;
+                        merged.push(Self::remark_entry(remark, lang));
+                    }
+                }
+            }
+            merged.push(entry);
+        }
+
+        let unmatched: Vec<DumpEntry> = candidates
+            .iter()
+            .zip(&used)
+            .filter(|(remark, used)| !**used && remark.function.is_some())
+            .map(|(remark, _)| Self::remark_entry(remark, lang))
+            .collect();
+        *entries = unmatched.into_iter().chain(merged).collect();
+    }
+
+    /// 构造注释行（汇编指令为空的条目会被表格按提示信息渲染）
+    fn remark_entry(remark: &Remark, lang: Lang) -> DumpEntry {
+        let source = match (remark.pass.is_empty(), lang) {
+            (true, Lang::Zh) => format!("第 {} 行", remark.line),
+            (false, Lang::Zh) => format!("第 {} 行，{}", remark.line, remark.pass),
+            (true, Lang::En) => format!("line {}", remark.line),
+            (false, Lang::En) => format!("line {}, {}", remark.line, remark.pass),
+        };
+        let text = match lang {
+            Lang::Zh => format!("🔧 编译器备注（{}）: {} {}", source, remark.kind.marker(), remark.message),
+            Lang::En => format!("🔧 Compiler remark ({}): {} {}", source, remark.kind.marker(), remark.message),
+        };
+        DumpEntry {
+            id: String::new(),
+            c_line: None,
+            c_code: text,
+            address: String::new(),
+            machine_code: String::new(),
+            asm_instruction: String::new(),
+            parsed_instruction: None,
+        }
+    }
+}
+
+/// 去掉多余空白后的代码
+fn normalize(code: &str) -> String {
+    code.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const SOURCE: &str = "int sum(int *a, int n)\n{\n    int s = 0;\n    for (int i = 0; i < n; i++)\n        s += a[i];\n    return s;\n}\n";
+
+    const DUMP: &str = r#"
+0000000000000000 <sum>:
+    for (int i = 0; i < n; i++)
+   0:   7100003f    cmp w1, #0x0
+    s += a[i];
+   4:   b8404401    ldr w1, [x0], #4
+   8:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_clang_remarks() {
+        let yaml = r#"--- !Passed
+Pass:            loop-vectorize
+Name:            Vectorized
+DebugLoc:        { File: sum.c, Line: 4, Column: 5 }
+Function:        sum
+Args:
+  - String:          'vectorized loop (vectorization width: '
+  - VectorizationFactor: '4'
+  - String:          ')'
+...
+--- !Missed
+Pass:            inline
+Name:            NoDefinition
+DebugLoc:        { File: sum.c, Line: 9, Column: 5 }
+Function:        sum
+Args:
+  - Callee:          ext
+  - String:          ' will not be inlined'
+...
+"#;
+        let mut remarks = Remarks::parse(yaml).unwrap();
+        assert_eq!(remarks.remarks().len(), 2);
+        assert_eq!(remarks.remarks()[0].message, "vectorized loop (vectorization width: 4)");
+        remarks.attach_source("sum.c", SOURCE);
+
+        let mut entries = ObjdumpParser::new(DUMP.to_string()).extract_function_data("sum").unwrap();
+        remarks.apply("sum", &mut entries, Lang::Zh);
+        // 找不到代码行的备注放在最前面，其余插在对应的 C 代码之前
+        assert_eq!(entries[0].c_code, "🔧 编译器备注（第 9 行，inline）: ❌ ext will not be inlined");
+        assert_eq!(
+            entries[1].c_code,
+            "🔧 编译器备注（第 4 行，loop-vectorize）: ✅ vectorized loop (vectorization width: 4)"
+        );
+        assert_eq!(entries[2].asm_instruction, "cmp w1, #0x0");
+    }
+
+    #[test]
+    fn test_gcc_remarks() {
+        let output = "sum.c:5:11: optimized: loop vectorized using 16 byte vectors\nsum.c:4:5: missed: couldn't vectorize loop\nunrelated line\n";
+        let mut remarks = Remarks::parse(output).unwrap();
+        assert_eq!(remarks.remarks().len(), 2);
+        assert_eq!(remarks.remarks()[1].kind, RemarkKind::Missed);
+        remarks.attach_source("sum.c", SOURCE);
+
+        let mut entries = ObjdumpParser::new(DUMP.to_string()).extract_function_data("sum").unwrap();
+        remarks.apply("sum", &mut entries, Lang::En);
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0].c_code, "🔧 Compiler remark (line 4): ❌ couldn't vectorize loop");
+        assert_eq!(entries[2].c_code, "🔧 Compiler remark (line 5): ✅ loop vectorized using 16 byte vectors");
+    }
+}
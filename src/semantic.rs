@@ -6,13 +6,16 @@
 
 use crate::instruction::{Instruction, InstructionType, Operand};
 use crate::instruction_db::{InstructionDatabase, InstructionDef};
+use crate::locale::Lang;
+use crate::register::{Condition, Register};
+use std::collections::HashMap;
 use std::sync::OnceLock;
 
 // 全局指令数据库（延迟初始化）
 static INSTRUCTION_DB: OnceLock<InstructionDatabase> = OnceLock::new();
 
 /// 获取指令数据库
-fn get_instruction_db() -> &'static InstructionDatabase {
+pub(crate) fn instruction_db() -> &'static InstructionDatabase {
     INSTRUCTION_DB.get_or_init(|| {
         InstructionDatabase::load_embedded()
             .expect("Failed to load instruction database")
@@ -20,380 +23,790 @@ fn get_instruction_db() -> &'static InstructionDatabase {
 }
 
 /// 指令语义解释器
-pub struct SemanticInterpreter;
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SemanticInterpreter {
+    /// 输出语言
+    lang: Lang,
+}
 
 impl SemanticInterpreter {
-    /// 解释单条指令（新版：优先使用数据库）
+    /// 创建使用指定语言的解释器
+    pub fn with_language(lang: Lang) -> Self {
+        Self { lang }
+    }
+
+    /// 解释单条指令（中文）
     pub fn interpret(instruction: &Instruction) -> String {
+        Self::default().describe(instruction)
+    }
+
+    /// 按解释器的语言解释单条指令（优先使用数据库）
+    pub fn describe(&self, instruction: &Instruction) -> String {
+        // 条件选择/条件比较按条件码展开
+        if let Some(text) = self.interpret_conditional(instruction) {
+            return text;
+        }
+        // ADR/ADRP 计算出实际地址
+        if let Some(text) = self.interpret_pc_relative(instruction) {
+            return text;
+        }
+        // 保存/恢复帧指针和返回地址的序言/尾声
+        if let Some(text) = self.interpret_frame(instruction) {
+            return text;
+        }
+        // 位测试（TBZ/TBNZ/TST）
+        if let Some(text) = self.interpret_bit_test(instruction) {
+            return text;
+        }
+        // 逻辑立即数（AND/ORR/EOR 的位掩码）
+        if let Some(text) = self.interpret_logical_immediate(instruction) {
+            return text;
+        }
+
         // 首先尝试从数据库获取指令定义
         let inst_type_str = format!("{:?}", instruction.instruction_type).to_lowercase();
-        if let Some(def) = get_instruction_db().find_instruction(&inst_type_str) {
-            return Self::interpret_from_db(&def, instruction);
+        if let Some(def) = instruction_db().find_instruction(&inst_type_str) {
+            return self.interpret_from_db(def, instruction);
         }
         
         // 回退到旧的硬编码解释（保持向后兼容）
-        Self::interpret_legacy(instruction)
+        self.interpret_legacy(instruction)
     }
 
     /// 从数据库定义生成语义解释
-    fn interpret_from_db(def: &InstructionDef, instruction: &Instruction) -> String {
+    fn interpret_from_db(&self, def: &InstructionDef, instruction: &Instruction) -> String {
         // 使用数据库中的描述作为基础
-        let base_desc = &def.description;
+        let base_desc = def.description_in(self.lang);
         
         // 如果有操作数，尝试生成更详细的解释
         if !instruction.operands.is_empty() {
             match def.mnemonic.as_str() {
                 // 三操作数算术/逻辑指令
-                "add" | "sub" | "mul" | "and" | "orr" | "eor" | "bic" => {
-                    if instruction.operands.len() >= 3 {
-                        let dest = Self::operand_name(&instruction.operands[0]);
-                        let src1 = Self::operand_name(&instruction.operands[1]);
-                        let src2 = Self::operand_name(&instruction.operands[2]);
-                        let op = match def.mnemonic.as_str() {
-                            "add" => "+",
-                            "sub" => "-",
-                            "mul" => "×",
-                            "and" => "&",
-                            "orr" => "|",
-                            "eor" => "^",
-                            "bic" => "& ~",
-                            _ => "",
-                        };
-                        return format!("{} = {} {} {}", dest, src1, op, src2);
-                    }
+                "add" | "sub" | "mul" | "and" | "orr" | "eor" | "bic"
+                | "fadd" | "fsub" | "fmul" | "fdiv"
+                    if instruction.operands.len() >= 3 =>
+                {
+                    let dest = Self::operand_name(&instruction.operands[0]);
+                    let src1 = Self::operand_name(&instruction.operands[1]);
+                    let src2 = Self::operand_name(&instruction.operands[2]);
+                    let op = match def.mnemonic.as_str() {
+                        "add" | "fadd" => "+",
+                        "sub" | "fsub" => "-",
+                        "mul" | "fmul" => "×",
+                        "fdiv" => "÷",
+                        "and" => "&",
+                        "orr" => "|",
+                        "eor" => "^",
+                        "bic" => "& ~",
+                        _ => "",
+                    };
+                    return format!("{} = {} {} {}", dest, src1, op, src2);
+                }
+                // 乘加/乘减：MSUB 为 Xd = Xa - Xn × Xm
+                "madd" | "msub" if instruction.operands.len() >= 4 => {
+                    let dest = Self::operand_name(&instruction.operands[0]);
+                    let src1 = Self::operand_name(&instruction.operands[1]);
+                    let src2 = Self::operand_name(&instruction.operands[2]);
+                    let acc = Self::operand_name(&instruction.operands[3]);
+                    return if def.mnemonic == "madd" {
+                        format!("{} = {} × {} + {}", dest, src1, src2, acc)
+                    } else {
+                        format!("{} = {} - {} × {}", dest, acc, src1, src2)
+                    };
+                }
+                // 扩展乘法：两个 32 位数相乘得到 64 位结果
+                "smull" | "umull" if instruction.operands.len() >= 3 => {
+                    let dest = Self::operand_name(&instruction.operands[0]);
+                    let src1 = Self::operand_name(&instruction.operands[1]);
+                    let src2 = Self::operand_name(&instruction.operands[2]);
+                    let kind = match (def.mnemonic.as_str(), self.lang) {
+                        ("smull", Lang::Zh) => "有符号 32×32→64 位",
+                        (_, Lang::Zh) => "无符号 32×32→64 位",
+                        ("smull", Lang::En) => "signed 32×32→64-bit",
+                        (_, Lang::En) => "unsigned 32×32→64-bit",
+                    };
+                    return format!("{} = {} × {} ({})", dest, src1, src2, kind);
                 }
                 // 加载/存储指令
-                "ldr" | "str" | "ldrb" | "strb" | "ldrh" | "strh" => {
-                    if instruction.operands.len() >= 2 {
-                        let reg = Self::operand_name(&instruction.operands[0]);
-                        let mem = Self::operand_name(&instruction.operands[1]);
-                        let action = if def.mnemonic.starts_with("ld") { "加载" } else { "存储" };
-                        return format!("{} {} {}", action, reg, mem);
+                "ldr" | "str" | "ldrb" | "strb" | "ldrh" | "strh"
+                    if instruction.operands.len() >= 2 =>
+                {
+                    let reg = Self::operand_name(&instruction.operands[0]);
+                    let mem = Self::operand_name(&instruction.operands[1]);
+                    let action = match (def.mnemonic.starts_with("ld"), self.lang) {
+                        (true, Lang::Zh) => "加载",
+                        (false, Lang::Zh) => "存储",
+                        (true, Lang::En) => "load",
+                        (false, Lang::En) => "store",
+                    };
+                    return format!("{} {} {}", action, reg, mem);
+                }
+                // 直接调用/跳转：优先使用 objdump 给出的目标符号
+                "bl" => {
+                    let symbol = Self::branch_target_name(&instruction.operands[0]);
+                    if let Some(note) = crate::libc::call_note(&symbol, self.lang) {
+                        return note;
                     }
+                    let target = Self::call_target_name(&instruction.operands[0]);
+                    return format!("{} {}", self.lang.pick("调用函数", "call"), target);
+                }
+                "b" => {
+                    let target = Self::branch_target_name(&instruction.operands[0]);
+                    return format!("{} {}", self.lang.pick("跳转到", "branch to"), target);
                 }
                 _ => {}
             }
         }
         
+        // 没有手写解释时按数据库模板渲染，模板引用的操作数缺失则使用描述
+        if let Some(text) = def
+            .template_in(self.lang)
+            .and_then(|template| Self::render_template(template, instruction))
+        {
+            return text;
+        }
+
         // 默认返回数据库中的描述
-        base_desc.clone()
+        base_desc.to_string()
+    }
+
+    /// 渲染语义模板
+    ///
+    /// 占位符：`{rd}`/`{rt}` 第一个操作数，`{rn}`/`{rt2}` 第二个，`{rm}` 第三个，
+    /// `{ra}` 第四个，`{0}`..`{9}` 按位置引用，`{imm}` 第一个立即数，
+    /// `{mem}` 第一个内存操作数，`{target}` 跳转目标
+    fn render_template(template: &str, instruction: &Instruction) -> Option<String> {
+        let ops = &instruction.operands;
+        let mut text = String::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            let end = start + rest[start..].find('}')?;
+            let value = match &rest[start + 1..end] {
+                "rd" | "rt" => Self::operand_name(ops.first()?),
+                "rn" | "rt2" => Self::operand_name(ops.get(1)?),
+                "rm" => Self::operand_name(ops.get(2)?),
+                "ra" => Self::operand_name(ops.get(3)?),
+                "imm" => Self::operand_name(ops.iter().find(|op| matches!(op, Operand::Immediate(_)))?),
+                "mem" => Self::memory_operand_desc(ops.iter().find(|op| matches!(op, Operand::Memory { .. }))?),
+                "target" => Self::branch_target_name(ops.iter().find(|op| matches!(op, Operand::Label(_)))?),
+                index => Self::operand_name(ops.get(index.parse::<usize>().ok()?)?),
+            };
+            text.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        text.push_str(rest);
+        Some(text)
+    }
+
+    /// 条件选择/条件比较：`X0 = (条件 LT 成立) ? X1 : X2`
+    fn interpret_conditional(&self, instruction: &Instruction) -> Option<String> {
+        let condition = instruction.condition?;
+        let cond = match self.lang {
+            Lang::Zh => format!("条件 {:?} 成立", condition),
+            Lang::En => format!("{:?} holds", condition),
+        };
+        self.conditional_text(instruction, &cond)
+    }
+
+    /// 按给定的条件文本展开条件选择/条件比较
+    fn conditional_text(&self, instruction: &Instruction, cond: &str) -> Option<String> {
+        use InstructionType::*;
+
+        let ops: Vec<String> = instruction.operands.iter().map(Self::operand_name).collect();
+        let op = |i: usize| ops.get(i).map(String::as_str);
+
+        let text = match (instruction.instruction_type, op(0), op(1), op(2)) {
+            (CSEL, Some(rd), Some(rn), Some(rm)) => format!("{} = ({}) ? {} : {}", rd, cond, rn, rm),
+            (CSINC, Some(rd), Some(rn), Some(rm)) => format!("{} = ({}) ? {} : {} + 1", rd, cond, rn, rm),
+            (CSINV, Some(rd), Some(rn), Some(rm)) => format!("{} = ({}) ? {} : ~{}", rd, cond, rn, rm),
+            (CSNEG, Some(rd), Some(rn), Some(rm)) => format!("{} = ({}) ? {} : -{}", rd, cond, rn, rm),
+            (CSET, Some(rd), ..) => format!("{} = ({}) ? 1 : 0", rd, cond),
+            (CSETM, Some(rd), ..) => format!("{} = ({}) ? -1 : 0", rd, cond),
+            (CINC, Some(rd), Some(rn), _) => format!("{} = ({}) ? {} + 1 : {}", rd, cond, rn, rn),
+            (CINV, Some(rd), Some(rn), _) => format!("{} = ({}) ? ~{} : {}", rd, cond, rn, rn),
+            (CNEG, Some(rd), Some(rn), _) => format!("{} = ({}) ? -{} : {}", rd, cond, rn, rn),
+            // ccmp rn, op2, #nzcv, cond：条件不成立时直接把标志位设为 nzcv
+            (CCMP | CCMN, Some(rn), Some(op2), Some(nzcv)) => {
+                let op2 = if instruction.instruction_type == CCMN { format!("-{}", op2) } else { op2.to_string() };
+                match self.lang {
+                    Lang::Zh => format!("{}时比较 {} 和 {}，否则 NZCV = {}", cond, rn, op2, nzcv),
+                    Lang::En => format!("if {} compare {} with {}, else NZCV = {}", cond, rn, op2, nzcv),
+                }
+            }
+            _ => return None,
+        };
+        Some(text)
+    }
+
+    /// 标准序言 `stp x29, x30, [sp, #-N]!` 和尾声 `ldp x29, x30, [sp], #N`
+    fn interpret_frame(&self, instruction: &Instruction) -> Option<String> {
+        let (prologue, size) = frame_adjustment(instruction)?;
+        Some(match (prologue, self.lang) {
+            (true, Lang::Zh) => format!("函数序言: 保存帧指针和返回地址，分配 {} 字节栈空间", size),
+            (false, Lang::Zh) => format!("函数尾声: 恢复帧指针和返回地址，释放 {} 字节栈空间", size),
+            (true, Lang::En) => format!("prologue: save frame pointer and return address, allocate {} bytes of stack", size),
+            (false, Lang::En) => format!("epilogue: restore frame pointer and return address, free {} bytes of stack", size),
+        })
+    }
+
+    /// 以 `movz`/`mov` 开头、随后的 `movk` 改写同一寄存器的常量拼接链：
+    /// 第一条给出拼接完成的常量，后续各条标注“接上条”（与 instructions 的前缀一一对应，不成链时返回 None）
+    pub fn describe_constant_chain(&self, instructions: &[&Instruction]) -> Option<Vec<String>> {
+        let (head, rest) = instructions.split_first()?;
+        let (Some(Operand::Register(reg)), Some(Operand::Immediate(_))) = (head.operands.first(), head.operands.get(1)) else {
+            return None;
+        };
+        if !matches!(head.instruction_type, InstructionType::MOVZ | InstructionType::MOV) {
+            return None;
+        }
+        let length = 1 + rest
+            .iter()
+            .take_while(|inst| {
+                inst.instruction_type == InstructionType::MOVK
+                    && matches!(inst.operands.first(), Some(Operand::Register(r)) if r.index() == reg.index())
+            })
+            .count();
+        if length < 2 {
+            return None;
+        }
+
+        let mut tracker = crate::values::ValueTracker::new();
+        for inst in &instructions[..length] {
+            tracker.step(inst);
+        }
+        let value = tracker.value(*reg)?;
+        let pair = format!("{:?} + movk", head.instruction_type).to_lowercase();
+        let pair = if length > 2 { format!("{} × {}", pair, length - 1) } else { pair };
+        let mut texts = vec![match self.lang {
+            Lang::Zh => format!("{:?} = {} ({} 拼接常量)", reg, value, pair),
+            Lang::En => format!("{:?} = {} (constant built by {})", reg, value, pair),
+        }];
+        let follow = self.lang.pick("(接上条，拼接常量)", "(continued, builds the constant)");
+        texts.resize(length, follow.to_string());
+        Some(texts)
+    }
+
+    /// 紧跟在序言/尾声之后的指令：序言后的 `mov x29, sp` 和尾声后的 `ret`
+    pub fn describe_frame_step(&self, previous: &Instruction, instruction: &Instruction) -> Option<String> {
+        let (prologue, _) = frame_adjustment(previous)?;
+        let sets_fp = matches!(
+            (instruction.instruction_type, instruction.operands.as_slice()),
+            (InstructionType::MOV, [Operand::Register(fp), Operand::Register(Register::SP)]) if fp.index() == Some(29)
+        );
+        match (prologue, instruction.instruction_type, self.lang) {
+            (true, InstructionType::MOV, Lang::Zh) if sets_fp => Some(String::from("函数序言: 建立帧指针 (X29 = SP)")),
+            (true, InstructionType::MOV, Lang::En) if sets_fp => Some(String::from("prologue: set up frame pointer (X29 = SP)")),
+            (false, InstructionType::RET, Lang::Zh) => Some(String::from("函数尾声: 返回调用者")),
+            (false, InstructionType::RET, Lang::En) => Some(String::from("epilogue: return to caller")),
+            _ => None,
+        }
+    }
+
+    /// 位测试：`如果 W0 的第 3 位为 0 则跳转到 f+0x20`、`测试 X1 & 0xff (低 8 位) 是否为 0`
+    fn interpret_bit_test(&self, instruction: &Instruction) -> Option<String> {
+        use InstructionType::*;
+
+        let ops = instruction.operands.as_slice();
+        match (instruction.instruction_type, ops) {
+            (TBZ | TBNZ, [reg, Operand::Immediate(bit), target]) => {
+                let reg = Self::operand_name(reg);
+                let target = Self::branch_target_name(target);
+                let zero = instruction.instruction_type == TBZ;
+                Some(match (zero, self.lang) {
+                    (true, Lang::Zh) => format!("如果 {} 的第 {} 位为 0 则跳转到 {}", reg, bit, target),
+                    (false, Lang::Zh) => format!("如果 {} 的第 {} 位为 1 则跳转到 {}", reg, bit, target),
+                    (true, Lang::En) => format!("if bit {} of {} is 0 branch to {}", bit, reg, target),
+                    (false, Lang::En) => format!("if bit {} of {} is 1 branch to {}", bit, reg, target),
+                })
+            }
+            (TST, [Operand::Register(reg), operand]) => {
+                let test = match operand {
+                    Operand::Immediate(mask) => {
+                        let width = if reg.is_64bit() { 64 } else { 32 };
+                        let mask = *mask as u64 & (u64::MAX >> (64 - width));
+                        match describe_mask(mask, width, self.lang) {
+                            Some(bits) => format!("{:?} & 0x{:x} ({})", reg, mask, bits),
+                            None => format!("{:?} & 0x{:x}", reg, mask),
+                        }
+                    }
+                    other => format!("{:?} & {}", reg, Self::operand_name(other)),
+                };
+                Some(match self.lang {
+                    Lang::Zh => format!("测试 {} 是否为 0，设置 N、Z 标志", test),
+                    Lang::En => format!("test whether {} is zero, setting N and Z", test),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// 逻辑立即数：AND/ORR/EOR 的位掩码按作用的位范围解释（`X0 = X1 清除低 4 位 (& 0xfffffffffffffff0)`）
+    fn interpret_logical_immediate(&self, instruction: &Instruction) -> Option<String> {
+        use InstructionType::*;
+
+        let (ty, [Operand::Register(rd), Operand::Register(rn), Operand::Immediate(imm)]) =
+            (instruction.instruction_type, instruction.operands.as_slice())
+        else {
+            return None;
+        };
+        if !matches!(ty, AND | ORR | EOR) || matches!(rn, Register::XZR | Register::WZR) {
+            return None;
+        }
+        let width = if rd.is_64bit() { 64 } else { 32 };
+        let mask = *imm as u64 & (u64::MAX >> (64 - width));
+        let (size, element) = repeating_element(mask, width);
+        let lang = self.lang;
+
+        // AND 的掩码不是从最低位开始的连续 1 时，按被清除的位描述（0xfffffffffffffff0 即清除低 4 位）
+        let ((zh, en), bits) = match ty {
+            AND => {
+                let kept = describe_mask(element, size, lang);
+                let cleared = describe_mask(!element & (u64::MAX >> (64 - size)), size, lang);
+                match (kept, cleared) {
+                    (Some(bits), _) if element & 1 == 1 => (("保留", "kept"), bits),
+                    (_, Some(bits)) => (("清除", "cleared"), bits),
+                    (Some(bits), None) => (("保留", "kept"), bits),
+                    (None, None) => return None,
+                }
+            }
+            ORR => (("置位", "set"), describe_mask(element, size, lang)?),
+            _ => (("翻转", "flipped"), describe_mask(element, size, lang)?),
+        };
+        let op = match ty {
+            AND => "&",
+            ORR => "|",
+            _ => "^",
+        };
+        let only = if en == "kept" { "only " } else { "" };
+        Some(match (lang, size < width) {
+            (Lang::Zh, false) => format!("{:?} = {:?} {}{} ({} 0x{:x})", rd, rn, zh, bits, op, mask),
+            (Lang::Zh, true) => format!("{:?} = {:?} {}每 {} 位中的{} ({} 0x{:x})", rd, rn, zh, size, bits, op, mask),
+            (Lang::En, false) => format!("{:?} = {:?} with {}{} {} ({} 0x{:x})", rd, rn, only, bits, en, op, mask),
+            (Lang::En, true) => format!("{:?} = {:?} with {}{} of every {} bits {} ({} 0x{:x})", rd, rn, only, bits, size, en, op, mask),
+        })
+    }
+
+    /// PC 相对寻址：`X0 = 0x411000 (foo 所在的 4KB 页)`
+    fn interpret_pc_relative(&self, instruction: &Instruction) -> Option<String> {
+        let page = match instruction.instruction_type {
+            InstructionType::ADRP => true,
+            InstructionType::ADR => false,
+            _ => return None,
+        };
+        let target = pc_relative_target(instruction)?;
+        let rd = Self::operand_name(instruction.operands.first()?);
+
+        if awaits_relocation(instruction, target) {
+            return Some(match (page, self.lang) {
+                (true, Lang::Zh) => format!("{} = 页基址 (待链接器重定位)", rd),
+                (false, Lang::Zh) => format!("{} = PC 相对地址 (待链接器重定位)", rd),
+                (true, Lang::En) => format!("{} = page base (pending relocation)", rd),
+                (false, Lang::En) => format!("{} = PC-relative address (pending relocation)", rd),
+            });
+        }
+        let symbol = exact_symbol(&instruction.operands[1]);
+        Some(match (page, symbol, self.lang) {
+            (true, Some(sym), Lang::Zh) => format!("{} = 0x{:x} ({} 所在的 4KB 页)", rd, target, sym),
+            (true, None, Lang::Zh) => format!("{} = 0x{:x} (4KB 页基址)", rd, target),
+            (false, Some(sym), Lang::Zh) => format!("{} = 0x{:x} ({} 的地址)", rd, target, sym),
+            (false, None, Lang::Zh) => format!("{} = 0x{:x} (PC 相对地址)", rd, target),
+            (true, Some(sym), Lang::En) => format!("{} = 0x{:x} (4KB page of {})", rd, target, sym),
+            (true, None, Lang::En) => format!("{} = 0x{:x} (4KB page base)", rd, target),
+            (false, Some(sym), Lang::En) => format!("{} = 0x{:x} (address of {})", rd, target, sym),
+            (false, None, Lang::En) => format!("{} = 0x{:x} (PC-relative address)", rd, target),
+        })
     }
 
     /// 旧版硬编码解释（保持向后兼容）
-    fn interpret_legacy(instruction: &Instruction) -> String {
+    fn interpret_legacy(&self, instruction: &Instruction) -> String {
         match instruction.instruction_type {
-            InstructionType::ADD => Self::interpret_add(instruction),
-            InstructionType::SUB => Self::interpret_sub(instruction),
-            InstructionType::MUL => Self::interpret_mul(instruction),
-            InstructionType::AND => Self::interpret_and(instruction),
-            InstructionType::ORR => Self::interpret_orr(instruction),
-            InstructionType::EOR => Self::interpret_eor(instruction),
-            InstructionType::LSL => Self::interpret_lsl(instruction),
-            InstructionType::LSR => Self::interpret_lsr(instruction),
-            InstructionType::ASR => Self::interpret_asr(instruction),
-            InstructionType::LDR => Self::interpret_ldr(instruction),
-            InstructionType::LDRB => Self::interpret_ldrb(instruction),
-            InstructionType::LDRH => Self::interpret_ldrh(instruction),
-            InstructionType::LDP => Self::interpret_ldp(instruction),
-            InstructionType::STR => Self::interpret_str(instruction),
-            InstructionType::STRB => Self::interpret_strb(instruction),
-            InstructionType::STRH => Self::interpret_strh(instruction),
-            InstructionType::STP => Self::interpret_stp(instruction),
-            InstructionType::MOV => Self::interpret_mov(instruction),
-            InstructionType::MOVZ => Self::interpret_movz(instruction),
-            InstructionType::MOVK => Self::interpret_movk(instruction),
-            InstructionType::CMP => Self::interpret_cmp(instruction),
-            InstructionType::B => Self::interpret_b(instruction),
-            InstructionType::BL => Self::interpret_bl(instruction),
-            InstructionType::BR => Self::interpret_br(instruction),
-            InstructionType::RET => String::from("从子程序返回"),
-            InstructionType::BEQ => String::from("如果相等则跳转 (Z=1)"),
-            InstructionType::BNE => String::from("如果不相等则跳转 (Z=0)"),
-            InstructionType::BHI => String::from("如果无符号大于则跳转 (C=1且Z=0)"),
-            InstructionType::BLS => String::from("如果无符号小于等于则跳转 (C=0或Z=1)"),
-            InstructionType::BCC => String::from("如果无进位则跳转 (C=0)"),
-            InstructionType::BGE => String::from("如果有符号大于等于则跳转 (N=V)"),
-            InstructionType::BLT => String::from("如果有符号小于则跳转 (N≠V)"),
-            InstructionType::BGT => String::from("如果有符号大于则跳转 (Z=0且N=V)"),
-            InstructionType::BLE => String::from("如果有符号小于等于则跳转 (Z=1或N≠V)"),
-            InstructionType::CBZ => Self::interpret_cbz(instruction),
-            InstructionType::CBNZ => Self::interpret_cbnz(instruction),
-            InstructionType::NOP => String::from("空操作"),
-            _ => format!("{:?} 指令", instruction.instruction_type),
+            InstructionType::ADD => self.interpret_add(instruction),
+            InstructionType::SUB => self.interpret_sub(instruction),
+            InstructionType::MUL => self.interpret_mul(instruction),
+            InstructionType::AND => self.interpret_and(instruction),
+            InstructionType::ORR => self.interpret_orr(instruction),
+            InstructionType::EOR => self.interpret_eor(instruction),
+            InstructionType::LSL => self.interpret_lsl(instruction),
+            InstructionType::LSR => self.interpret_lsr(instruction),
+            InstructionType::ASR => self.interpret_asr(instruction),
+            InstructionType::LDR => self.interpret_ldr(instruction),
+            InstructionType::LDRB => self.interpret_ldrb(instruction),
+            InstructionType::LDRH => self.interpret_ldrh(instruction),
+            InstructionType::LDP => self.interpret_ldp(instruction),
+            InstructionType::STR => self.interpret_str(instruction),
+            InstructionType::STRB => self.interpret_strb(instruction),
+            InstructionType::STRH => self.interpret_strh(instruction),
+            InstructionType::STP => self.interpret_stp(instruction),
+            InstructionType::MOV => self.interpret_mov(instruction),
+            InstructionType::MOVZ => self.interpret_movz(instruction),
+            InstructionType::MOVK => self.interpret_movk(instruction),
+            InstructionType::CMP => self.interpret_cmp(instruction),
+            InstructionType::B => self.interpret_b(instruction),
+            InstructionType::BL => self.interpret_bl(instruction),
+            InstructionType::BR => self.interpret_br(instruction),
+            InstructionType::RET => self.lang.pick("从子程序返回", "return from subroutine").to_string(),
+            InstructionType::BEQ => self.lang.pick("如果相等则跳转 (Z=1)", "branch if equal (Z=1)").to_string(),
+            InstructionType::BNE => self.lang.pick("如果不相等则跳转 (Z=0)", "branch if not equal (Z=0)").to_string(),
+            InstructionType::BHI => self.lang.pick("如果无符号大于则跳转 (C=1且Z=0)", "branch if unsigned higher (C=1 and Z=0)").to_string(),
+            InstructionType::BLS => self.lang.pick("如果无符号小于等于则跳转 (C=0或Z=1)", "branch if unsigned lower or same (C=0 or Z=1)").to_string(),
+            InstructionType::BCC => self.lang.pick("如果无进位则跳转 (C=0)", "branch if carry clear (C=0)").to_string(),
+            InstructionType::BGE => self.lang.pick("如果有符号大于等于则跳转 (N=V)", "branch if signed greater or equal (N=V)").to_string(),
+            InstructionType::BLT => self.lang.pick("如果有符号小于则跳转 (N≠V)", "branch if signed less than (N≠V)").to_string(),
+            InstructionType::BGT => self.lang.pick("如果有符号大于则跳转 (Z=0且N=V)", "branch if signed greater than (Z=0 and N=V)").to_string(),
+            InstructionType::BLE => self.lang.pick("如果有符号小于等于则跳转 (Z=1或N≠V)", "branch if signed less or equal (Z=1 or N≠V)").to_string(),
+            InstructionType::CBZ => self.interpret_cbz(instruction),
+            InstructionType::CBNZ => self.interpret_cbnz(instruction),
+            InstructionType::NOP => self.lang.pick("空操作", "no operation").to_string(),
+            _ => format!("{:?} {}", instruction.instruction_type, self.lang.pick("指令", "instruction")),
         }
     }
 
     // 各指令的解释函数
-    fn interpret_add(inst: &Instruction) -> String {
+    fn interpret_add(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src1 = Self::operand_name(&inst.operands[1]);
             let src2 = Self::operand_name(&inst.operands[2]);
             format!("{} = {} + {}", dest, src1, src2)
         } else {
-            String::from("加法运算")
+            self.lang.pick("加法运算", "addition").to_string()
         }
     }
 
-    fn interpret_sub(inst: &Instruction) -> String {
+    fn interpret_sub(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src1 = Self::operand_name(&inst.operands[1]);
             let src2 = Self::operand_name(&inst.operands[2]);
             format!("{} = {} - {}", dest, src1, src2)
         } else {
-            String::from("减法运算")
+            self.lang.pick("减法运算", "subtraction").to_string()
         }
     }
 
-    fn interpret_mul(inst: &Instruction) -> String {
+    fn interpret_mul(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src1 = Self::operand_name(&inst.operands[1]);
             let src2 = Self::operand_name(&inst.operands[2]);
             format!("{} = {} × {}", dest, src1, src2)
         } else {
-            String::from("乘法运算")
+            self.lang.pick("乘法运算", "multiplication").to_string()
         }
     }
 
-    fn interpret_and(inst: &Instruction) -> String {
+    fn interpret_and(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src1 = Self::operand_name(&inst.operands[1]);
             let src2 = Self::operand_name(&inst.operands[2]);
             format!("{} = {} & {}", dest, src1, src2)
         } else {
-            String::from("按位与")
+            self.lang.pick("按位与", "bitwise AND").to_string()
         }
     }
 
-    fn interpret_orr(inst: &Instruction) -> String {
+    fn interpret_orr(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src1 = Self::operand_name(&inst.operands[1]);
             let src2 = Self::operand_name(&inst.operands[2]);
             format!("{} = {} | {}", dest, src1, src2)
         } else {
-            String::from("按位或")
+            self.lang.pick("按位或", "bitwise OR").to_string()
         }
     }
 
-    fn interpret_eor(inst: &Instruction) -> String {
+    fn interpret_eor(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src1 = Self::operand_name(&inst.operands[1]);
             let src2 = Self::operand_name(&inst.operands[2]);
             format!("{} = {} ^ {}", dest, src1, src2)
         } else {
-            String::from("按位异或")
+            self.lang.pick("按位异或", "bitwise exclusive OR").to_string()
         }
     }
 
-    fn interpret_lsl(inst: &Instruction) -> String {
+    fn interpret_lsl(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src = Self::operand_name(&inst.operands[1]);
             let shift = Self::operand_name(&inst.operands[2]);
             format!("{} = {} << {}", dest, src, shift)
         } else {
-            String::from("逻辑左移")
+            self.lang.pick("逻辑左移", "logical shift left").to_string()
         }
     }
 
-    fn interpret_lsr(inst: &Instruction) -> String {
+    fn interpret_lsr(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src = Self::operand_name(&inst.operands[1]);
             let shift = Self::operand_name(&inst.operands[2]);
             format!("{} = {} >> {}", dest, src, shift)
         } else {
-            String::from("逻辑右移")
+            self.lang.pick("逻辑右移", "logical shift right").to_string()
         }
     }
 
-    fn interpret_asr(inst: &Instruction) -> String {
+    fn interpret_asr(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src = Self::operand_name(&inst.operands[1]);
             let shift = Self::operand_name(&inst.operands[2]);
-            format!("{} = {} >> {} (算术)", dest, src, shift)
+            match self.lang {
+                Lang::Zh => format!("{} = {} >> {} (算术)", dest, src, shift),
+                Lang::En => format!("{} = {} >> {} (arithmetic)", dest, src, shift),
+            }
         } else {
-            String::from("算术右移")
+            self.lang.pick("算术右移", "arithmetic shift right").to_string()
         }
     }
 
-    fn interpret_ldr(inst: &Instruction) -> String {
+    fn interpret_ldr(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let dest = Self::operand_name(&inst.operands[0]);
             let mem = Self::memory_operand_desc(&inst.operands[1]);
-            format!("从 {} 加载到 {}", mem, dest)
+            match self.lang {
+                Lang::Zh => format!("从 {} 加载到 {}", mem, dest),
+                Lang::En => format!("load {} from {}", dest, mem),
+            }
         } else {
-            String::from("从内存加载")
+            self.lang.pick("从内存加载", "load from memory").to_string()
         }
     }
 
-    fn interpret_ldrb(inst: &Instruction) -> String {
+    fn interpret_ldrb(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let dest = Self::operand_name(&inst.operands[0]);
             let mem = Self::memory_operand_desc(&inst.operands[1]);
-            format!("从 {} 加载字节到 {}", mem, dest)
+            match self.lang {
+                Lang::Zh => format!("从 {} 加载字节到 {}", mem, dest),
+                Lang::En => format!("load byte {} from {}", dest, mem),
+            }
         } else {
-            String::from("从内存加载字节")
+            self.lang.pick("从内存加载字节", "load a byte from memory").to_string()
         }
     }
 
-    fn interpret_ldrh(inst: &Instruction) -> String {
+    fn interpret_ldrh(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let dest = Self::operand_name(&inst.operands[0]);
             let mem = Self::memory_operand_desc(&inst.operands[1]);
-            format!("从 {} 加载半字到 {}", mem, dest)
+            match self.lang {
+                Lang::Zh => format!("从 {} 加载半字到 {}", mem, dest),
+                Lang::En => format!("load halfword {} from {}", dest, mem),
+            }
         } else {
-            String::from("从内存加载半字")
+            self.lang.pick("从内存加载半字", "load a halfword from memory").to_string()
         }
     }
 
-    fn interpret_ldp(inst: &Instruction) -> String {
+    fn interpret_ldp(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let dest1 = Self::operand_name(&inst.operands[0]);
             let dest2 = Self::operand_name(&inst.operands[1]);
             let mem = Self::memory_operand_desc(&inst.operands[2]);
-            format!("从 {} 加载 {} 和 {}", mem, dest1, dest2)
+            match self.lang {
+                Lang::Zh => format!("从 {} 加载 {} 和 {}", mem, dest1, dest2),
+                Lang::En => format!("load {} and {} from {}", dest1, dest2, mem),
+            }
         } else {
-            String::from("从内存加载一对寄存器")
+            self.lang.pick("从内存加载一对寄存器", "load a register pair from memory").to_string()
         }
     }
 
-    fn interpret_str(inst: &Instruction) -> String {
+    fn interpret_str(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let src = Self::operand_name(&inst.operands[0]);
             let mem = Self::memory_operand_desc(&inst.operands[1]);
-            format!("将 {} 存储到 {}", src, mem)
+            match self.lang {
+                Lang::Zh => format!("将 {} 存储到 {}", src, mem),
+                Lang::En => format!("store {} to {}", src, mem),
+            }
         } else {
-            String::from("存储到内存")
+            self.lang.pick("存储到内存", "store to memory").to_string()
         }
     }
 
-    fn interpret_strb(inst: &Instruction) -> String {
+    fn interpret_strb(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let src = Self::operand_name(&inst.operands[0]);
             let mem = Self::memory_operand_desc(&inst.operands[1]);
-            format!("将 {} (字节) 存储到 {}", src, mem)
+            match self.lang {
+                Lang::Zh => format!("将 {} (字节) 存储到 {}", src, mem),
+                Lang::En => format!("store {} (byte) to {}", src, mem),
+            }
         } else {
-            String::from("存储字节到内存")
+            self.lang.pick("存储字节到内存", "store a byte to memory").to_string()
         }
     }
 
-    fn interpret_strh(inst: &Instruction) -> String {
+    fn interpret_strh(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let src = Self::operand_name(&inst.operands[0]);
             let mem = Self::memory_operand_desc(&inst.operands[1]);
-            format!("将 {} (半字) 存储到 {}", src, mem)
+            match self.lang {
+                Lang::Zh => format!("将 {} (半字) 存储到 {}", src, mem),
+                Lang::En => format!("store {} (halfword) to {}", src, mem),
+            }
         } else {
-            String::from("存储半字到内存")
+            self.lang.pick("存储半字到内存", "store a halfword to memory").to_string()
         }
     }
 
-    fn interpret_stp(inst: &Instruction) -> String {
+    fn interpret_stp(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 3 {
             let src1 = Self::operand_name(&inst.operands[0]);
             let src2 = Self::operand_name(&inst.operands[1]);
             let mem = Self::memory_operand_desc(&inst.operands[2]);
-            format!("将 {} 和 {} 存储到 {}", src1, src2, mem)
+            match self.lang {
+                Lang::Zh => format!("将 {} 和 {} 存储到 {}", src1, src2, mem),
+                Lang::En => format!("store {} and {} to {}", src1, src2, mem),
+            }
         } else {
-            String::from("存储一对寄存器到内存")
+            self.lang.pick("存储一对寄存器到内存", "store a register pair to memory").to_string()
         }
     }
 
-    fn interpret_mov(inst: &Instruction) -> String {
+    fn interpret_mov(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src = Self::operand_name(&inst.operands[1]);
             format!("{} = {}", dest, src)
         } else {
-            String::from("数据移动")
+            self.lang.pick("数据移动", "move data").to_string()
         }
     }
 
-    fn interpret_movz(inst: &Instruction) -> String {
+    fn interpret_movz(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src = Self::operand_name(&inst.operands[1]);
-            format!("{} = {} (其他位清零)", dest, src)
+            match self.lang {
+                Lang::Zh => format!("{} = {} (其他位清零)", dest, src),
+                Lang::En => format!("{} = {} (other bits cleared)", dest, src),
+            }
         } else {
-            String::from("移动立即数并清零")
+            self.lang.pick("移动立即数并清零", "move immediate and clear other bits").to_string()
         }
     }
 
-    fn interpret_movk(inst: &Instruction) -> String {
+    fn interpret_movk(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let dest = Self::operand_name(&inst.operands[0]);
             let src = Self::operand_name(&inst.operands[1]);
-            format!("{} 的部分位 = {} (保持其他位)", dest, src)
+            match self.lang {
+                Lang::Zh => format!("{} 的部分位 = {} (保持其他位)", dest, src),
+                Lang::En => format!("part of {} = {} (other bits kept)", dest, src),
+            }
         } else {
-            String::from("移动立即数并保持")
+            self.lang.pick("移动立即数并保持", "move immediate and keep other bits").to_string()
         }
     }
 
-    fn interpret_cmp(inst: &Instruction) -> String {
+    fn interpret_cmp(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let src1 = Self::operand_name(&inst.operands[0]);
             let src2 = Self::operand_name(&inst.operands[1]);
-            format!("比较 {} 和 {} (设置标志位)", src1, src2)
+            match self.lang {
+                Lang::Zh => format!("比较 {} 和 {} (设置标志位)", src1, src2),
+                Lang::En => format!("compare {} with {} (sets flags)", src1, src2),
+            }
         } else {
-            String::from("比较")
+            self.lang.pick("比较", "compare").to_string()
         }
     }
 
-    fn interpret_b(inst: &Instruction) -> String {
+    fn interpret_b(&self, inst: &Instruction) -> String {
         if !inst.operands.is_empty() {
             let target = Self::operand_name(&inst.operands[0]);
-            format!("无条件跳转到 {}", target)
+            match self.lang {
+                Lang::Zh => format!("无条件跳转到 {}", target),
+                Lang::En => format!("branch unconditionally to {}", target),
+            }
         } else {
-            String::from("无条件跳转")
+            self.lang.pick("无条件跳转", "unconditional branch").to_string()
         }
     }
 
-    fn interpret_bl(inst: &Instruction) -> String {
+    fn interpret_bl(&self, inst: &Instruction) -> String {
         if !inst.operands.is_empty() {
+            if let Some(note) = crate::libc::call_note(&Self::branch_target_name(&inst.operands[0]), self.lang) {
+                return note;
+            }
             let target = Self::operand_name(&inst.operands[0]);
-            format!("调用函数 {} (保存返回地址)", target)
+            match self.lang {
+                Lang::Zh => format!("调用函数 {} (保存返回地址)", target),
+                Lang::En => format!("call {} (saves the return address)", target),
+            }
         } else {
-            String::from("调用函数")
+            self.lang.pick("调用函数", "call function").to_string()
         }
     }
 
-    fn interpret_br(inst: &Instruction) -> String {
+    fn interpret_br(&self, inst: &Instruction) -> String {
         if !inst.operands.is_empty() {
             let target = Self::operand_name(&inst.operands[0]);
-            format!("跳转到寄存器 {} 中的地址", target)
+            match self.lang {
+                Lang::Zh => format!("跳转到寄存器 {} 中的地址", target),
+                Lang::En => format!("branch to the address in {}", target),
+            }
         } else {
-            String::from("跳转到寄存器地址")
+            self.lang.pick("跳转到寄存器地址", "branch to register address").to_string()
         }
     }
 
-    fn interpret_cbz(inst: &Instruction) -> String {
+    fn interpret_cbz(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let reg = Self::operand_name(&inst.operands[0]);
             let target = Self::operand_name(&inst.operands[1]);
-            format!("如果 {} == 0 则跳转到 {}", reg, target)
+            match self.lang {
+                Lang::Zh => format!("如果 {} == 0 则跳转到 {}", reg, target),
+                Lang::En => format!("if {} == 0 branch to {}", reg, target),
+            }
         } else {
-            String::from("比较为零则跳转")
+            self.lang.pick("比较为零则跳转", "branch if zero").to_string()
         }
     }
 
-    fn interpret_cbnz(inst: &Instruction) -> String {
+    fn interpret_cbnz(&self, inst: &Instruction) -> String {
         if inst.operands.len() >= 2 {
             let reg = Self::operand_name(&inst.operands[0]);
             let target = Self::operand_name(&inst.operands[1]);
-            format!("如果 {} ≠ 0 则跳转到 {}", reg, target)
+            match self.lang {
+                Lang::Zh => format!("如果 {} ≠ 0 则跳转到 {}", reg, target),
+                Lang::En => format!("if {} ≠ 0 branch to {}", reg, target),
+            }
         } else {
-            String::from("比较非零则跳转")
+            self.lang.pick("比较非零则跳转", "branch if not zero").to_string()
         }
     }
 
     // 辅助函数
 
+    /// 分支目标名：`400560 <printf@plt>` -> `printf@plt`，无符号时保留原样
+    fn branch_target_name(operand: &Operand) -> String {
+        match operand {
+            Operand::Label(label) => crate::objdump::target_symbol(label)
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| label.clone()),
+            other => Self::operand_name(other),
+        }
+    }
+
+    /// 调用目标的函数名（去掉 `@plt` 后缀）
+    fn call_target_name(operand: &Operand) -> String {
+        let name = Self::branch_target_name(operand);
+        match name.strip_suffix("@plt") {
+            Some(stripped) => stripped.to_string(),
+            None => name,
+        }
+    }
+
     fn operand_name(operand: &Operand) -> String {
         match operand {
             Operand::Register(reg) => format!("{:?}", reg),
@@ -441,6 +854,348 @@ impl SemanticInterpreter {
     }
 }
 
+/// ADR/ADRP 计算出的地址
+///
+/// objdump 已把目标写成绝对地址（`adrp x0, 411000 <foo>`）；立即数形式按指令地址换算：
+/// ADR 为 PC + imm，ADRP 为 PC 所在的 4KB 页 + imm
+pub fn pc_relative_target(inst: &Instruction) -> Option<u64> {
+    match inst.operands.get(1)? {
+        Operand::Label(label) => {
+            let text = label.split_whitespace().next()?;
+            u64::from_str_radix(text.trim_start_matches("0x"), 16).ok()
+        }
+        Operand::Immediate(imm) => {
+            let base = match inst.instruction_type {
+                InstructionType::ADRP => inst.address & !0xfff,
+                _ => inst.address,
+            };
+            Some(base.wrapping_add(*imm as u64))
+        }
+        _ => None,
+    }
+}
+
+/// 把位掩码描述为可读的位范围（`第 3 位`、`低 8 位`、`高 4 位`、`第 4-7 位`）
+///
+/// 不是连续一段 1 的掩码返回 None。width 为寄存器位宽（32 或 64）。
+pub fn describe_mask(mask: u64, width: u32, lang: Lang) -> Option<String> {
+    if mask == 0 {
+        return None;
+    }
+    let low = mask.trailing_zeros();
+    let len = (mask >> low).trailing_ones();
+    if mask >> low >> len != 0 {
+        return None;
+    }
+    let high = low + len - 1;
+    Some(match lang {
+        Lang::Zh if len == 1 => format!("第 {} 位", low),
+        Lang::Zh if len == width => String::from("全部位"),
+        Lang::Zh if low == 0 => format!("低 {} 位", len),
+        Lang::Zh if high == width - 1 => format!("高 {} 位", len),
+        Lang::Zh => format!("第 {}-{} 位", low, high),
+        Lang::En if len == 1 => format!("bit {}", low),
+        Lang::En if len == width => String::from("all bits"),
+        Lang::En if low == 0 => format!("low {} bits", len),
+        Lang::En if high == width - 1 => format!("high {} bits", len),
+        Lang::En => format!("bits {}-{}", low, high),
+    })
+}
+
+/// 逻辑立即数按 2/4/8/16/32 位的元素重复：返回最小的元素位宽和元素内的掩码（不重复时为整个寄存器）
+fn repeating_element(mask: u64, width: u32) -> (u32, u64) {
+    let mut size = 2;
+    while size < width {
+        let element = mask & ((1u64 << size) - 1);
+        let replicated = (0..width / size).fold(0u64, |acc, i| acc | element << (i * size));
+        if replicated == mask {
+            return (size, element);
+        }
+        size *= 2;
+    }
+    (width, mask)
+}
+
+/// 帧指针和返回地址成对保存/恢复并调整 SP 的指令，返回（是否为序言，调整的字节数）
+fn frame_adjustment(instruction: &Instruction) -> Option<(bool, i64)> {
+    let frame_pair = |a: &Operand, b: &Operand| match (a, b) {
+        (Operand::Register(a), Operand::Register(b)) => a.index() == Some(29) && b.index() == Some(30),
+        _ => false,
+    };
+    match (instruction.instruction_type, instruction.operands.as_slice()) {
+        (InstructionType::STP, [a, b, Operand::Memory { base: Register::SP, offset: Some(offset), pre_indexed: true, .. }])
+            if frame_pair(a, b) && *offset < 0 =>
+        {
+            Some((true, -offset))
+        }
+        (InstructionType::LDP, [a, b, Operand::Memory { base: Register::SP, offset: None, .. }, Operand::Immediate(size)])
+            if frame_pair(a, b) && *size > 0 =>
+        {
+            Some((false, *size))
+        }
+        _ => None,
+    }
+}
+
+/// 未链接的目标文件中 ADR/ADRP 的偏移为 0，目标恰好是指令自身（所在的页）
+pub fn awaits_relocation(inst: &Instruction, target: u64) -> bool {
+    match inst.instruction_type {
+        InstructionType::ADRP => target == inst.address & !0xfff,
+        _ => target == inst.address,
+    }
+}
+
+/// 标签恰好指向某个符号时返回符号名（`411000 <foo>` -> `foo`，`<foo+0x10>` 不算）
+fn exact_symbol(operand: &Operand) -> Option<String> {
+    match operand {
+        Operand::Label(label) => crate::objdump::target_symbol(label)
+            .filter(|sym| !sym.contains('+'))
+            .map(|sym| sym.to_string()),
+        _ => None,
+    }
+}
+
+/// ADRP 写入的页基址
+#[derive(Debug, Clone)]
+struct Page {
+    address: u64,
+    symbol: Option<String>,
+    pending: bool,
+}
+
+/// 跟踪 ADRP 写入的页基址，把随后补全低 12 位的 ADD/LDR/STR 与之合并解释
+///
+/// 按地址顺序对每条指令调用 [`PageTracker::fuse`]；页基址所在的寄存器被改写后不再合并
+#[derive(Debug, Default)]
+pub struct PageTracker {
+    pages: HashMap<usize, Page>,
+}
+
+impl PageTracker {
+    /// 创建空的跟踪器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回与前面的 ADRP 合并后的解释；不是补全地址的指令返回 None
+    pub fn fuse(&mut self, interpreter: &SemanticInterpreter, inst: &Instruction) -> Option<String> {
+        let fused = self.complete(interpreter.lang, inst);
+
+        let (defs, _) = crate::analysis::def_use(inst);
+        for reg in defs.iter() {
+            self.pages.remove(&reg);
+        }
+        if inst.instruction_type == InstructionType::ADRP {
+            if let (Some(Operand::Register(rd)), Some(address)) = (inst.operands.first(), pc_relative_target(inst)) {
+                if let Some(index) = rd.index() {
+                    let page = Page {
+                        address,
+                        symbol: exact_symbol(&inst.operands[1]),
+                        pending: awaits_relocation(inst, address),
+                    };
+                    self.pages.insert(index, page);
+                }
+            }
+        }
+        fused
+    }
+
+    /// ADD 或基址为页基址寄存器、只带立即数偏移的加载/存储
+    fn complete(&self, lang: Lang, inst: &Instruction) -> Option<String> {
+        use InstructionType::*;
+
+        let (reg, base, offset) = match (inst.instruction_type, inst.operands.as_slice()) {
+            (ADD, [Operand::Register(rd), Operand::Register(rn), Operand::Immediate(imm)]) => (*rd, *rn, *imm),
+            (
+                LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | STR | STRB | STRH,
+                [Operand::Register(rt), Operand::Memory { base, offset, index: None, pre_indexed: false, post_indexed: false }],
+            ) => (*rt, *base, offset.unwrap_or(0)),
+            _ => return None,
+        };
+        let page = self.pages.get(&base.index()?)?;
+        let reg = SemanticInterpreter::operand_name(&Operand::Register(reg));
+        let pair = format!("adrp + {:?}", inst.instruction_type).to_lowercase();
+
+        // 页基址恰好是符号且没有页内偏移时使用符号名，否则使用完整地址
+        let name = match page.symbol {
+            Some(ref sym) if offset == 0 => sym.clone(),
+            _ => format!("0x{:x}", page.address.wrapping_add(offset as u64)),
+        };
+        let text = match (inst.instruction_type, page.pending, lang) {
+            (ADD, false, Lang::Zh) => format!("加载全局变量 {} 的地址到 {} ({})", name, reg, pair),
+            (ADD, true, Lang::Zh) => format!("加载全局变量的地址到 {} ({}，待链接器重定位)", reg, pair),
+            (ADD, false, Lang::En) => format!("load the address of global {} into {} ({})", name, reg, pair),
+            (ADD, true, Lang::En) => format!("load the address of a global into {} ({}, pending relocation)", reg, pair),
+            (STR | STRB | STRH, false, Lang::Zh) => format!("将 {} 存储到全局变量 {} ({})", reg, name, pair),
+            (STR | STRB | STRH, true, Lang::Zh) => format!("将 {} 存储到全局变量 ({}，待链接器重定位)", reg, pair),
+            (STR | STRB | STRH, false, Lang::En) => format!("store {} to global {} ({})", reg, name, pair),
+            (STR | STRB | STRH, true, Lang::En) => format!("store {} to a global ({}, pending relocation)", reg, pair),
+            (_, false, Lang::Zh) => format!("从全局变量 {} 加载到 {} ({})", name, reg, pair),
+            (_, true, Lang::Zh) => format!("从全局变量加载到 {} ({}，待链接器重定位)", reg, pair),
+            (_, false, Lang::En) => format!("load global {} into {} ({})", name, reg, pair),
+            (_, true, Lang::En) => format!("load a global into {} ({}, pending relocation)", reg, pair),
+        };
+        Some(text)
+    }
+}
+
+/// 最近一次设置 NZCV 的比较：`lhs` 与 `rhs` 的关系决定各条件码
+#[derive(Debug)]
+struct Comparison {
+    lhs: String,
+    rhs: String,
+    /// 与 0 比较的是按位测试（tst）或带 S 后缀运算的结果：C 标志不对应 `lhs` 与 `rhs` 的大小关系，
+    /// 无符号条件没有意义
+    logical: bool,
+    /// 参与比较的寄存器编号，被改写后比较结果不再对应当前的值
+    regs: Vec<usize>,
+    mnemonic: &'static str,
+    address: u64,
+}
+
+/// 跟踪最近一次设置条件标志位的比较，把条件跳转/条件选择解释为具体的比较
+///
+/// 按地址顺序对每条指令调用 [`FlagTracker::fuse`]；调用、其他设置标志位的指令和汇合点
+/// （函数内的跳转目标）使记录失效
+#[derive(Debug, Default)]
+pub struct FlagTracker {
+    last: Option<Comparison>,
+    previous: Option<u64>,
+}
+
+impl FlagTracker {
+    /// 创建空的跟踪器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 标志位来源不再确定（汇合点、无法解析但设置标志位的指令等）
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// 返回带具体比较的条件跳转/条件选择解释；其他指令返回 None
+    pub fn fuse(&mut self, interpreter: &SemanticInterpreter, inst: &Instruction) -> Option<String> {
+        let fused = self.last.as_ref().and_then(|cmp| self.explain(interpreter, inst, cmp));
+
+        let (defs, _) = crate::analysis::def_use(inst);
+        let clobbered = self.last.as_ref().is_some_and(|cmp| cmp.regs.iter().any(|r| defs.contains(*r)));
+        if clobbered || matches!(inst.instruction_type, InstructionType::BL | InstructionType::BLR) {
+            self.last = None;
+        }
+        if let Some(cmp) = comparison(inst) {
+            self.last = Some(cmp);
+        } else if sets_flags(inst) {
+            self.last = None;
+        }
+        self.previous = Some(inst.address);
+        fused
+    }
+
+    fn explain(&self, interpreter: &SemanticInterpreter, inst: &Instruction, cmp: &Comparison) -> Option<String> {
+        let lang = interpreter.lang;
+        let (condition, branch) = match crate::pseudocode::branch_condition(inst.instruction_type) {
+            Some(condition) => (condition, true),
+            None if matches!(inst.instruction_type, InstructionType::CCMP | InstructionType::CCMN) => return None,
+            None => (inst.condition?, false),
+        };
+        let test = relation(cmp, condition, lang)?;
+        let source = match (self.previous == Some(cmp.address), lang) {
+            (true, Lang::Zh) => format!("（来自上一条 {}）", cmp.mnemonic),
+            (false, Lang::Zh) => format!("（来自 {:x} 处的 {}）", cmp.address, cmp.mnemonic),
+            (true, Lang::En) => format!(" (from the preceding {})", cmp.mnemonic),
+            (false, Lang::En) => format!(" (from the {} at {:x})", cmp.mnemonic, cmp.address),
+        };
+        let text = if branch {
+            let target = inst.operands.first().map(SemanticInterpreter::branch_target_name)?;
+            match lang {
+                Lang::Zh => format!("如果 {} 则跳转到 {}", test, target),
+                Lang::En => format!("branch to {} if {}", target, test),
+            }
+        } else {
+            interpreter.conditional_text(inst, &test)?
+        };
+        Some(text + &source)
+    }
+}
+
+/// 设置标志位且能还原为比较的指令：cmp/cmn/tst 比较两个操作数，adds/subs/ands 把结果与 0 比较
+/// （有符号条件按数学上的结果解释，不考虑溢出）
+fn comparison(inst: &Instruction) -> Option<Comparison> {
+    use InstructionType::*;
+
+    let name = SemanticInterpreter::operand_name;
+    if let (ADDS | SUBS | ANDS, [result @ Operand::Register(reg), ..]) = (inst.instruction_type, inst.operands.as_slice()) {
+        let mnemonic = match inst.instruction_type {
+            ADDS => "adds",
+            SUBS => "subs",
+            _ => "ands",
+        };
+        return Some(Comparison {
+            lhs: name(result),
+            rhs: String::from("0"),
+            logical: true,
+            regs: reg.index().into_iter().collect(),
+            mnemonic,
+            address: inst.address,
+        });
+    }
+    let [lhs, rhs] = inst.operands.as_slice() else {
+        return None;
+    };
+    let Operand::Register(reg) = lhs else {
+        return None;
+    };
+    let (rhs_text, logical, mnemonic) = match (inst.instruction_type, rhs) {
+        (CMP, _) => (name(rhs), false, "cmp"),
+        // cmn a, b 即比较 a 和 -b
+        (CMN, Operand::Immediate(imm)) => (format!("{}", -imm), false, "cmn"),
+        (CMN, _) => (format!("-{}", name(rhs)), false, "cmn"),
+        (TST, _) => (String::from("0"), true, "tst"),
+        _ => return None,
+    };
+    let lhs_text = match inst.instruction_type {
+        TST => format!("({} & {})", name(lhs), name(rhs)),
+        _ => name(lhs),
+    };
+    let regs = [Some(*reg), match rhs { Operand::Register(r) => Some(*r), _ => None }]
+        .into_iter()
+        .flatten()
+        .filter_map(|r| r.index())
+        .collect();
+    Some(Comparison { lhs: lhs_text, rhs: rhs_text, logical, regs, mnemonic, address: inst.address })
+}
+
+/// 解析出的指令中改写 NZCV 的指令
+fn sets_flags(inst: &Instruction) -> bool {
+    use InstructionType::*;
+    matches!(inst.instruction_type, CMP | CMN | TST | ADDS | SUBS | ANDS | CCMP | CCMN | FCMP | FCMPE)
+}
+
+/// 条件码对应的比较关系；溢出标志等无法还原为比较的条件返回 None
+fn relation(cmp: &Comparison, condition: Condition, lang: Lang) -> Option<String> {
+    use Condition::*;
+
+    let (op, unsigned) = match condition {
+        EQ => ("==", false),
+        NE => ("!=", false),
+        LT => ("<", false),
+        LE => ("<=", false),
+        GT => (">", false),
+        GE => (">=", false),
+        CC if !cmp.logical => ("<", true),
+        LS if !cmp.logical => ("<=", true),
+        HI if !cmp.logical => (">", true),
+        CS if !cmp.logical => (">=", true),
+        _ => return None,
+    };
+    let mut text = format!("{} {} {}", cmp.lhs, op, cmp.rhs);
+    if unsigned {
+        text.push_str(lang.pick(" (无符号)", " (unsigned)"));
+    }
+    Some(text)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -461,6 +1216,236 @@ mod tests {
         assert_eq!(interpretation, "X0 = X1 + X2");
     }
 
+    #[test]
+    fn test_interpret_fp_arithmetic() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let inst = &parser.parse("fadd d0, d1, d2").unwrap()[0];
+        assert_eq!(SemanticInterpreter::interpret(inst), "D0 = D1 + D2");
+
+        let inst = &parser.parse("fdiv s0, s1, s2").unwrap()[0];
+        assert_eq!(SemanticInterpreter::interpret(inst), "S0 = S1 ÷ S2");
+    }
+
+    #[test]
+    fn test_interpret_call_target() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let inst = &parser.parse("bl 400560 <printf@plt>").unwrap()[0];
+        assert_eq!(SemanticInterpreter::interpret(inst), "调用标准库函数 printf：格式化输出");
+
+        let inst = &parser.parse("bl 400600 <helper>").unwrap()[0];
+        assert_eq!(SemanticInterpreter::interpret(inst), "调用函数 helper");
+
+        let inst = &parser.parse("b 40 <sum+0x40>").unwrap()[0];
+        assert_eq!(SemanticInterpreter::interpret(inst), "跳转到 sum+0x40");
+    }
+
+    #[test]
+    fn test_interpret_english() {
+        let interpreter = SemanticInterpreter::with_language(Lang::En);
+        let mut parser = crate::parser::AssemblyParser::new();
+
+        let inst = &parser.parse("bl 400560 <printf@plt>").unwrap()[0];
+        assert_eq!(interpreter.describe(inst), "call C library function printf: formatted output");
+        let inst = &parser.parse("sdiv w0, w1, w2").unwrap()[0];
+        assert_eq!(interpreter.describe(inst), "W0 = W1 ÷ W2 (signed)");
+        let inst = &parser.parse("movk x0, #0x1234, lsl #16").unwrap()[0];
+        assert!(!interpreter.describe(inst).chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c)));
+    }
+
+    #[test]
+    fn test_interpret_template() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let mut describe = |text: &str| SemanticInterpreter::interpret(&parser.parse(text).unwrap()[0]);
+
+        assert_eq!(describe("lsl x0, x0, #2"), "X0 = X0 << 0x2");
+        assert_eq!(describe("ldp x19, x20, [sp], #16"), "从 (SP) 加载 X19 和 X20");
+        assert_eq!(describe("cbnz w1, 8 <sum+0x8>"), "如果 W1 ≠ 0 则跳转到 sum+0x8");
+
+        let template = "{rd} = {rn} + {rm}";
+        let inst = &parser.parse("neg x0, x1").unwrap()[0];
+        assert_eq!(SemanticInterpreter::render_template(template, inst), None);
+    }
+
+    #[test]
+    fn test_interpret_conditional() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let inst = &parser.parse("csel x0, x1, x2, lt").unwrap()[0];
+        assert_eq!(inst.condition, Some(crate::register::Condition::LT));
+        assert_eq!(inst.operands.len(), 3);
+        assert_eq!(SemanticInterpreter::interpret(inst), "X0 = (条件 LT 成立) ? X1 : X2");
+
+        let inst = &parser.parse("cset w0, eq").unwrap()[0];
+        assert_eq!(SemanticInterpreter::interpret(inst), "W0 = (条件 EQ 成立) ? 1 : 0");
+        let inst = &parser.parse("ccmp w1, #0x3, #0x4, ne").unwrap()[0];
+        assert_eq!(
+            SemanticInterpreter::with_language(Lang::En).describe(inst),
+            "if NE holds compare W1 with 0x3, else NZCV = 0x4"
+        );
+    }
+
+    #[test]
+    fn test_interpret_adrp_pair() {
+        let dump = r#"
+0000000000400000 <main>:
+  400000:   90000080    adrp x0, 410000 <counter>
+  400004:   91002000    add x0, x0, #0x8
+  400008:   b9400001    ldr w1, [x0]
+  40000c:   90000082    adrp x2, 410000 <counter>
+  400010:   b9400043    ldr w3, [x2]
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string()).extract_function_data("main").unwrap();
+        let interpreter = SemanticInterpreter::default();
+        let mut tracker = PageTracker::new();
+        let fused: Vec<Option<String>> = entries
+            .iter()
+            .map(|e| tracker.fuse(&interpreter, e.parsed_instruction.as_ref().unwrap()))
+            .collect();
+
+        assert_eq!(interpreter.describe(entries[0].parsed_instruction.as_ref().unwrap()), "X0 = 0x410000 (counter 所在的 4KB 页)");
+        assert_eq!(fused[1].as_deref(), Some("加载全局变量 0x410008 的地址到 X0 (adrp + add)"));
+        // add 改写了 x0，后面的 ldr 不再合并
+        assert_eq!(fused[2], None);
+        assert_eq!(fused[4].as_deref(), Some("从全局变量 counter 加载到 W3 (adrp + ldr)"));
+
+        let mut parser = crate::parser::AssemblyParser::new();
+        let inst = &parser.parse("adrp x0, 0 <main>").unwrap()[0];
+        assert_eq!(SemanticInterpreter::interpret(inst), "X0 = 页基址 (待链接器重定位)");
+    }
+
+    #[test]
+    fn test_flag_tracker() {
+        let dump = r#"
+0000000000400000 <f>:
+  400000:   6b01001f    cmp w0, w1
+  400004:   5400006b    b.lt 400010 <f+0x10>
+  400008:   9a82b020    csel x0, x1, x2, lt
+  40000c:   eb02003f    cmp x1, x2
+  400010:   aa0303e1    mov x1, x3
+  400014:   54000048    b.hi 40001c <f+0x1c>
+  400018:   ab04001f    cmn x0, #0x4
+  40001c:   1a9f07e0    cset w0, ne
+  400020:   f240003f    tst x1, #0x1
+  400024:   54000040    b.eq 40002c <f+0x2c>
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string()).extract_function_data("f").unwrap();
+        let interpreter = SemanticInterpreter::default();
+        let mut tracker = FlagTracker::new();
+        let fused: Vec<Option<String>> = entries
+            .iter()
+            .map(|e| tracker.fuse(&interpreter, e.parsed_instruction.as_ref().unwrap()))
+            .collect();
+
+        assert_eq!(fused[1].as_deref(), Some("如果 W0 < W1 则跳转到 f+0x10（来自上一条 cmp）"));
+        assert_eq!(fused[2].as_deref(), Some("X0 = (W0 < W1) ? X1 : X2（来自 400000 处的 cmp）"));
+        // mov 改写了参与比较的 x1
+        assert_eq!(fused[5], None);
+        assert_eq!(fused[7].as_deref(), Some("W0 = (X0 != -4) ? 1 : 0（来自上一条 cmn）"));
+        assert_eq!(fused[9].as_deref(), Some("如果 (X1 & 0x1) == 0 则跳转到 f+0x2c（来自上一条 tst）"));
+
+        let en = SemanticInterpreter::with_language(Lang::En);
+        let mut tracker = FlagTracker::new();
+        let fused: Vec<Option<String>> = entries[3..]
+            .iter()
+            .map(|e| tracker.fuse(&en, e.parsed_instruction.as_ref().unwrap()))
+            .collect();
+        assert_eq!(fused[2], None);
+        let mut tracker = FlagTracker::new();
+        tracker.fuse(&en, entries[3].parsed_instruction.as_ref().unwrap());
+        assert_eq!(
+            tracker.fuse(&en, entries[5].parsed_instruction.as_ref().unwrap()).as_deref(),
+            Some("branch to f+0x1c if X1 > X2 (unsigned) (from the preceding cmp)")
+        );
+    }
+
+    #[test]
+    fn test_flag_tracker_result() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let insts = parser
+            .parse("subs w0, w0, #0x1\nb.ne 0 <g>\nands w1, w2, #0x3\ncset w3, eq\nadds x0, x0, x1\nb.cs 0 <g>")
+            .unwrap();
+        let interpreter = SemanticInterpreter::default();
+        let mut tracker = FlagTracker::new();
+        let fused: Vec<Option<String>> = insts.iter().map(|inst| tracker.fuse(&interpreter, inst)).collect();
+
+        assert_eq!(fused[1].as_deref(), Some("如果 W0 != 0 则跳转到 g（来自上一条 subs）"));
+        assert_eq!(fused[3].as_deref(), Some("W3 = (W1 == 0) ? 1 : 0（来自上一条 ands）"));
+        // 进位标志不对应结果与 0 的比较
+        assert_eq!(fused[5], None);
+    }
+
+    #[test]
+    fn test_constant_chain() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let insts = parser
+            .parse("mov x2, #0x1\nmovk x2, #0x2, lsl #16\nmovk x2, #0x3, lsl #32\nmovk x3, #0x1\nmovz w0, #0x5678\nmovk w0, #0x1234, lsl #16")
+            .unwrap();
+        let insts: Vec<&Instruction> = insts.iter().collect();
+        let interpreter = SemanticInterpreter::default();
+
+        let texts = interpreter.describe_constant_chain(&insts).unwrap();
+        assert_eq!(texts, ["X2 = 0x300020001 (mov + movk × 2 拼接常量)", "(接上条，拼接常量)", "(接上条，拼接常量)"]);
+        // movk 改写的是另一个寄存器，不成链
+        assert_eq!(interpreter.describe_constant_chain(&insts[2..]), None);
+        let texts = SemanticInterpreter::with_language(Lang::En).describe_constant_chain(&insts[4..]).unwrap();
+        assert_eq!(texts[0], "W0 = 0x12345678 (constant built by movz + movk)");
+    }
+
+    #[test]
+    fn test_interpret_bit_test() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let mut describe = |text: &str| SemanticInterpreter::interpret(&parser.parse(text).unwrap()[0]);
+
+        assert_eq!(describe("tbz w0, #3, 20 <f+0x20>"), "如果 W0 的第 3 位为 0 则跳转到 f+0x20");
+        assert_eq!(describe("tbnz x1, #63, 20 <f+0x20>"), "如果 X1 的第 63 位为 1 则跳转到 f+0x20");
+        assert_eq!(describe("tst x1, #0xff"), "测试 X1 & 0xff (低 8 位) 是否为 0，设置 N、Z 标志");
+        assert_eq!(describe("tst x1, #0xfffffffffffffff0"), "测试 X1 & 0xfffffffffffffff0 (高 60 位) 是否为 0，设置 N、Z 标志");
+        assert_eq!(describe("tst w0, w1"), "测试 W0 & W1 是否为 0，设置 N、Z 标志");
+
+        assert_eq!(describe_mask(0xf0, 32, Lang::Zh).as_deref(), Some("第 4-7 位"));
+        assert_eq!(describe_mask(0x5555, 32, Lang::En), None);
+    }
+
+    #[test]
+    fn test_interpret_logical_immediate() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let mut describe = |text: &str| SemanticInterpreter::interpret(&parser.parse(text).unwrap()[0]);
+
+        assert_eq!(describe("and x0, x1, #0xfffffffffffffff0"), "X0 = X1 清除低 4 位 (& 0xfffffffffffffff0)");
+        assert_eq!(describe("and w0, w1, #0xff"), "W0 = W1 保留低 8 位 (& 0xff)");
+        assert_eq!(describe("and w0, w1, #0xffff00ff"), "W0 = W1 清除第 8-15 位 (& 0xffff00ff)");
+        assert_eq!(describe("orr w0, w1, #0x8"), "W0 = W1 置位第 3 位 (| 0x8)");
+        assert_eq!(describe("and x0, x1, #0xff00ff00ff00ff00"), "X0 = X1 清除每 16 位中的低 8 位 (& 0xff00ff00ff00ff00)");
+        // 零寄存器作为源操作数时是 mov 的另一种写法，保持原样
+        assert_eq!(describe("orr w0, wzr, #0xff"), "W0 = WZR | 0xff");
+
+        assert_eq!(repeating_element(0x5555_5555_5555_5555, 64), (2, 0b01));
+        assert_eq!(repeating_element(0xff0, 64), (64, 0xff0));
+    }
+
+    #[test]
+    fn test_interpret_frame() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let insts = parser.parse("stp x29, x30, [sp, #-32]!\nmov x29, sp\nldp x29, x30, [sp], #32\nret").unwrap();
+        let interpreter = SemanticInterpreter::default();
+        assert_eq!(interpreter.describe(&insts[0]), "函数序言: 保存帧指针和返回地址，分配 32 字节栈空间");
+        assert_eq!(interpreter.describe_frame_step(&insts[0], &insts[1]).as_deref(), Some("函数序言: 建立帧指针 (X29 = SP)"));
+        assert_eq!(interpreter.describe(&insts[2]), "函数尾声: 恢复帧指针和返回地址，释放 32 字节栈空间");
+        assert_eq!(interpreter.describe_frame_step(&insts[2], &insts[3]).as_deref(), Some("函数尾声: 返回调用者"));
+        // 非帧指针的寄存器对仍按普通存储解释
+        let other = &parser.parse("stp x19, x20, [sp, #-32]!").unwrap()[0];
+        assert!(!interpreter.describe(other).starts_with("函数序言"));
+    }
+
+    #[test]
+    fn test_interpret_multiply_accumulate() {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let mut describe = |text: &str| SemanticInterpreter::interpret(&parser.parse(text).unwrap()[0]);
+
+        assert_eq!(describe("madd x0, x1, x2, x3"), "X0 = X1 × X2 + X3");
+        assert_eq!(describe("msub w0, w1, w2, w3"), "W0 = W3 - W1 × W2");
+        assert_eq!(describe("smull x0, w1, w2"), "X0 = W1 × W2 (有符号 32×32→64 位)");
+    }
+
     #[test]
     fn test_interpret_ldr() {
         let inst = Instruction::new(
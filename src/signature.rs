@@ -0,0 +1,643 @@
+//! 函数签名推断
+//!
+//! 综合参数寄存器的使用方式、栈上传递的参数和返回值来源，尽力还原函数的 C 签名
+//! （如 `int sum(int *, int)`）。汇编中不保留类型信息，推断结果附带依据和置信度。
+
+use crate::analysis::{self, LivenessAnalysis, ValueSource};
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::register::Register;
+use serde::Serialize;
+
+/// 推断的置信度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    /// 低：只凭调用约定或无法确定的来源推测
+    Low,
+    /// 中：位宽可靠，但可能是其他同宽类型
+    Medium,
+    /// 高：有直接的使用证据
+    High,
+}
+
+impl Confidence {
+    /// 显示名称
+    pub fn name(self, lang: Lang) -> &'static str {
+        match self {
+            Confidence::Low => lang.pick("低", "low"),
+            Confidence::Medium => lang.pick("中", "medium"),
+            Confidence::High => lang.pick("高", "high"),
+        }
+    }
+}
+
+/// 一个推断出的参数
+#[derive(Debug, Clone, Serialize)]
+pub struct Parameter {
+    /// 传递位置（如 `x0`、`d0`、`[sp+0]`）
+    pub location: String,
+    /// 推断的 C 类型
+    pub ty: String,
+    /// 推断依据
+    pub reason: String,
+    /// 置信度
+    pub confidence: Confidence,
+}
+
+/// 推断出的函数签名
+#[derive(Debug, Clone, Serialize)]
+pub struct Signature {
+    /// 函数名
+    pub name: String,
+    /// 返回类型
+    pub return_type: String,
+    /// 返回类型的推断依据
+    pub return_reason: String,
+    /// 返回类型的置信度
+    pub return_confidence: Confidence,
+    /// 返回值直接来自该函数的返回值时记录其名称，可用其签名修正返回类型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_callee: Option<String>,
+    /// 参数（按调用约定的顺序）
+    pub parameters: Vec<Parameter>,
+    /// 说明
+    pub notes: Vec<String>,
+    /// 推断依据和说明使用的语言
+    #[serde(skip)]
+    lang: Lang,
+}
+
+impl Signature {
+    /// 从函数条目推断签名，推断依据使用 lang 指定的语言；函数中没有可解析的指令时返回 None
+    pub fn infer(name: &str, entries: &[DumpEntry], lang: Lang) -> Option<Self> {
+        let entries: Vec<&DumpEntry> = entries.iter().filter(|e| e.parsed_instruction.is_some()).collect();
+        let instructions: Vec<Instruction> = entries
+            .iter()
+            .filter_map(|e| e.parsed_instruction.clone())
+            .collect();
+        if instructions.is_empty() {
+            return None;
+        }
+
+        let mut signature = Self {
+            name: name.to_string(),
+            return_type: String::new(),
+            return_reason: String::new(),
+            return_confidence: Confidence::High,
+            return_callee: None,
+            parameters: Vec::new(),
+            notes: Vec::new(),
+            lang,
+        };
+
+        let liveness = LivenessAnalysis::compute(&instructions);
+        let used = analysis::parameter_registers(&instructions, &liveness);
+        // 后面的参数寄存器被使用时，前面未使用的也必然是参数
+        let count = used.last().map_or(0, |last| last + 1);
+        for reg in 0..count {
+            let parameter = if used.contains(&reg) {
+                integer_parameter(&entries, &instructions, reg, lang)
+            } else {
+                let reason = match lang {
+                    Lang::Zh => format!("未使用，但 x{} 被使用，按调用约定必然存在", count - 1),
+                    Lang::En => format!("unused, but x{} is used, so the calling convention requires it", count - 1),
+                };
+                Parameter { location: format!("x{}", reg), ty: String::from("long"), reason, confidence: Confidence::Low }
+            };
+            signature.parameters.push(parameter);
+        }
+
+        let floats = float_parameters(&instructions, lang);
+        if !floats.is_empty() && count > 0 {
+            signature.notes.push(lang.pick(
+                "浮点参数与整数参数的相对顺序无法从寄存器确定，浮点参数列在最后",
+                "The order of floating-point and integer parameters cannot be told from registers; floating-point parameters are listed last",
+            ).to_string());
+        }
+        signature.parameters.extend(floats);
+
+        let stack = stack_parameters(&entries, &instructions, lang);
+        if !stack.is_empty() {
+            signature.notes.push(lang.pick(
+                "栈上参数按入口 SP 偏移列出，位于寄存器参数之后",
+                "Stack parameters are listed by their offset from the entry SP and follow the register parameters",
+            ).to_string());
+        }
+        signature.parameters.extend(stack);
+
+        signature.infer_return(&instructions);
+        Some(signature)
+    }
+
+    /// 返回值来自其他函数时，用该函数推断出的返回类型替换默认推测
+    pub fn resolve_return(&mut self, callee: &Signature) {
+        if self.return_callee.as_deref() != Some(callee.name.as_str()) {
+            return;
+        }
+        self.return_type = callee.return_type.clone();
+        self.return_reason = match self.lang {
+            Lang::Zh => format!("直接返回 {} 的返回值（{}）", callee.name, callee.return_type),
+            Lang::En => format!("returns the return value of {} ({})", callee.name, callee.return_type),
+        };
+        self.return_confidence = callee.return_confidence.min(Confidence::Medium);
+        self.return_callee = None;
+    }
+
+    /// 整体置信度：各参数和返回类型中最低的一项
+    pub fn confidence(&self) -> Confidence {
+        self.parameters.iter().map(|p| p.confidence).fold(self.return_confidence, Confidence::min)
+    }
+
+    /// C 风格的签名，如 `int sum(int *, int)`
+    pub fn to_c(&self) -> String {
+        let parameters = if self.parameters.is_empty() {
+            String::from("void")
+        } else {
+            self.parameters.iter().map(|p| p.ty.as_str()).collect::<Vec<_>>().join(", ")
+        };
+        format!("{} {}({})", self.return_type, self.name, parameters)
+    }
+
+    /// Markdown 格式的签名、推断依据和置信度说明
+    pub fn to_markdown(&self) -> String {
+        let lang = self.lang;
+        let mut output = format!("{}: `{}`\n\n", lang.pick("推断签名", "Inferred signature"), self.to_c());
+        output.push_str(lang.pick(
+            "| 位置 | 推断类型 | 依据 | 置信度 |\n|------|----------|------|--------|\n",
+            "| Location | Inferred type | Evidence | Confidence |\n|----------|---------------|----------|------------|\n",
+        ));
+        for p in &self.parameters {
+            output.push_str(&format!("| {} | `{}` | {} | {} |\n", p.location, p.ty, p.reason, p.confidence.name(lang)));
+        }
+        output.push_str(&format!(
+            "| {} | `{}` | {} | {} |\n",
+            lang.pick("返回值", "Return value"),
+            self.return_type,
+            self.return_reason,
+            self.return_confidence.name(lang)
+        ));
+        output.push_str(&format!("\n{}: {}\n", lang.pick("整体置信度", "Overall confidence"), self.confidence().name(lang)));
+        for note in &self.notes {
+            output.push_str(&format!("\n> {}\n", note));
+        }
+        output
+    }
+
+    /// 根据各 RET 出口处 x0 的来源推断返回类型
+    fn infer_return(&mut self, instructions: &[Instruction]) {
+        let lang = self.lang;
+        let exits = analysis::exit_paths(instructions);
+        let (ty, reason, confidence) = if exits.is_empty() {
+            (String::from("void"), lang.pick("函数没有返回指令", "the function has no return instruction").to_string(), Confidence::Medium)
+        } else if let Some(callee) = exits.iter().find_map(|e| e.tail_call.clone()).filter(|_| {
+            exits.iter().all(|e| e.tail_call.is_some())
+        }) {
+            self.return_callee = Some(callee.clone());
+            let reason = match lang {
+                Lang::Zh => format!("尾调用 {}，返回值由其提供", callee),
+                Lang::En => format!("tail-calls {}, which provides the return value", callee),
+            };
+            (String::from("long"), reason, Confidence::Low)
+        } else {
+            let sources: Vec<&(Option<usize>, ValueSource)> = exits.iter().flat_map(|e| &e.sources).collect();
+            return_from_sources(self, instructions, &sources)
+        };
+        self.return_type = ty;
+        self.return_reason = reason;
+        self.return_confidence = confidence;
+    }
+}
+
+/// 由 x0 的来源推断返回类型
+fn return_from_sources(
+    signature: &mut Signature,
+    instructions: &[Instruction],
+    sources: &[&(Option<usize>, ValueSource)],
+) -> (String, String, Confidence) {
+    let lang = signature.lang;
+    let widths: Vec<bool> = sources
+        .iter()
+        .filter_map(|(i, _)| match instructions[(*i)?].operands.first() {
+            Some(Operand::Register(reg)) => Some(reg.is_64bit()),
+            _ => None,
+        })
+        .collect();
+    if !widths.is_empty() {
+        return if widths.contains(&true) {
+            signature.notes.push(lang.pick("64 位返回值也可能是指针", "A 64-bit return value may also be a pointer").to_string());
+            let reason = lang.pick("返回前以 64 位 x0 写入", "written as 64-bit x0 before returning");
+            (String::from("long"), reason.to_string(), Confidence::Medium)
+        } else {
+            let reason = lang.pick("返回前以 32 位 w0 写入", "written as 32-bit w0 before returning");
+            (String::from("int"), reason.to_string(), Confidence::High)
+        };
+    }
+
+    if let Some(callee) = sources.iter().find_map(|(_, source)| match source {
+        ValueSource::CallResult(callee) => Some(callee.clone()),
+        _ => None,
+    }) {
+        signature.return_callee = Some(callee.clone());
+        let reason = match lang {
+            Lang::Zh => format!("直接返回 {} 的返回值，类型未知", callee),
+            Lang::En => format!("returns the return value of {}, type unknown", callee),
+        };
+        return (String::from("long"), reason, Confidence::Low);
+    }
+
+    // x0 未被写入：可能是 void，也可能是浮点返回值或原样返回第一个参数
+    let float = instructions.iter().rev().find_map(|inst| match inst.operands.first() {
+        Some(Operand::Register(reg)) if reg.fp_index() == Some(0) && !is_store(inst) => Some(*reg),
+        _ => None,
+    });
+    match float {
+        Some(reg) => {
+            let reason = match lang {
+                Lang::Zh => format!("返回前写入 {}", register_name(reg)),
+                Lang::En => format!("{} written before returning", register_name(reg)),
+            };
+            (float_type(reg).to_string(), reason, Confidence::Medium)
+        }
+        None => {
+            signature.notes.push(lang.pick(
+                "x0 在返回前未被修改，也可能原样返回第一个参数",
+                "x0 is not modified before returning, so the function may also return its first argument unchanged",
+            ).to_string());
+            let reason = lang.pick("返回前未写入 x0", "x0 is not written before returning");
+            (String::from("void"), reason.to_string(), Confidence::Medium)
+        }
+    }
+}
+
+/// 推断整数寄存器参数的类型
+fn integer_parameter(entries: &[&DumpEntry], instructions: &[Instruction], reg: usize, lang: Lang) -> Parameter {
+    let location = format!("x{}", reg);
+    if let Some(pointer) = pointer_use(entries, instructions, reg, lang) {
+        return Parameter { location, ty: pointer.0, reason: pointer.1, confidence: Confidence::High };
+    }
+
+    // 第一次读取参数的指令决定其位宽
+    let first = instructions.iter().position(|inst| analysis::def_use(inst).1.contains(reg));
+    let width = first.and_then(|i| mentioned_register(&instructions[i], reg).map(|r| (i, r)));
+    match width {
+        Some((i, _)) if matches!(instructions[i].instruction_type, InstructionType::STRB) => {
+            let reason = match lang {
+                Lang::Zh => format!("按字节保存（{}）", entries[i].asm_instruction.replace('\t', " ")),
+                Lang::En => format!("stored as a byte ({})", entries[i].asm_instruction.replace('\t', " ")),
+            };
+            Parameter { location, ty: String::from("char"), reason, confidence: Confidence::High }
+        }
+        Some((i, _)) if matches!(instructions[i].instruction_type, InstructionType::STRH) => {
+            let reason = match lang {
+                Lang::Zh => format!("按半字保存（{}）", entries[i].asm_instruction.replace('\t', " ")),
+                Lang::En => format!("stored as a halfword ({})", entries[i].asm_instruction.replace('\t', " ")),
+            };
+            Parameter { location, ty: String::from("short"), reason, confidence: Confidence::High }
+        }
+        Some((_, r)) if !r.is_64bit() => {
+            let reason = match lang {
+                Lang::Zh => format!("以 32 位 w{} 使用", reg),
+                Lang::En => format!("used as 32-bit w{}", reg),
+            };
+            Parameter { location, ty: String::from("int"), reason, confidence: Confidence::High }
+        }
+        _ => {
+            let reason = match lang {
+                Lang::Zh => format!("以 64 位 x{} 使用，未用作内存地址", reg),
+                Lang::En => format!("used as 64-bit x{}, never as a memory address", reg),
+            };
+            Parameter { location, ty: String::from("long"), reason, confidence: Confidence::Medium }
+        }
+    }
+}
+
+/// 参数（或由它复制、偏移得到的值）被用作内存基址时，返回指针类型和依据
+///
+/// 按地址顺序扫描，跟踪寄存器复制、ADD/SUB 偏移，以及 -O0 下先存入栈槽再重新加载的情况。
+fn pointer_use(entries: &[&DumpEntry], instructions: &[Instruction], reg: usize, lang: Lang) -> Option<(String, String)> {
+    use InstructionType::*;
+
+    // derived[r]: r 中的值来自参数；offset[r]: 是否经过了 ADD/SUB
+    let mut derived = [false; 31];
+    let mut offset = [false; 31];
+    let mut slots: Vec<(Option<usize>, i64)> = Vec::new();
+    let mut spilled = false;
+    let mut fields: Vec<(i64, &'static str)> = Vec::new();
+    let mut first = None;
+    derived[reg] = true;
+
+    for (i, inst) in instructions.iter().enumerate() {
+        let ops = inst.operands.as_slice();
+        let memory = ops.iter().find_map(|op| match op {
+            Operand::Memory { base, offset: off, .. } => Some((*base, off.unwrap_or(0))),
+            _ => None,
+        });
+
+        if let Some((base, off)) = memory {
+            let base_index = base.index().filter(|b| *b != 29);
+            if let Some(b) = base_index.filter(|b| derived[*b]) {
+                first.get_or_insert(i);
+                if !offset[b] && !fields.iter().any(|(o, _)| *o == off) {
+                    fields.push((off, access_type(inst)));
+                }
+            } else if is_stack(base) {
+                // 参数存入栈槽（STP 的第二个寄存器紧跟在第一个之后）
+                let stored: Vec<(Register, i64)> = match (inst.instruction_type, ops) {
+                    (STR | STUR, [Operand::Register(r), ..]) => vec![(*r, off)],
+                    (STP, [Operand::Register(a), Operand::Register(b), ..]) => {
+                        vec![(*a, off), (*b, off + if a.is_64bit() { 8 } else { 4 })]
+                    }
+                    _ => Vec::new(),
+                };
+                for (r, slot) in stored {
+                    // 32 位寄存器装不下指针
+                    let key = (base.index(), slot);
+                    let carries = r.is_64bit() && r.index().is_some_and(|r| derived[r] && !offset[r]);
+                    slots.retain(|s| *s != key);
+                    if carries {
+                        slots.push(key);
+                    }
+                }
+                // 从保存参数的栈槽重新加载
+                if let (LDR | LDUR, [Operand::Register(dst), ..]) = (inst.instruction_type, ops) {
+                    if let (Some(d), true) = (dst.index(), slots.contains(&(base.index(), off))) {
+                        derived[d] = true;
+                        offset[d] = false;
+                        spilled = true;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        let (defs, _) = analysis::def_use(inst);
+        let source = match (inst.instruction_type, ops) {
+            (MOV | ADD | SUB, [Operand::Register(dst), rest @ ..]) if dst.is_64bit() => rest
+                .iter()
+                .filter_map(|op| match op {
+                    Operand::Register(r) => r.index().filter(|r| derived[*r]),
+                    _ => None,
+                })
+                .next(),
+            _ => None,
+        };
+        for d in defs.iter() {
+            derived[d] = false;
+        }
+        if let (Some(src), [Operand::Register(dst), ..]) = (source, ops) {
+            if let Some(d) = dst.index() {
+                derived[d] = true;
+                offset[d] = offset[src] || inst.instruction_type != MOV;
+            }
+        }
+    }
+
+    let first = first?;
+    let via = if spilled { lang.pick("保存到栈上后重新加载，", "spilled to the stack and reloaded, then ") } else { "" };
+    if fields.len() >= 2 {
+        // 各偏移都是同一访问宽度的整数倍时是按元素访问的数组，否则字段类型未知，写作 void *
+        let (_, element) = fields[0];
+        let array = fields.iter().all(|(off, ty)| *ty == element && off % type_size(ty) == 0);
+        let reason = match (array, lang) {
+            (true, Lang::Zh) => format!("{}以 {} 的宽度在 {} 个不同偏移访问内存，可能是 {} 数组", via, element, fields.len(), element),
+            (true, Lang::En) => format!("{}accesses {}-sized elements at {} different offsets, likely an array of {}", via, element, fields.len(), element),
+            (false, Lang::Zh) => format!("{}以 {} 个不同偏移访问内存，可能是结构体指针", via, fields.len()),
+            (false, Lang::En) => format!("{}accesses memory at {} different offsets, likely a struct pointer", via, fields.len()),
+        };
+        let ty = if array { format!("{} *", element) } else { String::from("void *") };
+        return Some((ty, reason));
+    }
+    let pointee = fields.first().map_or_else(|| access_type(&instructions[first]), |(_, ty)| *ty);
+    let asm = entries[first].asm_instruction.replace('\t', " ");
+    let reason = match lang {
+        Lang::Zh => format!("{}用作内存基址（{}）", via, asm),
+        Lang::En => format!("{}used as a memory base ({})", via, asm),
+    };
+    Some((format!("{} *", pointee), reason))
+}
+
+/// 入口处被读取的 d0-d7/s0-s7 视为浮点参数
+fn float_parameters(instructions: &[Instruction], lang: Lang) -> Vec<Parameter> {
+    (0..8)
+        .filter_map(|n| {
+            let (inst, position, reg) = instructions.iter().find_map(|inst| {
+                inst.operands.iter().enumerate().find_map(|(p, op)| match op {
+                    Operand::Register(reg) if reg.fp_index() == Some(n) => Some((inst, p, *reg)),
+                    _ => None,
+                })
+            })?;
+            // 第一次出现在目标位置（存储和比较除外）说明入口时的值没有被使用
+            let reads = position > 0 || is_store(inst) || matches!(inst.instruction_type, InstructionType::FCMP);
+            reads.then(|| Parameter {
+                location: register_name(reg).replace(['s', 'h'], "d"),
+                ty: float_type(reg).to_string(),
+                reason: match lang {
+                    Lang::Zh => format!("入口处读取 {}", register_name(reg)),
+                    Lang::En => format!("{} read on entry", register_name(reg)),
+                },
+                confidence: Confidence::Medium,
+            })
+        })
+        .collect()
+}
+
+/// 从调用者栈帧（入口 SP 之上）加载的值视为栈上传递的参数
+fn stack_parameters(entries: &[&DumpEntry], instructions: &[Instruction], lang: Lang) -> Vec<Parameter> {
+    use InstructionType::*;
+
+    // 序言中分配的栈空间，以及帧指针相对入口 SP 的距离
+    let mut frame = 0i64;
+    let mut fp_gap = None;
+    for inst in instructions.iter().take_while(|i| !matches!(i.instruction_type, B | BL | BLR | RET)) {
+        match (inst.instruction_type, inst.operands.as_slice()) {
+            (STP | STR, [.., Operand::Memory { base: Register::SP, offset: Some(off), pre_indexed: true, .. }]) => {
+                frame -= off
+            }
+            (SUB, [Operand::Register(Register::SP), Operand::Register(Register::SP), Operand::Immediate(n)]) => {
+                frame += n
+            }
+            (MOV, [Operand::Register(fp), Operand::Register(Register::SP)]) if fp.index() == Some(29) => {
+                fp_gap = Some(frame)
+            }
+            (ADD, [Operand::Register(fp), Operand::Register(Register::SP), Operand::Immediate(n)])
+                if fp.index() == Some(29) =>
+            {
+                fp_gap = Some(frame - n)
+            }
+            _ => {}
+        }
+    }
+
+    let mut parameters: Vec<Parameter> = Vec::new();
+    for (i, inst) in instructions.iter().enumerate() {
+        if !matches!(inst.instruction_type, LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | LDUR) {
+            continue;
+        }
+        let Some(Operand::Memory { base, offset, .. }) = inst.operands.get(1) else {
+            continue;
+        };
+        let entry_offset = match base {
+            Register::SP => offset.unwrap_or(0) - frame,
+            r if r.index() == Some(29) => match fp_gap {
+                Some(gap) => offset.unwrap_or(0) - gap,
+                None => continue,
+            },
+            _ => continue,
+        };
+        let location = format!("[sp+{}]", entry_offset);
+        if entry_offset < 0 || parameters.iter().any(|p| p.location == location) {
+            continue;
+        }
+        let asm = entries[i].asm_instruction.replace('\t', " ");
+        let reason = match lang {
+            Lang::Zh => format!("从调用者栈帧加载（{}）", asm),
+            Lang::En => format!("loaded from the caller's frame ({})", asm),
+        };
+        parameters.push(Parameter { location, ty: access_type(inst).to_string(), reason, confidence: Confidence::Low });
+    }
+    parameters.sort_by_key(|p| p.location[4..p.location.len() - 1].parse::<i64>().unwrap_or(0));
+    parameters
+}
+
+/// 访存指令按数据宽度对应的 C 类型
+fn access_type(inst: &Instruction) -> &'static str {
+    use InstructionType::*;
+
+    match inst.instruction_type {
+        LDRB | STRB | LDRSB => "char",
+        LDRH | STRH | LDRSH => "short",
+        LDRSW => "int",
+        _ => match inst.operands.first() {
+            Some(Operand::Register(reg)) if reg.is_fp() => float_type(*reg),
+            Some(Operand::Register(reg)) if reg.is_64bit() => "long",
+            _ => "int",
+        },
+    }
+}
+
+/// 指令中编号为 reg 的通用寄存器（包括内存操作数的基址）
+fn mentioned_register(inst: &Instruction, reg: usize) -> Option<Register> {
+    inst.operands.iter().find_map(|op| match op {
+        Operand::Register(r) if r.index() == Some(reg) => Some(*r),
+        Operand::Memory { base, .. } if base.index() == Some(reg) => Some(*base),
+        _ => None,
+    })
+}
+
+fn is_stack(base: Register) -> bool {
+    base == Register::SP || base.index() == Some(29)
+}
+
+fn is_store(inst: &Instruction) -> bool {
+    use InstructionType::*;
+    matches!(inst.instruction_type, STR | STRB | STRH | STP | STUR | STLR)
+}
+
+/// access_type 给出的类型的字节数
+fn type_size(ty: &str) -> i64 {
+    match ty {
+        "char" => 1,
+        "short" => 2,
+        "int" | "float" => 4,
+        _ => 8,
+    }
+}
+
+fn float_type(reg: Register) -> &'static str {
+    if register_name(reg).starts_with('d') { "double" } else { "float" }
+}
+
+fn register_name(reg: Register) -> String {
+    format!("{:?}", reg).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <sum>:
+   0:	d10083ff 	sub	sp, sp, #0x20
+   4:	f90007e0 	str	x0, [sp, #8]
+   8:	b90007e1 	str	w1, [sp, #4]
+   c:	b9001fff 	str	wzr, [sp, #28]
+  10:	f94007e1 	ldr	x1, [sp, #8]
+  14:	b9400020 	ldr	w0, [x1]
+  18:	910083ff 	add	sp, sp, #0x20
+  1c:	d65f03c0 	ret
+
+0000000000000020 <main>:
+  20:	a9bf7bfd 	stp	x29, x30, [sp, #-16]!
+  24:	910003fd 	mov	x29, sp
+  28:	b94013a0 	ldr	w0, [x29, #16]
+  2c:	97fffff5 	bl	0 <sum>
+  30:	a8c17bfd 	ldp	x29, x30, [sp], #16
+  34:	d65f03c0 	ret
+"#;
+
+    #[test]
+    fn test_infer_signature() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let sum = Signature::infer("sum", &parser.extract_function_data("sum").unwrap(), Lang::Zh).unwrap();
+        assert_eq!(sum.to_c(), "int sum(int *, int)");
+        assert_eq!(sum.confidence(), Confidence::High);
+        assert!(sum.to_markdown().contains("保存到栈上后重新加载"));
+
+        let mut main = Signature::infer("main", &parser.extract_function_data("main").unwrap(), Lang::Zh).unwrap();
+        assert_eq!(main.to_c(), "long main(int)");
+        assert_eq!(main.parameters[0].location, "[sp+0]");
+        assert_eq!(main.confidence(), Confidence::Low);
+        main.resolve_return(&sum);
+        assert_eq!(main.to_c(), "int main(int)");
+        assert_eq!(main.return_reason, "直接返回 sum 的返回值（int）");
+    }
+
+    #[test]
+    fn test_pointer_element_type() {
+        let dump = r#"
+0000000000000000 <unr>:
+   0:	b9400001 	ldr	w1, [x0]
+   4:	b9400402 	ldr	w2, [x0, #4]
+   8:	b9400803 	ldr	w3, [x0, #8]
+   c:	0b020020 	add	w0, w1, w2
+  10:	0b030000 	add	w0, w0, w3
+  14:	d65f03c0 	ret
+
+0000000000000018 <rec>:
+  18:	b9400001 	ldr	w1, [x0]
+  1c:	f9400402 	ldr	x2, [x0, #8]
+  20:	8b020020 	add	x0, x1, x2
+  24:	d65f03c0 	ret
+"#;
+        let parser = ObjdumpParser::new(dump.to_string());
+        let unr = Signature::infer("unr", &parser.extract_function_data("unr").unwrap(), Lang::Zh).unwrap();
+        assert_eq!(unr.parameters[0].ty, "int *");
+        assert_eq!(unr.parameters[0].reason, "以 int 的宽度在 3 个不同偏移访问内存，可能是 int 数组");
+        // 宽度不同的字段：类型未知，仍然是合法的 C 类型
+        let rec = Signature::infer("rec", &parser.extract_function_data("rec").unwrap(), Lang::En).unwrap();
+        assert_eq!(rec.parameters[0].ty, "void *");
+        assert!(rec.to_c().starts_with("long rec(void *"));
+        assert!(rec.parameters[0].reason.ends_with("likely a struct pointer"));
+    }
+
+    #[test]
+    fn test_signature_in_english() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let sum = Signature::infer("sum", &parser.extract_function_data("sum").unwrap(), Lang::En).unwrap();
+        let markdown = sum.to_markdown();
+        assert!(markdown.starts_with("Inferred signature: `int sum(int *, int)`"));
+        assert!(markdown.contains("| x0 | `int *` | spilled to the stack and reloaded, then used as a memory base (ldr w0, [x1]) | high |"));
+        assert!(markdown.contains("| x1 | `int` | used as 32-bit w1 | high |"));
+        assert!(markdown.contains("Overall confidence: high"));
+        assert!(!markdown.chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c)));
+
+        let mut main = Signature::infer("main", &parser.extract_function_data("main").unwrap(), Lang::En).unwrap();
+        assert_eq!(main.parameters[0].reason, "loaded from the caller's frame (ldr w0, [x29, #16])");
+        assert_eq!(main.notes, ["Stack parameters are listed by their offset from the entry SP and follow the register parameters"]);
+        main.resolve_return(&sum);
+        assert_eq!(main.return_reason, "returns the return value of sum (int)");
+    }
+}
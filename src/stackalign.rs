@@ -0,0 +1,119 @@
+//! 栈对齐检查
+//!
+//! AAPCS64 要求调用发生时 SP 按 16 字节对齐。编译器生成的代码总是满足这一点，
+//! 手写汇编却很容易在 `sub sp, sp, #8` 之后直接调用函数。本模块沿控制流图推算每个调用点的 SP
+//! （假定入口处 SP 已对齐），找出所有路径上未对齐的调用。
+
+use crate::analysis;
+use crate::instruction::{Instruction, InstructionType};
+use crate::objdump::DumpEntry;
+use serde::Serialize;
+
+/// 调用时 SP 需要对齐的字节数
+pub const STACK_ALIGNMENT: i64 = 16;
+
+/// 一处 SP 未对齐的调用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Misalignment {
+    /// 调用指令地址
+    pub address: String,
+    /// 汇编指令
+    pub asm_instruction: String,
+    /// 调用时 SP 相对入口 SP 的偏移
+    pub sp_offset: i64,
+}
+
+/// 函数的栈对齐检查结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum StackAlignment {
+    /// 所有调用点的 SP 都已对齐
+    Aligned {
+        /// 检查的调用点数
+        calls: usize,
+    },
+    /// 存在 SP 未对齐的调用
+    Misaligned {
+        /// 未对齐的调用（按地址排序）
+        calls: Vec<Misalignment>,
+    },
+    /// SP 被无法推算的方式修改，或不同路径上的 SP 不一致，无法检查
+    Unknown,
+}
+
+/// 检查函数中每个调用点的 SP 是否按 16 字节对齐
+pub fn check(entries: &[DumpEntry]) -> StackAlignment {
+    let entries: Vec<&DumpEntry> = entries.iter().filter(|e| e.parsed_instruction.is_some()).collect();
+    let instructions: Vec<Instruction> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+    let Some(frames) = analysis::stack_frames(&instructions) else {
+        return StackAlignment::Unknown;
+    };
+
+    let mut calls = 0;
+    let mut misaligned = Vec::new();
+    for (i, inst) in instructions.iter().enumerate() {
+        if !matches!(inst.instruction_type, InstructionType::BL | InstructionType::BLR) {
+            continue;
+        }
+        // 不可达的调用不检查
+        let Some(frame) = frames[i] else {
+            continue;
+        };
+        calls += 1;
+        if frame.sp % STACK_ALIGNMENT != 0 {
+            misaligned.push(Misalignment {
+                address: entries[i].address.clone(),
+                asm_instruction: entries[i].asm_instruction.replace('\t', " "),
+                sp_offset: frame.sp,
+            });
+        }
+    }
+    if misaligned.is_empty() {
+        StackAlignment::Aligned { calls }
+    } else {
+        StackAlignment::Misaligned { calls: misaligned }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <good>:
+   0:   a9bf7bfd    stp x29, x30, [sp, #-16]!
+   4:   94000000    bl 0 <puts>
+   8:   a8c17bfd    ldp x29, x30, [sp], #16
+   c:   d65f03c0    ret
+
+0000000000000010 <bad>:
+  10:   f81f0ffe    str x30, [sp, #-16]!
+  14:   d10023ff    sub sp, sp, #0x8
+  18:   34000040    cbz w0, 20 <bad+0x10>
+  1c:   94000000    bl 0 <puts>
+  20:   910023ff    add sp, sp, #0x8
+  24:   94000000    bl 0 <puts>
+  28:   f84107fe    ldr x30, [sp], #16
+  2c:   d65f03c0    ret
+
+0000000000000030 <dynamic>:
+  30:   9100001f    mov sp, x0
+  34:   94000000    bl 0 <puts>
+  38:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_stack_alignment() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        assert_eq!(check(&parser.extract_function_data("good").unwrap()), StackAlignment::Aligned { calls: 1 });
+
+        let StackAlignment::Misaligned { calls } = check(&parser.extract_function_data("bad").unwrap()) else {
+            panic!("应检测到未对齐的调用");
+        };
+        assert_eq!(calls.len(), 1);
+        assert_eq!((calls[0].address.as_str(), calls[0].sp_offset), ("1c", -24));
+
+        assert_eq!(check(&parser.extract_function_data("dynamic").unwrap()), StackAlignment::Unknown);
+    }
+}
@@ -0,0 +1,129 @@
+//! 系统调用模拟
+//!
+//! 按 AArch64 Linux 约定处理 `svc #0`：X8 为系统调用号，X0-X5 为参数，返回值写回 X0。
+//! `write`、`exit`、`exit_group` 会被模拟执行，其他系统调用只做符号化记录并返回 `-ENOSYS`，
+//! 这样就可以把完整的小程序从头到尾跑一遍用于演示。
+
+use crate::emulator::CpuState;
+use serde::Serialize;
+use std::fmt;
+
+/// 未实现的系统调用返回的错误码
+const ENOSYS: i64 = 38;
+
+/// 常见系统调用号（AArch64 Linux，见 `include/uapi/asm-generic/unistd.h`）
+const SYSCALL_NAMES: &[(u64, &str)] = &[
+    (56, "openat"),
+    (57, "close"),
+    (63, "read"),
+    (64, "write"),
+    (93, "exit"),
+    (94, "exit_group"),
+    (113, "clock_gettime"),
+    (160, "uname"),
+    (172, "getpid"),
+    (214, "brk"),
+    (215, "munmap"),
+    (222, "mmap"),
+];
+
+/// 根据系统调用号查找名称
+pub fn syscall_name(number: u64) -> Option<&'static str> {
+    SYSCALL_NAMES
+        .iter()
+        .find(|(nr, _)| *nr == number)
+        .map(|(_, name)| *name)
+}
+
+/// 一次系统调用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyscallEvent {
+    /// 系统调用号（X8）
+    pub number: u64,
+    /// 参数（X0-X2）
+    pub args: [u64; 3],
+    /// 返回值（写回 X0）
+    pub result: u64,
+    /// 是否被模拟执行（否则只记录并返回 -ENOSYS）
+    pub stubbed: bool,
+    /// 进程退出码（exit/exit_group）
+    pub exit_code: Option<u64>,
+}
+
+impl fmt::Display for SyscallEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match syscall_name(self.number) {
+            Some(name) => write!(f, "{}", name)?,
+            None => write!(f, "syscall_{}", self.number)?,
+        }
+        write!(f, "(0x{:x}, 0x{:x}, 0x{:x})", self.args[0], self.args[1], self.args[2])?;
+        if self.exit_code.is_none() {
+            write!(f, " = {}", self.result as i64)?;
+        }
+        if !self.stubbed {
+            write!(f, " (未模拟)")?;
+        }
+        Ok(())
+    }
+}
+
+/// 执行 `svc #0`，程序向 stdout/stderr 写出的内容追加到 output
+pub fn handle(state: &mut CpuState, output: &mut Vec<u8>) -> SyscallEvent {
+    let number = state.x[8];
+    let args = [state.x[0], state.x[1], state.x[2]];
+    let mut exit_code = None;
+
+    let (result, stubbed) = match syscall_name(number) {
+        Some("write") if args[0] == 1 || args[0] == 2 => {
+            match (0..args[2]).map(|i| state.load(args[1] + i, 1)).collect::<crate::Result<Vec<u64>>>() {
+                Ok(bytes) => {
+                    output.extend(bytes.iter().map(|b| *b as u8));
+                    (args[2], true)
+                }
+                // 缓冲区未映射时按 -EFAULT 返回
+                Err(_) => ((-14i64) as u64, true),
+            }
+        }
+        Some("exit") | Some("exit_group") => {
+            exit_code = Some(args[0]);
+            (0, true)
+        }
+        _ => ((-ENOSYS) as u64, false),
+    };
+
+    if exit_code.is_none() {
+        state.x[0] = result;
+    }
+
+    SyscallEvent { number, args, result, stubbed, exit_code }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_exit() {
+        let mut state = CpuState::default();
+        state.map_buffer(0x10000, b"hi\n".to_vec()).unwrap();
+        state.x[8] = 64;
+        state.x[0] = 1;
+        state.x[1] = 0x10000;
+        state.x[2] = 3;
+
+        let mut output = Vec::new();
+        let event = handle(&mut state, &mut output);
+        assert_eq!(output, b"hi\n");
+        assert_eq!(state.x[0], 3);
+        assert_eq!(event.to_string(), "write(0x1, 0x10000, 0x3) = 3");
+
+        state.x[8] = 93;
+        state.x[0] = 7;
+        assert_eq!(handle(&mut state, &mut output).exit_code, Some(7));
+
+        state.x[8] = 172;
+        let event = handle(&mut state, &mut output);
+        assert!(!event.stubbed);
+        assert_eq!(state.x[0] as i64, -ENOSYS);
+    }
+}
@@ -2,198 +2,1722 @@
 //! 
 //! 生成汇编代码和 C 代码对应关系的 Markdown 表格
 
+use crate::analyzer::{AnalysisReport, LevelAnalysis};
+use crate::arrows::JumpArrows;
+use crate::instruction::Instruction;
 use crate::objdump::DumpEntry;
-use crate::semantic::SemanticInterpreter;
-use std::path::PathBuf;
+use crate::notes::Notes;
+use crate::remarks::Remarks;
+use crate::outline::OutliningReport;
+use crate::cost::CostModel;
+use crate::diagnostics::ParseDiagnostics;
+use crate::emulator::{CpuState, Emulator};
+use crate::levels::LevelLabels;
+use crate::locale::Lang;
+use crate::metrics::{BranchStats, SizeMetrics, Stats};
+use crate::progress::{Progress, ProgressSink, Silent};
+use crate::semantic::{FlagTracker, PageTracker, SemanticInterpreter};
+use crate::values::ValueTracker;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::fs;
 use std::io::Write;
 
+/// JSON 输出中的一条指令
+#[derive(Debug, Serialize)]
+struct JsonEntry<'a> {
+    id: &'a str,
+    address: &'a str,
+    machine_code: &'a str,
+    asm_instruction: &'a str,
+    c_code: &'a str,
+    semantic: String,
+}
+
+/// JSON 输出中的一个优化级别
+#[derive(Debug, Serialize)]
+struct JsonLevel<'a> {
+    level: &'a str,
+    instructions: Vec<JsonEntry<'a>>,
+}
+
+/// JSON 输出的顶层结构
+#[derive(Debug, Serialize)]
+struct JsonReport<'a> {
+    function: &'a str,
+    levels: Vec<JsonLevel<'a>>,
+}
+
+/// 按 C 语句分组的方式（仅用于单文件分析）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Grouping {
+    /// 逐条列出指令（默认）
+    #[default]
+    None,
+    /// 每条 C 语句只占一行，显示指令数和概要
+    Collapsed,
+    /// 每条 C 语句先输出一行概要，后跟详细的指令行
+    Expanded,
+}
+
+impl Grouping {
+    /// 解析分组方式名称（none, collapsed, expanded）
+    pub fn parse(text: &str) -> crate::Result<Self> {
+        match text {
+            "none" => Ok(Grouping::None),
+            "collapsed" => Ok(Grouping::Collapsed),
+            "expanded" => Ok(Grouping::Expanded),
+            _ => Err(crate::InterpreterError::ParseError(format!(
+                "不支持的分组方式: {} (可选: none, collapsed, expanded)",
+                text
+            ))),
+        }
+    }
+}
+
+/// 报告文件已存在时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overwrite {
+    /// 直接覆盖
+    Always,
+    /// 报错，保留已有的文件（默认）
+    #[default]
+    Never,
+}
+
+/// 报告的输出结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportOutput {
+    /// 报告已保存到该路径
+    Saved(PathBuf),
+    /// 终端模式下渲染好的文本，由调用方打印
+    Terminal(String),
+}
+
+/// 展开报告文件名模板中的占位符：`{function}` 函数名、`{level}` 级别（多个级别以 `-` 连接）、
+/// `{date}` 当天日期（UTC，`YYYY-MM-DD`）。其他内容原样保留
+pub fn expand_output_template(template: &str, function: &str, level: &str, date: &str) -> String {
+    template
+        .replace("{function}", function)
+        .replace("{level}", level)
+        .replace("{date}", date)
+}
+
+/// 当天日期（UTC，`YYYY-MM-DD`）
+fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // 由 1970-01-01 起的天数换算公历日期（Howard Hinnant 的 civil_from_days）
+    let z = (secs / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// 合并报告中函数小节的锚点（函数名中的 `.`、`@` 等字符换成 `-`）
+fn combined_anchor(function: &str) -> String {
+    let name: String = function
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '-' })
+        .collect();
+    format!("fn-{}", name)
+}
+
+/// 把 Markdown 的标题降一级（代码块中的内容不变）
+fn demote_headings(markdown: &str) -> String {
+    let mut in_code = false;
+    let mut output = String::with_capacity(markdown.len() + 64);
+    for line in markdown.split_inclusive('\n') {
+        if line.starts_with("```") {
+            in_code = !in_code;
+        }
+        if !in_code && line.starts_with('#') {
+            output.push('#');
+        }
+        output.push_str(line);
+    }
+    output
+}
+
+/// 指令表格中的一列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// 指令地址
+    Address,
+    /// 机器码
+    MachineCode,
+    /// C 代码（dump 中没有源码时自动省略）
+    CCode,
+    /// 汇编指令（选择了注释列时不含 objdump 的 `//` 注释）
+    Assembly,
+    /// 语义解释
+    Semantics,
+    /// objdump 在指令后附加的 `//` 注释
+    Comment,
+    /// 所选核心模型下的延迟/吞吐量（未设置核心模型时为空）
+    Cost,
+}
+
+impl Column {
+    /// 解析列名（address, machine-code, c, asm, semantics, comment, cost）
+    pub fn parse(text: &str) -> crate::Result<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "address" | "addr" => Ok(Column::Address),
+            "machine-code" | "code" | "encoding" => Ok(Column::MachineCode),
+            "c" | "c-code" | "source" => Ok(Column::CCode),
+            "asm" | "assembly" => Ok(Column::Assembly),
+            "semantics" | "semantic" => Ok(Column::Semantics),
+            "comment" | "comments" => Ok(Column::Comment),
+            "cost" | "latency" | "cycles" => Ok(Column::Cost),
+            _ => Err(crate::InterpreterError::ParseError(format!(
+                "不支持的列: {} (可选: address, machine-code, c, asm, semantics, comment, cost)",
+                text
+            ))),
+        }
+    }
+
+    /// 表头
+    fn header(self, lang: Lang) -> &'static str {
+        match self {
+            Column::Address => lang.pick("地址", "Address"),
+            Column::MachineCode => lang.pick("机器码", "Machine code"),
+            Column::CCode => lang.pick("C代码", "C code"),
+            Column::Assembly => lang.pick("汇编指令", "Assembly"),
+            Column::Semantics => lang.pick("语义解释", "Semantics"),
+            Column::Comment => lang.pick("注释", "Comment"),
+            Column::Cost => lang.pick("延迟/吞吐", "Latency/Throughput"),
+        }
+    }
+
+    /// 表头下的分隔线
+    fn separator(self) -> &'static str {
+        match self {
+            Column::Address => "------",
+            Column::MachineCode => "--------",
+            Column::CCode => "-------",
+            Column::Assembly | Column::Semantics => "----------",
+            Column::Comment => "------",
+            Column::Cost => "---------",
+        }
+    }
+}
+
+/// 指令表格的布局：显示哪些列以及列的顺序
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableConfig {
+    columns: Vec<Column>,
+}
+
+impl TableConfig {
+    /// 默认布局：C 代码、汇编指令、语义解释
+    pub fn new() -> Self {
+        Self { columns: vec![Column::CCode, Column::Assembly, Column::Semantics] }
+    }
+
+    /// 按顺序设置显示的列（重复的列只保留第一次）
+    pub fn with_columns(mut self, columns: Vec<Column>) -> Self {
+        self.columns.clear();
+        for column in columns {
+            if !self.columns.contains(&column) {
+                self.columns.push(column);
+            }
+        }
+        self
+    }
+
+    /// 解析逗号分隔的列名，如 `address,asm,semantics`
+    pub fn parse(names: &[String]) -> crate::Result<Self> {
+        let columns = names
+            .iter()
+            .flat_map(|name| name.split(','))
+            .filter(|name| !name.trim().is_empty())
+            .map(Column::parse)
+            .collect::<crate::Result<Vec<_>>>()?;
+        if columns.is_empty() {
+            return Err(crate::InterpreterError::ParseError(String::from("至少需要选择一列")));
+        }
+        Ok(Self::new().with_columns(columns))
+    }
+
+    /// 在最前面加上地址和机器码列（已选择的列不重复添加）
+    pub fn with_encoding(self) -> Self {
+        let mut columns = vec![Column::Address, Column::MachineCode];
+        columns.extend(self.columns);
+        Self::new().with_columns(columns)
+    }
+
+    /// 显示的列
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// 表格生成器
 pub struct TableGenerator {
     /// C 代码列宽度
     c_code_width: usize,
+    /// 是否把函数内的跳转目标显示为 `.L1` 这样的标签
+    branch_labels: bool,
+    /// 是否在语义解释后标注值追踪推导出的常量和线性值
+    value_tracking: bool,
+    /// 指令表格的列（为 None 时使用默认布局）
+    config: Option<TableConfig>,
+    /// 默认布局是否包含地址和机器码列（为 None 时只在单文件分析中包含）
+    address_columns: Option<bool>,
+    /// 用户备注（合并为注释行）
+    notes: Option<Notes>,
+    /// 编译器优化备注（级别为 None 时用于所有级别）
+    remarks: Vec<(Option<String>, Remarks)>,
+    /// 是否同时输出 JSON 文件
+    json_output: bool,
+    /// 是否直接在终端输出（不写入文件）
+    terminal: bool,
+    /// 是否检测共享代码（outlining）并在调用处注释
+    outlining: bool,
+    /// 是否把引用的编译器辅助函数（`.part.N` 等）的指令附加到报告中
+    expand_helpers: bool,
+    /// 执行轨迹的初始 CPU 状态（为 None 时不生成执行轨迹）
+    trace: Option<CpuState>,
+    /// 是否附加被调用者保存寄存器的活跃区间
+    liveness: bool,
+    /// 是否在表格前生成函数导读
+    walkthrough: bool,
+    /// 是否附加各出口的返回值来源
+    returns: bool,
+    /// 是否附加推断的函数签名
+    signature: bool,
+    /// 是否附加安全加固检查
+    hardening: bool,
+    /// 是否检测并标注无用存储
+    dead_stores: bool,
+    /// 是否检查操作数位宽（隐式零扩展、扩展方式与比较不一致）
+    width_checks: bool,
+    /// 是否检查调用点的栈对齐
+    stack_alignment: bool,
+    /// 是否附加跳转方向和循环头对齐统计
+    branch_stats: bool,
+    /// 是否附加近似的类 C 伪代码
+    pseudocode: bool,
+    /// 是否在指令左侧画出函数内跳转的箭头
+    jump_arrows: bool,
+    /// 标注延迟/吞吐量并估计周期数的核心模型（为 None 时不标注）
+    cost_model: Option<CostModel>,
+    /// 对比报告是否按 C 源码行对齐各级别的指令
+    source_aligned: bool,
+    /// 单文件分析时按 C 语句分组的方式
+    grouping: Grouping,
+    /// 对比的优化级别（为 None 时自动发现）
+    levels: Option<Vec<String>>,
+    /// 语义解释和表头的语言
+    lang: Lang,
+    /// 可复现输出：报告中只写文件名不写路径，索引按函数名排序
+    reproducible: bool,
+    /// 自定义的级别标题
+    level_labels: LevelLabels,
+    /// 读取、生成和保存的进度通知（默认不输出）
+    progress: Arc<dyn ProgressSink>,
+    /// 报告文件名模板（为 None 时使用 `{function}_comparison.md`/`{function}_analysis.md`）
+    output_template: Option<String>,
+    /// 报告文件已存在时的处理方式
+    overwrite: Overwrite,
+    /// 严格解析：有指令未能被文本解析器完整解析时报错，而不是照常生成报告
+    strict: bool,
 }
 
+/// 执行轨迹的最大步数
+const TRACE_MAX_STEPS: usize = 1000;
+
 impl TableGenerator {
     pub fn new() -> Self {
         Self {
             c_code_width: 80,  // 增加到 80，确保提示信息完整显示
+            branch_labels: true,
+            value_tracking: true,
+            config: None,
+            address_columns: None,
+            notes: None,
+            remarks: Vec::new(),
+            json_output: false,
+            terminal: false,
+            outlining: false,
+            expand_helpers: false,
+            trace: None,
+            liveness: false,
+            walkthrough: false,
+            returns: false,
+            signature: false,
+            hardening: false,
+            dead_stores: false,
+            width_checks: false,
+            stack_alignment: false,
+            branch_stats: false,
+            pseudocode: false,
+            jump_arrows: false,
+            cost_model: None,
+            source_aligned: false,
+            grouping: Grouping::None,
+            levels: None,
+            lang: Lang::Zh,
+            reproducible: false,
+            level_labels: LevelLabels::new(),
+            progress: Arc::new(Silent),
+            output_template: None,
+            overwrite: Overwrite::default(),
+            strict: false,
+        }
+    }
+
+    /// 设置进度通知的接收者（默认忽略所有进度）
+    pub fn with_progress(mut self, sink: impl ProgressSink + 'static) -> Self {
+        self.progress = Arc::new(sink);
+        self
+    }
+
+    /// 设置报告文件名模板，可用 `{function}`、`{level}`、`{date}` 占位符
+    ///
+    /// 相对路径相对于输出目录；JSON 文件与报告同名，扩展名为 `.json`
+    pub fn with_output_template(mut self, template: &str) -> Self {
+        self.output_template = Some(template.to_string());
+        self
+    }
+
+    /// 设置报告文件已存在时的处理方式
+    pub fn with_overwrite(mut self, overwrite: Overwrite) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// 设置指令表格显示的列和顺序
+    pub fn with_table_config(mut self, config: TableConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// 设置默认布局是否包含地址和机器码列，便于和调试器中的反汇编对照
+    ///
+    /// 不设置时单文件分析包含、多级别对比不包含；用 [`with_table_config`](Self::with_table_config)
+    /// 指定了列时以指定的列为准
+    pub fn with_address_columns(mut self, enabled: bool) -> Self {
+        self.address_columns = Some(enabled);
+        self
+    }
+
+    /// 设置核心模型：默认布局增加延迟/吞吐列，统计信息中附加各级别的周期估计
+    pub fn with_cost_model(mut self, model: CostModel) -> Self {
+        self.cost_model = Some(model);
+        self
+    }
+
+    /// 设置是否把函数内的跳转目标显示为标签（默认开启）：目标指令前插入 `.L1:` 行，
+    /// 跳转指令和语义解释引用标签和目标处的 C 代码
+    pub fn with_branch_labels(mut self, enabled: bool) -> Self {
+        self.branch_labels = enabled;
+        self
+    }
+
+    /// 设置是否标注值追踪的结果（默认开启）：`movz`/`movk` 拼出的常量、下标寄存器等于 `X1 × 8` 等
+    pub fn with_value_tracking(mut self, enabled: bool) -> Self {
+        self.value_tracking = enabled;
+        self
+    }
+
+    /// 实际使用的表格布局
+    fn table_config(&self, single_file: bool) -> TableConfig {
+        let config = match self.config {
+            Some(ref config) => return config.clone(),
+            None if self.address_columns.unwrap_or(single_file) => TableConfig::new().with_encoding(),
+            None => TableConfig::new(),
+        };
+        match self.cost_model {
+            Some(_) => {
+                let mut columns = config.columns().to_vec();
+                columns.push(Column::Cost);
+                config.with_columns(columns)
+            }
+            None => config,
+        }
+    }
+
+    /// 设置语义解释和表头的语言
+    pub fn with_language(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 设置是否生成可复现的报告（不含路径，顺序固定），便于纳入版本控制后比较
+    pub fn with_reproducible(mut self, enabled: bool) -> Self {
+        self.reproducible = enabled;
+        self
+    }
+
+    /// 是否生成可复现的报告
+    pub(crate) fn is_reproducible(&self) -> bool {
+        self.reproducible
+    }
+
+    /// 报告中显示的输入来源：可复现模式下去掉目录部分
+    pub(crate) fn source_name<'a>(&self, source: &'a str) -> &'a str {
+        if !self.reproducible {
+            return source;
+        }
+        std::path::Path::new(source)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(source)
+    }
+
+    /// 设置自定义的级别标题（如 `O2+LTO`），用于标题、统计信息和索引
+    pub fn with_level_labels(mut self, labels: LevelLabels) -> Self {
+        self.level_labels = labels;
+        self
+    }
+
+    /// 设置对比的优化级别（如 O0, O2, O3, Os）
+    pub fn with_levels(mut self, levels: Vec<String>) -> Self {
+        self.levels = Some(levels);
+        self
+    }
+
+    /// 启用执行轨迹：从给定的初始状态在模拟器中运行函数，逐条记录寄存器、标志位和内存访问
+    pub fn with_trace(mut self, initial_state: CpuState) -> Self {
+        self.trace = Some(initial_state);
+        self
+    }
+
+    /// 设置是否展开编译器辅助函数：在同一 dump 中找到函数引用的 `.part.N`、`.cold` 等辅助函数，
+    /// 把它们的指令附加在函数之后
+    pub fn with_helper_expansion(mut self, enabled: bool) -> Self {
+        self.expand_helpers = enabled;
+        self
+    }
+
+    /// 设置是否附加被调用者保存寄存器（X19-X28）的活跃区间
+    pub fn with_liveness(mut self, enabled: bool) -> Self {
+        self.liveness = enabled;
+        self
+    }
+
+    /// 设置是否检查操作数位宽：32 位结果未经扩展就作为 64 位值使用、`sxtw`/`uxtw` 与比较的有无符号不一致
+    pub fn with_width_checks(mut self, enabled: bool) -> Self {
+        self.width_checks = enabled;
+        self
+    }
+
+    /// 设置是否检查每个调用点的 SP 按 16 字节对齐（手写汇编常见的错误）
+    pub fn with_stack_alignment(mut self, enabled: bool) -> Self {
+        self.stack_alignment = enabled;
+        self
+    }
+
+    /// 设置是否附加条件跳转方向和循环头对齐的统计
+    pub fn with_branch_stats(mut self, enabled: bool) -> Self {
+        self.branch_stats = enabled;
+        self
+    }
+
+    /// 设置对比报告是否按 C 源码行对齐，代替各级别独立的表格
+    pub fn with_source_alignment(mut self, enabled: bool) -> Self {
+        self.source_aligned = enabled;
+        self
+    }
+
+    /// 设置单文件分析时按 C 语句分组的方式
+    pub fn with_grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// 设置是否附加各出口（RET/尾调用）的返回值来源
+    pub fn with_returns(mut self, enabled: bool) -> Self {
+        self.returns = enabled;
+        self
+    }
+
+    /// 设置是否附加推断的函数签名
+    pub fn with_signature(mut self, enabled: bool) -> Self {
+        self.signature = enabled;
+        self
+    }
+
+    /// 设置是否附加安全加固检查（PAC/BTI/栈保护、间接跳转）
+    pub fn with_hardening(mut self, enabled: bool) -> Self {
+        self.hardening = enabled;
+        self
+    }
+
+    /// 设置是否检测无用存储（在语义解释中标注，并附加列表和各级别的数量）
+    pub fn with_dead_stores(mut self, enabled: bool) -> Self {
+        self.dead_stores = enabled;
+        self
+    }
+
+    /// 设置是否附加近似的类 C 伪代码
+    pub fn with_pseudocode(mut self, enabled: bool) -> Self {
+        self.pseudocode = enabled;
+        self
+    }
+
+    /// 设置是否画出函数内跳转的箭头（类似 `objdump --visualize-jumps`）：终端输出在地址左侧加一栏，
+    /// Markdown 报告附加带箭头栏的代码块
+    pub fn with_jump_arrows(mut self, enabled: bool) -> Self {
+        self.jump_arrows = enabled;
+        self
+    }
+
+    /// 设置是否在表格前生成函数导读
+    pub fn with_walkthrough(mut self, enabled: bool) -> Self {
+        self.walkthrough = enabled;
+        self
+    }
+
+    /// 设置是否检测共享代码（outlining）
+    pub fn with_outlining(mut self, enabled: bool) -> Self {
+        self.outlining = enabled;
+        self
+    }
+
+    /// 设置是否同时输出 JSON 文件
+    pub fn with_json_output(mut self, enabled: bool) -> Self {
+        self.json_output = enabled;
+        self
+    }
+
+    /// 设置是否直接在终端输出彩色对齐的指令列表，而不写入 Markdown/JSON 文件
+    pub fn with_terminal_output(mut self, enabled: bool) -> Self {
+        self.terminal = enabled;
+        self
+    }
+
+    /// 设置严格解析：有指令未能被文本解析器完整解析时分析失败，错误中列出每条指令的行、列和原因
+    pub fn with_strict_parsing(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// 设置用户备注
+    pub fn with_notes(mut self, notes: Notes) -> Self {
+        self.notes = Some(notes);
+        self
+    }
+
+    /// 提取函数的指令并按设置加工：共享代码注释、跳转表、辅助函数展开、优化备注和用户备注
+    ///
+    /// `level` 为 None 时（单文件模式）使用全部优化备注
+    pub fn function_entries(
+        &self,
+        parser: &crate::objdump::ObjdumpParser,
+        level: Option<&str>,
+        function_name: &str,
+    ) -> anyhow::Result<Vec<DumpEntry>> {
+        let mut entries = parser.extract_function_data(function_name)?;
+        self.apply_outlining(parser, function_name, &mut entries)?;
+        self.apply_jump_tables(parser, &mut entries);
+        self.apply_helpers(parser, &mut entries);
+        self.apply_remarks(level, function_name, &mut entries);
+        self.apply_notes(function_name, &mut entries);
+        Ok(entries)
+    }
+
+    /// 将用户备注合并到条目中
+    fn apply_notes(&self, function_name: &str, entries: &mut Vec<DumpEntry>) {
+        if let Some(ref notes) = self.notes {
+            notes.apply(function_name, entries);
+        }
+    }
+
+    /// 添加编译器优化备注，`level` 为 None 时用于所有级别
+    pub fn with_remarks(mut self, level: Option<&str>, remarks: Remarks) -> Self {
+        self.remarks.push((level.map(String::from), remarks));
+        self
+    }
+
+    /// 将编译器优化备注合并到条目中（单文件模式下 `level` 为 None，使用全部备注）
+    fn apply_remarks(&self, level: Option<&str>, function_name: &str, entries: &mut Vec<DumpEntry>) {
+        for (for_level, remarks) in &self.remarks {
+            if level.is_none() || for_level.is_none() || for_level.as_deref() == level {
+                remarks.apply(function_name, entries, self.lang);
+            }
+        }
+    }
+
+    /// 检测共享代码并在调用处插入注释行
+    fn apply_outlining(
+        &self,
+        parser: &crate::objdump::ObjdumpParser,
+        function_name: &str,
+        entries: &mut Vec<DumpEntry>,
+    ) -> anyhow::Result<()> {
+        if self.outlining {
+            let report = OutliningReport::detect(parser)?;
+            report.annotate(function_name, entries);
+        }
+        Ok(())
+    }
+
+    /// 识别跳转表，在 `br` 之后插入一行列出各 case 的跳转目标
+    fn apply_jump_tables(&self, parser: &crate::objdump::ObjdumpParser, entries: &mut Vec<DumpEntry>) {
+        let tables = crate::jumptable::find(entries, parser);
+        if tables.is_empty() {
+            return;
+        }
+
+        let mut merged = Vec::with_capacity(entries.len() + tables.len());
+        for entry in entries.drain(..) {
+            let table = tables.iter().find(|t| !entry.asm_instruction.is_empty() && t.address == entry.address);
+            let note = table.map(|table| table.describe(parser.symbols(), self.lang));
+            merged.push(entry);
+            if let Some(text) = note {
+                merged.push(DumpEntry {
+                    id: String::new(),
+                    c_line: None,
+                    c_code: text,
+                    address: String::new(),
+                    machine_code: String::new(),
+                    asm_instruction: String::new(),
+                    parsed_instruction: None,
+                });
+            }
+        }
+        *entries = merged;
+    }
+
+    /// 展开编译器辅助函数（启用时），每个辅助函数前插入一行提示
+    fn apply_helpers(&self, parser: &crate::objdump::ObjdumpParser, entries: &mut Vec<DumpEntry>) {
+        if !self.expand_helpers {
+            return;
+        }
+        for (name, body) in parser.extract_helpers(entries) {
+            let hint = match self.lang {
+                Lang::Zh => format!("📎 展开辅助函数 <{}>（{} 条指令）", name, Self::instruction_count(&body)),
+                Lang::En => format!("📎 Expanded helper <{}> ({} instructions)", name, Self::instruction_count(&body)),
+            };
+            entries.push(DumpEntry {
+                id: String::new(),
+                c_line: None,
+                c_code: hint,
+                address: String::new(),
+                machine_code: String::new(),
+                asm_instruction: String::new(),
+                parsed_instruction: None,
+            });
+            entries.extend(body);
         }
     }
 
     /// 生成单个优化级别的表格
+    ///
+    /// 按 [`TableConfig`] 选择列，没有 C 源码的条目（如 `objdump -d` 的输出）会省略 C 代码列
     pub fn generate_table(&self, entries: &[DumpEntry]) -> String {
+        self.render_table(entries, &self.table_config(false))
+    }
+
+    /// 按给定布局生成逐条指令的表格
+    fn render_table(&self, entries: &[DumpEntry], config: &TableConfig) -> String {
         let mut output = String::new();
+        let fused = self.fused_semantics(entries);
+        let with_source = entries
+            .iter()
+            .any(|e| !e.asm_instruction.is_empty() && e.c_line.is_some());
+        let columns: Vec<Column> = config
+            .columns()
+            .iter()
+            .copied()
+            .filter(|c| with_source || *c != Column::CCode)
+            .collect();
+        let split_comment = columns.contains(&Column::Comment);
+        let dead: Vec<String> = if self.dead_stores {
+            crate::deadstore::detect(entries).into_iter().map(|d| d.address).collect()
+        } else {
+            Vec::new()
+        };
+        let labels = if self.branch_labels { Self::branch_labels(entries) } else { BTreeMap::new() };
         
         // 表头
-        output.push_str("| C代码 | 汇编指令 | 语义解释 |\n");
-        output.push_str("|-------|----------|----------|\n");
+        let headers: Vec<&str> = columns.iter().map(|c| c.header(self.lang)).collect();
+        let separators: Vec<&str> = columns.iter().map(|c| c.separator()).collect();
+        output.push_str(&format!("| {} |\n", headers.join(" | ")));
+        output.push_str(&format!("|{}|\n", separators.join("|")));
         
         // 按 C 代码分组
         let mut current_c_code = String::new();
         
         for entry in entries {
-            // 如果汇编指令为空，说明这是一条提示信息（不截断）
+            // 如果汇编指令为空，说明这是一条提示信息（不截断），放在第一列
             if entry.asm_instruction.is_empty() {
-                output.push_str(&format!(
-                    "| {} | | |\n",
-                    &entry.c_code  // 提示信息不截断
-                ));
+                let hint = &entry.c_code;
+                output.push_str(&format!("| {} |{}\n", hint, " |".repeat(columns.len().saturating_sub(1))));
                 continue;
             }
-            
-            let c_code = if entry.c_code.is_empty() || entry.c_code == current_c_code {
-                String::from("") // 相同的 C 代码不重复显示
-            } else {
-                current_c_code = entry.c_code.clone();
-                self.format_c_code(&entry.c_code)
-            };
-            
-            let asm_inst = &entry.asm_instruction;
-            
-            // 获取语义解释
-            let semantic = if let Some(ref parsed) = entry.parsed_instruction {
-                SemanticInterpreter::interpret(parsed)
-            } else {
-                // 如果无法解析，尝试提供基本解释
-                Self::basic_interpret(asm_inst)
+
+            let address = entry.parsed_instruction.as_ref().map(|inst| inst.address);
+            if let Some(label) = address.and_then(|a| labels.get(&a)) {
+                // 标签放在汇编指令列（没有时放在第一列）
+                let position = columns.iter().position(|c| *c == Column::Assembly).unwrap_or(0);
+                let cells: Vec<String> =
+                    (0..columns.len()).map(|i| if i == position { format!("{}:", label) } else { String::new() }).collect();
+                output.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+
+            let (asm_inst, comment) = match entry.asm_instruction.split_once("//") {
+                Some((asm, comment)) if split_comment => (asm.trim_end(), comment.trim()),
+                _ => (entry.asm_instruction.as_str(), ""),
             };
-            
-            output.push_str(&format!(
-                "| {} | {} | {} |\n",
-                c_code, asm_inst, semantic
-            ));
+            let branch = self.label_branch(entry, asm_inst, &labels, entries, &fused);
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|column| match column {
+                    Column::Address => format!("0x{}", entry.address),
+                    Column::MachineCode => entry.machine_code.clone(),
+                    // 相同的 C 代码不重复显示
+                    Column::CCode if entry.c_code.is_empty() || entry.c_code == current_c_code => String::new(),
+                    Column::CCode => self.format_c_code(&entry.c_code),
+                    Column::Assembly => branch.as_ref().map_or_else(|| asm_inst.to_string(), |(asm, _)| asm.clone()),
+                    // 获取语义解释（无法解析时使用基本解释）
+                    Column::Semantics if dead.contains(&entry.address) => format!(
+                        "{} {}",
+                        self.semantic_of(entry, &fused),
+                        self.lang.pick("（⚠ 无用存储）", "(⚠ dead store)")
+                    ),
+                    Column::Semantics => branch.as_ref().map_or_else(|| self.semantic_of(entry, &fused), |(_, s)| s.clone()),
+                    Column::Comment => comment.to_string(),
+                    Column::Cost => self
+                        .cost_model
+                        .as_ref()
+                        .and_then(|model| model.entry_cost(entry))
+                        .map_or_else(String::new, |cost| crate::cost::format_cost(&cost)),
+                })
+                .collect();
+            if !entry.c_code.is_empty() {
+                current_c_code = entry.c_code.clone();
+            }
+            output.push_str(&format!("| {} |\n", cells.join(" | ")));
         }
         
         output
     }
     
+    /// 函数内跳转目标的标签：目标地址 -> `.L1`、`.L2`……（按地址编号）
+    fn branch_labels(entries: &[DumpEntry]) -> BTreeMap<u64, String> {
+        let instructions: Vec<&Instruction> = entries.iter().filter_map(|e| e.parsed_instruction.as_ref()).collect();
+        let targets: BTreeSet<u64> = instructions
+            .iter()
+            .filter(|inst| crate::analysis::is_branch(inst))
+            .filter_map(|inst| crate::analysis::branch_target(inst))
+            .filter(|target| instructions.iter().any(|inst| inst.address == *target))
+            .collect();
+        targets.into_iter().enumerate().map(|(n, target)| (target, format!(".L{}", n + 1))).collect()
+    }
+
+    /// 跳转到标签的指令改写后的汇编指令和语义解释（目标不是标签时返回 None）
+    fn label_branch(
+        &self,
+        entry: &DumpEntry,
+        asm_inst: &str,
+        labels: &BTreeMap<u64, String>,
+        entries: &[DumpEntry],
+        fused: &HashMap<&str, String>,
+    ) -> Option<(String, String)> {
+        use crate::instruction::Operand;
+
+        let inst = entry.parsed_instruction.as_ref().filter(|inst| crate::analysis::is_branch(inst))?;
+        let target = crate::analysis::branch_target(inst)?;
+        let label = labels.get(&target)?;
+        let operand = inst.operands.iter().rev().find_map(|op| match op {
+            Operand::Label(text) => Some(text.as_str()),
+            _ => None,
+        })?;
+
+        let asm = asm_inst.replacen(operand, label, 1);
+        let mut semantic = self.semantic_of(entry, fused);
+        match crate::objdump::target_symbol(operand).filter(|symbol| semantic.contains(symbol)) {
+            Some(symbol) => semantic = semantic.replacen(symbol, label, 1),
+            None => semantic.push_str(&format!(" → {}", label)),
+        }
+        let code = entries
+            .iter()
+            .find(|e| e.parsed_instruction.as_ref().is_some_and(|i| i.address == target))
+            .map(|e| e.c_code.as_str())
+            .unwrap_or("");
+        if !code.is_empty() {
+            semantic.push_str(&match self.lang {
+                Lang::Zh => format!("（{}）", self.format_c_code(code)),
+                Lang::En => format!(" ({})", self.format_c_code(code)),
+            });
+        }
+        Some((asm, semantic))
+    }
+
+    /// 生成终端输出：地址（暗色）、助记符（青色）、操作数和语义解释（绿色）按列对齐，
+    /// C 代码变化时另起一行（黄色）
+    pub fn generate_terminal(&self, title: &str, entries: &[DumpEntry]) -> String {
+        use colored::Colorize;
+
+        let split = |e: &DumpEntry| -> (String, String) {
+            let asm = e.asm_instruction.split("//").next().unwrap_or("").trim().replace('\t', " ");
+            match asm.split_once(' ') {
+                Some((mnemonic, operands)) => (mnemonic.to_string(), operands.trim().to_string()),
+                None => (asm, String::new()),
+            }
+        };
+        let instructions: Vec<&DumpEntry> = entries.iter().filter(|e| !e.asm_instruction.is_empty()).collect();
+        let address_width = instructions.iter().map(|e| e.address.len()).max().unwrap_or(0);
+        let mnemonic_width = instructions.iter().map(|e| split(e).0.len()).max().unwrap_or(0);
+        let operand_width = instructions.iter().map(|e| split(e).1.chars().count()).max().unwrap_or(0);
+        let fused = self.fused_semantics(entries);
+        let arrows = JumpArrows::new(if self.jump_arrows { entries } else { &[] });
+
+        let mut output = format!("{}\n", format!("── {} ──", title).bold());
+        if let Some(text) = self.generate_walkthrough(entries) {
+            output.push_str(&format!("{}\n", text.italic()));
+        }
+        let mut current_c_code = "";
+        for (row, entry) in entries.iter().enumerate() {
+            let gutter = if arrows.is_empty() { String::new() } else { format!("{} ", arrows.between(row)).magenta().to_string() };
+            if entry.asm_instruction.is_empty() {
+                output.push_str(&format!("{}  {}\n", gutter, entry.c_code.dimmed()));
+                continue;
+            }
+            if !entry.c_code.is_empty() && entry.c_code != current_c_code {
+                current_c_code = &entry.c_code;
+                output.push_str(&format!("{}  {}\n", gutter, format!("// {}", self.format_c_code(&entry.c_code)).yellow()));
+            }
+            let (mnemonic, operands) = split(entry);
+            let gutter = if arrows.is_empty() { String::new() } else { format!("{} ", arrows.row(row)).magenta().to_string() };
+            output.push_str(&format!(
+                "{}  {}  {}  {:<width$}  {}\n",
+                gutter,
+                format!("{:>width$}", entry.address, width = address_width).dimmed(),
+                format!("{:<width$}", mnemonic, width = mnemonic_width).cyan(),
+                operands,
+                self.semantic_of(entry, &fused).green(),
+                width = operand_width
+            ));
+        }
+        output
+    }
+
+    /// 选取第 start 到第 end 条指令（从 1 开始，含两端），紧跟在这些指令后的提示/备注行一并保留
+    pub fn select_instructions(entries: &[DumpEntry], start: usize, end: usize) -> Vec<DumpEntry> {
+        let mut index = 0;
+        entries
+            .iter()
+            .filter(|e| {
+                if !e.asm_instruction.is_empty() {
+                    index += 1;
+                }
+                (start..=end).contains(&index)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// 生成纯文本片段：每行一条指令，语义解释作为行尾注释，C 代码变化时另起一行注释
+    pub fn generate_plain_snippet(&self, entries: &[DumpEntry]) -> String {
+        // 去掉 objdump 自带的 `// #0` 注释，制表符换成空格便于对齐
+        let asm_of = Self::plain_asm;
+        let width = entries.iter().map(|e| asm_of(e).chars().count()).max().unwrap_or(0);
+        let fused = self.fused_semantics(entries);
+        let mut output = String::new();
+        let mut current_c_code = "";
+
+        for entry in entries {
+            if entry.asm_instruction.is_empty() {
+                output.push_str(&format!("// {}\n", entry.c_code));
+                continue;
+            }
+            if !entry.c_code.is_empty() && entry.c_code != current_c_code {
+                current_c_code = &entry.c_code;
+                output.push_str(&format!("// {}\n", self.format_c_code(&entry.c_code)));
+            }
+            output.push_str(&format!(
+                "    {:<width$}  // {}\n",
+                asm_of(entry),
+                self.semantic_of(entry, &fused),
+                width = width
+            ));
+        }
+        output
+    }
+
+    /// 按 C 语句分组生成表格
+    ///
+    /// 连续对应同一条 C 语句的指令归为一组，每组输出一行指令数和按类别统计的概要；
+    /// `Grouping::Expanded` 时概要行之后再列出详细指令。没有 C 源码时退回逐条表格，
+    /// 逐条表格默认包含地址和机器码列。
+    pub fn generate_grouped_table(&self, entries: &[DumpEntry]) -> String {
+        let with_source = entries
+            .iter()
+            .any(|e| !e.asm_instruction.is_empty() && e.c_line.is_some());
+        if self.grouping == Grouping::None || !with_source {
+            return self.render_table(entries, &self.table_config(true));
+        }
+        let expanded = self.grouping == Grouping::Expanded;
+        let fused = self.fused_semantics(entries);
+
+        let mut output = String::new();
+        if expanded {
+            output.push_str(self.lang.pick("| C代码 | 汇编指令 | 语义解释 |\n", "| C code | Assembly | Semantics |\n"));
+            output.push_str("|-------|----------|----------|\n");
+        } else {
+            output.push_str(self.lang.pick("| C代码 | 指令数 | 概要 |\n", "| C code | Instructions | Summary |\n"));
+            output.push_str("|-------|--------|------|\n");
+        }
+
+        // 分组：遇到不同的非空 C 代码时开始新的一组，提示行归入当前组
+        let mut groups: Vec<(&str, Vec<&DumpEntry>)> = Vec::new();
+        for entry in entries {
+            let starts_group = !entry.asm_instruction.is_empty()
+                && !entry.c_code.is_empty()
+                && groups.last().is_none_or(|(code, _)| *code != entry.c_code);
+            match groups.last_mut() {
+                Some((_, members)) if !starts_group => members.push(entry),
+                _ => groups.push((entry.c_code.as_str(), vec![entry])),
+            }
+        }
+
+        for (c_code, members) in groups {
+            let instructions: Vec<&DumpEntry> = members
+                .iter()
+                .copied()
+                .filter(|e| !e.asm_instruction.is_empty())
+                .collect();
+            if !instructions.is_empty() {
+                let c_code = self.format_c_code(c_code);
+                let count = instructions.len();
+                let summary = self.statement_summary(&instructions);
+                if expanded {
+                    let count = match self.lang {
+                        Lang::Zh => format!("{} 条指令", count),
+                        Lang::En => format!("{} instructions", count),
+                    };
+                    output.push_str(&format!("| **{}** | *{}* | *{}* |\n", c_code, count, summary));
+                } else {
+                    output.push_str(&format!("| {} | {} | {} |\n", c_code, count, summary));
+                }
+            }
+
+            for entry in members {
+                if entry.asm_instruction.is_empty() {
+                    output.push_str(&format!("| {} | | |\n", entry.c_code));
+                } else if expanded {
+                    output.push_str(&format!(
+                        "| | {} | {} |\n",
+                        entry.asm_instruction,
+                        self.semantic_of(entry, &fused)
+                    ));
+                }
+            }
+        }
+
+        output
+    }
+
+    /// 一组指令的概要：按类别统计条数（如 `加载 ×2、运算 ×1、存储 ×1`）
+    fn statement_summary(&self, entries: &[&DumpEntry]) -> String {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for entry in entries {
+            let category = crate::metrics::Category::of(&entry.asm_instruction).name_in(self.lang);
+            match counts.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((category, 1)),
+            }
+        }
+        counts
+            .iter()
+            .map(|(category, n)| format!("{} ×{}", category, n))
+            .collect::<Vec<_>>()
+            .join(self.lang.pick("、", ", "))
+    }
+
+    /// 生成 JSON 格式的分析结果（每条指令带稳定标识符）
+    pub fn generate_json(&self, function_name: &str, levels: &[(&str, &[DumpEntry])]) -> String {
+        let report = JsonReport {
+            function: function_name,
+            levels: levels
+                .iter()
+                .map(|(level, entries)| {
+                    let fused = self.fused_semantics(entries);
+                    JsonLevel {
+                        level,
+                        instructions: entries
+                            .iter()
+                            .filter(|e| !e.asm_instruction.is_empty())
+                            .map(|e| JsonEntry {
+                                id: &e.id,
+                                address: &e.address,
+                                machine_code: &e.machine_code,
+                                asm_instruction: &e.asm_instruction,
+                                c_code: &e.c_code,
+                                semantic: self.semantic_of(e, &fused),
+                            })
+                            .collect(),
+                    }
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+
+    /// 需要前后文的解释（按指令地址）：ADRP 与随后补全地址的 ADD/LDR/STR 合并，序言/尾声中的后续指令，
+    /// 条件跳转/条件选择所依据的比较，`movz`/`movk` 拼接常量，以及值追踪推导出的值
+    fn fused_semantics<'a>(&self, entries: &'a [DumpEntry]) -> HashMap<&'a str, String> {
+        let interpreter = SemanticInterpreter::with_language(self.lang);
+        let mut tracker = PageTracker::new();
+        let mut flags = FlagTracker::new();
+        let mut values = ValueTracker::new();
+        let mut previous: Option<&Instruction> = None;
+        // 函数内的跳转目标是汇合点，标志位可能来自不同的路径
+        let targets: HashSet<u64> = entries
+            .iter()
+            .filter_map(|e| e.parsed_instruction.as_ref())
+            .filter(|inst| crate::analysis::is_branch(inst))
+            .filter_map(crate::analysis::branch_target)
+            .collect();
+        let rows: Vec<&DumpEntry> = entries.iter().filter(|e| !e.asm_instruction.is_empty()).collect();
+        let mut chains = Self::constant_chains(&interpreter, &rows);
+        rows.iter()
+            .filter_map(|e| {
+                let Some(inst) = e.parsed_instruction.as_ref() else {
+                    let mnemonic = e.asm_instruction.split_whitespace().next().unwrap_or_default();
+                    if crate::explain::sets_flags(mnemonic) {
+                        flags.reset();
+                    }
+                    values.reset();
+                    return None;
+                };
+                if targets.contains(&inst.address) {
+                    flags.reset();
+                    values.reset();
+                }
+                let frame = previous.and_then(|prev| interpreter.describe_frame_step(prev, inst));
+                previous = Some(inst);
+                let fused = tracker.fuse(&interpreter, inst);
+                let compared = flags.fuse(&interpreter, inst);
+                let derived = values.step(inst).filter(|_| self.value_tracking);
+                // 拼接链已经给出常量，不再重复标注
+                if let Some(text) = chains.remove(e.address.as_str()) {
+                    return Some((e.address.as_str(), text));
+                }
+                let text = frame.or(fused).or(compared);
+                match derived {
+                    Some(derived) => {
+                        let text = text.unwrap_or_else(|| interpreter.describe(inst));
+                        Some((e.address.as_str(), text + &derived.note(self.lang)))
+                    }
+                    None => text.map(|text| (e.address.as_str(), text)),
+                }
+            })
+            .collect()
+    }
+
+    /// 相邻指令中的 `movz`/`movk` 拼接链：按地址给出合并后的解释
+    fn constant_chains<'a>(interpreter: &SemanticInterpreter, rows: &[&'a DumpEntry]) -> HashMap<&'a str, String> {
+        let mut chains = HashMap::new();
+        for run in rows.split(|e| e.parsed_instruction.is_none()) {
+            let instructions: Vec<&Instruction> = run.iter().filter_map(|e| e.parsed_instruction.as_ref()).collect();
+            let mut i = 0;
+            while i < run.len() {
+                match interpreter.describe_constant_chain(&instructions[i..]) {
+                    Some(texts) => {
+                        let length = texts.len();
+                        chains.extend(run[i..].iter().map(|e| e.address.as_str()).zip(texts));
+                        i += length;
+                    }
+                    None => i += 1,
+                }
+            }
+        }
+        chains
+    }
+
+    /// 每个条目的语义解释（与 entries 一一对应，提示行为空），供终端界面等其他前端使用
+    pub fn semantic_column(&self, entries: &[DumpEntry]) -> Vec<String> {
+        let fused = self.fused_semantics(entries);
+        entries
+            .iter()
+            .map(|e| if e.asm_instruction.is_empty() { String::new() } else { self.semantic_of(e, &fused) })
+            .collect()
+    }
+
+    /// 获取条目的语义解释
+    fn semantic_of(&self, entry: &DumpEntry, fused: &HashMap<&str, String>) -> String {
+        if let Some(text) = fused.get(entry.address.as_str()) {
+            return text.clone();
+        }
+        match entry.parsed_instruction {
+            Some(ref parsed) => SemanticInterpreter::with_language(self.lang).describe(parsed),
+            None => crate::arm32::describe(&entry.asm_instruction, self.lang)
+                .unwrap_or_else(|| Self::basic_interpret(&entry.asm_instruction, self.lang)),
+        }
+    }
+
     /// 为无法解析的指令提供基本解释
-    fn basic_interpret(asm_inst: &str) -> String {
+    fn basic_interpret(asm_inst: &str, lang: Lang) -> String {
         let inst_lower = asm_inst.to_lowercase();
         
         // 尝试提取基本的操作数信息
         if inst_lower.starts_with("ldp") {
-            Self::interpret_ldp_basic(asm_inst)
+            Self::interpret_ldp_basic(asm_inst, lang)
         } else if inst_lower.starts_with("stp") {
-            Self::interpret_stp_basic(asm_inst)
+            Self::interpret_stp_basic(asm_inst, lang)
         } else if inst_lower.starts_with("ldr") {
-            Self::interpret_ldr_basic(asm_inst)
+            Self::interpret_ldr_basic(asm_inst, lang)
         } else if inst_lower.starts_with("str") {
-            Self::interpret_str_basic(asm_inst)
+            Self::interpret_str_basic(asm_inst, lang)
         } else if inst_lower.starts_with("bl ") {
-            String::from("调用函数")
+            lang.pick("调用函数", "call function").to_string()
         } else if inst_lower.starts_with("b.") {
-            String::from("条件跳转")
+            lang.pick("条件跳转", "conditional branch").to_string()
         } else if inst_lower.starts_with("b ") {
-            String::from("无条件跳转")
+            lang.pick("无条件跳转", "unconditional branch").to_string()
         } else if inst_lower.starts_with("ccmp") {
-            String::from("条件比较")
+            lang.pick("条件比较", "conditional compare").to_string()
         } else if inst_lower.starts_with("mov") {
-            Self::interpret_mov_basic(asm_inst)
+            Self::interpret_mov_basic(asm_inst, lang)
         } else if inst_lower.starts_with("add") {
-            String::from("加法运算")
+            lang.pick("加法运算", "addition").to_string()
         } else if inst_lower.starts_with("sub") {
-            String::from("减法运算")
+            lang.pick("减法运算", "subtraction").to_string()
         } else if inst_lower.starts_with("cmp") {
-            String::from("比较运算")
+            lang.pick("比较运算", "comparison").to_string()
         } else if inst_lower.starts_with("ret") {
-            String::from("函数返回")
+            lang.pick("函数返回", "return from function").to_string()
         } else if inst_lower.starts_with("nop") {
-            String::from("空操作")
+            lang.pick("空操作", "no operation").to_string()
         } else {
-            String::from("指令")
+            lang.pick("指令", "instruction").to_string()
         }
     }
     
-    fn interpret_ldr_basic(asm: &str) -> String {
+    fn interpret_ldr_basic(asm: &str, lang: Lang) -> String {
         // 尝试提取目标寄存器
         if let Some(parts) = asm.split_whitespace().nth(1) {
             if let Some(reg) = parts.split(',').next() {
-                return format!("从内存加载到 {}", reg.trim());
+                return match lang {
+                    Lang::Zh => format!("从内存加载到 {}", reg.trim()),
+                    Lang::En => format!("load {} from memory", reg.trim()),
+                };
             }
         }
-        String::from("从内存加载")
+        lang.pick("从内存加载", "load from memory").to_string()
     }
     
-    fn interpret_str_basic(asm: &str) -> String {
+    fn interpret_str_basic(asm: &str, lang: Lang) -> String {
         // 尝试提取源寄存器
         if let Some(parts) = asm.split_whitespace().nth(1) {
             if let Some(reg) = parts.split(',').next() {
-                return format!("将 {} 存储到内存", reg.trim());
+                return match lang {
+                    Lang::Zh => format!("将 {} 存储到内存", reg.trim()),
+                    Lang::En => format!("store {} to memory", reg.trim()),
+                };
             }
         }
-        String::from("存储到内存")
+        lang.pick("存储到内存", "store to memory").to_string()
     }
     
-    fn interpret_ldp_basic(asm: &str) -> String {
+    fn interpret_ldp_basic(asm: &str, lang: Lang) -> String {
         // 提取两个目标寄存器
         if let Some(operands) = asm.split_whitespace().nth(1) {
             let regs: Vec<&str> = operands.split(',').take(2).collect();
             if regs.len() == 2 {
-                return format!("从内存加载 {} 和 {}", regs[0].trim(), regs[1].trim());
+                return match lang {
+                    Lang::Zh => format!("从内存加载 {} 和 {}", regs[0].trim(), regs[1].trim()),
+                    Lang::En => format!("load {} and {} from memory", regs[0].trim(), regs[1].trim()),
+                };
             }
         }
-        String::from("从内存加载一对寄存器")
+        lang.pick("从内存加载一对寄存器", "load a register pair from memory").to_string()
     }
     
-    fn interpret_stp_basic(asm: &str) -> String {
+    fn interpret_stp_basic(asm: &str, lang: Lang) -> String {
         // 提取两个源寄存器
         if let Some(operands) = asm.split_whitespace().nth(1) {
             let regs: Vec<&str> = operands.split(',').take(2).collect();
             if regs.len() == 2 {
-                return format!("将 {} 和 {} 存储到内存", regs[0].trim(), regs[1].trim());
+                return match lang {
+                    Lang::Zh => format!("将 {} 和 {} 存储到内存", regs[0].trim(), regs[1].trim()),
+                    Lang::En => format!("store {} and {} to memory", regs[0].trim(), regs[1].trim()),
+                };
+            }
+        }
+        lang.pick("存储一对寄存器到内存", "store a register pair to memory").to_string()
+    }
+    
+    fn interpret_mov_basic(asm: &str, lang: Lang) -> String {
+        if let Some(operands) = asm.split_whitespace().nth(1) {
+            let parts: Vec<&str> = operands.split(',').take(2).collect();
+            if parts.len() == 2 {
+                return format!("{} = {}", parts[0].trim(), parts[1].trim());
+            }
+        }
+        lang.pick("数据移动", "move data").to_string()
+    }
+
+    /// 生成函数导读（未启用时返回 None）
+    pub fn generate_walkthrough(&self, entries: &[DumpEntry]) -> Option<String> {
+        if !self.walkthrough {
+            return None;
+        }
+        crate::walkthrough::generate(entries, self.lang)
+    }
+
+    /// 生成推断的函数签名（未启用时返回 None）
+    ///
+    /// 返回值直接来自同一文件中的其他函数时，用该函数的返回类型修正推测
+    pub fn generate_signature(
+        &self,
+        parser: &crate::objdump::ObjdumpParser,
+        function_name: &str,
+        entries: &[DumpEntry],
+    ) -> Option<String> {
+        use crate::signature::Signature;
+
+        if !self.signature {
+            return None;
+        }
+        let mut signature = Signature::infer(function_name, entries, self.lang)?;
+        if let Some(callee) = signature.return_callee.clone() {
+            let callee = parser
+                .extract_function_data(&callee)
+                .ok()
+                .and_then(|entries| Signature::infer(&callee, &entries, self.lang));
+            if let Some(callee) = callee {
+                signature.resolve_return(&callee);
+            }
+        }
+        Some(signature.to_markdown())
+    }
+
+    /// 生成近似的类 C 伪代码（未启用或没有可解析的指令时返回 None）
+    pub fn generate_pseudocode(&self, function_name: &str, entries: &[DumpEntry]) -> Option<String> {
+        if !self.pseudocode {
+            return None;
+        }
+        let text = crate::pseudocode::generate(function_name, entries, self.lang)?;
+        Some(format!("```c\n{}```\n", text))
+    }
+
+    /// 生成带跳转箭头的指令清单代码块（未启用或没有函数内跳转时返回 None）
+    pub fn generate_jump_arrows(&self, entries: &[DumpEntry]) -> Option<String> {
+        if !self.jump_arrows {
+            return None;
+        }
+        let arrows = JumpArrows::new(entries);
+        if arrows.is_empty() {
+            return None;
+        }
+        let address_width = entries.iter().map(|e| e.address.len()).max().unwrap_or(0);
+        let mut output = String::from("```text\n");
+        for (row, entry) in entries.iter().enumerate().filter(|(_, e)| !e.asm_instruction.is_empty()) {
+            let line = format!("{} {:>width$}:  {}", arrows.row(row), entry.address, Self::plain_asm(entry), width = address_width);
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        output.push_str("```\n");
+        Some(output)
+    }
+
+    /// 生成安全加固检查表（未启用时返回 None）
+    pub fn generate_hardening_table(&self, entries: &[DumpEntry]) -> Option<String> {
+        if !self.hardening {
+            return None;
+        }
+        Some(crate::hardening::HardeningReport::from_entries(entries).to_markdown(self.lang))
+    }
+
+    /// 生成无用存储列表（未启用时返回 None）
+    pub fn generate_dead_store_table(&self, entries: &[DumpEntry]) -> Option<String> {
+        use crate::deadstore::DeadTarget;
+
+        if !self.dead_stores {
+            return None;
+        }
+        let dead = crate::deadstore::detect(entries);
+        if dead.is_empty() {
+            return Some(self.lang.pick("未发现无用存储\n", "No dead stores found\n").to_string());
+        }
+
+        let mut output = String::new();
+        output.push_str(self.lang.pick("| 地址 | 指令 | 未被读取的目标 |\n", "| Address | Instruction | Never read |\n"));
+        output.push_str("|------|------|----------------|\n");
+        for store in &dead {
+            let target = match (&store.target, self.lang) {
+                (DeadTarget::Register(reg), Lang::Zh) => format!("寄存器 {}", reg),
+                (DeadTarget::Register(reg), Lang::En) => format!("register {}", reg),
+                (DeadTarget::Stack { offset, size }, Lang::Zh) => format!("栈槽 入口 SP{} ({} 字节)", offset, size),
+                (DeadTarget::Stack { offset, size }, Lang::En) => format!("stack slot entry SP{} ({} bytes)", offset, size),
+            };
+            output.push_str(&format!("| 0x{} | {} | {} |\n", store.address, store.asm_instruction, target));
+        }
+        output.push_str(&match self.lang {
+            Lang::Zh => format!("\n共 {} 处无用存储\n", dead.len()),
+            Lang::En => format!("\n{} dead stores in total\n", dead.len()),
+        });
+        Some(output)
+    }
+
+    /// 生成位宽警告列表（未启用时返回 None）
+    pub fn generate_width_table(&self, entries: &[DumpEntry]) -> Option<String> {
+        if !self.width_checks {
+            return None;
+        }
+        let warnings = crate::width::check(entries);
+        if warnings.is_empty() {
+            return Some(self.lang.pick("未发现位宽问题\n", "No width issues found\n").to_string());
+        }
+
+        let mut output = String::new();
+        output.push_str(self.lang.pick("| 地址 | 指令 | 问题 |\n", "| Address | Instruction | Issue |\n"));
+        output.push_str("|------|------|------|\n");
+        for warning in &warnings {
+            output.push_str(&format!(
+                "| 0x{} | {} | {} |\n",
+                warning.address,
+                warning.asm_instruction,
+                warning.issue.describe(self.lang)
+            ));
+        }
+        Some(output)
+    }
+
+    /// 生成栈对齐检查结果（未启用时返回 None）
+    pub fn generate_stack_alignment_table(&self, entries: &[DumpEntry]) -> Option<String> {
+        use crate::stackalign::{StackAlignment, STACK_ALIGNMENT};
+
+        if !self.stack_alignment {
+            return None;
+        }
+        let text = match (crate::stackalign::check(entries), self.lang) {
+            (StackAlignment::Aligned { calls: 0 }, lang) => {
+                lang.pick("函数中没有调用，无需检查\n", "No calls to check\n").to_string()
+            }
+            (StackAlignment::Aligned { calls }, Lang::Zh) => {
+                format!("{} 处调用的 SP 均按 {} 字节对齐\n", calls, STACK_ALIGNMENT)
+            }
+            (StackAlignment::Aligned { calls }, Lang::En) => {
+                format!("SP is {}-byte aligned at all {} call sites\n", STACK_ALIGNMENT, calls)
+            }
+            (StackAlignment::Unknown, lang) => lang
+                .pick(
+                    "SP 被无法推算的方式修改，或不同路径上的 SP 不一致，未检查栈对齐\n",
+                    "SP is modified in a way that cannot be tracked or differs between paths; alignment not checked\n",
+                )
+                .to_string(),
+            (StackAlignment::Misaligned { calls }, lang) => {
+                let mut output = String::new();
+                output.push_str(lang.pick("| 地址 | 指令 | 调用时的 SP |\n", "| Address | Instruction | SP at call |\n"));
+                output.push_str("|------|------|-------------|\n");
+                for call in &calls {
+                    let sp = match lang {
+                        Lang::Zh => format!("入口 SP{}，未按 {} 字节对齐", call.sp_offset, STACK_ALIGNMENT),
+                        Lang::En => format!("entry SP{}, not {}-byte aligned", call.sp_offset, STACK_ALIGNMENT),
+                    };
+                    output.push_str(&format!("| 0x{} | {} | ⚠ {} |\n", call.address, call.asm_instruction, sp));
+                }
+                output
+            }
+        };
+        Some(text)
+    }
+
+    /// 生成各出口的返回值来源表格（未启用时返回 None）
+    pub fn generate_returns_table(&self, entries: &[DumpEntry]) -> Option<String> {
+        if !self.returns {
+            return None;
+        }
+        let instructions: Vec<_> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+        let exits = crate::analysis::exit_paths(&instructions);
+
+        let mut output = String::new();
+        output.push_str(self.lang.pick("| 出口 | 地址 | 返回值来源 |\n", "| Exit | Address | Return value source |\n"));
+        output.push_str("|------|------|------------|\n");
+        for (n, exit) in exits.iter().enumerate() {
+            let source = match exit.tail_call {
+                Some(ref callee) => match self.lang {
+                    Lang::Zh => format!("尾调用，由 {} 提供", callee),
+                    Lang::En => format!("tail call, provided by {}", callee),
+                },
+                None => exit
+                    .sources
+                    .iter()
+                    .map(|(def, source)| match def {
+                        Some(i) => format!("{} @ 0x{:x}", source, instructions[*i].address),
+                        None => source.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("<br>"),
+            };
+            output.push_str(&format!(
+                "| {} | 0x{:x} | {} |\n",
+                n + 1,
+                instructions[exit.index].address,
+                source
+            ));
+        }
+        Some(output)
+    }
+
+    /// 生成被调用者保存寄存器的活跃区间表格（未启用或函数未使用这些寄存器时返回 None）
+    pub fn generate_liveness_table(&self, entries: &[DumpEntry]) -> Option<String> {
+        use crate::analysis::{LivenessAnalysis, FIRST_CALLEE_SAVED, LAST_CALLEE_SAVED};
+        use crate::instruction::InstructionType;
+
+        if !self.liveness {
+            return None;
+        }
+        let instructions: Vec<_> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+        let liveness = LivenessAnalysis::compute(&instructions);
+        let defined = liveness.defined();
+
+        let mut output = String::new();
+        output.push_str(self.lang.pick("| 寄存器 | 活跃区间 | 保存的值 |\n", "| Register | Live range | Value held |\n"));
+        output.push_str("|--------|----------|----------|\n");
+        for reg in (FIRST_CALLEE_SAVED..=LAST_CALLEE_SAVED).filter(|r| defined.contains(*r)) {
+            for (start, end) in liveness.live_ranges(reg) {
+                let first = &instructions[start];
+                // 从入口活跃或由 LDP/LDR 恢复后一直活跃到出口的区间保存的是调用者的值
+                let restored = liveness.defs[start].contains(reg)
+                    && matches!(first.instruction_type, InstructionType::LDP | InstructionType::LDR)
+                    && liveness.live_out[end].is_empty();
+                let value = if (start == 0 && liveness.live_in[0].contains(reg)) || restored {
+                    self.lang.pick("调用者的值", "caller's value")
+                } else {
+                    self.lang.pick("函数内部的值", "function's own value")
+                };
+                output.push_str(&format!(
+                    "| x{} | 0x{:x} - 0x{:x} | {} |\n",
+                    reg, first.address, instructions[end].address, value
+                ));
+            }
+        }
+
+        if defined.iter().any(|r| (FIRST_CALLEE_SAVED..=LAST_CALLEE_SAVED).contains(&r)) {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
+    /// 生成执行轨迹表格（未启用执行轨迹时返回 None）
+    pub fn generate_trace_table(&self, entries: &[DumpEntry]) -> Option<String> {
+        let mut emulator = Emulator::with_state(self.trace.clone()?);
+        emulator.state.pc = crate::emulator::entry_address(entries)?;
+
+        let trace = emulator.trace_function(entries, TRACE_MAX_STEPS);
+
+        let mut output = String::new();
+        output.push_str(self.lang.pick(
+            "| 步骤 | 地址 | 汇编指令 | 寄存器变化 | NZCV |\n",
+            "| Step | Address | Assembly | Register changes | NZCV |\n",
+        ));
+        output.push_str("|------|------|----------|------------|------|\n");
+
+        for (i, step) in trace.steps.iter().enumerate() {
+            let mut changes: Vec<String> = step
+                .changes
+                .iter()
+                .filter(|c| c.name != "NZCV")
+                .map(|c| format!("{}: 0x{:x} → 0x{:x}", c.name, c.old, c.new))
+                .collect();
+            for access in &step.accesses {
+                let arrow = if access.write { "←" } else { "→" };
+                changes.push(format!("[0x{:x}] {} 0x{:x}", access.address, arrow, access.value));
             }
-        }
-        String::from("存储一对寄存器到内存")
-    }
-    
-    fn interpret_mov_basic(asm: &str) -> String {
-        if let Some(operands) = asm.split_whitespace().nth(1) {
-            let parts: Vec<&str> = operands.split(',').take(2).collect();
-            if parts.len() == 2 {
-                return format!("{} = {}", parts[0].trim(), parts[1].trim());
+            if step.skipped_call {
+                changes.push(self.lang.pick("(跳过外部调用)", "(external call skipped)").to_string());
             }
+            if let Some(ref event) = step.syscall {
+                changes.push(format!("{} {}", self.lang.pick("系统调用", "syscall"), event));
+            }
+            output.push_str(&format!(
+                "| {} | {:x} | {} | {} | {:04b} |\n",
+                i + 1,
+                step.address,
+                step.asm_instruction,
+                changes.join(" <br> "),
+                step.nzcv
+            ));
         }
-        String::from("数据移动")
+
+        output.push_str(&format!("\n> {}\n", trace.end));
+        Some(output)
     }
 
     /// 生成多个优化级别的对比表格
-    pub fn generate_comparison_table(
-        &self,
-        o0_entries: &[DumpEntry],
-        o1_entries: &[DumpEntry],
-        o2_entries: &[DumpEntry],
-    ) -> String {
+    pub fn generate_comparison_table(&self, levels: &[(&str, &[DumpEntry])]) -> String {
         let mut output = String::new();
         
-        output.push_str("## 优化级别对比\n\n");
+        output.push_str(self.lang.pick("## 优化级别对比\n\n", "## Optimization level comparison\n\n"));
         
-        // O0 表格
-        output.push_str("### O0 (无优化)\n\n");
-        output.push_str(&self.generate_table(o0_entries));
-        output.push_str("\n");
-        
-        // O1 表格
-        output.push_str("### O1 (基础优化)\n\n");
-        output.push_str(&self.generate_table(o1_entries));
-        output.push_str("\n");
-        
-        // O2 表格
-        output.push_str("### O2 (高级优化)\n\n");
-        output.push_str(&self.generate_table(o2_entries));
-        output.push_str("\n");
+        match self.generate_source_aligned_table(levels) {
+            Some(table) => {
+                output.push_str(self.lang.pick("### 按源码行对齐\n\n", "### Aligned by source line\n\n"));
+                for (level, entries) in levels {
+                    if let Some(text) = self.generate_walkthrough(entries) {
+                        output.push_str(&format!("> **{}**: {}\n\n", self.level_labels.name(level), text));
+                    }
+                }
+                output.push_str(&table);
+                output.push('\n');
+            }
+            None => {
+                for (level, entries) in levels {
+                    output.push_str(&format!("### {}\n\n", self.level_labels.title_in(level, self.lang)));
+                    if let Some(text) = self.generate_walkthrough(entries) {
+                        output.push_str(&format!("> {}\n\n", text));
+                    }
+                    output.push_str(&self.generate_table(entries));
+                    output.push('\n');
+                }
+            }
+        }
         
         // 统计信息
-        output.push_str("### 统计信息\n\n");
-        output.push_str(&format!("- O0: {} 条指令\n", o0_entries.len()));
-        output.push_str(&format!("- O1: {} 条指令\n", o1_entries.len()));
-        output.push_str(&format!("- O2: {} 条指令\n", o2_entries.len()));
-        output.push_str("\n");
+        output.push_str(self.lang.pick("### 统计信息\n\n", "### Statistics\n\n"));
+        let sizes: Vec<(&str, SizeMetrics)> = levels
+            .iter()
+            .map(|(level, entries)| (self.level_labels.name(level), SizeMetrics::from_entries(entries)))
+            .collect();
+        let size_refs: Vec<(&str, &SizeMetrics)> = sizes.iter().map(|(level, s)| (*level, s)).collect();
+        output.push_str(&crate::metrics::size_table(&size_refs, self.lang));
+        if self.dead_stores {
+            output.push_str(&format!("| {} |", self.lang.pick("无用存储", "Dead stores")));
+            for (_, entries) in levels {
+                output.push_str(&format!(" {} |", crate::deadstore::detect(entries).len()));
+            }
+            output.push('\n');
+        }
+        if let (true, Some((base, _))) = (levels.len() > 1, levels.first()) {
+            output.push_str(&match self.lang {
+                Lang::Zh => format!("\n括号内为相对 {} 的变化。\n", self.level_labels.name(base)),
+                Lang::En => format!("\nPercentages are relative to {}.\n", self.level_labels.name(base)),
+            });
+        }
+        output.push('\n');
+
+        let named: Vec<(&str, &[DumpEntry])> =
+            levels.iter().map(|(level, entries)| (self.level_labels.name(level), *entries)).collect();
+        if let Some(summary) = crate::vectorize::summary(&named, self.lang) {
+            output.push_str(&format!("#### {}\n\n{}\n", self.lang.pick("向量化", "Vectorization"), summary));
+        }
+        if let Some(summary) = crate::unroll::summary(&named, self.lang) {
+            output.push_str(&format!("#### {}\n\n{}\n", self.lang.pick("循环展开", "Loop unrolling"), summary));
+        }
+
+        let stats: Vec<(&str, Stats)> = levels
+            .iter()
+            .map(|(level, entries)| (self.level_labels.name(level), Stats::from_entries(entries)))
+            .collect();
+        let stats_refs: Vec<(&str, &Stats)> = stats.iter().map(|(level, s)| (*level, s)).collect();
+        output.push_str(&crate::metrics::comparison_table(&stats_refs, self.lang));
+        output.push('\n');
+
+        if self.branch_stats {
+            let branches: Vec<(&str, BranchStats)> = levels
+                .iter()
+                .map(|(level, entries)| (self.level_labels.name(level), BranchStats::from_entries(entries)))
+                .collect();
+            let refs: Vec<(&str, &BranchStats)> = branches.iter().map(|(level, s)| (*level, s)).collect();
+            output.push_str(&crate::metrics::branch_table(&refs, self.lang));
+            output.push('\n');
+        }
+
+        if let Some(table) = self.generate_cycle_table(levels) {
+            output.push_str(&table);
+            output.push('\n');
+        }
         
         output
     }
 
+    /// 生成各级别的周期估计（未设置核心模型时返回 None）
+    pub fn generate_cycle_table(&self, levels: &[(&str, &[DumpEntry])]) -> Option<String> {
+        let model = self.cost_model.as_ref()?;
+        let levels: Vec<(&str, &[DumpEntry])> =
+            levels.iter().map(|(level, entries)| (self.level_labels.name(level), *entries)).collect();
+        let title = match self.lang {
+            Lang::Zh => format!("#### 估计周期（{}）\n\n", model.name()),
+            Lang::En => format!("#### Estimated cycles ({})\n\n", model.name()),
+        };
+        Some(format!("{}{}", title, model.estimate_table(&levels, self.lang)))
+    }
+
+    /// 生成按 C 源码行对齐的对比表格（未启用或各级别都没有 C 源码时返回 None）
+    ///
+    /// 相同的 C 代码（忽略空白差异）归为一行，按在各级别中首次出现的顺序排列；
+    /// 每个级别一列，列出该语句对应的指令条数和指令
+    pub fn generate_source_aligned_table(&self, levels: &[(&str, &[DumpEntry])]) -> Option<String> {
+        if !self.source_aligned {
+            return None;
+        }
+        let key = |e: &DumpEntry| e.c_code.replace("<br>", " ").split_whitespace().collect::<Vec<_>>().join(" ");
+        let instructions = |entries: &'_ [DumpEntry]| -> Vec<(String, String)> {
+            entries.iter().filter(|e| !e.asm_instruction.is_empty()).map(|e| (key(e), Self::plain_asm(e))).collect()
+        };
+        let per_level: Vec<Vec<(String, String)>> = levels.iter().map(|(_, entries)| instructions(entries)).collect();
+        if per_level.iter().flatten().all(|(code, _)| code.is_empty()) {
+            return None;
+        }
+
+        let mut statements: Vec<&str> = Vec::new();
+        for (code, _) in per_level.iter().flatten() {
+            if !statements.contains(&code.as_str()) {
+                statements.push(code);
+            }
+        }
+
+        let mut output = format!("| {} |", self.lang.pick("C代码", "C code"));
+        for (level, _) in levels {
+            output.push_str(&format!(" {} |", self.level_labels.name(level)));
+        }
+        output.push_str("\n|-------|");
+        output.push_str(&"----------|".repeat(levels.len()));
+        output.push('\n');
+        for statement in statements {
+            let code = match statement {
+                "" => self.lang.pick("（无对应源码）", "(no source line)").to_string(),
+                code => self.format_c_code(code),
+            };
+            output.push_str(&format!("| {} |", code));
+            for level in &per_level {
+                let asm: Vec<&str> =
+                    level.iter().filter(|(code, _)| code == statement).map(|(_, asm)| asm.as_str()).collect();
+                match (asm.len(), self.lang) {
+                    (0, _) => output.push_str(" - |"),
+                    (n, Lang::Zh) => output.push_str(&format!(" **{} 条**<br>{} |", n, asm.join("<br>"))),
+                    (n, Lang::En) => output.push_str(&format!(" **{}**<br>{} |", n, asm.join("<br>"))),
+                }
+            }
+            output.push('\n');
+        }
+        Some(output)
+    }
+
+    /// 去掉 objdump 注释、制表符换成空格的汇编指令
+    fn plain_asm(entry: &DumpEntry) -> String {
+        entry.asm_instruction.split("//").next().unwrap_or("").trim().replace('\t', " ")
+    }
+
+    /// 统计真实指令条数（不含提示/备注行）
+    fn instruction_count(entries: &[DumpEntry]) -> usize {
+        entries.iter().filter(|e| !e.asm_instruction.is_empty()).count()
+    }
+
     /// 格式化 C 代码（处理过长的代码）
     fn format_c_code(&self, code: &str) -> String {
         if code.is_empty() {
@@ -209,7 +1733,7 @@ impl TableGenerator {
         // 如果太长，智能截断（在合适的位置）
         if code.len() > self.c_code_width {
             // 尝试在逗号、分号、括号等位置截断
-            if let Some(pos) = code[..self.c_code_width].rfind(|c: char| c == ',' || c == ';' || c == ')' || c == ' ') {
+            if let Some(pos) = code[..self.c_code_width].rfind([',', ';', ')', ' ']) {
                 format!("{}...", &code[..pos + 1].trim())
             } else {
                 format!("{}...", &code[..self.c_code_width - 3])
@@ -219,6 +1743,49 @@ impl TableGenerator {
         }
     }
 
+    /// 报告的保存路径：按模板（未设置时为 `default`）展开文件名，相对路径放在输出目录下
+    fn output_path(&self, report: &AnalysisReport, output_dir: Option<&PathBuf>, default: &str) -> PathBuf {
+        let levels: Vec<&str> = report
+            .per_level
+            .iter()
+            .map(|l| Path::new(&l.level).file_stem().and_then(|s| s.to_str()).unwrap_or(&l.level))
+            .collect();
+        let template = self.output_template.as_deref().unwrap_or(default);
+        let name = expand_output_template(template, &report.function, &levels.join("-"), &today());
+        match output_dir {
+            Some(dir) => dir.join(name),
+            None => PathBuf::from(name),
+        }
+    }
+
+    /// 合并报告的保存路径：默认为 `<前缀>_report.md`，设置了文件名模板时按模板展开（`{function}` 为前缀名）
+    pub fn combined_path(&self, dump_prefix: &str, levels: &[String], output_dir: Option<&PathBuf>) -> PathBuf {
+        let template = self.output_template.as_deref().unwrap_or("{function}_report.md");
+        let name = expand_output_template(template, dump_prefix, &levels.join("-"), &today());
+        match output_dir {
+            Some(dir) => dir.join(name),
+            None => PathBuf::from(name),
+        }
+    }
+
+    /// 写入报告前的检查：不允许覆盖时报告或随附的 JSON 已存在则报错；创建模板中的子目录
+    pub fn prepare_output(&self, report: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = report.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.prepare_files(&self.output_files(report))
+    }
+
+    /// 写入一组文件前的检查：不允许覆盖时其中任何一个已存在则报错
+    pub fn prepare_files(&self, files: &[PathBuf]) -> anyhow::Result<()> {
+        if self.overwrite == Overwrite::Never {
+            if let Some(existing) = files.iter().find(|p| p.exists()) {
+                anyhow::bail!("{} 已存在 (使用 --force 覆盖)", existing.display());
+            }
+        }
+        Ok(())
+    }
+
     /// 保存到文件
     pub fn save_to_file(&self, content: &str, path: &PathBuf) -> std::io::Result<()> {
         let mut file = fs::File::create(path)?;
@@ -226,55 +1793,322 @@ impl TableGenerator {
         Ok(())
     }
 
-    /// 从三个 dump 文件生成对比表格并保存
+    /// 报告保存为 `report` 时写入的所有文件（终端输出时为空）
+    pub fn output_files(&self, report: &Path) -> Vec<PathBuf> {
+        if self.terminal {
+            return Vec::new();
+        }
+        let mut files = vec![report.to_path_buf()];
+        if self.json_output {
+            files.push(report.with_extension("json"));
+        }
+        files
+    }
+
+    /// 从各优化级别的 dump 文件生成对比表格并保存
+    ///
+    /// 未通过 `with_levels` 指定级别时，自动发现 `<前缀>_<级别>.dump` 文件。
+    /// 返回保存的报告路径（终端模式下为渲染好的文本）
     pub fn generate_from_dumps(
         &self,
         function_name: &str,
         dump_prefix: &str,
         output_dir: Option<&PathBuf>,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<ReportOutput> {
         use crate::objdump::ObjdumpParser;
         
-        // 智能处理前缀：如果包含 .dump 后缀，先去掉
-        let clean_prefix = dump_prefix
-            .strip_suffix(".dump").unwrap_or(dump_prefix)
-            .trim_end_matches("_O0")
-            .trim_end_matches("_O1")
-            .trim_end_matches("_O2");
-        
-        // 加载三个 dump 文件
-        let o0_path = format!("{}_O0.dump", clean_prefix);
-        let o1_path = format!("{}_O1.dump", clean_prefix);
-        let o2_path = format!("{}_O2.dump", clean_prefix);
-        
-        println!("读取 {} ...", o0_path);
-        let o0_parser = ObjdumpParser::from_file(&o0_path)?;
-        let o0_entries = o0_parser.extract_function_data(function_name)?;
-        
-        println!("读取 {} ...", o1_path);
-        let o1_parser = ObjdumpParser::from_file(&o1_path)?;
-        let o1_entries = o1_parser.extract_function_data(function_name)?;
-        
-        println!("读取 {} ...", o2_path);
-        let o2_parser = ObjdumpParser::from_file(&o2_path)?;
-        let o2_entries = o2_parser.extract_function_data(function_name)?;
-        
-        // 生成表格
-        println!("生成对比表格...");
-        let table = self.generate_comparison_table(&o0_entries, &o1_entries, &o2_entries);
-        
-        // 保存到文件
-        let output_path = if let Some(dir) = output_dir {
-            dir.join(format!("{}_comparison.md", function_name))
-        } else {
-            PathBuf::from(format!("{}_comparison.md", function_name))
+        // 智能处理前缀：去掉 .dump 和级别后缀
+        let clean_prefix = crate::levels::clean_prefix(dump_prefix);
+        let levels = match self.levels {
+            Some(ref levels) => levels.clone(),
+            None => crate::levels::discover(&clean_prefix),
         };
+        if levels.is_empty() {
+            anyhow::bail!("未找到 {}_<级别>.dump 文件 (如 {})", clean_prefix, crate::levels::dump_path(&clean_prefix, "O0"));
+        }
         
-        println!("保存到 {} ...", output_path.display());
-        self.save_to_file(&table, &output_path)?;
+        // 并行加载每个级别的 dump 文件
+        for level in &levels {
+            self.progress.report(Progress::Reading(&crate::levels::dump_path(&clean_prefix, level)));
+        }
+        let parsers = crate::levels::load_all(&clean_prefix, &levels)?;
+        let parsers: Vec<(&str, &ObjdumpParser)> = levels.iter().map(|level| level.as_str()).zip(&parsers).collect();
+        self.generate_from_parsers(function_name, &parsers, output_dir)
+    }
+
+    /// 从已解析的各优化级别 dump 生成对比表格并保存
+    ///
+    /// `parsers` 为 (级别, 解析器)，批量分析时多个函数共用同一组解析器。返回保存的报告路径（终端模式下为渲染好的文本）
+    pub fn generate_from_parsers(
+        &self,
+        function_name: &str,
+        parsers: &[(&str, &crate::objdump::ObjdumpParser)],
+        output_dir: Option<&PathBuf>,
+    ) -> anyhow::Result<ReportOutput> {
+        let report = self.analyze_parsers(function_name, parsers)?;
+        self.progress.report(Progress::GeneratingComparison);
+        self.write_comparison(&report, output_dir)
+    }
+
+    /// 分析函数在各优化级别下的指令，返回结构化结果（不写文件，也不输出）
+    pub fn analyze_parsers(
+        &self,
+        function_name: &str,
+        parsers: &[(&str, &crate::objdump::ObjdumpParser)],
+    ) -> anyhow::Result<AnalysisReport> {
+        let per_level = parsers
+            .iter()
+            .map(|&(level, parser)| self.analyze_level(parser, Some(level), level, function_name))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(AnalysisReport { function: function_name.to_string(), per_level })
+    }
+
+    /// 分析函数在一个 dump 中的指令，`level` 为 None 时（单文件模式）使用全部优化备注
+    fn analyze_level(
+        &self,
+        parser: &crate::objdump::ObjdumpParser,
+        level: Option<&str>,
+        name: &str,
+        function_name: &str,
+    ) -> anyhow::Result<LevelAnalysis> {
+        let diagnostics = parser.parse_diagnostics(function_name)?;
+        if self.strict {
+            diagnostics.check_strict(name)?;
+        }
+        let entries = self.function_entries(parser, level, function_name)?;
+        Ok(LevelAnalysis {
+            level: name.to_string(),
+            diagnostics,
+            semantics: self.semantic_column(&entries),
+            branches: BranchStats::from_entries(&entries),
+            size: SizeMetrics::from_entries(&entries),
+            signature: self.generate_signature(parser, function_name, &entries),
+            warnings: if parser.is_arm32() { Vec::new() } else { crate::analyzer::entry_warnings(&entries) },
+            entries,
+        })
+    }
+
+    /// 把多级别的分析结果渲染为 Markdown 对比报告
+    pub fn render_comparison(&self, report: &AnalysisReport) -> String {
+        let function_name = report.function.as_str();
+        let level_refs = report.level_refs();
+        let mut table = self.generate_comparison_table(&level_refs);
+        let diagnostics: Vec<(&str, &ParseDiagnostics)> = report
+            .per_level
+            .iter()
+            .map(|analysis| (self.level_labels.name(&analysis.level), &analysis.diagnostics))
+            .collect();
+        table.push_str(&format!(
+            "#### {}\n\n{}\n",
+            self.lang.pick("解析覆盖率", "Parse coverage"),
+            crate::diagnostics::summary(&diagnostics, self.lang)
+        ));
+        for ((level, entries), analysis) in level_refs.iter().zip(&report.per_level) {
+            if let Some(signature) = &analysis.signature {
+                let title = self.lang.pick("推断签名", "inferred signature");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, signature));
+            }
+            if let Some(issues) = crate::diagnostics::issue_table(&analysis.diagnostics, self.lang) {
+                let title = self.lang.pick("未完整解析的指令", "instructions not fully parsed");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, issues));
+            }
+            if let Some(returns) = self.generate_returns_table(entries) {
+                let title = self.lang.pick("返回值来源", "return value sources");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, returns));
+            }
+            if let Some(hardening) = self.generate_hardening_table(entries) {
+                let title = self.lang.pick("安全检查", "hardening checks");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, hardening));
+            }
+            if let Some(alignment) = self.generate_stack_alignment_table(entries) {
+                let title = self.lang.pick("栈对齐", "stack alignment");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, alignment));
+            }
+            if let Some(dead) = self.generate_dead_store_table(entries) {
+                let title = self.lang.pick("无用存储", "dead stores");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, dead));
+            }
+            if let Some(width) = self.generate_width_table(entries) {
+                let title = self.lang.pick("位宽检查", "width checks");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, width));
+            }
+            if let Some(liveness) = self.generate_liveness_table(entries) {
+                let title = self.lang.pick("被调用者保存寄存器", "callee-saved registers");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, liveness));
+            }
+            if let Some(trace) = self.generate_trace_table(entries) {
+                let title = self.lang.pick("执行轨迹", "execution trace");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, trace));
+            }
+            if let Some(arrows) = self.generate_jump_arrows(entries) {
+                let title = self.lang.pick("跳转箭头", "jump arrows");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, arrows));
+            }
+            if let Some(pseudocode) = self.generate_pseudocode(function_name, entries) {
+                let title = self.lang.pick("伪代码", "pseudo-code");
+                table.push_str(&format!("### {} {}\n\n{}\n", level, title, pseudocode));
+            }
+        }
+        table
+    }
+
+    /// 输出多级别的对比报告：终端模式下返回渲染好的文本，否则保存 Markdown（和 JSON）并返回报告路径
+    pub fn write_comparison(&self, report: &AnalysisReport, output_dir: Option<&PathBuf>) -> anyhow::Result<ReportOutput> {
+        let function_name = report.function.as_str();
+
+        if self.terminal {
+            let tables: Vec<String> = report
+                .level_refs()
+                .into_iter()
+                .map(|(level, entries)| self.generate_terminal(&self.level_labels.title_in(level, self.lang), entries))
+                .collect();
+            return Ok(ReportOutput::Terminal(tables.join("\n\n")));
+        }
+
+        let output_path = self.output_path(report, output_dir, "{function}_comparison.md");
+
+        self.prepare_output(&output_path)?;
+        self.progress.report(Progress::Saving(&output_path));
+        self.save_to_file(&self.render_comparison(report), &output_path)?;
+
+        if self.json_output {
+            let json = self.generate_json(function_name, &report.level_refs());
+            let json_path = output_path.with_extension("json");
+            self.progress.report(Progress::Saving(&json_path));
+            self.save_to_file(&json, &json_path)?;
+        }
         
-        println!("完成！");
-        Ok(())
+        self.progress.report(Progress::Done);
+        Ok(ReportOutput::Saved(output_path))
+    }
+
+    /// 生成批量分析的索引页
+    ///
+    /// `reports` 为每个函数的分析结果：成功时为报告路径，失败时为错误信息。
+    /// 索引与报告保存在同一目录，因此链接只使用文件名
+    pub fn generate_index(
+        &self,
+        dump_prefix: &str,
+        levels: &[String],
+        reports: &[(String, std::result::Result<PathBuf, String>)],
+    ) -> String {
+        let mut output = String::new();
+        let dump_prefix = self.source_name(dump_prefix);
+        let levels: Vec<&str> = levels.iter().map(|level| self.level_labels.name(level)).collect();
+        match self.lang {
+            Lang::Zh => output.push_str(&format!("# {} 函数分析索引\n\n优化级别: {}\n\n", dump_prefix, levels.join(", "))),
+            Lang::En => output.push_str(&format!("# {} function index\n\nOptimization levels: {}\n\n", dump_prefix, levels.join(", "))),
+        }
+
+        let mut reports: Vec<_> = reports.iter().collect();
+        if self.reproducible {
+            reports.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        output.push_str(self.lang.pick("| 函数 | 报告 |\n", "| Function | Report |\n"));
+        output.push_str("|------|------|\n");
+        for (function, report) in &reports {
+            match report {
+                Ok(path) => {
+                    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+                    output.push_str(&format!("| {} | [{}]({}) |\n", function, name, name));
+                }
+                Err(e) => output.push_str(&format!("| {} | ❌ {} |\n", function, e.replace('\n', "<br>"))),
+            }
+        }
+
+        let failed = reports.iter().filter(|(_, r)| r.is_err()).count();
+        let succeeded = reports.len() - failed;
+        output.push_str(&match self.lang {
+            Lang::Zh => format!("\n共 {} 个函数，成功 {} 个", reports.len(), succeeded),
+            Lang::En => format!("\n{} functions, {} succeeded", reports.len(), succeeded),
+        });
+        if failed > 0 {
+            output.push_str(&match self.lang {
+                Lang::Zh => format!("，失败 {} 个", failed),
+                Lang::En => format!(", {} failed", failed),
+            });
+        }
+        output.push('\n');
+        output
+    }
+
+    /// 把多个函数的分析结果合并为一份带目录的 Markdown 报告
+    ///
+    /// 目录中列出各级别的指令条数；每个函数一节（原报告的标题降一级），节首列出报告中其他函数与它的调用关系并互相链接；
+    /// 分析失败的函数只在目录中列出错误信息
+    pub fn generate_combined(
+        &self,
+        dump_prefix: &str,
+        levels: &[String],
+        reports: &[(String, std::result::Result<AnalysisReport, String>)],
+    ) -> String {
+        let dump_prefix = self.source_name(dump_prefix);
+        let level_names: Vec<&str> = levels.iter().map(|level| self.level_labels.name(level)).collect();
+        let mut output = match self.lang {
+            Lang::Zh => format!("# {} 函数分析报告\n\n优化级别: {}\n\n", dump_prefix, level_names.join(", ")),
+            Lang::En => format!("# {} function report\n\nOptimization levels: {}\n\n", dump_prefix, level_names.join(", ")),
+        };
+
+        let mut reports: Vec<_> = reports.iter().collect();
+        if self.reproducible {
+            reports.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        // 报告内函数之间的调用关系：函数 -> 调用的函数（按首次出现的顺序）
+        let analyzed: BTreeSet<&str> = reports.iter().filter(|(_, r)| r.is_ok()).map(|(f, _)| f.as_str()).collect();
+        let mut calls: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for (function, report) in &reports {
+            let Ok(report) = report else {
+                continue;
+            };
+            let callees = calls.entry(function.as_str()).or_default();
+            for target in report.per_level.iter().flat_map(|l| l.entries.iter().filter_map(|e| e.call_target())) {
+                if target != *function && analyzed.contains(target.as_str()) && !callees.contains(&target) {
+                    callees.push(target);
+                }
+            }
+        }
+        let link = |name: &str| format!("[{}](#{})", name, combined_anchor(name));
+
+        output.push_str(&format!("<a id=\"toc\"></a>\n\n## {}\n\n", self.lang.pick("目录", "Contents")));
+        for (function, report) in &reports {
+            match report {
+                Ok(report) => {
+                    let counts: Vec<String> = report
+                        .per_level
+                        .iter()
+                        .map(|l| format!("{}: {}", self.level_labels.name(&l.level), l.instruction_count()))
+                        .collect();
+                    output.push_str(&format!("- {} — {}\n", link(function), counts.join(", ")));
+                }
+                Err(e) => output.push_str(&format!("- {} ❌ {}\n", function, e)),
+            }
+        }
+
+        for (function, report) in &reports {
+            let Ok(report) = report else {
+                continue;
+            };
+            output.push_str(&format!("\n<a id=\"{}\"></a>\n\n## {}\n\n", combined_anchor(function), function));
+            let callees = &calls[function.as_str()];
+            let callers: Vec<&str> = calls
+                .iter()
+                .filter(|(caller, targets)| **caller != function.as_str() && targets.contains(function))
+                .map(|(caller, _)| *caller)
+                .collect();
+            if !callees.is_empty() {
+                let names: Vec<String> = callees.iter().map(|name| link(name)).collect();
+                output.push_str(&format!("{}: {}\n\n", self.lang.pick("调用", "Calls"), names.join(", ")));
+            }
+            if !callers.is_empty() {
+                let names: Vec<String> = callers.iter().map(|name| link(name)).collect();
+                output.push_str(&format!("{}: {}\n\n", self.lang.pick("被调用", "Called by"), names.join(", ")));
+            }
+            output.push_str(&demote_headings(&self.render_comparison(report)));
+            output.push_str(&format!("\n[↑ {}](#toc)\n", self.lang.pick("返回目录", "Back to contents")));
+        }
+        output
     }
 
     /// 从单个 dump 文件生成函数分析表格
@@ -283,29 +2117,132 @@ impl TableGenerator {
         function_name: &str,
         dump_path: &str,
         output_dir: Option<&PathBuf>,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<ReportOutput> {
         use crate::objdump::ObjdumpParser;
         
-        println!("读取 {} ...", dump_path);
+        self.progress.report(Progress::Reading(dump_path));
         let parser = ObjdumpParser::from_file(dump_path)?;
-        let entries = parser.extract_function_data(function_name)?;
-        
-        // 生成表格
-        println!("生成分析表格...");
-        let table = self.generate_table(&entries);
-        
-        // 保存到文件
-        let output_path = if let Some(dir) = output_dir {
-            dir.join(format!("{}_analysis.md", function_name))
-        } else {
-            PathBuf::from(format!("{}_analysis.md", function_name))
+        self.generate_from_parser(function_name, &parser, dump_path, output_dir)
+    }
+
+    /// 从已解析的 objdump 输出生成函数分析表格
+    ///
+    /// `source` 为输出来源（文件名），用于 JSON 中的级别标签
+    pub fn generate_from_parser(
+        &self,
+        function_name: &str,
+        parser: &crate::objdump::ObjdumpParser,
+        source: &str,
+        output_dir: Option<&PathBuf>,
+    ) -> anyhow::Result<ReportOutput> {
+        let report = self.analyze_parser(function_name, parser, source)?;
+        self.progress.report(Progress::GeneratingAnalysis);
+        self.write_analysis(&report, output_dir)
+    }
+
+    /// 分析单个 dump 中的函数，返回只有一个级别（名称为 `source` 的文件名）的结构化结果
+    pub fn analyze_parser(
+        &self,
+        function_name: &str,
+        parser: &crate::objdump::ObjdumpParser,
+        source: &str,
+    ) -> anyhow::Result<AnalysisReport> {
+        let level = self.analyze_level(parser, None, self.source_name(source), function_name)?;
+        Ok(AnalysisReport { function: function_name.to_string(), per_level: vec![level] })
+    }
+
+    /// 把单文件的分析结果渲染为 Markdown 报告（使用第一个级别）
+    pub fn render_analysis(&self, report: &AnalysisReport) -> String {
+        let function_name = report.function.as_str();
+        let Some(analysis) = report.per_level.first() else {
+            return String::new();
         };
+        let entries = &analysis.entries;
+        let mut table = String::new();
+        if let Some(text) = self.generate_walkthrough(entries) {
+            table.push_str(&format!("## {}\n\n{}\n\n", self.lang.pick("函数导读", "Walkthrough"), text));
+        }
+        if let Some(signature) = &analysis.signature {
+            table.push_str(&format!("## {}\n\n{}\n", self.lang.pick("推断签名", "Inferred signature"), signature));
+        }
+        table.push_str(&self.generate_grouped_table(entries));
+        if let Some(returns) = self.generate_returns_table(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("返回值来源", "Return value sources"), returns));
+        }
+        if let Some(hardening) = self.generate_hardening_table(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("安全检查", "Hardening checks"), hardening));
+        }
+        if let Some(alignment) = self.generate_stack_alignment_table(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("栈对齐", "Stack alignment"), alignment));
+        }
+        if let Some(dead) = self.generate_dead_store_table(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("无用存储", "Dead stores"), dead));
+        }
+        if let Some(width) = self.generate_width_table(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("位宽检查", "Width checks"), width));
+        }
+        if let Some(liveness) = self.generate_liveness_table(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("被调用者保存寄存器", "Callee-saved registers"), liveness));
+        }
+        if self.branch_stats {
+            let branches = crate::metrics::branch_table(&[(self.lang.pick("数量", "Count"), &analysis.branches)], self.lang);
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("跳转统计", "Branch statistics"), branches));
+        }
+        if let Some(model) = &self.cost_model {
+            let estimate = model.estimate_table(&[(self.lang.pick("本函数", "This function"), entries)], self.lang);
+            let title = match self.lang {
+                Lang::Zh => format!("估计周期（{}）", model.name()),
+                Lang::En => format!("Estimated cycles ({})", model.name()),
+            };
+            table.push_str(&format!("\n## {}\n\n{}", title, estimate));
+        }
+        if let Some(trace) = self.generate_trace_table(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("执行轨迹", "Execution trace"), trace));
+        }
+        if let Some(arrows) = self.generate_jump_arrows(entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("跳转箭头", "Jump arrows"), arrows));
+        }
+        if let Some(pseudocode) = self.generate_pseudocode(function_name, entries) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("伪代码", "Pseudo-code"), pseudocode));
+        }
+        let coverage = crate::diagnostics::summary(&[(self.lang.pick("本函数", "This function"), &analysis.diagnostics)], self.lang);
+        if !coverage.is_empty() {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("解析覆盖率", "Parse coverage"), coverage));
+        }
+        if let Some(issues) = crate::diagnostics::issue_table(&analysis.diagnostics, self.lang) {
+            table.push_str(&format!("\n## {}\n\n{}", self.lang.pick("未完整解析的指令", "Instructions not fully parsed"), issues));
+        }
+        table
+    }
+
+    /// 输出单文件的分析报告：终端模式下返回渲染好的文本，否则保存 Markdown（和 JSON）并返回报告路径
+    pub fn write_analysis(&self, report: &AnalysisReport, output_dir: Option<&PathBuf>) -> anyhow::Result<ReportOutput> {
+        let function_name = report.function.as_str();
+
+        if self.terminal {
+            let table = report
+                .per_level
+                .first()
+                .map(|analysis| self.generate_terminal(function_name, &analysis.entries))
+                .unwrap_or_default();
+            return Ok(ReportOutput::Terminal(table));
+        }
+
+        let output_path = self.output_path(report, output_dir, "{function}_analysis.md");
+
+        self.prepare_output(&output_path)?;
+        self.progress.report(Progress::Saving(&output_path));
+        self.save_to_file(&self.render_analysis(report), &output_path)?;
+
+        if self.json_output {
+            let json = self.generate_json(function_name, &report.level_refs());
+            let json_path = output_path.with_extension("json");
+            self.progress.report(Progress::Saving(&json_path));
+            self.save_to_file(&json, &json_path)?;
+        }
         
-        println!("保存到 {} ...", output_path.display());
-        self.save_to_file(&table, &output_path)?;
-        
-        println!("完成！");
-        Ok(())
+        self.progress.report(Progress::Done);
+        Ok(ReportOutput::Saved(output_path))
     }
 }
 
@@ -327,6 +2264,7 @@ mod tests {
         
         let entries = vec![
             DumpEntry {
+                id: String::from("test+0x0"),
                 c_line: Some(1),
                 c_code: String::from("int a = 0;"),
                 address: String::from("0x1000"),
@@ -347,5 +2285,273 @@ mod tests {
         assert!(table.contains("C代码"));
         assert!(table.contains("语义解释"));
         assert!(table.contains("mov x0, #0"));
+
+        let json = generator.generate_json("test", &[("O0", &entries)]);
+        assert!(json.contains("\"id\": \"test+0x0\""));
+
+        // objdump -d 的输出没有 C 源码，省略 C 代码列
+        let mut disassembly_only = entries.clone();
+        disassembly_only[0].c_line = None;
+        disassembly_only[0].c_code.clear();
+        let table = generator.generate_table(&disassembly_only);
+        assert!(table.starts_with("| 汇编指令 | 语义解释 |"));
+
+        let table = TableGenerator::new().with_language(Lang::En).generate_table(&entries);
+        assert!(table.starts_with("| C code | Assembly | Semantics |"));
+    }
+
+    #[test]
+    fn test_table_config() {
+        let dump = r#"
+0000000000000000 <f>:
+    return g;
+   0:   90000000    adrp x0, 0 <g>
+   4:   f9400400    ldr x0, [x0, #8]  // 0x8
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string()).extract_function_data("f").unwrap();
+        let names = vec![String::from("address,machine-code"), String::from("asm,comment")];
+        let config = TableConfig::parse(&names).unwrap();
+        let table = TableGenerator::new().with_table_config(config).generate_table(&entries);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| 地址 | 机器码 | 汇编指令 | 注释 |");
+        assert_eq!(lines[3], "| 0x4 | f9400400 | ldr x0, [x0, #8] | 0x8 |");
+
+        assert!(TableConfig::parse(&[String::from("asm,bogus")]).is_err());
+
+        // 单文件分析默认包含地址和机器码列，多级别对比默认不包含
+        let single = TableGenerator::new().generate_grouped_table(&entries);
+        assert!(single.starts_with("| 地址 | 机器码 | C代码 | 汇编指令 | 语义解释 |"));
+        assert!(single.contains("| 0x0 | 90000000 | return g; | adrp x0, 0 <g> |"));
+        assert!(TableGenerator::new().generate_table(&entries).starts_with("| C代码 |"));
+        let off = TableGenerator::new().with_address_columns(false).generate_grouped_table(&entries);
+        assert!(off.starts_with("| C代码 |"));
+    }
+
+    #[test]
+    fn test_cost_column() {
+        let dump = r#"
+0000000000000000 <f>:
+   0:   b9400001    ldr w1, [x0]
+   4:   1ac20c20    sdiv w0, w1, w2
+   8:   d65f03c0    ret
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string()).extract_function_data("f").unwrap();
+        let generator = TableGenerator::new().with_cost_model(CostModel::new(crate::cost::Core::CortexA72));
+        let table = generator.generate_table(&entries);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| 汇编指令 | 语义解释 | 延迟/吞吐 |");
+        assert!(lines[3].ends_with("| 12 / 1/12 |"));
+
+        let levels: Vec<(&str, &[DumpEntry])> = vec![("O2", &entries)];
+        let cycles = generator.generate_cycle_table(&levels).unwrap();
+        assert!(cycles.starts_with("#### 估计周期（Cortex-A72）"));
+        assert!(cycles.contains("| O2 | 3 | 17 | 13.5 |"));
+        assert!(TableGenerator::new().generate_cycle_table(&levels).is_none());
+    }
+
+    #[test]
+    fn test_source_aligned_table() {
+        let o0 = r#"
+0000000000000000 <f>:
+    int s = 0;
+   0:   b9000fff    str wzr, [sp, #12]
+    return s;
+   4:   b9400fe0    ldr w0, [sp, #12]
+   8:   d65f03c0    ret
+"#;
+        let o2 = r#"
+0000000000000000 <f>:
+    return  s;
+   0:   52800000    mov w0, #0x0
+   4:   d65f03c0    ret
+"#;
+        let o0 = crate::objdump::ObjdumpParser::new(o0.to_string()).extract_function_data("f").unwrap();
+        let o2 = crate::objdump::ObjdumpParser::new(o2.to_string()).extract_function_data("f").unwrap();
+        let levels: Vec<(&str, &[DumpEntry])> = vec![("O0", &o0), ("O2", &o2)];
+
+        assert!(TableGenerator::new().generate_source_aligned_table(&levels).is_none());
+        let table = TableGenerator::new().with_source_alignment(true).generate_source_aligned_table(&levels).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| C代码 | O0 | O2 |");
+        assert_eq!(lines[2], "| int s = 0; | **1 条**<br>str wzr, [sp, #12] | - |");
+        assert_eq!(lines[3], "| return s; | **2 条**<br>ldr w0, [sp, #12]<br>ret | **2 条**<br>mov w0, #0x0<br>ret |");
+    }
+
+    #[test]
+    fn test_branch_labels() {
+        let dump = r#"
+0000000000000000 <sum>:
+    s += a[i];
+   0:   b8404403    ldr w3, [x0], #4
+   4:   0b030042    add w2, w2, w3
+   8:   eb01001f    cmp x0, x1
+   c:   54ffffa1    b.ne 0 <sum>
+    return s;
+  10:   d65f03c0    ret
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string()).extract_function_data("sum").unwrap();
+        let config = TableConfig::new().with_columns(vec![Column::Assembly, Column::Semantics]);
+
+        let table = TableGenerator::new().render_table(&entries, &config);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[2], "| .L1: |  |");
+        assert!(lines[6].starts_with("| b.ne .L1 | "));
+        assert!(lines[6].ends_with("如果 X0 != X1 则跳转到 .L1（来自上一条 cmp）（s += a[i];） |"));
+
+        let table = TableGenerator::new().with_branch_labels(false).render_table(&entries, &config);
+        assert!(!table.contains(".L1"));
+    }
+
+    #[test]
+    fn test_grouped_table() {
+        let dump = r#"
+0000000000000000 <sum>:
+    s += a[i];
+   0:   b8404403    ldr w3, [x0], #4
+   4:   0b030042    add w2, w2, w3
+    return s;
+   8:   d65f03c0    ret
+"#;
+        let parser = crate::objdump::ObjdumpParser::new(dump.to_string());
+        let entries = parser.extract_function_data("sum").unwrap();
+
+        let collapsed = TableGenerator::new()
+            .with_grouping(Grouping::Collapsed)
+            .generate_grouped_table(&entries);
+        assert!(collapsed.contains("| s += a[i]; | 2 | 加载 ×1、算术 ×1 |"));
+        assert!(!collapsed.contains("ldr"));
+
+        let expanded = TableGenerator::new()
+            .with_grouping(Grouping::Expanded)
+            .generate_grouped_table(&entries);
+        assert!(expanded.contains("| **s += a[i];** | *2 条指令* |"));
+        assert!(expanded.contains("| | ldr w3, [x0], #4 |"));
+    }
+
+    #[test]
+    fn test_snippet() {
+        let dump = r#"
+0000000000000000 <sum>:
+    int s = 0;
+   0:   52800002    mov w2, #0x0
+    s += a[i];
+   4:   b8404403    ldr w3, [x0], #4
+   8:   0b030042    add w2, w2, w3
+   c:   d65f03c0    ret
+"#;
+        let entries = crate::objdump::ObjdumpParser::new(dump.to_string())
+            .extract_function_data("sum")
+            .unwrap();
+        let selected = TableGenerator::select_instructions(&entries, 2, 3);
+        assert_eq!(selected.len(), 2);
+
+        let generator = TableGenerator::new();
+        let plain = generator.generate_plain_snippet(&selected);
+        assert!(plain.starts_with("// s += a[i];\n    ldr w3, [x0], #4  // "));
+        assert_eq!(plain.lines().count(), 3);
+
+        // 终端输出：标题、C 代码行和每条指令一行，操作数列按最长者对齐
+        let terminal = generator.generate_terminal("sum", &selected);
+        assert_eq!(terminal.lines().count(), 4);
+        assert!(terminal.contains("w3, [x0], #4"));
+        assert!(terminal.contains("w2, w2, w3  "));
+    }
+
+    #[test]
+    fn test_generate_index() {
+        let reports = vec![
+            (String::from("main"), Ok(PathBuf::from("reports/main_comparison.md"))),
+            (String::from("sum"), Err(String::from("未找到函数: sum"))),
+        ];
+        let index = TableGenerator::new().generate_index("matrix", &[String::from("O0"), String::from("O2")], &reports);
+
+        assert!(index.contains("| main | [main_comparison.md](main_comparison.md) |"));
+        assert!(index.contains("| sum | ❌ 未找到函数: sum |"));
+        assert!(index.ends_with("共 2 个函数，成功 1 个，失败 1 个\n"));
+
+        let mut reports = reports;
+        reports.reverse();
+        let index = TableGenerator::new()
+            .with_reproducible(true)
+            .generate_index("/home/user/matrix", &[String::from("O0")], &reports);
+        assert!(index.starts_with("# matrix 函数分析索引"));
+        assert!(index.find("| main |").unwrap() < index.find("| sum |").unwrap());
+    }
+
+    #[test]
+    fn test_analyze_parsers() {
+        use crate::objdump::ObjdumpParser;
+
+        let o0 = ObjdumpParser::new(String::from(
+            "0000000000000000 <f>:\n   0:   d10043ff    sub sp, sp, #0x10\n   4:   910043ff    add sp, sp, #0x10\n   8:   d65f03c0    ret\n",
+        ));
+        let o2 = ObjdumpParser::new(String::from("0000000000000000 <f>:\n   0:   d65f03c0    ret\n"));
+        let generator = TableGenerator::new().with_signature(true);
+        let report = generator.analyze_parsers("f", &[("O0", &o0), ("O2", &o2)]).unwrap();
+
+        assert_eq!(report.function, "f");
+        assert_eq!(report.per_level.iter().map(|l| l.instruction_count()).collect::<Vec<_>>(), [3, 1]);
+        assert!(report.per_level.iter().all(|l| l.signature.is_some()));
+        let markdown = generator.render_comparison(&report);
+        assert!(markdown.contains("## O0"));
+        assert!(markdown.contains("### O2 推断签名"));
+        assert!(serde_json::to_value(&report).unwrap()["per_level"][1]["level"] == "O2");
+
+        let single = generator.analyze_parser("f", &o2, "t_O2.dump").unwrap();
+        assert_eq!(single.per_level[0].level, "t_O2.dump");
+        assert!(generator.render_analysis(&single).contains("## 推断签名"));
+        assert!(generator.analyze_parsers("g", &[("O0", &o0)]).is_err());
+    }
+
+    #[test]
+    fn test_output_template() {
+        use crate::objdump::ObjdumpParser;
+
+        assert_eq!(expand_output_template("{function}_{level}_{date}.md", "f", "O0-O2", "2024-01-02"), "f_O0-O2_2024-01-02.md");
+        let date = today();
+        assert_eq!(date.len(), 10);
+        assert!(date.as_str() > "2024-01-01");
+
+        let dir = std::env::temp_dir().join("alaz-output-template-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let parser = ObjdumpParser::new(String::from("0000000000000000 <f>:\n   0:   d65f03c0    ret\n"));
+        let generator = TableGenerator::new().with_output_template("reports/{function}_{level}.md");
+        let report = generator.analyze_parser("f", &parser, "/tmp/t_O2.dump").unwrap();
+        let output = generator.write_analysis(&report, Some(&dir)).unwrap();
+        assert_eq!(output, ReportOutput::Saved(dir.join("reports/f_t_O2.md")));
+        // 默认不覆盖，第二次写入报错
+        let err = generator.write_analysis(&report, Some(&dir)).unwrap_err();
+        assert!(err.to_string().contains("已存在"));
+        let generator = generator.with_overwrite(Overwrite::Always);
+        assert_eq!(generator.write_analysis(&report, Some(&dir)).unwrap(), output);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_generate_combined() {
+        use crate::objdump::ObjdumpParser;
+
+        let parser = ObjdumpParser::new(String::from(
+            "0000000000000000 <f.part.0>:\n   0:   d65f03c0    ret\n\n0000000000000004 <main>:\n   4:   97ffffff    bl 0 <f.part.0>\n   8:   d65f03c0    ret\n",
+        ));
+        let generator = TableGenerator::new();
+        let report = |name: &str| generator.analyze_parsers(name, &[("O2", &parser)]).map_err(|e| e.to_string());
+        let reports = vec![
+            (String::from("f.part.0"), report("f.part.0")),
+            (String::from("main"), report("main")),
+            (String::from("g"), report("g")),
+        ];
+        let combined = generator.generate_combined("t", &[String::from("O2")], &reports);
+
+        assert!(combined.starts_with("# t 函数分析报告"));
+        assert!(combined.contains("- [main](#fn-main) — O2: 2\n"));
+        assert!(combined.contains("- g ❌ ") && combined.contains("未找到函数: g"));
+        assert!(combined.contains("<a id=\"fn-f-part-0\"></a>"));
+        assert!(combined.contains("调用: [f.part.0](#fn-f-part-0)"));
+        assert!(combined.contains("被调用: [main](#fn-main)"));
+        // 原报告的标题降一级
+        assert!(combined.contains("\n### 优化级别对比"));
+        assert!(!combined.contains("\n## 优化级别对比"));
+        assert_eq!(demote_headings("## a\n```\n# c\n```\n"), "### a\n```\n# c\n```\n");
     }
 }
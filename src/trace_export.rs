@@ -0,0 +1,173 @@
+//! 执行轨迹导出
+//!
+//! 把模拟器记录的执行轨迹（每一步的寄存器前后值、内存访问、系统调用）导出为 JSON
+//! 或紧凑的二进制格式，供外部可视化工具回放执行过程。
+//!
+//! 二进制格式全部使用小端序：
+//!
+//! - 文件头：魔数 `ALTR`、版本号 `u8`、函数名（`u16` 长度 + UTF-8）、步数 `u32`
+//! - 每一步：地址 `u64`、执行后的 NZCV `u8`、标记 `u8`（bit0 跳过外部调用，bit1 含系统调用）
+//!   - 寄存器变化数 `u8`，每项为寄存器编号 `u8`（0-30 为 X0-X30，31 为 SP，32 为 NZCV）、旧值 `u64`、新值 `u64`
+//!   - 内存访问数 `u8`，每项为地址 `u64`、字节数 `u8`、是否写 `u8`、值 `u64`
+//!   - 含系统调用时：调用号 `u64`、参数 3 × `u64`、返回值 `u64`
+//! - 结束原因：类型 `u8`（0 返回、1 退出、2 离开函数、3 无法解析、4 执行失败、5 步数上限）+ 值 `u64`
+
+use crate::emulator::{Trace, TraceEnd};
+use crate::error::{Result, InterpreterError};
+use serde::Serialize;
+
+/// 二进制格式的魔数
+pub const BINARY_MAGIC: &[u8; 4] = b"ALTR";
+
+/// 二进制格式版本号
+pub const BINARY_VERSION: u8 = 1;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// 带缩进的 JSON
+    Json,
+    /// 紧凑二进制
+    Binary,
+}
+
+impl TraceFormat {
+    /// 解析格式名称（json, bin）
+    pub fn parse(text: &str) -> Result<Self> {
+        match text {
+            "json" => Ok(TraceFormat::Json),
+            "bin" | "binary" => Ok(TraceFormat::Binary),
+            _ => Err(InterpreterError::ParseError(format!(
+                "不支持的轨迹格式: {} (可选: json, bin)",
+                text
+            ))),
+        }
+    }
+
+    /// 根据文件扩展名推断格式，`.json` 以外均视为二进制
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => TraceFormat::Json,
+            _ => TraceFormat::Binary,
+        }
+    }
+}
+
+/// JSON 导出的顶层结构
+#[derive(Serialize)]
+struct TraceDocument<'a> {
+    function: &'a str,
+    #[serde(flatten)]
+    trace: &'a Trace,
+}
+
+/// 导出为 JSON
+pub fn to_json(function: &str, trace: &Trace) -> String {
+    serde_json::to_string_pretty(&TraceDocument { function, trace }).unwrap_or_default()
+}
+
+/// 导出为紧凑二进制格式
+pub fn to_binary(function: &str, trace: &Trace) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(BINARY_MAGIC);
+    out.push(BINARY_VERSION);
+    out.extend_from_slice(&(function.len() as u16).to_le_bytes());
+    out.extend_from_slice(function.as_bytes());
+    out.extend_from_slice(&(trace.steps.len() as u32).to_le_bytes());
+
+    for step in &trace.steps {
+        out.extend_from_slice(&step.address.to_le_bytes());
+        out.push(step.nzcv as u8);
+        out.push(step.skipped_call as u8 | ((step.syscall.is_some() as u8) << 1));
+
+        out.push(step.changes.len() as u8);
+        for change in &step.changes {
+            out.push(register_id(&change.name));
+            out.extend_from_slice(&change.old.to_le_bytes());
+            out.extend_from_slice(&change.new.to_le_bytes());
+        }
+
+        out.push(step.accesses.len() as u8);
+        for access in &step.accesses {
+            out.extend_from_slice(&access.address.to_le_bytes());
+            out.push(access.size as u8);
+            out.push(access.write as u8);
+            out.extend_from_slice(&access.value.to_le_bytes());
+        }
+
+        if let Some(ref event) = step.syscall {
+            out.extend_from_slice(&event.number.to_le_bytes());
+            for arg in event.args {
+                out.extend_from_slice(&arg.to_le_bytes());
+            }
+            out.extend_from_slice(&event.result.to_le_bytes());
+        }
+    }
+
+    let (kind, value) = match trace.end {
+        TraceEnd::Returned(x0) => (0, x0),
+        TraceEnd::Exited(code) => (1, code),
+        TraceEnd::LeftFunction(pc) => (2, pc),
+        TraceEnd::Unparsed(_) => (3, 0),
+        TraceEnd::Failed(_) => (4, 0),
+        TraceEnd::StepLimit(n) => (5, n as u64),
+    };
+    out.push(kind);
+    out.extend_from_slice(&value.to_le_bytes());
+    out
+}
+
+/// 按指定格式导出
+pub fn export(function: &str, trace: &Trace, format: TraceFormat) -> Vec<u8> {
+    match format {
+        TraceFormat::Json => to_json(function, trace).into_bytes(),
+        TraceFormat::Binary => to_binary(function, trace),
+    }
+}
+
+/// 寄存器名称对应的二进制编号
+fn register_id(name: &str) -> u8 {
+    match name {
+        "SP" => 31,
+        "NZCV" => 32,
+        _ => name
+            .strip_prefix('X')
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(u8::MAX),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::{StateChange, TraceStep};
+
+    #[test]
+    fn test_export_trace() {
+        let trace = Trace {
+            steps: vec![TraceStep {
+                address: 0x400000,
+                asm_instruction: String::from("add x0, x0, x1"),
+                changes: vec![StateChange { name: String::from("X0"), old: 1, new: 3 }],
+                accesses: Vec::new(),
+                syscall: None,
+                nzcv: 0,
+                skipped_call: false,
+            }],
+            end: TraceEnd::Returned(3),
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&to_json("sum", &trace)).unwrap();
+        assert_eq!(json["function"], "sum");
+        assert_eq!(json["steps"][0]["changes"][0]["new"], 3);
+        assert_eq!(json["end"]["kind"], "returned");
+
+        let bytes = to_binary("sum", &trace);
+        assert_eq!(&bytes[..4], BINARY_MAGIC);
+        assert_eq!(&bytes[7..10], b"sum");
+        // 头部 14 + 步骤 (10 + 1 + 17 + 1) + 结束原因 9
+        assert_eq!(bytes.len(), 14 + 29 + 9);
+        assert_eq!(bytes[14 + 11], 0);
+        assert_eq!(TraceFormat::from_path(std::path::Path::new("t.json")), TraceFormat::Json);
+    }
+}
@@ -0,0 +1,409 @@
+//! 全屏终端界面
+//!
+//! 左侧为函数列表，右侧为带语义解释的反汇编，顶部标签页切换优化级别。
+//! 反汇编可以滚动，也可以用 `/` 在函数内搜索，弥补逐行交互模式无法浏览长函数的不足。
+
+use crate::locale::Lang;
+use crate::objdump::{DumpEntry, ObjdumpParser};
+use crate::table::TableGenerator;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListState, Paragraph, Tabs};
+use ratatui::{DefaultTerminal, Frame};
+
+/// 反汇编窗格中的一行
+#[derive(Debug, Clone, PartialEq)]
+enum Row {
+    /// C 源代码
+    Source(String),
+    /// 提示/备注
+    Hint(String),
+    /// 指令：地址、助记符、操作数、语义解释
+    Instruction {
+        address: String,
+        mnemonic: String,
+        operands: String,
+        semantic: String,
+    },
+}
+
+impl Row {
+    /// 搜索时匹配的文本
+    fn text(&self) -> String {
+        match self {
+            Row::Source(text) | Row::Hint(text) => text.clone(),
+            Row::Instruction { address, mnemonic, operands, semantic } => {
+                format!("{} {} {} {}", address, mnemonic, operands, semantic)
+            }
+        }
+    }
+}
+
+/// 当前接收按键的窗格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Functions,
+    Code,
+}
+
+/// 界面状态
+pub struct App {
+    /// 各级别的名称和 dump 文件
+    levels: Vec<(String, ObjdumpParser)>,
+    /// 函数列表（所有级别的并集，按名称排序）
+    functions: Vec<String>,
+    generator: TableGenerator,
+    lang: Lang,
+    function_state: ListState,
+    level: usize,
+    focus: Focus,
+    rows: Vec<Row>,
+    cursor: usize,
+    offset: usize,
+    /// 反汇编窗格的可见行数（绘制时更新）
+    height: usize,
+    /// 正在输入的搜索词（为 None 时不在搜索状态）
+    input: Option<String>,
+    /// 最近一次搜索的关键字
+    query: String,
+    status: String,
+}
+
+impl App {
+    /// 从各级别的 dump 文件创建界面状态
+    pub fn new(levels: Vec<(String, ObjdumpParser)>, lang: Lang) -> crate::error::Result<Self> {
+        let mut functions = Vec::new();
+        for (_, parser) in &levels {
+            for name in parser.list_functions()? {
+                if !functions.contains(&name) {
+                    functions.push(name);
+                }
+            }
+        }
+        functions.sort();
+
+        let mut app = Self {
+            levels,
+            functions,
+            generator: TableGenerator::new().with_language(lang),
+            lang,
+            function_state: ListState::default(),
+            level: 0,
+            focus: Focus::Functions,
+            rows: Vec::new(),
+            cursor: 0,
+            offset: 0,
+            height: 0,
+            input: None,
+            query: String::new(),
+            status: String::new(),
+        };
+        if !app.functions.is_empty() {
+            app.function_state.select(Some(0));
+        }
+        app.load();
+        Ok(app)
+    }
+
+    /// 运行界面直到按下 q
+    pub fn run(mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.handle_key(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// 当前选中的函数
+    fn function(&self) -> Option<&str> {
+        self.function_state.selected().and_then(|i| self.functions.get(i)).map(String::as_str)
+    }
+
+    /// 重新生成当前函数在当前级别的反汇编行
+    fn load(&mut self) {
+        self.cursor = 0;
+        self.offset = 0;
+        self.rows.clear();
+        let (Some(function), Some((level, parser))) = (self.function(), self.levels.get(self.level)) else {
+            return;
+        };
+        match parser.extract_function_data(function) {
+            Ok(entries) => {
+                self.rows = rows(&self.generator, &entries);
+                self.status.clear();
+            }
+            Err(_) => {
+                self.status = match self.lang {
+                    Lang::Zh => format!("{} 中没有函数 {}", level, function),
+                    Lang::En => format!("{} has no function {}", level, function),
+                };
+            }
+        }
+    }
+
+    /// 处理按键，返回 false 表示退出
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        if let Some(input) = self.input.as_mut() {
+            match code {
+                KeyCode::Enter => {
+                    self.query = self.input.take().unwrap_or_default();
+                    self.search_next(false);
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Tab | KeyCode::Enter => {
+                self.focus = if self.focus == Focus::Functions { Focus::Code } else { Focus::Functions };
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.switch_level(self.level + self.levels.len() - 1),
+            KeyCode::Right | KeyCode::Char('l') => self.switch_level(self.level + 1),
+            KeyCode::Char(c @ '1'..='9') => self.switch_level(c as usize - '1' as usize),
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-(self.height.max(1) as isize)),
+            KeyCode::PageDown => self.move_by(self.height.max(1) as isize),
+            KeyCode::Home | KeyCode::Char('g') => self.move_by(isize::MIN / 2),
+            KeyCode::End | KeyCode::Char('G') => self.move_by(isize::MAX / 2),
+            KeyCode::Char('/') => {
+                self.focus = Focus::Code;
+                self.input = Some(String::new());
+            }
+            KeyCode::Char('n') => self.search_next(true),
+            _ => {}
+        }
+        true
+    }
+
+    /// 切换优化级别（超出范围时循环）
+    fn switch_level(&mut self, level: usize) {
+        if self.levels.is_empty() || level >= self.levels.len() * 2 {
+            return;
+        }
+        self.level = level % self.levels.len();
+        self.load();
+    }
+
+    /// 在当前窗格中移动光标
+    fn move_by(&mut self, delta: isize) {
+        let clamp = |current: usize, len: usize| (current as isize).saturating_add(delta).clamp(0, len.saturating_sub(1) as isize) as usize;
+        match self.focus {
+            Focus::Functions if !self.functions.is_empty() => {
+                let current = self.function_state.selected().unwrap_or(0);
+                let next = clamp(current, self.functions.len());
+                if next != current {
+                    self.function_state.select(Some(next));
+                    self.load();
+                }
+            }
+            Focus::Code if !self.rows.is_empty() => {
+                self.cursor = clamp(self.cursor, self.rows.len());
+                self.scroll_to_cursor();
+            }
+            _ => {}
+        }
+    }
+
+    /// 从光标处向下查找包含搜索词的行（不区分大小写，到末尾后从头继续）
+    fn search_next(&mut self, skip_current: bool) {
+        if self.query.is_empty() || self.rows.is_empty() {
+            return;
+        }
+        let query = self.query.to_lowercase();
+        let start = self.cursor + usize::from(skip_current);
+        let found = (0..self.rows.len())
+            .map(|i| (start + i) % self.rows.len())
+            .find(|i| self.rows[*i].text().to_lowercase().contains(&query));
+        match found {
+            Some(i) => {
+                self.cursor = i;
+                self.scroll_to_cursor();
+                self.status.clear();
+            }
+            None => {
+                self.status = match self.lang {
+                    Lang::Zh => format!("未找到: {}", self.query),
+                    Lang::En => format!("not found: {}", self.query),
+                };
+            }
+        }
+    }
+
+    /// 滚动反汇编窗格使光标可见
+    fn scroll_to_cursor(&mut self) {
+        let height = self.height.max(1);
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs_area, main_area, status_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, code_area] =
+            Layout::horizontal([Constraint::Percentage(25), Constraint::Percentage(75)]).areas(main_area);
+
+        let titles: Vec<String> = self
+            .levels
+            .iter()
+            .map(|(level, _)| crate::levels::title_in(level, self.lang))
+            .collect();
+        let tabs = Tabs::new(titles)
+            .select(self.level)
+            .block(Block::bordered().title(self.lang.pick(" 优化级别 ", " Optimization level ")))
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        frame.render_widget(tabs, tabs_area);
+
+        let current = self.focus;
+        let border = |focus: Focus| {
+            if current == focus { Style::default().fg(Color::Cyan) } else { Style::default() }
+        };
+        let list = List::new(self.functions.iter().map(String::as_str))
+            .block(Block::bordered().title(self.lang.pick(" 函数 ", " Functions ")).border_style(border(Focus::Functions)))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.function_state);
+
+        self.height = code_area.height.saturating_sub(2) as usize;
+        self.scroll_to_cursor();
+        let lines: Vec<Line> = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(self.height)
+            .map(|(i, row)| {
+                let line = row_line(row);
+                if i == self.cursor && self.focus == Focus::Code {
+                    line.add_modifier(Modifier::REVERSED)
+                } else {
+                    line
+                }
+            })
+            .collect();
+        let title = format!(" {} ", self.function().unwrap_or(""));
+        let code = Paragraph::new(lines).block(Block::bordered().title(title).border_style(border(Focus::Code)));
+        frame.render_widget(code, code_area);
+
+        let status = match (&self.input, self.status.is_empty()) {
+            (Some(input), _) => Line::from(format!("/{}", input)),
+            (None, false) => Line::from(self.status.as_str()).yellow(),
+            (None, true) => Line::from(self.lang.pick(
+                "q 退出  Tab 切换窗格  ←/→ 切换级别  ↑/↓ PgUp/PgDn 滚动  / 搜索  n 下一个",
+                "q quit  Tab switch pane  ←/→ level  ↑/↓ PgUp/PgDn scroll  / search  n next",
+            ))
+            .dim(),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+}
+
+/// 把函数条目转换为反汇编行：C 代码变化时先插入一行源代码
+fn rows(generator: &TableGenerator, entries: &[DumpEntry]) -> Vec<Row> {
+    let semantics = generator.semantic_column(entries);
+    let mut rows = Vec::new();
+    let mut current_c_code = "";
+    for (entry, semantic) in entries.iter().zip(semantics) {
+        if entry.asm_instruction.is_empty() {
+            rows.push(Row::Hint(entry.c_code.clone()));
+            continue;
+        }
+        if !entry.c_code.is_empty() && entry.c_code != current_c_code {
+            current_c_code = &entry.c_code;
+            rows.push(Row::Source(entry.c_code.clone()));
+        }
+        let asm = entry.asm_instruction.split("//").next().unwrap_or("").trim().replace('\t', " ");
+        let (mnemonic, operands) = asm.split_once(' ').unwrap_or((&asm, ""));
+        rows.push(Row::Instruction {
+            address: entry.address.clone(),
+            mnemonic: mnemonic.to_string(),
+            operands: operands.trim().to_string(),
+            semantic,
+        });
+    }
+    rows
+}
+
+/// 一行的显示样式：地址暗色、助记符青色、语义解释绿色、C 代码黄色
+fn row_line(row: &Row) -> Line<'_> {
+    match row {
+        Row::Source(text) => Line::from(format!("// {}", text)).yellow(),
+        Row::Hint(text) => Line::from(text.as_str()).dim(),
+        Row::Instruction { address, mnemonic, operands, semantic } => Line::from(vec![
+            Span::from(format!("{:>8}  ", address)).dim(),
+            Span::from(format!("{:<8}", mnemonic)).cyan(),
+            Span::from(format!("{:<28}", operands)),
+            Span::from(semantic.as_str()).green(),
+        ]),
+    }
+}
+
+/// 打开全屏界面浏览 `<PREFIX>_<级别>.dump`
+pub fn run(prefix: &str, levels: &[String], lang: Lang) -> anyhow::Result<()> {
+    let prefix = crate::levels::clean_prefix(prefix);
+    let levels = if levels.is_empty() { crate::levels::discover(&prefix) } else { levels.to_vec() };
+    if levels.is_empty() {
+        anyhow::bail!("未找到 {}_<级别>.dump 文件", prefix);
+    }
+    let parsers = levels.iter().cloned().zip(crate::levels::load_all(&prefix, &levels)?).collect();
+    let app = App::new(parsers, lang)?;
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"
+0000000000000000 <sum>:
+    int s = 0;
+   0:   52800002    mov w2, #0x0
+    s += a[i];
+   4:   b8404403    ldr w3, [x0], #4
+   8:   0b030042    add w2, w2, w3
+   c:   d65f03c0    ret
+
+0000000000000010 <main>:
+  10:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_navigation_and_search() {
+        let levels = vec![(String::from("O2"), ObjdumpParser::new(DUMP.to_string()))];
+        let mut app = App::new(levels, Lang::Zh).unwrap();
+        assert_eq!(app.functions, vec!["main", "sum"]);
+        assert_eq!(app.rows.len(), 1);
+
+        app.handle_key(KeyCode::Down);
+        assert_eq!(app.function(), Some("sum"));
+        assert_eq!(app.rows.len(), 6);
+        assert_eq!(app.rows[0], Row::Source(String::from("int s = 0;")));
+
+        app.height = 3;
+        for code in [KeyCode::Char('/'), KeyCode::Char('a'), KeyCode::Char('d'), KeyCode::Char('d'), KeyCode::Enter] {
+            app.handle_key(code);
+        }
+        assert_eq!(app.cursor, 4);
+        assert_eq!(app.offset, 2);
+        assert!(matches!(&app.rows[app.cursor], Row::Instruction { mnemonic, .. } if mnemonic == "add"));
+        assert!(!app.handle_key(KeyCode::Char('q')));
+    }
+}
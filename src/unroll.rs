@@ -0,0 +1,266 @@
+//! 循环展开检测
+//!
+//! 启发式地找出展开过的循环：循环体内按固定周期重复的同形指令（忽略寄存器编号和立即数），
+//! 且其中的访存偏移以同一个基址寄存器按固定步长递增（`ldr w3, [x0]`、`ldr w4, [x0, #4]`……）。
+//! `sp`/`x29` 上的访存是栈帧槽位（-O0 代码的局部变量），不作为展开的证据。
+//! 重复的次数即估计的展开倍数；编译器把各份交错调度时周期为 1，照原样复制时周期为一份循环体的长度。
+
+use crate::analysis;
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// 寄存器编号（`w12`、`v3.4s` 中的 `3`）
+fn register_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\b([wxvqsdhbz])\d+\b").unwrap())
+}
+
+/// 立即数（`#4`、`#0x10`、`#-8`）
+fn immediate_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"#-?(?:0x[0-9a-f]+|\d+)").unwrap())
+}
+
+/// 内存操作数（`[x0]`、`[x0, #8]`、`[x0, #8]!`），捕获基址寄存器和偏移
+fn memory_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\[\s*(\w+)\s*(?:,\s*#(-?(?:0x[0-9a-f]+|\d+)))?\s*\]").unwrap())
+}
+
+/// 一个展开过的循环
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnrolledLoop {
+    /// 循环头地址
+    pub head: u64,
+    /// 跳回循环头的分支地址
+    pub latch: u64,
+    /// 估计的展开倍数
+    pub factor: usize,
+    /// 相邻两份之间访存偏移的步长（字节）
+    pub stride: i64,
+}
+
+/// 循环体内的一条指令：形状（去掉寄存器编号和立即数）和内存操作数的基址与偏移
+struct Shape {
+    pattern: String,
+    access: Option<(String, i64)>,
+}
+
+impl Shape {
+    fn of(asm_instruction: &str) -> Self {
+        let asm = asm_instruction.split("//").next().unwrap_or("").trim().to_lowercase();
+        // 偏移不计入形状：`[x0]` 与 `[x0, #4]` 是同一条指令的两份
+        let access = memory_pattern()
+            .captures(&asm)
+            .filter(|caps| !matches!(&caps[1], "sp" | "x29" | "fp"))
+            .and_then(|caps| {
+                let offset = caps.get(2).map_or(Some(0), |imm| parse_immediate(imm.as_str()))?;
+                Some((caps[1].to_string(), offset))
+            });
+        let pattern = memory_pattern().replace_all(&asm, "[$1]");
+        let pattern = register_pattern().replace_all(&pattern, "$1");
+        let pattern = immediate_pattern().replace_all(&pattern, "#").split_whitespace().collect::<Vec<_>>().join(" ");
+        Self { pattern, access }
+    }
+}
+
+fn parse_immediate(text: &str) -> Option<i64> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None => digits.parse().ok()?,
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// 找出函数中展开过的循环（按循环头地址排序，每个循环头只报告一次）
+pub fn detect(entries: &[DumpEntry]) -> Vec<UnrolledLoop> {
+    let rows: Vec<(u64, &DumpEntry)> = entries
+        .iter()
+        .filter(|e| !e.asm_instruction.is_empty())
+        .filter_map(|e| Some((u64::from_str_radix(&e.address, 16).ok()?, e)))
+        .collect();
+    // 循环体中的指令可能无法解析，按向后跳转覆盖的地址范围取循环
+    let mut loops: Vec<(u64, u64)> = entries
+        .iter()
+        .filter_map(|e| e.parsed_instruction.as_ref())
+        .filter(|inst| analysis::is_branch(inst))
+        .filter_map(|inst| analysis::branch_target(inst).filter(|target| *target <= inst.address).map(|t| (t, inst.address)))
+        .collect();
+    loops.sort_unstable();
+    loops.dedup();
+
+    let mut detected: Vec<UnrolledLoop> = Vec::new();
+    for (head, latch) in loops {
+        let body: Vec<Shape> = rows
+            .iter()
+            .filter(|(address, _)| (head..=latch).contains(address))
+            .map(|(_, e)| Shape::of(&e.asm_instruction))
+            .collect();
+        let Some((factor, stride)) = repetition(&body) else {
+            continue;
+        };
+        // 同一个循环头有多条回边（如 continue）时保留展开倍数最大的一条
+        match detected.last_mut() {
+            Some(last) if last.head == head => {
+                if factor > last.factor {
+                    *last = UnrolledLoop { head, latch, factor, stride };
+                }
+            }
+            _ => detected.push(UnrolledLoop { head, latch, factor, stride }),
+        }
+    }
+    detected
+}
+
+/// 循环体中重复次数最多的同形片段：返回（重复次数，访存偏移步长）
+fn repetition(body: &[Shape]) -> Option<(usize, i64)> {
+    let mut best: Option<(usize, i64)> = None;
+    for period in 1..=body.len() / 2 {
+        for start in 0..body.len() {
+            let same = |copy: usize| {
+                let end = start + (copy + 1) * period;
+                end <= body.len()
+                    && (0..period).all(|i| body[start + copy * period + i].pattern == body[start + i].pattern)
+            };
+            let copies = (1..).take_while(|copy| same(*copy)).count() + 1;
+            if copies < 2 || best.is_some_and(|(factor, _)| factor >= copies) {
+                continue;
+            }
+            // 至少一条访存指令在各份之间使用同一个基址寄存器，偏移按固定的非零步长递增
+            let stride = (0..period).find_map(|i| {
+                let accesses: Option<Vec<&(String, i64)>> =
+                    (0..copies).map(|copy| body[start + copy * period + i].access.as_ref()).collect();
+                let accesses = accesses?;
+                if accesses.iter().any(|(base, _)| *base != accesses[0].0) {
+                    return None;
+                }
+                let offsets: Vec<i64> = accesses.iter().map(|(_, offset)| *offset).collect();
+                let stride = offsets[1] - offsets[0];
+                (stride != 0 && offsets.windows(2).all(|w| w[1] - w[0] == stride)).then_some(stride)
+            });
+            if let Some(stride) = stride {
+                best = Some((copies, stride));
+            }
+        }
+    }
+    best
+}
+
+/// 汇总各级别的循环展开情况（Markdown 列表）；所有级别都没有展开的循环时返回 None
+pub fn summary(levels: &[(&str, &[DumpEntry])], lang: Lang) -> Option<String> {
+    let detected: Vec<(&str, Vec<UnrolledLoop>)> = levels.iter().map(|(level, entries)| (*level, detect(entries))).collect();
+    if detected.iter().all(|(_, loops)| loops.is_empty()) {
+        return None;
+    }
+
+    let mut output = String::new();
+    for (level, loops) in &detected {
+        if loops.is_empty() {
+            output.push_str(&match lang {
+                Lang::Zh => format!("- {}: 未发现循环展开\n", level),
+                Lang::En => format!("- {}: no unrolled loops\n", level),
+            });
+        }
+        for l in loops {
+            output.push_str(&match lang {
+                Lang::Zh => format!(
+                    "- {}: 循环 0x{:x}–0x{:x} 展开约 {} 次（各份访存偏移相差 {} 字节）\n",
+                    level, l.head, l.latch, l.factor, l.stride
+                ),
+                Lang::En => format!(
+                    "- {}: loop 0x{:x}–0x{:x} unrolled about {}× (memory offsets {} bytes apart)\n",
+                    level, l.head, l.latch, l.factor, l.stride
+                ),
+            });
+        }
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unrolled_loop() {
+        let o0 = r#"
+0000000000000000 <sum>:
+   0:   b8404403    ldr w3, [x0], #4
+   4:   0b030042    add w2, w2, w3
+   8:   eb01001f    cmp x0, x1
+   c:   54ffffa1    b.ne 0 <sum>
+  10:   d65f03c0    ret
+"#;
+        let o3 = r#"
+0000000000000000 <sum>:
+   0:   b9400003    ldr w3, [x0]
+   4:   b9400404    ldr w4, [x0, #4]
+   8:   b9400805    ldr w5, [x0, #8]
+   c:   b9400c06    ldr w6, [x0, #12]
+  10:   0b030042    add w2, w2, w3
+  14:   0b040042    add w2, w2, w4
+  18:   0b050042    add w2, w2, w5
+  1c:   0b060042    add w2, w2, w6
+  20:   91004000    add x0, x0, #0x10
+  24:   eb01001f    cmp x0, x1
+  28:   54fffec1    b.ne 0 <sum>
+  2c:   d65f03c0    ret
+"#;
+        // 两条回边跳到同一个循环头，只报告一次
+        let two_latches = o3.replace("24:   eb01001f    cmp x0, x1", "24:   54fffee0    b.eq 0 <sum>");
+        let o0 = crate::objdump::ObjdumpParser::new(o0.to_string()).extract_function_data("sum").unwrap();
+        let o3 = crate::objdump::ObjdumpParser::new(o3.to_string()).extract_function_data("sum").unwrap();
+        let two_latches = crate::objdump::ObjdumpParser::new(two_latches).extract_function_data("sum").unwrap();
+
+        assert!(detect(&o0).is_empty());
+        assert_eq!(detect(&o3), vec![UnrolledLoop { head: 0, latch: 0x28, factor: 4, stride: 4 }]);
+        assert_eq!(detect(&two_latches).len(), 1);
+
+        let levels: Vec<(&str, &[DumpEntry])> = vec![("O0", &o0), ("O3", &o3)];
+        let text = summary(&levels, Lang::Zh).unwrap();
+        assert_eq!(text, "- O0: 未发现循环展开\n- O3: 循环 0x0–0x28 展开约 4 次（各份访存偏移相差 4 字节）\n");
+        assert_eq!(summary(&levels[..1], Lang::Zh), None);
+    }
+
+    #[test]
+    fn test_frame_slots_are_not_unrolling() {
+        // -O0 的循环：局部变量都在 sp/x29 栈帧槽位上，形状重复但不是展开
+        let o0 = r#"
+0000000000000000 <sum>:
+   0:   b9001fff    str wzr, [sp, #28]
+   4:   14000008    b 24 <sum+0x24>
+   8:   b9401be1    ldr w1, [sp, #24]
+   c:   b94007e0    ldr w0, [sp, #4]
+  10:   0b000020    add w0, w1, w0
+  14:   b9001be0    str w0, [sp, #24]
+  18:   b9401fa0    ldr w0, [x29, #28]
+  1c:   b9401ba1    ldr w1, [x29, #24]
+  20:   11000400    add w0, w0, #0x1
+  24:   b9401fe1    ldr w1, [sp, #28]
+  28:   b94017e0    ldr w0, [sp, #20]
+  2c:   6b00003f    cmp w1, w0
+  30:   54fffecb    b.lt 8 <sum+0x8>
+  34:   d65f03c0    ret
+"#;
+        // 两份使用不同的基址寄存器，同样不算展开
+        let mixed = r#"
+0000000000000000 <mix>:
+   0:   b9400023    ldr w3, [x1]
+   4:   b9400444    ldr w4, [x2, #4]
+   8:   eb01001f    cmp x0, x1
+   c:   54ffffa1    b.ne 0 <mix>
+  10:   d65f03c0    ret
+"#;
+        let o0 = crate::objdump::ObjdumpParser::new(o0.to_string()).extract_function_data("sum").unwrap();
+        let mixed = crate::objdump::ObjdumpParser::new(mixed.to_string()).extract_function_data("mix").unwrap();
+        assert!(detect(&o0).is_empty());
+        assert!(detect(&mixed).is_empty());
+    }
+}
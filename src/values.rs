@@ -0,0 +1,260 @@
+//! 值追踪（常量传播）
+//!
+//! 轻量的抽象解释：沿指令顺序在 `mov`/`movz`/`movk`/`add`/`sub`/`lsl` 之间传播已知的值。
+//! 寄存器的值要么是常量，要么是另一个寄存器的线性函数（`X1 × 8 + 16`）。
+//! 报告据此标注 `movz`/`movk` 拼出的 64 位常量，或下标寄存器等于 `i × 8`。
+//! 其他写入寄存器的指令使其值变为未知；汇合点由调用方调用 [`ValueTracker::reset`]。
+
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::locale::Lang;
+use crate::register::Register;
+use std::collections::HashMap;
+use std::fmt;
+
+/// 寄存器的抽象值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    /// 已知常量（按目标寄存器的宽度截断）
+    Const(u64),
+    /// `base × scale + offset`，`base` 为该寄存器在被追踪时的值
+    Linear {
+        base: Register,
+        scale: i64,
+        offset: i64,
+    },
+}
+
+impl Value {
+    fn add(self, other: Value) -> Option<Value> {
+        use Value::*;
+        Some(match (self, other) {
+            (Const(a), Const(b)) => Const(a.wrapping_add(b)),
+            (Linear { base, scale, offset }, Const(c)) | (Const(c), Linear { base, scale, offset }) => {
+                Linear { base, scale, offset: offset.checked_add(c as i64)? }
+            }
+            (Linear { base, scale, offset }, Linear { base: other, scale: s, offset: o }) if base.index() == other.index() => {
+                // 基相互抵消时结果是常量
+                match scale.checked_add(s)? {
+                    0 => Const(offset.checked_add(o)? as u64),
+                    scale => Linear { base, scale, offset: offset.checked_add(o)? },
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    fn neg(self) -> Option<Value> {
+        Some(match self {
+            Value::Const(c) => Value::Const(c.wrapping_neg()),
+            Value::Linear { base, scale, offset } => Value::Linear { base, scale: scale.checked_neg()?, offset: offset.checked_neg()? },
+        })
+    }
+
+    fn shl(self, amount: u32) -> Option<Value> {
+        let factor = 1i64.checked_shl(amount).filter(|f| *f > 0)?;
+        Some(match self {
+            Value::Const(c) => Value::Const(c.checked_shl(amount)?),
+            Value::Linear { base, scale, offset } => Value::Linear { base, scale: scale.checked_mul(factor)?, offset: offset.checked_mul(factor)? },
+        })
+    }
+
+    /// 写入 W 寄存器时常量截断为 32 位；线性值无法截断，不再追踪
+    fn truncate(self, dest: Register) -> Option<Value> {
+        match self {
+            _ if dest.is_64bit() => Some(self),
+            Value::Const(c) => Some(Value::Const(c & 0xffff_ffff)),
+            Value::Linear { scale: 1, offset: 0, .. } => Some(self),
+            Value::Linear { .. } => None,
+        }
+    }
+
+    /// 寄存器本身（未知但可以作为线性值的基）
+    fn is_identity(&self) -> bool {
+        matches!(self, Value::Linear { scale: 1, offset: 0, .. })
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Value::Const(c) => write!(f, "0x{:x}", c),
+            Value::Linear { base, scale, offset } => {
+                match scale {
+                    1 => write!(f, "{:?}", base)?,
+                    -1 => write!(f, "-{:?}", base)?,
+                    _ => write!(f, "{:?} × {}", base, scale)?,
+                }
+                match offset {
+                    0 => Ok(()),
+                    o if o < 0 => write!(f, " - {}", o.unsigned_abs()),
+                    o => write!(f, " + {}", o),
+                }
+            }
+        }
+    }
+}
+
+/// 一条指令写入的推导值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Derived {
+    /// 目标寄存器
+    pub register: Register,
+    /// 写入的值
+    pub value: Value,
+}
+
+impl Derived {
+    /// 报告中附加在语义解释后的标注
+    pub fn note(&self, lang: Lang) -> String {
+        match lang {
+            Lang::Zh => format!("（值: {:?} = {}）", self.register, self.value),
+            Lang::En => format!(" (value: {:?} = {})", self.register, self.value),
+        }
+    }
+}
+
+/// 按指令顺序追踪寄存器的值
+#[derive(Debug, Default)]
+pub struct ValueTracker {
+    values: HashMap<usize, Value>,
+}
+
+impl ValueTracker {
+    /// 创建空的追踪器（所有寄存器未知）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 所有寄存器的值变为未知（汇合点、无法解析的指令）
+    pub fn reset(&mut self) {
+        self.values.clear();
+    }
+
+    /// 寄存器当前已知的值
+    pub fn value(&self, reg: Register) -> Option<Value> {
+        match reg {
+            Register::XZR | Register::WZR => Some(Value::Const(0)),
+            _ => self.values.get(&reg.index()?).copied(),
+        }
+    }
+
+    /// 执行一条指令；写入的值依赖前面的指令（或把移位化为乘法）时返回推导结果，
+    /// 单看这条指令就能读出的值返回 None
+    pub fn step(&mut self, inst: &Instruction) -> Option<Derived> {
+        let mut derived = false;
+        let result = self.evaluate(inst, &mut derived);
+
+        let (defs, _) = crate::analysis::def_use(inst);
+        self.values.retain(|reg, value| {
+            let base = match value {
+                Value::Linear { base, .. } => base.index(),
+                Value::Const(_) => None,
+            };
+            !defs.contains(*reg) && !base.is_some_and(|b| defs.contains(b))
+        });
+
+        let (register, value) = result?;
+        let index = register.index()?;
+        let self_based = matches!(value, Value::Linear { base, .. } if base.index() == Some(index));
+        if !self_based {
+            self.values.insert(index, value);
+        }
+        let scaled = matches!(value, Value::Linear { scale, .. } if scale != 1);
+        (derived || scaled).then_some(Derived { register, value })
+    }
+
+    /// 读取寄存器：已追踪的值记为推导，未知寄存器作为线性值的基
+    fn read(&self, reg: Register, derived: &mut bool) -> Option<Value> {
+        if let Some(value) = self.value(reg) {
+            *derived |= !matches!(reg, Register::XZR | Register::WZR) && !value.is_identity();
+            return Some(value);
+        }
+        reg.index()?;
+        Some(Value::Linear { base: reg, scale: 1, offset: 0 })
+    }
+
+    fn operand(&self, op: &Operand, derived: &mut bool) -> Option<Value> {
+        match op {
+            Operand::Register(reg) => self.read(*reg, derived),
+            Operand::Immediate(imm) => Some(Value::Const(*imm as u64)),
+            _ => None,
+        }
+    }
+
+    fn evaluate(&self, inst: &Instruction, derived: &mut bool) -> Option<(Register, Value)> {
+        use InstructionType::*;
+
+        let Some(Operand::Register(dest)) = inst.operands.first() else {
+            return None;
+        };
+        let ops = &inst.operands[1..];
+        let value = match (inst.instruction_type, ops) {
+            (MOV, [src]) => self.operand(src, derived)?,
+            (MOVZ, [Operand::Immediate(imm), rest @ ..]) => Value::Const(*imm as u64).shl(shift_amount(rest)?)?,
+            (MOVK, [Operand::Immediate(imm), rest @ ..]) => {
+                let Value::Const(old) = self.value(*dest)? else {
+                    return None;
+                };
+                *derived = true;
+                let shift = shift_amount(rest)?;
+                let mask = 0xffffu64.checked_shl(shift)?;
+                Value::Const((old & !mask) | ((*imm as u64 & 0xffff) << shift))
+            }
+            (ADD | SUB | ADDS | SUBS, [lhs, rhs, rest @ ..]) => {
+                let lhs = self.operand(lhs, derived)?;
+                let rhs = self.operand(rhs, derived)?.shl(shift_amount(rest)?)?;
+                let rhs = if matches!(inst.instruction_type, SUB | SUBS) { rhs.neg()? } else { rhs };
+                lhs.add(rhs)?
+            }
+            (LSL, [src, Operand::Immediate(amount)]) => self.operand(src, derived)?.shl(u32::try_from(*amount).ok()?)?,
+            _ => return None,
+        };
+        Some((*dest, value.truncate(*dest)?))
+    }
+}
+
+/// 可选的 `lsl #N` 移位（objdump 把它解析为标签操作数）；其他移位/扩展方式返回 None
+fn shift_amount(rest: &[Operand]) -> Option<u32> {
+    match rest {
+        [] => Some(0),
+        [Operand::Label(text)] => text.trim().strip_prefix("lsl")?.trim().trim_start_matches('#').parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str) -> Vec<Option<String>> {
+        let mut parser = crate::parser::AssemblyParser::new();
+        let mut tracker = ValueTracker::new();
+        parser
+            .parse(text)
+            .unwrap()
+            .iter()
+            .map(|inst| tracker.step(inst).map(|d| format!("{:?} = {}", d.register, d.value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_value_tracking() {
+        let notes = run("movz x0, #0xbeef, lsl #32\nmovk x0, #0xdead, lsl #48\nmov w1, #0x64\nadd w2, w1, #0x8");
+        assert_eq!(notes[0], None);
+        assert_eq!(notes[1].as_deref(), Some("X0 = 0xdeadbeef00000000"));
+        assert_eq!(notes[2], None);
+        assert_eq!(notes[3].as_deref(), Some("W2 = 0x6c"));
+
+        // 移位化为乘法，随后的加法在线性值上累加
+        let notes = run("lsl x2, x1, #3\nadd x2, x2, #0x10\nsub x3, x2, x1, lsl #3\nadd x4, x0, x2");
+        assert_eq!(notes[0].as_deref(), Some("X2 = X1 × 8"));
+        assert_eq!(notes[1].as_deref(), Some("X2 = X1 × 8 + 16"));
+        assert_eq!(notes[2].as_deref(), Some("X3 = 0x10"));
+        // 基不同的两个线性值无法相加
+        assert_eq!(notes[3], None);
+
+        // 基寄存器被改写后依赖它的值不再追踪
+        let notes = run("lsl x2, x1, #3\nmov x1, #0x0\nadd x3, x2, #0x1");
+        assert_eq!(notes[2], None);
+    }
+}
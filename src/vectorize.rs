@@ -0,0 +1,167 @@
+//! 向量化检测
+//!
+//! 找出函数在各优化级别中使用的 NEON/SVE 指令，判断它们是否位于循环内，
+//! 汇总成“O2 对此循环进行了向量化 (使用 ld1/fmla/st1)”这样的结论。
+//! 向量化是优化级别之间最值得注意的差异之一，不必再逐行用肉眼比对。
+
+use crate::analysis;
+use crate::locale::Lang;
+use crate::metrics::Category;
+use crate::objdump::DumpEntry;
+use serde::Serialize;
+
+/// 摘要中最多列出的助记符个数
+const MAX_MNEMONICS: usize = 6;
+
+/// 一个级别中 SIMD 指令的使用情况
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Vectorization {
+    /// SIMD 指令条数
+    pub instructions: usize,
+    /// 使用的 SIMD 助记符（去重，按首次出现的顺序）
+    pub mnemonics: Vec<String>,
+    /// 是否有 SIMD 指令位于循环内
+    pub in_loop: bool,
+    /// 是否使用 SVE（Z 寄存器、谓词）
+    pub sve: bool,
+}
+
+impl Vectorization {
+    /// 统计函数中的 SIMD 指令；没有 SIMD 指令时返回 None
+    pub fn detect(entries: &[DumpEntry]) -> Option<Self> {
+        // SIMD 指令（包括循环头）常常无法解析，循环按向后跳转覆盖的地址范围判断
+        let loops: Vec<(u64, u64)> = entries
+            .iter()
+            .filter_map(|e| e.parsed_instruction.as_ref())
+            .filter(|inst| analysis::is_branch(inst))
+            .filter_map(|inst| analysis::branch_target(inst).filter(|target| *target <= inst.address).map(|t| (t, inst.address)))
+            .collect();
+
+        let mut result = Self { instructions: 0, mnemonics: Vec::new(), in_loop: false, sve: false };
+        for entry in entries.iter().filter(|e| !e.asm_instruction.is_empty()) {
+            let Some(sve) = vector_kind(&entry.asm_instruction) else {
+                continue;
+            };
+            result.instructions += 1;
+            result.sve |= sve;
+            let mnemonic = entry.asm_instruction.split_whitespace().next().unwrap_or_default().to_lowercase();
+            if !result.mnemonics.contains(&mnemonic) {
+                result.mnemonics.push(mnemonic);
+            }
+            if let Ok(address) = u64::from_str_radix(&entry.address, 16) {
+                result.in_loop |= loops.iter().any(|(head, latch)| (*head..=*latch).contains(&address));
+            }
+        }
+        (result.instructions > 0).then_some(result)
+    }
+
+    /// 用于摘要的助记符列表（`ld1/fmla/st1`）
+    fn mnemonic_list(&self) -> String {
+        let mut list = self.mnemonics.iter().take(MAX_MNEMONICS).cloned().collect::<Vec<_>>().join("/");
+        if self.mnemonics.len() > MAX_MNEMONICS {
+            list.push_str("/…");
+        }
+        list
+    }
+}
+
+/// 指令是否为 SIMD 指令：不是时返回 None，是时返回是否为 SVE 指令
+fn vector_kind(asm_instruction: &str) -> Option<bool> {
+    let lower = asm_instruction.split("//").next().unwrap_or("").to_lowercase();
+    let mut parts = lower.split_whitespace();
+    let mnemonic = parts.next().unwrap_or("");
+    let operands: Vec<&str> = parts
+        .flat_map(|part| part.split(|c: char| !c.is_ascii_alphanumeric() && c != '.'))
+        .filter(|op| !op.is_empty())
+        .collect();
+    let numbered = |op: &str, prefix: char| {
+        op.strip_prefix(prefix)
+            .is_some_and(|rest| rest.split('.').next().is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())))
+    };
+
+    // Z 寄存器（z0.s）、谓词寄存器（p0）和谓词生成指令属于 SVE
+    let sve = mnemonic.starts_with("while")
+        || mnemonic == "ptrue"
+        || operands.iter().any(|op| numbered(op, 'z') && op.contains('.'));
+    if sve {
+        return Some(true);
+    }
+    // 带排列说明符的 V 寄存器和 128 位的 Q 寄存器属于 NEON
+    let neon = Category::of(asm_instruction) == Category::Simd || operands.iter().any(|op| numbered(op, 'q'));
+    neon.then_some(false)
+}
+
+/// 汇总各级别的向量化情况（Markdown 列表）；所有级别都没有 SIMD 指令时返回 None
+pub fn summary(levels: &[(&str, &[DumpEntry])], lang: Lang) -> Option<String> {
+    let detected: Vec<(&str, Option<Vectorization>)> =
+        levels.iter().map(|(level, entries)| (*level, Vectorization::detect(entries))).collect();
+    if detected.iter().all(|(_, v)| v.is_none()) {
+        return None;
+    }
+
+    let mut output = String::new();
+    for (level, vectorization) in &detected {
+        let line = match (vectorization, lang) {
+            (None, Lang::Zh) => format!("{} 未使用 SIMD 指令", level),
+            (None, Lang::En) => format!("{} uses no SIMD instructions", level),
+            (Some(v), _) => {
+                let kind = if v.sve { "SVE" } else { "NEON" };
+                let list = v.mnemonic_list();
+                match (v.in_loop, lang) {
+                    (true, Lang::Zh) => format!("{} 对此循环进行了向量化 ({}，使用 {})", level, kind, list),
+                    (false, Lang::Zh) => format!("{} 使用了 {} 条 SIMD 指令 ({}，使用 {})，但不在循环中", level, v.instructions, kind, list),
+                    (true, Lang::En) => format!("{} vectorized this loop ({}, using {})", level, kind, list),
+                    (false, Lang::En) => format!("{} uses {} SIMD instructions ({}, using {}) outside loops", level, v.instructions, kind, list),
+                }
+            }
+        };
+        output.push_str(&format!("- {}\n", line));
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vectorization() {
+        let o0 = r#"
+0000000000000000 <scale>:
+   0:   bd400000    ldr s0, [x0]
+   4:   1e220800    fmul s0, s0, s2
+   8:   bd000000    str s0, [x0]
+   c:   91001000    add x0, x0, #0x4
+  10:   eb01001f    cmp x0, x1
+  14:   54ffff61    b.ne 0 <scale>
+  18:   d65f03c0    ret
+"#;
+        let o2 = r#"
+0000000000000000 <scale>:
+   0:   4c407800    ld1 {v0.4s}, [x0]
+   4:   6e22dc00    fmul v0.4s, v0.4s, v2.4s
+   8:   4c9f7800    st1 {v0.4s}, [x0], #16
+   c:   eb01001f    cmp x0, x1
+  10:   54ffff81    b.ne 0 <scale>
+  14:   3dc00001    ldr q1, [x0]
+  18:   d65f03c0    ret
+"#;
+        let o0 = crate::objdump::ObjdumpParser::new(o0.to_string()).extract_function_data("scale").unwrap();
+        let o2 = crate::objdump::ObjdumpParser::new(o2.to_string()).extract_function_data("scale").unwrap();
+
+        assert_eq!(Vectorization::detect(&o0), None);
+        let v = Vectorization::detect(&o2).unwrap();
+        assert_eq!(v.mnemonics, ["ld1", "fmul", "st1", "ldr"]);
+        assert!(v.in_loop && !v.sve);
+
+        let levels: Vec<(&str, &[DumpEntry])> = vec![("O0", &o0), ("O2", &o2)];
+        let text = summary(&levels, Lang::Zh).unwrap();
+        assert_eq!(text, "- O0 未使用 SIMD 指令\n- O2 对此循环进行了向量化 (NEON，使用 ld1/fmul/st1/ldr)\n");
+        assert_eq!(summary(&levels[..1], Lang::Zh), None);
+
+        assert_eq!(vector_kind("ld1w {z0.s}, p0/z, [x0, x2, lsl #2]"), Some(true));
+        assert_eq!(vector_kind("whilelo p0.s, x2, x1"), Some(true));
+        assert_eq!(vector_kind("fadd d0, d0, d1"), None);
+        assert_eq!(vector_kind("ldr x0, [x1]"), None);
+    }
+}
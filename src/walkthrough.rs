@@ -0,0 +1,255 @@
+//! 函数导读
+//!
+//! 综合栈帧识别、参数寄存器（调用约定）、循环检测和常见模式识别，为函数生成一段
+//! 按执行顺序叙述的导读（"函数首先建立栈帧…然后循环遍历数组…最后返回结果"），
+//! 放在指令表格之前，帮助初学者先把握函数的整体结构再看逐条指令。
+
+use crate::analysis::{self, LivenessAnalysis, Loop, RegSet};
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::register::Register;
+
+/// 为函数生成导读，函数中没有可解析的指令时返回 None
+pub fn generate(entries: &[DumpEntry], lang: Lang) -> Option<String> {
+    let entries: Vec<&DumpEntry> = entries.iter().filter(|e| e.parsed_instruction.is_some()).collect();
+    let instructions: Vec<Instruction> = entries
+        .iter()
+        .filter_map(|e| e.parsed_instruction.clone())
+        .collect();
+    if instructions.is_empty() {
+        return None;
+    }
+
+    let liveness = LivenessAnalysis::compute(&instructions);
+    let loops = analysis::find_loops(&instructions);
+    let mut clauses = Vec::new();
+
+    clauses.push(describe_frame(&instructions, lang));
+    clauses.push(describe_parameters(&instructions, &liveness, lang));
+    for l in outermost(&loops) {
+        clauses.push(describe_loop(&entries, &instructions, l, lang));
+    }
+
+    let branches = instructions
+        .iter()
+        .enumerate()
+        .filter(|(i, inst)| {
+            is_conditional(inst) && !loops.iter().any(|l| l.contains(*i))
+        })
+        .count();
+    if branches > 0 {
+        clauses.push(match lang {
+            Lang::Zh => format!("根据 {} 处条件判断选择不同的执行路径", branches),
+            Lang::En => format!("chooses between execution paths at {} conditional branches", branches),
+        });
+    }
+
+    let calls: Vec<String> = entries
+        .iter()
+        .filter(|e| e.asm_instruction.starts_with("bl"))
+        .filter_map(|e| e.call_target())
+        .map(|name| name.trim_end_matches("@plt").to_string())
+        .fold(Vec::new(), |mut names, name| {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+            names
+        });
+    if !calls.is_empty() {
+        clauses.push(format!("{} {}", lang.pick("调用", "calls"), calls.join(lang.pick("、", ", "))));
+    }
+    if instructions.iter().any(|i| i.instruction_type == InstructionType::BLR) {
+        clauses.push(lang.pick("通过函数指针进行间接调用", "makes an indirect call through a function pointer").to_string());
+    }
+
+    let (first, middle) = clauses.split_first()?;
+    let mut text = format!("{}{}", lang.pick("函数首先", "The function first "), first);
+    for (i, clause) in middle.iter().enumerate() {
+        text.push_str(match (i, lang) {
+            (0, Lang::Zh) => "；然后",
+            (_, Lang::Zh) => "；接着",
+            (0, Lang::En) => "; then ",
+            (_, Lang::En) => "; next ",
+        });
+        text.push_str(clause);
+    }
+    let exit = describe_exit(&entries, &instructions, &liveness, lang);
+    text.push_str(&match lang {
+        Lang::Zh => format!("；最后{}。", exit),
+        Lang::En => format!("; finally {}.", exit),
+    });
+    Some(text)
+}
+
+/// 描述栈帧的建立
+fn describe_frame(instructions: &[Instruction], lang: Lang) -> String {
+    let mut parts = Vec::new();
+    let mut saved = RegSet::new();
+
+    // 序言在第一个分支之前
+    for inst in instructions.iter().take_while(|i| !is_conditional(i) && i.instruction_type != InstructionType::B) {
+        match (inst.instruction_type, inst.operands.as_slice()) {
+            (InstructionType::STP, [Operand::Register(Register::X29), Operand::Register(Register::X30), Operand::Memory { base: Register::SP, offset: Some(offset), pre_indexed: true, .. }]) => {
+                parts.push(match lang {
+                    Lang::Zh => format!("建立 {} 字节的栈帧，保存帧指针 x29 和返回地址 x30", -offset),
+                    Lang::En => format!("sets up a {}-byte stack frame, saving frame pointer x29 and return address x30", -offset),
+                });
+            }
+            (InstructionType::SUB, [Operand::Register(Register::SP), Operand::Register(Register::SP), Operand::Immediate(size), ..]) => {
+                parts.push(match lang {
+                    Lang::Zh => format!("在栈上分配 {} 字节存放局部变量", size),
+                    Lang::En => format!("allocates {} bytes on the stack for local variables", size),
+                });
+            }
+            (InstructionType::STP | InstructionType::STR, ops) => {
+                for op in ops {
+                    if let Operand::Register(reg) = op {
+                        if let Some(index) = reg.index() {
+                            if (analysis::FIRST_CALLEE_SAVED..=analysis::LAST_CALLEE_SAVED).contains(&index) {
+                                saved.insert(index);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !saved.is_empty() {
+        parts.push(format!("{} {}", lang.pick("保存被调用者保存寄存器", "saves callee-saved registers"), saved));
+    }
+    if parts.is_empty() {
+        return lang.pick("不建立栈帧，直接在寄存器中计算", "computes directly in registers without a stack frame").to_string();
+    }
+    parts.join(lang.pick("，", ", "))
+}
+
+/// 描述参数寄存器
+fn describe_parameters(instructions: &[Instruction], liveness: &LivenessAnalysis, lang: Lang) -> String {
+    let params: Vec<String> = analysis::parameter_registers(instructions, liveness)
+        .iter()
+        .map(|r| format!("x{}", r))
+        .collect();
+    match (params.is_empty(), lang) {
+        (true, _) => lang.pick("不使用参数寄存器", "uses no argument registers").to_string(),
+        (false, Lang::Zh) => format!("从 {} 中取得参数", params.join("、")),
+        (false, Lang::En) => format!("takes its arguments from {}", params.join(", ")),
+    }
+}
+
+/// 描述循环的作用
+fn describe_loop(entries: &[&DumpEntry], instructions: &[Instruction], l: &Loop, lang: Lang) -> String {
+    use InstructionType::*;
+
+    let body = &instructions[l.head..=l.latch];
+    let loads = body.iter().any(|i| matches!(i.instruction_type, LDR | LDRB | LDRH | LDRSB | LDRSH | LDRSW | LDP | LDUR | LD1));
+    let stores = body.iter().any(|i| matches!(i.instruction_type, STR | STRB | STRH | STP | STUR | ST1));
+    // 目标寄存器同时也是源操作数的加法视为累加
+    let accumulates = body.iter().any(|i| {
+        matches!(i.instruction_type, ADD | FADD | MADD | FMADD)
+            && matches!(i.operands.first(), Some(Operand::Register(dst)) if i.operands[1..].contains(&Operand::Register(*dst)))
+            && i.operands.iter().skip(1).all(|op| !matches!(op, Operand::Immediate(_)))
+    });
+
+    let action = match (loads, stores, accumulates) {
+        (true, _, true) => lang.pick("循环遍历数组并累加", "loops over an array, accumulating"),
+        (true, true, false) => lang.pick("循环读取并写回内存", "loops reading and writing back memory"),
+        (false, true, _) => lang.pick("循环写入内存", "loops writing to memory"),
+        (true, false, false) => lang.pick("循环遍历数组", "loops over an array"),
+        (false, false, _) => lang.pick("循环计算", "loops computing"),
+    };
+    let latch = entries[l.latch].asm_instruction.split_whitespace().next().unwrap_or("");
+    let (head, tail) = (instructions[l.head].address, instructions[l.latch].address);
+    match lang {
+        Lang::Zh => format!(
+            "在 0x{:x} - 0x{:x} 之间{}（由 {} 控制，循环体 {} 条指令）",
+            head, tail, action, latch, body.len()
+        ),
+        Lang::En => format!(
+            "{} between 0x{:x} and 0x{:x} (controlled by {}, {} instructions in the body)",
+            action, head, tail, latch, body.len()
+        ),
+    }
+}
+
+/// 描述函数的出口
+fn describe_exit(entries: &[&DumpEntry], instructions: &[Instruction], liveness: &LivenessAnalysis, lang: Lang) -> String {
+    let last = instructions.len() - 1;
+    if instructions[last].instruction_type == InstructionType::B {
+        if let Some(target) = entries[last].call_target() {
+            let target = target.trim_end_matches("@plt");
+            return match lang {
+                Lang::Zh => format!("尾调用 {}，由它直接返回到调用者", target),
+                Lang::En => format!("tail-calls {}, which returns directly to the caller", target),
+            };
+        }
+    }
+
+    let restores = instructions.iter().any(|i| {
+        i.instruction_type == InstructionType::LDP
+            && matches!(i.operands.first(), Some(Operand::Register(Register::X29)))
+    });
+    let returns_value = liveness.defined().contains(0);
+    let text = match (restores, returns_value) {
+        (true, true) => lang.pick("恢复栈帧，通过 x0 返回结果", "restores the stack frame and returns the result in x0"),
+        (true, false) => lang.pick("恢复栈帧后返回", "restores the stack frame and returns"),
+        (false, true) => lang.pick("通过 x0 返回结果", "returns the result in x0"),
+        (false, false) => lang.pick("返回", "returns"),
+    };
+    text.to_string()
+}
+
+/// 是否为条件分支
+fn is_conditional(inst: &Instruction) -> bool {
+    use InstructionType::*;
+    matches!(
+        inst.instruction_type,
+        BEQ | BNE | BCS | BCC | BMI | BPL | BVS | BVC | BHI | BLS | BGE | BLT | BGT | BLE
+            | CBZ | CBNZ | TBZ | TBNZ
+    ) || (inst.instruction_type == B && inst.condition.is_some())
+}
+
+/// 去掉嵌套在其他循环内部的循环
+fn outermost(loops: &[Loop]) -> Vec<&Loop> {
+    loops
+        .iter()
+        .filter(|l| !loops.iter().any(|o| o != *l && o.contains(l.head) && o.contains(l.latch)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    #[test]
+    fn test_walkthrough_narrative() {
+        let dump = r#"
+0000000000000000 <sum>:
+   0:   a9bf7bfd    stp x29, x30, [sp, #-16]!
+   4:   52800002    mov w2, #0x0
+   8:   b8404403    ldr w3, [x0], #4
+   c:   0b030042    add w2, w2, w3
+  10:   51000421    sub w1, w1, #0x1
+  14:   35ffffa1    cbnz w1, 8 <sum+0x8>
+  18:   2a0203e0    mov w0, w2
+  1c:   a8c17bfd    ldp x29, x30, [sp], #16
+  20:   d65f03c0    ret
+"#;
+        let entries = ObjdumpParser::new(dump.to_string()).extract_function_data("sum").unwrap();
+        let text = generate(&entries, Lang::Zh).unwrap();
+
+        assert!(text.starts_with("函数首先建立 16 字节的栈帧"));
+        assert!(text.contains("从 x0、x1 中取得参数"));
+        assert!(text.contains("在 0x8 - 0x14 之间循环遍历数组并累加（由 cbnz 控制"));
+        assert!(text.ends_with("最后恢复栈帧，通过 x0 返回结果。"));
+
+        let text = generate(&entries, Lang::En).unwrap();
+        assert!(text.starts_with("The function first sets up a 16-byte stack frame"));
+        assert!(text.contains("; then takes its arguments from x0, x1"));
+        assert!(text.contains("loops over an array, accumulating between 0x8 and 0x14 (controlled by cbnz"));
+        assert!(text.ends_with("; finally restores the stack frame and returns the result in x0."));
+    }
+}
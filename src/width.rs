@@ -0,0 +1,314 @@
+//! 操作数位宽检查
+//!
+//! C 的整数提升在汇编中表现为 W 和 X 寄存器之间的转换。本模块找出两类可疑之处：
+//! 写入 W 寄存器的 32 位结果在某条路径上被直接当作 64 位值使用（只依赖写 W 寄存器时的隐式零扩展，
+//! 对有符号的 `int` 来说负数会出错），以及 `sxtw`/`uxtw` 的选择与同一寄存器参与的比较的有无符号不一致。
+
+use crate::analysis;
+use crate::instruction::{Instruction, InstructionType, Operand};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::register::Register;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// 一处位宽问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WidthIssue {
+    /// 32 位结果被当作 64 位值使用
+    ImplicitZeroExtension {
+        /// 被读取的 64 位寄存器
+        register: String,
+        /// 写入 32 位结果的指令地址
+        definition: String,
+    },
+    /// 扩展方式与比较的有无符号不一致
+    ExtensionMismatch {
+        /// 被扩展的 32 位寄存器
+        register: String,
+        /// `sxtw` 或 `uxtw`
+        extension: String,
+        /// 比较指令的地址
+        comparison: String,
+        /// 比较结果使用的条件码
+        condition: String,
+    },
+}
+
+impl WidthIssue {
+    /// 问题的说明
+    pub fn describe(&self, lang: Lang) -> String {
+        match (self, lang) {
+            (WidthIssue::ImplicitZeroExtension { register, definition }, Lang::Zh) => {
+                format!("{} 的低 32 位由 0x{} 写入，未显式扩展就作为 64 位值使用（隐式零扩展）", register, definition)
+            }
+            (WidthIssue::ImplicitZeroExtension { register, definition }, Lang::En) => format!(
+                "{} was written as 32 bits at 0x{} and is used as a 64-bit value without an explicit extension",
+                register, definition
+            ),
+            (WidthIssue::ExtensionMismatch { register, extension, comparison, condition }, Lang::Zh) => format!(
+                "{} 用 {} 扩展，但 0x{} 处的比较按{}条件 {} 使用",
+                register,
+                extension,
+                comparison,
+                if extension == "sxtw" { "无符号" } else { "有符号" },
+                condition
+            ),
+            (WidthIssue::ExtensionMismatch { register, extension, comparison, condition }, Lang::En) => format!(
+                "{} is extended with {}, but the comparison at 0x{} uses the {} condition {}",
+                register,
+                extension,
+                comparison,
+                if extension == "sxtw" { "unsigned" } else { "signed" },
+                condition
+            ),
+        }
+    }
+}
+
+/// 一条位宽警告
+#[derive(Debug, Clone, Serialize)]
+pub struct WidthWarning {
+    /// 指令地址
+    pub address: String,
+    /// 汇编指令
+    pub asm_instruction: String,
+    /// 问题
+    pub issue: WidthIssue,
+}
+
+/// 检查函数中的位宽问题（按地址排序）
+pub fn check(entries: &[DumpEntry]) -> Vec<WidthWarning> {
+    let entries: Vec<&DumpEntry> = entries.iter().filter(|e| e.parsed_instruction.is_some()).collect();
+    let instructions: Vec<Instruction> = entries.iter().filter_map(|e| e.parsed_instruction.clone()).collect();
+
+    let mut found = implicit_extensions(&entries, &instructions);
+    found.extend(extension_mismatches(&entries));
+    found.sort_by_key(|(i, _)| *i);
+    found
+        .into_iter()
+        .map(|(i, issue)| WidthWarning {
+            address: entries[i].address.clone(),
+            asm_instruction: entries[i].asm_instruction.replace('\t', " "),
+            issue,
+        })
+        .collect()
+}
+
+/// 寄存器名（小写）
+fn name(reg: Register) -> String {
+    format!("{:?}", reg).to_lowercase()
+}
+
+/// 指令以 32 位写入的通用寄存器编号
+fn narrow_defs(inst: &Instruction) -> Vec<usize> {
+    inst.operands
+        .iter()
+        .zip(analysis::register_access(inst))
+        .filter_map(|(op, access)| match (op, access) {
+            (Operand::Register(reg), Some(access)) if access.writes() && !reg.is_64bit() => reg.index(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 写入 W 寄存器本身就是有意的零扩展：`mov wN, wN`、按掩码取低位、加载无符号的字节/半字、
+/// 非负常量和 0/1 的比较结果
+fn zero_extends_on_purpose(entry: &DumpEntry, inst: &Instruction) -> bool {
+    use InstructionType::*;
+
+    let mnemonic = entry.asm_instruction.split_whitespace().next().unwrap_or("");
+    match (inst.instruction_type, inst.operands.as_slice()) {
+        (MOV, [Operand::Register(a), Operand::Register(b)]) => a.index() == b.index(),
+        (MOV | MOVZ, [_, Operand::Immediate(n)]) => *n >= 0,
+        (AND, [.., Operand::Immediate(_)]) | (LDRB | LDRH | CSET, _) => true,
+        _ => matches!(mnemonic, "uxtb" | "uxth" | "ubfx" | "ubfiz"),
+    }
+}
+
+/// 32 位结果到达 64 位读取的位置
+fn implicit_extensions(entries: &[&DumpEntry], instructions: &[Instruction]) -> Vec<(usize, WidthIssue)> {
+    // 到达定义：每个寄存器可能的定义指令（None 为入口处的值）
+    let empty: Vec<BTreeSet<Option<usize>>> = vec![BTreeSet::new(); 31];
+    let mut reach_in = vec![empty.clone(); instructions.len()];
+    if let Some(first) = reach_in.first_mut() {
+        first.iter_mut().for_each(|defs| {
+            defs.insert(None);
+        });
+    }
+    let mut pending: Vec<usize> = (0..instructions.len()).rev().collect();
+    while let Some(i) = pending.pop() {
+        let mut out = reach_in[i].clone();
+        let (defs, _) = analysis::def_use(&instructions[i]);
+        for r in defs.iter().filter(|r| *r < 31) {
+            out[r] = BTreeSet::from([Some(i)]);
+        }
+        for s in analysis::successors(instructions, i) {
+            let mut changed = false;
+            for r in 0..31 {
+                for def in &out[r] {
+                    changed |= reach_in[s][r].insert(*def);
+                }
+            }
+            if changed && !pending.contains(&s) {
+                pending.push(s);
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+    for (i, inst) in instructions.iter().enumerate() {
+        let mut wide_reads: Vec<Register> = inst
+            .operands
+            .iter()
+            .zip(analysis::register_access(inst))
+            .filter_map(|(op, access)| match (op, access) {
+                (Operand::Register(reg), Some(access)) if access.reads() && reg.is_64bit() => Some(*reg),
+                _ => None,
+            })
+            .collect();
+        for op in &inst.operands {
+            if let Operand::Memory { base, index, .. } = op {
+                wide_reads.push(*base);
+                wide_reads.extend(index.iter().filter(|r| r.is_64bit()));
+            }
+        }
+
+        let mut reported = Vec::new();
+        for reg in wide_reads {
+            let Some(r) = reg.index().filter(|r| *r < 31 && !reported.contains(r)) else {
+                continue;
+            };
+            let narrow = reach_in[i][r].iter().flatten().find(|&&d| {
+                narrow_defs(&instructions[d]).contains(&r) && !zero_extends_on_purpose(entries[d], &instructions[d])
+            });
+            if let Some(&d) = narrow {
+                reported.push(r);
+                found.push((
+                    i,
+                    WidthIssue::ImplicitZeroExtension { register: name(reg), definition: entries[d].address.clone() },
+                ));
+            }
+        }
+    }
+    found
+}
+
+/// 条件码的有无符号：Some(true) 为有符号比较，Some(false) 为无符号比较
+fn signed_condition(condition: &str) -> Option<bool> {
+    match condition {
+        "lt" | "le" | "gt" | "ge" => Some(true),
+        "lo" | "cc" | "ls" | "hi" | "hs" | "cs" => Some(false),
+        _ => None,
+    }
+}
+
+/// 比较之后第一条使用标志位的指令的条件码
+fn flags_consumer(entries: &[&DumpEntry], from: usize) -> Option<String> {
+    entries[from + 1..].iter().find_map(|e| {
+        let inst = e.parsed_instruction.as_ref()?;
+        if let Some(condition) = inst.condition {
+            return Some(format!("{:?}", condition).to_lowercase());
+        }
+        let mnemonic = e.asm_instruction.split_whitespace().next()?;
+        mnemonic.strip_prefix("b.").map(String::from)
+    })
+}
+
+/// `sxtw`/`uxtw` 与比较的有无符号不一致的位置
+fn extension_mismatches(entries: &[&DumpEntry]) -> Vec<(usize, WidthIssue)> {
+    let form = Regex::new(r"^([su]xtw)\s+x\d+,\s*(w\d+)").unwrap();
+    let operand = Regex::new(r"\b(w\d+),\s*([su]xtw)\b").unwrap();
+
+    // 每个 W 寄存器参与的比较：(指令下标, 条件码)
+    let comparisons: Vec<(usize, usize, String)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| {
+            let inst = e.parsed_instruction.as_ref()?;
+            let compares = matches!(inst.instruction_type, InstructionType::CMP | InstructionType::CMN)
+                || e.asm_instruction.starts_with("subs");
+            if !compares {
+                return None;
+            }
+            let condition = flags_consumer(entries, i)?;
+            signed_condition(&condition)?;
+            Some(inst.operands.iter().filter_map(move |op| match op {
+                Operand::Register(reg) if !reg.is_64bit() => reg.index().map(|r| (i, r, condition.clone())),
+                _ => None,
+            }))
+        })
+        .flatten()
+        .collect();
+
+    let mut found = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let asm = entry.asm_instruction.replace('\t', " ");
+        let extension = form
+            .captures(&asm)
+            .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+            .or_else(|| operand.captures(&asm).map(|caps| (caps[2].to_string(), caps[1].to_string())));
+        let Some((extension, register)) = extension else {
+            continue;
+        };
+        let Some(reg) = Register::parse(&register).ok().and_then(|r| r.index()) else {
+            continue;
+        };
+        let signed = extension == "sxtw";
+        let mismatch = comparisons
+            .iter()
+            .find(|(_, r, condition)| *r == reg && signed_condition(condition) != Some(signed));
+        if let Some((c, _, condition)) = mismatch {
+            found.push((
+                i,
+                WidthIssue::ExtensionMismatch {
+                    register,
+                    extension,
+                    comparison: entries[*c].address.clone(),
+                    condition: condition.clone(),
+                },
+            ));
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <index>:
+   0:   0b010000    add w0, w0, w1
+   4:   8b000040    add x0, x2, x0
+   8:   52800023    mov w3, #0x1
+   c:   8b030040    add x0, x2, x3
+  10:   d65f03c0    ret
+
+0000000000000020 <clamp>:
+  20:   7100003f    cmp w1, #0x0
+  24:   540000ab    b.lo 38 <clamp+0x18>
+  28:   8b214800    add x0, x0, w1, sxtw #2
+  2c:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_width_checks() {
+        let parser = ObjdumpParser::new(DUMP.to_string());
+        let warnings = check(&parser.extract_function_data("index").unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].address, "4");
+        assert_eq!(
+            warnings[0].issue,
+            WidthIssue::ImplicitZeroExtension { register: String::from("x0"), definition: String::from("0") }
+        );
+
+        let warnings = check(&parser.extract_function_data("clamp").unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].address, "28");
+        assert_eq!(warnings[0].issue.describe(Lang::Zh), "w1 用 sxtw 扩展，但 0x20 处的比较按无符号条件 lo 使用");
+    }
+}
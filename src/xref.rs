@@ -0,0 +1,149 @@
+//! 寄存器交叉引用
+//!
+//! 列出函数中读取或写入某个通用寄存器的所有指令（x19 和 w19 视为同一个寄存器），
+//! 按操作数的作用区分读取、写入和用作内存地址。调用破坏、返回值等隐式读写也一并列出，
+//! 便于审计一个寄存器在函数中的全部用法。
+
+use crate::analysis;
+use crate::explain::{self, OperandRole};
+use crate::instruction::{InstructionType, Operand};
+use crate::locale::Lang;
+use crate::objdump::DumpEntry;
+use crate::register::Register;
+use crate::table::TableGenerator;
+use serde::Serialize;
+
+/// 一处对寄存器的引用
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterXref {
+    /// 指令地址
+    pub address: String,
+    /// 汇编指令
+    pub asm_instruction: String,
+    /// 寄存器在指令中的作用（同一条指令中可能出现多次）
+    pub roles: Vec<OperandRole>,
+    /// 是否为隐式读写（寄存器不出现在操作数中，如 BL 写入 x30）
+    pub implicit: bool,
+    /// 语义解释
+    pub semantic: String,
+}
+
+impl RegisterXref {
+    /// 是否读取寄存器
+    pub fn reads(&self) -> bool {
+        self.roles.iter().any(|role| !matches!(role, OperandRole::Destination))
+    }
+
+    /// 是否写入寄存器
+    pub fn writes(&self) -> bool {
+        self.roles.iter().any(|role| {
+            matches!(role, OperandRole::Destination | OperandRole::SourceDestination | OperandRole::AddressWriteback)
+        })
+    }
+
+    /// 作用的显示名称
+    pub fn roles_in(&self, lang: Lang) -> String {
+        let roles: Vec<&str> = self.roles.iter().map(|role| role.name_in(lang)).collect();
+        if self.implicit {
+            format!("{} ({})", roles.join(", "), lang.pick("隐式", "implicit"))
+        } else {
+            roles.join(", ")
+        }
+    }
+}
+
+/// 查找函数中所有引用 reg 的指令
+pub fn find(entries: &[DumpEntry], reg: Register, lang: Lang) -> Vec<RegisterXref> {
+    let same = |r: &Register| match reg.index() {
+        Some(index) => r.index() == Some(index),
+        None => *r == reg,
+    };
+    let semantics = TableGenerator::new().with_language(lang).semantic_column(entries);
+
+    let mut xrefs = Vec::new();
+    for (entry, semantic) in entries.iter().zip(semantics) {
+        let Some(inst) = entry.parsed_instruction.as_ref() else {
+            continue;
+        };
+
+        let mut roles = Vec::new();
+        for (op, info) in inst.operands.iter().zip(explain::operand_roles(inst)) {
+            let mentioned = match op {
+                Operand::Register(r) => same(r),
+                Operand::Memory { base, index, .. } => same(base) || index.as_ref().is_some_and(same),
+                _ => false,
+            };
+            if mentioned && !roles.contains(&info.role) {
+                roles.push(info.role);
+            }
+        }
+
+        let implicit = roles.is_empty();
+        if implicit {
+            let Some(index) = reg.index() else {
+                continue;
+            };
+            let (defs, uses) = analysis::def_use(inst);
+            // RET 对被调用者保存寄存器的“使用”只是为了活跃性分析，不是真正的读取
+            let ret = inst.instruction_type == InstructionType::RET;
+            let reads = uses.contains(index) && (!ret || index == 0 || index == 30);
+            roles = match (reads, defs.contains(index)) {
+                (true, true) => vec![OperandRole::SourceDestination],
+                (true, false) => vec![OperandRole::Source],
+                (false, true) => vec![OperandRole::Destination],
+                (false, false) => continue,
+            };
+        }
+
+        xrefs.push(RegisterXref {
+            address: entry.address.clone(),
+            asm_instruction: entry.asm_instruction.replace('\t', " "),
+            roles,
+            implicit,
+            semantic,
+        });
+    }
+    xrefs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objdump::ObjdumpParser;
+
+    const DUMP: &str = r#"
+0000000000000000 <count>:
+   0:   a9bf7bf3    stp x19, x30, [sp, #-16]!
+   4:   aa0003f3    mov x19, x0
+   8:   94000000    bl 0 <helper>
+   c:   b9400260    ldr w0, [x19]
+  10:   11000673    add w19, w19, #0x1
+  14:   a8c17bf3    ldp x19, x30, [sp], #16
+  18:   d65f03c0    ret
+"#;
+
+    #[test]
+    fn test_register_xref() {
+        let entries = ObjdumpParser::new(DUMP.to_string()).extract_function_data("count").unwrap();
+        let xrefs = find(&entries, Register::X19, Lang::Zh);
+        let summary: Vec<(&str, Vec<OperandRole>)> =
+            xrefs.iter().map(|x| (x.address.as_str(), x.roles.clone())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("0", vec![OperandRole::Source]),
+                ("4", vec![OperandRole::Destination]),
+                ("c", vec![OperandRole::Address]),
+                ("10", vec![OperandRole::Destination, OperandRole::Source]),
+                ("14", vec![OperandRole::Destination]),
+            ]
+        );
+        assert!(xrefs[3].reads() && xrefs[3].writes());
+
+        // x30 被 BL 隐式写入，被 RET 隐式读取
+        let lr = find(&entries, Register::X30, Lang::Zh);
+        let implicit: Vec<&str> = lr.iter().filter(|x| x.implicit).map(|x| x.address.as_str()).collect();
+        assert_eq!(implicit, vec!["8", "18"]);
+        assert_eq!(lr[1].roles_in(Lang::Zh), "目标（写入） (隐式)");
+    }
+}